@@ -0,0 +1,395 @@
+# The texts of the UI in English, also used for the texts missing in the other languages
+
+menu-title = Bevy Rubik's Cube
+menu-new-game = New Game
+menu-relay = Relay 2-3-4
+menu-scramble-assistant = Scramble my cube
+menu-training = Training
+menu-statistics = Statistics
+menu-settings = Settings
+menu-quit = Quit
+menu-back = Back
+menu-close = Close
+menu-rating = Race rating:
+menu-races = Races:
+
+training-eo = Edge orientation
+training-notation = Notation quiz
+training-marathon = Marathon
+training-timer = External timer
+training-import = Smart cube solves
+training-race = Online race
+
+settings-display = Display
+settings-puzzle = Puzzle
+settings-cube-size = Cube size
+settings-language = Language
+settings-theme = Theme
+settings-scramble = Scramble
+settings-profile = Profile
+
+display-cubies = Cubies
+display-look = Look
+display-background = Background
+display-ground = Ground
+display-diagnostics = Diagnostics
+display-stream = Stream
+
+theme-colors = Colors
+theme-accent = Accent
+
+scramble-length = Length
+scramble-wide-moves = Wide moves
+scramble-rotations = Rotations
+scramble-seed = Seed
+
+option-flat = Flat
+option-beveled = Beveled
+option-smooth = Smooth
+option-realistic = Realistic
+option-glossy = Glossy
+option-plain = Plain
+option-sky = Sky
+option-dusk = Dusk
+option-off = Off
+option-on = On
+option-chroma = Chroma
+option-transparent = Transparent
+option-dark = Dark
+option-light = Light
+option-crimson = Crimson
+option-blue = Blue
+option-green = Green
+option-purple = Purple
+option-custom = Custom
+option-auto = Auto
+option-random = Random
+option-fixed = Fixed
+
+puzzle-cube = Cube
+puzzle-megaminx = Megaminx
+
+language-english = English
+language-chinese = Chinese
+
+game-back-to-menu = Back to menu
+game-scramble = Cube Scramble
+game-solve = Apply solver
+game-compare-solvers = Compare solvers
+game-exploded-view = Exploded view
+game-piece-types = Piece types
+game-tutorial = Tutorial
+game-fullscreen = Fullscreen
+game-moves = Moves
+game-cycles =
+    { $unsolved }/20 pieces unsolved
+    { $corners } corner and { $edges } edge cycles
+game-next-turn-layers = Next turn: { $layers } layers
+game-wide-move-layers = Wide moves turn { $layers } layers
+
+tutorial-off = { "" }
+tutorial-cross =
+    Step 1: White cross
+    Bring the four white edges around the white center, matching the centers on the sides.
+tutorial-first-layer =
+    Step 2: First layer
+    Put each white corner below its place and repeat R U R' U' until it is solved.
+tutorial-second-layer =
+    Step 3: Second layer
+    With white down, insert the middle edges with U R U' R' U' F' U F or its mirror.
+tutorial-last-layer-cross =
+    Step 4: Yellow cross
+    With yellow up, repeat F R U R' U' F' until the yellow edges form a cross.
+tutorial-last-layer-edges =
+    Step 5: Yellow edges
+    Match the yellow edges to the centers, swapping them with R U R' U R U2 R' U.
+tutorial-last-layer-corners =
+    Step 6: Yellow corners
+    Place the corners with U R U' L' U R' U' L, then twist each one with R' D' R D.
+tutorial-done = Solved, well done!
+
+status-spectating = The cube only turns with the moves broadcast
+status-tutorial-size = The tutorial needs a 3x3x3 cube
+solver-preparing = Preparing solver… { $percent }%
+solver-cube-size = The solver can't solve { $size }x{ $size }x{ $size } cubes
+
+confirm-discard = Give up the solve in progress?
+confirm-yes = Give up
+confirm-no = Keep solving
+confirm-never = Don't ask again
+
+playback-continue = Continue
+playback-play-all = Play to the end
+
+search-cube-size = The search can't be shown on { $size }x{ $size }x{ $size } cubes
+search-title = Thistlethwaite search ({ $key })
+search-states = { $states } states, { $rate } per second
+search-phase = Phase { $phase }, depth { $depth }
+search-best = Best: { $moves } ({ $left } moves left at least)
+search-searching = Searching…
+search-solved = Solved in { $moves } moves, { $ms } ms
+search-no-solution = No solution found
+search-ghost-visited = Ghost cube: visited { $frame }/{ $frames } (click)
+search-ghost-best = Ghost cube: best so far (click to animate)
+
+export-no-solve = Solve the cube to export a replay
+export-unsolved = Export a replay from the solved cube
+export-started = Exporting the replay...
+export-done = Replay exported to { $path }
+export-failed = Could not export the replay: { $error }
+
+screenshot-taken = Screenshot taken: { $path }
+screenshot-busy = A screenshot is already being taken
+
+script-error = Script error in { $error }
+
+scene-saved = Cube saved to { $path }
+scene-save-failed = Could not save the cube: { $error }
+scene-load-busy = Wait for the moves to finish to load
+scene-load-failed = Could not load the cube: { $error }
+scene-no-cube = Could not load the cube: { $path } holds no cube
+scene-several-cubes = Could not load the cube: { $path } holds several cubes
+scene-loaded = Cube loaded from { $path }
+
+solved-title = Solved!
+solved-plus-two = Stopped one move from solved
+solved-dnf = Stopped unsolved
+solved-solution = Your solution: { $moves } moves
+solved-stats = { $seconds } s, { $tps } TPS
+solved-pauses = { $pauses } pauses over { $over } s, longest { $longest } s
+solved-phase = { $phase } { $seconds } s ({ $moves })
+solved-searching = Looking for a shorter solution...
+solved-optimal = Optimal ≤ { $moves } moves
+solved-no-shorter = No shorter solution found
+solved-result = Result: { $result }
+solved-save-report = Save report
+solved-report-saved = Report saved to { $path }
+solved-report-failed = Report not saved: { $error }
+
+queue-stop = Stop (Space)
+queue-more = +{ $moves } more
+
+showcase-caption = Press any key to play
+
+marathon-rounds = Number of rounds, with the cube size of the settings
+marathon-round = Marathon, round { $round } of { $rounds }
+marathon-hint-next = Next once the penalty is given, if any
+marathon-hint-stop = Stop to end the round, with a penalty unless solved
+marathon-stop = Stop
+marathon-next = Next
+marathon-done = Marathon of { $rounds } rounds done!
+marathon-times = Best { $best } s, worst { $worst } s, mean { $mean } s, { $dnfs } DNF
+marathon-average = ao{ $n }: best { $best }, last { $last }
+marathon-main-menu = Main menu
+
+race-unavailable = Built without the online feature, only available on native builds
+race-not-connected = Not connected to the race server
+race-connected = Racing on { $address } as { $name }
+race-connection-failed = Can't connect to { $url }: { $error }
+race-connection-lost = Lost the race server: { $error }
+race-closed = The race server closed the connection
+race-invalid-message = Unexpected message from the race server: { $error }
+race-invalid-scramble = invalid scramble: { $error }
+race-server-error = { $message }
+race-your-rating = Your rating: { $rating }, after { $races } races
+race-lobby = Lobby { $lobby }, { $size }x{ $size }x{ $size } cubes
+race-lobby-name = { $name }'s lobby
+race-racer-racing = { $name } ({ $rating }): racing
+race-racer-ready = { $name } ({ $rating }): ready
+race-racer-not-ready = { $name } ({ $rating }): not ready
+race-last-race = Last race:
+race-ready = Ready
+race-not-ready = Not ready
+race-leave = Leave the lobby
+race-join = Join { $lobby }: { $size }x{ $size }x{ $size }, { $racers } racers
+race-join-racing = Join { $lobby }: { $size }x{ $size }x{ $size }, { $racers } racers, racing
+race-open = Open a lobby
+race-refresh = Refresh
+race-stop = Stop
+race-back-to-lobby = Back to the lobby
+race-results = Results:
+race-result = { $name } ({ $rating }): { $seconds } s, { $moves } moves, { $tps } TPS, { $change }
+race-result-dnf = { $name } ({ $rating }): DNF, { $moves } moves, { $change }
+race-finished = Finished, waiting for the others
+race-countdown = Starting in { $seconds }
+race-go = Go!
+race-racing = Racing: { $racers }
+
+achievement-unlocked = Achievement unlocked: { $title }
+achievement-first-solve = First solve
+achievement-sub-minute = Solve in under a minute
+achievement-no-rotations = Solve without rotating the cube
+achievement-four-by-four = Solve a 4x4x4
+achievement-seven-by-seven = Solve a 7x7x7
+achievement-hundred-solves = 100 solves
+
+assistant-next = Move { $move } of { $moves }, press { $key } once made on your cube
+assistant-done = Done
+assistant-scrambled = Your cube is scrambled, press { $key } for another scramble
+
+callout-pace = Every { $seconds } s ({ $slower }/{ $faster }), stop with { $stop }
+
+compare-title = Solvers compared, pick one to play its solution:
+compare-cube-size = The solvers can't be compared on { $size }x{ $size }x{ $size } cubes
+compare-moves-queued = Wait for the moves queued to be made
+compare-solution = { $method }: { $moves } moves ({ $steps })
+compare-no-solution = { $method }: no solution within { $seconds } s
+
+console-help = Commands: scramble [length | moves], alg <moves>, size <2-7>, solve, reset, help
+console-invalid-length = invalid number of moves '{ $length }'
+console-invalid-moves = invalid moves: { $error }
+console-missing-moves = missing the moves of the algorithm
+console-invalid-size = invalid cube size '{ $size }'
+console-unknown-solver = unknown solver '{ $solver }', try thistlethwaite
+console-no-arguments = { $command } takes no arguments
+console-unknown-command = unknown command '{ $command }', type help for the commands
+console-scrambling = Scrambling with { $moves } moves
+console-size = Starting over with a { $size }x{ $size }x{ $size } cube
+console-solving = Solving
+console-reset = Starting over with a solved cube
+
+diagnostics =
+    { $fps } FPS ({ $ms } ms)
+    { $entities } entities
+    { $queued } moves queued
+    Solver { $solver }
+diagnostics-solver-idle = idle
+diagnostics-solver-searching = searching
+
+eo-instructions = Click the edges which are bad on the F/B axis
+eo-check = Check
+eo-next = Next
+eo-marked = { $marked } edges marked
+eo-right = Right, { $bad } bad edges in { $seconds } s
+eo-wrong = Wrong, { $bad } bad edges and { $marked } marked in { $seconds } s
+eo-summary = { $right } of { $drills } right ({ $percent }%), { $seconds } s on average
+
+guide-solution = Guide solution
+guide-back-on-track = Back on track
+guide-done = Done, every move followed!
+guide-off-track = Off track, undo with { $moves } (Backspace)
+guide-next = Next: { $moves } ({ $done }/{ $total })
+
+history-title = Solve times
+history-all-sizes = All sizes
+history-cube-size = { $size }x{ $size }
+history-all-modes = All modes
+history-mode-standard = standard
+history-mode-assisted = assisted
+history-mode-race = race
+history-mode-external = external
+history-summary = { $solves } solves, { $dnfs } DNF, best { $best } s, mean { $mean } s
+history-all-dnf = { $solves } solves, all DNF
+history-no-solves = No solves yet
+
+macro-record = Record macro (M)
+macro-stop = Stop recording, { $moves } moves (M)
+
+quiz-right = { $move } right
+quiz-missed = { $move } missed
+quiz-make-move = Make the move on the cube
+quiz-score =
+    { $result }, { $left } s left
+    { $right } of { $asked } right, streak of { $streak }
+quiz-average = { $score }, { $seconds } s on average
+
+playback-next-phase =
+    Next: { $phase }, { $moves } moves
+    { $description }
+
+profile-export = Export the profile
+profile-import = Import a profile
+profile-about = Export your settings, macros, solves and saved cube to { $path }, to import them on another computer or in the web app. Import one saved as { $path }, or drop it here.
+profile-about-web = Download your settings, macros, solves and saved cube, to import them on another computer or in the desktop app. Import one by choosing its file.
+profile-imported = Imported { $files } files from { $source }, restart the app to use them
+profile-import-failed = { $source }: { $error }
+profile-write-failed = failed to import { $path }: { $error }
+profile-exported = Exported the profile to { $path }
+profile-export-failed = failed to export the profile to { $path }: { $error }
+profile-downloaded = Downloaded the profile as { $name }
+profile-download-unavailable = The profile can't be downloaded from this page
+profile-upload-unavailable = A profile can't be uploaded from this page
+profile-unreadable = The profile can't be read
+
+import-drop-here = Drop a csTimer or GAN app export here, or save it as { $path }
+import-read = { $solves } solves read from { $source }
+import-failed = { $source }: { $error }
+import-solve = { $number }. { $result }, { $moves } moves, { $tps } TPS
+replay-again = Replay again
+replay-scrambling = scrambling
+replay-unfinished = unfinished
+replay-status =
+    Replay of { $result } at { $tps } TPS: { $seconds } s, move { $move } of { $moves }, { $phase }
+    { $reconstruction }
+
+relay-title = Relay
+relay-cube = { $size }x{ $size }x{ $size }: { $split }
+relay-split = { $seconds } s
+relay-solving = solving
+relay-unsolved = -
+relay-done = Done in { $seconds } s
+relay-total = Total: { $seconds } s
+
+spectate-connecting = Connecting
+spectate-solving = solving
+spectate-waiting = waiting
+spectate-status =
+    Watching { $address }: { $seconds } s, { $state }, { $moves } moves
+    { $message }
+spectate-invalid-moves = Invalid moves broadcast: { $error }
+spectate-connection-failed = Can't connect to { $url }: { $error }
+spectate-connection-lost = { $error }
+spectate-invalid-event = invalid event: { $error }
+spectate-ended = The broadcast ended
+
+view-front = Front ({ $key })
+view-back = Back ({ $key })
+view-top = Top ({ $key })
+view-left = Left ({ $key })
+target-solved = Solved
+target-checkerboard = Checkerboard
+target-six-spots = Six spots
+target-cube-in-cube = Cube in a cube
+target-mistakes-shown = shown
+target-mistakes-hidden = hidden
+target-status =
+    Target: { $pattern }
+    Mistakes { $mistakes } ({ $key })
+
+timer-press-to-stop = Press the spacebar to stop
+timer-release-to-start = Release the spacebar to start
+timer-hold-to-start = Hold the spacebar, then release it to start
+stackmat-state = Stackmat: { $state }
+stackmat-waiting = Stackmat: waiting for the timer
+stackmat-reset = reset
+stackmat-ready = ready
+stackmat-running = running
+stackmat-stopped = stopped
+stackmat-left-hand = left hand down
+stackmat-right-hand = right hand down
+stackmat-both-hands = both hands down
+stackmat-no-input = No audio input found
+stackmat-unreadable = The audio input can't be read: { $error }
+stackmat-unsupported = The audio input's { $format } samples can't be read
+stackmat-unavailable = Built without the stackmat feature, use a timer typing the spacebar
+
+scan-face-up = the top face, the back face up
+scan-face-right = the right face, the top face up
+scan-face-front = the front face, the top face up
+scan-face-down = the bottom face, the front face up
+scan-face-left = the left face, the top face up
+scan-face-back = the back face, the top face up
+scan-step = Show { $face } in the grid and press { $key } ({ $scanned }/{ $faces }), { $cancel } to cancel
+scan-cancel = { $cancel } to cancel
+scan-cube-size = Only 3x3x3 cubes can be scanned
+scan-no-webcam = No webcam found: { $error }
+scan-webcam-unreadable = The webcam can't be read: { $error }
+scan-webcam-format = The webcam doesn't send YUYV frames
+scan-webcam-stopped = The webcam stopped: { $error }
+scan-unavailable = Built without the webcam feature, only available on Linux
+scan-centers-alike = The centers of { $face } and { $other } look alike, scan it again
+scan-invalid-cube = The scanned cube is off: { $error }, scan it again
+scan-no-solution-shown = No solution found for the cube on screen, scan it again
+scan-no-solution-scanned = No solution found for the scanned cube, scan it again
+scan-wait-for-moves = Wait for the moves queued to be made
+scan-setting-up = Setting the scanned cube up
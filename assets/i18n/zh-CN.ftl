@@ -0,0 +1,395 @@
+# The texts of the UI in Simplified Chinese
+
+menu-title = Bevy 魔方
+menu-new-game = 新游戏
+menu-relay = 接力 2-3-4
+menu-scramble-assistant = 打乱实体魔方
+menu-training = 训练
+menu-statistics = 统计
+menu-settings = 设置
+menu-quit = 退出
+menu-back = 返回
+menu-close = 关闭
+menu-rating = 竞速等级分：
+menu-races = 竞速场次：
+
+training-eo = 棱块方向
+training-notation = 转动记号测验
+training-marathon = 马拉松
+training-timer = 外接计时器
+training-import = 智能魔方复盘
+training-race = 联网竞速
+
+settings-display = 显示
+settings-puzzle = 魔方类型
+settings-cube-size = 魔方阶数
+settings-language = 语言
+settings-theme = 主题
+settings-scramble = 打乱
+settings-profile = 个人资料
+
+display-cubies = 小块
+display-look = 外观
+display-background = 背景
+display-ground = 地面
+display-diagnostics = 诊断信息
+display-stream = 直播
+
+theme-colors = 配色
+theme-accent = 强调色
+
+scramble-length = 步数
+scramble-wide-moves = 多层转动
+scramble-rotations = 整体转动
+scramble-seed = 随机种子
+
+option-flat = 平面
+option-beveled = 倒角
+option-smooth = 圆滑
+option-realistic = 写实
+option-glossy = 光泽
+option-plain = 纯色
+option-sky = 天空
+option-dusk = 黄昏
+option-off = 关
+option-on = 开
+option-chroma = 绿幕
+option-transparent = 透明
+option-dark = 深色
+option-light = 浅色
+option-crimson = 深红
+option-blue = 蓝色
+option-green = 绿色
+option-purple = 紫色
+option-custom = 自定义
+option-auto = 自动
+option-random = 随机
+option-fixed = 固定
+
+puzzle-cube = 魔方
+puzzle-megaminx = 五魔方
+
+language-english = 英语
+language-chinese = 中文
+
+game-back-to-menu = 返回菜单
+game-scramble = 打乱魔方
+game-solve = 自动还原
+game-compare-solvers = 比较解法
+game-exploded-view = 分解视图
+game-piece-types = 块类型
+game-tutorial = 教程
+game-fullscreen = 全屏
+game-moves = 步骤
+game-cycles =
+    { $unsolved }/20 块未复原
+    { $corners } 个角块循环，{ $edges } 个棱块循环
+game-next-turn-layers = 下一次转动：{ $layers } 层
+game-wide-move-layers = 宽层转动 { $layers } 层
+
+tutorial-off = { "" }
+tutorial-cross =
+    第 1 步：白色十字
+    把四个白色棱块移到白色中心周围，并与侧面的中心对齐。
+tutorial-first-layer =
+    第 2 步：第一层
+    把每个白色角块放到其位置下方，重复 R U R' U' 直到它复原。
+tutorial-second-layer =
+    第 3 步：第二层
+    白色朝下，用 U R U' R' U' F' U F 或其镜像插入中层棱块。
+tutorial-last-layer-cross =
+    第 4 步：黄色十字
+    黄色朝上，重复 F R U R' U' F' 直到黄色棱块组成十字。
+tutorial-last-layer-edges =
+    第 5 步：黄色棱块
+    把黄色棱块与中心对齐，用 R U R' U R U2 R' U 交换它们。
+tutorial-last-layer-corners =
+    第 6 步：黄色角块
+    用 U R U' L' U R' U' L 放好角块，再用 R' D' R D 逐个翻转。
+tutorial-done = 复原成功，做得好！
+
+status-spectating = 魔方只跟随广播的转动
+status-tutorial-size = 教程需要 3x3x3 魔方
+solver-preparing = 正在准备求解器… { $percent }%
+solver-cube-size = 求解器无法复原 { $size }x{ $size }x{ $size } 魔方
+
+confirm-discard = 放弃正在进行的复原？
+confirm-yes = 放弃
+confirm-no = 继续复原
+confirm-never = 不再询问
+
+playback-continue = 继续
+playback-play-all = 播放到最后
+
+search-cube-size = 无法在 { $size }x{ $size }x{ $size } 魔方上显示搜索
+search-title = Thistlethwaite 搜索（{ $key }）
+search-states = { $states } 个状态，每秒 { $rate } 个
+search-phase = 阶段 { $phase }，深度 { $depth }
+search-best = 最佳：{ $moves }（至少还需 { $left } 步）
+search-searching = 搜索中…
+search-solved = 用 { $moves } 步复原，耗时 { $ms } 毫秒
+search-no-solution = 未找到解法
+search-ghost-visited = 幽灵魔方：已访问 { $frame }/{ $frames }（点击）
+search-ghost-best = 幽灵魔方：目前最佳（点击播放动画）
+
+export-no-solve = 复原魔方后才能导出回放
+export-unsolved = 请在魔方复原时导出回放
+export-started = 正在导出回放...
+export-done = 回放已导出到 { $path }
+export-failed = 无法导出回放：{ $error }
+
+screenshot-taken = 已截图：{ $path }
+screenshot-busy = 正在截图
+
+script-error = 脚本错误：{ $error }
+
+scene-saved = 魔方已保存到 { $path }
+scene-save-failed = 无法保存魔方：{ $error }
+scene-load-busy = 请等转动完成后再加载
+scene-load-failed = 无法加载魔方：{ $error }
+scene-no-cube = 无法加载魔方：{ $path } 中没有魔方
+scene-several-cubes = 无法加载魔方：{ $path } 中有多个魔方
+scene-loaded = 已从 { $path } 加载魔方
+
+solved-title = 复原成功！
+solved-plus-two = 差一步复原时停止
+solved-dnf = 未复原时停止
+solved-solution = 你的解法：{ $moves } 步
+solved-stats = { $seconds } 秒，每秒 { $tps } 步
+solved-pauses = { $pauses } 次超过 { $over } 秒的停顿，最长 { $longest } 秒
+solved-phase = { $phase } { $seconds } 秒（{ $moves }）
+solved-searching = 正在寻找更短的解法...
+solved-optimal = 最优 ≤ { $moves } 步
+solved-no-shorter = 未找到更短的解法
+solved-result = 成绩：{ $result }
+solved-save-report = 保存报告
+solved-report-saved = 报告已保存到 { $path }
+solved-report-failed = 报告未保存：{ $error }
+
+queue-stop = 停止（空格）
+queue-more = 还有 { $moves } 步
+
+showcase-caption = 按任意键开始游戏
+
+marathon-rounds = 轮数，魔方阶数取自设置
+marathon-round = 马拉松，第 { $round } 轮，共 { $rounds } 轮
+marathon-hint-next = 如有罚时，给出后点“下一轮”
+marathon-hint-stop = 点“停止”结束本轮，未复原将被罚时
+marathon-stop = 停止
+marathon-next = 下一轮
+marathon-done = { $rounds } 轮马拉松完成！
+marathon-times = 最佳 { $best } 秒，最差 { $worst } 秒，平均 { $mean } 秒，{ $dnfs } 次 DNF
+marathon-average = ao{ $n }：最佳 { $best }，最近 { $last }
+marathon-main-menu = 主菜单
+
+race-unavailable = 构建时未启用 online 功能，仅原生版本可用
+race-not-connected = 未连接到比赛服务器
+race-connected = 以 { $name } 的身份在 { $address } 比赛
+race-connection-failed = 无法连接到 { $url }：{ $error }
+race-connection-lost = 与比赛服务器断开连接：{ $error }
+race-closed = 比赛服务器关闭了连接
+race-invalid-message = 比赛服务器发来意外的消息：{ $error }
+race-invalid-scramble = 无效的打乱：{ $error }
+race-server-error = { $message }
+race-your-rating = 你的等级分：{ $rating }，共 { $races } 场比赛
+race-lobby = 大厅 { $lobby }，{ $size }x{ $size }x{ $size } 魔方
+race-lobby-name = { $name } 的大厅
+race-racer-racing = { $name }（{ $rating }）：比赛中
+race-racer-ready = { $name }（{ $rating }）：已准备
+race-racer-not-ready = { $name }（{ $rating }）：未准备
+race-last-race = 上一场比赛：
+race-ready = 准备
+race-not-ready = 取消准备
+race-leave = 离开大厅
+race-join = 加入 { $lobby }：{ $size }x{ $size }x{ $size }，{ $racers } 名选手
+race-join-racing = 加入 { $lobby }：{ $size }x{ $size }x{ $size }，{ $racers } 名选手，比赛中
+race-open = 开设大厅
+race-refresh = 刷新
+race-stop = 停止
+race-back-to-lobby = 返回大厅
+race-results = 成绩：
+race-result = { $name }（{ $rating }）：{ $seconds } 秒，{ $moves } 步，每秒 { $tps } 步，{ $change }
+race-result-dnf = { $name }（{ $rating }）：DNF，{ $moves } 步，{ $change }
+race-finished = 已完成，等待其他选手
+race-countdown = { $seconds } 秒后开始
+race-go = 开始！
+race-racing = 比赛中：{ $racers }
+
+achievement-unlocked = 解锁成就：{ $title }
+achievement-first-solve = 首次复原
+achievement-sub-minute = 一分钟内复原
+achievement-no-rotations = 不转动整个魔方完成复原
+achievement-four-by-four = 复原 4x4x4
+achievement-seven-by-seven = 复原 7x7x7
+achievement-hundred-solves = 复原 100 次
+
+assistant-next = 第 { $move } 步，共 { $moves } 步，在你的魔方上完成后按 { $key }
+assistant-done = 完成
+assistant-scrambled = 你的魔方已打乱，按 { $key } 换一个打乱
+
+callout-pace = 每 { $seconds } 秒一步（{ $slower }/{ $faster }），按 { $stop } 停止
+
+compare-title = 求解器对比完成，选择一个播放其解法：
+compare-cube-size = 无法在 { $size }x{ $size }x{ $size } 魔方上对比求解器
+compare-moves-queued = 请等待队列中的转动完成
+compare-solution = { $method }：{ $moves } 步（{ $steps }）
+compare-no-solution = { $method }：{ $seconds } 秒内未找到解法
+
+console-help = 命令：scramble [步数 | 转动]，alg <转动>，size <2-7>，solve，reset，help
+console-invalid-length = 无效的步数“{ $length }”
+console-invalid-moves = 无效的转动：{ $error }
+console-missing-moves = 缺少公式的转动
+console-invalid-size = 无效的魔方阶数“{ $size }”
+console-unknown-solver = 未知的求解器“{ $solver }”，请试试 thistlethwaite
+console-no-arguments = { $command } 不接受参数
+console-unknown-command = 未知命令“{ $command }”，输入 help 查看命令
+console-scrambling = 用 { $moves } 步打乱
+console-size = 以 { $size }x{ $size }x{ $size } 魔方重新开始
+console-solving = 正在求解
+console-reset = 以复原的魔方重新开始
+
+diagnostics =
+    { $fps } FPS（{ $ms } 毫秒）
+    { $entities } 个实体
+    队列中 { $queued } 步
+    求解器{ $solver }
+diagnostics-solver-idle = 空闲
+diagnostics-solver-searching = 搜索中
+
+eo-instructions = 点击在 F/B 轴上方向错误的棱块
+eo-check = 检查
+eo-next = 下一题
+eo-marked = 已标记 { $marked } 个棱块
+eo-right = 正确，{ $bad } 个错误棱块，用时 { $seconds } 秒
+eo-wrong = 错误，{ $bad } 个错误棱块，标记了 { $marked } 个，用时 { $seconds } 秒
+eo-summary = { $drills } 题中答对 { $right } 题（{ $percent }%），平均 { $seconds } 秒
+
+guide-solution = 引导还原
+guide-back-on-track = 回到正轨
+guide-done = 完成，每一步都跟上了！
+guide-off-track = 偏离了，用 { $moves } 撤销（Backspace）
+guide-next = 下一步：{ $moves }（{ $done }/{ $total }）
+
+history-title = 还原时间
+history-all-sizes = 所有阶数
+history-cube-size = { $size }x{ $size }
+history-all-modes = 所有模式
+history-mode-standard = 标准
+history-mode-assisted = 辅助
+history-mode-race = 比赛
+history-mode-external = 外接计时器
+history-summary = { $solves } 次还原，{ $dnfs } 次 DNF，最佳 { $best } 秒，平均 { $mean } 秒
+history-all-dnf = { $solves } 次还原，全部 DNF
+history-no-solves = 还没有还原记录
+
+macro-record = 录制宏（M）
+macro-stop = 停止录制，{ $moves } 步（M）
+
+quiz-right = { $move } 正确
+quiz-missed = { $move } 错过了
+quiz-make-move = 在魔方上做出这一步
+quiz-score =
+    { $result }，剩余 { $left } 秒
+    { $asked } 题中答对 { $right } 题，连对 { $streak } 题
+quiz-average = { $score }，平均 { $seconds } 秒
+
+playback-next-phase =
+    下一阶段：{ $phase }，{ $moves } 步
+    { $description }
+
+profile-export = 导出配置
+profile-import = 导入配置
+profile-about = 将你的设置、宏、还原记录和保存的魔方导出到 { $path }，以便在另一台电脑或网页版中导入。导入保存为 { $path } 的配置，或将其拖到这里。
+profile-about-web = 下载你的设置、宏、还原记录和保存的魔方，以便在另一台电脑或桌面版中导入。选择文件即可导入配置。
+profile-imported = 已从 { $source } 导入 { $files } 个文件，重启应用后生效
+profile-import-failed = { $source }：{ $error }
+profile-write-failed = 导入 { $path } 失败：{ $error }
+profile-exported = 配置已导出到 { $path }
+profile-export-failed = 导出配置到 { $path } 失败：{ $error }
+profile-downloaded = 配置已下载为 { $name }
+profile-download-unavailable = 无法从此页面下载配置
+profile-upload-unavailable = 无法从此页面上传配置
+profile-unreadable = 无法读取配置
+
+import-drop-here = 将 csTimer 或 GAN 应用的导出文件拖到这里，或保存为 { $path }
+import-read = 从 { $source } 读取了 { $solves } 次还原
+import-failed = { $source }：{ $error }
+import-solve = { $number }. { $result }，{ $moves } 步，{ $tps } TPS
+replay-again = 再次回放
+replay-scrambling = 打乱中
+replay-unfinished = 未完成
+replay-status =
+    回放 { $result }，{ $tps } TPS：{ $seconds } 秒，第 { $move } / { $moves } 步，{ $phase }
+    { $reconstruction }
+
+relay-title = 接力
+relay-cube = { $size }x{ $size }x{ $size }：{ $split }
+relay-split = { $seconds } 秒
+relay-solving = 还原中
+relay-unsolved = -
+relay-done = 完成，用时 { $seconds } 秒
+relay-total = 总计：{ $seconds } 秒
+
+spectate-connecting = 连接中
+spectate-solving = 还原中
+spectate-waiting = 等待中
+spectate-status =
+    正在观看 { $address }：{ $seconds } 秒，{ $state }，{ $moves } 步
+    { $message }
+spectate-invalid-moves = 广播的转动无效：{ $error }
+spectate-connection-failed = 无法连接到 { $url }：{ $error }
+spectate-connection-lost = { $error }
+spectate-invalid-event = 无效的事件：{ $error }
+spectate-ended = 广播已结束
+
+view-front = 前（{ $key }）
+view-back = 后（{ $key }）
+view-top = 上（{ $key }）
+view-left = 左（{ $key }）
+target-solved = 复原
+target-checkerboard = 棋盘
+target-six-spots = 六点
+target-cube-in-cube = 魔方套魔方
+target-mistakes-shown = 显示
+target-mistakes-hidden = 隐藏
+target-status =
+    目标：{ $pattern }
+    错误 { $mistakes }（{ $key }）
+
+timer-press-to-stop = 按空格键停止
+timer-release-to-start = 松开空格键开始
+timer-hold-to-start = 按住空格键，然后松开开始
+stackmat-state = Stackmat：{ $state }
+stackmat-waiting = Stackmat：等待计时器
+stackmat-reset = 已归零
+stackmat-ready = 就绪
+stackmat-running = 计时中
+stackmat-stopped = 已停止
+stackmat-left-hand = 左手按下
+stackmat-right-hand = 右手按下
+stackmat-both-hands = 双手按下
+stackmat-no-input = 未找到音频输入
+stackmat-unreadable = 无法读取音频输入：{ $error }
+stackmat-unsupported = 无法读取音频输入的 { $format } 采样
+stackmat-unavailable = 构建时未启用 stackmat 功能，请使用输入空格键的计时器
+
+scan-face-up = 顶面，背面朝上
+scan-face-right = 右面，顶面朝上
+scan-face-front = 前面，顶面朝上
+scan-face-down = 底面，前面朝上
+scan-face-left = 左面，顶面朝上
+scan-face-back = 背面，顶面朝上
+scan-step = 将{ $face }放入网格并按 { $key }（{ $scanned }/{ $faces }），按 { $cancel } 取消
+scan-cancel = 按 { $cancel } 取消
+scan-cube-size = 只能扫描 3x3x3 魔方
+scan-no-webcam = 未找到摄像头：{ $error }
+scan-webcam-unreadable = 无法读取摄像头：{ $error }
+scan-webcam-format = 摄像头不发送 YUYV 帧
+scan-webcam-stopped = 摄像头已停止：{ $error }
+scan-unavailable = 构建时未启用 webcam 功能，仅在 Linux 上可用
+scan-centers-alike = { $face } 和 { $other } 的中心块看起来一样，请重新扫描
+scan-invalid-cube = 扫描的魔方有误：{ $error }，请重新扫描
+scan-no-solution-shown = 找不到屏幕上魔方的解法，请重新扫描
+scan-no-solution-scanned = 找不到扫描的魔方的解法，请重新扫描
+scan-wait-for-moves = 请等待排队的转动完成
+scan-setting-up = 正在设置扫描的魔方
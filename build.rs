@@ -1,7 +1,16 @@
 extern crate embed_resource;
-use std::env;
+use std::{env, path::Path};
 
 fn main() {
+    // the `bundled-tables` feature needs the tables generated first
+    let tables = "assets/tables/solver-tables.bin.gz";
+    if env::var_os("CARGO_FEATURE_BUNDLED_TABLES").is_some() && !Path::new(tables).exists() {
+        panic!(
+            "{tables} is missing, generate it with\n    \
+             cargo run -p cubesim --release --bin tables | gzip -9n > {tables}"
+        );
+    }
+
     let target = env::var("TARGET").unwrap();
     if target.contains("windows") {
         // on windows we will set our game icon as icon for the executable
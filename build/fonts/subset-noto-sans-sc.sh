@@ -0,0 +1,19 @@
+#!/usr/bin/env sh
+# Builds assets/fonts/NotoSansSC-Bold.ttf, the font of the Chinese texts, keeping only the
+# characters of the Chinese translations and the printable ASCII ones of the names and numbers.
+# Run it from the root of the repository again after changing assets/i18n/zh-CN.ftl.
+# Needs curl and fonttools (pip install fonttools).
+
+set -e
+
+NOTO_URL=https://github.com/notofonts/noto-cjk/raw/main/Sans/SubsetOTF/SC/NotoSansSC-Bold.otf
+
+tmp=$(mktemp -d)
+trap 'rm -rf "$tmp"' EXIT
+
+curl -fL -o "$tmp/NotoSansSC-Bold.otf" "$NOTO_URL"
+pyftsubset "$tmp/NotoSansSC-Bold.otf" \
+    --text-file=assets/i18n/zh-CN.ftl \
+    --unicodes=U+0020-007E \
+    --layout-features='*' \
+    --output-file=assets/fonts/NotoSansSC-Bold.ttf
@@ -0,0 +1,282 @@
+use crate::{
+    cube::{CurrentCube, MoveApplied, MoveQueue},
+    pieces::{
+        sticker_transform, Piece, PieceBody, PieceCoord, StickerFace, StickerPaint, StickerPlace,
+    },
+};
+use bevy::{ecs::system::SystemParam, prelude::*};
+use cubesim::{
+    prelude::{CubeSize, Move, MoveVariant},
+    Axis, Face, TwistyPuzzle,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    f32::consts::{FRAC_PI_2, PI},
+    ops::RangeInclusive,
+};
+
+const ROTATE_SPEED: f32 = 2.0;
+
+/// The speed the pieces are turned at, in half turns per second.
+#[derive(Resource, Reflect, Clone, Copy, PartialEq, Debug)]
+#[reflect(Resource)]
+pub struct RotateSpeed(pub f32);
+
+impl Default for RotateSpeed {
+    fn default() -> Self {
+        Self(ROTATE_SPEED)
+    }
+}
+
+/// A piece still turning with its layer, by the angle left to turn it by.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct Rotating {
+    axis: Vec3,
+    angle: f32,
+}
+
+/// Takes the next move from the ``MoveQueue`` of every cube whose pieces are all settled,
+/// applies it to its ``CurrentCube`` and starts turning the pieces of its layers.
+pub(crate) fn rotate_control(
+    mut commands: Commands,
+    mut q_cube: Query<(Entity, &mut CurrentCube, &mut MoveQueue, Option<&Children>)>,
+    mut q_piece: Query<(Entity, &mut PieceCoord, Has<Rotating>), With<Piece>>,
+    mut move_applied: EventWriter<MoveApplied>,
+) {
+    for (cube, mut current_cube, mut move_queue, pieces) in &mut q_cube {
+        let pieces = pieces.map_or(&[][..], |children| &**children);
+        if q_piece.iter_many(pieces).any(|(_, _, rotating)| rotating) {
+            continue;
+        }
+        if move_queue.in_flight.is_some() {
+            move_queue.in_flight = None;
+        }
+        if move_queue.held {
+            continue;
+        }
+        let Some(move_) = move_queue.pop_front() else {
+            continue;
+        };
+
+        move_queue.in_flight = Some(move_);
+        current_cube.geo_cube = current_cube.apply_move(move_);
+        current_cube.moves.push(move_);
+        move_applied.send(MoveApplied { cube, move_ });
+
+        debug!("move {}", move_);
+        let size = current_cube.cube_size;
+        let turn = LayerTurn::new(move_, size as CubeSize);
+        let mut count = 0;
+
+        let mut pieces = q_piece.iter_many_mut(pieces);
+        while let Some((entity, mut coord, _)) = pieces.fetch_next() {
+            if turn.turns(*coord) {
+                commands.entity(entity).insert(Rotating {
+                    axis: turn.axis_vector(),
+                    angle: turn.angle,
+                });
+                *coord = coord.turned(size, &turn);
+                count += 1;
+            }
+        }
+
+        trace!("{move_} apply {count}");
+    }
+}
+
+/// The pieces turned by a move and how they turn, worked out from the move's axis, base face
+/// and layers alone, so any move cubesim can describe is animated the same way.
+///
+/// Layers are selected by their ``PieceCoord`` index along the axis, so wide and inner layer
+/// moves of any depth work on any cube size.
+pub(crate) struct LayerTurn {
+    axis: Axis,
+    angle: f32,
+    layers: RangeInclusive<CubeSize>,
+}
+
+impl LayerTurn {
+    fn new(move_: Move, size: CubeSize) -> Self {
+        // the layers are counted from the base face, at the positive or negative end of the
+        // axis, clockwise as seen from that face
+        let turned = move_.affected_layers(size);
+        let (clockwise, layers) = match move_.base_face() {
+            Face::U | Face::R | Face::F => {
+                (-FRAC_PI_2, (size - turned.end)..=(size - 1 - turned.start))
+            }
+            _ => (FRAC_PI_2, turned.start..=(turned.end - 1)),
+        };
+
+        let angle = match move_.get_variant() {
+            MoveVariant::Standard => clockwise,
+            MoveVariant::Inverse => -clockwise,
+            MoveVariant::Double => PI,
+        };
+
+        Self {
+            axis: move_.axis(),
+            angle,
+            layers,
+        }
+    }
+
+    fn turns(&self, coord: PieceCoord) -> bool {
+        self.layers.contains(&coord.along(self.axis))
+    }
+
+    fn axis_vector(&self) -> Vec3 {
+        match self.axis {
+            Axis::X => Vec3::X,
+            Axis::Y => Vec3::Y,
+            Axis::Z => Vec3::Z,
+        }
+    }
+
+    pub(crate) fn rotation(&self) -> Quat {
+        Quat::from_axis_angle(self.axis_vector(), self.angle)
+    }
+}
+
+pub(crate) fn rotate_piece(
+    mut commands: Commands,
+    time: Res<Time>,
+    rotate_speed: Res<RotateSpeed>,
+    mut q_rotating: Query<(Entity, &mut Transform, &mut Rotating), With<Piece>>,
+) {
+    for (entity, mut transform, mut rotating) in q_rotating.iter_mut() {
+        let mut rotate_angle = if rotating.angle > 0.0 {
+            rotate_speed.0 * PI * time.delta_seconds()
+        } else {
+            -rotate_speed.0 * PI * time.delta_seconds()
+        };
+
+        rotating.angle -= rotate_angle;
+
+        if (rotate_angle > 0.0 && rotating.angle < 0.0)
+            || (rotate_angle < 0.0 && rotating.angle > 0.0)
+        {
+            rotate_angle += rotating.angle;
+            commands.entity(entity).remove::<Rotating>();
+        }
+
+        let rotation = Quat::from_axis_angle(rotating.axis, rotate_angle);
+        transform.rotate_around(Vec3::ZERO, rotation);
+    }
+}
+
+// Rounds a rotation to the nearest quarter turns, the only ones pieces are turned by
+fn snap_rotation(rotation: Quat) -> Quat {
+    let matrix = Mat3::from_quat(rotation);
+    let snapped = Mat3::from_cols(
+        matrix.x_axis.round(),
+        matrix.y_axis.round(),
+        matrix.z_axis.round(),
+    );
+    Quat::from_mat3(&snapped).normalize()
+}
+
+// A sticker, by the doubled coordinates of its piece, to keep them whole on even cubes, and
+// the direction it faces
+fn sticker_key(translation: Vec3, rotation: Quat) -> (IVec3, IVec3) {
+    (
+        (translation * 2.0).round().as_ivec3(),
+        (rotation * Vec3::Y).round().as_ivec3(),
+    )
+}
+
+// The pieces that just stopped turning, and whether stickers were added, as by loading a
+// scene or spawning another cube
+#[derive(SystemParam)]
+pub(crate) struct PiecesSettled<'w, 's> {
+    finished: RemovedComponents<'w, 's, Rotating>,
+    q_added: Query<'w, 's, (), Added<StickerPlace>>,
+}
+
+impl PiecesSettled<'_, '_> {
+    fn changed(&mut self) -> (HashSet<Entity>, bool) {
+        (self.finished.read().collect(), !self.q_added.is_empty())
+    }
+}
+
+type ReconciledPiece<'a> = (
+    &'a mut Transform,
+    &'a PieceCoord,
+    &'a Children,
+    Has<Rotating>,
+);
+
+type ReconciledSticker<'a> = (
+    &'a mut StickerFace,
+    &'a mut StickerPlace,
+    &'a Transform,
+    &'a Handle<StandardMaterial>,
+);
+
+/// Once every turn of a cube is animated, snaps its pieces back onto their ``PieceCoord``, so
+/// the float drift of the rotations never builds up, finds the place of each sticker, and
+/// repaints any sticker that doesn't match the state of the cube.
+pub(crate) fn reconcile_pieces(
+    mut settled: PiecesSettled,
+    q_cube: Query<(&CurrentCube, &Children)>,
+    mut q_piece: Query<ReconciledPiece, With<Piece>>,
+    q_body: Query<&Children, With<PieceBody>>,
+    mut q_sticker: Query<ReconciledSticker, Without<Piece>>,
+    mut paint: StickerPaint,
+) {
+    let (finished, added) = settled.changed();
+    if finished.is_empty() && !added {
+        return;
+    }
+
+    for (current_cube, pieces) in &q_cube {
+        let changed = added || pieces.iter().any(|piece| finished.contains(piece));
+        if !changed || q_piece.iter_many(pieces).any(|(.., rotating)| rotating) {
+            continue;
+        }
+
+        let size = current_cube.cube_size;
+        let state = current_cube.state();
+        let indices: HashMap<(IVec3, IVec3), usize> = (0..state.len())
+            .map(|index| {
+                let transform = sticker_transform(size, index);
+                (
+                    sticker_key(transform.translation, transform.rotation),
+                    index,
+                )
+            })
+            .collect();
+
+        let mut q_pieces = q_piece.iter_many_mut(pieces);
+        while let Some((mut transform, coord, children, _)) = q_pieces.fetch_next() {
+            let snapped = Transform {
+                translation: coord.translation(size),
+                rotation: snap_rotation(transform.rotation),
+                ..*transform
+            };
+            transform.set_if_neq(snapped);
+
+            for body in q_body.iter_many(children) {
+                let mut stickers = q_sticker.iter_many_mut(body);
+                while let Some((mut sticker, mut place, local, material)) = stickers.fetch_next() {
+                    let key = sticker_key(snapped.translation, snapped.rotation * local.rotation);
+                    let Some(&index) = indices.get(&key) else {
+                        warn!("sticker {} is off the cube", sticker.index);
+                        continue;
+                    };
+                    place.set_if_neq(StickerPlace(index));
+                    // masked stickers stay masked
+                    if sticker.face == state[index] || sticker.face == Face::X {
+                        continue;
+                    }
+                    warn!(
+                        "sticker {} shows {:?} where the cube has {:?}",
+                        index, sticker.face, state[index]
+                    );
+                    sticker.face = state[index];
+                    paint.paint(material, state[index]);
+                }
+            }
+        }
+    }
+}
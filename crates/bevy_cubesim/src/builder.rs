@@ -0,0 +1,220 @@
+use crate::{
+    cube::{CurrentCube, MoveQueue},
+    pieces::{
+        sticker_transform, FaceColors, Piece, PieceBody, PieceCoord, StickerFace, StickerPlace,
+        PIECE_SIZE,
+    },
+};
+use bevy::{ecs::system::EntityCommands, prelude::*};
+use cubesim::{
+    piece_type,
+    prelude::{Cube, CubeSize},
+    Face, GeoCube, TwistyPuzzle,
+};
+use std::collections::HashMap;
+
+// how far a sticker is from the center of its piece, as a fraction of the piece's size
+const STICKER_OFFSET: f32 = 0.501;
+// the size of the default sticker mesh, as a fraction of the piece's size
+const STICKER_SIZE: f32 = 0.9;
+
+/// The root of the pieces of a cube, whose transform places, orients and scales the whole cube
+/// in the scene. Its pieces are turned about its center, as the moves of the ``MoveQueue`` on
+/// the root are applied to the ``CurrentCube`` beside it.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct CubeRoot;
+
+/// Spawns the pieces of a solved cube under a ``CubeRoot``, holding the cube and its empty
+/// ``MoveQueue``.
+///
+/// The pieces sit on a lattice of ``PIECE_SIZE`` around the root, each piece's body and
+/// stickers being ``with_piece_size`` large, so a smaller piece size leaves gaps between
+/// the pieces. Stickers repainted once the pieces settle take the colors of the
+/// ``FaceColors`` resource.
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_cubesim::{CubeEntityBuilder, FaceColors};
+///
+/// fn spawn_cube(
+///     mut commands: Commands,
+///     mut meshes: ResMut<Assets<Mesh>>,
+///     mut materials: ResMut<Assets<StandardMaterial>>,
+/// ) {
+///     CubeEntityBuilder::new(3)
+///         .with_colors(FaceColors::default())
+///         .with_piece_size(0.8)
+///         .with_transform(Transform::from_xyz(4.0, 0.0, 0.0).with_scale(Vec3::splat(0.5)))
+///         .spawn(&mut commands, &mut meshes, &mut materials);
+/// }
+/// ```
+pub struct CubeEntityBuilder {
+    size: usize,
+    colors: FaceColors,
+    piece_size: f32,
+    transform: Transform,
+    meshes: Option<(Handle<Mesh>, Handle<Mesh>)>,
+    body_material: StandardMaterial,
+    sticker_material: StandardMaterial,
+}
+
+impl CubeEntityBuilder {
+    pub fn new(size: usize) -> Self {
+        Self {
+            size,
+            colors: FaceColors::default(),
+            piece_size: PIECE_SIZE,
+            transform: Transform::IDENTITY,
+            meshes: None,
+            body_material: StandardMaterial::from(Color::BLACK),
+            sticker_material: StandardMaterial::default(),
+        }
+    }
+
+    /// The colors of the stickers, by the face they belong to when solved.
+    pub fn with_colors(mut self, colors: FaceColors) -> Self {
+        self.colors = colors;
+        self
+    }
+
+    /// The size of each piece, ``PIECE_SIZE`` leaving no gap between them.
+    pub fn with_piece_size(mut self, piece_size: f32) -> Self {
+        self.piece_size = piece_size;
+        self
+    }
+
+    /// The transform of the ``CubeRoot``, at the center of the cube.
+    pub fn with_transform(mut self, transform: Transform) -> Self {
+        self.transform = transform;
+        self
+    }
+
+    /// The meshes shared by the bodies and by the stickers of every piece, instead of plain
+    /// cubes and squares of the piece size. A sticker's mesh lies in the XZ plane, facing up.
+    pub fn with_meshes(mut self, body: Handle<Mesh>, sticker: Handle<Mesh>) -> Self {
+        self.meshes = Some((body, sticker));
+        self
+    }
+
+    /// The materials of the bodies and of the stickers, each piece and sticker getting a copy
+    /// of its own, the stickers' copies in the color of their face.
+    pub fn with_materials(mut self, body: StandardMaterial, sticker: StandardMaterial) -> Self {
+        self.body_material = body;
+        self.sticker_material = sticker;
+        self
+    }
+
+    /// Spawns the ``CubeRoot`` and its pieces, returning the root.
+    pub fn spawn(
+        self,
+        commands: &mut Commands,
+        meshes: &mut Assets<Mesh>,
+        materials: &mut Assets<StandardMaterial>,
+    ) -> Entity {
+        let mut root = commands.spawn_empty();
+        self.insert(&mut root, meshes, materials);
+        root.id()
+    }
+
+    /// Makes an entity the ``CubeRoot`` of a solved cube and spawns its pieces, as when a game
+    /// keeps the same root from one cube to the next. The cube and its queue are replaced,
+    /// while any pieces the root still has are left for the game to despawn.
+    pub fn insert(
+        self,
+        root: &mut EntityCommands,
+        meshes: &mut Assets<Mesh>,
+        materials: &mut Assets<StandardMaterial>,
+    ) {
+        let size = self.size;
+        let state = GeoCube::new(size as CubeSize).state();
+        let (body_mesh, sticker_mesh) = self.meshes.clone().unwrap_or_else(|| {
+            let body = meshes.add(Mesh::from(shape::Cube {
+                size: self.piece_size,
+            }));
+            let sticker = meshes.add(Mesh::from(shape::Plane::from_size(
+                self.piece_size * STICKER_SIZE,
+            )));
+            (body, sticker)
+        });
+
+        // the stickers of each piece, by the piece's place on the cube
+        let mut pieces: HashMap<PieceCoord, Vec<(usize, Transform)>> = HashMap::new();
+        for index in 0..state.len() {
+            let transform = sticker_transform(size, index);
+            pieces
+                .entry(PieceCoord::from_translation(size, transform.translation))
+                .or_default()
+                .push((index, transform));
+        }
+
+        root.insert((
+            SpatialBundle::from_transform(self.transform),
+            CubeRoot,
+            CurrentCube::new(size),
+            MoveQueue::default(),
+        ))
+        .with_children(|parent| {
+            for (coord, stickers) in pieces {
+                let piece_type = piece_type(size as CubeSize, stickers[0].0 as CubeSize);
+                let body = PbrBundle {
+                    mesh: body_mesh.clone(),
+                    material: materials.add(self.body_material.clone()),
+                    ..default()
+                };
+                let stickers: Vec<_> = stickers
+                    .into_iter()
+                    .map(|(index, transform)| {
+                        let face = state[index];
+                        let material = materials.add(StandardMaterial {
+                            base_color: self.colors.color(face),
+                            ..self.sticker_material.clone()
+                        });
+                        self.sticker(&sticker_mesh, material, index, face, transform)
+                    })
+                    .collect();
+
+                parent
+                    .spawn(SpatialBundle::from_transform(Transform::from_translation(
+                        coord.translation(size),
+                    )))
+                    .insert((Piece, coord))
+                    .with_children(|parent| {
+                        parent
+                            .spawn((body, PieceBody(piece_type)))
+                            .with_children(|parent| {
+                                for sticker in stickers {
+                                    parent.spawn(sticker);
+                                }
+                            });
+                    });
+            }
+        });
+    }
+
+    // A sticker on the side of its piece it faces, from the place of the piece holding it
+    fn sticker(
+        &self,
+        mesh: &Handle<Mesh>,
+        material: Handle<StandardMaterial>,
+        index: usize,
+        face: Face,
+        transform: Transform,
+    ) -> (PbrBundle, StickerFace, StickerPlace) {
+        let rotation = transform.rotation;
+        let offset = rotation * Vec3::new(0.0, STICKER_OFFSET * self.piece_size, 0.0);
+        (
+            PbrBundle {
+                mesh: mesh.clone(),
+                material,
+                transform: Transform::from_translation(offset).with_rotation(rotation),
+                ..default()
+            },
+            StickerFace {
+                index: index as CubeSize,
+                face,
+            },
+            StickerPlace(index),
+        )
+    }
+}
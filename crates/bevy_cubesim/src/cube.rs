@@ -0,0 +1,198 @@
+use bevy::prelude::*;
+use cubesim::{
+    prelude::{Cube, CubeSize, Move},
+    GeoCube, TwistyPuzzle,
+};
+use std::{
+    collections::VecDeque,
+    ops::{Deref, DerefMut},
+};
+
+/// The cube, with the moves made on it since it was solved and the solve they belong to, on
+/// the ``CubeRoot`` of its pieces.
+///
+/// The state is left out of reflection, the moves made being enough to replay it.
+#[derive(Component, Reflect)]
+#[reflect(Component, from_reflect = false)]
+pub struct CurrentCube {
+    #[reflect(ignore)]
+    pub(crate) geo_cube: GeoCube,
+    pub(crate) cube_size: usize,
+    pub(crate) moves: Vec<Move>,
+    // the number of moves before the last scramble started
+    scrambled_from: usize,
+    // the number of moves once the last scramble is done, until the cube is solved again
+    scrambled_at: Option<usize>,
+}
+
+impl Default for CurrentCube {
+    fn default() -> Self {
+        Self::new(3)
+    }
+}
+
+impl CurrentCube {
+    pub fn new(cube_size: usize) -> Self {
+        let geo_cube = GeoCube::new(cube_size as CubeSize);
+        Self {
+            geo_cube,
+            cube_size,
+            moves: vec![],
+            scrambled_from: 0,
+            scrambled_at: None,
+        }
+    }
+
+    /// Rebuilds the state of the cube by replaying its moves on a solved cube, as when the
+    /// moves were loaded from a scene.
+    pub fn replay(&mut self) {
+        self.geo_cube = GeoCube::new(self.cube_size as CubeSize);
+        for &move_ in &self.moves {
+            self.geo_cube = self.geo_cube.apply_move(move_);
+        }
+    }
+
+    /// Replays the cube from solved with other moves, as when seeking back and forth through
+    /// the moves made. The last scramble ends no later than the new moves.
+    pub fn set_moves(&mut self, moves: Vec<Move>) {
+        self.moves = moves;
+        self.replay();
+        self.cut_scramble(0);
+    }
+
+    pub fn cube_size(&self) -> usize {
+        self.cube_size
+    }
+
+    pub fn moves(&self) -> &[Move] {
+        &self.moves
+    }
+
+    /// The number of moves once the last scramble is done, while the cube isn't solved.
+    pub fn scrambled_at(&self) -> Option<usize> {
+        self.scrambled_at
+    }
+
+    /// Whether moves were made since the last scramble, without solving the cube yet.
+    pub fn is_solving(&self) -> bool {
+        self.scrambled_at
+            .is_some_and(|start| start < self.moves.len())
+    }
+
+    /// The moves of the last scramble made so far, while the cube isn't solved.
+    pub fn scramble(&self) -> Option<&[Move]> {
+        let end = self.scrambled_at?.min(self.moves.len());
+        Some(&self.moves[self.scrambled_from.min(end)..end])
+    }
+
+    /// Ends the last scramble after the moves made so far and the ``queued`` moves left, once
+    /// the other moves queued for it are dropped.
+    pub fn cut_scramble(&mut self, queued: usize) {
+        let end = self.moves.len() + queued;
+        if let Some(start) = &mut self.scrambled_at {
+            *start = (*start).min(end);
+        }
+    }
+
+    /// Queues the moves of a scramble, the solve starting once they are made.
+    pub fn queue_scramble(&mut self, move_queue: &mut MoveQueue, scramble: Vec<Move>) {
+        self.scrambled_from = self.moves.len() + move_queue.len();
+        move_queue.extend(scramble);
+        self.scrambled_at = Some(self.moves.len() + move_queue.len());
+    }
+
+    /// Splits the moves into the last scramble and the moves made since, once those solve
+    /// the cube, only returning them once.
+    pub fn take_solve(&mut self) -> Option<(&[Move], &[Move])> {
+        if !self.is_solved() {
+            return None;
+        }
+        self.stop_solve()
+    }
+
+    /// Splits the moves like ``take_solve`` whether they solve the cube or not, as when the
+    /// timer is stopped, only returning them once.
+    pub fn stop_solve(&mut self) -> Option<(&[Move], &[Move])> {
+        let start = self
+            .scrambled_at
+            .filter(|&start| start < self.moves.len())?;
+
+        self.scrambled_at = None;
+        Some(self.moves.split_at(start))
+    }
+}
+
+impl Deref for CurrentCube {
+    type Target = GeoCube;
+
+    fn deref(&self) -> &Self::Target {
+        &self.geo_cube
+    }
+}
+
+impl DerefMut for CurrentCube {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.geo_cube
+    }
+}
+
+/// Sent whenever a move from the ``MoveQueue`` of a cube is applied to it.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct MoveApplied {
+    /// The ``CubeRoot`` of the cube.
+    pub cube: Entity,
+    pub move_: Move,
+}
+
+/// The moves waiting to be made on the ``CurrentCube`` next to it, one at a time as their
+/// turns are animated. Each cube has a queue of its own, so several cubes turn at once.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct MoveQueue {
+    pub(crate) moves: VecDeque<Move>,
+    pub(crate) in_flight: Option<Move>,
+    pub(crate) held: bool,
+}
+
+impl MoveQueue {
+    /// The move taken from the queue and still being animated, if any.
+    pub fn in_flight(&self) -> Option<Move> {
+        self.in_flight
+    }
+
+    /// Keeps the queued moves from being made until released, the move being animated still
+    /// finishing, as when a solution pauses between its phases.
+    pub fn hold(&mut self) {
+        self.held = true;
+    }
+
+    pub fn release(&mut self) {
+        self.held = false;
+    }
+
+    pub fn is_held(&self) -> bool {
+        self.held
+    }
+
+    /// Drops the queued moves and releases the hold, forgetting the move in flight, as when
+    /// the pieces it turns are despawned.
+    pub fn reset(&mut self) {
+        self.moves.clear();
+        self.in_flight = None;
+        self.held = false;
+    }
+}
+
+impl Deref for MoveQueue {
+    type Target = VecDeque<Move>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.moves
+    }
+}
+
+impl DerefMut for MoveQueue {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.moves
+    }
+}
@@ -0,0 +1,103 @@
+//! The cubesim cubes of the Bevy Rubik's cube simulator, as a plugin any Bevy game can add.
+//!
+//! The ``CubesimPlugin`` keeps every ``CurrentCube`` in step with its pieces: the moves pushed
+//! onto the ``MoveQueue`` of a cube are applied to it one at a time, while the pieces of the
+//! layers they turn are animated, and once its pieces settle the stickers are checked against
+//! the state of the cube. Both live on the ``CubeRoot`` of the pieces, so any number of cubes
+//! turn at once. Spawning the cubes with the ``CubeEntityBuilder``, and any menus, are left to
+//! the game.
+//!
+//! ```no_run
+//! use bevy::prelude::*;
+//! use bevy_cubesim::{CubeEntityBuilder, CubesimPlugin, MoveQueue};
+//! use cubesim::prelude::{Move, MoveVariant};
+//!
+//! fn spawn_cube(
+//!     mut commands: Commands,
+//!     mut meshes: ResMut<Assets<Mesh>>,
+//!     mut materials: ResMut<Assets<StandardMaterial>>,
+//! ) {
+//!     CubeEntityBuilder::new(3).spawn(&mut commands, &mut meshes, &mut materials);
+//! }
+//!
+//! fn turn_right(mut q_cube: Query<&mut MoveQueue, Added<MoveQueue>>) {
+//!     for mut move_queue in &mut q_cube {
+//!         move_queue.push_back(Move::R(MoveVariant::Standard));
+//!     }
+//! }
+//!
+//! App::new()
+//!     .add_plugins((MinimalPlugins, CubesimPlugin))
+//!     .add_systems(Startup, spawn_cube)
+//!     .add_systems(Update, turn_right)
+//!     .run();
+//! ```
+
+use bevy::prelude::*;
+use cubesim::{Face, Move, MoveVariant, PieceType};
+use std::collections::VecDeque;
+
+pub use crate::{
+    animation::{RotateSpeed, Rotating},
+    builder::{CubeEntityBuilder, CubeRoot},
+    cube::{CurrentCube, MoveApplied, MoveQueue},
+    pieces::{
+        sticker_transform, FaceColors, Piece, PieceBody, PieceCoord, StickerFace, StickerPaint,
+        StickerPlace, PIECE_SIZE,
+    },
+};
+
+mod animation;
+mod builder;
+mod cube;
+mod pieces;
+
+/// The systems of the ``CubesimPlugin``, in the ``Update`` schedule, for the game to order its
+/// own systems around them or to run them only while its cube is shown.
+#[derive(SystemSet, Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum CubesimSet {
+    /// Takes the next move from the ``MoveQueue`` of each cube, applies it to the
+    /// ``CurrentCube`` and starts turning the pieces of its layers.
+    Control,
+    /// Turns the pieces, then snaps them back onto their cube once they all settled.
+    Animate,
+}
+
+pub struct CubesimPlugin;
+
+impl Plugin for CubesimPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FaceColors>()
+            .init_resource::<RotateSpeed>()
+            .register_type::<CubeRoot>()
+            .register_type::<Piece>()
+            .register_type::<PieceCoord>()
+            .register_type::<Rotating>()
+            .register_type::<PieceBody>()
+            .register_type::<StickerFace>()
+            .register_type::<CurrentCube>()
+            .register_type::<MoveQueue>()
+            .register_type::<FaceColors>()
+            .register_type::<RotateSpeed>()
+            // the types of the fields above, which aren't registered along with them
+            .register_type::<Face>()
+            .register_type::<PieceType>()
+            .register_type::<Move>()
+            .register_type::<MoveVariant>()
+            .register_type::<Vec<Move>>()
+            .register_type::<VecDeque<Move>>()
+            .register_type::<Option<Move>>()
+            .register_type::<Option<usize>>()
+            .add_event::<MoveApplied>()
+            .configure_sets(Update, CubesimSet::Control.before(CubesimSet::Animate))
+            .add_systems(
+                Update,
+                (
+                    animation::rotate_control.in_set(CubesimSet::Control),
+                    (animation::rotate_piece, animation::reconcile_pieces)
+                        .chain()
+                        .in_set(CubesimSet::Animate),
+                ),
+            );
+    }
+}
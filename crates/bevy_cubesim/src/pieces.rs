@@ -0,0 +1,201 @@
+use crate::animation::LayerTurn;
+use bevy::{ecs::system::SystemParam, prelude::*};
+use cubesim::{prelude::CubeSize, Axis, Face, PieceType};
+use std::f32::consts::{FRAC_PI_2, PI};
+
+const UP_COLOR: Color = Color::WHITE;
+const RIGHT_COLOR: Color = Color::RED;
+const FRONT_COLOR: Color = Color::GREEN;
+const DOWN_COLOR: Color = Color::YELLOW;
+const LEFT_COLOR: Color = Color::ORANGE;
+const BACK_COLOR: Color = Color::BLUE;
+const MASKED_COLOR: Color = Color::DARK_GRAY;
+/// The size of the pieces, and of the gaps between the layers of the cube.
+pub const PIECE_SIZE: f32 = 1.0;
+
+// A piece's transform is its place on the cube, following its `PieceCoord` once animated,
+// while the mesh in its `PieceBody` child is offset from there by the exploded view
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct Piece;
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct PieceBody(pub PieceType);
+
+// only needed to reflect the component, the type being set whenever a body is spawned
+impl Default for PieceBody {
+    fn default() -> Self {
+        Self(PieceType::Center)
+    }
+}
+
+/// The place of a piece on the lattice of the cube, from 0 to the cube size - 1 along each
+/// axis. It's updated as soon as a move starts turning the piece, so the layers of a move are
+/// selected exactly, whatever the piece's transform during or after the animation.
+#[derive(Component, Reflect, Clone, Copy, Eq, Hash, PartialEq, Debug, Default)]
+#[reflect(Component)]
+pub struct PieceCoord {
+    x: CubeSize,
+    y: CubeSize,
+    z: CubeSize,
+}
+
+impl PieceCoord {
+    /// The place of the piece at a translation from the center of a cube of the given size.
+    pub fn from_translation(size: usize, translation: Vec3) -> Self {
+        let border = (size as f32 * PIECE_SIZE) / 2.0 - 0.5 * PIECE_SIZE;
+        let coord = ((translation + border) / PIECE_SIZE).round().as_ivec3();
+        Self {
+            x: coord.x,
+            y: coord.y,
+            z: coord.z,
+        }
+    }
+
+    /// The translation of the piece from the center of a cube of the given size.
+    pub fn translation(self, size: usize) -> Vec3 {
+        let border = (size as f32 * PIECE_SIZE) / 2.0 - 0.5 * PIECE_SIZE;
+        Vec3::new(self.x as f32, self.y as f32, self.z as f32) * PIECE_SIZE - border
+    }
+
+    // The index of the layer holding the piece, along one of the axes
+    pub(crate) fn along(self, axis: Axis) -> CubeSize {
+        match axis {
+            Axis::X => self.x,
+            Axis::Y => self.y,
+            Axis::Z => self.z,
+        }
+    }
+
+    pub(crate) fn turned(self, size: usize, turn: &LayerTurn) -> Self {
+        let translation = turn.rotation() * self.translation(size);
+        Self::from_translation(size, translation)
+    }
+}
+
+// The sticker's face and index in the solved state, which stay with it as it moves around. A
+// masked sticker, such as in a shared teaching setup, keeps the masked face.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct StickerFace {
+    pub index: CubeSize,
+    pub face: Face,
+}
+
+/// The index of the place a sticker is at, in the state of the cube, updated once the pieces
+/// stop turning.
+#[derive(Component, Clone, Copy, Debug, Eq, PartialEq)]
+pub struct StickerPlace(pub usize);
+
+// only needed to reflect the component, like the default `PieceBody`
+impl Default for StickerFace {
+    fn default() -> Self {
+        Self {
+            index: 0,
+            face: Face::X,
+        }
+    }
+}
+
+/// The colors of the stickers, by the face they belong to when solved.
+#[derive(Resource, Reflect, Clone, Debug)]
+#[reflect(Resource)]
+pub struct FaceColors {
+    pub up: Color,
+    pub left: Color,
+    pub front: Color,
+    pub right: Color,
+    pub back: Color,
+    pub down: Color,
+    /// The stickers hidden by a mask, such as those not part of a tutorial step.
+    pub masked: Color,
+}
+
+impl Default for FaceColors {
+    fn default() -> Self {
+        Self {
+            up: UP_COLOR,
+            left: LEFT_COLOR,
+            front: FRONT_COLOR,
+            right: RIGHT_COLOR,
+            back: BACK_COLOR,
+            down: DOWN_COLOR,
+            masked: MASKED_COLOR,
+        }
+    }
+}
+
+impl FaceColors {
+    pub fn color(&self, face: Face) -> Color {
+        match face {
+            Face::U => self.up,
+            Face::L => self.left,
+            Face::F => self.front,
+            Face::R => self.right,
+            Face::B => self.back,
+            Face::D => self.down,
+            Face::X => self.masked,
+        }
+    }
+}
+
+/// Recolors the material of a sticker with the colors of its face.
+#[derive(SystemParam)]
+pub struct StickerPaint<'w> {
+    pub face_colors: Res<'w, FaceColors>,
+    materials: ResMut<'w, Assets<StandardMaterial>>,
+}
+
+impl StickerPaint<'_> {
+    pub fn paint(&mut self, material: &Handle<StandardMaterial>, face: Face) {
+        let color = self.face_colors.color(face);
+        if let Some(material) = self.materials.get_mut(material) {
+            material.base_color = color;
+        }
+    }
+}
+
+/// The place of the piece holding the sticker at an index of the facelet state, its sticker
+/// facing away from the cube.
+pub fn sticker_transform(size: usize, index: usize) -> Transform {
+    let border = (size as f32 * PIECE_SIZE) / 2.0 - 0.5 * PIECE_SIZE;
+    let (j, k) = (index % (size * size) / size, index % size);
+    let saw_face = match index / (size * size) {
+        0 => Face::U,
+        1 => Face::R,
+        2 => Face::F,
+        3 => Face::D,
+        4 => Face::L,
+        5 => Face::B,
+        _ => panic!("invalid index"),
+    };
+
+    let mut transform = Transform::from_xyz(k as f32 - border, border, j as f32 - border);
+    match saw_face {
+        Face::U => {}
+        Face::L => {
+            transform.rotate_around(Vec3::ZERO, Quat::from_rotation_z(FRAC_PI_2));
+            transform.rotate_around(Vec3::ZERO, Quat::from_rotation_x(FRAC_PI_2));
+        }
+        Face::F => {
+            transform.rotate_around(Vec3::ZERO, Quat::from_rotation_x(FRAC_PI_2));
+        }
+        Face::R => {
+            transform.rotate_around(Vec3::ZERO, Quat::from_rotation_z(-FRAC_PI_2));
+            transform.rotate_around(Vec3::ZERO, Quat::from_rotation_x(FRAC_PI_2));
+        }
+        Face::B => {
+            transform.rotate_around(Vec3::ZERO, Quat::from_rotation_x(-FRAC_PI_2));
+            transform.rotate_around(Vec3::ZERO, Quat::from_rotation_z(PI));
+        }
+        Face::D => {
+            transform.rotate_around(Vec3::ZERO, Quat::from_rotation_z(PI));
+            transform.rotate_around(Vec3::ZERO, Quat::from_rotation_y(PI));
+        }
+        Face::X => {
+            unreachable!()
+        }
+    }
+    transform
+}
@@ -1,4 +1,4 @@
-use cubesim::{parse_scramble, solve, Cube, FaceletCube};
+use cubesim::{parse_scramble, solve, Cube, FaceletCube, TwistyPuzzle};
 
 fn main() {
     let moves = parse_scramble(String::from("Lw"));
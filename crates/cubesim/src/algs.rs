@@ -0,0 +1,514 @@
+use alloc::{string::ToString, vec::Vec};
+
+use crate::beginner::layer;
+use crate::generic_cube::{
+    piece_type, solved_state, sticker_index, Cube, CubeSize, Face, Move, MoveVariant::*, PieceType,
+};
+use crate::scramble_parser::parse_scramble;
+
+/// A set of algorithms solving one step of the CFOP method on the 3x3x3 cube, with the
+/// cross on the ``D`` face and the last layer on the ``U`` face.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum AlgSet {
+    /// Pairs a first layer corner with its edge and inserts them into the front right slot.
+    F2l,
+    /// Orients the last layer.
+    Oll,
+    /// Permutes the last layer, solving the cube.
+    Pll,
+}
+
+impl AlgSet {
+    /// The algorithms of the set.
+    pub fn algorithms(self) -> &'static [Algorithm] {
+        match self {
+            AlgSet::F2l => &F2L,
+            AlgSet::Oll => &OLL,
+            AlgSet::Pll => &PLL,
+        }
+    }
+
+    /// Masks a sticker of a 3x3x3 cube, given its index in the solved state, to ``Face::X``
+    /// if it doesn't matter to recognize the cases of the set. OLL cases only keep the
+    /// last layer stickers of the ``U`` color.
+    ///
+    /// This can be passed to ``Cube::mask`` as ``&|i, f| set.mask(i, f)``.
+    pub fn mask(self, index: CubeSize, face: Face) -> Face {
+        match self {
+            AlgSet::F2l => {
+                let cross = layer(index) == Face::D && piece_type(3, index) != PieceType::Corner;
+                if cross || front_right_slot().contains(&index) {
+                    face
+                } else {
+                    Face::X
+                }
+            }
+            AlgSet::Oll if layer(index) == Face::U && face != Face::U => Face::X,
+            AlgSet::Oll | AlgSet::Pll => face,
+        }
+    }
+
+    // Whether the stickers kept by the mask are solved, up to a last U turn for the PLL
+    fn is_solved(self, cube: &impl Cube) -> bool {
+        let goal: Vec<Face> = solved_state(3)
+            .into_iter()
+            .enumerate()
+            .map(|(i, face)| self.mask(i as CubeSize, face))
+            .collect();
+        let masked = cube.mask(&|i, f| self.mask(i, f));
+
+        adjustments().any(|auf| masked.apply_moves(&auf).state() == goal)
+    }
+}
+
+// The stickers of the front right corner and edge
+fn front_right_slot() -> [CubeSize; 5] {
+    [
+        sticker_index(3, Face::F, 6),
+        sticker_index(3, Face::R, 4),
+        sticker_index(3, Face::F, 9),
+        sticker_index(3, Face::R, 7),
+        sticker_index(3, Face::D, 3),
+    ]
+}
+
+// The turns of the U face, including none, to adjust the last layer before or after an
+// algorithm
+pub(crate) fn adjustments() -> impl Iterator<Item = Vec<Move>> {
+    [None, Some(Standard), Some(Double), Some(Inverse)]
+        .into_iter()
+        .map(|turn| turn.map(Move::U).into_iter().collect())
+}
+
+/// A named algorithm of a CFOP step.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Algorithm {
+    /// The set the algorithm belongs to.
+    pub set: AlgSet,
+    /// The usual name of the case the algorithm solves, like ``OLL 27`` or ``T perm``.
+    pub name: &'static str,
+    /// The group of similar looking cases the case belongs to, like ``Cross`` for the OLL
+    /// cases with an oriented edge cross.
+    pub group: &'static str,
+    /// The moves of the algorithm in WCA notation, with wide moves written as ``Rw`` and
+    /// the ``M`` slice as ``2L``.
+    pub alg: &'static str,
+}
+
+impl Algorithm {
+    const fn new(set: AlgSet, name: &'static str, group: &'static str, alg: &'static str) -> Self {
+        Self {
+            set,
+            name,
+            group,
+            alg,
+        }
+    }
+
+    /// The moves of the algorithm.
+    pub fn moves(&self) -> Vec<Move> {
+        parse_scramble(self.alg.to_string())
+    }
+
+    /// The case the algorithm solves, made by undoing it on a solved cube.
+    pub fn case<C: Cube>(&self) -> C {
+        let undo: Vec<Move> = self.moves().iter().rev().map(|mv| mv.inverse()).collect();
+        C::new(3).apply_moves(&undo)
+    }
+}
+
+/// Looks up the algorithms of a set solving the current case of a 3x3x3 cube, each with the
+/// turn of the ``U`` face to make before it, if any.
+///
+/// Only the stickers kept by ``AlgSet::mask`` are considered, so the OLL algorithms are
+/// found as soon as the first two layers are solved, whatever the permutation of the last
+/// layer.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::{Cube, Move, MoveVariant::*, TwistyPuzzle};
+/// use cubesim::{matching_algorithms, parse_scramble, AlgSet, FaceletCube};
+///
+/// // undoing a T perm, then turning the U face
+/// let scramble = parse_scramble(String::from("R U R' U' R' F R2 U' R' U' R U R' F' U"));
+/// let cube = FaceletCube::new(3).apply_moves(&scramble);
+///
+/// let found = matching_algorithms(&cube, AlgSet::Pll);
+/// assert_eq!(found.len(), 1);
+/// assert_eq!(found[0].0, Some(Move::U(Inverse)));
+/// assert_eq!(found[0].1.name, "T perm");
+/// ```
+pub fn matching_algorithms(
+    cube: &impl Cube,
+    set: AlgSet,
+) -> Vec<(Option<Move>, &'static Algorithm)> {
+    if cube.size() != 3 {
+        return Vec::new();
+    }
+
+    set.algorithms()
+        .iter()
+        .filter_map(|algorithm| {
+            let moves = algorithm.moves();
+            adjustments()
+                .find(|auf| set.is_solved(&cube.apply_moves(auf).apply_moves(&moves)))
+                .map(|auf| (auf.first().copied(), algorithm))
+        })
+        .collect()
+}
+
+/// The 57 OLL algorithms, numbered as usual.
+const OLL: [Algorithm; 57] = [
+    Algorithm::new(AlgSet::Oll, "OLL 1", "Dot", "R U2 R2 F R F' U2 R' F R F'"),
+    Algorithm::new(
+        AlgSet::Oll,
+        "OLL 2",
+        "Dot",
+        "F R U R' U' F' Fw R U R' U' Fw'",
+    ),
+    Algorithm::new(
+        AlgSet::Oll,
+        "OLL 3",
+        "Dot",
+        "Fw R U R' U' Fw' U' F R U R' U' F'",
+    ),
+    Algorithm::new(
+        AlgSet::Oll,
+        "OLL 4",
+        "Dot",
+        "Fw R U R' U' Fw' U F R U R' U' F'",
+    ),
+    Algorithm::new(AlgSet::Oll, "OLL 5", "Square", "Rw' U2 R U R' U Rw"),
+    Algorithm::new(AlgSet::Oll, "OLL 6", "Square", "Rw U2 R' U' R U' Rw'"),
+    Algorithm::new(
+        AlgSet::Oll,
+        "OLL 7",
+        "Small lightning",
+        "Rw U R' U R U2 Rw'",
+    ),
+    Algorithm::new(
+        AlgSet::Oll,
+        "OLL 8",
+        "Small lightning",
+        "Lw' U' L U' L' U2 Lw",
+    ),
+    Algorithm::new(AlgSet::Oll, "OLL 9", "Fish", "R U R' U' R' F R2 U R' U' F'"),
+    Algorithm::new(AlgSet::Oll, "OLL 10", "Fish", "R U R' U R' F R F' R U2 R'"),
+    Algorithm::new(
+        AlgSet::Oll,
+        "OLL 11",
+        "Small lightning",
+        "Rw U R' U R' F R F' R U2 Rw'",
+    ),
+    Algorithm::new(
+        AlgSet::Oll,
+        "OLL 12",
+        "Small lightning",
+        "2L' R' U' R U' R' U2 R U' 2L",
+    ),
+    Algorithm::new(
+        AlgSet::Oll,
+        "OLL 13",
+        "Knight move",
+        "F U R U' R2 F' R U R U' R'",
+    ),
+    Algorithm::new(
+        AlgSet::Oll,
+        "OLL 14",
+        "Knight move",
+        "R' F R U R' F' R F U' F'",
+    ),
+    Algorithm::new(
+        AlgSet::Oll,
+        "OLL 15",
+        "Knight move",
+        "Rw' U' Rw R' U' R U Rw' U Rw",
+    ),
+    Algorithm::new(
+        AlgSet::Oll,
+        "OLL 16",
+        "Knight move",
+        "Rw U Rw' R U R' U' Rw U' Rw'",
+    ),
+    Algorithm::new(
+        AlgSet::Oll,
+        "OLL 17",
+        "Dot",
+        "R U R' U R' F R F' U2 R' F R F'",
+    ),
+    Algorithm::new(
+        AlgSet::Oll,
+        "OLL 18",
+        "Dot",
+        "Rw U R' U R U2 Rw2 U' R U' R' U2 Rw",
+    ),
+    Algorithm::new(AlgSet::Oll, "OLL 19", "Dot", "2L U R U R' U' 2L' R' F R F'"),
+    Algorithm::new(
+        AlgSet::Oll,
+        "OLL 20",
+        "Dot",
+        "Rw U R' U' 2L2 U R U' R' U' 2L'",
+    ),
+    Algorithm::new(
+        AlgSet::Oll,
+        "OLL 21",
+        "Cross",
+        "R U2 R' U' R U R' U' R U' R'",
+    ),
+    Algorithm::new(AlgSet::Oll, "OLL 22", "Cross", "R U2 R2 U' R2 U' R2 U2 R"),
+    Algorithm::new(AlgSet::Oll, "OLL 23", "Cross", "R2 D' R U2 R' D R U2 R"),
+    Algorithm::new(AlgSet::Oll, "OLL 24", "Cross", "Rw U R' U' Rw' F R F'"),
+    Algorithm::new(AlgSet::Oll, "OLL 25", "Cross", "F' Rw U R' U' Rw' F R"),
+    Algorithm::new(AlgSet::Oll, "OLL 26", "Cross", "R U2 R' U' R U' R'"),
+    Algorithm::new(AlgSet::Oll, "OLL 27", "Cross", "R U R' U R U2 R'"),
+    Algorithm::new(
+        AlgSet::Oll,
+        "OLL 28",
+        "Corners oriented",
+        "Rw U R' U' 2L U R U' R'",
+    ),
+    Algorithm::new(
+        AlgSet::Oll,
+        "OLL 29",
+        "Awkward",
+        "R U R' U' R U' R' F' U' F R U R'",
+    ),
+    Algorithm::new(
+        AlgSet::Oll,
+        "OLL 30",
+        "Awkward",
+        "F R' F R2 U' R' U' R U R' F2",
+    ),
+    Algorithm::new(AlgSet::Oll, "OLL 31", "P", "R' U' F U R U' R' F' R"),
+    Algorithm::new(AlgSet::Oll, "OLL 32", "P", "L U F' U' L' U L F L'"),
+    Algorithm::new(AlgSet::Oll, "OLL 33", "T", "R U R' U' R' F R F'"),
+    Algorithm::new(AlgSet::Oll, "OLL 34", "C", "R U R2 U' R' F R U R U' F'"),
+    Algorithm::new(AlgSet::Oll, "OLL 35", "Fish", "R U2 R2 F R F' R U2 R'"),
+    Algorithm::new(AlgSet::Oll, "OLL 36", "W", "L' U' L U' L' U L U L F' L' F"),
+    Algorithm::new(AlgSet::Oll, "OLL 37", "Fish", "F R' F' R U R U' R'"),
+    Algorithm::new(AlgSet::Oll, "OLL 38", "W", "R U R' U R U' R' U' R' F R F'"),
+    Algorithm::new(
+        AlgSet::Oll,
+        "OLL 39",
+        "Big lightning",
+        "L F' L' U' L U F U' L'",
+    ),
+    Algorithm::new(
+        AlgSet::Oll,
+        "OLL 40",
+        "Big lightning",
+        "R' F R U R' U' F' U R",
+    ),
+    Algorithm::new(
+        AlgSet::Oll,
+        "OLL 41",
+        "Awkward",
+        "R U R' U R U2 R' F R U R' U' F'",
+    ),
+    Algorithm::new(
+        AlgSet::Oll,
+        "OLL 42",
+        "Awkward",
+        "R' U' R U' R' U2 R F R U R' U' F'",
+    ),
+    Algorithm::new(AlgSet::Oll, "OLL 43", "P", "F' U' L' U L F"),
+    Algorithm::new(AlgSet::Oll, "OLL 44", "P", "F U R U' R' F'"),
+    Algorithm::new(AlgSet::Oll, "OLL 45", "T", "F R U R' U' F'"),
+    Algorithm::new(AlgSet::Oll, "OLL 46", "C", "R' U' R' F R F' U R"),
+    Algorithm::new(
+        AlgSet::Oll,
+        "OLL 47",
+        "Small L",
+        "R' U' R' F R F' R' F R F' U R",
+    ),
+    Algorithm::new(AlgSet::Oll, "OLL 48", "Small L", "F R U R' U' R U R' U' F'"),
+    Algorithm::new(
+        AlgSet::Oll,
+        "OLL 49",
+        "Small L",
+        "Rw U' Rw2 U Rw2 U Rw2 U' Rw",
+    ),
+    Algorithm::new(
+        AlgSet::Oll,
+        "OLL 50",
+        "Small L",
+        "Rw' U Rw2 U' Rw2 U' Rw2 U Rw'",
+    ),
+    Algorithm::new(AlgSet::Oll, "OLL 51", "Line", "F U R U' R' U R U' R' F'"),
+    Algorithm::new(AlgSet::Oll, "OLL 52", "Line", "R U R' U R U' B U' B' R'"),
+    Algorithm::new(
+        AlgSet::Oll,
+        "OLL 53",
+        "Small L",
+        "Lw' U2 L U L' U' L U L' U Lw",
+    ),
+    Algorithm::new(
+        AlgSet::Oll,
+        "OLL 54",
+        "Small L",
+        "Rw U2 R' U' R U R' U' R U' Rw'",
+    ),
+    Algorithm::new(
+        AlgSet::Oll,
+        "OLL 55",
+        "Line",
+        "R' F R U R U' R2 F' R2 U' R' U R U R'",
+    ),
+    Algorithm::new(
+        AlgSet::Oll,
+        "OLL 56",
+        "Line",
+        "Rw' U' Rw U' R' U R U' R' U R Rw' U Rw",
+    ),
+    Algorithm::new(
+        AlgSet::Oll,
+        "OLL 57",
+        "Corners oriented",
+        "R U R' U' 2L' U R U' Rw'",
+    ),
+];
+
+/// The 21 PLL algorithms, named by their usual letters.
+const PLL: [Algorithm; 21] = [
+    Algorithm::new(
+        AlgSet::Pll,
+        "Aa perm",
+        "Corners only",
+        "x R' U R' D2 R U' R' D2 R2 x'",
+    ),
+    Algorithm::new(
+        AlgSet::Pll,
+        "Ab perm",
+        "Corners only",
+        "x R2 D2 R U R' D2 R U' R x'",
+    ),
+    Algorithm::new(
+        AlgSet::Pll,
+        "E perm",
+        "Corners only",
+        "x' R U' R' D R U R' D' R U R' D R U' R' D' x",
+    ),
+    Algorithm::new(
+        AlgSet::Pll,
+        "F perm",
+        "Adjacent corner swap",
+        "R' U' F' R U R' U' R' F R2 U' R' U' R U R' U R",
+    ),
+    Algorithm::new(
+        AlgSet::Pll,
+        "Ga perm",
+        "Adjacent corner swap",
+        "R2 U R' U R' U' R U' R2 U' D R' U R D'",
+    ),
+    Algorithm::new(
+        AlgSet::Pll,
+        "Gb perm",
+        "Adjacent corner swap",
+        "R' U' R U D' R2 U R' U R U' R U' R2 D",
+    ),
+    Algorithm::new(
+        AlgSet::Pll,
+        "Gc perm",
+        "Adjacent corner swap",
+        "R2 U' R U' R U R' U R2 U D' R U' R' D",
+    ),
+    Algorithm::new(
+        AlgSet::Pll,
+        "Gd perm",
+        "Adjacent corner swap",
+        "R U R' U' D R2 U' R U' R' U R' U R2 D'",
+    ),
+    Algorithm::new(
+        AlgSet::Pll,
+        "H perm",
+        "Edges only",
+        "2L2 U 2L2 U2 2L2 U 2L2",
+    ),
+    Algorithm::new(
+        AlgSet::Pll,
+        "Ja perm",
+        "Adjacent corner swap",
+        "R' U L' U2 R U' R' U2 R L",
+    ),
+    Algorithm::new(
+        AlgSet::Pll,
+        "Jb perm",
+        "Adjacent corner swap",
+        "R U R' F' R U R' U' R' F R2 U' R'",
+    ),
+    Algorithm::new(
+        AlgSet::Pll,
+        "Na perm",
+        "Diagonal corner swap",
+        "R U R' U R U R' F' R U R' U' R' F R2 U' R' U2 R U' R'",
+    ),
+    Algorithm::new(
+        AlgSet::Pll,
+        "Nb perm",
+        "Diagonal corner swap",
+        "R' U R U' R' F' U' F R U R' F R' F' R U' R",
+    ),
+    Algorithm::new(
+        AlgSet::Pll,
+        "Ra perm",
+        "Adjacent corner swap",
+        "R U' R' U' R U R D R' U' R D' R' U2 R'",
+    ),
+    Algorithm::new(
+        AlgSet::Pll,
+        "Rb perm",
+        "Adjacent corner swap",
+        "R2 F R U R U' R' F' R U2 R' U2 R",
+    ),
+    Algorithm::new(
+        AlgSet::Pll,
+        "T perm",
+        "Adjacent corner swap",
+        "R U R' U' R' F R2 U' R' U' R U R' F'",
+    ),
+    Algorithm::new(
+        AlgSet::Pll,
+        "Ua perm",
+        "Edges only",
+        "2L2 U 2L U2 2L' U 2L2",
+    ),
+    Algorithm::new(
+        AlgSet::Pll,
+        "Ub perm",
+        "Edges only",
+        "2L2 U' 2L U2 2L' U' 2L2",
+    ),
+    Algorithm::new(
+        AlgSet::Pll,
+        "V perm",
+        "Diagonal corner swap",
+        "R' U R' U' B' R' B2 U' B' U B' R B R",
+    ),
+    Algorithm::new(
+        AlgSet::Pll,
+        "Y perm",
+        "Diagonal corner swap",
+        "F R U' R' U' R U R' F' R U R' U' R' F R F'",
+    ),
+    Algorithm::new(
+        AlgSet::Pll,
+        "Z perm",
+        "Edges only",
+        "2L' U 2L2 U 2L2 U 2L' U2 2L2",
+    ),
+];
+
+/// F2L algorithms for the front right slot, with both pieces in the top layer.
+const F2L: [Algorithm; 10] = [
+    Algorithm::new(AlgSet::F2l, "F2L 1", "Pair in top", "U R U' R'"),
+    Algorithm::new(AlgSet::F2l, "F2L 2", "Pair in top", "U' F' U F"),
+    Algorithm::new(AlgSet::F2l, "F2L 3", "Pair in top", "F' U' F"),
+    Algorithm::new(AlgSet::F2l, "F2L 4", "Pair in top", "R U R'"),
+    Algorithm::new(AlgSet::F2l, "F2L 5", "Split pair", "U' R U R' U2 R U' R'"),
+    Algorithm::new(AlgSet::F2l, "F2L 6", "Split pair", "U F' U' F U2 F' U F"),
+    Algorithm::new(AlgSet::F2l, "F2L 7", "Split pair", "U' R U2 R' U2 R U' R'"),
+    Algorithm::new(AlgSet::F2l, "F2L 8", "Split pair", "U F' U2 F U2 F' U F"),
+    Algorithm::new(AlgSet::F2l, "F2L 9", "Split pair", "U' R U' R' U F' U' F"),
+    Algorithm::new(AlgSet::F2l, "F2L 10", "Split pair", "U' R U R' U R U R'"),
+];
@@ -0,0 +1,140 @@
+use alloc::{vec, vec::Vec};
+
+use crate::generic_cube::{
+    piece_type, solved_state, Cube, CubeSize, Face, Move, MoveVariant::*, PieceType, ORDERED_FACES,
+};
+
+/// A step of the beginner layer-by-layer method for the 3x3x3 cube, solving the white (``U``)
+/// layer first and the yellow (``D``) layer last.
+///
+/// Each step is defined by a mask of the stickers it solves, including those solved by the
+/// steps before it.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::{Cube, Move, MoveVariant::*, TwistyPuzzle};
+/// use cubesim::{BeginnerStep, FaceletCube};
+///
+/// // a D turn keeps the first two layers solved, and the yellow edges facing down
+/// let cube = FaceletCube::new(3).apply_move(Move::D(Standard));
+/// assert!(BeginnerStep::SecondLayer.is_done(&cube));
+/// assert!(BeginnerStep::LastLayerCross.is_done(&cube));
+/// assert_eq!(BeginnerStep::current(&cube), Some(BeginnerStep::LastLayerEdges));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum BeginnerStep {
+    /// The four edges of the white layer.
+    Cross,
+    /// The corners of the white layer, completing it.
+    FirstLayer,
+    /// The four edges between the white and yellow layers.
+    SecondLayer,
+    /// The yellow edges facing up, in any position.
+    LastLayerCross,
+    /// The yellow edges in their positions.
+    LastLayerEdges,
+    /// The yellow corners, solving the cube.
+    LastLayerCorners,
+}
+
+impl BeginnerStep {
+    /// Every step, in the order they are solved.
+    pub const ALL: [BeginnerStep; 6] = [
+        BeginnerStep::Cross,
+        BeginnerStep::FirstLayer,
+        BeginnerStep::SecondLayer,
+        BeginnerStep::LastLayerCross,
+        BeginnerStep::LastLayerEdges,
+        BeginnerStep::LastLayerCorners,
+    ];
+
+    /// The first step not done yet on a 3x3x3 cube, or ``None`` if the cube is solved.
+    pub fn current(cube: &impl Cube) -> Option<BeginnerStep> {
+        Self::ALL.into_iter().find(|step| !step.is_done(cube))
+    }
+
+    /// The step following this one.
+    pub fn next(self) -> Option<BeginnerStep> {
+        Self::ALL
+            .into_iter()
+            .skip_while(|step| *step != self)
+            .nth(1)
+    }
+
+    /// Masks a sticker of a 3x3x3 cube, given its index in the solved state, to ``Face::X`` if
+    /// the step doesn't solve it. Stickers only oriented by the step are masked to the face
+    /// of the piece's yellow sticker.
+    ///
+    /// This can be passed to ``Cube::mask`` as ``&|i, f| step.mask(i, f)``.
+    pub fn mask(self, index: CubeSize, face: Face) -> Face {
+        let piece = piece_type(3, index);
+        let layer = layer(index);
+        let solved_by = |other: BeginnerStep| self as usize >= other as usize;
+
+        let solved = match (layer, piece) {
+            (_, PieceType::Center) => true,
+            (Face::U, PieceType::Edge) => solved_by(BeginnerStep::Cross),
+            (Face::U, PieceType::Corner) => solved_by(BeginnerStep::FirstLayer),
+            (Face::D, PieceType::Edge) => solved_by(BeginnerStep::LastLayerEdges),
+            (Face::D, PieceType::Corner) => solved_by(BeginnerStep::LastLayerCorners),
+            _ => solved_by(BeginnerStep::SecondLayer),
+        };
+        let oriented =
+            layer == Face::D && piece == PieceType::Edge && solved_by(BeginnerStep::LastLayerCross);
+
+        if solved {
+            face
+        } else if oriented && face == Face::D {
+            Face::D
+        } else {
+            Face::X
+        }
+    }
+
+    /// Whether the step is done on a 3x3x3 cube, however the cube is held.
+    pub fn is_done(self, cube: &impl Cube) -> bool {
+        let goal: Vec<Face> = solved_state(3)
+            .into_iter()
+            .enumerate()
+            .map(|(i, face)| self.mask(i as CubeSize, face))
+            .collect();
+        let masked = cube.mask(&|i, f| self.mask(i, f));
+
+        orientations().any(|rotation| masked.apply_moves(&rotation).state() == goal)
+    }
+}
+
+/// The layer of a 3x3x3 cube a sticker is in, given its index in the solved state, with
+/// ``Face::X`` for the middle layer between ``U`` and ``D``.
+pub(crate) fn layer(index: CubeSize) -> Face {
+    match (ORDERED_FACES[(index / 9) as usize], (index % 9) / 3) {
+        (Face::U, _) => Face::U,
+        (Face::D, _) => Face::D,
+        (_, 0) => Face::U,
+        (_, 2) => Face::D,
+        _ => Face::X,
+    }
+}
+
+/// The whole cube rotations bringing a cube into each of its 24 orientations.
+pub(crate) fn orientations() -> impl Iterator<Item = Vec<Move>> {
+    let up_faces = [
+        Vec::new(),
+        vec![Move::X(Standard)],
+        vec![Move::X(Double)],
+        vec![Move::X(Inverse)],
+        vec![Move::Z(Standard)],
+        vec![Move::Z(Inverse)],
+    ];
+
+    up_faces.into_iter().flat_map(|up| {
+        [None, Some(Standard), Some(Double), Some(Inverse)]
+            .into_iter()
+            .map(move |turn| {
+                let mut rotation = up.clone();
+                rotation.extend(turn.map(Move::Y));
+                rotation
+            })
+    })
+}
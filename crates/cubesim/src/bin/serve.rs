@@ -0,0 +1,19 @@
+//! HTTP server exposing the solver, scrambler and renderer as a JSON API.
+//!
+//! Listens on ``127.0.0.1:8080`` unless another address is given as the only argument.
+
+use std::{env, process::ExitCode};
+
+fn main() -> ExitCode {
+    let addr = env::args()
+        .nth(1)
+        .unwrap_or_else(|| String::from("127.0.0.1:8080"));
+
+    println!("listening on http://{addr}");
+    if let Err(error) = cubesim::serve(&addr) {
+        eprintln!("failed to listen on {addr}: {error}");
+        return ExitCode::FAILURE;
+    }
+
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,317 @@
+//! Command line solver for 3x3x3 scrambles.
+//!
+//! Scrambles are taken from the command line, from files given with ``--file`` (one scramble
+//! per line), or from stdin when neither is given. Each scramble is solved independently and
+//! the process exits with a non-zero status if any of them could not be solved.
+
+use std::{
+    env,
+    fmt::Display,
+    fs,
+    io::{self, BufRead},
+    process::ExitCode,
+    str::FromStr,
+};
+
+use cubesim::{
+    all_moves, centering_rotation, format_scramble, ida_star_observed, json_string,
+    parse_algorithm_for, remove_rotations, simplify_moves, solve, Cube, FaceletCube, Move,
+    MoveVariant, PruningTable, ScrambleFormat, Solver, TwistyPuzzle,
+};
+
+const USAGE: &str = "\
+Usage: solve [OPTIONS] [SCRAMBLE]...
+
+Solves 3x3x3 scrambles given in WCA notation. Without a scramble or --file,
+scrambles are read from stdin, one per line.
+
+Options:
+  --method <METHOD>  thistlethwaite (default) or optimal, which finds the
+                     shortest solution of scrambles up to about 10 moves and
+                     gives up on longer ones after 67108864 states searched,
+                     around 15 s; use thistlethwaite for full scrambles
+  --metric <METRIC>  htm (default) or qtm, in which lengths are printed and
+                     limited by --max-len
+  --max-len <N>      fail if a solution is longer than N moves in the chosen metric
+  --format <FORMAT>  text (default) or json, printing one JSON object per line
+  --group <N>        separate the moves of text solutions in groups of N
+  --file <PATH>      read scrambles from a file, may be given multiple times
+  -h, --help         print this message";
+
+/// The search depth of the pruning table used by the optimal solver.
+const OPTIMAL_TABLE_DEPTH: i32 = 4;
+/// The longest solution the optimal solver looks for when ``--max-len`` is not given.
+const OPTIMAL_DEFAULT_LIMIT: usize = 20;
+/// The states the optimal solver visits before giving up on a scramble, a multiple of the
+/// states between the reports of its progress for it to report the last one.
+const OPTIMAL_NODE_LIMIT: u64 = 1 << 26;
+
+#[derive(Clone, Copy)]
+enum Method {
+    Thistlethwaite,
+    Optimal,
+}
+
+#[derive(Clone, Copy)]
+enum Metric {
+    Htm,
+    Qtm,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Format {
+    Text,
+    Json,
+}
+
+impl FromStr for Method {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "thistlethwaite" => Ok(Method::Thistlethwaite),
+            "optimal" => Ok(Method::Optimal),
+            _ => Err(format!("unknown method {s}")),
+        }
+    }
+}
+
+impl FromStr for Metric {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "htm" => Ok(Metric::Htm),
+            "qtm" => Ok(Metric::Qtm),
+            _ => Err(format!("unknown metric {s}")),
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("unknown format {s}")),
+        }
+    }
+}
+
+impl Display for Metric {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Metric::Htm => write!(f, "htm"),
+            Metric::Qtm => write!(f, "qtm"),
+        }
+    }
+}
+
+impl Metric {
+    fn length(&self, moves: &[Move]) -> usize {
+        match self {
+            Metric::Htm => moves.len(),
+            Metric::Qtm => moves
+                .iter()
+                .map(|mv| match mv.get_variant() {
+                    MoveVariant::Double => 2,
+                    _ => 1,
+                })
+                .sum(),
+        }
+    }
+}
+
+struct Options {
+    method: Method,
+    metric: Metric,
+    max_len: Option<usize>,
+    format: Format,
+    group: Option<usize>,
+    files: Vec<String>,
+    scramble: Vec<String>,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options {
+        method: Method::Thistlethwaite,
+        metric: Metric::Htm,
+        max_len: None,
+        format: Format::Text,
+        group: None,
+        files: vec![],
+        scramble: vec![],
+    };
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("missing value for {arg}"));
+
+        match arg.as_str() {
+            "--method" => options.method = value()?.parse()?,
+            "--metric" => options.metric = value()?.parse()?,
+            "--format" => options.format = value()?.parse()?,
+            "--file" => options.files.push(value()?),
+            "--max-len" => {
+                let n = value()?;
+                options.max_len = Some(n.parse().map_err(|_| format!("invalid length {n}"))?);
+            }
+            "--group" => {
+                let n = value()?;
+                options.group = Some(n.parse().map_err(|_| format!("invalid group size {n}"))?);
+            }
+            _ => options.scramble.push(arg),
+        }
+    }
+
+    Ok(options)
+}
+
+struct Solvers {
+    optimal: Option<Solver>,
+}
+
+impl Solvers {
+    // The solution and its length in the metric of the options
+    fn solve(&mut self, options: &Options, scramble: &str) -> Result<(Vec<Move>, usize), String> {
+        let moves = parse_algorithm_for(scramble, 3).map_err(|error| error.to_string())?;
+        let scrambled = FaceletCube::new(3).apply_moves(&moves);
+        // rotations, slices and wide moves of the scramble turn the centers, which the
+        // solvers expect in place
+        let rotation = centering_rotation(&scrambled).ok_or("no solution found")?;
+        let cube = scrambled.apply_moves(&rotation);
+
+        let solution = match options.method {
+            Method::Thistlethwaite => solve(&cube),
+            Method::Optimal => {
+                let face_moves = all_moves(3)[..18].to_vec();
+                let solver = self.optimal.get_or_insert_with(|| {
+                    Solver::new(
+                        face_moves.clone(),
+                        PruningTable::new(&[FaceletCube::new(3)], OPTIMAL_TABLE_DEPTH, &face_moves),
+                    )
+                    .with_node_limit(OPTIMAL_NODE_LIMIT)
+                });
+                // a solution no longer than the limit in either metric has at most as many
+                // moves, the search counting half turns
+                let limit = options.max_len.unwrap_or(OPTIMAL_DEFAULT_LIMIT) as i32;
+                let mut nodes = 0;
+                let solution = ida_star_observed(&cube, solver, limit, &mut |progress| {
+                    nodes = progress.nodes
+                });
+                if solution.is_none() && nodes >= OPTIMAL_NODE_LIMIT {
+                    return Err(format!(
+                        "no optimal solution found within {OPTIMAL_NODE_LIMIT} states searched"
+                    ));
+                }
+                solution
+            }
+        }
+        .ok_or("no solution found")?;
+        let solution = simplify_moves(&remove_rotations(&[rotation, solution].concat()));
+
+        let length = options.metric.length(&solution);
+
+        match options.max_len {
+            Some(max) if length > max => Err(format!("no solution within {max} moves")),
+            _ => Ok((solution, length)),
+        }
+    }
+}
+
+fn report(options: &Options, scramble: &str, result: &Result<(Vec<Move>, usize), String>) {
+    let solution = |moves: &[Move]| {
+        moves
+            .iter()
+            .map(|mv| mv.to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+
+    match (options.format, result) {
+        (Format::Text, Ok((moves, length))) => println!(
+            "{} ({} {})",
+            format_scramble(
+                moves,
+                ScrambleFormat {
+                    group: options.group,
+                    width: None,
+                    numbered: false,
+                }
+            ),
+            length,
+            options.metric
+        ),
+        (Format::Text, Err(error)) => eprintln!("{scramble}: {error}"),
+        (Format::Json, Ok((moves, length))) => println!(
+            "{{\"scramble\":{},\"solution\":{},\"length\":{},\"metric\":\"{}\"}}",
+            json_string(scramble),
+            json_string(&solution(moves)),
+            length,
+            options.metric
+        ),
+        (Format::Json, Err(error)) => println!(
+            "{{\"scramble\":{},\"error\":{}}}",
+            json_string(scramble),
+            json_string(error)
+        ),
+    }
+}
+
+fn read_scrambles(options: &Options) -> io::Result<Vec<String>> {
+    let lines = if !options.scramble.is_empty() {
+        vec![options.scramble.join(" ")]
+    } else if !options.files.is_empty() {
+        let mut lines = vec![];
+        for file in &options.files {
+            lines.extend(fs::read_to_string(file)?.lines().map(String::from));
+        }
+        lines
+    } else {
+        io::stdin().lock().lines().collect::<io::Result<_>>()?
+    };
+
+    Ok(lines
+        .into_iter()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect())
+}
+
+fn main() -> ExitCode {
+    if env::args().any(|arg| arg == "-h" || arg == "--help") {
+        println!("{USAGE}");
+        return ExitCode::SUCCESS;
+    }
+
+    let options = match parse_args(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{message}\n\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let scrambles = match read_scrambles(&options) {
+        Ok(scrambles) => scrambles,
+        Err(error) => {
+            eprintln!("failed to read scrambles: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut solvers = Solvers { optimal: None };
+    let mut all_solved = true;
+
+    for scramble in &scrambles {
+        let result = solvers.solve(&options, scramble);
+        all_solved &= result.is_ok();
+        report(&options, scramble, &result);
+    }
+
+    if all_solved {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
@@ -0,0 +1,34 @@
+//! Writes the pruning tables of the solvers, to ship them ready-made with an app.
+//!
+//! The tables are written to the file given on the command line, or to stdout when none is
+//! given. Apps read them back with ``cubesim::read_tables``.
+
+use std::{env, fs, io::{self, Write}, process::ExitCode};
+
+use cubesim::write_tables;
+
+const USAGE: &str = "\
+Usage: tables [PATH]
+
+Writes the pruning tables of the solvers to PATH, or to stdout.";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.len() > 1 || args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        eprintln!("{USAGE}");
+        return ExitCode::FAILURE;
+    }
+
+    let bytes = write_tables();
+    let written = match args.first() {
+        Some(path) => fs::write(path, &bytes),
+        None => io::stdout().write_all(&bytes),
+    };
+    match written {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,29 @@
+//! Stand-ins for the parts of the standard library used by the core, so that it also builds
+//! under ``no_std`` with only ``alloc``.
+
+use core::ops::DerefMut;
+
+#[cfg(feature = "std")]
+pub(crate) use rustc_hash::FxHashMap;
+#[cfg(feature = "std")]
+pub(crate) use std::sync::Mutex;
+
+#[cfg(not(feature = "std"))]
+pub(crate) type FxHashMap<K, V> =
+    hashbrown::HashMap<K, V, core::hash::BuildHasherDefault<rustc_hash::FxHasher>>;
+#[cfg(not(feature = "std"))]
+pub(crate) use spin::Mutex;
+
+/// Locks a mutex, ignoring poisoning as the cached data can't be left half written.
+#[cfg(feature = "std")]
+pub(crate) fn lock<T>(mutex: &Mutex<T>) -> impl DerefMut<Target = T> + '_ {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Locks a mutex, spinning until it is available.
+#[cfg(not(feature = "std"))]
+pub(crate) fn lock<T>(mutex: &Mutex<T>) -> impl DerefMut<Target = T> + '_ {
+    mutex.lock()
+}
@@ -0,0 +1,132 @@
+use alloc::{format, string::String, string::ToString, vec::Vec};
+
+use crate::cycles::validate_state;
+use crate::generic_cube::{Face, Move};
+use crate::scramble_parser::{parse_scramble, validate_scramble};
+
+/// A line of a Cube Explorer list: a maneuver, or a position given by its 54 facelets in the
+/// order of ``Cube::state``, such as ``UUUUUUUUURRRRRRRRRFFFFFFFFFDDDDDDDDDLLLLLLLLLBBBBBBBBB``
+/// for the solved cube.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CubeExplorerEntry {
+    /// The moves of a maneuver, such as a solution found by Cube Explorer.
+    Maneuver(Vec<Move>),
+    /// The stickers of a 3x3x3 position.
+    Position(Vec<Face>),
+}
+
+// The facelet string of a position, or nothing if the line is a maneuver
+fn facelets(line: &str) -> Option<Vec<Face>> {
+    if line.len() != 54 {
+        return None;
+    }
+    line.chars()
+        .map(|letter| match letter {
+            'U' => Some(Face::U),
+            'R' => Some(Face::R),
+            'F' => Some(Face::F),
+            'D' => Some(Face::D),
+            'L' => Some(Face::L),
+            'B' => Some(Face::B),
+            _ => None,
+        })
+        .collect()
+}
+
+// A move of Cube Explorer in WCA notation, which also counts quarter turns as in `U1` or `U3`
+fn wca_move(token: &str) -> String {
+    if let Some(turn) = token.strip_suffix('1') {
+        turn.to_string()
+    } else if let Some(turn) = token.strip_suffix('3') {
+        format!("{turn}'")
+    } else {
+        token.to_string()
+    }
+}
+
+/// Reads the maneuvers and positions of a list saved by Cube Explorer, one per line. The
+/// lengths Cube Explorer writes after its maneuvers, such as ``(18f*)``, comments starting
+/// with ``//`` and empty lines are skipped.
+///
+/// # Errors
+///
+/// Returns an error naming the first line that's neither a maneuver nor a position a cube can
+/// be turned into.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::{Face, Move, MoveVariant::*};
+/// use cubesim::{read_cube_explorer, CubeExplorerEntry};
+///
+/// let entries = read_cube_explorer(
+///     "// superflip\n\
+///      U R2 F B R B2 R U2 L B2 R U' D' R2 F R' L B2 U2 F2 (20f*)\n\
+///      R1 U3  (2f)\n\
+///      UUUUUUUUURRRRRRRRRFFFFFFFFFDDDDDDDDDLLLLLLLLLBBBBBBBBB\n",
+/// )
+/// .unwrap();
+///
+/// assert_eq!(entries.len(), 3);
+/// assert_eq!(
+///     entries[1],
+///     CubeExplorerEntry::Maneuver(vec![Move::R(Standard), Move::U(Inverse)])
+/// );
+/// assert!(matches!(&entries[2], CubeExplorerEntry::Position(state) if state[0] == Face::U));
+/// ```
+pub fn read_cube_explorer(text: &str) -> Result<Vec<CubeExplorerEntry>, String> {
+    let mut entries = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        let line = line.split("//").next().unwrap_or_default();
+        let line = line.split('(').next().unwrap_or_default().trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let entry = match facelets(line) {
+            Some(state) => validate_state(&state).map(|_| CubeExplorerEntry::Position(state)),
+            None => {
+                let moves: Vec<String> = line.split_whitespace().map(wca_move).collect();
+                let maneuver = moves.join(" ");
+                validate_scramble(&maneuver)
+                    .map(|_| CubeExplorerEntry::Maneuver(parse_scramble(maneuver)))
+            }
+        };
+        entries.push(entry.map_err(|err| format!("line {}: {err}", number + 1))?);
+    }
+    Ok(entries)
+}
+
+/// Writes maneuvers and positions as a list Cube Explorer can read, one per line, each
+/// maneuver followed by its length in face turns.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::{Cube, Move, MoveVariant::*, TwistyPuzzle};
+/// use cubesim::{read_cube_explorer, write_cube_explorer, CubeExplorerEntry, FaceletCube};
+///
+/// let entries = vec![
+///     CubeExplorerEntry::Maneuver(vec![Move::R(Standard), Move::U(Double)]),
+///     CubeExplorerEntry::Position(FaceletCube::new(3).apply_move(Move::F(Inverse)).state()),
+/// ];
+/// let text = write_cube_explorer(&entries);
+///
+/// assert!(text.starts_with("R U2 (2f)\n"));
+/// assert_eq!(read_cube_explorer(&text).unwrap(), entries);
+/// ```
+pub fn write_cube_explorer(entries: &[CubeExplorerEntry]) -> String {
+    entries
+        .iter()
+        .map(|entry| match entry {
+            CubeExplorerEntry::Maneuver(moves) => {
+                let moves: Vec<String> = moves.iter().map(|mv| mv.to_string()).collect();
+                format!("{} ({}f)\n", moves.join(" "), moves.len())
+            }
+            CubeExplorerEntry::Position(state) => {
+                let facelets: String = state.iter().map(|face| face.to_string()).collect();
+                format!("{facelets}\n")
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,166 @@
+use alloc::{format, string::String, vec::Vec};
+
+use crate::compat::{lock, FxHashMap, Mutex};
+use crate::cycles::{locate_pieces, pieces_state, validate_state, CORNER_NAMES, EDGE_NAMES};
+use crate::generic_cube::{Cube, Face, Move};
+use crate::generic_puzzle::TwistyPuzzle;
+use crate::{FaceletCube, GeoCube};
+
+/// A 3x3x3 cube stored by its pieces: the corner and the edge at each position, and how they
+/// are turned there.
+///
+/// The pieces are placed relative to the centers, which stay put: moves turning the centers,
+/// such as rotations, slices and wide turns, only move the pieces relative to the centers,
+/// the cube being held again with its centers in place. Compared to the ``FaceletCube``, it
+/// takes far less memory, can only hold valid states and tells at once how far each piece is
+/// from solved.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::{Cube, Move, MoveVariant::*, TwistyPuzzle};
+/// use cubesim::{CubieCube, FaceletCube};
+///
+/// let moves = [Move::R(Standard), Move::U(Double), Move::F(Inverse)];
+/// let cube = CubieCube::new().apply_moves(&moves);
+/// assert_eq!(FaceletCube::from(cube), FaceletCube::new(3).apply_moves(&moves));
+/// assert_eq!(CubieCube::try_from(FaceletCube::new(3).apply_moves(&moves)), Ok(cube));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct CubieCube {
+    corners: [(usize, u8); 8],
+    edges: [(usize, u8); 12],
+}
+
+impl CubieCube {
+    /// A solved cube.
+    pub fn new() -> Self {
+        Self {
+            corners: core::array::from_fn(|position| (position, 0)),
+            edges: core::array::from_fn(|position| (position, 0)),
+        }
+    }
+
+    /// A cube with the given pieces at each position, checking that a solved cube can be
+    /// turned into them.
+    ///
+    /// # Errors
+    ///
+    /// Returns why the pieces can't be reached by turning the cube, as ``validate_state``
+    /// does: a piece missing, twisted, flipped or swapped.
+    pub fn from_pieces(corners: [(usize, u8); 8], edges: [(usize, u8); 12]) -> Result<Self, String> {
+        if let Some(position) = (0..8).find(|&i| corners[i].0 >= 8 || corners[i].1 >= 3) {
+            return Err(format!("the corner at {} is no corner", CORNER_NAMES[position]));
+        }
+        if let Some(position) = (0..12).find(|&i| edges[i].0 >= 12 || edges[i].1 >= 2) {
+            return Err(format!("the edge at {} is no edge", EDGE_NAMES[position]));
+        }
+        validate_state(&pieces_state(&corners, &edges))?;
+        Ok(Self { corners, edges })
+    }
+
+    /// The corner at each position of ``CORNER_NAMES``, by the index of its home position,
+    /// with its twist: how many stickers its U or D sticker is clockwise of the position's
+    /// U or D sticker.
+    pub fn corners(&self) -> [(usize, u8); 8] {
+        self.corners
+    }
+
+    /// The edge at each position of ``EDGE_NAMES``, by the index of its home position, with
+    /// its flip: 1 if its U, D, F or B sticker isn't the one of the position of
+    /// ``EDGE_STICKERS`` first, 0 otherwise.
+    pub fn edges(&self) -> [(usize, u8); 12] {
+        self.edges
+    }
+
+    /// The stickers of the cube, in the order of ``Cube::state``.
+    pub fn state(&self) -> Vec<Face> {
+        pieces_state(&self.corners, &self.edges)
+    }
+
+    /// Whether every piece is solved.
+    pub fn is_solved(&self) -> bool {
+        *self == Self::new()
+    }
+
+    /// The cube with a move applied.
+    pub fn apply_move(&self, mv: Move) -> Self {
+        static MOVES: Mutex<Option<FxHashMap<Move, CubieCube>>> = Mutex::new(None);
+
+        let mut moves = lock(&MOVES);
+        let turned = *moves
+            .get_or_insert_with(FxHashMap::default)
+            .entry(mv)
+            .or_insert_with(|| held_again(&FaceletCube::new(3).apply_move(mv)));
+
+        // the piece turned into each position comes from where the move takes it from
+        Self {
+            corners: turned.corners.map(|(from, twist)| {
+                let (home, turned_twist) = self.corners[from];
+                (home, (turned_twist + twist) % 3)
+            }),
+            edges: turned.edges.map(|(from, flip)| {
+                let (home, turned_flip) = self.edges[from];
+                (home, (turned_flip + flip) % 2)
+            }),
+        }
+    }
+
+    /// The cube with a sequence of moves applied.
+    pub fn apply_moves(&self, moves: &[Move]) -> Self {
+        moves.iter().fold(*self, |cube, mv| cube.apply_move(*mv))
+    }
+}
+
+impl Default for CubieCube {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// The pieces of a valid 3x3x3 cube, relative to its centers
+fn held_again(cube: &impl Cube) -> CubieCube {
+    let (corners, edges) = locate_pieces(&cube.state()).unwrap_or_else(|err| panic!("{err}"));
+    CubieCube { corners, edges }
+}
+
+/// The pieces of a 3x3x3 cube, relative to its centers however it's held.
+///
+/// # Errors
+///
+/// Returns why the cube can't be stored by its pieces: it isn't a 3x3x3 cube, has masked
+/// stickers or its stickers can't be reached by turning a solved cube.
+impl TryFrom<FaceletCube> for CubieCube {
+    type Error = String;
+
+    fn try_from(cube: FaceletCube) -> Result<Self, Self::Error> {
+        validate_state(&cube.state())?;
+        Ok(held_again(&cube))
+    }
+}
+
+/// The pieces of a 3x3x3 cube, relative to its centers however it's held, as for a
+/// ``FaceletCube``.
+impl TryFrom<GeoCube> for CubieCube {
+    type Error = String;
+
+    fn try_from(cube: GeoCube) -> Result<Self, Self::Error> {
+        validate_state(&cube.state())?;
+        Ok(held_again(&cube))
+    }
+}
+
+/// The cube with its centers in place, each sticker known by where it was on the solved
+/// cube, so it converts back into the same ``CubieCube``.
+impl From<CubieCube> for FaceletCube {
+    fn from(cube: CubieCube) -> Self {
+        FaceletCube::from(cube.state())
+    }
+}
+
+/// The cube with its centers in place, as for a ``FaceletCube``.
+impl From<CubieCube> for GeoCube {
+    fn from(cube: CubieCube) -> Self {
+        GeoCube::from(FaceletCube::from(cube))
+    }
+}
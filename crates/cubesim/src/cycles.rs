@@ -0,0 +1,333 @@
+use alloc::{format, string::String, string::ToString, vec, vec::Vec};
+
+use crate::generic_cube::{Cube, Face, ORDERED_FACES};
+
+/// The corner positions of a 3x3x3 cube, named by their faces.
+pub const CORNER_NAMES: [&str; 8] = ["URF", "UFL", "ULB", "UBR", "DFR", "DLF", "DBL", "DRB"];
+
+/// The edge positions of a 3x3x3 cube, named by their faces.
+pub const EDGE_NAMES: [&str; 12] = [
+    "UR", "UF", "UL", "UB", "DR", "DF", "DL", "DB", "FR", "FL", "BL", "BR",
+];
+
+// The stickers of each corner position, starting with its U or D sticker and going clockwise
+pub(crate) const CORNER_STICKERS: [[usize; 3]; 8] = [
+    [8, 9, 20],
+    [6, 18, 38],
+    [0, 36, 47],
+    [2, 45, 11],
+    [29, 26, 15],
+    [27, 44, 24],
+    [33, 53, 42],
+    [35, 17, 51],
+];
+
+/// The stickers of each edge position of ``EDGE_NAMES`` by their index in the state of a
+/// 3x3x3 cube, starting with its U, D, F or B sticker.
+pub const EDGE_STICKERS: [[usize; 2]; 12] = [
+    [5, 10],
+    [7, 19],
+    [3, 37],
+    [1, 46],
+    [32, 16],
+    [28, 25],
+    [30, 43],
+    [34, 52],
+    [23, 12],
+    [21, 41],
+    [50, 39],
+    [48, 14],
+];
+
+/// A cycle of pieces of the same kind, each piece taking the place of the next one.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Cycle {
+    /// The positions of the pieces in the cycle, as indices into ``CORNER_NAMES`` or
+    /// ``EDGE_NAMES``. The piece at each position belongs to the next position, and the
+    /// piece at the last position belongs to the first.
+    pub positions: Vec<usize>,
+    /// The twist of the corners (0 to 2, clockwise) or the flip of the edges (0 or 1) left
+    /// once the cycle is solved.
+    pub orientation: u8,
+}
+
+/// The cycles of the corners and edges of a 3x3x3 cube, as used for blindfolded solving
+/// and insertions.
+///
+/// Pieces in place but twisted or flipped form cycles of a single position, while solved
+/// pieces aren't part of any cycle.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct CycleInfo {
+    /// The cycles of the corners.
+    pub corner_cycles: Vec<Cycle>,
+    /// The cycles of the edges.
+    pub edge_cycles: Vec<Cycle>,
+}
+
+impl CycleInfo {
+    /// Whether every corner and edge is solved.
+    pub fn is_solved(&self) -> bool {
+        self.corner_cycles.is_empty() && self.edge_cycles.is_empty()
+    }
+
+    /// The number of corners and edges out of place or misoriented.
+    pub fn unsolved_pieces(&self) -> usize {
+        self.corner_cycles
+            .iter()
+            .chain(&self.edge_cycles)
+            .map(|cycle| cycle.positions.len())
+            .sum()
+    }
+
+    /// Whether the corners, and so the edges, are permuted by an odd permutation, like
+    /// after a single quarter turn.
+    pub fn has_parity(&self) -> bool {
+        let swaps: usize = self
+            .corner_cycles
+            .iter()
+            .map(|cycle| cycle.positions.len() - 1)
+            .sum();
+        swaps % 2 == 1
+    }
+}
+
+/// Decomposes the corners and edges of a 3x3x3 cube into cycles, however the cube is held.
+///
+/// # Panics
+///
+/// Panics if the cube isn't a 3x3x3 cube, or has masked stickers.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::{Cube, Move, MoveVariant::*, TwistyPuzzle};
+/// use cubesim::{cycle_structure, FaceletCube};
+///
+/// let cycles = cycle_structure(&FaceletCube::new(3).apply_move(Move::U(Standard)));
+///
+/// assert_eq!(cycles.corner_cycles.len(), 1);
+/// assert_eq!(cycles.corner_cycles[0].positions.len(), 4);
+/// assert_eq!(cycles.edge_cycles[0].orientation, 0);
+/// assert!(cycles.has_parity());
+/// ```
+pub fn cycle_structure(cube: &impl Cube) -> CycleInfo {
+    assert_eq!(cube.size(), 3, "cycles are only found on 3x3x3 cubes");
+    let (corners, edges) = locate_pieces(&cube.state()).unwrap_or_else(|err| panic!("{err}"));
+
+    CycleInfo {
+        corner_cycles: cycles(&corners, 3),
+        edge_cycles: cycles(&edges, 2),
+    }
+}
+
+/// Checks that the stickers of a 3x3x3 cube, in the order of ``Cube::state``, can be reached
+/// by turning a solved cube however it's held, such as stickers read from a real cube.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::{Cube, Face, Move, MoveVariant, TwistyPuzzle};
+/// use cubesim::{validate_state, FaceletCube};
+///
+/// let turned = FaceletCube::new(3).apply_move(Move::R(MoveVariant::Standard));
+/// assert!(validate_state(&turned.state()).is_ok());
+///
+/// // swapping two stickers of an edge flips it
+/// let mut flipped = turned.state();
+/// flipped.swap(7, 19);
+/// assert_eq!(validate_state(&flipped), Err("an edge is flipped".to_string()));
+/// ```
+pub fn validate_state(state: &[Face]) -> Result<(), String> {
+    if state.len() != 54 {
+        return Err(format!("a 3x3x3 cube has 54 stickers, not {}", state.len()));
+    }
+    for color in (0..6).map(|f| state[f * 9 + 4]) {
+        let count = state.iter().filter(|&&face| face == color).count();
+        if count != 9 {
+            return Err(format!("{count} stickers are {color:?}, instead of 9"));
+        }
+    }
+
+    let (corners, edges) = locate_pieces(state)?;
+    if let Some(home) = (0..8).find(|&home| !corners.iter().any(|&(h, _)| h == home)) {
+        return Err(format!("no corner is {}", CORNER_NAMES[home]));
+    }
+    if let Some(home) = (0..12).find(|&home| !edges.iter().any(|&(h, _)| h == home)) {
+        return Err(format!("no edge is {}", EDGE_NAMES[home]));
+    }
+
+    let corner_cycles = cycles(&corners, 3);
+    let edge_cycles = cycles(&edges, 2);
+    if corner_cycles.iter().map(|c| c.orientation).sum::<u8>() % 3 != 0 {
+        return Err("a corner is twisted".to_string());
+    }
+    if edge_cycles.iter().map(|c| c.orientation).sum::<u8>() % 2 != 0 {
+        return Err("an edge is flipped".to_string());
+    }
+    let swaps = |cycles: &[Cycle]| -> usize { cycles.iter().map(|c| c.positions.len() - 1).sum() };
+    if swaps(&corner_cycles) % 2 != swaps(&edge_cycles) % 2 {
+        return Err("two pieces are swapped".to_string());
+    }
+    Ok(())
+}
+
+/// The positions of the edges of a 3x3x3 state, as indices of ``EDGE_NAMES``, which are
+/// misoriented on the F/B axis: the edges which can't be solved without quarter turns of the F
+/// or B faces, as recognized in the ZZ method and fewest moves solving. The state is held the
+/// way it's given, the colors of its centers telling which face each sticker belongs to.
+///
+/// # Errors
+///
+/// Returns the error of ``validate_state`` if the state isn't one a cube can be turned into.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::{Cube, Move, MoveVariant::*, TwistyPuzzle};
+/// use cubesim::{bad_edges, FaceletCube, EDGE_NAMES};
+///
+/// let cube = FaceletCube::new(3).apply_move(Move::F(Standard));
+/// let bad: Vec<&str> = bad_edges(&cube.state()).unwrap().iter().map(|&i| EDGE_NAMES[i]).collect();
+/// assert_eq!(bad, vec!["UF", "DF", "FR", "FL"]);
+///
+/// let cube = FaceletCube::new(3).apply_move(Move::R(Standard));
+/// assert!(bad_edges(&cube.state()).unwrap().is_empty());
+/// ```
+pub fn bad_edges(state: &[Face]) -> Result<Vec<usize>, String> {
+    validate_state(state)?;
+    let (_, edges) = locate_pieces(state)?;
+    Ok((0..edges.len()).filter(|&i| edges[i].1 == 1).collect())
+}
+
+// Where each sticker of a valid 3x3x3 state was on the solved cube, as its index in the
+// state, so the stickers of a cube known only by its colors can be followed like the ones of
+// a cube turned from solved
+pub(crate) fn sticker_origins(state: &[Face]) -> Result<Vec<u16>, String> {
+    validate_state(state)?;
+    let (corners, edges) = locate_pieces(state)?;
+
+    let mut origins: Vec<u16> = (0..54).collect();
+    for f in 0..6 {
+        let center = f * 9 + 4;
+        let home = (0..6).find(|&h| state[h * 9 + 4] == state[center]).unwrap_or(f);
+        origins[center] = (home * 9 + 4) as u16;
+    }
+    for (position, (home, twist)) in corners.into_iter().enumerate() {
+        for i in 0..3 {
+            let sticker = CORNER_STICKERS[position][(i + twist as usize) % 3];
+            origins[sticker] = CORNER_STICKERS[home][i] as u16;
+        }
+    }
+    for (position, (home, flip)) in edges.into_iter().enumerate() {
+        for i in 0..2 {
+            let sticker = EDGE_STICKERS[position][i];
+            origins[sticker] = EDGE_STICKERS[home][i ^ flip as usize] as u16;
+        }
+    }
+    Ok(origins)
+}
+
+// The state of a 3x3x3 cube with the pieces given by their home and orientation at each
+// position, the inverse of ``locate_pieces`` with the centers solved
+pub(crate) fn pieces_state(corners: &[(usize, u8); 8], edges: &[(usize, u8); 12]) -> Vec<Face> {
+    let mut state: Vec<Face> = ORDERED_FACES.iter().flat_map(|&face| [face; 9]).collect();
+    for (position, &(home, twist)) in corners.iter().enumerate() {
+        for i in 0..3 {
+            let sticker = CORNER_STICKERS[position][(i + twist as usize) % 3];
+            state[sticker] = ORDERED_FACES[CORNER_STICKERS[home][i] / 9];
+        }
+    }
+    for (position, &(home, flip)) in edges.iter().enumerate() {
+        for i in 0..2 {
+            let sticker = EDGE_STICKERS[position][i];
+            state[sticker] = ORDERED_FACES[EDGE_STICKERS[home][i ^ flip as usize] / 9];
+        }
+    }
+    state
+}
+
+// The home position and the orientation of the piece at each corner and edge position, by
+// the colors of its stickers
+pub(crate) type Pieces = ([(usize, u8); 8], [(usize, u8); 12]);
+
+pub(crate) fn locate_pieces(state: &[Face]) -> Result<Pieces, String> {
+    // the centers tell which face each color belongs to, whatever the cube's orientation
+    let home_face = |color: Face| {
+        (0..6)
+            .find(|&f| state[f * 9 + 4] == color)
+            .map(|center| ORDERED_FACES[center])
+            .ok_or_else(|| format!("no center is {color:?}"))
+    };
+    let faces = |stickers: &[usize]| -> Result<Vec<Face>, String> {
+        stickers.iter().map(|&i| home_face(state[i])).collect()
+    };
+    let solved_faces = |stickers: &[usize]| -> Vec<Face> {
+        stickers.iter().map(|&i| ORDERED_FACES[i / 9]).collect()
+    };
+
+    let mut corners = [(0, 0); 8];
+    for (position, stickers) in CORNER_STICKERS.iter().enumerate() {
+        let faces = faces(stickers)?;
+        let unknown = || format!("the corner at {} is no corner", CORNER_NAMES[position]);
+        // the twist is where the U or D sticker of the corner went
+        let twist = faces
+            .iter()
+            .position(|&face| face == Face::U || face == Face::D)
+            .ok_or_else(unknown)?;
+        let home = CORNER_STICKERS
+            .iter()
+            .position(|home| {
+                let home = solved_faces(home);
+                (0..3).all(|i| home[i] == faces[(i + twist) % 3])
+            })
+            .ok_or_else(unknown)?;
+        corners[position] = (home, twist as u8);
+    }
+
+    let mut edges = [(0, 0); 12];
+    for (position, stickers) in EDGE_STICKERS.iter().enumerate() {
+        let faces = faces(stickers)?;
+        let home = EDGE_STICKERS
+            .iter()
+            .position(|home| {
+                let mut home = solved_faces(home);
+                if home != faces {
+                    home.reverse();
+                }
+                home == faces
+            })
+            .ok_or_else(|| format!("the edge at {} is no edge", EDGE_NAMES[position]))?;
+        let flip = (solved_faces(&EDGE_STICKERS[home]) != faces) as u8;
+        edges[position] = (home, flip);
+    }
+
+    Ok((corners, edges))
+}
+
+// Follows the pieces from each position to their home, given the home and orientation of
+// the piece at each position
+fn cycles(pieces: &[(usize, u8)], orientations: u8) -> Vec<Cycle> {
+    let mut visited = vec![false; pieces.len()];
+    let mut cycles = Vec::new();
+
+    for start in 0..pieces.len() {
+        if visited[start] || pieces[start] == (start, 0) {
+            continue;
+        }
+
+        let mut cycle = Cycle {
+            positions: Vec::new(),
+            orientation: 0,
+        };
+        let mut position = start;
+        while !visited[position] {
+            visited[position] = true;
+            cycle.positions.push(position);
+            cycle.orientation = (cycle.orientation + pieces[position].1) % orientations;
+            position = pieces[position].0;
+        }
+        cycles.push(cycle);
+    }
+
+    cycles
+}
@@ -0,0 +1,76 @@
+use alloc::vec::Vec;
+
+use crate::generic_cube::{Cube, Face};
+
+/// A sticker showing different faces on two cubes.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct StickerDiff {
+    /// The index of the sticker, in the order of ``Cube::state``.
+    pub index: usize,
+    /// The face shown by the sticker on the first cube.
+    pub left: Face,
+    /// The face shown by the sticker on the second cube.
+    pub right: Face,
+}
+
+/// The stickers where two cubes of the same size show different faces, in the order of
+/// ``Cube::state``.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::{Cube, Face, Move, MoveVariant, TwistyPuzzle};
+/// use cubesim::{diff, FaceletCube, GeoCube};
+///
+/// let solved = FaceletCube::new(3);
+/// assert!(diff(&solved, &GeoCube::new(3)).is_empty());
+///
+/// // the turned face stays the same color, only the 12 stickers around it change
+/// let turned = solved.apply_move(Move::U(MoveVariant::Standard));
+/// let diffs = diff(&solved, &turned);
+/// assert_eq!(diffs.len(), 12);
+/// assert_eq!((diffs[0].left, diffs[0].right), (Face::R, Face::B));
+/// ```
+///
+/// # Panics
+///
+/// Panics if the cubes aren't the same size.
+pub fn diff(a: &impl Cube, b: &impl Cube) -> Vec<StickerDiff> {
+    assert_eq!(a.size(), b.size(), "can't compare cubes of different sizes");
+    a.state_iter()
+        .zip(b.state_iter())
+        .enumerate()
+        .filter(|(_, (left, right))| left != right)
+        .map(|(index, (left, right))| StickerDiff { index, left, right })
+        .collect()
+}
+
+/// A lower bound of the number of single layer turns, quarter or half, turning one cube into
+/// the other. Cube rotations aren't free: a rotated cube is as far as the turns rotating it.
+///
+/// A turn of an outer layer changes at most the stickers of its face and the four rows
+/// around it, so the bound is the number of stickers differing over that many. It's zero only
+/// when the cubes are the same.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::{Cube, Move, MoveVariant, TwistyPuzzle};
+/// use cubesim::{distance_lower_bound, parse_scramble, FaceletCube};
+///
+/// let solved = FaceletCube::new(3);
+/// assert_eq!(distance_lower_bound(&solved, &solved), 0);
+///
+/// let scrambled = solved.apply_moves(&parse_scramble(String::from("R U F")));
+/// let bound = distance_lower_bound(&solved, &scrambled);
+/// assert!(bound >= 1 && bound <= 3);
+/// ```
+///
+/// # Panics
+///
+/// Panics if the cubes aren't the same size.
+pub fn distance_lower_bound(a: &impl Cube, b: &impl Cube) -> usize {
+    let size = a.size() as usize;
+    let changed_per_turn = size * size + 4 * size;
+    diff(a, b).len().div_ceil(changed_per_turn)
+}
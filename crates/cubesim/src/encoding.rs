@@ -0,0 +1,142 @@
+use alloc::{format, string::String, vec::Vec};
+
+use crate::generic_cube::{CubeSize, Move, MoveVariant};
+
+// Each move is written as a byte, its kind times three plus its variant. The wide and inner
+// moves of other layers than the second are followed by their layer, seven bits a byte from
+// the lowest, the highest bit of a byte telling whether another one follows.
+const FACE_TURNS: [fn(MoveVariant) -> Move; 6] =
+    [Move::U, Move::L, Move::F, Move::R, Move::B, Move::D];
+const ROTATIONS: [fn(MoveVariant) -> Move; 3] = [Move::X, Move::Y, Move::Z];
+const WIDE_TURNS: [fn(CubeSize, MoveVariant) -> Move; 6] =
+    [Move::Uw, Move::Lw, Move::Fw, Move::Rw, Move::Bw, Move::Dw];
+const INNER_TURNS: [fn(CubeSize, MoveVariant) -> Move; 6] =
+    [Move::Ui, Move::Li, Move::Fi, Move::Ri, Move::Bi, Move::Di];
+
+// The first kind of each group of moves, of the second layer or of the layer written after
+const WIDE_SECOND: u8 = 9;
+const INNER_SECOND: u8 = 15;
+const WIDE_LAYER: u8 = 21;
+const INNER_LAYER: u8 = 27;
+const KINDS: u8 = 33;
+
+const VARIANTS: [MoveVariant; 3] = [
+    MoveVariant::Standard,
+    MoveVariant::Double,
+    MoveVariant::Inverse,
+];
+
+// The kind of a move, and the layer to write after it
+fn kind(mv: Move) -> (u8, Option<CubeSize>) {
+    let layered = |face: u8, n: CubeSize, second: u8, layer: u8| match n {
+        2 => (second + face, None),
+        n => (layer + face, Some(n)),
+    };
+    match mv {
+        Move::U(_) => (0, None),
+        Move::L(_) => (1, None),
+        Move::F(_) => (2, None),
+        Move::R(_) => (3, None),
+        Move::B(_) => (4, None),
+        Move::D(_) => (5, None),
+        Move::X(_) => (6, None),
+        Move::Y(_) => (7, None),
+        Move::Z(_) => (8, None),
+        Move::Uw(n, _) => layered(0, n, WIDE_SECOND, WIDE_LAYER),
+        Move::Lw(n, _) => layered(1, n, WIDE_SECOND, WIDE_LAYER),
+        Move::Fw(n, _) => layered(2, n, WIDE_SECOND, WIDE_LAYER),
+        Move::Rw(n, _) => layered(3, n, WIDE_SECOND, WIDE_LAYER),
+        Move::Bw(n, _) => layered(4, n, WIDE_SECOND, WIDE_LAYER),
+        Move::Dw(n, _) => layered(5, n, WIDE_SECOND, WIDE_LAYER),
+        Move::Ui(n, _) => layered(0, n, INNER_SECOND, INNER_LAYER),
+        Move::Li(n, _) => layered(1, n, INNER_SECOND, INNER_LAYER),
+        Move::Fi(n, _) => layered(2, n, INNER_SECOND, INNER_LAYER),
+        Move::Ri(n, _) => layered(3, n, INNER_SECOND, INNER_LAYER),
+        Move::Bi(n, _) => layered(4, n, INNER_SECOND, INNER_LAYER),
+        Move::Di(n, _) => layered(5, n, INNER_SECOND, INNER_LAYER),
+    }
+}
+
+/// Writes moves in a compact binary form, as read by ``decode_moves``, for replay files, links
+/// and network messages. Every move of a 3x3x3 cube, including its wide and slice moves, takes
+/// a single byte, and the moves of bigger cubes a few more for their layer.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::{Move, MoveVariant::*};
+/// use cubesim::{decode_moves, encode_moves};
+///
+/// let moves = [Move::R(Standard), Move::Uw(2, Inverse), Move::Fw(3, Double)];
+/// let bytes = encode_moves(&moves);
+///
+/// assert_eq!(bytes.len(), 4);
+/// assert_eq!(decode_moves(&bytes).unwrap(), moves);
+/// ```
+pub fn encode_moves(moves: &[Move]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(moves.len());
+    for &mv in moves {
+        let (kind, layer) = kind(mv);
+        bytes.push(kind * 3 + mv.get_variant() as u8 - 1);
+
+        if let Some(layer) = layer {
+            let mut layer = layer as u32;
+            while layer >= 0x80 {
+                bytes.push(layer as u8 | 0x80);
+                layer >>= 7;
+            }
+            bytes.push(layer as u8);
+        }
+    }
+    bytes
+}
+
+/// Reads moves written by ``encode_moves``.
+///
+/// # Errors
+///
+/// Returns an error if a byte isn't a move, or the bytes end within the layer of a move.
+pub fn decode_moves(bytes: &[u8]) -> Result<Vec<Move>, String> {
+    let mut moves = Vec::new();
+    let mut bytes = bytes.iter().copied();
+    while let Some(byte) = bytes.next() {
+        let (kind, variant) = (byte / 3, VARIANTS[(byte % 3) as usize]);
+        if kind >= KINDS {
+            return Err(format!("{byte:#04x} isn't a move"));
+        }
+
+        let mv = if kind >= WIDE_LAYER {
+            let mut layer: u32 = 0;
+            let mut shift = 0;
+            loop {
+                let byte = bytes
+                    .next()
+                    .ok_or("the layer of the last move is cut short")?;
+                if shift > 28 || (shift == 28 && byte & 0x70 != 0) {
+                    return Err(String::from("the layer of a move is too large"));
+                }
+                layer |= ((byte & 0x7f) as u32) << shift;
+                shift += 7;
+                if byte & 0x80 == 0 {
+                    break;
+                }
+            }
+            let turns = if kind >= INNER_LAYER {
+                INNER_TURNS[(kind - INNER_LAYER) as usize]
+            } else {
+                WIDE_TURNS[(kind - WIDE_LAYER) as usize]
+            };
+            turns(layer as CubeSize, variant)
+        } else if kind >= INNER_SECOND {
+            INNER_TURNS[(kind - INNER_SECOND) as usize](2, variant)
+        } else if kind >= WIDE_SECOND {
+            WIDE_TURNS[(kind - WIDE_SECOND) as usize](2, variant)
+        } else if kind >= 6 {
+            ROTATIONS[(kind - 6) as usize](variant)
+        } else {
+            FACE_TURNS[kind as usize](variant)
+        };
+        moves.push(mv);
+    }
+    Ok(moves)
+}
@@ -1,7 +1,17 @@
+use alloc::{vec, vec::Vec};
+use core::fmt::{Debug, Formatter};
+use core::hash::{Hash, Hasher};
+
 use crate::{
+    cycles::sticker_origins,
     generic_cube::{Cube, CubeSize, Face, Face::*, Move},
+    generic_puzzle::{cube_puzzle, TwistyPuzzle},
     GeoCube,
 };
+#[cfg(feature = "std")]
+use crate::random_state::{random_masked_pieces, StateMask};
+#[cfg(feature = "std")]
+use rand::Rng;
 
 use super::moves::compute_permutation;
 
@@ -14,12 +24,29 @@ use super::moves::compute_permutation;
 /// it is harder to define moves from scratch. Instead of deriving index mappings from scratch,
 /// we first implement a GeoCube move, then use our conversion function to map the move
 /// to a FaceletCube move.
-#[derive(Clone, Eq, Hash, PartialEq)]
+#[derive(Clone, Eq, PartialEq)]
 pub struct FaceletCube {
     size: CubeSize,
     faces: Vec<(Face, u16)>,
 }
 
+// Hashes the stickers only, equal cubes having equal stickers
+impl Hash for FaceletCube {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.zobrist());
+    }
+}
+
+// Shows the stickers, leaving out the pieces they came from
+impl Debug for FaceletCube {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("FaceletCube")
+            .field("size", &self.size)
+            .field("state", &self.state())
+            .finish()
+    }
+}
+
 impl Cube for FaceletCube {
     fn new(size: CubeSize) -> Self {
         Self {
@@ -44,8 +71,8 @@ impl Cube for FaceletCube {
         self.size
     }
 
-    fn state(&self) -> Vec<Face> {
-        self.faces.iter().map(|(s, _)| *s).collect()
+    fn state_iter(&self) -> impl Iterator<Item = Face> + '_ {
+        self.faces.iter().map(|(s, _)| *s)
     }
 
     fn mask(&self, mask: &dyn Fn(CubeSize, Face) -> Face) -> Self {
@@ -60,6 +87,14 @@ impl Cube for FaceletCube {
             ..*self
         }
     }
+}
+
+impl TwistyPuzzle for FaceletCube {
+    cube_puzzle!();
+
+    fn state(&self) -> Vec<Face> {
+        self.state_iter().collect()
+    }
 
     fn apply_move(&self, mv: Move) -> Self {
         Self {
@@ -69,21 +104,102 @@ impl Cube for FaceletCube {
     }
 }
 
+#[cfg(feature = "std")]
+impl FaceletCube {
+    /// A 3x3x3 cube in a random state, each state it can be turned into being as likely: the
+    /// pieces are placed and turned at random, then the last corner's twist, the last edge's
+    /// flip and the parity of the edges are fixed for the cube to be solvable.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use cubesim::prelude::*;
+    /// use cubesim::{solve, validate_state, FaceletCube};
+    ///
+    /// let cube = FaceletCube::random_state(&mut rand::thread_rng());
+    /// assert!(validate_state(&cube.state()).is_ok());
+    ///
+    /// let solution = solve(&cube).unwrap();
+    /// assert!(cube.apply_moves(&solution).is_solved());
+    /// ```
+    pub fn random_state(rng: &mut impl Rng) -> Self {
+        Self::random_masked_state(&StateMask::RANDOM, rng)
+    }
+
+    /// A 3x3x3 cube in a random state with the pieces of the mask kept solved, each state
+    /// it can be turned into that way being as likely. See ``StateMask`` for the presets.
+    pub fn random_masked_state(mask: &StateMask, rng: &mut impl Rng) -> Self {
+        Self::from(random_masked_pieces(mask, rng))
+    }
+}
+
+/// A cube with the given stickers. The stickers of a valid 3x3x3 state are traced back to
+/// where they were on the solved cube, so the cube can be solved as if it was turned from
+/// solved.
 impl From<Vec<Face>> for FaceletCube {
     fn from(faces: Vec<Face>) -> FaceletCube {
+        let origins = sticker_origins(&faces).unwrap_or_else(|_| vec![0; faces.len()]);
         FaceletCube {
-            size: ((faces.len() / 6) as f64).sqrt() as CubeSize,
-            faces: faces.iter().map(|f| (*f, 0)).collect(),
+            size: (0..)
+                .take_while(|size| size * size <= faces.len() / 6)
+                .last()
+                .unwrap_or(0) as CubeSize,
+            faces: faces.into_iter().zip(origins).collect(),
         }
     }
 }
 
+/// The same cube, masked stickers included, each sticker still known by where it was on the
+/// solved cube: a ``GeoCube`` turned from solved becomes the ``FaceletCube`` turned the same way.
 impl From<GeoCube> for FaceletCube {
     fn from(geo_cube: GeoCube) -> Self {
-        Self::from(geo_cube.state())
+        let size = geo_cube.size;
+        let mut faces = vec![(X, 0); (6 * size * size) as usize];
+        for (sticker, _) in &geo_cube.stickers {
+            let origin = GeoCube::slot_index(size, sticker.initial) as u16;
+            faces[GeoCube::slot_index(size, sticker.current)] = (sticker.face, origin);
+        }
+        FaceletCube { size, faces }
+    }
+}
+
+/// The same cube, masked stickers included, as stickers at points in space. The stickers are
+/// moved from where they were on the solved cube, unless they aren't known, such as for a
+/// ``FaceletCube`` made from the stickers of a state which isn't valid.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::{Cube, Move, MoveVariant::*, TwistyPuzzle};
+/// use cubesim::{FaceletCube, GeoCube};
+///
+/// let moves = [Move::Rw(2, Standard), Move::Ui(3, Inverse), Move::Y(Double)];
+/// let cube = FaceletCube::new(5).apply_moves(&moves);
+/// let geo_cube = GeoCube::from(cube.clone());
+/// assert!(geo_cube == GeoCube::new(5).apply_moves(&moves));
+/// assert_eq!(FaceletCube::from(geo_cube), cube);
+/// ```
+impl From<FaceletCube> for GeoCube {
+    fn from(cube: FaceletCube) -> Self {
+        let solved = GeoCube::new(cube.size);
+        // the origins are only known if each sticker comes from a different one
+        let mut seen = vec![false; cube.faces.len()];
+        let tracked = cube.faces.iter().all(|&(_, origin)| {
+            seen.get_mut(origin as usize)
+                .is_some_and(|seen| !core::mem::replace(seen, true))
+        });
+
+        let mut stickers = solved.stickers.clone();
+        for (slot, &(face, origin)) in cube.faces.iter().enumerate() {
+            let origin = if tracked { origin as usize } else { slot };
+            let (sticker, _) = &mut stickers[origin];
+            sticker.current = solved.stickers[slot].0.current;
+            sticker.face = face;
+        }
+        GeoCube { stickers, ..solved }
     }
 }
 
 fn repeat<T: Clone>(element: T, count: i32) -> Vec<T> {
-    std::iter::repeat(element).take(count as usize).collect()
+    core::iter::repeat(element).take(count as usize).collect()
 }
@@ -1,10 +1,9 @@
-use cached::proc_macro::cached;
-use lazy_static::lazy_static;
-use rustc_hash::FxHashMap;
-use std::sync::Mutex;
+use alloc::{borrow::ToOwned, vec, vec::Vec};
 
 use crate::{
+    compat::{lock, FxHashMap, Mutex},
     generic_cube::{Cube, CubeSize, Move, Move::*, MoveVariant::*},
+    generic_puzzle::TwistyPuzzle,
     geometric_cube::{GeoCube, Sticker},
 };
 use glam::IVec3;
@@ -12,12 +11,10 @@ use glam::IVec3;
 pub struct FaceletMove(pub Vec<(u16, u16)>);
 
 pub fn compute_permutation<T: Clone + Copy>(old_faces: &[T], size: CubeSize, mv: Move) -> Vec<T> {
-    lazy_static! {
-        static ref CACHE: Mutex<FxHashMap<(CubeSize, Move), FaceletMove>> =
-            Mutex::new(FxHashMap::default());
-    }
+    static CACHE: Mutex<Option<FxHashMap<(CubeSize, Move), FaceletMove>>> = Mutex::new(None);
 
-    let mut cache = CACHE.lock().unwrap();
+    let mut cache = lock(&CACHE);
+    let cache = cache.get_or_insert_with(FxHashMap::default);
 
     let facelet_move = if let Some(res) = cache.get(&(size, mv)) {
         res
@@ -49,7 +46,6 @@ fn convert_move(size: CubeSize, mv: Move) -> FaceletMove {
     )
 }
 
-#[cached]
 fn create_piece_map(size: CubeSize) -> FxHashMap<IVec3, u16> {
     let mut map = FxHashMap::default();
 
@@ -0,0 +1,200 @@
+use alloc::{format, string::String, string::ToString, vec::Vec};
+
+use crate::cycles::validate_state;
+use crate::generic_cube::{Cube, CubeSize, Face, Move, ORDERED_FACES};
+#[cfg(any(feature = "arbitrary", feature = "proptest"))]
+use crate::generic_cube::{all_moves, MoveVariant};
+#[cfg(any(feature = "arbitrary", feature = "proptest"))]
+use crate::generic_puzzle::TwistyPuzzle;
+use crate::scramble_parser::parse_scramble;
+
+/// The largest cube generated for property tests and fuzzing, and the largest cube a single
+/// ``Move`` generated is sure to apply to.
+pub const ARBITRARY_MAX_SIZE: CubeSize = 7;
+
+// Every kind of move of a cube of the size: face turns, wide turns, slices of the inner layers
+// and rotations
+#[cfg(any(feature = "arbitrary", feature = "proptest"))]
+fn candidate_moves(size: CubeSize) -> Vec<Move> {
+    use Move::*;
+    use MoveVariant::*;
+
+    let mut moves = all_moves(size);
+    for mv in [Ui, Li, Fi, Ri, Bi, Di] {
+        for variant in [Standard, Double, Inverse] {
+            for layer in 2..size {
+                moves.push(mv(layer, variant));
+            }
+        }
+    }
+    for mv in [X, Y, Z] {
+        for variant in [Standard, Double, Inverse] {
+            moves.push(mv(variant));
+        }
+    }
+    moves
+}
+
+/// Checks what holds for every state a cube can be turned into: it has ``6 * size * size``
+/// stickers, none of them masked, with as many stickers of each face. The stickers of a
+/// 3x3x3 cube must also be reachable by turning a solved cube, as ``validate_state`` checks.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::{Cube, Move, MoveVariant, TwistyPuzzle};
+/// use cubesim::{check_cube, FaceletCube};
+///
+/// let cube = FaceletCube::new(4).apply_move(Move::Rw(2, MoveVariant::Double));
+/// assert!(check_cube(&cube).is_ok());
+/// ```
+pub fn check_cube(cube: &impl Cube) -> Result<(), String> {
+    let state = cube.state();
+    let face_stickers = (cube.size() * cube.size()) as usize;
+    if state.len() != 6 * face_stickers {
+        return Err(format!(
+            "a cube of size {} has {} stickers, not {}",
+            cube.size(),
+            6 * face_stickers,
+            state.len()
+        ));
+    }
+    if state.contains(&Face::X) {
+        return Err("a sticker is masked".to_string());
+    }
+    for face in ORDERED_FACES {
+        let count = state.iter().filter(|&&sticker| sticker == face).count();
+        if count != face_stickers {
+            return Err(format!("{count} stickers are {face:?}, instead of {face_stickers}"));
+        }
+    }
+    if cube.size() == 3 {
+        validate_state(&state)?;
+    }
+    Ok(())
+}
+
+/// Checks that a solution found for a cube solves it, keeping the invariants of ``check_cube``
+/// after every move.
+pub fn check_solution(cube: &impl Cube, solution: &[Move]) -> Result<(), String> {
+    let mut turned = cube.clone();
+    for (i, mv) in solution.iter().enumerate() {
+        turned = turned.apply_move(*mv);
+        check_cube(&turned).map_err(|err| format!("after move {} ({mv}): {err}", i + 1))?;
+    }
+    if !turned.is_solved() {
+        return Err("the solution doesn't solve the cube".to_string());
+    }
+    Ok(())
+}
+
+/// Checks that moves written in WCA notation are parsed back into the same moves.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::{Move, MoveVariant::*};
+/// use cubesim::check_notation;
+///
+/// assert!(check_notation(&[Move::Rw(3, Inverse), Move::Ui(2, Double), Move::X(Standard)]).is_ok());
+/// ```
+pub fn check_notation(moves: &[Move]) -> Result<(), String> {
+    let written: Vec<String> = moves.iter().map(|mv| mv.to_string()).collect();
+    let written = written.join(" ");
+    let parsed = parse_scramble(written.clone());
+    if parsed != moves {
+        return Err(format!("{written} is parsed as {parsed:?}, not {moves:?}"));
+    }
+    Ok(())
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for MoveVariant {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(*u.choose(&[MoveVariant::Standard, MoveVariant::Double, MoveVariant::Inverse])?)
+    }
+}
+
+/// Any kind of move, turning few enough layers to apply to cubes of ``ARBITRARY_MAX_SIZE``.
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Move {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(*u.choose(&candidate_moves(ARBITRARY_MAX_SIZE))?)
+    }
+}
+
+/// A cube of any size up to ``ARBITRARY_MAX_SIZE``, turned by any moves of its size, so that
+/// its state is always valid.
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for crate::FaceletCube {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let size = u.int_in_range(2..=ARBITRARY_MAX_SIZE)?;
+        let candidates = candidate_moves(size);
+        let mut cube = crate::FaceletCube::new(size);
+        for _ in 0..u.arbitrary_len::<u8>()? {
+            cube = cube.apply_move(*u.choose(&candidates)?);
+        }
+        Ok(cube)
+    }
+}
+
+/// A move of a cube of the size, shrinking towards ``U``.
+#[cfg(feature = "proptest")]
+pub fn move_strategy(size: CubeSize) -> impl proptest::strategy::Strategy<Value = Move> {
+    proptest::sample::select(candidate_moves(size))
+}
+
+/// Moves of a cube of the size, as many as the range allows, shrinking towards fewer and
+/// simpler moves.
+#[cfg(feature = "proptest")]
+pub fn moves_strategy(
+    size: CubeSize,
+    len: impl Into<proptest::collection::SizeRange>,
+) -> impl proptest::strategy::Strategy<Value = Vec<Move>> {
+    proptest::collection::vec(move_strategy(size), len)
+}
+
+/// A cube of the size turned by up to 50 moves, its state always valid, shrinking towards
+/// the solved cube.
+#[cfg(feature = "proptest")]
+pub fn cube_strategy(size: CubeSize) -> impl proptest::strategy::Strategy<Value = crate::FaceletCube> {
+    use proptest::strategy::Strategy;
+
+    moves_strategy(size, 0..=50).prop_map(move |moves| crate::FaceletCube::new(size).apply_moves(&moves))
+}
+
+#[cfg(feature = "proptest")]
+impl proptest::arbitrary::Arbitrary for MoveVariant {
+    type Parameters = ();
+    type Strategy = proptest::sample::Select<MoveVariant>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        proptest::sample::select(&[MoveVariant::Standard, MoveVariant::Double, MoveVariant::Inverse][..])
+    }
+}
+
+/// Any kind of move, turning few enough layers to apply to cubes of ``ARBITRARY_MAX_SIZE``.
+#[cfg(feature = "proptest")]
+impl proptest::arbitrary::Arbitrary for Move {
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<Move>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        use proptest::strategy::Strategy;
+
+        move_strategy(ARBITRARY_MAX_SIZE).boxed()
+    }
+}
+
+/// A cube of any size up to ``ARBITRARY_MAX_SIZE``, as made by ``cube_strategy``.
+#[cfg(feature = "proptest")]
+impl proptest::arbitrary::Arbitrary for crate::FaceletCube {
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<crate::FaceletCube>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        use proptest::strategy::Strategy;
+
+        (2..=ARBITRARY_MAX_SIZE).prop_flat_map(cube_strategy).boxed()
+    }
+}
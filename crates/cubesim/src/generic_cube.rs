@@ -1,8 +1,14 @@
-use std::{
+use alloc::{format, string::String, string::ToString, vec, vec::Vec};
+use core::{
     fmt::{Display, Formatter},
     hash::Hash,
+    ops::Range,
 };
 
+use crate::beginner::orientations;
+use crate::generic_puzzle::TwistyPuzzle;
+use crate::scramble_parser::{parse_algorithm_for, ParseError};
+
 pub type CubeSize = i32;
 
 /// A Rubik's Cube of arbitrary size.
@@ -10,53 +16,129 @@ pub type CubeSize = i32;
 /// All implementors of this trait are (externally) immutable and persistent.
 /// Methods that involve mutating a Rubik's Cube will instead return a new
 /// Cube with the mutation applied, leaving the old Cube intact.
-pub trait Cube: Clone + Eq + Hash + PartialEq {
+///
+/// A cube is a ``TwistyPuzzle`` turned by ``Move``s, whose state is the sequence of the faces
+/// of its stickers, face after face.
+///
+/// # Examples
+///
+/// Rotate the upper layer of a solved 3x3x3 cube by 90 degrees:
+///
+/// ```rust
+/// use cubesim::prelude::{Cube, Face::*, Move, MoveVariant, TwistyPuzzle};
+/// use cubesim::FaceletCube;
+///
+/// let solved_cube = FaceletCube::new(3);
+/// assert!(solved_cube.is_solved());
+///
+/// let turned_cube = solved_cube.apply_move(Move::U(MoveVariant::Standard));
+/// assert_eq!(turned_cube.state(), vec![
+///     U, U, U, U, U, U, U, U, U,
+///     B, B, B, R, R, R, R, R, R,
+///     R, R, R, F, F, F, F, F, F,
+///     D, D, D, D, D, D, D, D, D,
+///     F, F, F, L, L, L, L, L, L,
+///     L, L, L, B, B, B, B, B, B
+/// ]);
+/// ```
+pub trait Cube: TwistyPuzzle<Move = Move, State = Vec<Face>> + PartialEq {
     /// Creates a solved cube of the given size.
     fn new(size: CubeSize) -> Self;
 
     /// The size of the cube.
     fn size(&self) -> CubeSize;
 
-    /// A one-dimensional representation of a cube as a sequence of the faces.
+    /// Iterates over the faces of ``state`` in the same order, without collecting them.
     ///
-    /// # Examples
+    /// Implementors that store their stickers directly, such as the ``FaceletCube``, borrow
+    /// them instead of allocating a new ``Vec`` on every call.
     ///
-    /// Solved 3x3x3 cube:
+    /// # Examples
     ///
     /// ```rust
-    /// use cubesim::prelude::{Cube, Face::*};
+    /// use cubesim::prelude::{Cube, Move, MoveVariant, TwistyPuzzle};
     /// use cubesim::FaceletCube;
     ///
-    /// let cube = FaceletCube::new(3);
-    /// assert_eq!(cube.state(), vec![
-    ///     U, U, U, U, U, U, U, U, U,
-    ///     R, R, R, R, R, R, R, R, R,
-    ///     F, F, F, F, F, F, F, F, F,
-    ///     D, D, D, D, D, D, D, D, D,
-    ///     L, L, L, L, L, L, L, L, L,
-    ///     B, B, B, B, B, B, B, B, B
-    /// ]);
+    /// let cube = FaceletCube::new(3).apply_move(Move::R(MoveVariant::Standard));
+    /// assert!(cube.state_iter().eq(cube.state()));
     /// ```
-    fn state(&self) -> Vec<Face>;
-
-    /// Whether a cube is solved.
-    fn is_solved(&self) -> bool {
-        fn all_equal<T: Clone + PartialEq>(arr: &[T]) -> bool {
-            arr.iter().all(|x| *x == arr[0])
-        }
+    fn state_iter(&self) -> impl Iterator<Item = Face> + '_ {
+        self.state().into_iter()
+    }
 
+    /// Iterates over the stickers of a single face, in the same order as in ``state``.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use cubesim::prelude::{Cube, Face, Move, MoveVariant, TwistyPuzzle};
+    /// use cubesim::FaceletCube;
+    ///
+    /// let cube = FaceletCube::new(2).apply_move(Move::U(MoveVariant::Standard));
+    /// assert!(cube.face_iter(Face::F).eq([Face::R, Face::R, Face::F, Face::F]));
+    /// ```
+    fn face_iter(&self, face: Face) -> impl Iterator<Item = Face> + '_ {
         let face_length = (self.size() * self.size()) as usize;
-        let state = self.state();
+        let start = sticker_index(self.size(), face, 1) as usize;
 
-        let mut is_solved = true;
-        for i in 0..6 {
-            let face_start = i * face_length;
-            let face_end = face_start + face_length;
+        self.state_iter().skip(start).take(face_length)
+    }
 
-            is_solved = is_solved && all_equal(&state[face_start..face_end]);
-        }
+    /// A 64-bit Zobrist hash of the stickers, the same for equal states and almost surely
+    /// different for different ones, computed without building the state. Solvers and sets
+    /// of cubes use it rather than hashing every sticker with its origin.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use cubesim::prelude::{Cube, Move, MoveVariant::*, TwistyPuzzle};
+    /// use cubesim::{FaceletCube, GeoCube};
+    ///
+    /// let turned = FaceletCube::new(3).apply_moves(&[Move::R(Standard), Move::U(Standard)]);
+    /// assert_ne!(turned.zobrist(), FaceletCube::new(3).zobrist());
+    /// assert_eq!(turned.zobrist(), GeoCube::new(3).apply_moves(&[Move::R(Standard), Move::U(Standard)]).zobrist());
+    /// ```
+    fn zobrist(&self) -> u64 {
+        zobrist_hash(self.state_iter())
+    }
 
-        is_solved
+    /// A Zobrist hash which is also the same for states differing only by how the cube is held
+    /// and colored: rotating the whole cube or swapping its colors keeps the hash, so that
+    /// states solved the same way are only searched once.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use cubesim::prelude::{Cube, Move, MoveVariant::*, TwistyPuzzle};
+    /// use cubesim::FaceletCube;
+    ///
+    /// let r = FaceletCube::new(3).apply_move(Move::R(Standard));
+    /// let f = FaceletCube::new(3).apply_moves(&[Move::F(Standard), Move::Y(Inverse)]);
+    /// assert_eq!(r.symmetric_zobrist(), f.symmetric_zobrist());
+    /// assert_ne!(r.symmetric_zobrist(), FaceletCube::new(3).apply_move(Move::R(Double)).symmetric_zobrist());
+    /// ```
+    fn symmetric_zobrist(&self) -> u64
+    where
+        Self: Sized,
+    {
+        orientations()
+            .map(|rotation| {
+                // the colors are named in the order they first appear in, whatever they are
+                let mut colors: Vec<Face> = Vec::with_capacity(6);
+                let rotated = self.apply_moves(&rotation);
+                zobrist_hash(rotated.state_iter().map(|face| match face {
+                    Face::X => Face::X,
+                    face => {
+                        let color = colors.iter().position(|&c| c == face).unwrap_or_else(|| {
+                            colors.push(face);
+                            colors.len() - 1
+                        });
+                        ORDERED_FACES[color]
+                    }
+                }))
+            })
+            .min()
+            .unwrap()
     }
 
     /// Replaces each piece of the cube according to the given mapping function.
@@ -68,7 +150,7 @@ pub trait Cube: Clone + Eq + Hash + PartialEq {
     /// Cross Mask
     ///
     /// ```rust
-    /// use cubesim::prelude::{Cube, Face::*, Move, MoveVariant};
+    /// use cubesim::prelude::{Cube, Face::*, Move, MoveVariant, TwistyPuzzle};
     /// use cubesim::FaceletCube;
     /// use cubesim::sticker_index;
     ///
@@ -89,80 +171,65 @@ pub trait Cube: Clone + Eq + Hash + PartialEq {
     /// ```
     fn mask(&self, mask: &dyn Fn(CubeSize, Face) -> Face) -> Self;
 
-    /// Apply a move to a cube.
+    /// Parses an algorithm in WCA notation and applies it, in one step.
     ///
-    /// # Examples
+    /// # Errors
     ///
-    /// Rotate the upper layer by 90 degrees:
-    ///
-    /// ```rust
-    /// use cubesim::prelude::{Cube, Face::*, Move, MoveVariant};
-    /// use cubesim::FaceletCube;
-    ///
-    /// let solved_cube = FaceletCube::new(3);
-    /// let turned_cube = solved_cube.apply_move(Move::U(MoveVariant::Standard));
-    /// assert_eq!(turned_cube.state(), vec![
-    ///     U, U, U, U, U, U, U, U, U,
-    ///     B, B, B, R, R, R, R, R, R,
-    ///     R, R, R, F, F, F, F, F, F,
-    ///     D, D, D, D, D, D, D, D, D,
-    ///     F, F, F, L, L, L, L, L, L,
-    ///     L, L, L, B, B, B, B, B, B
-    /// ]);
-    /// ```
-    fn apply_move(&self, mv: Move) -> Self;
-
-    /// Apply a sequence of moves to a cube.
+    /// Returns the first move that can't be parsed or turns layers deeper than the cube, as
+    /// ``parse_algorithm_for`` does, the cube being left as it was.
     ///
     /// # Examples
     ///
-    /// Rotate the upper layer by 90 degrees:
-    ///
     /// ```rust
-    /// use cubesim::prelude::{Cube, Face::*, Move, MoveVariant};
+    /// use cubesim::prelude::{Cube, Move, MoveVariant::*, TwistyPuzzle};
     /// use cubesim::FaceletCube;
     ///
-    /// let solved_cube = FaceletCube::new(3);
-    /// let turned_cube = solved_cube.apply_moves(&vec![
-    ///     Move::U(MoveVariant::Standard),
-    ///     Move::R(MoveVariant::Double),
-    ///     Move::B(MoveVariant::Inverse),
-    /// ]);
-    /// assert_eq!(turned_cube.state(), vec![
-    ///     L, L, F, U, U, D, U, U, D,
-    ///     R, R, U, R, R, U, B, B, D,
-    ///     R, R, B, F, F, B, F, F, L,
-    ///     D, D, U, D, D, U, B, R, R,
-    ///     D, F, F, D, L, L, U, L, L,
-    ///     L, B, B, L, B, B, F, F, R
-    /// ]);
+    /// let cube = FaceletCube::new(3).apply_algorithm("R U R' U'").unwrap();
+    /// assert_eq!(cube, FaceletCube::new(3).apply_moves(&[
+    ///     Move::R(Standard), Move::U(Standard), Move::R(Inverse), Move::U(Inverse)
+    /// ]));
+    /// assert!(cube.apply_algorithm(&"R U R' U' ".repeat(5)).unwrap().is_solved());
+    /// assert!(cube.apply_algorithm("R Q").is_err());
+    /// assert!(cube.apply_algorithm("4R").is_err());
     /// ```
-    fn apply_moves(&self, mvs: &[Move]) -> Self
+    fn apply_algorithm(&self, algorithm: &str) -> Result<Self, ParseError>
     where
         Self: Sized,
     {
-        let mut cube = self.clone();
-
-        for mv in mvs {
-            cube = cube.apply_move(*mv);
-        }
-
-        cube
+        Ok(self.apply_moves(&parse_algorithm_for(algorithm, self.size())?))
     }
 }
 
 use derive_more::Display;
+#[cfg(feature = "std")]
 use rand::{
     distributions::{Distribution, Standard},
     Rng,
 };
 
+// The Zobrist hash of stickers: the keys of each sticker showing its face, XORed together
+pub(crate) fn zobrist_hash(stickers: impl Iterator<Item = Face>) -> u64 {
+    stickers
+        .enumerate()
+        .fold(0, |hash, (i, face)| hash ^ zobrist_key(i, face))
+}
+
+// The random key of a sticker showing a face, mixed from both by SplitMix64 rather than kept
+// in a table, so it's the same for every size and every run
+fn zobrist_key(index: usize, face: Face) -> u64 {
+    let mut z = ((index as u64) << 3 | face as u64).wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
 /// A face of a Rubik's Cube sticker represented in WCA notation.
 ///
 /// The faces follow the standard WCA notation as described in the [WCA regulations].
 ///
 /// [WCA regulations]: worldcubeassociation.org/regulations/#article-12-notation
 #[derive(Clone, Copy, Debug, Display, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
 pub enum Face {
     /// Upper face.
     U,
@@ -200,6 +267,56 @@ pub fn sticker_index(size: CubeSize, face: Face, index: CubeSize) -> CubeSize {
         - 1 as CubeSize
 }
 
+/// The kind of piece a sticker belongs to.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+pub enum PieceType {
+    /// A piece with a single sticker, including the inner pieces of big cube faces.
+    Center,
+    /// A piece with two stickers, including the wings of big cubes.
+    Edge,
+    /// A piece with three stickers.
+    Corner,
+}
+
+/// Get the type of the piece a sticker belongs to, given the sticker's index in the state of
+/// a cube of a given size.
+///
+/// As pieces never change type, this classifies the stickers of any state, solved or not.
+///
+/// # Examples
+///
+/// The first three stickers on the front face of a 4x4x4 cube:
+///
+/// ```rust
+/// use cubesim::prelude::Face;
+/// use cubesim::{piece_type, sticker_index, PieceType};
+///
+/// assert_eq!(piece_type(4, sticker_index(4, Face::F, 1)), PieceType::Corner);
+/// assert_eq!(piece_type(4, sticker_index(4, Face::F, 2)), PieceType::Edge);
+/// assert_eq!(piece_type(4, sticker_index(4, Face::F, 6)), PieceType::Center);
+/// ```
+pub fn piece_type(size: CubeSize, index: CubeSize) -> PieceType {
+    let on_border = |i: CubeSize| i == 0 || i == size - 1;
+    let index = index % (size * size);
+
+    match (on_border(index / size), on_border(index % size)) {
+        (true, true) => PieceType::Corner,
+        (true, false) | (false, true) => PieceType::Edge,
+        (false, false) => PieceType::Center,
+    }
+}
+
+/// An axis of a cube, named after the rotation about it: ``X`` through the R and L faces,
+/// ``Y`` through U and D, and ``Z`` through F and B.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+pub enum Axis {
+    X,
+    Y,
+    Z,
+}
+
 /// A move of a NxNxN Rubik's Cube represented in WCA notation.
 ///
 /// Each Move must be tagged with a ``MoveVariant`` to completely define a move.
@@ -209,6 +326,7 @@ pub fn sticker_index(size: CubeSize, face: Face, index: CubeSize) -> CubeSize {
 /// [WCA regulations]: worldcubeassociation.org/regulations/#article-12-notation
 #[allow(dead_code)]
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
 pub enum Move {
     /// Rotate the upper layer.
     U(MoveVariant),
@@ -234,6 +352,18 @@ pub enum Move {
     Bw(CubeSize, MoveVariant),
     /// Rotate the downmost n layers.
     Dw(CubeSize, MoveVariant),
+    /// Rotate only the nth layer from the top.
+    Ui(CubeSize, MoveVariant),
+    /// Rotate only the nth layer from the left.
+    Li(CubeSize, MoveVariant),
+    /// Rotate only the nth layer from the front.
+    Fi(CubeSize, MoveVariant),
+    /// Rotate only the nth layer from the right.
+    Ri(CubeSize, MoveVariant),
+    /// Rotate only the nth layer from the back.
+    Bi(CubeSize, MoveVariant),
+    /// Rotate only the nth layer from the bottom.
+    Di(CubeSize, MoveVariant),
     /// Rotate the entire cube along the x-axis.
     X(MoveVariant),
     /// Rotate the entire cube along the y-axis.
@@ -260,7 +390,13 @@ impl Move {
             | Move::Fw(_, v)
             | Move::Rw(_, v)
             | Move::Bw(_, v)
-            | Move::Dw(_, v) => *v,
+            | Move::Dw(_, v)
+            | Move::Ui(_, v)
+            | Move::Li(_, v)
+            | Move::Fi(_, v)
+            | Move::Ri(_, v)
+            | Move::Bi(_, v)
+            | Move::Di(_, v) => *v,
         }
     }
 
@@ -279,12 +415,95 @@ impl Move {
             Move::Rw(n, _) => Move::Rw(*n, variant),
             Move::Bw(n, _) => Move::Bw(*n, variant),
             Move::Dw(n, _) => Move::Dw(*n, variant),
+            Move::Ui(n, _) => Move::Ui(*n, variant),
+            Move::Li(n, _) => Move::Li(*n, variant),
+            Move::Fi(n, _) => Move::Fi(*n, variant),
+            Move::Ri(n, _) => Move::Ri(*n, variant),
+            Move::Bi(n, _) => Move::Bi(*n, variant),
+            Move::Di(n, _) => Move::Di(*n, variant),
             Move::X(_) => Move::X(variant),
             Move::Y(_) => Move::Y(variant),
             Move::Z(_) => Move::Z(variant),
         }
     }
 
+    /// Returns the Move undoing this Move.
+    pub fn inverse(&self) -> Move {
+        match self.get_variant() {
+            MoveVariant::Standard => self.with_variant(MoveVariant::Inverse),
+            MoveVariant::Double => *self,
+            MoveVariant::Inverse => self.with_variant(MoveVariant::Standard),
+        }
+    }
+
+    /// The axis the Move turns about.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use cubesim::prelude::{Move, MoveVariant::*};
+    /// use cubesim::Axis;
+    ///
+    /// assert_eq!(Move::Dw(2, Inverse).axis(), Axis::Y);
+    /// assert_eq!(Move::X(Standard).axis(), Move::Li(2, Double).axis());
+    /// ```
+    pub fn axis(&self) -> Axis {
+        match self.base_face() {
+            Face::R | Face::L => Axis::X,
+            Face::U | Face::D => Axis::Y,
+            _ => Axis::Z,
+        }
+    }
+
+    /// The face whose layers the Move turns, clockwise as seen from that face for a
+    /// ``Standard`` move. Rotations turn like R, U and F respectively.
+    pub fn base_face(&self) -> Face {
+        match self {
+            Move::U(_) | Move::Uw(..) | Move::Ui(..) | Move::Y(_) => Face::U,
+            Move::L(_) | Move::Lw(..) | Move::Li(..) => Face::L,
+            Move::F(_) | Move::Fw(..) | Move::Fi(..) | Move::Z(_) => Face::F,
+            Move::R(_) | Move::Rw(..) | Move::Ri(..) | Move::X(_) => Face::R,
+            Move::B(_) | Move::Bw(..) | Move::Bi(..) => Face::B,
+            Move::D(_) | Move::Dw(..) | Move::Di(..) => Face::D,
+        }
+    }
+
+    /// The layers the Move turns on a cube of the given size, counted from 0 at its
+    /// ``base_face``, and only as deep as the cube. A wide or slice move of depth 0 turns no
+    /// layer.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use cubesim::prelude::{Move, MoveVariant::*};
+    ///
+    /// assert_eq!(Move::R(Standard).affected_layers(4), 0..1);
+    /// assert_eq!(Move::Rw(3, Standard).affected_layers(4), 0..3);
+    /// assert_eq!(Move::Ri(3, Standard).affected_layers(4), 2..3);
+    /// assert_eq!(Move::X(Standard).affected_layers(4), 0..4);
+    /// assert_eq!(Move::Rw(5, Standard).affected_layers(4), 0..4);
+    /// assert!(Move::Ri(0, Standard).affected_layers(4).is_empty());
+    /// ```
+    pub fn affected_layers(&self, cube_size: CubeSize) -> Range<CubeSize> {
+        let layers = match *self {
+            Move::U(_) | Move::L(_) | Move::F(_) | Move::R(_) | Move::B(_) | Move::D(_) => 0..1,
+            Move::Uw(n, _)
+            | Move::Lw(n, _)
+            | Move::Fw(n, _)
+            | Move::Rw(n, _)
+            | Move::Bw(n, _)
+            | Move::Dw(n, _) => 0..n,
+            Move::Ui(n, _)
+            | Move::Li(n, _)
+            | Move::Fi(n, _)
+            | Move::Ri(n, _)
+            | Move::Bi(n, _)
+            | Move::Di(n, _) => (n - 1)..n,
+            Move::X(_) | Move::Y(_) | Move::Z(_) => 0..cube_size,
+        };
+        layers.start.clamp(0, cube_size)..layers.end.clamp(0, cube_size)
+    }
+
     fn get_move_name(&self) -> String {
         match self {
             Move::U(_) => "U".to_string(),
@@ -335,6 +554,12 @@ impl Move {
                     format!("{n}Dw")
                 }
             }
+            Move::Ui(n, _) => format!("{n}U"),
+            Move::Li(n, _) => format!("{n}L"),
+            Move::Fi(n, _) => format!("{n}F"),
+            Move::Ri(n, _) => format!("{n}R"),
+            Move::Bi(n, _) => format!("{n}B"),
+            Move::Di(n, _) => format!("{n}D"),
             Move::X(_) => "X".to_string(),
             Move::Y(_) => "Y".to_string(),
             Move::Z(_) => "Z".to_string(),
@@ -343,7 +568,7 @@ impl Move {
 }
 
 impl Display for Move {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}{}", self.get_move_name(), self.get_variant())
     }
 }
@@ -351,6 +576,7 @@ impl Display for Move {
 /// A move variation that must be applied to the ```Move``` struct.
 #[allow(dead_code)]
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
 pub enum MoveVariant {
     /// A 90 degree clockwise turn.
     Standard = 1,
@@ -360,6 +586,7 @@ pub enum MoveVariant {
     Inverse,
 }
 
+#[cfg(feature = "std")]
 impl Distribution<MoveVariant> for Standard {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> MoveVariant {
         match rng.gen_range(1..=3) {
@@ -371,7 +598,7 @@ impl Distribution<MoveVariant> for Standard {
 }
 
 impl Display for MoveVariant {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         let s = match self {
             MoveVariant::Standard => "",
             MoveVariant::Double => "2",
@@ -0,0 +1,159 @@
+use alloc::vec::Vec;
+use core::{
+    fmt::Display,
+    hash::{Hash, Hasher},
+};
+
+#[cfg(feature = "std")]
+use rand::seq::SliceRandom;
+use rustc_hash::FxHasher;
+
+/// A twisty puzzle of any shape.
+///
+/// This trait captures what the generic solver and scrambler need to know about a puzzle:
+/// how to apply its moves and how to snapshot its state. Cubes form one family of twisty
+/// puzzles: every ``Cube`` is a ``TwistyPuzzle`` with ``Move`` as its moves and its sticker
+/// faces as its state. Other puzzles such as the ``Megaminx`` implement this trait directly.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::{Cube, Move::*, MoveVariant::*, TwistyPuzzle};
+/// use cubesim::FaceletCube;
+///
+/// let cube = FaceletCube::new(3).apply_moves(&[R(Standard), U(Double)]);
+/// assert!(!cube.is_solved());
+/// assert!(cube.apply_moves(&[U(Double), R(Inverse)]).is_solved());
+///
+/// assert!(FaceletCube::same_layer(R(Standard), R(Inverse)));
+/// assert!(!FaceletCube::same_layer(Rw(2, Standard), Rw(3, Standard)));
+/// assert!(!FaceletCube::same_layer(R(Standard), Rw(2, Standard)));
+/// ```
+pub trait TwistyPuzzle: Clone + Eq + Hash {
+    /// A single move of the puzzle.
+    type Move: Copy + Eq + Hash + Display;
+
+    /// A snapshot of the puzzle, used as the key of pruning tables.
+    type State: Clone + Eq + Hash;
+
+    /// The current state of the puzzle.
+    fn state(&self) -> Self::State;
+
+    /// A hash of the current state, the same for equal states, by which pruning tables find
+    /// it. Cubes use their ``Cube::zobrist`` hash.
+    fn state_hash(&self) -> u64 {
+        let mut hasher = FxHasher::default();
+        self.state().hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Whether the puzzle is in the given state, as ``state`` would tell.
+    fn in_state(&self, state: &Self::State) -> bool {
+        self.state() == *state
+    }
+
+    /// Whether the puzzle is solved.
+    fn is_solved(&self) -> bool;
+
+    /// Apply a move to the puzzle.
+    fn apply_move(&self, mv: Self::Move) -> Self;
+
+    /// Apply a sequence of moves to the puzzle.
+    fn apply_moves(&self, mvs: &[Self::Move]) -> Self {
+        let mut puzzle = self.clone();
+
+        for mv in mvs {
+            puzzle = puzzle.apply_move(*mv);
+        }
+
+        puzzle
+    }
+
+    /// Every move that can be applied to the puzzle.
+    fn moves(&self) -> Vec<Self::Move>;
+
+    /// The move undoing the given move.
+    fn inverse_move(mv: Self::Move) -> Self::Move;
+
+    /// Whether two moves turn the same layer, in which case performing them back to back
+    /// can always be replaced by a single move.
+    fn same_layer(a: Self::Move, b: Self::Move) -> bool;
+}
+
+// The part of ``TwistyPuzzle`` shared by every ``Cube``, its moves being the ``Move``s of its
+// size and its state the faces of its stickers, implemented in the cubes' own
+// ``impl TwistyPuzzle`` next to ``state`` and ``apply_move``
+macro_rules! cube_puzzle {
+    () => {
+        type Move = $crate::generic_cube::Move;
+        type State = alloc::vec::Vec<$crate::generic_cube::Face>;
+
+        fn state_hash(&self) -> u64 {
+            $crate::generic_cube::Cube::zobrist(self)
+        }
+
+        fn in_state(&self, state: &Self::State) -> bool {
+            $crate::generic_cube::Cube::state_iter(self).eq(state.iter().copied())
+        }
+
+        fn is_solved(&self) -> bool {
+            let size = $crate::generic_cube::Cube::size(self);
+            let face_length = (size * size) as usize;
+            let mut face_color = None;
+
+            $crate::generic_cube::Cube::state_iter(self)
+                .enumerate()
+                .all(|(i, sticker)| {
+                    if i % face_length == 0 {
+                        face_color = Some(sticker);
+                    }
+                    face_color == Some(sticker)
+                })
+        }
+
+        fn moves(&self) -> alloc::vec::Vec<Self::Move> {
+            $crate::generic_cube::all_moves($crate::generic_cube::Cube::size(self))
+        }
+
+        fn inverse_move(mv: Self::Move) -> Self::Move {
+            mv.inverse()
+        }
+
+        // the same face and depth, turned any way
+        fn same_layer(a: Self::Move, b: Self::Move) -> bool {
+            use $crate::generic_cube::MoveVariant::Standard;
+            a.with_variant(Standard) == b.with_variant(Standard)
+        }
+    };
+}
+pub(crate) use cube_puzzle;
+
+/// Generates a random sequence of moves for any puzzle, never turning the same layer twice
+/// in a row.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::{random_moves, Megaminx, TwistyPuzzle};
+///
+/// let scramble = random_moves(&Megaminx::new(), 20);
+/// assert_eq!(scramble.len(), 20);
+/// assert!(!Megaminx::new().apply_moves(&scramble).is_solved());
+/// ```
+#[cfg(feature = "std")]
+pub fn random_moves<P: TwistyPuzzle>(puzzle: &P, length: usize) -> Vec<P::Move> {
+    let mut rng = rand::thread_rng();
+    let candidates = puzzle.moves();
+    let mut scramble: Vec<P::Move> = Vec::with_capacity(length);
+
+    while scramble.len() < length {
+        let mv = *candidates.choose(&mut rng).unwrap();
+
+        match scramble.last() {
+            Some(last) if P::same_layer(*last, mv) => continue,
+            _ => scramble.push(mv),
+        }
+    }
+
+    scramble
+}
@@ -1,8 +1,12 @@
-use rustc_hash::FxHashMap;
-use std::mem::discriminant;
+use hashbrown::HashTable;
+use std::hash::Hash;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
 
-use crate::generic_cube::{Cube, Face, Move};
+use crate::generic_cube::{zobrist_hash, Face, Move};
+use crate::generic_puzzle::TwistyPuzzle;
 use crate::facelet_cube::FaceletCube;
+use crate::instrument::record_search;
 
 /// A combination of a Pruning Table and the candidate moves to solve into a specific state.
 /// 
@@ -25,9 +29,15 @@ use crate::facelet_cube::FaceletCube;
 /// ];
 /// 
 /// let solver = Solver::new(moves.clone(), PruningTable::new(&[FaceletCube::new(3)], 6, &moves));
-pub struct Solver {
-    pub candidate_moves: Vec<Move>,
-    pub pruning_table: PruningTable
+/// ```
+///
+/// The solver is generic over the move and state types of any ``TwistyPuzzle``, defaulting to
+/// those shared by all cubes.
+pub struct Solver<M = Move, S = Vec<Face>> {
+    pub candidate_moves: Vec<M>,
+    pub pruning_table: PruningTable<S>,
+    transposition_bytes: usize,
+    node_limit: Option<u64>
 }
 
 #[derive(Clone)]
@@ -37,12 +47,13 @@ pub struct Solver {
 /// the iterative deepening depth first search. If at any point during a search we reach a state 
 /// that would take too many moves to solve, we can abandon this search branch, greatly reducing
 /// our search space.
-pub struct PruningTable {
-    pruning_table: FxHashMap<Vec<Face>, i32>,
+pub struct PruningTable<S = Vec<Face>> {
+    // each state with its hash, by which it's found, and the moves to solve it
+    pruning_table: HashTable<(u64, S, i32)>,
     depth: i32 
 }
 
-impl PruningTable {
+impl<S: Clone + Eq + Hash> PruningTable<S> {
     /// Constructs a Pruning Table given the set of starting cubes, pruning depth and allowable moves.
     /// 
     /// # Examples
@@ -61,12 +72,15 @@ impl PruningTable {
     /// 
     /// let pruning_table = PruningTable::new(&[FaceletCube::new(3)], 6, &moves);
     /// ```
-    pub fn new(starting_cubes: &[impl Cube], depth: i32, moveset: &[Move]) -> Self {
-        let mut pruning_table: FxHashMap<Vec<Face>, i32> = FxHashMap::default();
+    pub fn new<P: TwistyPuzzle<State = S>>(starting_cubes: &[P], depth: i32, moveset: &[P::Move]) -> Self {
+        let mut table = Self {
+            pruning_table: HashTable::new(),
+            depth
+        };
         let mut previous_frontier = starting_cubes.to_vec();
     
         for cube in starting_cubes {
-            pruning_table.insert(cube.state(), 0);
+            table.insert(cube, 0);
         }
     
         for i in 1..=depth {
@@ -75,8 +89,7 @@ impl PruningTable {
             for cube in previous_frontier {
                 for mv in moveset {
                     let new_cube = cube.apply_move(*mv);
-                    if let std::collections::hash_map::Entry::Vacant(e) = pruning_table.entry(new_cube.state()) {
-                        e.insert(i);
+                    if table.insert(&new_cube, i) {
                         frontier.push(new_cube);
                     }
                 }
@@ -85,92 +98,652 @@ impl PruningTable {
             previous_frontier = frontier.clone();
         } 
     
-        Self {
-            pruning_table,
-            depth
+        table
+    }
+
+    // Adds the puzzle's state, unless it's already there
+    fn insert<P: TwistyPuzzle<State = S>>(&mut self, puzzle: &P, moves: i32) -> bool {
+        let hash = puzzle.state_hash();
+        if self.pruning_table.find(hash, |(_, state, _)| puzzle.in_state(state)).is_some() {
+            return false;
         }
+        self.pruning_table.insert_unique(hash, (hash, puzzle.state(), moves), |&(hash, _, _)| hash);
+        true
+    }
+
+    fn get<P: TwistyPuzzle<State = S>>(&self, puzzle: &P) -> Option<i32> {
+        self.get_hashed(puzzle.state_hash(), puzzle)
     }
 
+    fn get_hashed<P: TwistyPuzzle<State = S>>(&self, hash: u64, puzzle: &P) -> Option<i32> {
+        self.pruning_table
+            .find(hash, |(_, state, _)| puzzle.in_state(state))
+            .map(|&(_, _, moves)| moves)
+    }
+}
+
+impl PruningTable {
     /// Constructs a Pruning Table using all the states in an existing Pruning Table as start states.
     pub fn from_existing_table(other: &PruningTable, depth: i32, moveset: &[Move]) -> Self {
         Self::new(
-            &other.pruning_table.keys().map(|faces| FaceletCube::from(faces.clone())).collect::<Vec<_>>(),
+            &other.pruning_table.iter().map(|(_, faces, _)| FaceletCube::from(faces.clone())).collect::<Vec<_>>(),
             depth,
             moveset
         )
     }
 
-    fn get(&self, k: &[Face]) -> Option<&i32> {
-        self.pruning_table.get(k)
+    /// Writes the Pruning Table as bytes, as read by ``PruningTable::read``: its depth, the
+    /// number of states and the stickers of each, then a byte for each sticker and one for
+    /// the moves to solve the state. The states are sorted, so the same table is always
+    /// written the same way.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use cubesim::prelude::{Cube, Move, MoveVariant::*};
+    /// use cubesim::{FaceletCube, PruningTable};
+    ///
+    /// let moves = vec![Move::U(Double), Move::D(Double)];
+    /// let pruning_table = PruningTable::new(&[FaceletCube::new(3)], 2, &moves);
+    ///
+    /// let mut bytes = Vec::new();
+    /// pruning_table.write(&mut bytes);
+    /// let read = PruningTable::read(&mut bytes.as_slice()).unwrap();
+    /// assert_eq!(read.len(), 4);
+    /// ```
+    pub fn write(&self, out: &mut Vec<u8>) {
+        let mut entries: Vec<(&Vec<Face>, &i32)> = self.pruning_table.iter().map(|(_, state, moves)| (state, moves)).collect();
+        entries.sort_by_key(|(state, _)| state.iter().map(|&face| face_byte(face)).collect::<Vec<_>>());
+        let stickers = entries.first().map_or(0, |(state, _)| state.len());
+
+        out.push(self.depth as u8);
+        out.extend((entries.len() as u32).to_le_bytes());
+        out.extend((stickers as u16).to_le_bytes());
+        for (state, moves) in entries {
+            out.extend(state.iter().map(|&face| face_byte(face)));
+            out.push(*moves as u8);
+        }
+    }
+
+    /// Reads a Pruning Table written by ``PruningTable::write`` from the start of the bytes,
+    /// leaving the bytes after it.
+    pub fn read(bytes: &mut &[u8]) -> Result<Self, String> {
+        let depth = take(bytes, 1)?[0] as i32;
+        let len = u32::from_le_bytes(take(bytes, 4)?.try_into().unwrap()) as usize;
+        let stickers = u16::from_le_bytes(take(bytes, 2)?.try_into().unwrap()) as usize;
+
+        let mut pruning_table = HashTable::with_capacity(len.min(bytes.len() / (stickers + 1)));
+        for _ in 0..len {
+            let state = take(bytes, stickers)?
+                .iter()
+                .map(|&byte| byte_face(byte))
+                .collect::<Result<Vec<_>, _>>()?;
+            let moves = take(bytes, 1)?[0] as i32;
+            if moves > depth {
+                return Err(format!("a state {moves} moves deep in a table {depth} moves deep"));
+            }
+            // the hash of the cubes the table is for, as their ``state_hash``
+            let hash = zobrist_hash(state.iter().copied());
+            pruning_table.insert_unique(hash, (hash, state, moves), |&(hash, _, _)| hash);
+        }
+        Ok(Self {
+            pruning_table,
+            depth
+        })
     }
 }
 
-impl Solver {
-    pub fn new(candidate_moves: Vec<Move>, pruning_table: PruningTable) -> Self {
+impl<S> PruningTable<S> {
+    /// The number of states of the Pruning Table.
+    pub fn len(&self) -> usize {
+        self.pruning_table.len()
+    }
+
+    /// Whether the Pruning Table has no states, not even a solved one.
+    pub fn is_empty(&self) -> bool {
+        self.pruning_table.is_empty()
+    }
+}
+
+const FACES: [Face; 7] = [Face::U, Face::L, Face::F, Face::R, Face::B, Face::D, Face::X];
+
+fn face_byte(face: Face) -> u8 {
+    FACES.iter().position(|&f| f == face).unwrap() as u8
+}
+
+fn byte_face(byte: u8) -> Result<Face, String> {
+    FACES
+        .get(byte as usize)
+        .copied()
+        .ok_or_else(|| format!("no face written as {byte}"))
+}
+
+// Takes the next bytes of a table
+fn take<'a>(bytes: &mut &'a [u8], len: usize) -> Result<&'a [u8], String> {
+    if bytes.len() < len {
+        return Err("the table is cut short".to_string());
+    }
+    let (taken, rest) = bytes.split_at(len);
+    *bytes = rest;
+    Ok(taken)
+}
+
+impl<M, S: Clone + Eq + Hash> Solver<M, S> {
+    pub fn new(candidate_moves: Vec<M>, pruning_table: PruningTable<S>) -> Self {
         Self {
             candidate_moves,
-            pruning_table
+            pruning_table,
+            transposition_bytes: TRANSPOSITION_TABLE_BYTES,
+            node_limit: None
+        }
+    }
+
+    /// Caps the memory of the transposition table of each search at ``bytes``, or searches
+    /// without one given 0.
+    ///
+    /// The table remembers the states a search gave up on, with how many moves were left, so
+    /// that the same state reached by other moves isn't searched again with no more moves left.
+    /// Once full, newer states take the place of older ones. States are told apart by their
+    /// ``TwistyPuzzle::state_hash``, and it takes two moves of the same layer in a row to turn
+    /// it like one of the candidate moves or not at all, as with face turns, for the search to
+    /// find the same solutions with the table as without.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use cubesim::prelude::{Cube, Move, MoveVariant::*, TwistyPuzzle};
+    /// use cubesim::{ida_star, FaceletCube, PruningTable, Solver};
+    ///
+    /// let moves = vec![Move::U(Double), Move::R(Double), Move::F(Double)];
+    /// let table = PruningTable::new(&[FaceletCube::new(3)], 1, &moves);
+    /// let solver = Solver::new(moves.clone(), table).with_transposition_table(1 << 16);
+    /// let cube = FaceletCube::new(3).apply_moves(&moves);
+    /// assert_eq!(ida_star(&cube, &solver, 5).unwrap().len(), 3);
+    /// ```
+    pub fn with_transposition_table(self, bytes: usize) -> Self {
+        Self {
+            transposition_bytes: bytes,
+            ..self
+        }
+    }
+
+    /// Gives up each search once it visited ``nodes`` states, at every depth, finding no
+    /// solution rather than searching on for as long as it takes. Each thread of a parallel
+    /// search visits up to as many.
+    ///
+    /// Deep solutions can take a search with a shallow pruning table far longer than anyone
+    /// would wait, which the states visited tell, as reported by ``ida_star_observed``.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use cubesim::prelude::{Cube, Move, MoveVariant::*, TwistyPuzzle};
+    /// use cubesim::{all_moves, ida_star, FaceletCube, PruningTable, Solver};
+    ///
+    /// let moves = all_moves(3)[..18].to_vec();
+    /// let table = PruningTable::new(&[FaceletCube::new(3)], 1, &moves);
+    /// let cube = FaceletCube::new(3).apply_moves(&[
+    ///     Move::R(Standard), Move::U(Double), Move::F(Inverse), Move::L(Standard)
+    /// ]);
+    ///
+    /// let solver = Solver::new(moves.clone(), table.clone());
+    /// assert_eq!(ida_star(&cube, &solver, 4).unwrap().len(), 4);
+    /// assert_eq!(ida_star(&cube, &solver.with_node_limit(100), 4), None);
+    /// ```
+    pub fn with_node_limit(self, nodes: u64) -> Self {
+        Self {
+            node_limit: Some(nodes),
+            ..self
         }
     }
 
-    pub fn is_solved(&self, cube: &impl Cube) -> bool {
-        matches!(self.pruning_table.get(&cube.state()), Some(0))
+    pub fn is_solved(&self, cube: &impl TwistyPuzzle<State = S>) -> bool {
+        matches!(self.pruning_table.get(cube), Some(0))
     }
 
-    pub fn lower_bound(&self, cube: &impl Cube) -> i32 {
-        match self.pruning_table.get(&cube.state()) {
-            Some(n) => *n,
+    pub fn lower_bound(&self, cube: &impl TwistyPuzzle<State = S>) -> i32 {
+        match self.pruning_table.get(cube) {
+            Some(n) => n,
             _ => self.pruning_table.depth + 1
         }
     }
+
+    // Follows the candidate moves down the pruning table from a state ``moves_left`` moves from
+    // solved, after the moves leading to it
+    fn descend<P: TwistyPuzzle<Move = M, State = S>>(&self, puzzle: &P, moves_left: i32, mut moves: Vec<M>) -> Option<Vec<M>>
+    where
+        M: Copy {
+        let mut puzzle = puzzle.clone();
+        for moves_left in (0..moves_left).rev() {
+            let mv = *self
+                .candidate_moves
+                .iter()
+                .find(|&&mv| self.pruning_table.get(&puzzle.apply_move(mv)) == Some(moves_left))?;
+            puzzle = puzzle.apply_move(mv);
+            moves.push(mv);
+        }
+        Some(moves)
+    }
+}
+
+/// How many states an IDA* search visits between two reports of its progress.
+pub const SEARCH_REPORT_INTERVAL: u64 = 4096;
+
+/// The memory of the transposition table of each IDA* search, unless set otherwise by
+/// ``Solver::with_transposition_table``.
+pub const TRANSPOSITION_TABLE_BYTES: usize = 1 << 20;
+
+// The states a search gave up on by their hash, each with the moves it had left plus one, or 0
+// for an empty slot. A state only ever takes the slot its hash points to, replacing whatever
+// was there.
+struct TranspositionTable {
+    slots: Vec<(u64, u32)>
 }
 
-pub fn ida_star(cube: &impl Cube, 
-         solver: &Solver,
-         limit: i32) -> Option<Vec<Move>> {
+impl TranspositionTable {
+    fn new(bytes: usize) -> Self {
+        Self {
+            slots: vec![(0, 0); bytes / std::mem::size_of::<(u64, u32)>()]
+        }
+    }
+
+    // Whether the state was already given up on with as many moves left, remembering it
+    // otherwise
+    fn visit(&mut self, hash: u64, moves_left: i32) -> bool {
+        if self.slots.is_empty() {
+            return false;
+        }
+        let len = self.slots.len() as u64;
+        let slot = &mut self.slots[(hash % len) as usize];
+        let moves = moves_left.max(0) as u32 + 1;
+        if slot.0 == hash && slot.1 >= moves {
+            return true;
+        }
+        *slot = (hash, moves);
+        false
+    }
+}
+
+/// How far an IDA* search got, as reported by ``ida_star_observed``.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SearchProgress<M> {
+    /// The number of moves searched, no shorter solution having been found.
+    pub depth: i32,
+    /// The states visited so far, at every depth.
+    pub nodes: u64,
+    /// The moves leading to the state visited last.
+    pub moves: Vec<M>,
+    /// The moves leading to the state closest to solved visited so far, by the pruning table.
+    pub best: Vec<M>,
+    /// The lower bound of the moves left to solve after ``best``.
+    pub best_bound: i32,
+}
+
+/// Searches for a sequence of at most ``limit`` moves bringing a puzzle into a state the
+/// solver's pruning table considers solved, using iterative deepening A*.
+pub fn ida_star<P: TwistyPuzzle>(cube: &P, 
+         solver: &Solver<P::Move, P::State>,
+         limit: i32) -> Option<Vec<P::Move>> {
+    ida_star_observed(cube, solver, limit, &mut |_| ())
+}
+
+/// Searches like ``ida_star``, reporting its progress to ``observe`` as each depth starts,
+/// every ``SEARCH_REPORT_INTERVAL`` states visited and once a solution is found, to show how
+/// the search goes.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::{Cube, Move, MoveVariant::*, TwistyPuzzle};
+/// use cubesim::{ida_star_observed, FaceletCube, PruningTable, Solver};
+///
+/// let moves = vec![Move::U(Double), Move::R(Double), Move::F(Double)];
+/// let solver = Solver::new(moves.clone(), PruningTable::new(&[FaceletCube::new(3)], 1, &moves));
+/// let cube = FaceletCube::new(3).apply_moves(&moves);
+///
+/// let mut reports = Vec::new();
+/// let solution = ida_star_observed(&cube, &solver, 5, &mut |progress| reports.push(progress.clone()));
+/// assert_eq!(solution.unwrap().len(), 3);
+/// let last = reports.last().unwrap();
+/// assert_eq!((last.depth, last.best_bound, last.best.len()), (3, 0, 3));
+/// ```
+pub fn ida_star_observed<P: TwistyPuzzle>(cube: &P,
+         solver: &Solver<P::Move, P::State>,
+         limit: i32,
+         observe: &mut dyn FnMut(&SearchProgress<P::Move>)) -> Option<Vec<P::Move>> {
+    let mut search = Search::new(solver, observe, solver.lower_bound(cube));
+
+    let mut solution = None;
     for i in 0..=limit {
-        if let Some(sol) = dfs(cube, solver, &mut vec![], i) {
-            return Some(sol);
+        search.progress.depth = i;
+        (search.observe)(&search.progress);
+        if let Some(sol) = search.dfs(cube, i) {
+            search.progress.moves = sol.clone();
+            search.progress.best = sol.clone();
+            search.progress.best_bound = 0;
+            (search.observe)(&search.progress);
+            solution = Some(sol);
+            break;
         }
     }
 
-    None
+    search.record();
+    solution
 }
 
-fn dfs(cube: &impl Cube, 
-       solver: &Solver, 
-       solution: &mut Vec<Move>,
-       depth_remaining: i32) -> Option<Vec<Move>> {
-    if solver.is_solved(cube) {
-        return Some(solution.to_vec());
+/// Searches like ``ida_star``, sharing the moves out of the puzzle's state between ``threads``
+/// threads at each depth, to find the same solution sooner on several cores. With one thread
+/// or none, it searches on the calling thread alone.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::{Cube, Move, MoveVariant::*, TwistyPuzzle};
+/// use cubesim::{all_moves, ida_star, ida_star_parallel, FaceletCube, PruningTable, Solver};
+///
+/// let moves = all_moves(3);
+/// let solver = Solver::new(moves.clone(), PruningTable::new(&[FaceletCube::new(3)], 2, &moves));
+/// let cube = FaceletCube::new(3).apply_moves(&[Move::R(Standard), Move::U(Double), Move::F(Inverse)]);
+///
+/// let solution = ida_star_parallel(&cube, &solver, 5, 4);
+/// assert_eq!(solution, ida_star(&cube, &solver, 5));
+/// assert!(cube.apply_moves(&solution.unwrap()).is_solved());
+/// ```
+pub fn ida_star_parallel<P>(cube: &P,
+         solver: &Solver<P::Move, P::State>,
+         limit: i32,
+         threads: usize) -> Option<Vec<P::Move>>
+where
+    P: TwistyPuzzle + Sync,
+    P::Move: Send + Sync,
+    P::State: Sync {
+    ida_star_parallel_observed(cube, solver, limit, threads, &mut |_| ())
+}
+
+/// Searches like ``ida_star_parallel``, reporting its progress to ``observe`` as each depth
+/// starts and once a solution is found, as ``ida_star_observed`` does. The threads report
+/// nothing while they search, so the states visited are only counted once each depth is over.
+pub fn ida_star_parallel_observed<P>(cube: &P,
+         solver: &Solver<P::Move, P::State>,
+         limit: i32,
+         threads: usize,
+         observe: &mut dyn FnMut(&SearchProgress<P::Move>)) -> Option<Vec<P::Move>>
+where
+    P: TwistyPuzzle + Sync,
+    P::Move: Send + Sync,
+    P::State: Sync {
+    if threads <= 1 {
+        return ida_star_observed(cube, solver, limit, observe);
     }
 
-    if solver.lower_bound(cube) > depth_remaining {
-        return None;
+    let bound = solver.lower_bound(cube);
+    let mut progress = SearchProgress {
+        depth: 0,
+        nodes: 0,
+        moves: vec![],
+        best: vec![],
+        best_bound: bound
+    };
+
+    for depth in 0..=limit {
+        progress.depth = depth;
+        progress.nodes += 1;
+        observe(&progress);
+        if solver.is_solved(cube) {
+            progress.best_bound = 0;
+            observe(&progress);
+            return Some(vec![]);
+        }
+        if bound > depth {
+            continue;
+        }
+
+        // the moves out of the puzzle's state are taken in turn by the threads, each
+        // searching on until an earlier move solves
+        let next = AtomicUsize::new(0);
+        let first_solved = AtomicUsize::new(usize::MAX);
+        let searches: Vec<_> = thread::scope(|scope| {
+            let handles: Vec<_> = (0..threads)
+                .map(|_| scope.spawn(|| {
+                    let mut observe = |_: &SearchProgress<P::Move>| ();
+                    let mut search = Search::new(solver, &mut observe, bound);
+                    search.first_solved = Some(&first_solved);
+                    let mut solution = None;
+
+                    loop {
+                        let branch = next.fetch_add(1, Ordering::Relaxed);
+                        if branch >= solver.candidate_moves.len() || branch > first_solved.load(Ordering::Relaxed) {
+                            break;
+                        }
+                        let mv = solver.candidate_moves[branch];
+                        search.branch = branch;
+                        search.progress.moves.push(mv);
+                        let result = search.dfs(&cube.apply_move(mv), depth - 1);
+                        search.progress.moves.pop();
+
+                        if let Some(result) = result {
+                            first_solved.fetch_min(branch, Ordering::Relaxed);
+                            solution = Some((branch, result));
+                            break;
+                        }
+                    }
+
+                    search.record();
+                    (solution, search.progress)
+                }))
+                .collect();
+            handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+        });
+
+        let mut solution = None;
+        for (found, searched) in searches {
+            progress.nodes += searched.nodes;
+            if searched.best_bound < progress.best_bound {
+                progress.best = searched.best;
+                progress.best_bound = searched.best_bound;
+            }
+            solution = solution.into_iter().chain(found).min_by_key(|(branch, _)| *branch);
+        }
+        if let Some((_, solution)) = solution {
+            progress.moves = solution.clone();
+            progress.best = solution.clone();
+            progress.best_bound = 0;
+            observe(&progress);
+            return Some(solution);
+        }
     }
 
-    for mv in &solver.candidate_moves {
-        if let Some(last_mv) = solution.last() {
-            if discriminant(last_mv) == discriminant(mv) {
-                continue;
+    None
+}
+
+/// Searches for a shortest sequence of at most ``limit`` moves solving a puzzle from both
+/// ends: breadth first from the puzzle, one depth at a time, until a state is found in the
+/// solver's pruning table, which is the search from the solved states, then down the table
+/// to a solved state.
+///
+/// It needs far fewer moves searched than ``ida_star`` for short solutions, such as those of
+/// the phases of a solver, but keeps every state of the depth searched from the puzzle, so
+/// its memory grows with the moves left past the depth of the table. The moves of the
+/// solver must undo each other, as the table is searched down by them.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::{Cube, Move, MoveVariant::*, TwistyPuzzle};
+/// use cubesim::{all_moves, bidirectional_search, FaceletCube, PruningTable, Solver};
+///
+/// let moves = all_moves(3);
+/// let solver = Solver::new(moves.clone(), PruningTable::new(&[FaceletCube::new(3)], 3, &moves));
+/// let cube = FaceletCube::new(3).apply_moves(&[
+///     Move::R(Standard), Move::U(Double), Move::F(Inverse), Move::L(Standard), Move::D(Double)
+/// ]);
+///
+/// let solution = bidirectional_search(&cube, &solver, 6).unwrap();
+/// assert_eq!(solution.len(), 5);
+/// assert!(cube.apply_moves(&solution).is_solved());
+/// assert_eq!(bidirectional_search(&cube, &solver, 4), None);
+/// ```
+pub fn bidirectional_search<P: TwistyPuzzle>(cube: &P,
+         solver: &Solver<P::Move, P::State>,
+         limit: i32) -> Option<Vec<P::Move>> {
+    let table = &solver.pruning_table;
+    let (mut nodes, mut hits, mut misses) = (0, 0, 0);
+    let mut visited = HashTable::new();
+    visited.insert_unique(cube.state_hash(), (cube.state_hash(), cube.state()), |&(hash, _)| hash);
+    let mut frontier: Vec<(P, Vec<P::Move>)> = vec![(cube.clone(), vec![])];
+    let mut depth = 0;
+
+    let solution = loop {
+        // the states of the depth found in the table, those closest to solved first
+        let mut found: Vec<(i32, usize)> = frontier
+            .iter()
+            .enumerate()
+            .filter_map(|(i, (puzzle, _))| {
+                nodes += 1;
+                let moves_left = table.get(puzzle);
+                if moves_left.is_some() { hits += 1 } else { misses += 1 }
+                moves_left.filter(|&moves_left| depth + moves_left <= limit).map(|moves_left| (moves_left, i))
+            })
+            .collect();
+        found.sort();
+        if let Some(solution) = found.into_iter().find_map(|(moves_left, i)| {
+            let (puzzle, moves) = &frontier[i];
+            solver.descend(puzzle, moves_left, moves.clone())
+        }) {
+            break Some(solution);
+        }
+
+        if depth + table.depth + 1 > limit {
+            break None;
+        }
+        depth += 1;
+
+        let mut next = vec![];
+        for (puzzle, moves) in &frontier {
+            for mv in &solver.candidate_moves {
+                if moves.last().is_some_and(|last| P::same_layer(*last, *mv)) {
+                    continue;
+                }
+                let turned = puzzle.apply_move(*mv);
+                let hash = turned.state_hash();
+                if visited.find(hash, |(_, state)| turned.in_state(state)).is_some() {
+                    continue;
+                }
+                visited.insert_unique(hash, (hash, turned.state()), |&(hash, _)| hash);
+                let mut moves = moves.clone();
+                moves.push(*mv);
+                next.push((turned, moves));
             }
         }
+        frontier = next;
+    };
 
-        solution.push(*mv);
+    record_search(nodes, hits, misses, 0);
+    solution
+}
 
-        let result = dfs(
-            &cube.apply_move(*mv),
+struct Search<'a, M, S> {
+    solver: &'a Solver<M, S>,
+    observe: &'a mut dyn FnMut(&SearchProgress<M>),
+    progress: SearchProgress<M>,
+    // the states visited found in the pruning table, or deeper than it
+    table_hits: u64,
+    table_misses: u64,
+    // kept through every depth, the states given up on staying so with fewer moves left
+    transpositions: TranspositionTable,
+    transposition_hits: u64,
+    // in a parallel search, the move out of the puzzle's state searched by this thread and the
+    // first of those moves found to solve, after which the later ones are given up
+    branch: usize,
+    first_solved: Option<&'a AtomicUsize>
+}
+
+impl<'a, M: Copy, S: Clone + Eq + Hash> Search<'a, M, S> {
+    fn new(solver: &'a Solver<M, S>, observe: &'a mut dyn FnMut(&SearchProgress<M>), bound: i32) -> Self {
+        Search {
             solver,
-            solution,
-            depth_remaining - 1
-        );
+            observe,
+            progress: SearchProgress {
+                depth: 0,
+                nodes: 0,
+                moves: vec![],
+                best: vec![],
+                best_bound: bound
+            },
+            table_hits: 0,
+            table_misses: 0,
+            transpositions: TranspositionTable::new(solver.transposition_bytes),
+            transposition_hits: 0,
+            branch: 0,
+            first_solved: None
+        }
+    }
 
-        solution.pop();
+    // Records the work of the search, once it's over
+    fn record(&self) {
+        record_search(self.progress.nodes, self.table_hits, self.table_misses, self.transposition_hits);
+    }
 
-        if result.is_some() {
-            return result;
+    fn dfs<P: TwistyPuzzle<Move = M, State = S>>(&mut self, cube: &P, depth_remaining: i32) -> Option<Vec<M>> {
+        if self.first_solved.is_some_and(|first| first.load(Ordering::Relaxed) < self.branch) {
+            return None;
+        }
+        if self.solver.node_limit.is_some_and(|limit| self.progress.nodes >= limit) {
+            return None;
         }
-    }
 
-    None
-}
\ No newline at end of file
+        self.progress.nodes += 1;
+        let hash = cube.state_hash();
+        let entry = self.solver.pruning_table.get_hashed(hash, cube);
+        let bound = match entry {
+            Some(n) => {
+                self.table_hits += 1;
+                n
+            }
+            None => {
+                self.table_misses += 1;
+                self.solver.pruning_table.depth + 1
+            }
+        };
+        if bound < self.progress.best_bound {
+            self.progress.best = self.progress.moves.clone();
+            self.progress.best_bound = bound;
+        }
+        if self.progress.nodes.is_multiple_of(SEARCH_REPORT_INTERVAL) {
+            (self.observe)(&self.progress);
+        }
+
+        if entry == Some(0) {
+            return Some(self.progress.moves.to_vec());
+        }
+
+        if bound > depth_remaining {
+            return None;
+        }
+
+        if self.transpositions.visit(hash, depth_remaining) {
+            self.transposition_hits += 1;
+            return None;
+        }
+
+        for mv in &self.solver.candidate_moves {
+            if let Some(last_mv) = self.progress.moves.last() {
+                if P::same_layer(*last_mv, *mv) {
+                    continue;
+                }
+            }
+
+            self.progress.moves.push(*mv);
+
+            let result = self.dfs(&cube.apply_move(*mv), depth_remaining - 1);
+
+            self.progress.moves.pop();
+
+            if result.is_some() {
+                return result;
+            }
+        }
+
+        None
+    }
+}
@@ -1,30 +1,32 @@
-use lazy_static::lazy_static;
+use alloc::{vec, vec::Vec};
+use core::hash::{Hash, Hasher};
 
-use crate::generic_cube::{Cube, CubeSize, Face, Move, Move::*, MoveVariant::*};
+use glam::IVec3;
+
+use crate::generic_cube::{Cube, CubeSize, Face, Move, Move::*, MoveVariant::*, ORDERED_FACES};
+use crate::generic_puzzle::{cube_puzzle, TwistyPuzzle};
 
 use super::{moves::GeometricMove, sticker::Sticker};
 
-lazy_static! {
-    static ref FACE_ROTATING_MOVES: Vec<Vec<Move>> = vec![
-        vec![],
-        vec![Y(Standard), X(Standard)],
-        vec![X(Standard)],
-        vec![X(Double)],
-        vec![Y(Inverse), X(Standard)],
-        vec![Y(Double), X(Standard)],
-    ];
-}
+const FACE_ROTATING_MOVES: [&[Move]; 6] = [
+    &[],
+    &[Y(Standard), X(Standard)],
+    &[X(Standard)],
+    &[X(Double)],
+    &[Y(Inverse), X(Standard)],
+    &[Y(Double), X(Standard)],
+];
 
 /// A Rubik's Cube with each of its facelets represented as a Sticker.
 ///
 /// Each move is implemented as a rotation matrix and hence a move is applied
 /// by multiplying each of the relevant vectors with the matrix.
 ///
-/// This implementation of a Rubik's Cube is very programmatic but suffers
-/// from poor performance due to the expensive nature of matrix multiplication.
-/// This implementation should not be used directly, instead it should only be
-/// used to bootstrap more efficient implementations like the FaceletCube.
-#[derive(Clone, Eq, Hash, PartialEq)]
+/// Sticker coordinates are integers and moves are exact quarter turn permutations of
+/// them, so no floating point error can build up. Each move still touches every sticker,
+/// so the FaceletCube remains faster for searching; the GeoCube is mostly used to
+/// bootstrap its moves.
+#[derive(Clone, Eq, PartialEq)]
 pub struct GeoCube {
     pub(crate) size: CubeSize,
     pub(crate) stickers: Vec<(Sticker, CubeSize)>,
@@ -42,21 +44,6 @@ impl Cube for GeoCube {
         self.size
     }
 
-    fn state(&self) -> Vec<Face> {
-        let mut faces = Vec::new();
-
-        for mvs in FACE_ROTATING_MOVES.iter() {
-            let rotated_cube = self.apply_moves(mvs);
-            let top_layer_stickers = rotated_cube.top_layer_stickers();
-
-            for (sticker, _) in top_layer_stickers {
-                faces.push(sticker.initial_face());
-            }
-        }
-
-        faces
-    }
-
     fn mask(&self, mask: &dyn Fn(CubeSize, Face) -> Face) -> Self {
         let masked_stickers = self
             .stickers
@@ -77,19 +64,42 @@ impl Cube for GeoCube {
             ..*self
         }
     }
+}
+
+impl TwistyPuzzle for GeoCube {
+    cube_puzzle!();
+
+    fn state(&self) -> Vec<Face> {
+        let mut faces = vec![Face::X; (6 * self.size * self.size) as usize];
+
+        for (sticker, _) in &self.stickers {
+            faces[Self::slot_index(self.size, sticker.current)] = sticker.initial_face();
+        }
+
+        faces
+    }
 
     fn apply_move(&self, mv: Move) -> Self {
+        let mv = GeometricMove::from(mv);
+
         Self {
             stickers: self
                 .stickers
                 .iter()
-                .map(|(s, i)| (s.rotate(GeometricMove::from(mv)), *i))
+                .map(|(s, i)| (s.rotate(mv), *i))
                 .collect(),
             ..self.clone()
         }
     }
 }
 
+// Hashes the stickers only, equal cubes having equal stickers
+impl Hash for GeoCube {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.zobrist());
+    }
+}
+
 impl GeoCube {
     fn generate_stickers(size: CubeSize) -> Vec<(Sticker, CubeSize)> {
         let mut stickers = Vec::new();
@@ -104,38 +114,30 @@ impl GeoCube {
             }
         }
 
-        Self::set_sticker_initial_index(size, stickers)
-    }
-
-    fn set_sticker_initial_index(
-        size: CubeSize,
-        stickers: Vec<(Sticker, CubeSize)>,
-    ) -> Vec<(Sticker, CubeSize)> {
-        let mut result = Vec::new();
-        let cube = Self { size, stickers };
-
-        for (idx, mvs) in (FACE_ROTATING_MOVES).iter().enumerate() {
-            let rotated_cube = cube.apply_moves(mvs);
-            let top_layer_stickers = rotated_cube.top_layer_stickers();
-
-            for (sticker, _) in top_layer_stickers.iter() {
-                result.push((sticker.set_solved(), idx as CubeSize));
-            }
-        }
-
-        result
+        // order the stickers like the state, tagging each with the index of its face
+        let face_length = (size * size) as usize;
+        stickers.sort_by_key(|(s, _)| Self::slot_index(size, s.current));
+        stickers
+            .into_iter()
+            .enumerate()
+            .map(|(slot, (s, _))| (s, (slot / face_length) as CubeSize))
+            .collect()
     }
 
-    fn top_layer_stickers(&self) -> Vec<(Sticker, CubeSize)> {
-        let mut top_layer_stickers = self
-            .stickers
+    /// The index in ``state`` of the sticker slot at the given position.
+    ///
+    /// ``state`` lists each face as seen after rotating it to the top, row by row from back
+    /// to front, so the position is rotated the same way to find its row and column.
+    pub(crate) fn slot_index(size: CubeSize, position: IVec3) -> usize {
+        let face = Sticker::compute_face(size, position.x, position.y, position.z);
+        let face_index = ORDERED_FACES.iter().position(|f| *f == face).unwrap();
+        let top = FACE_ROTATING_MOVES[face_index]
             .iter()
-            .copied()
-            .filter(|(s, _)| matches!(s.current_face(), Face::U))
-            .collect::<Vec<_>>();
+            .fold(position, |p, mv| GeometricMove::from(*mv).rotate_point(p));
 
-        top_layer_stickers.sort_by_key(|(s, _)| (s.current.z as CubeSize, s.current.x as CubeSize));
-        top_layer_stickers
+        let row = (top.z + size - 1) / 2;
+        let col = (top.x + size - 1) / 2;
+        (face_index as CubeSize * size * size + row * size + col) as usize
     }
 
     /// Returns the range of facelet center coordinates along an arbitrary axis.
@@ -148,8 +150,8 @@ impl GeoCube {
     }
 }
 
-impl std::fmt::Display for GeoCube {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+impl core::fmt::Display for GeoCube {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         for (v, _) in &self.stickers {
             writeln!(f, "{v}")?;
         }
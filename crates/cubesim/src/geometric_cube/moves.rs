@@ -1,25 +1,61 @@
 use crate::generic_cube::Move::*;
 use crate::generic_cube::MoveVariant::*;
-use crate::generic_cube::{CubeSize, Move, MoveVariant};
-use glam::DMat3;
+use crate::generic_cube::{Axis, CubeSize, Move, MoveVariant};
+use glam::IVec3;
 
 use super::sticker::Sticker;
 
+/// A move expressed as a number of clockwise quarter turns of some layers about an axis.
+///
+/// Sticker coordinates are integers, so quarter turns are exact coordinate permutations and
+/// no floating point rotations are needed.
+#[derive(Clone, Copy)]
 pub struct GeometricMove {
     axis: Axis,
-    angle: f64,
-    pub predicate: Box<dyn Fn(&Sticker) -> bool>,
+    quarter_turns: i32,
+    layers: Layers,
 }
 
 impl GeometricMove {
-    pub fn get_rotation_matrix(&self) -> DMat3 {
-        match self.axis {
-            Axis::X => DMat3::from_rotation_x(-self.angle.to_radians()),
-            Axis::Y => DMat3::from_rotation_y(-self.angle.to_radians()),
-            Axis::Z => DMat3::from_rotation_z(-self.angle.to_radians()),
+    /// Whether the move turns the layer the sticker currently sits in.
+    pub fn affects(&self, sticker: &Sticker) -> bool {
+        let coordinate = match self.axis {
+            Axis::X => sticker.current.x,
+            Axis::Y => sticker.current.y,
+            Axis::Z => sticker.current.z,
+        };
+
+        // the outermost layer also holds the stickers on the face itself
+        match self.layers {
+            Layers::All => true,
+            Layers::Positive(n) => coordinate >= sticker.size - (n * 2),
+            Layers::Negative(n) => coordinate <= -sticker.size + (n * 2),
+            Layers::PositiveSlice(n) => {
+                coordinate >= sticker.size - (n * 2)
+                    && (n == 1 || coordinate < sticker.size - (n - 1) * 2)
+            }
+            Layers::NegativeSlice(n) => {
+                coordinate <= -sticker.size + (n * 2)
+                    && (n == 1 || coordinate > -sticker.size + (n - 1) * 2)
+            }
         }
     }
 
+    /// Rotates a point about the move's axis, regardless of the layers being turned.
+    pub fn rotate_point(&self, point: IVec3) -> IVec3 {
+        let mut p = point;
+
+        for _ in 0..self.quarter_turns.rem_euclid(4) {
+            p = match self.axis {
+                Axis::X => IVec3::new(p.x, p.z, -p.y),
+                Axis::Y => IVec3::new(-p.z, p.y, p.x),
+                Axis::Z => IVec3::new(p.y, -p.x, p.z),
+            };
+        }
+
+        p
+    }
+
     pub fn from(mv: Move) -> Self {
         match (mv, 1) {
             (U(variant), n) | (Uw(n, variant), _) => modify_move(u_move(n), variant),
@@ -28,6 +64,12 @@ impl GeometricMove {
             (L(variant), n) | (Lw(n, variant), _) => modify_move(l_move(n), variant),
             (D(variant), n) | (Dw(n, variant), _) => modify_move(d_move(n), variant),
             (B(variant), n) | (Bw(n, variant), _) => modify_move(b_move(n), variant),
+            (Ui(n, variant), _) => modify_move(inner(u_move(n)), variant),
+            (Ri(n, variant), _) => modify_move(inner(r_move(n)), variant),
+            (Fi(n, variant), _) => modify_move(inner(f_move(n)), variant),
+            (Li(n, variant), _) => modify_move(inner(l_move(n)), variant),
+            (Di(n, variant), _) => modify_move(inner(d_move(n)), variant),
+            (Bi(n, variant), _) => modify_move(inner(b_move(n)), variant),
             (X(variant), _) => modify_move(x_move(), variant),
             (Y(variant), _) => modify_move(y_move(), variant),
             (Z(variant), _) => modify_move(z_move(), variant),
@@ -35,22 +77,38 @@ impl GeometricMove {
     }
 }
 
+/// The layers turned by a move, counted from either end of its axis.
 #[derive(Copy, Clone)]
-pub enum Axis {
-    X,
-    Y,
-    Z,
+enum Layers {
+    All,
+    /// The outermost n layers.
+    Positive(CubeSize),
+    Negative(CubeSize),
+    /// Only the nth layer.
+    PositiveSlice(CubeSize),
+    NegativeSlice(CubeSize),
+}
+
+// narrows a move of the outermost n layers down to the nth layer alone
+fn inner(mv: GeometricMove) -> GeometricMove {
+    let layers = match mv.layers {
+        Layers::Positive(n) => Layers::PositiveSlice(n),
+        Layers::Negative(n) => Layers::NegativeSlice(n),
+        layers => layers,
+    };
+
+    GeometricMove { layers, ..mv }
 }
 
 fn modify_move(mv: GeometricMove, variant: MoveVariant) -> GeometricMove {
     match variant {
         Standard => mv,
         Double => GeometricMove {
-            angle: 2.0 * mv.angle,
+            quarter_turns: 2 * mv.quarter_turns,
             ..mv
         },
         Inverse => GeometricMove {
-            angle: -mv.angle,
+            quarter_turns: -mv.quarter_turns,
             ..mv
         },
     }
@@ -58,14 +116,14 @@ fn modify_move(mv: GeometricMove, variant: MoveVariant) -> GeometricMove {
 
 fn u_move(n: CubeSize) -> GeometricMove {
     GeometricMove {
-        predicate: Box::new(move |s| s.current.y >= s.size - (n * 2)),
+        layers: Layers::Positive(n),
         ..y_move()
     }
 }
 fn d_move(n: CubeSize) -> GeometricMove {
     modify_move(
         GeometricMove {
-            predicate: Box::new(move |s| s.current.y <= -s.size + (n * 2)),
+            layers: Layers::Negative(n),
             ..y_move()
         },
         Inverse,
@@ -74,15 +132,15 @@ fn d_move(n: CubeSize) -> GeometricMove {
 fn y_move() -> GeometricMove {
     GeometricMove {
         axis: Axis::Y,
-        angle: 90.0,
-        predicate: Box::new(|_| true),
+        quarter_turns: 1,
+        layers: Layers::All,
     }
 }
 
 fn l_move(n: CubeSize) -> GeometricMove {
     modify_move(
         GeometricMove {
-            predicate: Box::new(move |s| s.current.x <= -s.size + (n * 2)),
+            layers: Layers::Negative(n),
             ..x_move()
         },
         Inverse,
@@ -90,28 +148,28 @@ fn l_move(n: CubeSize) -> GeometricMove {
 }
 fn r_move(n: CubeSize) -> GeometricMove {
     GeometricMove {
-        predicate: Box::new(move |s| s.current.x >= s.size - (n * 2)),
+        layers: Layers::Positive(n),
         ..x_move()
     }
 }
 fn x_move() -> GeometricMove {
     GeometricMove {
         axis: Axis::X,
-        angle: 90.0,
-        predicate: Box::new(|_| true),
+        quarter_turns: 1,
+        layers: Layers::All,
     }
 }
 
 fn f_move(n: CubeSize) -> GeometricMove {
     GeometricMove {
-        predicate: Box::new(move |s| s.current.z >= s.size - (n * 2)),
+        layers: Layers::Positive(n),
         ..z_move()
     }
 }
 fn b_move(n: CubeSize) -> GeometricMove {
     modify_move(
         GeometricMove {
-            predicate: Box::new(move |s| s.current.z <= -s.size + (n * 2)),
+            layers: Layers::Negative(n),
             ..z_move()
         },
         Inverse,
@@ -120,7 +178,7 @@ fn b_move(n: CubeSize) -> GeometricMove {
 fn z_move() -> GeometricMove {
     GeometricMove {
         axis: Axis::Z,
-        angle: 90.0,
-        predicate: Box::new(|_| true),
+        quarter_turns: 1,
+        layers: Layers::All,
     }
 }
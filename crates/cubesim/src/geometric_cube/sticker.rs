@@ -1,5 +1,5 @@
 use derive_more::Display;
-use glam::{DVec3, IVec3};
+use glam::IVec3;
 
 use crate::generic_cube::{CubeSize, Face};
 use crate::geometric_cube::moves::GeometricMove;
@@ -32,24 +32,12 @@ impl Sticker {
     }
 
     pub fn rotate(&self, mv: GeometricMove) -> Self {
-        if !(mv.predicate)(self) {
+        if !mv.affects(self) {
             return *self;
         }
 
-        let rotation_matrix = mv.get_rotation_matrix();
-        let new_position = rotation_matrix
-            * DVec3::new(
-                self.current.x as f64,
-                self.current.y as f64,
-                self.current.z as f64,
-            );
-
         Self {
-            current: IVec3 {
-                x: new_position.x.round() as CubeSize,
-                y: new_position.y.round() as CubeSize,
-                z: new_position.z.round() as CubeSize,
-            },
+            current: mv.rotate_point(self.current),
             ..*self
         }
     }
@@ -0,0 +1,76 @@
+// The counters and histograms of the solvers' work, recorded through the `metrics` facade with
+// the `metrics` feature, and doing nothing without it. The application installs the recorder
+// exporting them, e.g. to Prometheus.
+
+#[cfg(feature = "metrics")]
+use std::time::Instant;
+
+#[cfg(feature = "metrics")]
+const SEARCH_NODES: &str = "cubesim.search.nodes";
+#[cfg(feature = "metrics")]
+const TABLE_HITS: &str = "cubesim.pruning_table.hits";
+#[cfg(feature = "metrics")]
+const TABLE_MISSES: &str = "cubesim.pruning_table.misses";
+#[cfg(feature = "metrics")]
+const TRANSPOSITION_HITS: &str = "cubesim.transposition_table.hits";
+#[cfg(feature = "metrics")]
+const SOLVE_DURATION: &str = "cubesim.solve.duration";
+#[cfg(feature = "metrics")]
+const SOLVE_MOVES: &str = "cubesim.solve.moves";
+
+/// Describes the metrics recorded by the solvers to the recorder installed, with their units.
+///
+/// Every IDA* search counts the states it visits as ``cubesim.search.nodes``, and the lookups
+/// of those states in the pruning table as ``cubesim.pruning_table.hits`` or
+/// ``cubesim.pruning_table.misses``, a miss meaning the state is deeper than the table, and the
+/// states it skips as already searched as ``cubesim.transposition_table.hits``. Every
+/// solve by ``solve_phases`` or ``solve_cfop`` records how long it took as the
+/// ``cubesim.solve.duration`` histogram, and the moves of its solution as
+/// ``cubesim.solve.moves``, labelled by its ``method`` and whether it ``solved`` the cube.
+#[cfg(feature = "metrics")]
+pub fn describe_metrics() {
+    use metrics::{describe_counter, describe_histogram, Unit};
+
+    describe_counter!(SEARCH_NODES, Unit::Count, "States visited by the IDA* searches");
+    describe_counter!(TABLE_HITS, Unit::Count, "States found in the pruning tables");
+    describe_counter!(TABLE_MISSES, Unit::Count, "States deeper than the pruning tables");
+    describe_counter!(TRANSPOSITION_HITS, Unit::Count, "States skipped as already searched");
+    describe_histogram!(SOLVE_DURATION, Unit::Seconds, "How long each solve took");
+    describe_histogram!(SOLVE_MOVES, Unit::Count, "The moves of each solution found");
+}
+
+// Records the work of an IDA* search, once it's over
+#[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+pub(crate) fn record_search(nodes: u64, table_hits: u64, table_misses: u64, transposition_hits: u64) {
+    #[cfg(feature = "metrics")]
+    {
+        metrics::counter!(SEARCH_NODES).increment(nodes);
+        metrics::counter!(TABLE_HITS).increment(table_hits);
+        metrics::counter!(TABLE_MISSES).increment(table_misses);
+        metrics::counter!(TRANSPOSITION_HITS).increment(transposition_hits);
+    }
+}
+
+// Solves with ``solve``, recording how long it took and the moves of the solution it returns
+#[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+pub(crate) fn timed_solve<T>(
+    method: &'static str,
+    solve: impl FnOnce() -> Option<T>,
+    moves: impl Fn(&T) -> usize,
+) -> Option<T> {
+    #[cfg(feature = "metrics")]
+    {
+        let start = Instant::now();
+        let solution = solve();
+        let solved = if solution.is_some() { "true" } else { "false" };
+        metrics::histogram!(SOLVE_DURATION, "method" => method, "solved" => solved)
+            .record(start.elapsed().as_secs_f64());
+        if let Some(solution) = &solution {
+            metrics::histogram!(SOLVE_MOVES, "method" => method, "solved" => solved)
+                .record(moves(solution) as f64);
+        }
+        solution
+    }
+    #[cfg(not(feature = "metrics"))]
+    solve()
+}
@@ -0,0 +1,464 @@
+use std::{
+    fmt::{Display, Formatter},
+    hash::{Hash, Hasher},
+    ops::Deref,
+    sync::Arc,
+};
+
+use crate::generic_puzzle::TwistyPuzzle;
+
+/// An error encountered while reading a KPuzzle definition.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KPuzzleError {
+    /// The 1-based line number the error was found on.
+    pub line: usize,
+    pub message: String,
+}
+
+impl Display for KPuzzleError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for KPuzzleError {}
+
+/// A set of interchangeable pieces, such as the corners of a cube.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PieceSet {
+    pub name: String,
+    pub size: usize,
+    pub orientations: u8,
+}
+
+/// The permutation and orientation of every piece set, in the order the sets were declared.
+///
+/// Permutations use the 1-based piece numbers of the definition file.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct KState(Vec<(Vec<u8>, Vec<u8>)>);
+
+impl KState {
+    /// Applies a transformation, where the piece ending up in position ``i`` is taken from
+    /// position ``permutation[i]`` and twisted by ``orientation[i]``.
+    fn apply(&self, transformation: &KState, sets: &[PieceSet]) -> KState {
+        KState(
+            self.0
+                .iter()
+                .zip(&transformation.0)
+                .zip(sets)
+                .map(|(((perm, ori), (t_perm, t_ori)), set)| {
+                    let from = t_perm.iter().map(|p| (*p - 1) as usize);
+                    let new_perm = from.clone().map(|i| perm[i]).collect();
+                    let new_ori = from
+                        .zip(t_ori)
+                        .map(|(i, twist)| (ori[i] + twist) % set.orientations)
+                        .collect();
+                    (new_perm, new_ori)
+                })
+                .collect(),
+        )
+    }
+}
+
+/// A puzzle defined in the KPuzzle/ksolve definition format.
+///
+/// Only the ``Name``, ``Set``, ``Solved`` and ``Move`` sections are supported. Orientation
+/// lines may be left out, in which case they default to all zeros.
+#[derive(Debug)]
+pub struct KPuzzleDefinition {
+    pub name: String,
+    pub sets: Vec<PieceSet>,
+    solved: KState,
+    moves: Vec<(String, KState, u32)>,
+}
+
+impl KPuzzleDefinition {
+    /// The names of all the moves defined for the puzzle.
+    pub fn move_names(&self) -> Vec<&str> {
+        self.moves.iter().map(|(name, _, _)| name.as_str()).collect()
+    }
+
+    /// Looks up a move by its base name, e.g. ``"R"``.
+    pub fn get_move(&self, name: &str) -> Option<KMove> {
+        self.moves
+            .iter()
+            .find(|(n, _, _)| n == name)
+            .and_then(|(name, _, order)| {
+                Some(KMove {
+                    name: KMoveName::new(name)?,
+                    amount: 1,
+                    order: *order,
+                })
+            })
+    }
+
+    fn transformation(&self, name: &str) -> &KState {
+        &self.moves.iter().find(|(n, _, _)| *n == name).unwrap().1
+    }
+}
+
+/// The longest name of a move of a ``KPuzzle``, in bytes.
+pub const MAX_KMOVE_NAME_LEN: usize = 15;
+
+/// The name of a move of a ``KPuzzle``, kept within the move so moves can be copied around
+/// like those of the cubes, whatever definition they belong to.
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+pub struct KMoveName {
+    bytes: [u8; MAX_KMOVE_NAME_LEN],
+    len: u8,
+}
+
+impl KMoveName {
+    /// The name, unless it's longer than ``MAX_KMOVE_NAME_LEN`` bytes.
+    pub fn new(name: &str) -> Option<Self> {
+        let mut bytes = [0; MAX_KMOVE_NAME_LEN];
+        bytes.get_mut(..name.len())?.copy_from_slice(name.as_bytes());
+        Some(Self {
+            bytes,
+            len: name.len() as u8,
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        // only ever copied whole from a str
+        std::str::from_utf8(&self.bytes[..self.len as usize]).unwrap()
+    }
+}
+
+impl Deref for KMoveName {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Display for KMoveName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self)
+    }
+}
+
+impl std::fmt::Debug for KMoveName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.as_str())
+    }
+}
+
+/// A move of a ``KPuzzle``: a defined move applied ``amount`` times.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct KMove {
+    pub name: KMoveName,
+    pub amount: u32,
+    /// The number of times the move has to be applied to return to the starting state.
+    pub order: u32,
+}
+
+impl Display for KMove {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.amount == 1 {
+            write!(f, "{}", self.name)
+        } else if self.amount + 1 == self.order {
+            write!(f, "{}'", self.name)
+        } else {
+            write!(f, "{}{}", self.name, self.amount)
+        }
+    }
+}
+
+/// A puzzle loaded from a KPuzzle/ksolve definition.
+///
+/// # Examples
+///
+/// A puzzle of four pieces swapped around by a single 4-cycle:
+///
+/// ```rust
+/// use cubesim::{parse_kpuzzle, KPuzzle, TwistyPuzzle};
+///
+/// let definition = parse_kpuzzle("
+///     Name Cycle
+///     Set PIECES 4 1
+///
+///     Solved
+///     PIECES
+///     1 2 3 4
+///     End
+///
+///     Move A
+///     PIECES
+///     2 3 4 1
+///     End
+/// ").unwrap();
+///
+/// let puzzle = KPuzzle::new(definition);
+/// let a = puzzle.definition().get_move("A").unwrap();
+/// assert_eq!(a.order, 4);
+/// assert!(!puzzle.apply_move(a).is_solved());
+/// assert!(puzzle.apply_moves(&[a; 4]).is_solved());
+/// ```
+#[derive(Clone, Debug)]
+pub struct KPuzzle {
+    definition: Arc<KPuzzleDefinition>,
+    state: KState,
+}
+
+impl KPuzzle {
+    /// Creates a solved puzzle from a definition.
+    pub fn new(definition: KPuzzleDefinition) -> Self {
+        Self {
+            state: definition.solved.clone(),
+            definition: Arc::new(definition),
+        }
+    }
+
+    pub fn definition(&self) -> &KPuzzleDefinition {
+        &self.definition
+    }
+}
+
+impl PartialEq for KPuzzle {
+    fn eq(&self, other: &Self) -> bool {
+        self.state == other.state
+    }
+}
+
+impl Eq for KPuzzle {}
+
+impl Hash for KPuzzle {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.state.hash(state);
+    }
+}
+
+impl TwistyPuzzle for KPuzzle {
+    type Move = KMove;
+    type State = KState;
+
+    fn state(&self) -> KState {
+        self.state.clone()
+    }
+
+    fn is_solved(&self) -> bool {
+        self.state == self.definition.solved
+    }
+
+    fn apply_move(&self, mv: KMove) -> Self {
+        let transformation = self.definition.transformation(&mv.name);
+        let mut state = self.state.clone();
+
+        for _ in 0..mv.amount {
+            state = state.apply(transformation, &self.definition.sets);
+        }
+
+        Self {
+            definition: self.definition.clone(),
+            state,
+        }
+    }
+
+    fn moves(&self) -> Vec<KMove> {
+        self.definition
+            .moves
+            .iter()
+            .flat_map(|(name, _, order)| {
+                // the names were checked as the definition was parsed
+                let name = KMoveName::new(name).unwrap();
+                (1..*order).map(move |amount| KMove {
+                    name,
+                    amount,
+                    order: *order,
+                })
+            })
+            .collect()
+    }
+
+    fn inverse_move(mv: KMove) -> KMove {
+        KMove {
+            amount: (mv.order - mv.amount) % mv.order,
+            ..mv
+        }
+    }
+
+    fn same_layer(a: KMove, b: KMove) -> bool {
+        a.name == b.name
+    }
+}
+
+/// Parses a puzzle definition in the KPuzzle/ksolve format.
+pub fn parse_kpuzzle(definition: &str) -> Result<KPuzzleDefinition, KPuzzleError> {
+    let mut lines = definition
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.split('#').next().unwrap().trim()))
+        .filter(|(_, line)| !line.is_empty())
+        .peekable();
+
+    let mut name = String::new();
+    let mut sets: Vec<PieceSet> = Vec::new();
+    let mut solved = None;
+    let mut moves = Vec::new();
+
+    while let Some((line_number, line)) = lines.next() {
+        let error = |message: String| KPuzzleError {
+            line: line_number,
+            message,
+        };
+        let words = line.split_whitespace().collect::<Vec<_>>();
+
+        match words[0] {
+            "Name" => name = words[1..].join(" "),
+            "Set" => {
+                let [_, set_name, size, orientations] = words[..] else {
+                    return Err(error(
+                        "expected `Set <name> <pieces> <orientations>`".into(),
+                    ));
+                };
+                let size = size
+                    .parse()
+                    .map_err(|_| error(format!("invalid piece count {size}")))?;
+                let orientations = orientations
+                    .parse()
+                    .ok()
+                    .filter(|o| *o > 0)
+                    .ok_or_else(|| error(format!("invalid orientation count {orientations}")))?;
+
+                sets.push(PieceSet {
+                    name: set_name.to_string(),
+                    size,
+                    orientations,
+                });
+            }
+            "Solved" => solved = Some(parse_state(&mut lines, &sets, line_number, false)?),
+            "Move" => {
+                let [_, move_name] = words[..] else {
+                    return Err(error("expected `Move <name>`".into()));
+                };
+                if move_name.len() > MAX_KMOVE_NAME_LEN {
+                    return Err(error(format!(
+                        "move name {move_name} is longer than {MAX_KMOVE_NAME_LEN} bytes"
+                    )));
+                }
+                let transformation = parse_state(&mut lines, &sets, line_number, true)?;
+                let order = move_order(&transformation, &sets)
+                    .ok_or_else(|| error(format!("move {move_name} has no finite order")))?;
+
+                moves.push((move_name.to_string(), transformation, order));
+            }
+            section => return Err(error(format!("unsupported section {section}"))),
+        }
+    }
+
+    let solved = solved.ok_or(KPuzzleError {
+        line: definition.lines().count(),
+        message: "missing Solved section".into(),
+    })?;
+
+    Ok(KPuzzleDefinition {
+        name,
+        sets,
+        solved,
+        moves,
+    })
+}
+
+/// Parses the body of a ``Solved`` or ``Move`` section up to its ``End`` line.
+fn parse_state<'a>(
+    lines: &mut std::iter::Peekable<impl Iterator<Item = (usize, &'a str)>>,
+    sets: &[PieceSet],
+    section_line: usize,
+    is_move: bool,
+) -> Result<KState, KPuzzleError> {
+    let mut parsed: Vec<Option<(Vec<u8>, Vec<u8>)>> = vec![None; sets.len()];
+
+    loop {
+        let Some((line_number, line)) = lines.next() else {
+            return Err(KPuzzleError {
+                line: section_line,
+                message: "section is missing its End".into(),
+            });
+        };
+        let error = |message: String| KPuzzleError {
+            line: line_number,
+            message,
+        };
+
+        if line == "End" {
+            break;
+        }
+
+        let index = sets
+            .iter()
+            .position(|s| s.name == line)
+            .ok_or_else(|| error(format!("unknown piece set {line}")))?;
+        let set = &sets[index];
+
+        let numbers = |(line_number, line): (usize, &str)| {
+            line.split_whitespace()
+                .map(|n| n.parse::<u8>())
+                .collect::<Result<Vec<_>, _>>()
+                .ok()
+                .filter(|n| n.len() == set.size)
+                .ok_or(KPuzzleError {
+                    line: line_number,
+                    message: format!("expected {} numbers for {}", set.size, set.name),
+                })
+        };
+
+        let permutation = numbers(lines.next().unwrap_or((line_number, "")))?;
+        if permutation
+            .iter()
+            .any(|p| *p == 0 || *p as usize > set.size)
+        {
+            return Err(error(format!("piece out of range in {}", set.name)));
+        }
+        if is_move {
+            let mut sorted = permutation.clone();
+            sorted.sort();
+            if sorted != (1..=set.size as u8).collect::<Vec<_>>() {
+                return Err(error(format!("{} is not a permutation", set.name)));
+            }
+        }
+
+        // The orientation line is optional, so only consume the next line if it is numeric.
+        let has_orientation = lines
+            .peek()
+            .is_some_and(|(_, l)| l.starts_with(|c: char| c.is_ascii_digit()));
+        let orientation = if has_orientation {
+            numbers(lines.next().unwrap())?
+        } else {
+            vec![0; set.size]
+        };
+        if orientation.iter().any(|o| *o >= set.orientations) {
+            return Err(error(format!("orientation out of range in {}", set.name)));
+        }
+
+        parsed[index] = Some((permutation, orientation));
+    }
+
+    // Sets left out of a section stay untouched.
+    Ok(KState(
+        parsed
+            .into_iter()
+            .zip(sets)
+            .map(|(p, set)| p.unwrap_or(((1..=set.size as u8).collect(), vec![0; set.size])))
+            .collect(),
+    ))
+}
+
+fn move_order(transformation: &KState, sets: &[PieceSet]) -> Option<u32> {
+    let identity = KState(
+        sets.iter()
+            .map(|set| ((1..=set.size as u8).collect(), vec![0; set.size]))
+            .collect(),
+    );
+
+    let mut state = transformation.clone();
+    for order in 1..=10_000 {
+        if state == identity {
+            return Some(order);
+        }
+        state = state.apply(transformation, sets);
+    }
+
+    None
+}
@@ -5,7 +5,7 @@
 //! To quickly get a Rubik's Cube simulation running, we can follow the code sample below:
 //!
 //! ```rust
-//! use cubesim::prelude::{Cube, Move, MoveVariant};
+//! use cubesim::prelude::{Cube, Move, MoveVariant, TwistyPuzzle};
 //! use cubesim::FaceletCube;
 //!
 //! let cube = FaceletCube::new(3);
@@ -18,21 +18,156 @@
 //! which define the minimal set of behaviours expected of a Rubik's Cube. Specific implementations
 //! can then be used for different scenarios that fit its characteristics. For example, the
 //! ``FaceletCube`` is the most performant and versatile while the ``GeoCube`` allows for easy 3D
-//! modelling.
+//! modelling. The ``CubieCube`` stores a 3x3x3 cube by its pieces instead of its stickers.
+//!
+//! Each representation converts into the others with ``From``, or ``TryFrom`` into the
+//! ``CubieCube`` as it only holds valid 3x3x3 states. The ``FaceletCube`` and ``GeoCube``
+//! convert into each other without losing anything, while the ``CubieCube`` keeps the pieces
+//! relative to the centers, but not how the cube is held.
+//!
+//! # Other Puzzles
+//! The more general ``TwistyPuzzle`` trait describes any puzzle by its moves and states. Every
+//! ``Cube`` is a ``TwistyPuzzle``, and other puzzles such as the ``Megaminx`` implement it
+//! directly, so they share the generic scrambler and IDA* solver. Puzzles written in the
+//! KPuzzle/ksolve definition format can be loaded at runtime as a ``KPuzzle``.
+//!
+//! # ``no_std`` Support
+//! Disabling the default ``std`` feature builds the core of the crate with only ``alloc``: the
+//! ``Cube`` trait, the ``FaceletCube`` and ``GeoCube`` and move parsing. Random scrambles, the
+//! solvers and the other puzzles require ``std``.
+//!
+//! # Bevy Support
+//! The ``bevy_reflect`` feature derives ``Reflect`` for ``Face``, ``Move``, ``MoveVariant`` and
+//! ``PieceType``, so they can be inspected and serialized as part of Bevy components and
+//! resources.
+//!
+//! # Metrics
+//! The ``metrics`` feature records the work of the solvers through the ``metrics`` facade: the
+//! states their searches visit, the lookups in their pruning tables and how long each solve
+//! takes. ``describe_metrics`` lists them to the recorder installed by the application.
+//!
+//! # Property Testing and Fuzzing
+//! The ``proptest`` feature provides strategies of moves and valid cubes, and the ``arbitrary``
+//! feature implements ``Arbitrary`` for them, to test solvers and parsers on many generated
+//! cases. ``check_cube``, ``check_solution`` and ``check_notation`` check the invariants
+//! those should keep.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
 #[doc(hidden)]
 pub mod prelude;
 
+pub use algs::{matching_algorithms, AlgSet, Algorithm};
+pub use beginner::BeginnerStep;
+pub use cube_explorer::{read_cube_explorer, write_cube_explorer, CubeExplorerEntry};
+pub use cubie_cube::CubieCube;
+pub use cycles::{
+    bad_edges, cycle_structure, validate_state, Cycle, CycleInfo, CORNER_NAMES, EDGE_NAMES,
+    EDGE_STICKERS,
+};
+pub use diff::{diff, distance_lower_bound, StickerDiff};
+pub use encoding::{decode_moves, encode_moves};
 pub use facelet_cube::FaceletCube;
-pub use generic_cube::{all_moves, solved_state, sticker_index, Cube, Face, Move, MoveVariant};
-pub use generic_solver::{PruningTable, Solver};
+pub use fuzzing::{check_cube, check_notation, check_solution, ARBITRARY_MAX_SIZE};
+#[cfg(feature = "proptest")]
+pub use fuzzing::{cube_strategy, move_strategy, moves_strategy};
+pub use generic_cube::{
+    all_moves, piece_type, solved_state, sticker_index, Axis, Cube, Face, Move, MoveVariant,
+    PieceType,
+};
+#[cfg(feature = "std")]
+pub use generic_puzzle::random_moves;
+pub use generic_puzzle::TwistyPuzzle;
+#[cfg(feature = "std")]
+pub use generic_solver::{
+    bidirectional_search, ida_star, ida_star_observed, ida_star_parallel, ida_star_parallel_observed,
+    PruningTable, SearchProgress, Solver, SEARCH_REPORT_INTERVAL, TRANSPOSITION_TABLE_BYTES,
+};
 pub use geometric_cube::GeoCube;
-pub use scramble_parser::{parse_scramble, random_scramble, simplify_moves};
-pub use thistlethwaite::solve;
+#[cfg(feature = "metrics")]
+pub use instrument::describe_metrics;
+#[cfg(feature = "std")]
+pub use kpuzzle::{
+    parse_kpuzzle, KMove, KMoveName, KPuzzle, KPuzzleDefinition, KPuzzleError, KState, PieceSet,
+    MAX_KMOVE_NAME_LEN,
+};
+#[cfg(feature = "std")]
+pub use methods::{centering_rotation, prepare_tables, solve_cfop, MethodSolution, SolveMethod};
+#[cfg(feature = "std")]
+pub use megaminx::{
+    parse_megaminx_scramble, random_megaminx_scramble, Megaminx, MinxFace, MinxMove,
+    MINX_STICKERS_PER_FACE, ORDERED_MINX_FACES,
+};
+pub use random_state::{PieceMask, StateMask};
+pub use recolor::{detect_scheme, recolor};
+pub use reid::{parse_reid, reid_string, REID_POSITIONS};
+pub use report::{json_string, SolveReport};
+#[cfg(feature = "std")]
+pub use scramble_parser::{random_scramble, random_scramble_with};
+pub use scramble_parser::{
+    format_scramble, parse_algorithm, parse_algorithm_for, parse_scramble, remove_rotations,
+    simplify_moves, simplify_moves_with, validate_scramble, ParseError, ScrambleFormat,
+    ScrambleOptions, SimplifyOptions,
+};
+#[cfg(feature = "server")]
+pub use server::{handle_request, serve, Response};
+pub use stats::{
+    average_of, penalty_at_stop, solve_stats, CfopPhase, Pause, Penalty, PhaseTime, SolveResult,
+    SolveStats, TimedMove,
+};
+pub use solve_log::{
+    parse_timed_moves, read_cstimer_export, read_gan_export, read_solve_log, LoggedSolve,
+};
+#[cfg(feature = "std")]
+pub use svg::net_svg;
+#[cfg(feature = "std")]
+pub use tables::{read_tables, write_tables};
+#[cfg(feature = "std")]
+pub use thistlethwaite::{
+    solve, solve_phases, solve_phases_observed, solve_phases_with, Solution, SolveOptions,
+    ThistlethwaitePhase,
+};
+pub use triggers::{annotate_triggers, find_triggers, Trigger, TriggerMatch};
 
+mod algs;
+mod beginner;
+mod compat;
+mod cube_explorer;
+mod cubie_cube;
+mod cycles;
+mod diff;
+mod encoding;
 mod facelet_cube;
+mod fuzzing;
 mod generic_cube;
+mod generic_puzzle;
+#[cfg(feature = "std")]
 mod generic_solver;
 mod geometric_cube;
+#[cfg(feature = "std")]
+mod instrument;
+mod json;
+#[cfg(feature = "std")]
+mod kpuzzle;
+#[cfg(feature = "std")]
+mod megaminx;
+#[cfg(feature = "std")]
+mod methods;
+mod random_state;
+mod recolor;
+mod reid;
+mod report;
 mod scramble_parser;
+#[cfg(feature = "server")]
+mod server;
+mod solve_log;
+mod stats;
+#[cfg(feature = "std")]
+mod svg;
+#[cfg(feature = "std")]
+mod tables;
+#[cfg(feature = "std")]
 mod thistlethwaite;
+mod triggers;
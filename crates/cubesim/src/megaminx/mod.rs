@@ -0,0 +1,5 @@
+mod moves;
+mod puzzle;
+
+pub use moves::{parse_megaminx_scramble, random_megaminx_scramble, MinxMove};
+pub use puzzle::{Megaminx, MinxFace, MINX_STICKERS_PER_FACE, ORDERED_MINX_FACES};
@@ -0,0 +1,132 @@
+use std::fmt::{Display, Formatter};
+
+use rand::Rng;
+
+use super::puzzle::{MinxFace, ORDERED_MINX_FACES};
+use crate::ParseError;
+
+/// A move of a Megaminx.
+///
+/// Amounts are given in fifths of a full turn, positive amounts turning clockwise as seen
+/// from the named face.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum MinxMove {
+    /// Rotate a single face layer.
+    Turn(MinxFace, i32),
+    /// Rotate everything except the layer opposite the named face, as used by the Pochmann
+    /// scramble notation where ``R++`` is ``Wide(R, 2)`` and ``D--`` is ``Wide(D, -2)``.
+    Wide(MinxFace, i32),
+}
+
+impl MinxMove {
+    /// Returns the move undoing this one.
+    pub fn inverse(&self) -> MinxMove {
+        match *self {
+            MinxMove::Turn(face, amount) => MinxMove::Turn(face, -amount),
+            MinxMove::Wide(face, amount) => MinxMove::Wide(face, -amount),
+        }
+    }
+}
+
+impl Display for MinxMove {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            MinxMove::Wide(face, amount) => {
+                let sign = if amount > 0 { "+" } else { "-" };
+                write!(f, "{face}{}", sign.repeat(amount.unsigned_abs() as usize))
+            }
+            MinxMove::Turn(face, amount) => match amount {
+                1 => write!(f, "{face}"),
+                -1 => write!(f, "{face}'"),
+                n if n > 0 => write!(f, "{face}{n}"),
+                n => write!(f, "{face}{}'", -n),
+            },
+        }
+    }
+}
+
+/// Converts a Pochmann/WCA notation Megaminx scramble into ``Vec<MinxMove>``.
+///
+/// Wide moves are written as ``R++``/``R--`` and face turns as ``U``, ``U'``, ``BR2``
+/// or ``BR2'``. The first move that can't be parsed is returned as an error, like
+/// ``parse_algorithm`` does.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::{parse_megaminx_scramble, MinxFace, MinxMove};
+///
+/// assert_eq!(
+///     parse_megaminx_scramble("R++ D-- U'"),
+///     Ok(vec![
+///         MinxMove::Wide(MinxFace::R, 2),
+///         MinxMove::Wide(MinxFace::D, -2),
+///         MinxMove::Turn(MinxFace::U, -1)
+///     ])
+/// );
+///
+/// let error = parse_megaminx_scramble("R++ Q++").unwrap_err();
+/// assert_eq!((error.position, error.token.as_str()), (2, "Q++"));
+/// ```
+pub fn parse_megaminx_scramble(scramble: &str) -> Result<Vec<MinxMove>, ParseError> {
+    scramble
+        .split_whitespace()
+        .enumerate()
+        .map(|(i, token)| {
+            convert_move(token).ok_or_else(|| ParseError {
+                position: i + 1,
+                token: token.to_string(),
+            })
+        })
+        .collect()
+}
+
+fn convert_move(mv: &str) -> Option<MinxMove> {
+    let name_length = mv
+        .find(|c: char| !c.is_ascii_uppercase())
+        .unwrap_or(mv.len());
+    let (name, suffix) = mv.split_at(name_length);
+
+    let face = *ORDERED_MINX_FACES.iter().find(|f| f.to_string() == name)?;
+
+    if !suffix.is_empty() && suffix.chars().all(|c| c == '+') {
+        Some(MinxMove::Wide(face, i32::try_from(suffix.len()).ok()?))
+    } else if !suffix.is_empty() && suffix.chars().all(|c| c == '-') {
+        Some(MinxMove::Wide(face, -i32::try_from(suffix.len()).ok()?))
+    } else {
+        let (amount, inverse) = match suffix.strip_suffix('\'') {
+            Some(amount) => (amount, true),
+            None => (suffix, false),
+        };
+        let amount = match amount {
+            "" => 1,
+            // only digits, a sign would be read as a wide move
+            _ if amount.chars().all(|c| c.is_ascii_digit()) => amount.parse::<i32>().ok()?,
+            _ => return None,
+        };
+
+        Some(MinxMove::Turn(face, if inverse { -amount } else { amount }))
+    }
+}
+
+/// Generates a random WCA style Megaminx scramble.
+///
+/// The scramble consists of seven lines, each made of ten alternating ``R``/``D`` wide moves
+/// followed by a ``U`` or ``U'`` turn.
+pub fn random_megaminx_scramble() -> Vec<MinxMove> {
+    let mut rng = rand::thread_rng();
+    let mut scramble = vec![];
+
+    for _ in 0..7 {
+        for i in 0..10 {
+            let face = if i % 2 == 0 { MinxFace::R } else { MinxFace::D };
+            let amount = if rng.gen_bool(0.5) { 2 } else { -2 };
+            scramble.push(MinxMove::Wide(face, amount));
+        }
+
+        let amount = if rng.gen_bool(0.5) { 1 } else { -1 };
+        scramble.push(MinxMove::Turn(MinxFace::U, amount));
+    }
+
+    scramble
+}
@@ -0,0 +1,308 @@
+use std::f64::consts::TAU;
+
+use derive_more::Display;
+use glam::{DMat3, DQuat, DVec3};
+use lazy_static::lazy_static;
+
+use super::moves::MinxMove;
+use crate::generic_puzzle::TwistyPuzzle;
+
+/// A face of a Megaminx.
+///
+/// The upper ring of faces (``F``, ``R``, ``BR``, ``BL``, ``L``) is listed counter-clockwise as
+/// seen from above, starting at the front. The lower ring then lists the faces opposite to
+/// each of these in the same order, e.g. ``B`` is opposite ``F``.
+#[derive(Clone, Copy, Debug, Display, Eq, Hash, PartialEq)]
+pub enum MinxFace {
+    U,
+    F,
+    R,
+    BR,
+    BL,
+    L,
+    D,
+    B,
+    DBL,
+    DL,
+    DR,
+    DBR,
+}
+
+/// A designated ordering of the Megaminx faces.
+pub const ORDERED_MINX_FACES: [MinxFace; 12] = {
+    use MinxFace::*;
+    [U, F, R, BR, BL, L, D, B, DBL, DL, DR, DBR]
+};
+
+/// The number of stickers on a single face: one center, five corners and five edges.
+pub const MINX_STICKERS_PER_FACE: usize = 11;
+
+/// How far towards a face's vertices its corner and edge stickers sit.
+const STICKER_OFFSET: f64 = 0.65;
+
+impl MinxFace {
+    /// The position of the face in [`ORDERED_MINX_FACES`].
+    pub fn index(&self) -> usize {
+        ORDERED_MINX_FACES.iter().position(|f| f == self).unwrap()
+    }
+
+    /// The face on the opposite side of the puzzle.
+    pub fn opposite(&self) -> MinxFace {
+        let index = self.index();
+        if index == 0 || index == 6 {
+            ORDERED_MINX_FACES[6 - index]
+        } else {
+            ORDERED_MINX_FACES[(index + 6) % 12]
+        }
+    }
+
+    /// The outward unit normal of the face, with ``U`` pointing along +y and ``F`` towards +z.
+    pub fn normal(&self) -> DVec3 {
+        GEOMETRY.normals[self.index()]
+    }
+}
+
+/// The static sticker layout of a Megaminx, shared by all puzzle instances.
+struct MinxGeometry {
+    normals: Vec<DVec3>,
+    vertices: Vec<Vec<DVec3>>,
+    stickers: Vec<DVec3>,
+    face_turns: Vec<Vec<usize>>,
+    wide_turns: Vec<Vec<usize>>,
+}
+
+lazy_static! {
+    static ref GEOMETRY: MinxGeometry = MinxGeometry::new();
+}
+
+impl MinxGeometry {
+    fn new() -> Self {
+        let normals = Self::face_normals();
+        let vertices = Self::face_vertices(&normals);
+
+        let mut stickers = Vec::new();
+        for (normal, corners) in normals.iter().zip(&vertices) {
+            stickers.push(*normal);
+            for i in 0..5 {
+                let edge = (corners[i] + corners[(i + 1) % 5]) / 2.0;
+                stickers.push(*normal + STICKER_OFFSET * (corners[i] - *normal));
+                stickers.push(*normal + STICKER_OFFSET * (edge - *normal));
+            }
+        }
+
+        // A face layer contains the face itself plus a strip of three stickers on each
+        // neighbouring face; those strips sit exactly this far along the face normal.
+        let adjacent_dot = 1.0 / 5.0_f64.sqrt();
+        let layer_cut = adjacent_dot + STICKER_OFFSET * (1.0 - adjacent_dot) - 1e-6;
+
+        let face_turns = normals
+            .iter()
+            .map(|n| Self::permutation(&stickers, *n, |p| p.dot(*n) >= layer_cut))
+            .collect();
+        let wide_turns = normals
+            .iter()
+            .map(|n| Self::permutation(&stickers, *n, |p| p.dot(-*n) < layer_cut))
+            .collect();
+
+        Self {
+            normals,
+            vertices,
+            stickers,
+            face_turns,
+            wide_turns,
+        }
+    }
+
+    fn face_normals() -> Vec<DVec3> {
+        let cos = 1.0 / 5.0_f64.sqrt();
+        let sin = 2.0 / 5.0_f64.sqrt();
+        let ring = (0..5)
+            .map(|i| {
+                let azimuth = TAU * i as f64 / 5.0;
+                DVec3::new(sin * azimuth.sin(), cos, sin * azimuth.cos())
+            })
+            .collect::<Vec<_>>();
+
+        let mut normals = vec![DVec3::Y];
+        normals.extend(&ring);
+        normals.push(-DVec3::Y);
+        normals.extend(ring.iter().map(|n| -*n));
+        normals
+    }
+
+    /// The five vertices of each face, ordered clockwise as seen from outside the puzzle.
+    fn face_vertices(normals: &[DVec3]) -> Vec<Vec<DVec3>> {
+        let adjacent = |a: DVec3, b: DVec3| (a.dot(b) - 1.0 / 5.0_f64.sqrt()).abs() < 1e-9;
+
+        let mut all_vertices = Vec::new();
+        for i in 0..12 {
+            for j in (i + 1)..12 {
+                for k in (j + 1)..12 {
+                    let (a, b, c) = (normals[i], normals[j], normals[k]);
+                    if adjacent(a, b) && adjacent(b, c) && adjacent(a, c) {
+                        // Each vertex lies on the three face planes n . v = 1.
+                        let planes = DMat3::from_cols(a, b, c).transpose();
+                        all_vertices.push(planes.inverse() * DVec3::ONE);
+                    }
+                }
+            }
+        }
+
+        normals
+            .iter()
+            .map(|normal| {
+                let reference = if normal.y.abs() > 0.99 {
+                    DVec3::Z
+                } else {
+                    DVec3::Y
+                };
+                let e1 = (reference - normal.dot(reference) * *normal).normalize();
+                let e2 = normal.cross(e1);
+                let clockwise_angle = |v: &DVec3| {
+                    let d = *v - *normal;
+                    (-d.dot(e2)).atan2(d.dot(e1)).rem_euclid(TAU)
+                };
+
+                let mut vertices = all_vertices
+                    .iter()
+                    .copied()
+                    .filter(|v| (v.dot(*normal) - 1.0).abs() < 1e-9)
+                    .collect::<Vec<_>>();
+                vertices.sort_by(|a, b| clockwise_angle(a).total_cmp(&clockwise_angle(b)));
+                vertices
+            })
+            .collect()
+    }
+
+    /// Computes where each sticker slot ends up after a clockwise fifth turn about ``axis`` of
+    /// all stickers matching ``predicate``.
+    fn permutation(
+        stickers: &[DVec3],
+        axis: DVec3,
+        predicate: impl Fn(DVec3) -> bool,
+    ) -> Vec<usize> {
+        let rotation = DQuat::from_axis_angle(axis, -TAU / 5.0);
+
+        stickers
+            .iter()
+            .enumerate()
+            .map(|(i, p)| {
+                if !predicate(*p) {
+                    return i;
+                }
+
+                let target = rotation * *p;
+                stickers
+                    .iter()
+                    .position(|q| q.distance_squared(target) < 1e-9)
+                    .expect("megaminx rotation must map stickers onto stickers")
+            })
+            .collect()
+    }
+}
+
+/// A Megaminx with its 132 stickers stored sequentially, face by face.
+///
+/// Each face holds its center sticker followed by alternating corner and edge stickers
+/// in clockwise order. Moves are derived geometrically by rotating sticker positions on a
+/// dodecahedron, then cached as index permutations so applying a move is a simple shuffle.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Megaminx {
+    stickers: Vec<MinxFace>,
+}
+
+impl Default for Megaminx {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Megaminx {
+    /// Creates a solved Megaminx.
+    pub fn new() -> Self {
+        Self {
+            stickers: ORDERED_MINX_FACES
+                .iter()
+                .flat_map(|&face| vec![face; MINX_STICKERS_PER_FACE])
+                .collect(),
+        }
+    }
+
+    /// The resting position of each sticker slot, in the same order as the puzzle state.
+    ///
+    /// The puzzle is modelled as a dodecahedron whose faces are at unit distance from the origin.
+    pub fn sticker_positions() -> Vec<DVec3> {
+        GEOMETRY.stickers.clone()
+    }
+
+    /// The five corners of a face, ordered clockwise as seen from outside the puzzle.
+    pub fn face_vertices(face: MinxFace) -> Vec<DVec3> {
+        GEOMETRY.vertices[face.index()].clone()
+    }
+}
+
+impl TwistyPuzzle for Megaminx {
+    type Move = MinxMove;
+    type State = Vec<MinxFace>;
+
+    /// A one-dimensional representation of the puzzle as a sequence of faces.
+    fn state(&self) -> Vec<MinxFace> {
+        self.stickers.clone()
+    }
+
+    /// Whether every face shows a single color.
+    fn is_solved(&self) -> bool {
+        self.stickers
+            .chunks(MINX_STICKERS_PER_FACE)
+            .all(|face| face.iter().all(|s| *s == face[0]))
+    }
+
+    /// Apply a move to the puzzle.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use cubesim::{Megaminx, MinxFace, MinxMove, TwistyPuzzle};
+    ///
+    /// let minx = Megaminx::new().apply_move(MinxMove::Turn(MinxFace::U, 1));
+    /// assert!(!minx.is_solved());
+    /// assert!(minx.apply_move(MinxMove::Turn(MinxFace::U, -1)).is_solved());
+    /// ```
+    fn apply_move(&self, mv: MinxMove) -> Self {
+        let (permutation, amount) = match mv {
+            MinxMove::Turn(face, amount) => (&GEOMETRY.face_turns[face.index()], amount),
+            MinxMove::Wide(face, amount) => (&GEOMETRY.wide_turns[face.index()], amount),
+        };
+
+        let mut stickers = self.stickers.clone();
+        for _ in 0..amount.rem_euclid(5) {
+            let mut turned = stickers.clone();
+            for (from, to) in permutation.iter().enumerate() {
+                turned[*to] = stickers[from];
+            }
+            stickers = turned;
+        }
+
+        Self { stickers }
+    }
+
+    /// Every single face turn, leaving out the wide moves used for scrambling.
+    fn moves(&self) -> Vec<MinxMove> {
+        ORDERED_MINX_FACES
+            .iter()
+            .flat_map(|&face| [1, 2, -1, -2].map(|amount| MinxMove::Turn(face, amount)))
+            .collect()
+    }
+
+    fn inverse_move(mv: MinxMove) -> MinxMove {
+        mv.inverse()
+    }
+
+    fn same_layer(a: MinxMove, b: MinxMove) -> bool {
+        match (a, b) {
+            (MinxMove::Turn(f1, _), MinxMove::Turn(f2, _)) => f1 == f2,
+            (MinxMove::Wide(f1, _), MinxMove::Wide(f2, _)) => f1 == f2,
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,433 @@
+use std::fmt::{Display, Formatter};
+
+use lazy_static::lazy_static;
+
+use crate::{
+    algs::{adjustments, matching_algorithms, AlgSet},
+    beginner::orientations,
+    facelet_cube::FaceletCube,
+    generic_cube::{
+        all_moves, solved_state, sticker_index as S, Cube, CubeSize, Face, Move,
+    },
+    generic_puzzle::TwistyPuzzle,
+    generic_solver::{ida_star, PruningTable, Solver},
+    instrument::timed_solve,
+    scramble_parser::remove_rotations,
+    stats::CfopPhase,
+    tables::cached_table,
+    thistlethwaite::{solve_phases, ThistlethwaitePhase},
+};
+
+// The longest cross searched, every cross being solvable in 8 moves
+const CROSS_LIMIT: i32 = 8;
+// The longest F2L pair searched, most pairs taking far fewer moves
+const PAIR_LIMIT: i32 = 12;
+// The share of the time ``prepare_tables`` takes to build the tables of each Thistlethwaite
+// phase, the cross and the F2L pairs, roughly
+const PHASE_SHARES: [f32; 4] = [0.08, 0.04, 0.39, 0.03];
+const CROSS_SHARE: f32 = 0.35;
+
+/// A method of solving the 3x3x3 cube, to compare the solutions each finds for the same
+/// scramble.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum SolveMethod {
+    /// The four phases of ``solve_phases``, each allowing fewer kinds of moves.
+    Thistlethwaite,
+    /// The cross searched on ``D``, each F2L pair searched in turn, then the OLL and PLL
+    /// algorithms of ``AlgSet``.
+    Cfop,
+}
+
+impl SolveMethod {
+    /// Every method available.
+    pub const ALL: [SolveMethod; 2] = [SolveMethod::Thistlethwaite, SolveMethod::Cfop];
+
+    /// Solves a 3x3x3 cube with the method, however it's held, giving up once ``out_of_time``
+    /// returns true.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use cubesim::prelude::{Cube, TwistyPuzzle};
+    /// use cubesim::{parse_scramble, FaceletCube, SolveMethod};
+    ///
+    /// let cube = FaceletCube::new(3).apply_moves(&parse_scramble(String::from("R U R' F2 D")));
+    /// for method in SolveMethod::ALL {
+    ///     let solution = method.solve(&cube, &|| false).unwrap();
+    ///     assert!(cube.apply_moves(&solution.moves()).is_solved());
+    /// }
+    /// ```
+    pub fn solve(self, cube: &impl Cube, out_of_time: &dyn Fn() -> bool) -> Option<MethodSolution> {
+        let rotation = centering_rotation(cube)?;
+        let cube = FaceletCube::from(cube.apply_moves(&rotation).state());
+
+        let steps = match self {
+            SolveMethod::Thistlethwaite => solve_phases(&cube)?
+                .phases
+                .into_iter()
+                .map(|(phase, moves)| (phase.to_string(), moves))
+                .collect(),
+            SolveMethod::Cfop => solve_cfop(&cube, out_of_time)?
+                .into_iter()
+                .map(|(phase, moves)| (phase.to_string(), moves))
+                .collect(),
+        };
+        Some(MethodSolution {
+            method: self,
+            rotation,
+            steps,
+        })
+    }
+}
+
+impl Display for SolveMethod {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            SolveMethod::Thistlethwaite => "Thistlethwaite",
+            SolveMethod::Cfop => "CFOP",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// The moves of a solution found by ``SolveMethod::solve``, step by step.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MethodSolution {
+    /// The method solving the cube.
+    pub method: SolveMethod,
+    /// The cube rotation bringing the centers to their solved faces, made first.
+    pub rotation: Vec<Move>,
+    /// The name and moves of each step, in the order they are made.
+    pub steps: Vec<(String, Vec<Move>)>,
+}
+
+impl MethodSolution {
+    /// Every move of the solution, its rotation first.
+    pub fn moves(&self) -> Vec<Move> {
+        let steps = self.steps.iter().flat_map(|(_, moves)| moves);
+        self.rotation.iter().chain(steps).copied().collect()
+    }
+
+    /// The number of moves of the solution, not counting its rotation.
+    pub fn len(&self) -> usize {
+        self.steps.iter().map(|(_, moves)| moves.len()).sum()
+    }
+
+    /// Whether the cube was already solved.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The solution without any cube rotation, neither first nor in its steps, the moves of
+    /// each step rewritten by ``remove_rotations`` for the cube to be held as it was at the
+    /// start throughout.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use cubesim::prelude::{Cube, Move, TwistyPuzzle};
+    /// use cubesim::{parse_scramble, FaceletCube, SolveMethod};
+    ///
+    /// let cube = FaceletCube::new(3).apply_moves(&parse_scramble(String::from("x R U R' F2 y")));
+    /// let solution = SolveMethod::Cfop.solve(&cube, &|| false).unwrap().without_rotations();
+    /// assert!(solution.rotation.is_empty());
+    /// assert!(!solution.moves().iter().any(|mv| matches!(mv, Move::X(_) | Move::Y(_) | Move::Z(_))));
+    /// assert!(cube.apply_moves(&solution.moves()).is_solved());
+    /// ```
+    pub fn without_rotations(&self) -> MethodSolution {
+        // the rotations made before each step, which it's rewritten after
+        let mut rotations = self.rotation.clone();
+        let steps = self
+            .steps
+            .iter()
+            .map(|(name, moves)| {
+                let rotated: Vec<Move> = rotations.iter().chain(moves).copied().collect();
+                rotations.extend(moves.iter().filter(|mv| matches!(mv, Move::X(_) | Move::Y(_) | Move::Z(_))));
+                (name.clone(), remove_rotations(&rotated))
+            })
+            .collect();
+        MethodSolution {
+            method: self.method,
+            rotation: vec![],
+            steps,
+        }
+    }
+}
+
+/// Builds the pruning tables of every method ahead of time, which their first solve would
+/// otherwise build, calling ``progress`` with the share of the work done after each table,
+/// the last call being with 1.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prepare_tables;
+///
+/// let mut shares = Vec::new();
+/// prepare_tables(&mut |share| shares.push(share));
+/// assert_eq!(shares.last(), Some(&1.0));
+/// ```
+pub fn prepare_tables(progress: &mut dyn FnMut(f32)) {
+    let solved = FaceletCube::new(3);
+    let mut done = 0.0;
+    for (phase, share) in ThistlethwaitePhase::ALL.into_iter().zip(PHASE_SHARES) {
+        phase.solve(&solved);
+        done += share;
+        progress(done);
+    }
+    lazy_static::initialize(&CROSS_SOLVER);
+    progress(done + CROSS_SHARE);
+    lazy_static::initialize(&PAIR_SOLVERS);
+    progress(1.0);
+}
+
+/// The cube rotation bringing the centers of a 3x3x3 cube to their solved faces, once cube
+/// rotations, slices or wide moves have turned them. Solvers expecting the centers in place
+/// solve the cube turned by it, and ``remove_rotations`` rewrites the rotation followed by the
+/// solution for the cube held as it was. Returns ``None`` for cubes of other sizes.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::{Cube, Move::*, MoveVariant::*, TwistyPuzzle};
+/// use cubesim::{centering_rotation, parse_scramble, remove_rotations, solve, FaceletCube};
+///
+/// let cube = FaceletCube::new(3).apply_moves(&parse_scramble(String::from("y R 2U")));
+/// let rotation = centering_rotation(&cube).unwrap();
+/// let solution = solve(&cube.apply_moves(&rotation)).unwrap();
+///
+/// let moves = remove_rotations(&[rotation, solution].concat());
+/// assert!(!moves.iter().any(|mv| matches!(mv, X(_) | Y(_) | Z(_))));
+/// assert!(cube.apply_moves(&moves).is_solved());
+/// assert_eq!(centering_rotation(&FaceletCube::new(3)), Some(vec![]));
+/// ```
+pub fn centering_rotation(cube: &impl Cube) -> Option<Vec<Move>> {
+    if cube.size() != 3 {
+        return None;
+    }
+    orientations().find(|rotation| centers_home(&cube.apply_moves(rotation)))
+}
+
+/// Solves a 3x3x3 cube held with its centers on their solved faces with the CFOP method:
+/// the shortest cross on ``D``, the F2L pair of the slot needing the fewest moves until the
+/// four are solved, then the OLL and PLL algorithms found for the last layer. Returns
+/// ``None`` once ``out_of_time`` returns true.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::{Cube, TwistyPuzzle};
+/// use cubesim::{parse_scramble, solve_cfop, CfopPhase, FaceletCube};
+///
+/// let cube = FaceletCube::new(3).apply_moves(&parse_scramble(String::from("F R U' R'")));
+/// let phases = solve_cfop(&cube, &|| false).unwrap();
+/// assert_eq!(phases[0].0, CfopPhase::Cross);
+/// assert_eq!(phases[0].1.len(), 1);
+///
+/// let moves: Vec<_> = phases.into_iter().flat_map(|(_, moves)| moves).collect();
+/// assert!(cube.apply_moves(&moves).is_solved());
+/// ```
+pub fn solve_cfop(
+    cube: &impl Cube,
+    out_of_time: &dyn Fn() -> bool,
+) -> Option<Vec<(CfopPhase, Vec<Move>)>> {
+    timed_solve("cfop", || cfop_phases(cube, out_of_time), |phases| {
+        phases.iter().map(|(_, moves)| moves.len()).sum()
+    })
+}
+
+fn cfop_phases(
+    cube: &impl Cube,
+    out_of_time: &dyn Fn() -> bool,
+) -> Option<Vec<(CfopPhase, Vec<Move>)>> {
+    if cube.size() != 3 || !centers_home(cube) {
+        return None;
+    }
+    let mut cube = FaceletCube::from(cube.state());
+    let mut phases = Vec::new();
+
+    let cross = ida_star(&cube.mask(&cross_mask), &CROSS_SOLVER, CROSS_LIMIT)?;
+    cube = cube.apply_moves(&cross);
+    phases.push((CfopPhase::Cross, cross));
+
+    let mut f2l = Vec::new();
+    let mut solved = [false; 4];
+    while solved.contains(&false) {
+        let (slot, pair) = solve_pair(&cube, &solved, out_of_time)?;
+        cube = cube.apply_moves(&pair);
+        f2l.extend(pair);
+        solved[slot] = true;
+    }
+    phases.push((CfopPhase::F2l, f2l));
+
+    for (phase, set) in [(CfopPhase::Oll, AlgSet::Oll), (CfopPhase::Pll, AlgSet::Pll)] {
+        let mut moves = Vec::new();
+        // the last layer may only need a turn of U, without a PLL algorithm
+        let skipped = match phase {
+            CfopPhase::Pll => adjustments().any(|auf| cube.apply_moves(&auf).is_solved()),
+            _ => phase.is_done(&cube),
+        };
+        if !skipped {
+            let (auf, algorithm) = *matching_algorithms(&cube, set).first()?;
+            moves.extend(auf);
+            moves.extend(algorithm.moves());
+            // the wide moves of some algorithms turn the centers, turned back by a rotation
+            let turned = cube.apply_moves(&moves);
+            moves.extend(centering_rotation(&turned)?);
+        }
+        if phase == CfopPhase::Pll {
+            let turned = cube.apply_moves(&moves);
+            moves.extend(adjustments().find(|auf| turned.apply_moves(auf).is_solved())?);
+        }
+        cube = cube.apply_moves(&moves);
+        phases.push((phase, moves));
+    }
+
+    Some(phases)
+}
+
+// Whether the centers of a 3x3x3 cube are on their solved faces
+fn centers_home(cube: &impl Cube) -> bool {
+    let state = cube.state();
+    let solved = solved_state(3);
+    (0..6).all(|face| state[face * 9 + 4] == solved[face * 9 + 4])
+}
+
+// The stickers of the corner and edge of each F2L slot, front right, front left, back left and
+// back right
+fn slot_stickers(slot: usize) -> [CubeSize; 5] {
+    use Face::*;
+
+    match slot {
+        0 => [S(3, F, 6), S(3, R, 4), S(3, F, 9), S(3, R, 7), S(3, D, 3)],
+        1 => [S(3, F, 4), S(3, L, 6), S(3, F, 7), S(3, L, 9), S(3, D, 1)],
+        2 => [S(3, B, 6), S(3, L, 4), S(3, B, 9), S(3, L, 7), S(3, D, 7)],
+        _ => [S(3, B, 4), S(3, R, 6), S(3, B, 7), S(3, R, 9), S(3, D, 9)],
+    }
+}
+
+fn cross_mask(index: CubeSize, face: Face) -> Face {
+    CfopPhase::Cross.mask(index, face)
+}
+
+// Keeps the cross and the pair of the slot
+fn pair_mask(slot: usize, index: CubeSize, face: Face) -> Face {
+    if slot_stickers(slot).contains(&index) {
+        face
+    } else {
+        cross_mask(index, face)
+    }
+}
+
+lazy_static! {
+    // every move turning a face, the slices and wide moves being no shorter
+    static ref FACE_MOVES: Vec<Move> = all_moves(3)
+        .into_iter()
+        .filter(|mv| !matches!(mv, Move::Uw(..) | Move::Lw(..) | Move::Fw(..) | Move::Rw(..) | Move::Bw(..) | Move::Dw(..)))
+        .collect();
+    static ref CROSS_SOLVER: Solver = {
+        let pruning_depth = 5;
+        let solved = FaceletCube::new(3).mask(&cross_mask);
+        let pruning_table = cached_table("cross", || PruningTable::new(&[solved], pruning_depth, &FACE_MOVES));
+        Solver::new(FACE_MOVES.clone(), pruning_table)
+    };
+    // the pairs are inserted without turning D, keeping the cross
+    static ref PAIR_MOVES: Vec<Move> = FACE_MOVES
+        .iter()
+        .copied()
+        .filter(|mv| !matches!(mv, Move::D(_)))
+        .collect();
+    static ref PAIR_SOLVERS: Vec<Solver> = (0..4)
+        .map(|slot| {
+            let pruning_depth = 4;
+            let solved = FaceletCube::new(3).mask(&|i, f| pair_mask(slot, i, f));
+            let pruning_table = cached_table(&format!("pair {slot}"), || {
+                PruningTable::new(&[solved], pruning_depth, &PAIR_MOVES)
+            });
+            Solver::new(PAIR_MOVES.clone(), pruning_table)
+        })
+        .collect();
+}
+
+// Searches the pair of an unsolved slot needing the fewest moves, keeping the cross and the
+// pairs solved before it
+fn solve_pair(
+    cube: &FaceletCube,
+    solved: &[bool; 4],
+    out_of_time: &dyn Fn() -> bool,
+) -> Option<(usize, Vec<Move>)> {
+    for depth in 0..=PAIR_LIMIT {
+        for slot in (0..4).filter(|&slot| !solved[slot]) {
+            let kept: Vec<usize> = (0..4).filter(|&i| i == slot || solved[i]).collect();
+            let search = PairSearch {
+                solvers: &PAIR_SOLVERS,
+                kept: &kept,
+                out_of_time,
+            };
+            match search.dfs(cube, &mut Vec::new(), depth) {
+                Ok(Some(pair)) => return Some((slot, pair)),
+                Ok(None) => {}
+                Err(OutOfTime) => return None,
+            }
+        }
+    }
+    None
+}
+
+struct OutOfTime;
+
+// A depth-first search keeping the pairs of several slots solved, bounded by the pruning
+// table of each
+struct PairSearch<'a> {
+    solvers: &'a [Solver],
+    kept: &'a [usize],
+    out_of_time: &'a dyn Fn() -> bool,
+}
+
+impl PairSearch<'_> {
+    fn lower_bound(&self, cube: &FaceletCube) -> i32 {
+        self.kept
+            .iter()
+            .map(|&slot| {
+                let masked = cube.mask(&|i, f| pair_mask(slot, i, f));
+                self.solvers[slot].lower_bound(&masked)
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn dfs(
+        &self,
+        cube: &FaceletCube,
+        moves: &mut Vec<Move>,
+        depth: i32,
+    ) -> Result<Option<Vec<Move>>, OutOfTime> {
+        let bound = self.lower_bound(cube);
+        if bound == 0 {
+            return Ok(Some(moves.clone()));
+        }
+        if bound > depth {
+            return Ok(None);
+        }
+        if (self.out_of_time)() {
+            return Err(OutOfTime);
+        }
+
+        for &mv in PAIR_MOVES.iter() {
+            if moves.last().is_some_and(|last| same_face(*last, mv)) {
+                continue;
+            }
+            moves.push(mv);
+            let found = self.dfs(&cube.apply_move(mv), moves, depth - 1)?;
+            moves.pop();
+            if found.is_some() {
+                return Ok(found);
+            }
+        }
+        Ok(None)
+    }
+}
+
+fn same_face(a: Move, b: Move) -> bool {
+    std::mem::discriminant(&a) == std::mem::discriminant(&b)
+}
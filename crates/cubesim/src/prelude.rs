@@ -1,3 +1,5 @@
 pub use crate::generic_cube::{Cube, CubeSize, Face, Move, MoveVariant};
+pub use crate::generic_puzzle::TwistyPuzzle;
 
+#[cfg(feature = "std")]
 pub use crate::generic_solver::Solver;
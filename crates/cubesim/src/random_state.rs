@@ -0,0 +1,174 @@
+#[cfg(feature = "std")]
+use alloc::{vec, vec::Vec};
+
+#[cfg(feature = "std")]
+use crate::cycles::pieces_state;
+#[cfg(feature = "std")]
+use crate::generic_cube::Face;
+#[cfg(feature = "std")]
+use rand::{seq::SliceRandom, Rng};
+
+/// What a random state does with the piece at a position of a 3x3x3 cube.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum PieceMask {
+    /// The piece stays solved.
+    Solved,
+    /// The position gets any of the pieces not kept solved, turned the way it is when solved:
+    /// the U or D sticker of a corner on its U or D face, and for an edge, its U or D sticker, or
+    /// else its F or B one, on the U, D, F or B face.
+    Oriented,
+    /// The position gets any of the pieces not kept solved, turned at random.
+    Random,
+}
+
+use PieceMask::*;
+
+/// Which pieces of a 3x3x3 cube a random state scrambles, by position in the order of
+/// ``CORNER_NAMES`` and ``EDGE_NAMES``, to drill the steps of a method from the states they
+/// start from.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::*;
+/// use cubesim::{FaceletCube, StateMask};
+///
+/// // a random PLL case
+/// let cube = FaceletCube::random_masked_state(&StateMask::ORIENTED_LAST_LAYER, &mut rand::thread_rng());
+/// let state = cube.state();
+///
+/// assert!(state[..9].iter().all(|&face| face == Face::U));
+/// assert!(state[27..36].iter().all(|&face| face == Face::D));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct StateMask {
+    pub corners: [PieceMask; 8],
+    pub edges: [PieceMask; 12],
+}
+
+impl StateMask {
+    /// Every piece scrambled.
+    pub const RANDOM: StateMask = StateMask {
+        corners: [Random; 8],
+        edges: [Random; 12],
+    };
+
+    /// The edges of the D face solved, everything else scrambled, to drill the first two
+    /// layers.
+    pub const CROSS: StateMask = StateMask {
+        corners: [Random; 8],
+        edges: [
+            Random, Random, Random, Random, Solved, Solved, Solved, Solved, Random, Random, Random,
+            Random,
+        ],
+    };
+
+    /// The D layer solved, the two layers above it scrambled.
+    pub const FIRST_LAYER: StateMask = StateMask {
+        corners: [
+            Random, Random, Random, Random, Solved, Solved, Solved, Solved,
+        ],
+        edges: [
+            Random, Random, Random, Random, Solved, Solved, Solved, Solved, Random, Random, Random,
+            Random,
+        ],
+    };
+
+    /// Only the U layer scrambled, to drill the orientation of the last layer.
+    pub const LAST_LAYER: StateMask = StateMask {
+        corners: [
+            Random, Random, Random, Random, Solved, Solved, Solved, Solved,
+        ],
+        edges: [
+            Random, Random, Random, Random, Solved, Solved, Solved, Solved, Solved, Solved, Solved,
+            Solved,
+        ],
+    };
+
+    /// Only the U layer scrambled with its U face solved, to drill the permutation of the last
+    /// layer.
+    pub const ORIENTED_LAST_LAYER: StateMask = StateMask {
+        corners: [
+            Oriented, Oriented, Oriented, Oriented, Solved, Solved, Solved, Solved,
+        ],
+        edges: [
+            Oriented, Oriented, Oriented, Oriented, Solved, Solved, Solved, Solved, Solved, Solved,
+            Solved, Solved,
+        ],
+    };
+}
+
+// The state of a cube with the pieces of the positions of the mask not kept solved placed and
+// turned at random, the last ones fixed for the cube to be solvable
+#[cfg(feature = "std")]
+pub(crate) fn random_masked_pieces(mask: &StateMask, rng: &mut impl Rng) -> Vec<Face> {
+    let mut corners = random_pieces(&mask.corners, 3, rng);
+    let mut edges = random_pieces(&mask.edges, 2, rng);
+
+    // swapping two pieces of the same kind makes both permutations even or both odd, as
+    // turns do, and a permutation of less than two pieces is even so one kind can be swapped
+    if is_odd(&corners) != is_odd(&edges) && !swap_two(&mut edges, &mask.edges) {
+        swap_two(&mut corners, &mask.corners);
+    }
+    pieces_state(&corners, &edges)
+}
+
+// The home and orientation of the piece at each position, the pieces of the positions not kept
+// solved shuffled among them and those turned at random turned so their orientations add up to
+// a multiple of the number of orientations
+#[cfg(feature = "std")]
+fn random_pieces<const N: usize>(
+    mask: &[PieceMask; N],
+    orientations: u8,
+    rng: &mut impl Rng,
+) -> [(usize, u8); N] {
+    let free: Vec<usize> = (0..N).filter(|&i| mask[i] != Solved).collect();
+    let mut homes = free.clone();
+    homes.shuffle(rng);
+
+    let mut pieces = core::array::from_fn(|i| (i, 0));
+    for (&position, home) in free.iter().zip(homes) {
+        pieces[position].0 = home;
+    }
+    let turned: Vec<usize> = (0..N).filter(|&i| mask[i] == Random).collect();
+    if let Some((&last, others)) = turned.split_last() {
+        for &position in others {
+            pieces[position].1 = rng.gen_range(0..orientations);
+        }
+        let sum: u8 = others.iter().map(|&position| pieces[position].1).sum();
+        pieces[last].1 = (orientations - sum % orientations) % orientations;
+    }
+    pieces
+}
+
+// Swaps the pieces of the first two positions not kept solved, if there are two
+#[cfg(feature = "std")]
+fn swap_two(pieces: &mut [(usize, u8)], mask: &[PieceMask]) -> bool {
+    let mut free = (0..mask.len()).filter(|&i| mask[i] != Solved);
+    let (Some(a), Some(b)) = (free.next(), free.next()) else {
+        return false;
+    };
+    let home = pieces[a].0;
+    pieces[a].0 = pieces[b].0;
+    pieces[b].0 = home;
+    true
+}
+
+// Whether the permutation of the pieces is made of an odd number of swaps
+#[cfg(feature = "std")]
+fn is_odd(pieces: &[(usize, u8)]) -> bool {
+    let mut visited = vec![false; pieces.len()];
+    let mut swaps = 0;
+    for start in 0..pieces.len() {
+        // a cycle of n pieces is n - 1 swaps
+        let mut position = start;
+        while !visited[position] {
+            visited[position] = true;
+            position = pieces[position].0;
+            if position != start {
+                swaps += 1;
+            }
+        }
+    }
+    swaps % 2 == 1
+}
@@ -0,0 +1,73 @@
+use alloc::{format, string::String, vec::Vec};
+
+use crate::generic_cube::{Face, ORDERED_FACES};
+
+/// The stickers of a state with each face replaced by the face it's mapped to, as pairs of a
+/// face and its replacement. The faces not mapped are kept.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::{Cube, Face, TwistyPuzzle};
+/// use cubesim::{recolor, FaceletCube};
+///
+/// // a scheme with the U and D colors swapped
+/// let state = recolor(&FaceletCube::new(3).state(), &[(Face::U, Face::D), (Face::D, Face::U)]);
+/// assert_eq!(&state[..9], &[Face::D; 9]);
+/// assert_eq!(&state[9..18], &[Face::R; 9]);
+/// ```
+pub fn recolor(state: &[Face], mapping: &[(Face, Face)]) -> Vec<Face> {
+    state
+        .iter()
+        .map(|&face| {
+            mapping
+                .iter()
+                .find(|(from, _)| *from == face)
+                .map_or(face, |&(_, to)| to)
+        })
+        .collect()
+}
+
+/// The mapping normalizing the colors of a state to the standard scheme, found from its
+/// centers: each center's color is mapped to the face it's on. States read from photos,
+/// strings or smart cubes with their own color schemes can then be solved.
+///
+/// Only the states of cubes of odd sizes, in the order of ``Cube::state``, have centers.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::{Cube, Face, Move, MoveVariant, TwistyPuzzle};
+/// use cubesim::{detect_scheme, recolor, FaceletCube};
+///
+/// let turned = FaceletCube::new(3).apply_move(Move::R(MoveVariant::Standard));
+/// // the same cube with the U and F colors swapped
+/// let swapped = [(Face::U, Face::F), (Face::F, Face::U)];
+/// let imported = recolor(&turned.state(), &swapped);
+///
+/// let mapping = detect_scheme(&imported).unwrap();
+/// assert_eq!(recolor(&imported, &mapping), turned.state());
+/// ```
+pub fn detect_scheme(state: &[Face]) -> Result<Vec<(Face, Face)>, String> {
+    let stickers = state.len() / 6;
+    let size = (1..=stickers).find(|size| size * size >= stickers).unwrap_or(0);
+    if size == 0 || state.len() != 6 * size * size {
+        return Err(format!("{} stickers aren't the state of a cube", state.len()));
+    }
+    if size % 2 == 0 {
+        return Err(format!("a {size}x{size}x{size} cube has no centers"));
+    }
+
+    let mut mapping = Vec::with_capacity(6);
+    for (i, &face) in ORDERED_FACES.iter().enumerate() {
+        let color = state[i * stickers + stickers / 2];
+        if color == Face::X {
+            return Err(format!("the center of {face} is masked"));
+        }
+        if let Some(&(_, other)) = mapping.iter().find(|(c, _)| *c == color) {
+            return Err(format!("the centers of {other} and {face} have the same color"));
+        }
+        mapping.push((color, face));
+    }
+    Ok(mapping)
+}
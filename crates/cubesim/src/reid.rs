@@ -0,0 +1,126 @@
+use alloc::{format, string::String, vec::Vec};
+
+use crate::cycles::{validate_state, CORNER_NAMES, CORNER_STICKERS, EDGE_NAMES, EDGE_STICKERS};
+use crate::generic_cube::{Face, ORDERED_FACES};
+use crate::recolor::{detect_scheme, recolor};
+
+/// The positions of Reid notation, in the order they're written: the edges then the corners.
+/// The solved cube is written as these positions, each piece named by its stickers' colors
+/// in the order of the faces of its position.
+pub const REID_POSITIONS: [&str; 20] = [
+    "UF", "UR", "UB", "UL", "DF", "DR", "DB", "DL", "FR", "FL", "BR", "BL", "UFR", "URB", "UBL",
+    "ULF", "DRF", "DFL", "DLB", "DBR",
+];
+
+// The stickers of a position, in the order of its faces in the name given
+fn position_stickers(name: &str) -> Vec<usize> {
+    let same_faces =
+        |other: &str| other.len() == name.len() && name.chars().all(|face| other.contains(face));
+    let (other, stickers): (&str, &[usize]) = match name.len() {
+        3 => {
+            let position = CORNER_NAMES.iter().position(|&n| same_faces(n)).unwrap();
+            (CORNER_NAMES[position], &CORNER_STICKERS[position])
+        }
+        _ => {
+            let position = EDGE_NAMES.iter().position(|&n| same_faces(n)).unwrap();
+            (EDGE_NAMES[position], &EDGE_STICKERS[position])
+        }
+    };
+    name.chars()
+        .map(|face| stickers[other.find(face).unwrap()])
+        .collect()
+}
+
+fn face(letter: char) -> Option<Face> {
+    match letter {
+        'U' => Some(Face::U),
+        'R' => Some(Face::R),
+        'F' => Some(Face::F),
+        'D' => Some(Face::D),
+        'L' => Some(Face::L),
+        'B' => Some(Face::B),
+        _ => None,
+    }
+}
+
+/// Reads a 3x3x3 state written in Reid notation, as used by several solvers and blindfolded
+/// solving tools: the pieces at the twenty positions of ``REID_POSITIONS``, separated by
+/// spaces. The centers are taken to be solved.
+///
+/// # Errors
+///
+/// Returns an error if the string doesn't name twenty pieces, or their stickers don't make a
+/// state a cube can be turned into.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::{Cube, Move, MoveVariant, TwistyPuzzle};
+/// use cubesim::{parse_reid, FaceletCube};
+///
+/// let state = parse_reid(
+///     "UB UL UF UR DF DR DB DL FR FL BR BL UBL ULF UFR URB DRF DFL DLB DBR",
+/// )
+/// .unwrap();
+/// let turned = FaceletCube::new(3).apply_move(Move::U(MoveVariant::Double));
+/// assert_eq!(state, turned.state());
+/// ```
+pub fn parse_reid(reid: &str) -> Result<Vec<Face>, String> {
+    let pieces: Vec<&str> = reid.split_whitespace().collect();
+    if pieces.len() != REID_POSITIONS.len() {
+        return Err(format!(
+            "{} pieces given instead of {}",
+            pieces.len(),
+            REID_POSITIONS.len()
+        ));
+    }
+
+    let mut state: Vec<Face> = ORDERED_FACES.iter().flat_map(|&face| [face; 9]).collect();
+    for (position, piece) in REID_POSITIONS.iter().zip(pieces) {
+        if piece.chars().count() != position.len() {
+            return Err(format!("{piece} can't be at {position}"));
+        }
+        for (sticker, letter) in position_stickers(position).into_iter().zip(piece.chars()) {
+            state[sticker] = face(letter).ok_or_else(|| format!("{letter} isn't a face"))?;
+        }
+    }
+
+    validate_state(&state)?;
+    Ok(state)
+}
+
+/// Writes a 3x3x3 state in Reid notation, as read by ``parse_reid``. The colors are taken
+/// from the centers, so a cube held another way or of another color scheme is written the
+/// same as the standard cube with its pieces in the same places.
+///
+/// # Errors
+///
+/// Returns an error if the state isn't one a 3x3x3 cube can be turned into.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::{Cube, Move, MoveVariant, TwistyPuzzle};
+/// use cubesim::{reid_string, FaceletCube};
+///
+/// let cube = FaceletCube::new(3).apply_move(Move::R(MoveVariant::Standard));
+/// assert_eq!(
+///     reid_string(&cube.state()).unwrap(),
+///     "UF FR UB UL DF BR DB DL DR FL UR BL FDR FRU UBL ULF BRD DFL DLB BUR"
+/// );
+/// ```
+pub fn reid_string(state: &[Face]) -> Result<String, String> {
+    validate_state(state)?;
+    let state = recolor(state, &detect_scheme(state)?);
+
+    let pieces: Vec<String> = REID_POSITIONS
+        .iter()
+        .map(|position| {
+            position_stickers(position)
+                .into_iter()
+                .map(|sticker| format!("{}", state[sticker]))
+                .collect()
+        })
+        .collect();
+    Ok(pieces.join(" "))
+}
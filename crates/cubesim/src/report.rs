@@ -0,0 +1,207 @@
+use alloc::{format, string::String, string::ToString, vec::Vec};
+use core::time::Duration;
+
+use crate::facelet_cube::FaceletCube;
+use crate::generic_cube::{Cube, CubeSize, Move};
+use crate::generic_puzzle::TwistyPuzzle;
+use crate::triggers::annotate_triggers;
+use crate::stats::{solve_stats, Penalty, SolveResult, SolveStats, TimedMove};
+
+/// A solve and its statistics, for training dashboards and other tools to read as JSON.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SolveReport {
+    /// The size of the cube solved.
+    pub cube_size: CubeSize,
+    /// The moves scrambling the solved cube.
+    pub scramble: Vec<Move>,
+    /// The moves of the solve, timed from the first one.
+    pub solution: Vec<TimedMove>,
+    /// The statistics of the solve, including the times of its CFOP phases.
+    pub stats: SolveStats,
+    /// When the solve ended, as the time since the Unix epoch, if known.
+    pub finished_at: Option<Duration>,
+    /// The penalty given to the solve, if any.
+    pub penalty: Option<Penalty>,
+}
+
+impl SolveReport {
+    /// The report of a solve of a cube of the given size, given the timed moves made after
+    /// the scramble. Every pause between moves longer than ``pause`` is reported.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use core::time::Duration;
+    /// use cubesim::prelude::{Move, MoveVariant::*};
+    /// use cubesim::{SolveReport, TimedMove};
+    ///
+    /// let solve = [
+    ///     TimedMove { mv: Move::U(Standard), at: Duration::ZERO },
+    ///     TimedMove { mv: Move::R(Inverse), at: Duration::from_millis(1500) },
+    /// ];
+    /// let report = SolveReport::new(3, &[Move::R(Standard), Move::U(Inverse)], &solve, Duration::from_secs(1));
+    ///
+    /// assert!(report.stats.phases.iter().any(|phase| phase.phase.to_string() == "PLL"));
+    /// assert!(report.to_json().starts_with(r#"{"cube_size":3,"scramble":"R U'","solution":"U R'""#));
+    /// ```
+    pub fn new(
+        cube_size: CubeSize,
+        scramble: &[Move],
+        solution: &[TimedMove],
+        pause: Duration,
+    ) -> Self {
+        let scrambled = FaceletCube::new(cube_size).apply_moves(scramble);
+        SolveReport {
+            cube_size,
+            scramble: scramble.to_vec(),
+            solution: solution.to_vec(),
+            stats: solve_stats(&scrambled, solution, pause),
+            finished_at: None,
+            penalty: None,
+        }
+    }
+
+    /// The time of the solve with its penalty.
+    pub fn result(&self) -> SolveResult {
+        SolveResult {
+            time: self.stats.duration,
+            penalty: self.penalty,
+        }
+    }
+
+    /// The moves of the solve with their triggers in parentheses, a line for each CFOP phase
+    /// commented with its moves and time, the way solves are reconstructed. A phase solved
+    /// with the one before is a skip, and on a 3x3x3 cube the moves after the last phase solved
+    /// are unfinished.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use core::time::Duration;
+    /// use cubesim::prelude::{Move, MoveVariant::*};
+    /// use cubesim::{SolveReport, TimedMove};
+    ///
+    /// let solve = [
+    ///     TimedMove { mv: Move::U(Inverse), at: Duration::ZERO },
+    ///     TimedMove { mv: Move::R(Inverse), at: Duration::from_millis(520) },
+    /// ];
+    /// let report = SolveReport::new(3, &[Move::R(Standard), Move::U(Standard)], &solve, Duration::from_secs(1));
+    ///
+    /// assert_eq!(report.reconstruction(), "U' R' // Cross (2 moves, 0.52 s), F2L skip, OLL skip, PLL skip");
+    /// ```
+    pub fn reconstruction(&self) -> String {
+        let moves: Vec<Move> = self.solution.iter().map(|timed| timed.mv).collect();
+        let mut lines: Vec<String> = Vec::new();
+        let mut start = 0;
+        for phase in &self.stats.phases {
+            let end = start + phase.moves;
+            match lines.last_mut() {
+                Some(line) if phase.moves == 0 => line.push_str(&format!(", {} skip", phase.phase)),
+                _ if phase.moves == 0 => lines.push(format!("// {} skip", phase.phase)),
+                _ => lines.push(format!(
+                    "{} // {} ({} move{}, {:.2} s)",
+                    annotate_triggers(&moves[start..end]),
+                    phase.phase,
+                    phase.moves,
+                    if phase.moves == 1 { "" } else { "s" },
+                    phase.duration.as_secs_f32()
+                )),
+            }
+            start = end;
+        }
+        if start < moves.len() {
+            let rest = annotate_triggers(&moves[start..]);
+            lines.push(if self.cube_size == 3 { format!("{rest} // unfinished") } else { rest });
+        }
+        lines.join("\n")
+    }
+
+    /// The report as a JSON object, with every duration in seconds:
+    ///
+    /// ```json
+    /// {"cube_size":3,"scramble":"R U'","solution":"U R'","move_times":[0.000,1.500],
+    ///  "finished_at":null,"penalty":null,"moves":2,"duration":1.500,"tps":1.33,
+    ///  "pauses":[{"before":1,"length":1.500}],
+    ///  "phases":[{"phase":"Cross","moves":2,"duration":1.500},...]}
+    /// ```
+    pub fn to_json(&self) -> String {
+        let times: Vec<String> = self
+            .solution
+            .iter()
+            .map(|timed| seconds(timed.at))
+            .collect();
+        let pauses: Vec<String> = self
+            .stats
+            .pauses
+            .iter()
+            .map(|pause| {
+                format!(
+                    r#"{{"before":{},"length":{}}}"#,
+                    pause.before,
+                    seconds(pause.length)
+                )
+            })
+            .collect();
+        let phases: Vec<String> = self
+            .stats
+            .phases
+            .iter()
+            .map(|phase| {
+                format!(
+                    r#"{{"phase":{},"moves":{},"duration":{}}}"#,
+                    json_string(&phase.phase.to_string()),
+                    phase.moves,
+                    seconds(phase.duration)
+                )
+            })
+            .collect();
+
+        format!(
+            r#"{{"cube_size":{},"scramble":{},"solution":{},"move_times":[{}],"finished_at":{},"penalty":{},"moves":{},"duration":{},"tps":{:.2},"pauses":[{}],"phases":[{}]}}"#,
+            self.cube_size,
+            json_string(&moves_string(self.scramble.iter())),
+            json_string(&moves_string(self.solution.iter().map(|timed| &timed.mv))),
+            times.join(","),
+            self.finished_at.map_or("null".to_string(), seconds),
+            self.penalty.map_or("null".to_string(), |penalty| json_string(&penalty.to_string())),
+            self.stats.moves,
+            seconds(self.stats.duration),
+            self.stats.tps,
+            pauses.join(","),
+            phases.join(",")
+        )
+    }
+}
+
+fn seconds(duration: Duration) -> String {
+    format!("{:.3}", duration.as_secs_f64())
+}
+
+fn moves_string<'a>(moves: impl Iterator<Item = &'a Move>) -> String {
+    moves.map(|mv| mv.to_string()).collect::<Vec<_>>().join(" ")
+}
+
+/// A string as a JSON string literal, quoted and escaped, as written in the JSON of reports,
+/// the solver's output and the server's responses.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::json_string;
+///
+/// assert_eq!(json_string("R U'"), r#""R U'""#);
+/// assert_eq!(json_string("a \"b\"\n"), r#""a \"b\"\u000a""#);
+/// ```
+pub fn json_string(s: &str) -> String {
+    let mut escaped = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
@@ -1,16 +1,145 @@
-use crate::generic_cube::{CubeSize, Move, Move::*, MoveVariant, MoveVariant::*};
-use rand::Rng;
+use alloc::{format, string::String, string::ToString, vec, vec::Vec};
+use core::fmt::{Display, Formatter};
+
+use crate::generic_cube::{CubeSize, Face, Move, Move::*, MoveVariant, MoveVariant::*};
+#[cfg(feature = "std")]
+use rand::{rngs::StdRng, Rng, SeedableRng};
 
 /// Converts a WCA Notation scramble into ``Vec<Move>``.
 pub fn parse_scramble(scramble: String) -> Vec<Move> {
     scramble.split_whitespace().map(convert_move).collect()
 }
 
+/// A move of an algorithm that can't be parsed, as found by ``parse_algorithm``.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct ParseError {
+    /// The 1-based position of the move in the algorithm.
+    pub position: usize,
+    /// The move, as written.
+    pub token: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "invalid move {}", self.token)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+/// Converts an algorithm in WCA Notation into ``Vec<Move>``, like ``parse_scramble``, returning
+/// the first move that can't be parsed instead of panicking on it.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::{Move::*, MoveVariant::*};
+/// use cubesim::parse_algorithm;
+///
+/// assert_eq!(parse_algorithm("R U' 3Fw2"), Ok(vec![R(Standard), U(Inverse), Fw(3, Double)]));
+///
+/// let error = parse_algorithm("R U Q2 F").unwrap_err();
+/// assert_eq!((error.position, error.token.as_str()), (3, "Q2"));
+/// ```
+pub fn parse_algorithm(algorithm: &str) -> Result<Vec<Move>, ParseError> {
+    parse_moves(algorithm, CubeSize::MAX)
+}
+
+/// Converts an algorithm for a cube of the given size, like ``parse_algorithm``, also
+/// returning the first move turning layers deeper than the cube, such as ``4R`` or ``5Rw``
+/// on a 3x3x3 cube.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::{Move::*, MoveVariant::*};
+/// use cubesim::parse_algorithm_for;
+///
+/// assert_eq!(parse_algorithm_for("2R 3Rw'", 3), Ok(vec![Ri(2, Standard), Rw(3, Inverse)]));
+/// assert_eq!(parse_algorithm_for("R 4R", 3).unwrap_err().position, 2);
+/// assert_eq!(parse_algorithm_for("5Rw", 4).unwrap_err().token, "5Rw");
+/// ```
+pub fn parse_algorithm_for(algorithm: &str, size: CubeSize) -> Result<Vec<Move>, ParseError> {
+    parse_moves(algorithm, size)
+}
+
+fn parse_moves(algorithm: &str, size: CubeSize) -> Result<Vec<Move>, ParseError> {
+    algorithm
+        .split_whitespace()
+        .enumerate()
+        .map(|(i, token)| {
+            if !is_valid_move(token) || get_slice(token) > size {
+                return Err(ParseError {
+                    position: i + 1,
+                    token: token.to_string(),
+                });
+            }
+            Ok(convert_move(token))
+        })
+        .collect()
+}
+
+/// Checks that every move of a WCA Notation scramble can be parsed, returning the first
+/// invalid move otherwise.
+///
+/// A number before a face turn selects the layer: ``3Rw`` turns the three rightmost layers,
+/// while ``3R`` turns only the third layer from the right. The number counts from 1, so
+/// ``0R`` is invalid. Cube rotations are written ``x``, ``y`` and ``z``, or in capitals as
+/// ``Move`` displays them.
+///
+/// ``parse_scramble`` panics on invalid moves, so this should be used to check scrambles
+/// coming from user input first.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::validate_scramble;
+///
+/// assert!(validate_scramble("R U2 3Fw' 2L x").is_ok());
+/// assert_eq!(validate_scramble("R Q"), Err(String::from("invalid move Q")));
+/// assert!(validate_scramble("0R").is_err());
+/// assert!(validate_scramble("X Y2 z'").is_ok());
+/// ```
+pub fn validate_scramble(scramble: &str) -> Result<(), String> {
+    parse_algorithm(scramble).map(|_| ()).map_err(|error| error.to_string())
+}
+
+fn is_valid_move(token: &str) -> bool {
+    let rest = token.trim_start_matches(|c: char| c.is_ascii_digit());
+    let (base, variant) = match rest.find(['2', '\'']) {
+        Some(i) => rest.split_at(i),
+        None => (rest, ""),
+    };
+    let valid_base = matches!(base, "U" | "R" | "F" | "L" | "D" | "B")
+        || matches!(base, "x" | "y" | "z" | "X" | "Y" | "Z")
+        || (base.len() == 2 && base.ends_with('w') && "URFLDB".contains(&base[..1]));
+    let prefix = &token[..token.len() - rest.len()];
+    // the layers count from 1, and a number too large for a ``CubeSize`` is no layer of any cube
+    let valid_prefix = prefix.is_empty() || prefix.parse::<CubeSize>().is_ok_and(|layer| layer > 0);
+
+    valid_base
+        && valid_prefix
+        && (prefix.is_empty() || !base.starts_with(['x', 'y', 'z', 'X', 'Y', 'Z']))
+        && ["", "2", "'", "2'"].contains(&variant)
+}
+
 fn convert_move(mv: &str) -> Move {
     let slice = get_slice(mv);
     let variant = get_variant(mv);
+    let base = mv.trim_start_matches(|c: char| c.is_ascii_digit());
 
-    if !mv.contains('w') {
+    if base.len() != mv.len() && !mv.contains('w') {
+        match &base[0..1] {
+            "U" => Ui(slice, variant),
+            "R" => Ri(slice, variant),
+            "F" => Fi(slice, variant),
+            "L" => Li(slice, variant),
+            "D" => Di(slice, variant),
+            "B" => Bi(slice, variant),
+            _ => panic!(),
+        }
+    } else if !mv.contains('w') {
         match &mv[0..1] {
             "U" => U(variant),
             "R" => R(variant),
@@ -18,9 +147,9 @@ fn convert_move(mv: &str) -> Move {
             "L" => L(variant),
             "D" => D(variant),
             "B" => B(variant),
-            "x" => X(variant),
-            "y" => Y(variant),
-            "z" => Z(variant),
+            "x" | "X" => X(variant),
+            "y" | "Y" => Y(variant),
+            "z" | "Z" => Z(variant),
             _ => panic!(),
         }
     } else if mv.contains('U') {
@@ -47,14 +176,19 @@ fn convert_move(mv: &str) -> Move {
 }
 
 fn get_slice(mv: &str) -> CubeSize {
-    if !mv.contains('w') {
-        1
-    } else {
-        mv[0..1].parse::<CubeSize>().unwrap_or(2)
+    let digits = mv.len() - mv.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+
+    match &mv[..digits] {
+        "" if mv.contains('w') => 2,
+        "" => 1,
+        digits => digits.parse().expect("layer number too large"),
     }
 }
 
 fn get_variant(mv: &str) -> MoveVariant {
+    // skip the layer number, so ``2R`` isn't taken for a double turn
+    let mv = mv.trim_start_matches(|c: char| c.is_ascii_digit());
+
     if mv.contains('2') {
         Double
     } else if mv.contains('\'') {
@@ -84,9 +218,8 @@ fn get_variant(mv: &str) -> MoveVariant {
 /// assert_eq!(simplified, vec![X(Standard)]);
 /// ```
 pub fn simplify_moves(moves: &[Move]) -> Vec<Move> {
-    // Recursively merges adjacent moves of the same Move discriminant
+    // Recursively merges adjacent moves of the same Move and layers
     // until no further simplification is possible.
-    use std::mem::discriminant;
     let mut result = vec![];
     if moves.is_empty() {
         return result;
@@ -112,9 +245,10 @@ pub fn simplify_moves(moves: &[Move]) -> Vec<Move> {
         }
     }
 
-    // merge adjacent moves of the same type
+    // merge adjacent moves of the same type and layers
     for mv in moves[1..].iter() {
-        if discriminant(&movement.mv) == discriminant(mv) {
+        if movement.mv.with_variant(MoveVariant::Standard) == mv.with_variant(MoveVariant::Standard)
+        {
             movement.total_turns = (movement.total_turns + mv.get_variant() as u8) % 4;
         } else {
             if let Some(m) = movement_to_move(movement) {
@@ -137,87 +271,291 @@ pub fn simplify_moves(moves: &[Move]) -> Vec<Move> {
     simplify_moves(result.as_slice())
 }
 
-pub fn random_scramble(cube_size: CubeSize, has_move_slice: bool) -> Vec<Move> {
-    let mut rng = rand::thread_rng();
-    let mut scramble = vec![];
-    let mut last_move = None;
-    let mut last_move_variant = None;
-    let mut last_move_slice = None;
-
-    for _ in 0..(cube_size * 10) {
-        let mut move_variant: MoveVariant = rand::random();
-        let mut move_slice = 1;
-        // not gen x y z
-        let mut move_type = rng.gen_range(0..=5);
-
-        // don't allow the same move twice in a row
-        if let Some(last_move) = last_move {
-            if move_type == last_move {
-                move_type = (move_type + 1) % 6;
+/// The options of ``simplify_moves_with``.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct SimplifyOptions {
+    /// Whether the moves may rotate the whole cube, or are rewritten without rotations by
+    /// ``remove_rotations`` first.
+    pub rotations: bool,
+}
+
+/// Merges adjacent moves like ``simplify_moves``, with the options.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::{parse_scramble, simplify_moves_with, SimplifyOptions};
+/// use cubesim::prelude::{Move::*, MoveVariant::*};
+///
+/// let moves = parse_scramble(String::from("R x U x' F"));
+/// let simplified = simplify_moves_with(&moves, SimplifyOptions::default());
+/// assert_eq!(simplified, vec![R(Standard), F(Double)]);
+/// ```
+pub fn simplify_moves_with(moves: &[Move], options: SimplifyOptions) -> Vec<Move> {
+    if options.rotations {
+        simplify_moves(moves)
+    } else {
+        simplify_moves(&remove_rotations(moves))
+    }
+}
+
+/// Rewrites moves without the cube rotations among them, each move after a rotation turning
+/// the layers it turned on the rotated cube instead. The cube ends up turned the same, only
+/// held as it was at the start, as needed by robots and by anyone following along on a cube
+/// they don't rotate.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::{Cube, Move::*, MoveVariant::*, TwistyPuzzle};
+/// use cubesim::{parse_scramble, remove_rotations, FaceletCube};
+///
+/// let moves = parse_scramble(String::from("y R U' x2 Rw2"));
+/// let rewritten = remove_rotations(&moves);
+/// assert_eq!(rewritten, vec![B(Standard), U(Inverse), Bw(2, Double)]);
+///
+/// let rotated = FaceletCube::new(3).apply_moves(&rewritten).apply_moves(&[Y(Standard), X(Double)]);
+/// assert_eq!(rotated, FaceletCube::new(3).apply_moves(&moves));
+/// ```
+pub fn remove_rotations(moves: &[Move]) -> Vec<Move> {
+    // the side of the cube held at each side, in the order U, L, F, R, B, D
+    let mut held = [0, 1, 2, 3, 4, 5];
+    let mut result = vec![];
+    for mv in moves {
+        // each side of a cycle is turned into the one before it
+        let cycle = match mv {
+            X(_) => [0, 2, 5, 4],
+            Y(_) => [2, 3, 4, 1],
+            Z(_) => [0, 1, 5, 3],
+            _ => {
+                result.push(turning_side(*mv, held[side(*mv)]));
+                continue;
             }
+        };
+        for _ in 0..mv.get_variant() as u8 {
+            let first = held[cycle[0]];
+            for i in 0..3 {
+                held[cycle[i]] = held[cycle[i + 1]];
+            }
+            held[cycle[3]] = first;
         }
+    }
+    result
+}
 
-        // don't allow the same move variant twice in a row
-        if let Some(last_move_variant) = last_move_variant {
-            if move_variant == last_move_variant {
-                move_variant = match move_variant {
-                    Standard => Inverse,
-                    Inverse => Double,
-                    Double => Standard,
-                }
-            }
+// The side of the cube turned by a move, in the order U, L, F, R, B, D
+fn side(mv: Move) -> usize {
+    const SIDES: [Face; 6] = [Face::U, Face::L, Face::F, Face::R, Face::B, Face::D];
+    SIDES.iter().position(|&face| face == mv.base_face()).unwrap()
+}
+
+// The move turning the same layers as a face turn, wide or slice move, from another side
+fn turning_side(mv: Move, side: usize) -> Move {
+    const FACE_TURNS: [fn(MoveVariant) -> Move; 6] = [U, L, F, R, B, D];
+    const WIDE_MOVES: [fn(CubeSize, MoveVariant) -> Move; 6] = [Uw, Lw, Fw, Rw, Bw, Dw];
+    const SLICES: [fn(CubeSize, MoveVariant) -> Move; 6] = [Ui, Li, Fi, Ri, Bi, Di];
+
+    match mv {
+        U(variant) | L(variant) | F(variant) | R(variant) | B(variant) | D(variant) => FACE_TURNS[side](variant),
+        Uw(n, variant) | Lw(n, variant) | Fw(n, variant) | Rw(n, variant) | Bw(n, variant) | Dw(n, variant) => {
+            WIDE_MOVES[side](n, variant)
         }
+        Ui(n, variant) | Li(n, variant) | Fi(n, variant) | Ri(n, variant) | Bi(n, variant) | Di(n, variant) => {
+            SLICES[side](n, variant)
+        }
+        X(_) | Y(_) | Z(_) => mv,
+    }
+}
 
-        // don't allow the same move slice twice in a row
-        if let Some(last_move_slice) = last_move_slice {
-            if move_slice == last_move_slice {
-                move_slice = (move_slice + 1) % cube_size;
-            }
+/// How ``format_scramble`` lays out the moves of a scramble.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ScrambleFormat {
+    /// The number of moves of each group, the groups being separated by two spaces, or none
+    /// for the moves to be separated by a single space.
+    pub group: Option<usize>,
+    /// The most characters of a line, not counting its number. Lines are only broken between
+    /// groups, so a group wider than this has a line of its own, or none for a single line.
+    pub width: Option<usize>,
+    /// Whether each line starts with its number, from 1.
+    pub numbered: bool,
+}
+
+impl Default for ScrambleFormat {
+    /// Groups of five moves on a single line, as on the scramble sheets of competitions.
+    fn default() -> Self {
+        ScrambleFormat {
+            group: Some(5),
+            width: None,
+            numbered: false,
         }
+    }
+}
+
+/// Writes moves in WCA notation, laid out in groups and lines for them to be read or called out
+/// more easily.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::{format_scramble, parse_scramble, ScrambleFormat};
+///
+/// let scramble = parse_scramble(String::from("R U' F2 D L2 B R' U2 F L' D2 B'"));
+/// assert_eq!(
+///     format_scramble(&scramble, ScrambleFormat::default()),
+///     "R U' F2 D L2  B R' U2 F L'  D2 B'"
+/// );
+///
+/// let format = ScrambleFormat {
+///     group: Some(4),
+///     width: Some(24),
+///     numbered: true,
+/// };
+/// assert_eq!(
+///     format_scramble(&scramble, format),
+///     "1. R U' F2 D  L2 B R' U2\n2. F L' D2 B'"
+/// );
+/// ```
+pub fn format_scramble(moves: &[Move], format: ScrambleFormat) -> String {
+    let moves: Vec<String> = moves.iter().map(|mv| mv.to_string()).collect();
+    let (groups, separator): (Vec<String>, &str) = match format.group {
+        Some(size) if size > 0 => (
+            moves.chunks(size).map(|group| group.join(" ")).collect(),
+            "  ",
+        ),
+        _ => (moves, " "),
+    };
 
-        // don't allow the same move slice twice in a row
-        if rng.gen_bool(0.5) {
-            move_slice = rng.gen_range(1..cube_size);
+    let mut lines: Vec<String> = vec![];
+    for group in groups.into_iter().filter(|group| !group.is_empty()) {
+        match lines.last_mut() {
+            Some(line)
+                if format
+                    .width
+                    .is_none_or(|width| line.len() + separator.len() + group.len() <= width) =>
+            {
+                line.push_str(separator);
+                line.push_str(&group);
+            }
+            _ => lines.push(group),
         }
+    }
 
-        let mv = match move_type {
-            0 => U(move_variant),
-            1 => R(move_variant),
-            2 => F(move_variant),
-            3 => L(move_variant),
-            4 => D(move_variant),
-            5 => B(move_variant),
-            6 => X(move_variant),
-            7 => Y(move_variant),
-            8 => Z(move_variant),
-            _ => panic!(),
+    if !format.numbered {
+        return lines.join("\n");
+    }
+    let digits = lines.len().to_string().len();
+    lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| format!("{:>digits$}. {line}", i + 1))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The options of ``random_scramble_with``.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct ScrambleOptions {
+    /// The number of moves, or none for ten times the size of the cube.
+    pub length: Option<usize>,
+    /// Whether the moves may turn several layers, as needed to scramble the inner layers of
+    /// cubes bigger than 3x3x3.
+    pub wide_moves: bool,
+    /// Whether the scramble may rotate the whole cube.
+    pub rotations: bool,
+    /// The seed of the random moves, the same seed making the same scramble, or none for a
+    /// new scramble each time.
+    pub seed: Option<u64>,
+}
+
+/// A random scramble of a cube of the given size, with wide moves if ``has_move_slice``.
+#[cfg(feature = "std")]
+pub fn random_scramble(cube_size: CubeSize, has_move_slice: bool) -> Vec<Move> {
+    random_scramble_with(
+        cube_size,
+        ScrambleOptions {
+            wide_moves: has_move_slice,
+            ..ScrambleOptions::default()
+        },
+    )
+}
+
+/// A random scramble of a cube of the given size, of the length and the kinds of moves of the
+/// options.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::Move;
+/// use cubesim::{random_scramble_with, ScrambleOptions};
+///
+/// let options = ScrambleOptions {
+///     length: Some(30),
+///     rotations: true,
+///     seed: Some(42),
+///     ..ScrambleOptions::default()
+/// };
+/// let scramble = random_scramble_with(3, options);
+///
+/// assert_eq!(scramble.len(), 30);
+/// assert!(scramble.iter().all(|mv| !matches!(mv, Move::Rw(..) | Move::Uw(..) | Move::Fw(..))));
+/// assert_eq!(random_scramble_with(3, options), scramble);
+/// ```
+#[cfg(feature = "std")]
+pub fn random_scramble_with(cube_size: CubeSize, options: ScrambleOptions) -> Vec<Move> {
+    match options.seed {
+        Some(seed) => scramble_moves(&mut StdRng::seed_from_u64(seed), cube_size, options),
+        None => scramble_moves(&mut rand::thread_rng(), cube_size, options),
+    }
+}
+
+// The types of move of the scrambles, the face turns, then the rotations
+#[cfg(feature = "std")]
+const MOVE_TYPES: [fn(MoveVariant) -> Move; 9] = [U, R, F, L, D, B, X, Y, Z];
+
+// No two moves in a row turn the same face, and no three in a row turn the same axis, as
+// "R L R" is only "R2 L" and "U D U'" only "D"
+#[cfg(feature = "std")]
+fn scramble_moves(rng: &mut impl Rng, cube_size: CubeSize, options: ScrambleOptions) -> Vec<Move> {
+    let move_types = if options.rotations { 9 } else { 6 };
+    let axis = |move_type: usize| MOVE_TYPES[move_type](Standard).axis();
+    let mut scramble = vec![];
+    // the types of the last two moves, the last one first
+    let mut last_moves: [Option<usize>; 2] = [None, None];
+
+    for _ in 0..options.length.unwrap_or(cube_size as usize * 10) {
+        let axis_used_twice = match last_moves {
+            [Some(last), Some(before)] if axis(last) == axis(before) => Some(axis(last)),
+            _ => None,
         };
+        let allowed: Vec<usize> = (0..move_types)
+            .filter(|&move_type| Some(move_type) != last_moves[0])
+            .filter(|&move_type| Some(axis(move_type)) != axis_used_twice)
+            .collect();
+        let move_type = allowed[rng.gen_range(0..allowed.len())];
+        let move_variant: MoveVariant = rng.gen();
 
-        let mv = if has_move_slice {
-            match move_slice {
-                1 => mv,
-
-                _ => match mv {
-                    U(variant) => Uw(move_slice, variant),
-                    R(variant) => Rw(move_slice, variant),
-                    F(variant) => Fw(move_slice, variant),
-                    L(variant) => Lw(move_slice, variant),
-                    D(variant) => Dw(move_slice, variant),
-                    B(variant) => Bw(move_slice, variant),
-                    X(variant) => X(variant),
-                    Y(variant) => Y(variant),
-                    Z(variant) => Z(variant),
-                    _ => panic!(),
-                },
-            }
+        // half of the face turns of a scramble with wide moves turn several layers
+        let move_slice = if options.wide_moves && cube_size > 1 && rng.gen_bool(0.5) {
+            rng.gen_range(1..cube_size)
         } else {
-            mv
+            1
+        };
+
+        let mv = MOVE_TYPES[move_type](move_variant);
+
+        let mv = match (move_slice, mv) {
+            (1, mv) => mv,
+            (_, U(variant)) => Uw(move_slice, variant),
+            (_, R(variant)) => Rw(move_slice, variant),
+            (_, F(variant)) => Fw(move_slice, variant),
+            (_, L(variant)) => Lw(move_slice, variant),
+            (_, D(variant)) => Dw(move_slice, variant),
+            (_, B(variant)) => Bw(move_slice, variant),
+            (_, mv) => mv,
         };
 
         scramble.push(mv);
-        last_move = Some(move_type);
-        last_move_variant = Some(move_variant);
-        last_move_slice = Some(move_slice);
+        last_moves = [Some(move_type), last_moves[0]];
     }
 
     scramble
@@ -0,0 +1,247 @@
+use std::{
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+    thread,
+    time::Duration,
+};
+
+use crate::{
+    facelet_cube::FaceletCube,
+    generic_cube::{Cube, CubeSize, Move},
+    generic_puzzle::TwistyPuzzle,
+    methods::SolveMethod,
+    report::json_string,
+    scramble_parser::{parse_algorithm_for, random_scramble, simplify_moves, validate_scramble},
+    svg::net_svg,
+};
+
+/// The largest cube the server will scramble or render.
+const MAX_CUBE_SIZE: CubeSize = 20;
+
+/// The largest request body the server will read, far more than any scramble needs.
+const MAX_BODY_LEN: usize = 64 * 1024;
+
+/// How long a client may keep the server waiting for its request before it is dropped.
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// An HTTP response produced by the server.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: String,
+}
+
+impl Response {
+    fn json(status: u16, body: String) -> Self {
+        Self {
+            status,
+            content_type: "application/json",
+            body,
+        }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Self::json(status, format!(r#"{{"error":{}}}"#, json_string(message)))
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            413 => "Payload Too Large",
+            _ => "Internal Server Error",
+        }
+    }
+}
+
+/// Serves the JSON API over HTTP, handling every connection on its own thread.
+///
+/// This only returns if the address can't be bound. See ``handle_request`` for the endpoints.
+pub fn serve(addr: impl ToSocketAddrs) -> io::Result<()> {
+    let listener = TcpListener::bind(addr)?;
+
+    for stream in listener.incoming().flatten() {
+        thread::spawn(move || {
+            // a client hanging up mid request is not an error of the server
+            let _ = handle_connection(stream);
+        });
+    }
+
+    Ok(())
+}
+
+fn handle_connection(mut stream: TcpStream) -> io::Result<()> {
+    // a client that never finishes its request would hold its thread forever
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let (method, target) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+
+    let response = if content_length > MAX_BODY_LEN {
+        Response::error(413, "request body too large")
+    } else {
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body)?;
+        handle_request(method, target, &String::from_utf8_lossy(&body))
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nAccess-Control-Allow-Origin: *\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        response.reason(),
+        response.content_type,
+        response.body.len(),
+        response.body
+    )
+}
+
+/// Handles a single request of the JSON API.
+///
+/// Parameters are read from the query string, and a ``POST`` body is used as the
+/// ``scramble`` parameter. The endpoints are:
+///
+/// - ``/solve?scramble=...``: solves a 3x3x3 scramble with the Thistlethwaite algorithm,
+///   without cube rotations, for the cube held as the scramble left it.
+/// - ``/scramble?size=3``: generates a random scramble.
+/// - ``/validate?scramble=...``: checks whether a scramble can be parsed.
+/// - ``/image?scramble=...&size=3``: renders the scrambled cube as an SVG net.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::handle_request;
+///
+/// let response = handle_request("GET", "/validate?scramble=R%20U%27", "");
+/// assert_eq!(response.status, 200);
+/// assert_eq!(response.body, r#"{"valid":true}"#);
+/// ```
+pub fn handle_request(method: &str, target: &str, body: &str) -> Response {
+    if method != "GET" && method != "POST" {
+        return Response::error(405, "only GET and POST are supported");
+    }
+
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let param = |name: &str| {
+        query
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| percent_decode(value))
+    };
+    let scramble = if method == "POST" {
+        body.trim().to_string()
+    } else {
+        param("scramble").unwrap_or_default()
+    };
+    let size = match param("size").map(|s| s.parse::<CubeSize>()) {
+        None => 3,
+        Some(Ok(size)) if (2..=MAX_CUBE_SIZE).contains(&size) => size,
+        Some(_) => return Response::error(400, "invalid cube size"),
+    };
+
+    match path {
+        "/solve" => {
+            let cube = match parse_algorithm_for(&scramble, 3) {
+                Ok(moves) => FaceletCube::new(3).apply_moves(&moves),
+                Err(error) => return Response::error(400, &error.to_string()),
+            };
+
+            // rotations, slices and wide moves of the scramble turn the centers
+            match SolveMethod::Thistlethwaite.solve(&cube, &|| false) {
+                Some(solution) => {
+                    let solution = simplify_moves(&solution.without_rotations().moves());
+                    Response::json(
+                        200,
+                        format!(
+                            r#"{{"scramble":{},"solution":{},"length":{}}}"#,
+                            json_string(&scramble),
+                            json_string(&moves_string(&solution)),
+                            solution.len()
+                        ),
+                    )
+                }
+                None => Response::error(400, "no solution found"),
+            }
+        }
+        "/scramble" => Response::json(
+            200,
+            format!(
+                r#"{{"size":{size},"scramble":{}}}"#,
+                json_string(&moves_string(&random_scramble(size, size > 3)))
+            ),
+        ),
+        "/validate" => match validate_scramble(&scramble) {
+            Ok(()) => Response::json(200, r#"{"valid":true}"#.to_string()),
+            Err(error) => Response::json(
+                200,
+                format!(r#"{{"valid":false,"error":{}}}"#, json_string(&error)),
+            ),
+        },
+        "/image" => {
+            let moves = match parse_algorithm_for(&scramble, size) {
+                Ok(moves) => moves,
+                Err(error) => return Response::error(400, &error.to_string()),
+            };
+
+            Response {
+                status: 200,
+                content_type: "image/svg+xml",
+                body: net_svg(&FaceletCube::new(size).apply_moves(&moves)),
+            }
+        }
+        _ => Response::error(404, "unknown endpoint"),
+    }
+}
+
+fn moves_string(moves: &[Move]) -> String {
+    moves
+        .iter()
+        .map(|mv| mv.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+                match hex.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+                    Some(byte) => {
+                        decoded.push(byte);
+                        i += 2;
+                    }
+                    None => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
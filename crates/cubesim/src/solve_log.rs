@@ -0,0 +1,201 @@
+use alloc::{format, string::String, string::ToString, vec::Vec};
+use core::time::Duration;
+
+use crate::generic_cube::Move;
+use crate::json::Json;
+use crate::report::SolveReport;
+use crate::scramble_parser::{parse_scramble, validate_scramble};
+use crate::stats::{Penalty, TimedMove};
+
+/// A solve of a 3x3x3 smart cube read from the log of another app, its moves timed as the cube
+/// sent them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LoggedSolve {
+    /// The moves scrambling the solved cube.
+    pub scramble: Vec<Move>,
+    /// The moves of the solve, timed from the first one.
+    pub solution: Vec<TimedMove>,
+    /// The penalty given to the solve, if any.
+    pub penalty: Option<Penalty>,
+}
+
+impl LoggedSolve {
+    /// The report of the solve, with the times of its CFOP phases. Every pause between moves
+    /// longer than ``pause`` is reported.
+    pub fn report(&self, pause: Duration) -> SolveReport {
+        let mut report = SolveReport::new(3, &self.scramble, &self.solution, pause);
+        report.penalty = self.penalty;
+        report
+    }
+}
+
+/// Parses the moves of a smart cube solve, each followed by ``@`` and the milliseconds since
+/// the timer started, like ``R@120 U@340 R'@515``. The times are given from the first move.
+///
+/// # Examples
+///
+/// ```rust
+/// use core::time::Duration;
+/// use cubesim::parse_timed_moves;
+/// use cubesim::prelude::{Move, MoveVariant::*};
+///
+/// let moves = parse_timed_moves("R@120 U@340 R'@515").unwrap();
+/// assert_eq!(moves[1].mv, Move::U(Standard));
+/// assert_eq!(moves[1].at, Duration::from_millis(220));
+///
+/// assert!(parse_timed_moves("R@120 U").is_err());
+/// ```
+pub fn parse_timed_moves(moves: &str) -> Result<Vec<TimedMove>, String> {
+    let mut timed = Vec::new();
+    for token in moves.split_whitespace() {
+        let Some((mv, millis)) = token.split_once('@') else {
+            return Err(format!("untimed move {token}"));
+        };
+        validate_scramble(mv)?;
+        let millis: u64 = millis
+            .parse()
+            .map_err(|_| format!("invalid time of move {token}"))?;
+        for mv in parse_scramble(mv.to_string()) {
+            timed.push(TimedMove {
+                mv,
+                at: Duration::from_millis(millis),
+            });
+        }
+    }
+
+    let start = timed.first().map_or(Duration::ZERO, |first| first.at);
+    for timed in &mut timed {
+        timed.at = timed.at.saturating_sub(start);
+    }
+    Ok(timed)
+}
+
+/// Reads the smart cube solves of a csTimer export, a JSON object of sessions. Each solve of a
+/// session is ``[[penalty, milliseconds], scramble, comment, date, [moves, puzzle]]``, its
+/// moves timed like ``parse_timed_moves`` reads them and its penalty 0, 2000 for a +2 or -1
+/// for a DNF. The solves timed without a smart cube are skipped.
+pub fn read_cstimer_export(text: &str) -> Result<Vec<LoggedSolve>, String> {
+    let Json::Object(members) = Json::parse(text)? else {
+        return Err(String::from("expected an object of sessions"));
+    };
+
+    let mut solves = Vec::new();
+    for (name, session) in members.iter().filter(|(name, _)| name.starts_with("session")) {
+        // older versions of csTimer export each session as a string of JSON
+        let session = match session {
+            Json::String(text) => Json::parse(text)?,
+            session => session.clone(),
+        };
+        for (i, solve) in session.as_array().unwrap_or_default().iter().enumerate() {
+            let fields = solve.as_array().unwrap_or_default();
+            let moves = match fields.get(4) {
+                Some(Json::Array(moves)) => moves.first().and_then(Json::as_str),
+                Some(moves) => moves.as_str(),
+                None => None,
+            };
+            let Some(moves) = moves.filter(|moves| !moves.trim().is_empty()) else {
+                continue;
+            };
+            let in_solve = |err: String| format!("{name}, solve {}: {err}", i + 1);
+
+            let scramble = fields.get(1).and_then(Json::as_str).unwrap_or_default();
+            validate_scramble(scramble).map_err(in_solve)?;
+            let penalty = fields
+                .first()
+                .and_then(Json::as_array)
+                .and_then(|time| time.first())
+                .and_then(Json::as_f64);
+            solves.push(LoggedSolve {
+                scramble: parse_scramble(scramble.to_string()),
+                solution: parse_timed_moves(moves).map_err(in_solve)?,
+                penalty: match penalty {
+                    Some(penalty) if penalty < 0.0 => Some(Penalty::Dnf),
+                    Some(penalty) if penalty > 0.0 => Some(Penalty::PlusTwo),
+                    _ => None,
+                },
+            });
+        }
+    }
+    Ok(solves)
+}
+
+/// Reads the solves of a GAN app export, a CSV file with a header naming its columns. The
+/// ``Scramble`` and ``Solution`` columns are read, the solution's moves timed like
+/// ``parse_timed_moves`` reads them, and the ``Penalty`` column if any, ``+2`` or ``DNF``.
+pub fn read_gan_export(text: &str) -> Result<Vec<LoggedSolve>, String> {
+    let mut rows = text.lines().filter(|line| !line.trim().is_empty());
+    let header = csv_fields(rows.next().unwrap_or_default());
+    let column = |name: &str| {
+        header
+            .iter()
+            .position(|field| field.trim().eq_ignore_ascii_case(name))
+    };
+    let (Some(scramble_column), Some(solution_column)) = (column("scramble"), column("solution"))
+    else {
+        return Err(String::from("expected Scramble and Solution columns"));
+    };
+    let penalty_column = column("penalty");
+
+    let mut solves = Vec::new();
+    for (i, row) in rows.enumerate() {
+        let fields = csv_fields(row);
+        let field = |column: usize| fields.get(column).map_or("", |field| field.trim());
+        let in_solve = |err: String| format!("solve {}: {err}", i + 1);
+
+        validate_scramble(field(scramble_column)).map_err(in_solve)?;
+        solves.push(LoggedSolve {
+            scramble: parse_scramble(field(scramble_column).to_string()),
+            solution: parse_timed_moves(field(solution_column)).map_err(in_solve)?,
+            penalty: match penalty_column.map(field) {
+                Some("+2") => Some(Penalty::PlusTwo),
+                Some("DNF") => Some(Penalty::Dnf),
+                _ => None,
+            },
+        });
+    }
+    Ok(solves)
+}
+
+/// Reads the solves of the log of a smart cube app, either a csTimer export or a GAN app
+/// export.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::read_solve_log;
+///
+/// let log = "Time,Scramble,Solution\n0.52,R U,U'@0 R'@520\n";
+/// let solves = read_solve_log(log).unwrap();
+/// assert_eq!(solves.len(), 1);
+/// let report = solves[0].report(core::time::Duration::from_secs(1));
+/// assert!(report.reconstruction().starts_with("U' R' // Cross (2 moves, 0.52 s)"));
+/// ```
+pub fn read_solve_log(text: &str) -> Result<Vec<LoggedSolve>, String> {
+    let solves = if text.trim_start().starts_with('{') {
+        read_cstimer_export(text)?
+    } else {
+        read_gan_export(text)?
+    };
+    if solves.is_empty() {
+        return Err(String::from("no smart cube solves found"));
+    }
+    Ok(solves)
+}
+
+// The fields of a line of CSV, the quoted ones unquoted
+fn csv_fields(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.next_if_eq(&'"').is_some() => field.push('"'),
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(core::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
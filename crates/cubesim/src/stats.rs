@@ -0,0 +1,327 @@
+use alloc::vec::Vec;
+use core::{
+    fmt::{Display, Formatter},
+    time::Duration,
+};
+
+use crate::beginner::{layer, orientations};
+use crate::generic_cube::{
+    all_moves, piece_type, solved_state, Cube, CubeSize, Face, Move, PieceType,
+};
+
+/// A step of the CFOP method on the 3x3x3 cube, on any face.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum CfopPhase {
+    /// The four edges of the first layer.
+    Cross,
+    /// The first two layers.
+    F2l,
+    /// The orientation of the last layer.
+    Oll,
+    /// The permutation of the last layer, solving the cube.
+    Pll,
+}
+
+impl CfopPhase {
+    /// Every phase, in the order they are solved.
+    pub const ALL: [CfopPhase; 4] = [
+        CfopPhase::Cross,
+        CfopPhase::F2l,
+        CfopPhase::Oll,
+        CfopPhase::Pll,
+    ];
+
+    // Masks the stickers of a 3x3x3 cube the phase doesn't solve, solving the cross on D
+    pub(crate) fn mask(self, index: CubeSize, face: Face) -> Face {
+        let piece = piece_type(3, index);
+        let solved = match self {
+            CfopPhase::Cross => {
+                piece == PieceType::Center || (layer(index) == Face::D && piece == PieceType::Edge)
+            }
+            CfopPhase::F2l => layer(index) != Face::U,
+            CfopPhase::Oll => layer(index) != Face::U || face == Face::U,
+            CfopPhase::Pll => true,
+        };
+
+        if solved {
+            face
+        } else {
+            Face::X
+        }
+    }
+
+    /// Whether the phase is done on a 3x3x3 cube, however the cube is held.
+    pub fn is_done(self, cube: &impl Cube) -> bool {
+        let goal: Vec<Face> = solved_state(3)
+            .into_iter()
+            .enumerate()
+            .map(|(i, face)| self.mask(i as CubeSize, face))
+            .collect();
+        let masked = cube.mask(&|i, f| self.mask(i, f));
+
+        orientations().any(|rotation| masked.apply_moves(&rotation).state() == goal)
+    }
+}
+
+impl Display for CfopPhase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let name = match self {
+            CfopPhase::Cross => "Cross",
+            CfopPhase::F2l => "F2L",
+            CfopPhase::Oll => "OLL",
+            CfopPhase::Pll => "PLL",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// A move made during a solve, and when it was made since the first move.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct TimedMove {
+    /// The move made.
+    pub mv: Move,
+    /// The time since the first move of the solve.
+    pub at: Duration,
+}
+
+/// A pause between two moves of a solve, such as for looking ahead or recognizing a case.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Pause {
+    /// The index of the move made after the pause.
+    pub before: usize,
+    /// The time since the previous move.
+    pub length: Duration,
+}
+
+/// The moves and time spent on a CFOP phase of a solve.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct PhaseTime {
+    /// The phase solved.
+    pub phase: CfopPhase,
+    /// The number of moves made during the phase.
+    pub moves: usize,
+    /// The time from the end of the previous phase to the last move of this one.
+    pub duration: Duration,
+}
+
+/// The timing statistics of a solve.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SolveStats {
+    /// The number of moves made.
+    pub moves: usize,
+    /// The time from the first move to the last one.
+    pub duration: Duration,
+    /// The turns per second.
+    pub tps: f32,
+    /// The pauses longer than the threshold given.
+    pub pauses: Vec<Pause>,
+    /// The CFOP phases, as far as they were solved, for 3x3x3 cubes only.
+    pub phases: Vec<PhaseTime>,
+}
+
+/// Computes the statistics of a solve of a scrambled cube, given the timed moves made.
+///
+/// Every pause between moves longer than ``pause`` is reported.
+///
+/// # Examples
+///
+/// ```rust
+/// use core::time::Duration;
+/// use cubesim::prelude::{Cube, Move, MoveVariant::*, TwistyPuzzle};
+/// use cubesim::{solve_stats, FaceletCube, TimedMove};
+///
+/// let scrambled = FaceletCube::new(3).apply_move(Move::R(Standard));
+/// let solve = [
+///     TimedMove { mv: Move::R(Standard), at: Duration::ZERO },
+///     TimedMove { mv: Move::R(Double), at: Duration::from_millis(2500) },
+/// ];
+/// let stats = solve_stats(&scrambled, &solve, Duration::from_secs(1));
+///
+/// assert_eq!(stats.tps, 0.8);
+/// assert_eq!(stats.pauses.len(), 1);
+/// assert_eq!(stats.pauses[0].before, 1);
+/// ```
+pub fn solve_stats(scrambled: &impl Cube, solve: &[TimedMove], pause: Duration) -> SolveStats {
+    let duration = solve.last().map_or(Duration::ZERO, |last| last.at);
+    let tps = if duration.is_zero() {
+        0.0
+    } else {
+        solve.len() as f32 / duration.as_secs_f32()
+    };
+
+    let pauses = solve
+        .windows(2)
+        .enumerate()
+        .map(|(i, pair)| Pause {
+            before: i + 1,
+            length: pair[1].at.saturating_sub(pair[0].at),
+        })
+        .filter(|p| p.length > pause)
+        .collect();
+
+    SolveStats {
+        moves: solve.len(),
+        duration,
+        tps,
+        pauses,
+        phases: if scrambled.size() == 3 {
+            phase_times(scrambled, solve)
+        } else {
+            Vec::new()
+        },
+    }
+}
+
+fn phase_times(scrambled: &impl Cube, solve: &[TimedMove]) -> Vec<PhaseTime> {
+    let mut phases = CfopPhase::ALL.into_iter().peekable();
+    let mut times = Vec::new();
+    // the number of moves made and the time at the end of the last phase
+    let mut phase_start = (0, Duration::ZERO);
+
+    let mut cube = scrambled.clone();
+    for made in 0..=solve.len() {
+        if made > 0 {
+            cube = cube.apply_move(solve[made - 1].mv);
+        }
+        let at = made.checked_sub(1).map_or(Duration::ZERO, |i| solve[i].at);
+
+        // a move can finish several phases at once, like a lucky OLL skipping the PLL
+        while let Some(&phase) = phases.peek() {
+            if !phase.is_done(&cube) {
+                break;
+            }
+            times.push(PhaseTime {
+                phase,
+                moves: made - phase_start.0,
+                duration: at.saturating_sub(phase_start.1),
+            });
+            phase_start = (made, at);
+            phases.next();
+        }
+    }
+
+    times
+}
+
+/// A penalty given to a solve, as in the WCA regulations.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Penalty {
+    /// Two seconds added to the time, such as for a cube left one move from solved.
+    PlusTwo,
+    /// Did not finish: the solve counts as worse than any time.
+    Dnf,
+}
+
+/// Written as on a score sheet, ``+2`` or ``DNF``.
+impl Display for Penalty {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let name = match self {
+            Penalty::PlusTwo => "+2",
+            Penalty::Dnf => "DNF",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// The penalty of a solve whose timer was stopped with the cube in the given state: none once
+/// solved, a +2 while one move from solved, and a DNF further away.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::{Cube, Move, MoveVariant::*, TwistyPuzzle};
+/// use cubesim::{penalty_at_stop, FaceletCube, Penalty};
+///
+/// let cube = FaceletCube::new(3).apply_move(Move::U(Double));
+/// assert_eq!(penalty_at_stop(&cube), Some(Penalty::PlusTwo));
+/// assert_eq!(penalty_at_stop(&cube.apply_move(Move::R(Standard))), Some(Penalty::Dnf));
+/// ```
+pub fn penalty_at_stop(cube: &impl Cube) -> Option<Penalty> {
+    if cube.is_solved() {
+        None
+    } else if all_moves(cube.size())
+        .into_iter()
+        .any(|mv| cube.apply_move(mv).is_solved())
+    {
+        Some(Penalty::PlusTwo)
+    } else {
+        Some(Penalty::Dnf)
+    }
+}
+
+/// The time of a solve and its penalty, if any.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct SolveResult {
+    /// The time on the timer.
+    pub time: Duration,
+    /// The penalty given.
+    pub penalty: Option<Penalty>,
+}
+
+impl SolveResult {
+    /// A solve without a penalty.
+    pub fn new(time: Duration) -> Self {
+        SolveResult {
+            time,
+            penalty: None,
+        }
+    }
+
+    /// The time counted, two seconds longer for a +2, or ``None`` for a DNF.
+    pub fn counted(&self) -> Option<Duration> {
+        match self.penalty {
+            None => Some(self.time),
+            Some(Penalty::PlusTwo) => Some(self.time + Duration::from_secs(2)),
+            Some(Penalty::Dnf) => None,
+        }
+    }
+}
+
+/// Written in seconds to the hundredth, with a ``+`` after a +2 and ``DNF`` for a DNF.
+impl Display for SolveResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match (self.counted(), self.penalty) {
+            (Some(time), Some(Penalty::PlusTwo)) => write!(f, "{:.2}+", time.as_secs_f32()),
+            (Some(time), _) => write!(f, "{:.2}", time.as_secs_f32()),
+            (None, _) => write!(f, "DNF"),
+        }
+    }
+}
+
+/// Averages the results of consecutive solves as the WCA does, such as the ao5 of five solves.
+///
+/// The best and worst results are left out, or 5% of them on each side, rounded up, from
+/// averages of more than twenty solves like the ao100. An average of less than five solves is
+/// their mean. ``None`` is returned for an average counting a DNF, or of no results.
+///
+/// # Examples
+///
+/// ```rust
+/// use core::time::Duration;
+/// use cubesim::{average_of, Penalty, SolveResult};
+///
+/// let mut results: Vec<SolveResult> = [12.0, 10.0, 11.0, 30.0, 9.0]
+///     .map(|secs| SolveResult::new(Duration::from_secs_f32(secs)))
+///     .to_vec();
+/// assert_eq!(average_of(&results), Some(Duration::from_secs(11)));
+///
+/// results[3].penalty = Some(Penalty::Dnf);
+/// assert_eq!(average_of(&results), Some(Duration::from_secs(11)));
+/// results[0].penalty = Some(Penalty::Dnf);
+/// assert_eq!(average_of(&results), None);
+/// ```
+pub fn average_of(results: &[SolveResult]) -> Option<Duration> {
+    let trimmed = if results.len() < 5 {
+        0
+    } else {
+        results.len().div_ceil(20)
+    };
+    // the DNFs sort after every time
+    let mut counted: Vec<Option<Duration>> =
+        results.iter().map(|result| result.counted()).collect();
+    counted.sort_by_key(|time| (time.is_none(), *time));
+
+    let kept = &counted[trimmed..counted.len() - trimmed];
+    let sum = kept.iter().copied().sum::<Option<Duration>>()?;
+    (!kept.is_empty()).then(|| sum / kept.len() as u32)
+}
@@ -0,0 +1,78 @@
+use std::fmt::Write;
+
+use crate::generic_cube::{Cube, Face, ORDERED_FACES};
+
+/// The width of a single sticker in the rendered image, in pixels.
+const STICKER_SIZE: i32 = 20;
+
+/// Where each face sits in the unfolded net, in units of whole faces.
+fn net_position(face: Face) -> (i32, i32) {
+    match face {
+        Face::U => (1, 0),
+        Face::L => (0, 1),
+        Face::F => (1, 1),
+        Face::R => (2, 1),
+        Face::B => (3, 1),
+        Face::D => (1, 2),
+        Face::X => unreachable!(),
+    }
+}
+
+fn color(face: Face) -> &'static str {
+    match face {
+        Face::U => "#ffffff",
+        Face::L => "#ff8c00",
+        Face::F => "#00a651",
+        Face::R => "#e8112d",
+        Face::B => "#0051ba",
+        Face::D => "#ffd500",
+        Face::X => "#808080",
+    }
+}
+
+/// Renders the unfolded net of a cube as an SVG image, with the standard color scheme.
+///
+/// Masked stickers are drawn in gray.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::Cube;
+/// use cubesim::{net_svg, FaceletCube};
+///
+/// let svg = net_svg(&FaceletCube::new(3));
+/// assert!(svg.starts_with("<svg"));
+/// assert_eq!(svg.matches("<rect").count(), 54);
+/// ```
+pub fn net_svg(cube: &impl Cube) -> String {
+    let size = cube.size();
+    let face_size = size * STICKER_SIZE;
+    let state = cube.state();
+
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}">"#,
+        4 * face_size,
+        3 * face_size
+    );
+
+    for (i, face) in ORDERED_FACES.iter().enumerate() {
+        let (net_x, net_y) = net_position(*face);
+
+        for row in 0..size {
+            for col in 0..size {
+                let sticker = state[(i as i32 * size * size + row * size + col) as usize];
+                write!(
+                    svg,
+                    r#"<rect x="{}" y="{}" width="{STICKER_SIZE}" height="{STICKER_SIZE}" fill="{}" stroke="black"/>"#,
+                    net_x * face_size + col * STICKER_SIZE,
+                    net_y * face_size + row * STICKER_SIZE,
+                    color(sticker)
+                )
+                .unwrap();
+            }
+        }
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
@@ -0,0 +1,82 @@
+use std::collections::BTreeMap;
+use std::string::{String, ToString};
+use std::sync::Mutex;
+use std::vec::Vec;
+
+use crate::generic_solver::PruningTable;
+use crate::methods::prepare_tables;
+
+// Starts the bytes of the tables, with the version of their format
+const HEADER: &[u8] = b"cubesim tables 1\n";
+
+// The tables read, each taken by the solver using it in place of building it
+static READ: Mutex<BTreeMap<String, PruningTable>> = Mutex::new(BTreeMap::new());
+// The tables of the solvers, built or read, to write them
+static USED: Mutex<BTreeMap<String, PruningTable>> = Mutex::new(BTreeMap::new());
+
+// The pruning table of a solver, read or built
+pub(crate) fn cached_table(name: &str, build: impl FnOnce() -> PruningTable) -> PruningTable {
+    let read = READ.lock().unwrap().remove(name);
+    let table = read.unwrap_or_else(build);
+    USED.lock().unwrap().insert(name.to_string(), table.clone());
+    table
+}
+
+/// Writes the pruning tables of every solver as bytes, building any of them not built yet,
+/// to ship them ready-made with ``read_tables``.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::{read_tables, write_tables};
+///
+/// let bytes = write_tables();
+/// assert!(read_tables(&bytes).is_ok());
+/// assert!(read_tables(&bytes[..100]).is_err());
+/// ```
+pub fn write_tables() -> Vec<u8> {
+    prepare_tables(&mut |_| ());
+
+    let used = USED.lock().unwrap();
+    let mut bytes = HEADER.to_vec();
+    bytes.push(used.len() as u8);
+    for (name, table) in used.iter() {
+        bytes.push(name.len() as u8);
+        bytes.extend(name.as_bytes());
+        table.write(&mut bytes);
+    }
+    bytes
+}
+
+/// Reads the pruning tables written by ``write_tables``, which the solvers then use in place
+/// of building them. Returns the number of tables read, none being used if any is invalid.
+/// Tables the solvers already built are left as they are.
+pub fn read_tables(mut bytes: &[u8]) -> Result<usize, String> {
+    bytes = bytes
+        .strip_prefix(HEADER)
+        .ok_or("not solver tables, or of another version")?;
+    let (&count, rest) = bytes.split_first().ok_or("no tables")?;
+    bytes = rest;
+
+    let mut tables = Vec::new();
+    for _ in 0..count {
+        let (&len, rest) = bytes.split_first().ok_or("the tables are cut short")?;
+        let name = rest.get(..len as usize).ok_or("the tables are cut short")?;
+        let name = String::from_utf8(name.to_vec()).map_err(|err| err.to_string())?;
+        bytes = &rest[len as usize..];
+        let table = PruningTable::read(&mut bytes).map_err(|err| format!("table {name}: {err}"))?;
+        tables.push((name, table));
+    }
+    if !bytes.is_empty() {
+        return Err("bytes left after the tables".to_string());
+    }
+
+    let used = USED.lock().unwrap();
+    let mut read = READ.lock().unwrap();
+    for (name, table) in tables {
+        if !used.contains_key(&name) {
+            read.insert(name, table);
+        }
+    }
+    Ok(count as usize)
+}
@@ -1,9 +1,17 @@
+use std::fmt::{Display, Formatter};
+use std::thread;
+
 use lazy_static::lazy_static;
 
 use crate::{
     facelet_cube::FaceletCube,
     generic_cube::{all_moves, sticker_index as S, Cube, CubeSize, Face, Move, MoveVariant},
-    generic_solver::{ida_star, PruningTable, Solver},
+    generic_solver::{
+        bidirectional_search, ida_star_observed, ida_star_parallel_observed, PruningTable, SearchProgress,
+        Solver,
+    },
+    instrument::timed_solve,
+    tables::cached_table,
 };
 
 /// Solves a 3x3x3 Cube using the Thistlethwaite Algorithm.
@@ -20,7 +28,7 @@ use crate::{
 /// Solve the superflip position:
 ///
 /// ```rust
-/// use cubesim::prelude::{Cube};
+/// use cubesim::prelude::{Cube, TwistyPuzzle};
 /// use cubesim::FaceletCube;
 /// use cubesim::parse_scramble;
 /// use cubesim::solve;
@@ -31,19 +39,230 @@ use crate::{
 //// assert!(cube.apply_moves(&solve(&cube).unwrap()).is_solved());
 /// ```
 pub fn solve(cube: &impl Cube) -> Option<Vec<Move>> {
-    let mut solution = vec![];
-    let mut cube = cube.clone();
+    solve_phases(cube).map(|solution| solution.moves())
+}
+
+/// A phase of the Thistlethwaite Algorithm, each bringing the cube into a smaller group of
+/// states, which can be solved with fewer kinds of moves.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ThistlethwaitePhase {
+    /// Orients the edges, so quarter turns of F and B are no longer needed.
+    EdgeOrientation,
+    /// Orients the corners and brings the middle layer's edges into it, so quarter turns of
+    /// L and R are no longer needed.
+    Domino,
+    /// Brings every piece into its orbit of half turns, fixing the parity.
+    HalfTurnReduction,
+    /// Solves the cube with half turns only.
+    HalfTurns,
+}
+
+impl ThistlethwaitePhase {
+    /// Every phase, in the order they are solved.
+    pub const ALL: [ThistlethwaitePhase; 4] = [
+        ThistlethwaitePhase::EdgeOrientation,
+        ThistlethwaitePhase::Domino,
+        ThistlethwaitePhase::HalfTurnReduction,
+        ThistlethwaitePhase::HalfTurns,
+    ];
+
+    /// What the phase accomplishes, for learners following a solution.
+    pub fn description(self) -> &'static str {
+        match self {
+            ThistlethwaitePhase::EdgeOrientation => {
+                "Orients every edge, so F and B are only turned by half turns from then on."
+            }
+            ThistlethwaitePhase::Domino => {
+                "Orients the corners and brings the middle layer's edges into it, so L and R \
+                 are only turned by half turns too."
+            }
+            ThistlethwaitePhase::HalfTurnReduction => {
+                "Brings every piece where half turns can solve it, fixing the parity."
+            }
+            ThistlethwaitePhase::HalfTurns => "Solves the cube with half turns only.",
+        }
+    }
+
+    pub(crate) fn solve(self, cube: &impl Cube) -> Option<Vec<Move>> {
+        self.solve_observed(cube, SolveOptions::default(), &mut |_| ())
+    }
+
+    fn solve_observed(
+        self,
+        cube: &impl Cube,
+        options: SolveOptions,
+        observe: &mut dyn FnMut(&SearchProgress<Move>),
+    ) -> Option<Vec<Move>> {
+        match self {
+            ThistlethwaitePhase::EdgeOrientation => phase1(cube, options, observe),
+            ThistlethwaitePhase::Domino => phase2(cube, options, observe),
+            ThistlethwaitePhase::HalfTurnReduction => phase3(cube, options, observe),
+            ThistlethwaitePhase::HalfTurns => phase4(cube, options, observe),
+        }
+    }
+}
+
+impl Display for ThistlethwaitePhase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            ThistlethwaitePhase::EdgeOrientation => "EO",
+            ThistlethwaitePhase::Domino => "Domino",
+            ThistlethwaitePhase::HalfTurnReduction => "G3",
+            ThistlethwaitePhase::HalfTurns => "Half turns",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// The moves of a solution found by ``solve_phases``, phase by phase.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Solution {
+    /// The moves of each phase, in the order they are made. A phase already done before the
+    /// solve has no moves.
+    pub phases: Vec<(ThistlethwaitePhase, Vec<Move>)>,
+}
+
+impl Solution {
+    /// Every move of the solution.
+    pub fn moves(&self) -> Vec<Move> {
+        self.phases
+            .iter()
+            .flat_map(|(_, moves)| moves.iter().copied())
+            .collect()
+    }
+
+    /// The number of moves of the solution.
+    pub fn len(&self) -> usize {
+        self.phases.iter().map(|(_, moves)| moves.len()).sum()
+    }
+
+    /// Whether the cube was already solved.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The phase of the move at an index of the solution, with the moves of that phase and
+    /// the move's index among them.
+    pub fn phase_of(&self, index: usize) -> Option<(ThistlethwaitePhase, &[Move], usize)> {
+        let mut start = 0;
+        for (phase, moves) in &self.phases {
+            if index < start + moves.len() {
+                return Some((*phase, moves, index - start));
+            }
+            start += moves.len();
+        }
+        None
+    }
+}
+
+/// Solves a 3x3x3 Cube like ``solve``, keeping the moves of each phase apart.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::{Cube, TwistyPuzzle};
+/// use cubesim::{parse_scramble, solve_phases, FaceletCube, ThistlethwaitePhase};
+///
+/// let cube = FaceletCube::new(3).apply_moves(&parse_scramble(String::from("R U F")));
+/// let solution = solve_phases(&cube).unwrap();
+/// assert!(cube.apply_moves(&solution.moves()).is_solved());
+///
+/// let (phase, _, _) = solution.phase_of(0).unwrap();
+/// assert_eq!(phase, ThistlethwaitePhase::EdgeOrientation);
+/// ```
+pub fn solve_phases(cube: &impl Cube) -> Option<Solution> {
+    solve_phases_observed(cube, &mut |_, _| ())
+}
 
-    for phase in [phase1, phase2, phase3, phase4] {
-        let mut phase_solution = phase(&cube)?;
-        cube = cube.apply_moves(&phase_solution);
-        solution.append(&mut phase_solution);
+/// Solves a 3x3x3 Cube like ``solve_phases``, reporting the progress of the search of each
+/// phase to ``observe``, as ``ida_star_observed`` does, to show how the search goes.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::{Cube, TwistyPuzzle};
+/// use cubesim::{parse_scramble, solve_phases_observed, FaceletCube, ThistlethwaitePhase};
+///
+/// let cube = FaceletCube::new(3).apply_moves(&parse_scramble(String::from("R U F")));
+/// let mut phases = Vec::new();
+/// solve_phases_observed(&cube, &mut |phase, _| phases.push(phase)).unwrap();
+/// assert_eq!(phases[0], ThistlethwaitePhase::EdgeOrientation);
+/// assert_eq!(phases.last(), Some(&ThistlethwaitePhase::HalfTurns));
+/// ```
+pub fn solve_phases_observed(
+    cube: &impl Cube,
+    observe: &mut dyn FnMut(ThistlethwaitePhase, &SearchProgress<Move>),
+) -> Option<Solution> {
+    solve_phases_observed_with(cube, SolveOptions::default(), observe)
+}
+
+/// The options of ``solve_phases_with``.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct SolveOptions {
+    /// The threads searching each phase, sharing its pruning table as ``ida_star_parallel``
+    /// does, or 0 or 1 to search on the calling thread alone.
+    pub threads: usize,
+    /// Whether each phase is searched from both ends, as ``bidirectional_search`` does, on the
+    /// calling thread and reporting no progress. Its solutions are as short, though their
+    /// moves may differ.
+    pub bidirectional: bool,
+}
+
+impl SolveOptions {
+    /// The options searching with a thread for each core of the machine.
+    pub fn all_cores() -> Self {
+        Self {
+            threads: thread::available_parallelism().map_or(1, |cores| cores.get()),
+            ..Self::default()
+        }
     }
+}
 
-    Some(solution)
+/// Solves a 3x3x3 Cube like ``solve_phases``, with the options. The same solution is found
+/// however many threads search.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::prelude::{Cube, TwistyPuzzle};
+/// use cubesim::{parse_scramble, solve_phases, solve_phases_with, FaceletCube, SolveOptions};
+///
+/// let cube = FaceletCube::new(3).apply_moves(&parse_scramble(String::from("R U F' L2 D B")));
+/// let solution = solve_phases_with(&cube, SolveOptions { threads: 4, ..SolveOptions::default() });
+/// assert_eq!(solution, solve_phases(&cube));
+///
+/// let options = SolveOptions { bidirectional: true, ..SolveOptions::default() };
+/// let solution = solve_phases_with(&cube, options).unwrap();
+/// assert!(cube.apply_moves(&solution.moves()).is_solved());
+/// ```
+pub fn solve_phases_with(cube: &impl Cube, options: SolveOptions) -> Option<Solution> {
+    solve_phases_observed_with(cube, options, &mut |_, _| ())
+}
+
+fn solve_phases_observed_with(
+    cube: &impl Cube,
+    options: SolveOptions,
+    observe: &mut dyn FnMut(ThistlethwaitePhase, &SearchProgress<Move>),
+) -> Option<Solution> {
+    timed_solve("thistlethwaite", || {
+        let mut solution = Solution::default();
+        let mut cube = cube.clone();
+
+        for phase in ThistlethwaitePhase::ALL {
+            let phase_solution = phase.solve_observed(&cube, options, &mut |progress| observe(phase, progress))?;
+            cube = cube.apply_moves(&phase_solution);
+            solution.phases.push((phase, phase_solution));
+        }
+
+        Some(solution)
+    }, Solution::len)
 }
 
-pub fn phase1(cube: &impl Cube) -> Option<Vec<Move>> {
+pub fn phase1(
+    cube: &impl Cube,
+    options: SolveOptions,
+    observe: &mut dyn FnMut(&SearchProgress<Move>),
+) -> Option<Vec<Move>> {
     use Face::*;
 
     lazy_static! {
@@ -68,15 +287,21 @@ pub fn phase1(cube: &impl Cube) -> Option<Vec<Move>> {
         static ref MOVES: Vec<Move> = all_moves(3);
         static ref PRUNING_TABLE: PruningTable = {
             let pruning_depth = 7;
-            PruningTable::new(&[FaceletCube::new(3).mask(&*MASK)], pruning_depth, &MOVES)
+            cached_table("eo", || {
+                PruningTable::new(&[FaceletCube::new(3).mask(&*MASK)], pruning_depth, &MOVES)
+            })
         };
         static ref SOLVER: Solver = Solver::new(all_moves(3), (*PRUNING_TABLE).clone());
     }
 
-    ida_star(&cube.mask(&*MASK), &SOLVER, 10)
+    search(&cube.mask(&*MASK), &SOLVER, 10, options, observe)
 }
 
-pub fn phase2(cube: &impl Cube) -> Option<Vec<Move>> {
+pub fn phase2(
+    cube: &impl Cube,
+    options: SolveOptions,
+    observe: &mut dyn FnMut(&SearchProgress<Move>),
+) -> Option<Vec<Move>> {
     use Face::*;
     use MoveVariant::*;
 
@@ -134,15 +359,21 @@ pub fn phase2(cube: &impl Cube) -> Option<Vec<Move>> {
         ];
         static ref PRUNING_TABLE: PruningTable = {
             let pruning_depth = 5;
-            PruningTable::new(&[FaceletCube::new(3).mask(&*MASK)], pruning_depth, &MOVES)
+            cached_table("domino", || {
+                PruningTable::new(&[FaceletCube::new(3).mask(&*MASK)], pruning_depth, &MOVES)
+            })
         };
         static ref SOLVER: Solver = Solver::new((*MOVES).clone(), (*PRUNING_TABLE).clone());
     }
 
-    ida_star(&cube.mask(&*MASK), &SOLVER, 10)
+    search(&cube.mask(&*MASK), &SOLVER, 10, options, observe)
 }
 
-pub fn phase3(cube: &impl Cube) -> Option<Vec<Move>> {
+pub fn phase3(
+    cube: &impl Cube,
+    options: SolveOptions,
+    observe: &mut dyn FnMut(&SearchProgress<Move>),
+) -> Option<Vec<Move>> {
     use Face::*;
     use MoveVariant::*;
 
@@ -206,15 +437,21 @@ pub fn phase3(cube: &impl Cube) -> Option<Vec<Move>> {
         ];
         static ref PRUNING_TABLE: PruningTable = {
             let pruning_depth = 5;
-            PruningTable::from_existing_table(&G2_SOLVED_STATES, pruning_depth, &MOVES)
+            cached_table("g3", || {
+                PruningTable::from_existing_table(&G2_SOLVED_STATES, pruning_depth, &MOVES)
+            })
         };
         static ref SOLVER: Solver = Solver::new((*MOVES).clone(), (*PRUNING_TABLE).clone());
     }
 
-    ida_star(&cube.mask(&*MASK), &SOLVER, 13)
+    search(&cube.mask(&*MASK), &SOLVER, 13, options, observe)
 }
 
-pub fn phase4(cube: &impl Cube) -> Option<Vec<Move>> {
+pub fn phase4(
+    cube: &impl Cube,
+    options: SolveOptions,
+    observe: &mut dyn FnMut(&SearchProgress<Move>),
+) -> Option<Vec<Move>> {
     use MoveVariant::*;
 
     let moves = vec![
@@ -231,5 +468,23 @@ pub fn phase4(cube: &impl Cube) -> Option<Vec<Move>> {
 
     let solver = Solver::new(moves, pruning_table);
 
-    ida_star(cube, &solver, search_limit)
+    search(cube, &solver, search_limit, options, observe)
+}
+
+// Searches for the moves of a phase as the options say, on several threads with the cube
+// shared between them as a ``FaceletCube``
+fn search(
+    cube: &impl Cube,
+    solver: &Solver,
+    limit: i32,
+    options: SolveOptions,
+    observe: &mut dyn FnMut(&SearchProgress<Move>),
+) -> Option<Vec<Move>> {
+    if options.bidirectional {
+        return bidirectional_search(cube, solver, limit);
+    }
+    if options.threads <= 1 {
+        return ida_star_observed(cube, solver, limit, observe);
+    }
+    ida_star_parallel_observed(&FaceletCube::from(cube.state()), solver, limit, options.threads, observe)
 }
@@ -0,0 +1,148 @@
+use alloc::{string::String, string::ToString, vec::Vec};
+use core::fmt::{Display, Formatter};
+
+use crate::generic_cube::{Move, Move::*, MoveVariant::*};
+
+/// A short sequence of moves commonly found inside algorithms and solves, executed as a
+/// single motion by speedcubers.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Trigger {
+    /// ``R U R' U'``
+    Sexy,
+    /// ``R' F R F'``
+    Sledgehammer,
+    /// ``F R' F' R``, the sledgehammer's inverse.
+    Hedgeslammer,
+}
+
+impl Trigger {
+    /// Every trigger that can be detected.
+    pub const ALL: [Trigger; 3] = [Trigger::Sexy, Trigger::Sledgehammer, Trigger::Hedgeslammer];
+
+    /// The moves of the trigger, or of its mirror performed with the left hand.
+    pub fn moves(self, left_handed: bool) -> [Move; 4] {
+        let (right, left) = if left_handed {
+            (L(Inverse), L(Standard))
+        } else {
+            (R(Standard), R(Inverse))
+        };
+        // mirroring also reverses the direction of the U and F turns
+        let (up, down) = if left_handed {
+            (U(Inverse), U(Standard))
+        } else {
+            (U(Standard), U(Inverse))
+        };
+        let (front, back) = if left_handed {
+            (F(Inverse), F(Standard))
+        } else {
+            (F(Standard), F(Inverse))
+        };
+
+        match self {
+            Trigger::Sexy => [right, up, left, down],
+            Trigger::Sledgehammer => [left, front, right, back],
+            Trigger::Hedgeslammer => [front, left, back, right],
+        }
+    }
+}
+
+impl Display for Trigger {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let name = match self {
+            Trigger::Sexy => "sexy move",
+            Trigger::Sledgehammer => "sledgehammer",
+            Trigger::Hedgeslammer => "hedgeslammer",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// A trigger found in a sequence of moves.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct TriggerMatch {
+    /// The trigger found.
+    pub trigger: Trigger,
+    /// Whether it was the mirror of the trigger, performed with the left hand.
+    pub left_handed: bool,
+    /// The index of the first move of the trigger in the sequence.
+    pub start: usize,
+}
+
+impl TriggerMatch {
+    /// The indices of the moves of the trigger in the sequence.
+    pub fn range(&self) -> core::ops::Range<usize> {
+        self.start..self.start + 4
+    }
+}
+
+/// Finds the triggers in a sequence of moves, such as a recorded solve, from left to right
+/// and without overlaps.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::{find_triggers, parse_scramble, Trigger};
+///
+/// let moves = parse_scramble(String::from("F R U R' U' F' L F' L' F"));
+/// let triggers = find_triggers(&moves);
+///
+/// assert_eq!(triggers.len(), 2);
+/// assert_eq!((triggers[0].trigger, triggers[0].start), (Trigger::Sexy, 1));
+/// assert!(triggers[1].left_handed);
+/// ```
+pub fn find_triggers(moves: &[Move]) -> Vec<TriggerMatch> {
+    let mut found = Vec::new();
+    let mut start = 0;
+
+    while start < moves.len() {
+        let matched = Trigger::ALL
+            .into_iter()
+            .flat_map(|trigger| [false, true].map(|left_handed| (trigger, left_handed)))
+            .find(|&(trigger, left_handed)| {
+                moves[start..].starts_with(&trigger.moves(left_handed))
+            });
+
+        match matched {
+            Some((trigger, left_handed)) => {
+                found.push(TriggerMatch {
+                    trigger,
+                    left_handed,
+                    start,
+                });
+                start += 4;
+            }
+            None => start += 1,
+        }
+    }
+
+    found
+}
+
+/// Writes a sequence of moves with its triggers in parentheses, the way solves are
+/// commented.
+///
+/// # Examples
+///
+/// ```rust
+/// use cubesim::{annotate_triggers, parse_scramble};
+///
+/// let moves = parse_scramble(String::from("F R U R' U' F'"));
+/// assert_eq!(annotate_triggers(&moves), "F (R U R' U') F'");
+/// ```
+pub fn annotate_triggers(moves: &[Move]) -> String {
+    let triggers = find_triggers(moves);
+    let mut words = Vec::new();
+
+    for (i, mv) in moves.iter().enumerate() {
+        let mut word = mv.to_string();
+        if triggers.iter().any(|t| t.start == i) {
+            word.insert(0, '(');
+        }
+        if triggers.iter().any(|t| t.range().end == i + 1) {
+            word.push(')');
+        }
+        words.push(word);
+    }
+
+    words.join(" ")
+}
@@ -183,4 +183,4 @@ fn generic_solved_state() {
         L, L, L, L, L, L, L, L, L,
         B, B, B, B, B, B, B, B, B
     ]);
-}
\ No newline at end of file
+}
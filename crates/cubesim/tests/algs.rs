@@ -0,0 +1,83 @@
+//
+// Algorithm Database Tests
+//
+
+use cubesim::prelude::*;
+use cubesim::{matching_algorithms, parse_scramble, AlgSet, Algorithm, FaceletCube};
+
+fn first_two_layers(index: CubeSize, face: Face) -> Face {
+    if index / 9 == 0 || (index / 9 != 3 && index % 9 < 3) {
+        Face::X
+    } else {
+        face
+    }
+}
+
+fn is_first_two_layers_solved(cube: &FaceletCube) -> bool {
+    cube.mask(&first_two_layers).state() == FaceletCube::new(3).mask(&first_two_layers).state()
+}
+
+fn auf() -> Vec<Vec<Move>> {
+    vec![vec![], vec![Move::U(MoveVariant::Standard)], vec![Move::U(MoveVariant::Double)],
+         vec![Move::U(MoveVariant::Inverse)]]
+}
+
+#[test]
+fn test_set_sizes() {
+    assert_eq!(AlgSet::Oll.algorithms().len(), 57);
+    assert_eq!(AlgSet::Pll.algorithms().len(), 21);
+    assert!(AlgSet::F2l.algorithms().iter().all(|a| a.set == AlgSet::F2l));
+}
+
+#[test]
+fn test_last_layer_algorithms_keep_the_first_two_layers() {
+    for algorithm in AlgSet::Oll.algorithms().iter().chain(AlgSet::Pll.algorithms()) {
+        assert!(is_first_two_layers_solved(&algorithm.case()), "{} breaks the F2L", algorithm.name);
+    }
+}
+
+#[test]
+fn test_pll_algorithms_keep_the_orientation() {
+    for algorithm in AlgSet::Pll.algorithms() {
+        let case: FaceletCube = algorithm.case();
+        assert!(case.face_iter(Face::U).all(|f| f == Face::U), "{} changes the orientation",
+                algorithm.name);
+    }
+}
+
+#[test]
+fn test_every_algorithm_matches_only_its_case() {
+    for set in [AlgSet::F2l, AlgSet::Oll, AlgSet::Pll] {
+        for algorithm in set.algorithms() {
+            for adjustment in auf() {
+                let case: FaceletCube = algorithm.case();
+                let found: Vec<&Algorithm> = matching_algorithms(&case.apply_moves(&adjustment), set)
+                    .into_iter()
+                    .map(|(_, a)| a)
+                    .collect();
+                assert_eq!(found, vec![algorithm], "{} case after {:?}", algorithm.name, adjustment);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_matching_returns_the_adjustment() {
+    let sune = parse_scramble(String::from("R U2 R' U' R U' R' U2"));
+    let found = matching_algorithms(&FaceletCube::new(3).apply_moves(&sune), AlgSet::Oll);
+
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].1.name, "OLL 27");
+    let solved = FaceletCube::new(3)
+        .apply_moves(&sune)
+        .apply_moves(&found[0].0.into_iter().collect::<Vec<_>>())
+        .apply_moves(&found[0].1.moves());
+    assert!(solved.face_iter(Face::U).all(|f| f == Face::U));
+}
+
+#[test]
+fn test_no_match_on_solved_or_other_sizes() {
+    assert!(matching_algorithms(&FaceletCube::new(3), AlgSet::Pll).is_empty());
+    assert!(matching_algorithms(&FaceletCube::new(3), AlgSet::Oll).is_empty());
+    assert!(matching_algorithms(&FaceletCube::new(4), AlgSet::Oll).is_empty());
+}
@@ -0,0 +1,42 @@
+//
+// Beginner Method Tests
+//
+
+use cubesim::prelude::*;
+use cubesim::{parse_scramble, BeginnerStep, FaceletCube, GeoCube};
+
+#[test]
+fn test_solved_cube_has_no_steps_left() {
+    let cube = FaceletCube::new(3);
+    assert_eq!(BeginnerStep::current(&cube), None);
+    assert_eq!(BeginnerStep::current(&cube.apply_moves(&parse_scramble(String::from("x y2 z'")))), None);
+}
+
+#[test]
+fn test_steps_follow_the_layers() {
+    let cube = FaceletCube::new(3);
+    assert_eq!(BeginnerStep::current(&cube.apply_moves(&parse_scramble(String::from("R")))),
+               Some(BeginnerStep::Cross));
+    assert_eq!(BeginnerStep::current(&cube.apply_moves(&parse_scramble(String::from("U")))),
+               Some(BeginnerStep::Cross));
+    assert_eq!(BeginnerStep::current(&cube.apply_moves(&parse_scramble(String::from("D")))),
+               Some(BeginnerStep::LastLayerEdges));
+
+    // a sune on the yellow layer only twists its corners and moves its edges
+    let sune = parse_scramble(String::from("x2 R U R' U R U2 R' x2"));
+    assert!(BeginnerStep::LastLayerCross.is_done(&cube.apply_moves(&sune)));
+    assert!(!BeginnerStep::LastLayerCorners.is_done(&cube.apply_moves(&sune)));
+}
+
+#[test]
+fn test_steps_on_both_cubes() {
+    let scramble = parse_scramble(String::from("F R U' D2"));
+    assert_eq!(BeginnerStep::current(&GeoCube::new(3).apply_moves(&scramble)),
+               BeginnerStep::current(&FaceletCube::new(3).apply_moves(&scramble)));
+}
+
+#[test]
+fn test_next_step() {
+    assert_eq!(BeginnerStep::Cross.next(), Some(BeginnerStep::FirstLayer));
+    assert_eq!(BeginnerStep::LastLayerCorners.next(), None);
+}
@@ -0,0 +1,93 @@
+//
+// Cube Conversion Tests
+//
+
+use cubesim::prelude::*;
+use cubesim::prelude::{Move::*, MoveVariant::*};
+use cubesim::{random_scramble, sticker_index, CubieCube, FaceletCube, GeoCube};
+
+#[test]
+fn test_facelet_and_geo_cubes_convert_without_loss() {
+    for size in 2..=5 {
+        for _ in 0..10 {
+            let scramble = random_scramble(size, true);
+            let facelet_cube = FaceletCube::new(size).apply_moves(&scramble);
+            let geo_cube = GeoCube::new(size).apply_moves(&scramble);
+
+            assert!(GeoCube::from(facelet_cube.clone()) == geo_cube);
+            assert_eq!(FaceletCube::from(geo_cube), facelet_cube);
+        }
+    }
+}
+
+#[test]
+fn test_masks_are_kept() {
+    let corners = |_, face| if face == Face::U { Face::X } else { face };
+    let cube = FaceletCube::new(4).apply_move(R(Standard)).mask(&corners);
+
+    let converted = FaceletCube::from(GeoCube::from(cube.clone()));
+    assert_eq!(converted, cube);
+    assert_eq!(converted.state().iter().filter(|&&face| face == Face::X).count(), 16);
+}
+
+#[test]
+fn test_stickers_without_origins_convert() {
+    // the stickers of a 2x2x2 cube aren't traced back to where they were on the solved cube
+    let state = FaceletCube::new(2).apply_moves(&[R(Standard), U(Inverse)]).state();
+    let cube = FaceletCube::from(state.clone());
+    assert_eq!(GeoCube::from(cube).state(), state);
+}
+
+#[test]
+fn test_cubie_cube_turns_like_facelet_cube() {
+    for _ in 0..20 {
+        let scramble = random_scramble(3, false);
+        let facelet_cube = FaceletCube::new(3).apply_moves(&scramble);
+        let cubie_cube = CubieCube::new().apply_moves(&scramble);
+
+        assert_eq!(FaceletCube::from(cubie_cube), facelet_cube);
+        assert_eq!(CubieCube::try_from(facelet_cube.clone()), Ok(cubie_cube));
+        assert_eq!(CubieCube::try_from(GeoCube::from(facelet_cube)), Ok(cubie_cube));
+        assert!(cubie_cube.apply_moves(&scramble.iter().rev().map(Move::inverse).collect::<Vec<_>>()).is_solved());
+    }
+}
+
+#[test]
+fn test_cubie_cube_is_held_by_its_centers() {
+    let solved = CubieCube::new();
+    assert!(solved.apply_move(X(Standard)).is_solved());
+    assert_eq!(solved.apply_move(Rw(2, Standard)), solved.apply_move(L(Standard)));
+    assert_eq!(solved.apply_moves(&[Y(Standard), R(Standard)]), solved.apply_move(R(Standard)));
+
+    let rotated = FaceletCube::new(3).apply_moves(&[Y(Standard), R(Standard)]);
+    assert_eq!(CubieCube::try_from(rotated), Ok(solved.apply_move(R(Standard))));
+}
+
+#[test]
+fn test_invalid_cubes_are_rejected() {
+    assert!(CubieCube::try_from(FaceletCube::new(2)).is_err());
+
+    let masked = FaceletCube::new(3).mask(&|i, face| if i == 0 { Face::X } else { face });
+    assert!(CubieCube::try_from(masked).is_err());
+
+    let mut flipped = FaceletCube::new(3).state();
+    flipped.swap(sticker_index(3, Face::U, 8) as usize, sticker_index(3, Face::F, 2) as usize);
+    assert_eq!(CubieCube::try_from(FaceletCube::from(flipped)), Err("an edge is flipped".to_string()));
+}
+
+#[test]
+fn test_pieces_are_checked() {
+    let cube = CubieCube::new().apply_moves(&[R(Standard), U(Standard)]);
+    assert_eq!(CubieCube::from_pieces(cube.corners(), cube.edges()), Ok(cube));
+
+    let mut corners = cube.corners();
+    corners[0].1 = (corners[0].1 + 1) % 3;
+    assert_eq!(CubieCube::from_pieces(corners, cube.edges()), Err("a corner is twisted".to_string()));
+
+    let mut edges = cube.edges();
+    edges.swap(0, 1);
+    assert_eq!(CubieCube::from_pieces(cube.corners(), edges), Err("two pieces are swapped".to_string()));
+
+    corners[0].0 = 8;
+    assert_eq!(CubieCube::from_pieces(corners, cube.edges()), Err("the corner at URF is no corner".to_string()));
+}
@@ -0,0 +1,54 @@
+//
+// Cube Explorer Format Tests
+//
+
+use cubesim::prelude::*;
+use cubesim::{
+    parse_scramble, read_cube_explorer, solve, write_cube_explorer, CubeExplorerEntry, FaceletCube,
+};
+
+#[test]
+fn test_read_maneuvers() {
+    let entries = read_cube_explorer("R U R' U'  (4f)\r\n\n  F1 D3 B2 (3f*) // a comment\n").unwrap();
+
+    assert_eq!(
+        entries,
+        vec![
+            CubeExplorerEntry::Maneuver(parse_scramble(String::from("R U R' U'"))),
+            CubeExplorerEntry::Maneuver(parse_scramble(String::from("F D' B2"))),
+        ]
+    );
+}
+
+#[test]
+fn test_round_trip() {
+    let scramble = parse_scramble(String::from("D2 L' F R2 B U' L2 D F'"));
+    let entries = vec![
+        CubeExplorerEntry::Maneuver(scramble.clone()),
+        CubeExplorerEntry::Position(FaceletCube::new(3).apply_moves(&scramble).state()),
+    ];
+
+    assert_eq!(read_cube_explorer(&write_cube_explorer(&entries)).unwrap(), entries);
+}
+
+#[test]
+fn test_solve_imported_positions() {
+    let scrambled = FaceletCube::new(3).apply_moves(&parse_scramble(String::from("R U2 B' L D2 F")));
+    let text = write_cube_explorer(&[CubeExplorerEntry::Position(scrambled.state())]);
+
+    for entry in read_cube_explorer(&text).unwrap() {
+        let CubeExplorerEntry::Position(state) = entry else {
+            panic!("a position was read as a maneuver");
+        };
+        let cube = FaceletCube::from(state);
+        assert!(cube.apply_moves(&solve(&cube).unwrap()).is_solved());
+    }
+}
+
+#[test]
+fn test_invalid_lines() {
+    assert_eq!(read_cube_explorer("R U\nR Q\n").unwrap_err(), "line 2: invalid move Q");
+    // the solved cube with two of its stickers swapped
+    let twisted = "UUUUUUUURRRRRRRRRUFFFFFFFFFDDDDDDDDDLLLLLLLLLBBBBBBBBB";
+    assert!(read_cube_explorer(twisted).unwrap_err().starts_with("line 1: "));
+}
@@ -0,0 +1,114 @@
+//
+// Cycle Decomposition Tests
+//
+
+use cubesim::prelude::*;
+use cubesim::{cycle_structure, parse_scramble, validate_state, FaceletCube, GeoCube, CORNER_NAMES, EDGE_NAMES};
+
+fn scrambled(scramble: &str) -> FaceletCube {
+    FaceletCube::new(3).apply_moves(&parse_scramble(String::from(scramble)))
+}
+
+#[test]
+fn test_solved_in_any_orientation() {
+    assert!(cycle_structure(&FaceletCube::new(3)).is_solved());
+    assert!(cycle_structure(&scrambled("x y2 z'")).is_solved());
+}
+
+#[test]
+fn test_t_perm_swaps() {
+    let cycles = cycle_structure(&scrambled("R U R' U' R' F R2 U' R' U' R U R' F'"));
+
+    assert_eq!(cycles.corner_cycles.len(), 1);
+    assert_eq!(cycles.edge_cycles.len(), 1);
+    let corners: Vec<&str> = cycles.corner_cycles[0].positions.iter().map(|&i| CORNER_NAMES[i]).collect();
+    let edges: Vec<&str> = cycles.edge_cycles[0].positions.iter().map(|&i| EDGE_NAMES[i]).collect();
+    assert_eq!(corners, vec!["URF", "UBR"]);
+    assert_eq!(edges, vec!["UR", "UL"]);
+    assert_eq!(cycles.unsolved_pieces(), 4);
+    assert!(cycles.has_parity());
+}
+
+#[test]
+fn test_twisted_corners() {
+    let cycles = cycle_structure(&scrambled("R' D' R D R' D' R D U D' R' D R D' R' D R U'"));
+
+    assert!(cycles.edge_cycles.is_empty());
+    assert_eq!(cycles.corner_cycles.len(), 2);
+    assert!(cycles.corner_cycles.iter().all(|cycle| cycle.positions.len() == 1));
+    let twists: u8 = cycles.corner_cycles.iter().map(|cycle| cycle.orientation).sum();
+    assert_eq!(twists, 3);
+    assert!(!cycles.has_parity());
+}
+
+#[test]
+fn test_flipped_edges() {
+    // F turns flip the edges they move
+    let cycles = cycle_structure(&scrambled("F"));
+    assert_eq!(cycles.edge_cycles.len(), 1);
+    assert_eq!(cycles.edge_cycles[0].positions.len(), 4);
+    assert_eq!(cycles.edge_cycles[0].orientation, 0);
+    assert_eq!(cycle_structure(&scrambled("F2")).edge_cycles.len(), 2);
+}
+
+#[test]
+fn test_cycles_on_both_cubes() {
+    let scramble = parse_scramble(String::from("R U2 F' L D B2 x"));
+    assert_eq!(cycle_structure(&GeoCube::new(3).apply_moves(&scramble)),
+               cycle_structure(&FaceletCube::new(3).apply_moves(&scramble)));
+}
+
+#[test]
+fn test_valid_states() {
+    assert_eq!(validate_state(&FaceletCube::new(3).state()), Ok(()));
+    assert_eq!(validate_state(&scrambled("R U2 F' L D B2 x y'").state()), Ok(()));
+}
+
+#[test]
+fn test_invalid_states() {
+    let state = scrambled("R U F").state();
+    assert!(validate_state(&state[..45]).is_err());
+
+    // the URF corner turned in place
+    let mut twisted = FaceletCube::new(3).state();
+    twisted[8] = Face::R;
+    twisted[9] = Face::F;
+    twisted[20] = Face::U;
+    assert_eq!(validate_state(&twisted), Err("a corner is twisted".to_string()));
+
+    // two edges swapped by peeling their stickers off
+    let mut swapped = FaceletCube::new(3).state();
+    swapped.swap(10, 19);
+    assert_eq!(validate_state(&swapped), Err("two pieces are swapped".to_string()));
+
+    // a sticker of the wrong color
+    let mut painted = FaceletCube::new(3).state();
+    painted[0] = Face::R;
+    assert!(validate_state(&painted).is_err());
+}
+
+#[test]
+fn test_bad_edges() {
+    use cubesim::bad_edges;
+
+    assert!(bad_edges(&FaceletCube::new(3).state()).unwrap().is_empty());
+    // only quarter turns of F and B flip edges
+    assert!(bad_edges(&scrambled("R U L' D2 F2 B2 R' U'").state()).unwrap().is_empty());
+    assert_eq!(bad_edges(&scrambled("F B").state()).unwrap().len(), 8);
+    assert_eq!(bad_edges(&scrambled("F R B'").state()).unwrap().len(), 8);
+    // the count of bad edges is always even
+    let count = bad_edges(&scrambled("F U R' B D L2 F'").state()).unwrap().len();
+    assert_eq!(count % 2, 0);
+}
+
+#[test]
+fn test_bad_edges_after_rotation() {
+    use cubesim::bad_edges;
+
+    // held with the old R face in front, the R turns flip edges
+    let cube = scrambled("R y");
+    assert_eq!(bad_edges(&cube.state()).unwrap().len(), 4);
+    let mut state = cube.state();
+    state.swap(7, 19);
+    assert!(bad_edges(&state).is_err());
+}
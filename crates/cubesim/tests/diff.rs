@@ -0,0 +1,69 @@
+//
+// Cube Diff Tests
+//
+
+use cubesim::prelude::*;
+use cubesim::{diff, distance_lower_bound, parse_scramble, FaceletCube, GeoCube, StickerDiff};
+
+#[test]
+fn test_same_state_in_any_cube() {
+    let scramble = parse_scramble(String::from("R U R' U' F2 D"));
+    let facelet = FaceletCube::new(3).apply_moves(&scramble);
+    let geo = GeoCube::new(3).apply_moves(&scramble);
+
+    assert!(diff(&facelet, &geo).is_empty());
+}
+
+#[test]
+fn test_diff_is_symmetric() {
+    let solved = FaceletCube::new(4);
+    let turned = solved.apply_move(Move::Rw(2, MoveVariant::Double));
+
+    let diffs = diff(&solved, &turned);
+    let swapped: Vec<StickerDiff> = diff(&turned, &solved)
+        .into_iter()
+        .map(|d| StickerDiff { index: d.index, left: d.right, right: d.left })
+        .collect();
+    assert_eq!(diffs, swapped);
+}
+
+#[test]
+fn test_diff_faces() {
+    let solved = FaceletCube::new(3);
+    let turned = solved.apply_move(Move::R(MoveVariant::Standard));
+    let (solved_state, turned_state) = (solved.state(), turned.state());
+
+    let diffs = diff(&solved, &turned);
+    assert_eq!(diffs.len(), 12);
+    for d in diffs {
+        assert_eq!(d.left, solved_state[d.index]);
+        assert_eq!(d.right, turned_state[d.index]);
+        assert_ne!(d.left, d.right);
+    }
+}
+
+#[test]
+fn test_distance_lower_bound() {
+    let solved = FaceletCube::new(3);
+    assert_eq!(distance_lower_bound(&solved, &GeoCube::new(3)), 0);
+
+    // never more than the moves actually made
+    for scramble in ["R", "R U", "R U F' L2", "R U R' U' R' F R2 U' R' U' R U R' F'"] {
+        let moves = parse_scramble(String::from(scramble));
+        let scrambled = solved.apply_moves(&moves);
+        let bound = distance_lower_bound(&solved, &scrambled);
+        assert!(bound >= 1 && bound <= moves.len(), "{scramble}: {bound}");
+    }
+
+    // a superflip changes 24 stickers, more than a single turn can
+    let superflip = parse_scramble(String::from(
+        "U R2 F B R B2 R U2 L B2 R U' D' R2 F R' L B2 U2 F2",
+    ));
+    assert_eq!(distance_lower_bound(&solved, &solved.apply_moves(&superflip)), 2);
+}
+
+#[test]
+#[should_panic]
+fn test_different_sizes() {
+    diff(&FaceletCube::new(2), &FaceletCube::new(3));
+}
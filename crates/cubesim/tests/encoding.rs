@@ -0,0 +1,39 @@
+//
+// Move Encoding Tests
+//
+
+use cubesim::prelude::*;
+use cubesim::{decode_moves, encode_moves, parse_scramble, random_scramble};
+
+#[test]
+fn test_3x3x3_moves_take_a_byte() {
+    let moves = parse_scramble(String::from("R U' F2 Rw Uw2 2R 2F' x y2 z'"));
+    let moves = [moves, random_scramble(3, true)].concat();
+    let bytes = encode_moves(&moves);
+
+    assert_eq!(bytes.len(), moves.len());
+    assert_eq!(decode_moves(&bytes).unwrap(), moves);
+}
+
+#[test]
+fn test_big_cube_moves() {
+    for size in [4, 7, 20] {
+        let moves = random_scramble(size, true);
+        assert_eq!(decode_moves(&encode_moves(&moves)).unwrap(), moves);
+    }
+
+    let moves = [Move::Rw(3, MoveVariant::Double), Move::Di(200, MoveVariant::Inverse)];
+    let bytes = encode_moves(&moves);
+    assert_eq!(bytes.len(), 5);
+    assert_eq!(decode_moves(&bytes).unwrap(), moves);
+}
+
+#[test]
+fn test_invalid_bytes() {
+    assert!(decode_moves(&[]).unwrap().is_empty());
+    assert!(decode_moves(&[0, 99]).is_err());
+    assert!(decode_moves(&[0xff]).is_err());
+    // the layer of a move cut short, and a layer too large to be one
+    assert!(decode_moves(&[63, 0x83]).is_err());
+    assert!(decode_moves(&[63, 0xff, 0xff, 0xff, 0xff, 0x7f]).is_err());
+}
@@ -0,0 +1,65 @@
+//
+// Property Tests
+//
+// Cases are kept few for the solvers, the Thistlethwaite solver taking a while
+//
+
+#![cfg(feature = "proptest")]
+
+use cubesim::prelude::*;
+use cubesim::{
+    all_moves, check_cube, check_notation, check_solution, cube_strategy, moves_strategy, simplify_moves, solve,
+    FaceletCube, ARBITRARY_MAX_SIZE,
+};
+use proptest::prelude::*;
+use proptest::sample::select;
+
+proptest! {
+    #[test]
+    fn notation_round_trip(moves in moves_strategy(ARBITRARY_MAX_SIZE, 0..30)) {
+        prop_assert_eq!(check_notation(&moves), Ok(()));
+    }
+
+    #[test]
+    fn turned_cubes_stay_valid(cube in any::<FaceletCube>()) {
+        prop_assert_eq!(check_cube(&cube), Ok(()));
+    }
+
+    #[test]
+    fn inverse_undoes(cube in cube_strategy(4), moves in moves_strategy(4, 0..20)) {
+        let inverse: Vec<Move> = moves.iter().rev().map(|mv| mv.inverse()).collect();
+        prop_assert_eq!(cube.apply_moves(&moves).apply_moves(&inverse), cube);
+    }
+
+    #[test]
+    fn simplified_moves_turn_alike(moves in moves_strategy(3, 0..30)) {
+        let cube = FaceletCube::new(3);
+        prop_assert_eq!(cube.apply_moves(&simplify_moves(&moves)), cube.apply_moves(&moves));
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(16))]
+
+    #[test]
+    fn thistlethwaite_solves(moves in prop::collection::vec(select(all_moves(3)), 0..40)) {
+        let cube = FaceletCube::new(3).apply_moves(&moves);
+        let solution = solve(&cube).unwrap();
+        prop_assert_eq!(check_solution(&cube, &solution), Ok(()));
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+#[test]
+fn arbitrary_cubes_are_valid() {
+    use arbitrary::{Arbitrary, Unstructured};
+
+    let bytes: Vec<u8> = (0..4096u32).map(|i| (i.wrapping_mul(2654435761) >> 13) as u8).collect();
+    let mut u = Unstructured::new(&bytes);
+    while !u.is_empty() {
+        let cube = <FaceletCube as Arbitrary>::arbitrary(&mut u).unwrap();
+        assert_eq!(check_cube(&cube), Ok(()));
+        let mv = <Move as Arbitrary>::arbitrary(&mut u).unwrap();
+        assert_eq!(check_notation(&[mv]), Ok(()));
+    }
+}
@@ -0,0 +1,30 @@
+//
+// Inner Move Tests
+//
+
+use test_case::test_case;
+
+use cubesim::prelude::*;
+use cubesim::{FaceletCube, GeoCube};
+
+#[test_case(GeoCube::new(4) ; "Geometric Cube")]
+#[test_case(FaceletCube::new(4) ; "Facelet Cube")]
+fn inner_moves(cube: impl Cube) {
+    use Move::*;
+    use MoveVariant::*;
+
+    // an inner layer turn is the wide turn up to it, undoing the layers outside of it
+    let cases = [
+        (Ui(2, Standard), Uw(2, Standard), U(Inverse)),
+        (Li(2, Inverse), Lw(2, Inverse), L(Standard)),
+        (Fi(3, Double), Fw(3, Double), Fw(2, Double)),
+        (Ri(3, Standard), Rw(3, Standard), Rw(2, Inverse)),
+        (Bi(2, Double), Bw(2, Double), B(Double)),
+        (Di(3, Inverse), Dw(3, Inverse), Dw(2, Standard)),
+    ];
+
+    for (inner, wide, outer) in cases {
+        assert_eq!(cube.apply_move(inner).state(), cube.apply_moves(&[wide, outer]).state());
+    }
+    assert_eq!(cube.apply_move(Ri(1, Standard)).state(), cube.apply_move(R(Standard)).state());
+}
@@ -0,0 +1,96 @@
+//
+// KPuzzle Definition Tests
+//
+
+use cubesim::{ida_star, parse_kpuzzle, KPuzzle, PruningTable, Solver, TwistyPuzzle};
+
+const CORNERS_2X2X2: &str = "
+Name 2x2x2 Corners
+
+# corners are numbered UFR UFL UBL UBR DFR DFL DBL DBR
+Set CORNERS 8 3
+
+Solved
+CORNERS
+1 2 3 4 5 6 7 8
+End
+
+Move U
+CORNERS
+4 1 2 3 5 6 7 8
+End
+
+Move R
+CORNERS
+5 2 3 1 8 6 7 4
+1 0 0 2 2 0 0 1
+End
+
+Move F
+CORNERS
+2 6 3 4 1 5 7 8
+2 1 0 0 1 2 0 0
+End
+";
+
+#[test]
+fn parses_definition() {
+    let definition = parse_kpuzzle(CORNERS_2X2X2).unwrap();
+
+    assert_eq!(definition.name, "2x2x2 Corners");
+    assert_eq!(definition.sets.len(), 1);
+    assert_eq!(definition.sets[0].orientations, 3);
+    assert_eq!(definition.move_names(), vec!["U", "R", "F"]);
+    assert_eq!(definition.get_move("R").unwrap().order, 4);
+}
+
+#[test]
+fn generates_move_powers() {
+    let puzzle = KPuzzle::new(parse_kpuzzle(CORNERS_2X2X2).unwrap());
+    let moves = puzzle.moves();
+
+    assert_eq!(moves.len(), 9);
+    assert_eq!(
+        moves.iter().map(|m| m.to_string()).collect::<Vec<_>>(),
+        vec!["U", "U2", "U'", "R", "R2", "R'", "F", "F2", "F'"]
+    );
+}
+
+#[test]
+fn move_and_inverse_is_solved() {
+    let puzzle = KPuzzle::new(parse_kpuzzle(CORNERS_2X2X2).unwrap());
+
+    for mv in puzzle.moves() {
+        let turned = puzzle.apply_move(mv);
+        assert!(!turned.is_solved());
+        assert!(turned.apply_move(KPuzzle::inverse_move(mv)).is_solved());
+    }
+}
+
+#[test]
+fn solves_with_generic_solver() {
+    let puzzle = KPuzzle::new(parse_kpuzzle(CORNERS_2X2X2).unwrap());
+    let moves = puzzle.moves();
+    let scrambled = puzzle.apply_moves(&[moves[0], moves[3], moves[8]]);
+
+    let solver = Solver::new(moves.clone(), PruningTable::new(&[puzzle], 2, &moves));
+    let solution = ida_star(&scrambled, &solver, 4).unwrap();
+
+    assert!(scrambled.apply_moves(&solution).is_solved());
+}
+
+#[test]
+fn reports_errors() {
+    let error = parse_kpuzzle("Set CORNERS 8 3\nMove U\nCORNERS\n1 1 2 3 4 5 6 7\nEnd").unwrap_err();
+    assert_eq!(error.line, 3);
+
+    let error = parse_kpuzzle("Set CORNERS 8 3\nSolved\nEDGES\n").unwrap_err();
+    assert_eq!(error.line, 3);
+
+    assert!(parse_kpuzzle("Ignore\nEnd").is_err());
+    assert!(parse_kpuzzle("Name Empty").is_err());
+
+    let long = CORNERS_2X2X2.replace("Move F", "Move FrontFaceClockwise");
+    let error = parse_kpuzzle(&long).unwrap_err();
+    assert_eq!(error.message, "move name FrontFaceClockwise is longer than 15 bytes");
+}
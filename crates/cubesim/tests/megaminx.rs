@@ -0,0 +1,115 @@
+//
+// Megaminx Tests
+//
+
+use cubesim::{
+    parse_megaminx_scramble, random_megaminx_scramble, Megaminx, MinxFace, MinxMove, TwistyPuzzle,
+};
+
+//
+// State Tests
+//
+#[test]
+fn solved_state() {
+    let minx = Megaminx::new();
+    assert!(minx.is_solved());
+    assert_eq!(minx.state().len(), 132);
+    assert_eq!(
+        minx.state().iter().filter(|f| **f == MinxFace::BR).count(),
+        11
+    );
+}
+
+#[test]
+fn face_turn_moves_neighbour_strips() {
+    for face in [MinxFace::U, MinxFace::F, MinxFace::DBL] {
+        let changed = Megaminx::new()
+            .apply_move(MinxMove::Turn(face, 1))
+            .state()
+            .iter()
+            .zip(Megaminx::new().state())
+            .filter(|(a, b)| **a != *b)
+            .count();
+
+        // 5 neighbouring faces each lose a strip of 3 stickers
+        assert_eq!(changed, 15);
+    }
+}
+
+#[test]
+fn five_turns_are_identity() {
+    for face in [MinxFace::U, MinxFace::R, MinxFace::DR] {
+        let minx = Megaminx::new().apply_moves(&[MinxMove::Turn(face, 1); 5]);
+        assert!(minx.is_solved());
+        assert_eq!(minx, Megaminx::new());
+
+        let minx = Megaminx::new().apply_moves(&[MinxMove::Wide(face, 2); 5]);
+        assert_eq!(minx, Megaminx::new());
+    }
+}
+
+#[test]
+fn move_then_inverse_is_solved() {
+    let moves = parse_megaminx_scramble("R++ D-- U BR2' F").unwrap();
+
+    let mut minx = Megaminx::new().apply_moves(&moves);
+    assert!(!minx.is_solved());
+
+    for mv in moves.iter().rev() {
+        minx = minx.apply_move(mv.inverse());
+    }
+    assert!(minx.is_solved());
+}
+
+#[test]
+fn wide_move_leaves_opposite_layer() {
+    let minx = Megaminx::new().apply_move(MinxMove::Wide(MinxFace::D, 2));
+    let state = minx.state();
+
+    // The U face is never touched by a D++ move.
+    assert!(state[0..11].iter().all(|f| *f == MinxFace::U));
+    assert!(!minx.is_solved());
+}
+
+//
+// Notation Tests
+//
+#[test]
+fn parse_and_display() {
+    let scramble = "R++ D-- R-- D++ U' DBL2 BR";
+    let moves = parse_megaminx_scramble(scramble).unwrap();
+
+    assert_eq!(moves[0], MinxMove::Wide(MinxFace::R, 2));
+    assert_eq!(moves[5], MinxMove::Turn(MinxFace::DBL, 2));
+    assert_eq!(
+        moves
+            .iter()
+            .map(|m| m.to_string())
+            .collect::<Vec<_>>()
+            .join(" "),
+        scramble
+    );
+}
+
+#[test]
+fn malformed_moves_are_errors() {
+    for (scramble, position, token) in [
+        ("Q++", 1, "Q++"),
+        ("R++ D+-", 2, "D+-"),
+        ("U BR2x", 2, "BR2x"),
+        ("U 99999999999", 2, "99999999999"),
+        ("F U-2", 2, "U-2"),
+    ] {
+        let error = parse_megaminx_scramble(scramble).unwrap_err();
+        assert_eq!((error.position, error.token.as_str()), (position, token));
+    }
+}
+
+#[test]
+fn random_scramble_shape() {
+    let scramble = random_megaminx_scramble();
+
+    assert_eq!(scramble.len(), 77);
+    assert!(matches!(scramble[10], MinxMove::Turn(MinxFace::U, _)));
+    assert!(!Megaminx::new().apply_moves(&scramble).is_solved());
+}
@@ -0,0 +1,83 @@
+//
+// Solving Method Tests
+//
+
+use cubesim::prelude::*;
+use cubesim::{prepare_tables, solve_cfop, CfopPhase, FaceletCube, SolveMethod};
+
+fn scrambled(scramble: &str) -> FaceletCube {
+    FaceletCube::new(3).apply_algorithm(scramble).unwrap()
+}
+
+#[test]
+fn test_methods_solve() {
+    let cube = scrambled("D2 F' R2 U' B2 L' U2 F R' D");
+    for method in SolveMethod::ALL {
+        let solution = method.solve(&cube, &|| false).unwrap();
+        assert_eq!(solution.method, method);
+        assert!(solution.rotation.is_empty());
+        assert!(cube.apply_moves(&solution.moves()).is_solved(), "{method} failed");
+    }
+}
+
+#[test]
+fn test_cfop_phases() {
+    let cube = scrambled("R U2 F' L D' B R2 U");
+    let phases = solve_cfop(&cube, &|| false).unwrap();
+    let names: Vec<CfopPhase> = phases.iter().map(|(phase, _)| *phase).collect();
+    assert_eq!(names, CfopPhase::ALL.to_vec());
+
+    let mut solving = cube.clone();
+    for (phase, moves) in phases {
+        solving = solving.apply_moves(&moves);
+        assert!(phase.is_done(&solving), "{phase} not done");
+    }
+    assert!(solving.is_solved());
+}
+
+#[test]
+fn test_rotated_cube() {
+    let cube = scrambled("x R U R' U' y2");
+    let solution = SolveMethod::Cfop.solve(&cube, &|| false).unwrap();
+    assert!(!solution.rotation.is_empty());
+    assert_eq!(solution.len(), solution.moves().len() - solution.rotation.len());
+    assert!(cube.apply_moves(&solution.moves()).is_solved());
+}
+
+#[test]
+fn test_solution_without_rotations() {
+    let rotation = |mv: &Move| matches!(mv, Move::X(_) | Move::Y(_) | Move::Z(_));
+    // a PLL algorithm with rotations is needed for the last layer
+    let cube = scrambled("z2 x R' U R' D2 R U' R' D2 R2 x' y");
+    for method in SolveMethod::ALL {
+        let solution = method.solve(&cube, &|| false).unwrap();
+        let rewritten = solution.without_rotations();
+
+        assert!(rewritten.rotation.is_empty());
+        assert!(!rewritten.moves().iter().any(rotation));
+        let turns = solution.steps.iter().flat_map(|(_, moves)| moves).filter(|mv| !rotation(mv));
+        assert_eq!(rewritten.len(), turns.count());
+        assert_eq!(rewritten.steps.len(), solution.steps.len());
+        assert!(cube.apply_moves(&rewritten.moves()).is_solved());
+    }
+}
+
+#[test]
+fn test_solved_and_out_of_time() {
+    let solved = FaceletCube::new(3);
+    assert!(SolveMethod::Cfop.solve(&solved, &|| false).unwrap().is_empty());
+
+    assert_eq!(solve_cfop(&scrambled("R U F' L2 D B'"), &|| true), None);
+    assert_eq!(SolveMethod::Thistlethwaite.solve(&FaceletCube::new(4), &|| false), None);
+}
+
+#[test]
+fn test_prepare_tables() {
+    let mut shares = Vec::new();
+    prepare_tables(&mut |share| shares.push(share));
+    assert!(shares.windows(2).all(|pair| pair[0] < pair[1]));
+    assert_eq!(shares.last(), Some(&1.0));
+
+    let cube = scrambled("R U R' U'");
+    assert!(SolveMethod::Cfop.solve(&cube, &|| false).is_some());
+}
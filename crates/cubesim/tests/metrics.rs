@@ -0,0 +1,98 @@
+//
+// Metrics Tests
+//
+
+#![cfg(feature = "metrics")]
+
+use cubesim::prelude::*;
+use cubesim::{describe_metrics, parse_scramble, solve, solve_cfop, FaceletCube};
+use metrics::{SharedString, Unit};
+use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+use metrics_util::CompositeKey;
+
+// What was recorded under each metric, by its name and labels, taken once as taking it clears
+// the values
+struct Recorded(Vec<(CompositeKey, Option<Unit>, Option<SharedString>, DebugValue)>);
+
+impl Recorded {
+    fn of(recorder: &DebuggingRecorder) -> Self {
+        Recorded(recorder.snapshotter().snapshot().into_vec())
+    }
+
+    fn values(&self, name: &str, labels: &[(&str, &str)]) -> Vec<&DebugValue> {
+        self.0
+            .iter()
+            .filter(|(key, _, _, _)| {
+                let key = key.key();
+                key.name() == name
+                    && key.labels().map(|label| (label.key(), label.value())).eq(labels.iter().copied())
+            })
+            .map(|(_, _, _, value)| value)
+            .collect()
+    }
+
+    fn counter(&self, name: &str) -> u64 {
+        match self.values(name, &[]).as_slice() {
+            [DebugValue::Counter(n)] => *n,
+            values => panic!("{name} recorded {values:?}"),
+        }
+    }
+
+    fn histogram(&self, name: &str, labels: &[(&str, &str)]) -> Vec<f64> {
+        match self.values(name, labels).as_slice() {
+            [DebugValue::Histogram(values)] => values.iter().map(|value| value.into_inner()).collect(),
+            values => panic!("{name} recorded {values:?}"),
+        }
+    }
+
+    fn unit(&self, name: &str) -> Option<Unit> {
+        self.0.iter().find(|(key, _, _, _)| key.key().name() == name).and_then(|(_, unit, _, _)| *unit)
+    }
+}
+
+#[test]
+fn search_work() {
+    let recorder = DebuggingRecorder::new();
+    let cube = FaceletCube::new(3).apply_moves(&parse_scramble(String::from("R U F' L2 D B")));
+    let solution = metrics::with_local_recorder(&recorder, || solve(&cube)).unwrap();
+
+    let recorded = Recorded::of(&recorder);
+    let nodes = recorded.counter("cubesim.search.nodes");
+    let hits = recorded.counter("cubesim.pruning_table.hits");
+    let misses = recorded.counter("cubesim.pruning_table.misses");
+    assert!(nodes >= solution.len() as u64);
+    assert_eq!(hits + misses, nodes);
+    assert!(recorded.counter("cubesim.transposition_table.hits") < nodes);
+
+    let labels = [("method", "thistlethwaite"), ("solved", "true")];
+    let durations = recorded.histogram("cubesim.solve.duration", &labels);
+    assert_eq!(durations.len(), 1);
+    assert!(durations[0] >= 0.0);
+    assert_eq!(recorded.histogram("cubesim.solve.moves", &labels), vec![solution.len() as f64]);
+}
+
+#[test]
+fn unsolved_cfop() {
+    let recorder = DebuggingRecorder::new();
+    let cube = FaceletCube::new(3).apply_moves(&parse_scramble(String::from("R U R' U'")));
+    let phases = metrics::with_local_recorder(&recorder, || solve_cfop(&cube, &|| true));
+    assert!(phases.is_none());
+
+    let recorded = Recorded::of(&recorder);
+    let labels = [("method", "cfop"), ("solved", "false")];
+    assert_eq!(recorded.histogram("cubesim.solve.duration", &labels).len(), 1);
+    assert!(recorded.values("cubesim.solve.moves", &labels).is_empty());
+}
+
+#[test]
+fn described_units() {
+    let recorder = DebuggingRecorder::new();
+    metrics::with_local_recorder(&recorder, || {
+        describe_metrics();
+        solve(&FaceletCube::new(3));
+    });
+
+    let recorded = Recorded::of(&recorder);
+    assert_eq!(recorded.unit("cubesim.solve.duration"), Some(Unit::Seconds));
+    assert_eq!(recorded.unit("cubesim.search.nodes"), Some(Unit::Count));
+}
@@ -0,0 +1,15 @@
+//
+// Piece Type Tests
+//
+
+use cubesim::{piece_type, PieceType};
+
+#[test]
+fn generic_piece_types() {
+    let count = |piece: PieceType| (0..54).filter(|i| piece_type(3, *i) == piece).count();
+
+    // 6 centers of one sticker, 12 edges of two and 8 corners of three
+    assert_eq!(count(PieceType::Center), 6);
+    assert_eq!(count(PieceType::Edge), 24);
+    assert_eq!(count(PieceType::Corner), 24);
+}
@@ -0,0 +1,81 @@
+//
+// Random State Tests
+//
+
+use rand::{rngs::StdRng, SeedableRng};
+
+use cubesim::prelude::*;
+use cubesim::{solve, validate_state, FaceletCube, PieceMask, StateMask};
+
+#[test]
+fn test_random_state_is_solvable() {
+    for seed in 0..20 {
+        let cube = FaceletCube::random_state(&mut StdRng::seed_from_u64(seed));
+        assert!(validate_state(&cube.state()).is_ok(), "seed {seed}");
+        let solution = solve(&cube).unwrap();
+        assert!(cube.apply_moves(&solution).is_solved(), "seed {seed}");
+    }
+}
+
+#[test]
+fn test_random_state_varies() {
+    let mut rng = StdRng::seed_from_u64(1);
+    let states: Vec<Vec<Face>> = (0..10).map(|_| FaceletCube::random_state(&mut rng).state()).collect();
+    assert!(states.iter().all(|state| *state != FaceletCube::new(3).state()));
+    assert!(states.windows(2).all(|pair| pair[0] != pair[1]));
+}
+
+fn random_states(mask: &StateMask) -> impl Iterator<Item = Vec<Face>> + '_ {
+    (0..50).map(move |seed| FaceletCube::random_masked_state(mask, &mut StdRng::seed_from_u64(seed)).state())
+}
+
+#[test]
+fn test_masked_states_are_valid() {
+    for mask in [StateMask::RANDOM, StateMask::CROSS, StateMask::FIRST_LAYER, StateMask::LAST_LAYER, StateMask::ORIENTED_LAST_LAYER] {
+        for state in random_states(&mask) {
+            assert_eq!(validate_state(&state), Ok(()), "{mask:?}");
+        }
+    }
+}
+
+#[test]
+fn test_cross_stays_solved() {
+    let solved = FaceletCube::new(3).state();
+    // the D stickers and the side stickers of the DF, DR, DB and DL edges
+    let cross = [28, 30, 31, 32, 34, 25, 16, 52, 43];
+    for state in random_states(&StateMask::CROSS) {
+        assert!(cross.iter().all(|&i| state[i] == solved[i]));
+    }
+}
+
+#[test]
+fn test_last_layer_only_scrambles_the_u_layer() {
+    let solved = FaceletCube::new(3).state();
+    for state in random_states(&StateMask::LAST_LAYER) {
+        // the D face and the two bottom rows of the sides
+        for face in [1, 2, 4, 5] {
+            assert_eq!(state[face * 9 + 3..face * 9 + 9], solved[face * 9 + 3..face * 9 + 9]);
+        }
+        assert_eq!(state[27..36], solved[27..36]);
+    }
+    assert!(random_states(&StateMask::LAST_LAYER).any(|state| state[..9] != solved[..9]));
+}
+
+#[test]
+fn test_oriented_last_layer_keeps_the_u_face() {
+    let solved = FaceletCube::new(3).state();
+    for state in random_states(&StateMask::ORIENTED_LAST_LAYER) {
+        assert!(state[..9].iter().all(|&face| face == Face::U));
+    }
+    assert!(random_states(&StateMask::ORIENTED_LAST_LAYER).any(|state| state != solved));
+}
+
+#[test]
+fn test_corners_only_fix_parity() {
+    let mask = StateMask { corners: [PieceMask::Random; 8], edges: [PieceMask::Solved; 12] };
+    for state in random_states(&mask) {
+        let cube = FaceletCube::from(state);
+        assert!(validate_state(&cube.state()).is_ok());
+        assert!(cube.apply_moves(&solve(&cube).unwrap()).is_solved());
+    }
+}
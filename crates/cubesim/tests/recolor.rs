@@ -0,0 +1,53 @@
+//
+// Color Scheme Tests
+//
+
+use cubesim::prelude::*;
+use cubesim::{detect_scheme, parse_scramble, recolor, solve, FaceletCube};
+
+// A scheme with the colors of the side faces moved around and U and D swapped
+const SCHEME: [(Face, Face); 6] = [
+    (Face::U, Face::D),
+    (Face::D, Face::U),
+    (Face::R, Face::F),
+    (Face::F, Face::L),
+    (Face::L, Face::B),
+    (Face::B, Face::R),
+];
+
+#[test]
+fn test_recolor_keeps_unmapped_faces() {
+    let state = vec![Face::U, Face::X, Face::R];
+    assert_eq!(recolor(&state, &[(Face::U, Face::F)]), vec![Face::F, Face::X, Face::R]);
+    assert_eq!(recolor(&state, &[]), state);
+}
+
+#[test]
+fn test_standard_scheme_is_kept() {
+    let state = FaceletCube::new(5).apply_moves(&parse_scramble(String::from("2R U' F2"))).state();
+    let mapping = detect_scheme(&state).unwrap();
+
+    assert!(mapping.iter().all(|(from, to)| from == to));
+    assert_eq!(recolor(&state, &mapping), state);
+}
+
+#[test]
+fn test_normalized_state_is_solved() {
+    let scrambled = FaceletCube::new(3).apply_moves(&parse_scramble(String::from("R U2 F' L D B2")));
+    let imported = recolor(&scrambled.state(), &SCHEME);
+
+    let normalized = recolor(&imported, &detect_scheme(&imported).unwrap());
+    assert_eq!(normalized, scrambled.state());
+    let solution = solve(&FaceletCube::from(normalized)).unwrap();
+    assert!(scrambled.apply_moves(&solution).is_solved());
+}
+
+#[test]
+fn test_no_scheme_detected() {
+    assert!(detect_scheme(&FaceletCube::new(4).state()).is_err());
+    assert!(detect_scheme(&FaceletCube::new(3).state()[..50]).is_err());
+
+    let mut same_centers = FaceletCube::new(3).state();
+    same_centers[13] = Face::U;
+    assert!(detect_scheme(&same_centers).is_err());
+}
@@ -0,0 +1,57 @@
+//
+// Reid Notation Tests
+//
+
+use cubesim::prelude::*;
+use cubesim::{parse_reid, parse_scramble, recolor, reid_string, solve, FaceletCube};
+
+const SOLVED: &str = "UF UR UB UL DF DR DB DL FR FL BR BL UFR URB UBL ULF DRF DFL DLB DBR";
+
+#[test]
+fn test_solved_reid() {
+    let solved = FaceletCube::new(3);
+    assert_eq!(reid_string(&solved.state()).unwrap(), SOLVED);
+    assert_eq!(parse_reid(SOLVED).unwrap(), solved.state());
+}
+
+#[test]
+fn test_reid_round_trip() {
+    let cube = FaceletCube::new(3).apply_moves(&parse_scramble(String::from(
+        "R U R' U' F2 D' L B2 R' U F' D2 B L2",
+    )));
+    let reid = reid_string(&cube.state()).unwrap();
+
+    assert_eq!(parse_reid(&reid).unwrap(), cube.state());
+}
+
+#[test]
+fn test_reid_ignores_scheme_and_rotation() {
+    let scramble = parse_scramble(String::from("R U2 F' L D"));
+    let cube = FaceletCube::new(3).apply_moves(&scramble);
+    let rotated = FaceletCube::new(3).apply_moves(&[Move::Y(MoveVariant::Standard)]).apply_moves(&scramble);
+    let recolored = recolor(&cube.state(), &[(Face::U, Face::D), (Face::D, Face::U)]);
+
+    let reid = reid_string(&cube.state()).unwrap();
+    assert_eq!(reid_string(&rotated.state()).unwrap(), reid);
+    assert_eq!(reid_string(&recolored).unwrap(), reid);
+}
+
+#[test]
+fn test_imported_reid_is_solved() {
+    let imported = parse_reid("UF FR UB UL DF BR DB DL DR FL UR BL FDR FRU UBL ULF BRD DFL DLB BUR").unwrap();
+    let cube = FaceletCube::from(imported);
+    let solution = solve(&cube).unwrap();
+
+    assert!(cube.apply_moves(&solution).is_solved());
+}
+
+#[test]
+fn test_invalid_reid() {
+    assert!(parse_reid("UF UR").is_err());
+    assert!(parse_reid(&SOLVED.replace("UFR", "UF")).is_err());
+    assert!(parse_reid(&SOLVED.replace("UFR", "UFX")).is_err());
+    // a flipped edge and two swapped edges
+    assert!(parse_reid(&SOLVED.replacen("UF", "FU", 1)).is_err());
+    assert!(parse_reid(&SOLVED.replace("UR UB", "UB UR")).is_err());
+    assert!(reid_string(&FaceletCube::new(2).state()).is_err());
+}
@@ -0,0 +1,71 @@
+//
+// Solve Report Tests
+//
+
+use core::time::Duration;
+use cubesim::{parse_scramble, Penalty, SolveReport, TimedMove};
+
+fn timed(moves: &str, step: Duration) -> Vec<TimedMove> {
+    parse_scramble(moves.to_string())
+        .into_iter()
+        .enumerate()
+        .map(|(i, mv)| TimedMove { mv, at: step * i as u32 })
+        .collect()
+}
+
+#[test]
+fn test_report_json() {
+    let solve = timed("U R'", Duration::from_millis(1500));
+    let mut report = SolveReport::new(3, &parse_scramble(String::from("R U'")), &solve, Duration::from_secs(1));
+    report.finished_at = Some(Duration::from_millis(1_700_000_000_250));
+
+    assert_eq!(
+        report.to_json(),
+        concat!(
+            r#"{"cube_size":3,"scramble":"R U'","solution":"U R'","move_times":[0.000,1.500],"#,
+            r#""finished_at":1700000000.250,"penalty":null,"moves":2,"duration":1.500,"tps":1.33,"#,
+            r#""pauses":[{"before":1,"length":1.500}],"#,
+            r#""phases":[{"phase":"Cross","moves":2,"duration":1.500},"#,
+            r#"{"phase":"F2L","moves":0,"duration":0.000},"#,
+            r#"{"phase":"OLL","moves":0,"duration":0.000},"#,
+            r#"{"phase":"PLL","moves":0,"duration":0.000}]}"#
+        )
+    );
+}
+
+#[test]
+fn test_report_without_phases() {
+    let solve = timed("Rw2 U", Duration::from_millis(200));
+    let report = SolveReport::new(4, &parse_scramble(String::from("U' Rw2")), &solve, Duration::from_secs(1));
+
+    assert!(report.stats.phases.is_empty());
+    let json = report.to_json();
+    assert!(json.contains(r#""finished_at":null,"#));
+    assert!(json.ends_with(r#""pauses":[],"phases":[]}"#));
+}
+
+#[test]
+fn test_report_penalty() {
+    let solve = timed("R'", Duration::from_millis(100));
+    let mut report = SolveReport::new(3, &parse_scramble(String::from("R")), &solve, Duration::from_secs(1));
+    assert_eq!(report.result().counted(), Some(Duration::ZERO));
+
+    report.penalty = Some(Penalty::PlusTwo);
+    assert!(report.to_json().contains(r#""penalty":"+2","#));
+    assert_eq!(report.result().counted(), Some(Duration::from_secs(2)));
+}
+
+#[test]
+fn test_report_reconstruction() {
+    let step = Duration::from_millis(500);
+    let scramble = parse_scramble("F U R U' R' F'".to_string());
+    let report = SolveReport::new(3, &scramble, &timed("F R U R' U' F'", step), step);
+    assert_eq!(report.reconstruction(), "// Cross skip, F2L skip\nF (R U R' U') F' // OLL (6 moves, 2.50 s), PLL skip");
+
+    // a solve stopped before the cross
+    let report = SolveReport::new(3, &parse_scramble("R U".to_string()), &timed("U'", step), step);
+    assert_eq!(report.reconstruction(), "U' // unfinished");
+
+    let report = SolveReport::new(4, &parse_scramble("R U".to_string()), &timed("U' R'", step), step);
+    assert_eq!(report.reconstruction(), "U' R'");
+}
@@ -2,8 +2,12 @@
 // Move Parser Tests
 //
 
-use cubesim::{parse_scramble, simplify_moves};
-use cubesim::prelude::{Move::*, MoveVariant::*};
+use cubesim::{
+    format_scramble, parse_algorithm, parse_algorithm_for, parse_scramble, random_scramble, random_scramble_with, remove_rotations,
+    simplify_moves, simplify_moves_with, FaceletCube, GeoCube, ParseError, ScrambleFormat, ScrambleOptions,
+    SimplifyOptions,
+};
+use cubesim::prelude::{Cube, Move, Move::*, MoveVariant::*, TwistyPuzzle};
 
 #[test]
 fn test_basic_moves() {
@@ -77,6 +81,60 @@ fn test_wide_moves() {
     ]);
 }
 
+#[test]
+fn test_inner_moves() {
+    assert_eq!(parse_scramble(String::from("2R 3U' 10F2 1L")), vec![
+        Ri(2, Standard),
+        Ui(3, Inverse),
+        Fi(10, Double),
+        Li(1, Standard)
+    ]);
+    assert_eq!(Ri(3, Inverse).to_string(), "3R'");
+}
+
+#[test]
+fn test_parse_algorithm() {
+    let algorithm = "R U2 3Fw' 2L x Dw";
+    assert_eq!(parse_algorithm(algorithm), Ok(parse_scramble(String::from(algorithm))));
+    assert_eq!(parse_algorithm("  "), Ok(vec![]));
+
+    for (algorithm, position, token) in [
+        ("R Uw3", 2, "Uw3"),
+        ("2x", 1, "2x"),
+        ("R U R' Q", 4, "Q"),
+        ("0R", 1, "0R"),
+        ("R 0Fw2", 2, "0Fw2"),
+        ("U 99999999999R", 2, "99999999999R"),
+    ] {
+        let error = ParseError { position, token: String::from(token) };
+        assert_eq!(parse_algorithm(algorithm), Err(error.clone()));
+        assert_eq!(error.to_string(), format!("invalid move {token}"));
+    }
+}
+
+#[test]
+fn test_parse_algorithm_for_size() {
+    assert_eq!(parse_algorithm_for("3R 3Rw2 x", 3), Ok(vec![Ri(3, Standard), Rw(3, Double), X(Standard)]));
+    assert_eq!(parse_algorithm_for("4R", 4), Ok(vec![Ri(4, Standard)]));
+
+    for (algorithm, position, token) in [("R 4R", 2, "4R"), ("5Rw", 1, "5Rw"), ("Rw 0R", 2, "0R")] {
+        let error = ParseError { position, token: String::from(token) };
+        assert_eq!(parse_algorithm_for(algorithm, 3), Err(error));
+    }
+    assert_eq!(parse_algorithm_for("Rw", 1).unwrap_err().token, "Rw");
+}
+
+#[test]
+fn test_apply_algorithm() {
+    let algorithm = "R U R' U' 2Lw2 z";
+    let moves = parse_scramble(String::from(algorithm));
+
+    assert_eq!(FaceletCube::new(4).apply_algorithm(algorithm), Ok(FaceletCube::new(4).apply_moves(&moves)));
+    assert!(GeoCube::new(4).apply_algorithm(algorithm).unwrap() == GeoCube::new(4).apply_moves(&moves));
+    assert_eq!(FaceletCube::new(3).apply_algorithm("R U R'2'").unwrap_err().position, 3);
+    assert_eq!(FaceletCube::new(3).apply_algorithm("R 4Lw").unwrap_err().position, 2);
+}
+
 #[test]
 fn test_simplify_unsimplifiable_moves() {
     assert_eq!(simplify_moves(&parse_scramble(String::from("x2"))), vec![X(Double)]);
@@ -94,7 +152,13 @@ fn test_simplify_standard_and_inverse_cancel() {
 #[test]
 fn test_simplify_two_doubles_cancel() {
     assert_eq!(simplify_moves(&parse_scramble(String::from("D2 D2"))), vec![]);
-    assert_eq!(simplify_moves(&parse_scramble(String::from("2Lw 2Lw"))), vec![]);
+    assert_eq!(simplify_moves(&parse_scramble(String::from("Lw2 2Lw2"))), vec![]);
+}
+
+#[test]
+fn test_simplify_different_layers_do_not_merge() {
+    assert_eq!(simplify_moves(&parse_scramble(String::from("Rw 3Rw 2R 3R"))),
+               vec![Rw(2, Standard), Rw(3, Standard), Ri(2, Standard), Ri(3, Standard)]);
 }
 
 #[test]
@@ -128,4 +192,183 @@ fn test_simplify_long() {
 fn test_simplify_complex() {
     assert_eq!(simplify_moves(&parse_scramble(String::from("R U2 R' R U2 F F' U2 B2 B2 U' U2 U R'"))), 
                vec![])
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_remove_rotations() {
+    assert_eq!(remove_rotations(&parse_scramble(String::from("x y2 z'"))), vec![]);
+    assert_eq!(remove_rotations(&parse_scramble(String::from("x U z 3Ri2 y' 2Fw"))),
+               vec![F(Standard), Fi(3, Double), Bw(2, Standard)]);
+
+    for seed in 0..20 {
+        let options = ScrambleOptions {
+            length: Some(40),
+            wide_moves: true,
+            rotations: true,
+            seed: Some(seed),
+        };
+        let scramble = random_scramble_with(4, options);
+        let rotations: Vec<Move> = scramble.iter().copied().filter(|mv| matches!(mv, X(_) | Y(_) | Z(_))).collect();
+        let rewritten = remove_rotations(&scramble);
+
+        assert_eq!(rewritten.len(), scramble.len() - rotations.len());
+        assert_eq!(FaceletCube::new(4).apply_moves(&rewritten).apply_moves(&rotations),
+                   FaceletCube::new(4).apply_moves(&scramble));
+    }
+}
+
+#[test]
+fn test_simplify_without_rotations() {
+    let scramble = parse_scramble(String::from("R x' F y2 B' y2 x U2"));
+    let with_rotations = SimplifyOptions { rotations: true };
+
+    // F and B' both turn U once rotated, cancelling out
+    assert_eq!(simplify_moves_with(&scramble, SimplifyOptions::default()), vec![R(Standard), U(Double)]);
+    assert_eq!(simplify_moves_with(&scramble, with_rotations), simplify_moves(&scramble));
+}
+
+#[test]
+fn test_format_scramble_without_groups() {
+    let scramble = parse_scramble(String::from("R U' F2 D L2 B R' U2"));
+    let format = ScrambleFormat {
+        group: None,
+        width: Some(10),
+        numbered: false,
+    };
+
+    assert_eq!(format_scramble(&scramble, format), "R U' F2 D\nL2 B R' U2");
+    assert_eq!(format_scramble(&[], format), "");
+}
+
+#[test]
+fn test_format_long_scramble() {
+    let scramble = random_scramble(7, true);
+    let format = ScrambleFormat {
+        width: Some(12),
+        numbered: true,
+        ..ScrambleFormat::default()
+    };
+    let formatted = format_scramble(&scramble, format);
+    let lines: Vec<&str> = formatted.lines().collect();
+
+    // no two groups of five moves fit on a line
+    assert_eq!(lines.len(), scramble.len().div_ceil(5));
+    assert!(lines[0].trim_start().starts_with("1. "));
+    assert!(lines.last().unwrap().starts_with(&format!("{}. ", lines.len())));
+    let groups: Vec<&str> = lines.iter().map(|line| line.split_once(". ").unwrap().1).collect();
+    assert!(groups.iter().all(|group| group.split(' ').count() <= 5));
+    assert_eq!(parse_scramble(groups.join(" ")), scramble);
+}
+
+#[test]
+fn test_scramble_options() {
+    let options = ScrambleOptions {
+        length: Some(200),
+        wide_moves: false,
+        rotations: true,
+        seed: Some(7),
+    };
+    let scramble = random_scramble_with(5, options);
+
+    assert_eq!(scramble.len(), 200);
+    assert!(scramble.iter().any(|mv| matches!(mv, X(_) | Y(_) | Z(_))));
+    assert!(scramble.iter().all(|mv| matches!(mv, U(_) | L(_) | F(_) | R(_) | B(_) | D(_) | X(_) | Y(_) | Z(_))));
+    assert_eq!(random_scramble_with(5, options), scramble);
+    assert_ne!(random_scramble_with(5, ScrambleOptions { seed: Some(8), ..options }), scramble);
+
+    let default = random_scramble_with(4, ScrambleOptions::default());
+    assert_eq!(default.len(), 40);
+    assert!(default.iter().all(|mv| !matches!(mv, X(_) | Y(_) | Z(_))));
+}
+
+// The face turned by a move of a scramble, and its axis
+fn face_and_axis(mv: &cubesim::prelude::Move) -> (char, usize) {
+    match mv {
+        U(_) | Uw(..) => ('U', 0),
+        D(_) | Dw(..) => ('D', 0),
+        Y(_) => ('y', 0),
+        R(_) | Rw(..) => ('R', 1),
+        L(_) | Lw(..) => ('L', 1),
+        X(_) => ('x', 1),
+        F(_) | Fw(..) => ('F', 2),
+        B(_) | Bw(..) => ('B', 2),
+        Z(_) => ('z', 2),
+        mv => panic!("unexpected move {mv} in a scramble"),
+    }
+}
+
+#[test]
+fn test_scramble_no_same_face_twice() {
+    for seed in 0..100 {
+        let options = ScrambleOptions { wide_moves: true, rotations: true, seed: Some(seed), ..ScrambleOptions::default() };
+        let scramble = random_scramble_with(5, options);
+        for pair in scramble.windows(2) {
+            assert_ne!(face_and_axis(&pair[0]).0, face_and_axis(&pair[1]).0, "{pair:?} with seed {seed}");
+        }
+    }
+}
+
+#[test]
+fn test_scramble_no_same_axis_thrice() {
+    for seed in 0..100 {
+        for size in [2, 3, 4] {
+            let options = ScrambleOptions { length: Some(100), wide_moves: size > 3, rotations: seed % 2 == 0, seed: Some(seed) };
+            let scramble = random_scramble_with(size, options);
+            for triple in scramble.windows(3) {
+                let axes: Vec<usize> = triple.iter().map(|mv| face_and_axis(mv).1).collect();
+                assert!(axes[0] != axes[1] || axes[1] != axes[2], "{triple:?} with seed {seed}");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_scramble_same_axis_pairs_allowed() {
+    // "R L" is fine, only a third move on the axis isn't
+    let scramble = random_scramble_with(3, ScrambleOptions { length: Some(1000), seed: Some(1), ..ScrambleOptions::default() });
+    assert!(scramble.windows(2).any(|pair| face_and_axis(&pair[0]).1 == face_and_axis(&pair[1]).1));
+}
+
+#[test]
+fn test_move_accessors() {
+    let size = 5;
+    for (mv, opposite) in [(Ri(2, Standard), Li(4, Inverse)), (Ui(3, Double), Di(3, Double)), (F(Inverse), Bi(5, Standard))] {
+        assert_eq!(mv.axis(), opposite.axis());
+        assert_ne!(mv.base_face(), opposite.base_face());
+        assert_eq!(FaceletCube::new(size).apply_move(mv), FaceletCube::new(size).apply_move(opposite));
+
+        // the layers of the move and its opposite counted from the same face are the same
+        let layers = mv.affected_layers(size);
+        let opposite_layers = opposite.affected_layers(size);
+        assert_eq!(layers.start, size - opposite_layers.end);
+        assert_eq!(layers.end, size - opposite_layers.start);
+    }
+
+    assert_eq!(Y(Standard).base_face(), U(Standard).base_face());
+    assert_eq!(Z(Inverse).affected_layers(3), 0..3);
+    assert_eq!(Dw(4, Standard).affected_layers(3), 0..3);
+
+    // moves of depth 0 turn no layer
+    assert_eq!(Ri(0, Standard).affected_layers(3), 0..0);
+    assert_eq!(Rw(0, Double).affected_layers(3), 0..0);
+    assert_eq!(Li(4, Standard).affected_layers(3), 3..3);
+}
+
+#[test]
+fn test_display_moves() {
+    let moves = [
+        R(Standard),
+        U(Inverse),
+        F(Double),
+        Rw(2, Standard),
+        Lw(3, Inverse),
+        Bi(2, Double),
+        X(Standard),
+        Y(Inverse),
+        Z(Double),
+    ];
+    let written = moves.iter().map(|mv| mv.to_string()).collect::<Vec<_>>();
+    assert_eq!(written, ["R", "U'", "F2", "Rw", "3Lw'", "2B2", "X", "Y'", "Z2"]);
+
+    assert_eq!(parse_scramble(written.join(" ")), moves);
+}
@@ -0,0 +1,100 @@
+//
+// Server Tests
+//
+
+#![cfg(feature = "server")]
+
+use cubesim::prelude::Move::*;
+use cubesim::{handle_request, parse_scramble, Cube, FaceletCube, TwistyPuzzle};
+
+fn solution(body: &str) -> String {
+    let start = body.find(r#""solution":""#).unwrap() + r#""solution":""#.len();
+    body[start..].split('"').next().unwrap().to_string()
+}
+
+#[test]
+fn solves_scramble() {
+    let response = handle_request("GET", "/solve?scramble=R+U+F%27", "");
+    assert_eq!(response.status, 200);
+    assert_eq!(response.content_type, "application/json");
+    assert!(response.body.starts_with(r#"{"scramble":"R U F'","solution":""#));
+
+    let response = handle_request("POST", "/solve", "R U F'\n");
+    assert!(response.body.starts_with(r#"{"scramble":"R U F'","solution":""#));
+}
+
+#[test]
+fn solves_scrambles_turning_the_centers() {
+    for (query, scramble) in [("x", "x"), ("y+R", "y R"), ("2R", "2R"), ("3Rw+U", "3Rw U")] {
+        let response = handle_request("GET", &format!("/solve?scramble={query}"), "");
+        assert_eq!(response.status, 200, "{scramble}: {}", response.body);
+
+        let solution = solution(&response.body);
+        let moves = parse_scramble(solution.clone());
+        assert!(!moves.iter().any(|mv| matches!(mv, X(_) | Y(_) | Z(_))), "{scramble}: {solution}");
+        let cube = FaceletCube::new(3)
+            .apply_moves(&parse_scramble(scramble.to_string()))
+            .apply_moves(&moves);
+        assert!(cube.is_solved(), "{scramble}: {solution}");
+    }
+}
+
+#[test]
+fn generates_scramble() {
+    let response = handle_request("GET", "/scramble?size=4", "");
+    assert_eq!(response.status, 200);
+    assert!(response.body.starts_with(r#"{"size":4,"scramble":""#));
+
+    assert_eq!(handle_request("GET", "/scramble?size=0", "").status, 400);
+}
+
+#[test]
+fn validates_scramble() {
+    let response = handle_request("GET", "/validate?scramble=R%20Q", "");
+    assert_eq!(response.status, 200);
+    assert_eq!(response.body, r#"{"valid":false,"error":"invalid move Q"}"#);
+
+    assert_eq!(handle_request("GET", "/solve?scramble=R%20Q", "").status, 400);
+    assert_eq!(handle_request("GET", "/solve?scramble=4R", "").status, 400);
+    assert_eq!(handle_request("GET", "/image?scramble=4R&size=3", "").status, 400);
+    assert_eq!(handle_request("GET", "/image?scramble=4R&size=4", "").status, 200);
+}
+
+#[test]
+fn renders_image() {
+    let response = handle_request("GET", "/image?scramble=R&size=2", "");
+    assert_eq!(response.status, 200);
+    assert_eq!(response.content_type, "image/svg+xml");
+    assert_eq!(response.body.matches("<rect").count(), 24);
+}
+
+#[test]
+fn rejects_unknown_requests() {
+    assert_eq!(handle_request("GET", "/unknown", "").status, 404);
+    assert_eq!(handle_request("DELETE", "/solve", "").status, 405);
+}
+
+#[test]
+fn rejects_large_bodies() {
+    use std::{
+        io::{Read, Write},
+        net::TcpStream,
+        thread,
+        time::Duration,
+    };
+
+    let addr = "127.0.0.1:38917";
+    thread::spawn(move || cubesim::serve(addr));
+    let mut stream = (0..50)
+        .find_map(|_| {
+            thread::sleep(Duration::from_millis(20));
+            TcpStream::connect(addr).ok()
+        })
+        .expect("the server never started");
+
+    // the body isn't sent, the length alone is refused
+    write!(stream, "POST /solve HTTP/1.1\r\nContent-Length: 99999999999\r\n\r\n").unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    assert!(response.starts_with("HTTP/1.1 413 Payload Too Large\r\n"), "{response}");
+}
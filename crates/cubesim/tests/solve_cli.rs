@@ -0,0 +1,128 @@
+//
+// Solver CLI Tests
+//
+
+use std::{
+    io::Write,
+    process::{Command, Output, Stdio},
+};
+
+use cubesim::{parse_scramble, Cube, FaceletCube, TwistyPuzzle};
+
+fn run(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_solve"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn solves(scramble: &str, solution: &str) -> bool {
+    FaceletCube::new(3)
+        .apply_moves(&parse_scramble(scramble.to_string()))
+        .apply_moves(&parse_scramble(solution.to_string()))
+        .is_solved()
+}
+
+#[test]
+fn solves_scramble_from_args() {
+    let output = run(&["R", "U", "F'"], "");
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let solution = stdout.split(" (").next().unwrap();
+    assert!(solves("R U F'", solution));
+}
+
+#[test]
+fn solves_scrambles_turning_the_centers() {
+    for method in ["thistlethwaite", "optimal"] {
+        let output = run(&["--method", method], "x\ny R\n2R\n3Rw U\n");
+        assert!(output.status.success(), "{method}");
+
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        for (scramble, line) in ["x", "y R", "2R", "3Rw U"].iter().zip(stdout.lines()) {
+            let solution = line.split(" (").next().unwrap();
+            assert!(
+                solves(scramble, solution),
+                "{method} {scramble}: {solution}"
+            );
+        }
+    }
+}
+
+#[test]
+fn limits_lengths_in_the_metric() {
+    let output = run(
+        &["--method", "optimal", "--metric", "qtm", "--max-len", "2"],
+        "R2",
+    );
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap().trim(),
+        "R2 (2 qtm)"
+    );
+
+    let output = run(
+        &["--method", "optimal", "--metric", "qtm", "--max-len", "1"],
+        "R2",
+    );
+    assert!(!output.status.success());
+}
+
+#[test]
+fn solves_batch_from_stdin_as_json() {
+    let output = run(
+        &["--method", "optimal", "--metric", "qtm", "--format", "json"],
+        "R U2\n\nF' D\n",
+    );
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines = stdout.lines().collect::<Vec<_>>();
+    assert_eq!(
+        lines,
+        vec![
+            r#"{"scramble":"R U2","solution":"U2 R'","length":3,"metric":"qtm"}"#,
+            r#"{"scramble":"F' D","solution":"D' F","length":2,"metric":"qtm"}"#,
+        ]
+    );
+}
+
+#[test]
+fn reports_failures() {
+    let output = run(&["--format", "json"], "R Q\n");
+    assert!(!output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap().trim(),
+        r#"{"scramble":"R Q","error":"invalid move Q"}"#
+    );
+
+    let output = run(&["--method", "optimal", "--max-len", "1"], "R U");
+    assert!(!output.status.success());
+
+    let output = run(&["--metric", "xtm"], "");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn groups_text_solutions() {
+    let output = run(&["--group", "3", "R U F' D2 B L'"], "");
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let solution = stdout.split(" (").next().unwrap();
+    let groups: Vec<&str> = solution.split("  ").collect();
+    assert!(groups.len() > 1);
+    assert!(groups.iter().all(|group| group.split(' ').count() <= 3));
+    assert!(solves("R U F' D2 B L'", &groups.join(" ")));
+}
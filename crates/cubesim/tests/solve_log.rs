@@ -0,0 +1,73 @@
+//
+// Smart Cube Solve Log Tests
+//
+
+use core::time::Duration;
+use cubesim::prelude::*;
+use cubesim::{parse_scramble, parse_timed_moves, read_cstimer_export, read_gan_export, read_solve_log, FaceletCube, Penalty};
+
+#[test]
+fn test_parse_timed_moves() {
+    let moves = parse_timed_moves("U'@1200 R2@1450  F@1800").unwrap();
+    let times: Vec<u64> = moves.iter().map(|timed| timed.at.as_millis() as u64).collect();
+    assert_eq!(times, vec![0, 250, 600]);
+    assert_eq!(moves[1].mv, Move::R(MoveVariant::Double));
+
+    assert!(parse_timed_moves("").unwrap().is_empty());
+    assert!(parse_timed_moves("R@12 Q@40").is_err());
+    assert!(parse_timed_moves("R@twelve").is_err());
+}
+
+#[test]
+fn test_read_cstimer_export() {
+    let export = r#"{
+        "session1": [
+            [[0, 1040], "R U", "", 1700000000, ["U'@300 R'@1340", "333"]],
+            [[0, 9120], "F2 D", "", 1700000100],
+            [[2000, 820], "R", "first \"fast\" one", 1700000200, ["R'@0", "333"]]
+        ],
+        "session2": "[[[-1, 5000], \"U\", \"\", 1700000300, [\"U'@40\", \"333\"]]]",
+        "properties": {"sessionData": "{}"}
+    }"#;
+    let solves = read_cstimer_export(export).unwrap();
+
+    // the solve timed without a smart cube is skipped
+    assert_eq!(solves.len(), 3);
+    assert_eq!(solves[0].scramble, parse_scramble("R U".to_string()));
+    assert_eq!(solves[0].solution[1].at, Duration::from_millis(1040));
+    let penalties: Vec<Option<Penalty>> = solves.iter().map(|solve| solve.penalty).collect();
+    assert_eq!(penalties, vec![None, Some(Penalty::PlusTwo), Some(Penalty::Dnf)]);
+
+    // the moves solve the scrambles
+    for solve in &solves {
+        let moves: Vec<Move> = solve.solution.iter().map(|timed| timed.mv).collect();
+        assert!(FaceletCube::new(3).apply_moves(&solve.scramble).apply_moves(&moves).is_solved());
+    }
+
+    assert!(read_cstimer_export(r#"{"session1": [[[0, 10], "R", "", 0, ["R'"]]]}"#).is_err());
+    assert!(read_cstimer_export("[1, 2").is_err());
+}
+
+#[test]
+fn test_read_gan_export() {
+    let export = "Date,Time,Scramble,Solution,Penalty\n\
+                  2024-01-02,1.04,R U,U'@0 R'@1040,\n\
+                  \"Jan 3, 2024\",0.82,R,R'@500,DNF\n";
+    let solves = read_gan_export(export).unwrap();
+    assert_eq!(solves.len(), 2);
+    assert_eq!(solves[1].scramble, parse_scramble("R".to_string()));
+    assert_eq!(solves[1].solution[0].at, Duration::ZERO);
+    assert_eq!(solves[1].penalty, Some(Penalty::Dnf));
+
+    assert!(read_gan_export("Time,Scramble\n1.00,R\n").is_err());
+}
+
+#[test]
+fn test_read_solve_log() {
+    let solves = read_solve_log(r#"{"session1": [[[0, 520], "R U", "", 0, ["U'@0 R'@520", "333"]]]}"#).unwrap();
+    let report = solves[0].report(Duration::from_secs(1));
+    assert_eq!(report.stats.duration, Duration::from_millis(520));
+    assert!(report.reconstruction().starts_with("U' R' // Cross"));
+
+    assert!(read_solve_log("Scramble,Solution\n").is_err());
+}
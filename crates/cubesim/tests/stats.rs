@@ -0,0 +1,106 @@
+//
+// Solve Statistics Tests
+//
+
+use core::time::Duration;
+use cubesim::prelude::*;
+use cubesim::{average_of, parse_scramble, penalty_at_stop, solve_stats, CfopPhase, FaceletCube, Penalty, SolveResult, TimedMove};
+
+fn results(millis: &[u64]) -> Vec<SolveResult> {
+    millis.iter().map(|&ms| SolveResult::new(Duration::from_millis(ms))).collect()
+}
+
+fn timed(moves: &str, step_millis: u64) -> Vec<TimedMove> {
+    parse_scramble(String::from(moves))
+        .into_iter()
+        .enumerate()
+        .map(|(i, mv)| TimedMove { mv, at: Duration::from_millis(i as u64 * step_millis) })
+        .collect()
+}
+
+#[test]
+fn test_phase_times() {
+    // undoing a T perm only leaves the PLL to solve
+    let t_perm = "R U R' U' R' F R2 U' R' U' R U R' F'";
+    let scrambled = FaceletCube::new(3).apply_moves(&parse_scramble(String::from(t_perm)));
+    let stats = solve_stats(&scrambled, &timed(t_perm, 100), Duration::from_secs(1));
+
+    let phases: Vec<(CfopPhase, usize)> = stats.phases.iter().map(|p| (p.phase, p.moves)).collect();
+    assert_eq!(phases, vec![(CfopPhase::Cross, 0), (CfopPhase::F2l, 0), (CfopPhase::Oll, 0),
+                            (CfopPhase::Pll, 14)]);
+    assert_eq!(stats.phases[3].duration, Duration::from_millis(1300));
+    assert!(stats.pauses.is_empty());
+}
+
+#[test]
+fn test_unfinished_solve() {
+    let scrambled = FaceletCube::new(3).apply_moves(&parse_scramble(String::from("F2 R")));
+    let stats = solve_stats(&scrambled, &timed("R'", 100), Duration::from_secs(1));
+
+    assert_eq!(stats.phases.len(), 0);
+    assert_eq!(stats.moves, 1);
+    assert_eq!(stats.tps, 0.0);
+}
+
+#[test]
+fn test_phases_on_any_face() {
+    let scrambled = FaceletCube::new(3).apply_moves(&parse_scramble(String::from("z2 R D R'")));
+    assert!(CfopPhase::Cross.is_done(&scrambled));
+    assert!(!CfopPhase::F2l.is_done(&scrambled));
+}
+
+#[test]
+fn test_no_phases_on_other_sizes() {
+    let scrambled = FaceletCube::new(4).apply_moves(&parse_scramble(String::from("R")));
+    let stats = solve_stats(&scrambled, &timed("R'", 100), Duration::from_secs(1));
+    assert!(stats.phases.is_empty());
+}
+
+#[test]
+fn test_average_of_twelve() {
+    // only the fastest and slowest are left out
+    let mut solves = results(&[9000, 10000, 10000, 10000, 10000, 10000, 10000, 10000, 10000, 10000, 10000, 60000]);
+    assert_eq!(average_of(&solves), Some(Duration::from_secs(10)));
+
+    solves[0].penalty = Some(Penalty::PlusTwo);
+    assert_eq!(average_of(&solves), Some(Duration::from_millis(10100)));
+}
+
+#[test]
+fn test_average_of_hundred() {
+    // five DNFs are left out with the slowest times, a sixth one counts
+    let mut solves = results(&[10000; 100]);
+    for solve in &mut solves[..5] {
+        solve.penalty = Some(Penalty::Dnf);
+    }
+    assert_eq!(average_of(&solves), Some(Duration::from_secs(10)));
+
+    solves[5].penalty = Some(Penalty::Dnf);
+    assert_eq!(average_of(&solves), None);
+}
+
+#[test]
+fn test_mean_of_three() {
+    assert_eq!(average_of(&results(&[1000, 2000, 6000])), Some(Duration::from_secs(3)));
+    assert_eq!(average_of(&[]), None);
+}
+
+#[test]
+fn test_solve_result_display() {
+    let mut solve = SolveResult::new(Duration::from_millis(12340));
+    assert_eq!(solve.to_string(), "12.34");
+    solve.penalty = Some(Penalty::PlusTwo);
+    assert_eq!(solve.to_string(), "14.34+");
+    solve.penalty = Some(Penalty::Dnf);
+    assert_eq!(solve.to_string(), "DNF");
+}
+
+#[test]
+fn test_penalty_at_stop() {
+    let cube = |moves: &str| FaceletCube::new(4).apply_moves(&parse_scramble(String::from(moves)));
+    assert_eq!(penalty_at_stop(&cube("")), None);
+    assert_eq!(penalty_at_stop(&cube("x y")), None);
+    assert_eq!(penalty_at_stop(&cube("Rw'")), Some(Penalty::PlusTwo));
+    assert_eq!(penalty_at_stop(&cube("2R")), Some(Penalty::Dnf));
+    assert_eq!(penalty_at_stop(&cube("R U")), Some(Penalty::Dnf));
+}
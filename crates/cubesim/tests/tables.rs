@@ -0,0 +1,64 @@
+//
+// Solver Table Tests
+//
+
+use cubesim::prelude::*;
+use cubesim::prelude::MoveVariant::*;
+use cubesim::{parse_scramble, read_tables, solve, write_tables, FaceletCube, PruningTable, Solver};
+
+#[test]
+fn test_pruning_table_round_trip() {
+    let moves = vec![Move::U(Double), Move::R(Double), Move::F(Double)];
+    let pruning_table = PruningTable::new(&[FaceletCube::new(3)], 4, &moves);
+    let mut bytes = Vec::new();
+    pruning_table.write(&mut bytes);
+
+    let mut rest = bytes.as_slice();
+    let read = PruningTable::read(&mut rest).unwrap();
+    assert!(rest.is_empty());
+    assert_eq!(read.len(), pruning_table.len());
+
+    let written = Solver::new(moves.clone(), pruning_table);
+    let read = Solver::new(moves.clone(), read);
+    let mut cube = FaceletCube::new(3);
+    for mv in moves.iter().cycle().take(6) {
+        cube = cube.apply_move(*mv);
+        assert_eq!(read.lower_bound(&cube), written.lower_bound(&cube));
+    }
+}
+
+#[test]
+fn test_pruning_table_written_alike() {
+    let moves = vec![Move::U(Standard), Move::R(Double)];
+    let write = |table: &PruningTable| {
+        let mut bytes = Vec::new();
+        table.write(&mut bytes);
+        bytes
+    };
+    let first = write(&PruningTable::new(&[FaceletCube::new(3)], 3, &moves));
+    let second = write(&PruningTable::new(&[FaceletCube::new(3)], 3, &moves));
+    assert_eq!(first, second);
+    assert!(PruningTable::read(&mut &first[..first.len() - 1]).is_err());
+}
+
+#[test]
+fn test_tables_round_trip() {
+    let bytes = write_tables();
+    assert_eq!(read_tables(&bytes), Ok(8));
+    assert_eq!(write_tables(), bytes);
+
+    let cube = FaceletCube::new(3).apply_moves(&parse_scramble(String::from("R U2 F' L D")));
+    assert!(cube.apply_moves(&solve(&cube).unwrap()).is_solved());
+}
+
+#[test]
+fn test_invalid_tables() {
+    let bytes = write_tables();
+    assert!(read_tables(b"").is_err());
+    assert!(read_tables(b"cubesim tables 0\n").is_err());
+    assert!(read_tables(&bytes[..bytes.len() - 1]).is_err());
+
+    let mut longer = bytes.clone();
+    longer.push(0);
+    assert!(read_tables(&longer).is_err());
+}
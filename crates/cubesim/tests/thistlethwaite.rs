@@ -6,7 +6,7 @@
 
 use cubesim::prelude::*;
 use cubesim::{GeoCube, FaceletCube};
-use cubesim::solve;
+use cubesim::{solve, solve_phases, solve_phases_observed, solve_phases_with, SolveOptions, ThistlethwaitePhase};
 use cubesim::parse_scramble;
 
 #[test]
@@ -35,3 +35,68 @@ fn superflip() {
     assert!(cube.apply_moves(&solve(&cube).unwrap()).is_solved());
 }
 
+
+#[test]
+fn phases() {
+    let cube = FaceletCube::new(3).apply_moves(&parse_scramble(String::from("U F R2 D2 B2 L R")));
+    let solution = solve_phases(&cube).unwrap();
+
+    let phases: Vec<ThistlethwaitePhase> = solution.phases.iter().map(|(phase, _)| *phase).collect();
+    assert_eq!(phases, ThistlethwaitePhase::ALL);
+    assert_eq!(solution.moves(), solve(&cube).unwrap());
+    assert_eq!(solution.len(), solution.moves().len());
+
+    // the last phase only turns faces by half turns
+    let (_, half_turns) = &solution.phases[3];
+    assert!(half_turns.iter().all(|mv| mv.get_variant() == MoveVariant::Double));
+
+    let last = solution.len() - 1;
+    let (phase, moves, index) = solution.phase_of(last).unwrap();
+    assert_eq!(phase, ThistlethwaitePhase::HalfTurns);
+    assert_eq!(index, moves.len() - 1);
+    assert!(solution.phase_of(solution.len()).is_none());
+}
+
+#[test]
+fn observed_phases() {
+    let cube = FaceletCube::new(3).apply_moves(&parse_scramble(String::from("L2 F' U R D2 B")));
+    let mut reports = Vec::new();
+    let solution = solve_phases_observed(&cube, &mut |phase, progress| {
+        reports.push((phase, progress.clone()))
+    }).unwrap();
+    assert_eq!(solution, solve_phases(&cube).unwrap());
+
+    for (phase, moves) in &solution.phases {
+        let phase_reports: Vec<_> = reports.iter().filter(|(p, _)| p == phase).map(|(_, r)| r).collect();
+        assert!(phase_reports.windows(2).all(|pair| pair[0].nodes <= pair[1].nodes));
+        let last = phase_reports.last().unwrap();
+        assert_eq!(last.depth as usize, moves.len());
+        assert_eq!((&last.best, last.best_bound), (moves, 0));
+    }
+}
+
+#[test]
+fn parallel_phases() {
+    let cube = FaceletCube::new(3).apply_moves(&parse_scramble(String::from("U F R2 D2 B2 L R")));
+    let solution = solve_phases(&cube).unwrap();
+
+    for threads in [0, 2, 8] {
+        let options = SolveOptions { threads, ..SolveOptions::default() };
+        assert_eq!(solve_phases_with(&cube, options), Some(solution.clone()));
+    }
+    assert!(SolveOptions::all_cores().threads >= 1);
+}
+
+#[test]
+fn bidirectional_phases() {
+    let options = SolveOptions { bidirectional: true, ..SolveOptions::default() };
+    for scramble in ["", "R", "U F R2 D2 B2 L R", "R U F' L2 D B R2 U' F2 D L'"] {
+        let cube = FaceletCube::new(3).apply_moves(&parse_scramble(String::from(scramble)));
+        let solution = solve_phases_with(&cube, options).unwrap();
+        assert!(cube.apply_moves(&solution.moves()).is_solved());
+
+        // the phases after the first may start from another state, the moves differing
+        let searched = solve_phases(&cube).unwrap();
+        assert_eq!(solution.phases[0].1.len(), searched.phases[0].1.len());
+    }
+}
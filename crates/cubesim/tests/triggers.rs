@@ -0,0 +1,39 @@
+//
+// Trigger Detection Tests
+//
+
+use cubesim::{annotate_triggers, find_triggers, parse_scramble, Trigger};
+
+#[test]
+fn test_repeated_triggers() {
+    let moves = parse_scramble(String::from("R U R' U' R U R' U' R U R' U'"));
+    let starts: Vec<usize> = find_triggers(&moves).iter().map(|t| t.start).collect();
+    assert_eq!(starts, vec![0, 4, 8]);
+}
+
+#[test]
+fn test_all_triggers_and_mirrors() {
+    for trigger in Trigger::ALL {
+        for left_handed in [false, true] {
+            let found = find_triggers(&trigger.moves(left_handed));
+            assert_eq!(found.len(), 1);
+            assert_eq!((found[0].trigger, found[0].left_handed), (trigger, left_handed));
+        }
+    }
+}
+
+#[test]
+fn test_no_overlapping_triggers() {
+    // the sexy move starting on the last move of the hedgeslammer isn't counted
+    let moves = parse_scramble(String::from("F R' F' R U R' U'"));
+    let found = find_triggers(&moves);
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].trigger, Trigger::Hedgeslammer);
+}
+
+#[test]
+fn test_annotate_triggers() {
+    let moves = parse_scramble(String::from("R U R' U' R' F R2 U' R' U' R U R' F'"));
+    assert_eq!(annotate_triggers(&moves), "(R U R' U') R' F R2 U' R' U' R U R' F'");
+    assert_eq!(annotate_triggers(&[]), "");
+}
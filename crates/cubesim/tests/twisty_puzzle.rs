@@ -0,0 +1,103 @@
+//
+// Generic Twisty Puzzle Tests
+//
+
+use cubesim::{
+    all_moves, bidirectional_search, ida_star, ida_star_observed, ida_star_parallel,
+    parse_megaminx_scramble, parse_scramble, random_moves, FaceletCube, Megaminx, PruningTable, Solver, TwistyPuzzle,
+};
+use cubesim::prelude::{Move::*, MoveVariant::*};
+
+fn solved<P: TwistyPuzzle>(puzzle: &P) -> bool {
+    puzzle.is_solved()
+}
+
+#[test]
+fn cubes_are_twisty_puzzles() {
+    let cube = <FaceletCube as cubesim::Cube>::new(3);
+    assert!(solved(&cube));
+    assert!(!solved(&TwistyPuzzle::apply_move(&cube, R(Standard))));
+    assert_eq!(FaceletCube::inverse_move(R(Standard)), R(Inverse));
+    assert!(FaceletCube::same_layer(R(Standard), R(Double)));
+}
+
+#[test]
+fn same_layer_compares_depths() {
+    assert!(FaceletCube::same_layer(Rw(2, Standard), Rw(2, Inverse)));
+    assert!(!FaceletCube::same_layer(Rw(2, Standard), Rw(3, Standard)));
+    assert!(!FaceletCube::same_layer(Ri(2, Standard), Ri(3, Double)));
+    assert!(!FaceletCube::same_layer(R(Standard), L(Standard)));
+}
+
+#[test]
+fn random_moves_never_repeat_layers() {
+    let scramble = random_moves(&Megaminx::new(), 100);
+
+    assert_eq!(scramble.len(), 100);
+    for pair in scramble.windows(2) {
+        assert!(!Megaminx::same_layer(pair[0], pair[1]));
+    }
+}
+
+#[test]
+fn generic_solver_solves_megaminx() {
+    let minx = Megaminx::new().apply_moves(&parse_megaminx_scramble("U R2 F'").unwrap());
+    let moves = Megaminx::new().moves();
+    let solver = Solver::new(moves.clone(), PruningTable::new(&[Megaminx::new()], 2, &moves));
+
+    let solution = ida_star(&minx, &solver, 3).unwrap();
+    assert!(solution.len() <= 3);
+    assert!(minx.apply_moves(&solution).is_solved());
+}
+
+#[test]
+fn transposition_table_skips_states_searched() {
+    let cube = <FaceletCube as cubesim::Cube>::new(3);
+    let scrambled = cube.apply_moves(&parse_scramble(String::from("R U D F' L2 B")));
+    let moves = all_moves(3);
+    let search = |bytes| {
+        let table = PruningTable::new(std::slice::from_ref(&cube), 3, &moves);
+        let solver = Solver::new(moves.clone(), table).with_transposition_table(bytes);
+        let mut nodes = 0;
+        let solution = ida_star_observed(&scrambled, &solver, 6, &mut |progress| nodes = progress.nodes);
+        (solution.unwrap(), nodes)
+    };
+
+    let (solution, nodes) = search(1 << 20);
+    let (without, nodes_without) = search(0);
+    assert_eq!(solution, without);
+    assert_eq!(solution.len(), 6);
+    assert!(scrambled.apply_moves(&solution).is_solved());
+    assert!(nodes < nodes_without);
+
+    // a table too small to hold much still finds the same solution
+    assert_eq!(search(64).0, solution);
+}
+
+#[test]
+fn parallel_search_finds_the_same_solution() {
+    let minx = Megaminx::new().apply_moves(&parse_megaminx_scramble("U R2 F' BL").unwrap());
+    let moves = Megaminx::new().moves();
+    let solver = Solver::new(moves.clone(), PruningTable::new(&[Megaminx::new()], 2, &moves));
+
+    let solution = ida_star(&minx, &solver, 4);
+    assert!(solution.is_some());
+    for threads in [1, 3, 16] {
+        assert_eq!(ida_star_parallel(&minx, &solver, 4, threads), solution);
+    }
+    assert_eq!(ida_star_parallel(&Megaminx::new(), &solver, 4, 3), Some(vec![]));
+    assert_eq!(ida_star_parallel(&minx, &solver, 2, 3), None);
+}
+
+#[test]
+fn bidirectional_search_finds_as_short_a_solution() {
+    let minx = Megaminx::new().apply_moves(&parse_megaminx_scramble("U R2 F' BL DR2").unwrap());
+    let moves = Megaminx::new().moves();
+    let solver = Solver::new(moves.clone(), PruningTable::new(&[Megaminx::new()], 2, &moves));
+
+    let solution = bidirectional_search(&minx, &solver, 5).unwrap();
+    assert_eq!(Some(solution.len()), ida_star(&minx, &solver, 5).map(|solution| solution.len()));
+    assert!(minx.apply_moves(&solution).is_solved());
+    assert_eq!(bidirectional_search(&minx, &solver, 4), None);
+    assert_eq!(bidirectional_search(&Megaminx::new(), &solver, 0), Some(vec![]));
+}
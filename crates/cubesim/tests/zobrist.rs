@@ -0,0 +1,78 @@
+//
+// Zobrist Hash Tests
+//
+
+use std::collections::{HashMap, HashSet};
+
+use cubesim::prelude::*;
+use cubesim::prelude::{Move::*, MoveVariant::*};
+use cubesim::{random_scramble, FaceletCube, GeoCube};
+
+#[test]
+fn test_equal_states_hash_alike() {
+    for size in 2..=5 {
+        let scramble = random_scramble(size, true);
+        let facelet_cube = FaceletCube::new(size).apply_moves(&scramble);
+        let geo_cube = GeoCube::new(size).apply_moves(&scramble);
+        let from_state = FaceletCube::from(facelet_cube.state());
+
+        assert_eq!(facelet_cube.zobrist(), geo_cube.zobrist());
+        assert_eq!(facelet_cube.zobrist(), from_state.zobrist());
+    }
+}
+
+#[test]
+fn test_different_states_hash_apart() {
+    let mut hashes: HashMap<u64, Vec<Face>> = HashMap::new();
+    for _ in 0..2000 {
+        let cube = FaceletCube::new(3).apply_moves(&random_scramble(3, false));
+        let state = hashes.entry(cube.zobrist()).or_insert_with(|| cube.state());
+        assert_eq!(*state, cube.state());
+    }
+    assert!(hashes.len() > 1990);
+
+    let masked = FaceletCube::new(3).mask(&|i, face| if i == 0 { Face::X } else { face });
+    assert_ne!(masked.zobrist(), FaceletCube::new(3).zobrist());
+    assert_ne!(FaceletCube::new(2).zobrist(), FaceletCube::new(3).zobrist());
+}
+
+#[test]
+fn test_symmetric_hash_ignores_rotations_and_colors() {
+    for _ in 0..20 {
+        let cube = FaceletCube::new(3).apply_moves(&random_scramble(3, false));
+        let hash = cube.symmetric_zobrist();
+
+        for rotation in [X(Standard), Y(Double), Z(Inverse)] {
+            assert_eq!(cube.apply_move(rotation).symmetric_zobrist(), hash);
+        }
+
+        // U and D swapped, as well as L and R
+        let recolored: Vec<Face> = cube
+            .state()
+            .iter()
+            .map(|face| match face {
+                Face::U => Face::D,
+                Face::D => Face::U,
+                Face::L => Face::R,
+                Face::R => Face::L,
+                face => *face,
+            })
+            .collect();
+        assert_eq!(FaceletCube::from(recolored).symmetric_zobrist(), hash);
+    }
+}
+
+#[test]
+fn test_symmetric_hash_groups_states() {
+    // the states a clockwise quarter turn away are all alike, as are the counter-clockwise and
+    // half turn ones, mirroring the cube being no symmetry kept
+    let hashes = |variant| -> HashSet<u64> {
+        [U, L, F, R, B, D]
+            .iter()
+            .map(|mv| FaceletCube::new(3).apply_move(mv(variant)).symmetric_zobrist())
+            .collect()
+    };
+    let classes: Vec<HashSet<u64>> = [Standard, Inverse, Double].into_iter().map(hashes).collect();
+    assert!(classes.iter().all(|hashes| hashes.len() == 1));
+    assert_eq!(classes.iter().flatten().collect::<HashSet<_>>().len(), 3);
+}
@@ -0,0 +1,225 @@
+use crate::{
+    history::{record_solves, SolveHistory},
+    i18n::{Localized, Translations},
+    solved::{CubeSolvedEvent, SolveMode},
+    storage,
+};
+use bevy::prelude::*;
+use cubesim::prelude::*;
+use std::{collections::HashSet, time::Duration};
+
+// The names of the unlocked achievements, one per line
+pub(crate) const ACHIEVEMENTS_PATH: &str = "achievements.txt";
+const TOAST_SECONDS: f32 = 4.0;
+
+pub struct AchievementsPlugin;
+
+impl Plugin for AchievementsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Achievements::load())
+            .add_systems(
+                Update,
+                (check_achievements.after(record_solves), show_toasts),
+            )
+            .add_systems(Last, save_achievements);
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+enum Achievement {
+    FirstSolve,
+    SubMinute,
+    NoRotations,
+    FourByFour,
+    SevenBySeven,
+    HundredSolves,
+}
+
+impl Achievement {
+    const ALL: [Achievement; 6] = [
+        Achievement::FirstSolve,
+        Achievement::SubMinute,
+        Achievement::NoRotations,
+        Achievement::FourByFour,
+        Achievement::SevenBySeven,
+        Achievement::HundredSolves,
+    ];
+
+    // Kept in the achievements file, so never renamed
+    fn id(self) -> &'static str {
+        match self {
+            Achievement::FirstSolve => "first-solve",
+            Achievement::SubMinute => "sub-minute",
+            Achievement::NoRotations => "no-rotations",
+            Achievement::FourByFour => "four-by-four",
+            Achievement::SevenBySeven => "seven-by-seven",
+            Achievement::HundredSolves => "hundred-solves",
+        }
+    }
+
+    // The id of its title, in the translations
+    fn title_id(self) -> String {
+        format!("achievement-{}", self.id())
+    }
+
+    // Whether a solve earns the achievement, given the number of solves made so far
+    fn is_earned(self, solved: &CubeSolvedEvent, solves: usize) -> bool {
+        // help from the solver, or a timer stopped before solving the cube, only counts
+        // towards the number of solves
+        let unassisted = matches!(solved.mode, SolveMode::Standard | SolveMode::Race)
+            && solved.report.penalty.is_none();
+
+        match self {
+            Achievement::FirstSolve => unassisted,
+            Achievement::SubMinute => {
+                unassisted && solved.report.stats.duration < Duration::from_secs(60)
+            }
+            Achievement::NoRotations => {
+                unassisted
+                    && !solved
+                        .solution
+                        .iter()
+                        .any(|mv| matches!(mv, Move::X(_) | Move::Y(_) | Move::Z(_)))
+            }
+            Achievement::FourByFour => unassisted && solved.cube_size == 4,
+            Achievement::SevenBySeven => unassisted && solved.cube_size == 7,
+            Achievement::HundredSolves => solves >= 100,
+        }
+    }
+}
+
+/// The achievements unlocked, kept between runs of the app.
+#[derive(Resource, Default, Debug)]
+struct Achievements(HashSet<Achievement>);
+
+impl Achievements {
+    fn load() -> Self {
+        let text = match storage::read_to_string(ACHIEVEMENTS_PATH) {
+            Ok(text) => text,
+            Err(err) => {
+                debug!("no achievements loaded from {ACHIEVEMENTS_PATH}: {err}");
+                return Self::default();
+            }
+        };
+
+        let unlocked = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .filter_map(|line| {
+                let achievement = Achievement::ALL.into_iter().find(|a| a.id() == line);
+                if achievement.is_none() {
+                    warn!("skipping unknown achievement '{line}' in {ACHIEVEMENTS_PATH}");
+                }
+                achievement
+            })
+            .collect();
+        Self(unlocked)
+    }
+
+    fn to_text(&self) -> String {
+        // in a stable order, to keep the file diffable
+        Achievement::ALL
+            .into_iter()
+            .filter(|achievement| self.0.contains(achievement))
+            .map(|achievement| format!("{}\n", achievement.id()))
+            .collect()
+    }
+}
+
+fn save_achievements(achievements: Res<Achievements>) {
+    // the achievements were just loaded from the file
+    if !achievements.is_changed() || achievements.is_added() {
+        return;
+    }
+
+    if let Err(err) = storage::write(ACHIEVEMENTS_PATH, &achievements.to_text()) {
+        warn!("failed to save achievements to {ACHIEVEMENTS_PATH}: {err}");
+    }
+}
+
+#[derive(Component)]
+struct Toast(Timer);
+
+fn check_achievements(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut solved_events: EventReader<CubeSolvedEvent>,
+    history: Res<SolveHistory>,
+    mut achievements: ResMut<Achievements>,
+    translations: Translations,
+    q_toasts: Query<(), With<Toast>>,
+) {
+    let mut toasts = q_toasts.iter().count();
+
+    for solved in solved_events.read() {
+        for achievement in Achievement::ALL {
+            if achievements.0.contains(&achievement)
+                || !achievement.is_earned(solved, history.0.len())
+            {
+                continue;
+            }
+
+            info!("achievement unlocked: {}", achievement.id());
+            achievements.0.insert(achievement);
+            let title = translations.get(&achievement.title_id());
+            let unlocked = Localized::new("achievement-unlocked").with("title", title);
+            spawn_toast(&mut commands, &asset_server, unlocked, toasts);
+            toasts += 1;
+        }
+    }
+}
+
+// Shows an unlocked achievement at the top of the window, below the toasts already shown
+fn spawn_toast(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    unlocked: Localized,
+    shown: usize,
+) {
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(60.0 + shown as f32 * 55.0),
+                width: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            z_index: ZIndex::Global(10),
+            ..default()
+        })
+        .insert(Toast(Timer::from_seconds(TOAST_SECONDS, TimerMode::Once)))
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        padding: UiRect::all(Val::Px(10.0)),
+                        ..default()
+                    },
+                    background_color: Color::rgba(0.1, 0.1, 0.1, 0.9).into(),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section(
+                            String::new(),
+                            TextStyle {
+                                font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                                font_size: 30.0,
+                                color: Color::GOLD,
+                            },
+                        ),
+                        unlocked,
+                    ));
+                });
+        });
+}
+
+fn show_toasts(mut commands: Commands, time: Res<Time>, mut q_toasts: Query<(Entity, &mut Toast)>) {
+    for (entity, mut toast) in &mut q_toasts {
+        if toast.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
@@ -0,0 +1,253 @@
+use crate::{
+    console::ConsoleInput,
+    i18n::{Localized, Translations},
+    scramble::Scrambler,
+    simulator::{CurrentCube, MoveQueue, Played, PlayedMut},
+    theme::{Theme, ThemeColor},
+    GameState, Puzzle,
+};
+use bevy::{input::InputSystem, prelude::*};
+use cubesim::prelude::*;
+
+const NEXT_KEY: KeyCode = KeyCode::Space;
+// The moves already made fade out, the next one stands out
+const MADE_COLOR: Color = Color::rgba(0.6, 0.6, 0.6, 0.6);
+const NEXT_COLOR: Color = Color::rgb(1.0, 0.8, 0.0);
+
+pub struct AssistantPlugin;
+
+impl Plugin for AssistantPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(GameState::Playing),
+            assistant_ui.run_if(resource_exists::<ScrambleAssistant>()),
+        )
+        .add_systems(
+            PreUpdate,
+            next_move_input
+                .after(InputSystem)
+                .after(ConsoleInput)
+                .run_if(in_state(GameState::Playing))
+                .run_if(resource_exists::<ScrambleAssistant>()),
+        )
+        .add_systems(
+            Update,
+            (new_scramble, show_assistant)
+                .chain()
+                .run_if(in_state(GameState::Playing))
+                .run_if(resource_exists::<ScrambleAssistant>()),
+        )
+        .add_systems(OnExit(GameState::Playing), clean_up)
+        .add_systems(OnEnter(GameState::Menu), end_assistant);
+    }
+}
+
+/// Walks through the scramble of a real cube, started from the main menu: the move to make
+/// next is shown on a large card, and the cube on screen makes it once `Space` is pressed, to
+/// compare the real cube with it before going on.
+#[derive(Resource, Debug, Default)]
+struct ScrambleAssistant {
+    moves: Vec<Move>,
+    // the number of moves made on both cubes
+    made: usize,
+}
+
+impl ScrambleAssistant {
+    fn next_move(&self) -> Option<Move> {
+        self.moves.get(self.made).copied()
+    }
+}
+
+#[derive(Component)]
+struct AssistantUiRoot;
+
+// The next move in large letters, then the progress through the scramble and the key to use
+#[derive(Component)]
+struct MoveCard;
+
+// The whole scramble in three sections: the moves made, the next move and the moves after
+#[derive(Component)]
+struct ScrambleLine;
+
+fn assistant_ui(mut commands: Commands, asset_server: Res<AssetServer>, theme: Res<Theme>) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    let style = TextStyle {
+        font: font.clone(),
+        font_size: 25.0,
+        color: theme.text(),
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(10.0),
+                    width: Val::Percent(100.0),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                ..default()
+            },
+            AssistantUiRoot,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    NodeBundle {
+                        style: Style {
+                            flex_direction: FlexDirection::Column,
+                            align_items: AlignItems::Center,
+                            max_width: Val::Px(600.0),
+                            padding: UiRect::all(Val::Px(10.0)),
+                            ..default()
+                        },
+                        ..default()
+                    },
+                    ThemeColor::Panel,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_sections([
+                            TextSection::new(
+                                String::new(),
+                                TextStyle {
+                                    font,
+                                    font_size: 160.0,
+                                    color: NEXT_COLOR,
+                                },
+                            ),
+                            TextSection::new(String::new(), style.clone()),
+                        ])
+                        .with_text_alignment(TextAlignment::Center),
+                        MoveCard,
+                    ));
+                    parent.spawn((
+                        TextBundle::from_sections([
+                            TextSection::new(
+                                String::new(),
+                                TextStyle {
+                                    color: MADE_COLOR,
+                                    ..style.clone()
+                                },
+                            ),
+                            TextSection::new(
+                                String::new(),
+                                TextStyle {
+                                    color: NEXT_COLOR,
+                                    ..style.clone()
+                                },
+                            ),
+                            TextSection::new(String::new(), style),
+                        ]),
+                        ScrambleLine,
+                    ));
+                });
+        });
+}
+
+// Takes the key before the queue's stop key would, making the next move or, once the
+// scramble is over, starting over with another one
+fn next_move_input(
+    mut keyboard_input: ResMut<Input<KeyCode>>,
+    mut assistant: ResMut<ScrambleAssistant>,
+    mut move_queue: PlayedMut<MoveQueue>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    if !keyboard_input.clear_just_pressed(NEXT_KEY) {
+        return;
+    }
+
+    match assistant.next_move() {
+        Some(mv) => {
+            move_queue.push_back(mv);
+            assistant.made += 1;
+        }
+        None => game_state.set(GameState::Restarting),
+    }
+}
+
+// Picks the scramble once the game starts with a solved cube
+fn new_scramble(
+    current_cube: Played<CurrentCube>,
+    mut scrambler: Scrambler,
+    mut assistant: ResMut<ScrambleAssistant>,
+) {
+    if !assistant.moves.is_empty() {
+        return;
+    }
+
+    assistant.moves = scrambler.scramble(current_cube.cube_size() as CubeSize);
+}
+
+fn spaced(moves: &[Move]) -> String {
+    moves.iter().map(|mv| format!("{mv} ")).collect()
+}
+
+fn show_assistant(
+    assistant: Res<ScrambleAssistant>,
+    translations: Translations,
+    mut q_card: Query<&mut Text, (With<MoveCard>, Without<ScrambleLine>)>,
+    mut q_line: Query<&mut Text, (With<ScrambleLine>, Without<MoveCard>)>,
+) {
+    if !assistant.is_changed() {
+        return;
+    }
+
+    let total = assistant.moves.len();
+    let (card, hint) = match assistant.next_move() {
+        Some(mv) => (
+            mv.to_string(),
+            Localized::new("assistant-next")
+                .with("move", assistant.made + 1)
+                .with("moves", total),
+        ),
+        None => (
+            translations.get("assistant-done"),
+            Localized::new("assistant-scrambled"),
+        ),
+    };
+    let hint = translations.text(hint.with("key", format!("{NEXT_KEY:?}")));
+    let hint = format!("\n{hint}");
+    let next = (assistant.made + 1).min(total);
+    let sections = [
+        spaced(&assistant.moves[..assistant.made]),
+        spaced(&assistant.moves[assistant.made..next]),
+        spaced(&assistant.moves[next..]),
+    ];
+
+    for mut text in &mut q_card {
+        text.sections[0].value = card.clone();
+        text.sections[1].value = hint.clone();
+    }
+    for mut text in &mut q_line {
+        for (section, value) in text.sections.iter_mut().zip(&sections) {
+            section.value = value.clone();
+        }
+    }
+}
+
+// Starting over, the next scramble is picked for the solved cube
+fn clean_up(
+    mut commands: Commands,
+    assistant: Option<ResMut<ScrambleAssistant>>,
+    q_ui: Query<Entity, With<AssistantUiRoot>>,
+) {
+    if let Some(mut assistant) = assistant {
+        *assistant = ScrambleAssistant::default();
+    }
+    for entity in &q_ui {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn end_assistant(mut commands: Commands) {
+    commands.remove_resource::<ScrambleAssistant>();
+}
+
+/// Starts walking through a scramble for a real cube from the main menu, with a cube of the
+/// size in the settings.
+pub(crate) fn start_assistant(commands: &mut Commands, puzzle: &mut Puzzle) {
+    commands.insert_resource(ScrambleAssistant::default());
+    *puzzle = Puzzle::Cube;
+}
@@ -0,0 +1,289 @@
+use crate::{
+    i18n::{Localized, Translations},
+    settings::Settings,
+    simulator::{CurrentCube, Played},
+    theme::{Theme, ThemeColor},
+    GameState, Puzzle,
+};
+use bevy::prelude::*;
+use cubesim::prelude::*;
+#[cfg(feature = "speech")]
+use std::{sync::mpsc, thread};
+use std::{sync::mpsc::Sender, time::Duration};
+
+const CALLOUT_KEY: KeyCode = KeyCode::C;
+const SLOWER_KEY: KeyCode = KeyCode::Minus;
+const FASTER_KEY: KeyCode = KeyCode::Equals;
+// The seconds between two moves, a step of the keys above
+const PACE_RANGE: (f32, f32) = (0.5, 5.0);
+const PACE_STEP: f32 = 0.5;
+
+pub struct CalloutPlugin;
+
+impl Plugin for CalloutPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Callout>()
+            .add_systems(
+                OnEnter(GameState::Playing),
+                callout_ui.run_if(resource_equals(Puzzle::Cube)),
+            )
+            .add_systems(
+                Update,
+                (callout_input, start_callout, read_moves, show_callout)
+                    .chain()
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(resource_equals(Puzzle::Cube)),
+            )
+            .add_systems(OnExit(GameState::Playing), clean_up);
+    }
+}
+
+/// Reads each scramble aloud once it's made on screen, one move at a time at the pace of the
+/// settings, to scramble a real cube without looking at the screen. Toggled with `C`, the pace
+/// changed with `-` and `=`.
+///
+/// The moves are spoken by the system's text-to-speech with the `speech` feature, and only
+/// shown otherwise.
+#[derive(Resource, Default)]
+struct Callout {
+    // the scramble being read and the number of its moves read so far
+    moves: Vec<Move>,
+    read: usize,
+    timer: Timer,
+    // the scramble read last, so each one is read once
+    last: Vec<Move>,
+    voice: Option<Sender<String>>,
+    voice_started: bool,
+}
+
+impl Callout {
+    // until a pace after the last move, which stays shown meanwhile
+    fn is_reading(&self) -> bool {
+        !self.moves.is_empty()
+    }
+
+    fn stop(&mut self) {
+        self.moves.clear();
+        self.read = 0;
+    }
+
+    fn say(&mut self, text: String) {
+        if !self.voice_started {
+            self.voice = start_voice();
+            self.voice_started = true;
+        }
+        if let Some(voice) = &self.voice {
+            // the voice's thread only ends when it has no voice, as already warned
+            let _ = voice.send(text);
+        }
+    }
+}
+
+// Speaks the texts sent on its own thread, the speech of each one taking a while
+#[cfg(feature = "speech")]
+fn start_voice() -> Option<Sender<String>> {
+    let (sender, receiver) = mpsc::channel::<String>();
+    thread::spawn(move || {
+        let mut tts = match tts::Tts::default() {
+            Ok(tts) => tts,
+            Err(err) => {
+                warn!("no voice to read the scrambles aloud: {err}");
+                return;
+            }
+        };
+        for text in receiver {
+            if let Err(err) = tts.speak(&text, false) {
+                warn!("'{text}' not read aloud: {err}");
+            }
+        }
+    });
+    Some(sender)
+}
+
+#[cfg(not(feature = "speech"))]
+fn start_voice() -> Option<Sender<String>> {
+    warn!("scrambles not read aloud: built without the speech feature");
+    None
+}
+
+// A move the way it's said, such as "2 R wide two" for `2Rw2`
+fn spoken(mv: &Move) -> String {
+    let notation = mv.to_string();
+    let (turn, amount) = if let Some(turn) = notation.strip_suffix('\'') {
+        (turn, " prime")
+    } else if let Some(turn) = notation.strip_suffix('2') {
+        (turn, " two")
+    } else {
+        (notation.as_str(), "")
+    };
+    let (turn, wide) = match turn.strip_suffix('w') {
+        Some(turn) => (turn, " wide"),
+        None => (turn, ""),
+    };
+    let (depth, face) = turn.split_at(turn.len() - 1);
+    if depth.is_empty() {
+        format!("{face}{wide}{amount}")
+    } else {
+        format!("{depth} {face}{wide}{amount}")
+    }
+}
+
+#[derive(Component)]
+struct CalloutUiRoot;
+
+#[derive(Component)]
+struct CalloutText;
+
+fn callout_ui(mut commands: Commands, asset_server: Res<AssetServer>, theme: Res<Theme>) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    bottom: Val::Px(60.0),
+                    left: Val::Px(220.0),
+                    padding: UiRect::all(Val::Px(10.0)),
+                    ..default()
+                },
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+            ThemeColor::Panel,
+            CalloutUiRoot,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    String::new(),
+                    TextStyle {
+                        font,
+                        font_size: 25.0,
+                        color: theme.text(),
+                    },
+                ),
+                CalloutText,
+            ));
+        });
+}
+
+fn callout_input(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut settings: ResMut<Settings>,
+    mut callout: ResMut<Callout>,
+) {
+    if keyboard_input.just_pressed(CALLOUT_KEY) {
+        settings.callout = !settings.callout;
+        // turning it back on reads the scramble again
+        callout.stop();
+        callout.last.clear();
+    }
+
+    let step = if keyboard_input.just_pressed(SLOWER_KEY) {
+        PACE_STEP
+    } else if keyboard_input.just_pressed(FASTER_KEY) {
+        -PACE_STEP
+    } else {
+        return;
+    };
+    settings.callout_pace = (settings.callout_pace + step).clamp(PACE_RANGE.0, PACE_RANGE.1);
+}
+
+// Starts reading a scramble once all its moves are made
+fn start_callout(
+    settings: Res<Settings>,
+    current_cube: Played<CurrentCube>,
+    mut callout: ResMut<Callout>,
+) {
+    if !settings.callout {
+        return;
+    }
+    let scrambled = current_cube.scrambled_at() == Some(current_cube.moves().len());
+    let Some(scramble) = current_cube.scramble().filter(|_| scrambled) else {
+        return;
+    };
+    if scramble.is_empty() || callout.last == scramble {
+        return;
+    }
+
+    callout.moves = scramble.to_vec();
+    callout.last = scramble.to_vec();
+    callout.read = 0;
+}
+
+fn read_moves(time: Res<Time>, settings: Res<Settings>, mut callout: ResMut<Callout>) {
+    if !callout.is_reading() {
+        return;
+    }
+
+    let pace = Duration::from_secs_f32(settings.callout_pace);
+    if callout.read > 0 {
+        if callout.timer.duration() != pace {
+            callout.timer.set_duration(pace);
+        }
+        if !callout.timer.tick(time.delta()).just_finished() {
+            return;
+        }
+        if callout.read == callout.moves.len() {
+            callout.stop();
+            return;
+        }
+    } else {
+        callout.timer = Timer::new(pace, TimerMode::Repeating);
+    }
+
+    let text = spoken(&callout.moves[callout.read]);
+    callout.say(text);
+    callout.read += 1;
+}
+
+fn show_callout(
+    settings: Res<Settings>,
+    callout: Res<Callout>,
+    translations: Translations,
+    mut q_root: Query<&mut Visibility, With<CalloutUiRoot>>,
+    mut q_text: Query<&mut Text, With<CalloutText>>,
+) {
+    if !callout.is_changed() && !settings.is_changed() {
+        return;
+    }
+
+    for mut visibility in &mut q_root {
+        visibility.set_if_neq(if callout.is_reading() {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        });
+    }
+    if let Some(mv) = callout
+        .read
+        .checked_sub(1)
+        .and_then(|i| callout.moves.get(i))
+    {
+        let pace = Localized::new("callout-pace")
+            .with("seconds", format!("{:.1}", settings.callout_pace))
+            .with("slower", format!("{SLOWER_KEY:?}"))
+            .with("faster", format!("{FASTER_KEY:?}"))
+            .with("stop", format!("{CALLOUT_KEY:?}"));
+        let message = format!(
+            "{mv}   {}/{}\n{}",
+            callout.read,
+            callout.moves.len(),
+            translations.text(pace)
+        );
+        for mut text in &mut q_text {
+            text.sections[0].value = message.clone();
+        }
+    }
+}
+
+fn clean_up(
+    mut commands: Commands,
+    mut callout: ResMut<Callout>,
+    q_ui: Query<Entity, With<CalloutUiRoot>>,
+) {
+    callout.stop();
+    for entity in &q_ui {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,213 @@
+use crate::{
+    i18n::Localized,
+    session::SolveSession,
+    simulator::{CurrentCube, MoveQueue, PlayButtonActions, Played, PlayedMut},
+    spectate::Spectator,
+    theme::{Theme, ThemeColor},
+    warmup::SolverTables,
+    GameState, Puzzle,
+};
+use bevy::{prelude::*, utils::Instant};
+use cubesim::{prelude::*, FaceletCube, MethodSolution, SolveMethod};
+use std::time::Duration;
+
+// How long each method may search, the app freezing meanwhile
+const SOLVER_BUDGET: Duration = Duration::from_secs(5);
+
+pub struct ComparePlugin;
+
+impl Plugin for ComparePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (compare_solvers, comparison_buttons)
+                .chain()
+                .run_if(in_state(GameState::Playing))
+                .run_if(resource_equals(Puzzle::Cube))
+                .run_if(|tables: Res<SolverTables>| tables.is_ready())
+                .run_if(not(resource_exists::<Spectator>())),
+        )
+        .add_systems(OnExit(GameState::Playing), clean_up);
+    }
+}
+
+/// The solutions each method found for the cube, as it was once the moves made so far.
+#[derive(Resource)]
+struct Comparison {
+    moves: usize,
+    solutions: Vec<(SolveMethod, Option<MethodSolution>)>,
+}
+
+#[derive(Component)]
+struct ComparisonPanel;
+
+#[derive(Component, Clone, Copy)]
+enum ComparisonButton {
+    Play(usize),
+    Close,
+}
+
+// Solves the cube with every method on pressing the button, listing their solutions side by
+// side
+fn compare_solvers(
+    mut commands: Commands,
+    interaction_query: Query<(&Interaction, &PlayButtonActions), Changed<Interaction>>,
+    current_cube: Played<CurrentCube>,
+    move_queue: Played<MoveQueue>,
+    asset_server: Res<AssetServer>,
+    theme: Res<Theme>,
+    q_panel: Query<Entity, With<ComparisonPanel>>,
+) {
+    let pressed = interaction_query.iter().any(|(interaction, button)| {
+        *interaction == Interaction::Pressed && *button == PlayButtonActions::CompareSolvers
+    });
+    if !pressed {
+        return;
+    }
+    for entity in &q_panel {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    let mut message = Localized::new("compare-title");
+    let mut solutions = Vec::new();
+    let size = current_cube.cube_size();
+    if size != 3 {
+        message = Localized::new("compare-cube-size").with("size", size);
+    } else if !move_queue.is_empty() {
+        message = Localized::new("compare-moves-queued");
+    } else {
+        let cube = FaceletCube::new(3).apply_moves(current_cube.moves());
+        for method in SolveMethod::ALL {
+            let deadline = Instant::now() + SOLVER_BUDGET;
+            let solution = method.solve(&cube, &|| Instant::now() > deadline);
+            info!(
+                "{method}: {}",
+                solution
+                    .as_ref()
+                    .map_or("no solution".to_string(), |solution| {
+                        format!("{} moves", solution.len())
+                    })
+            );
+            solutions.push((method, solution));
+        }
+    }
+
+    let text_style = TextStyle {
+        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+        font_size: 25.0,
+        color: theme.text(),
+    };
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(10.0),
+                    left: Val::Px(220.0),
+                    max_width: Val::Px(700.0),
+                    padding: UiRect::all(Val::Px(10.0)),
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+                ..default()
+            },
+            ThemeColor::Panel,
+            ComparisonPanel,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(String::new(), text_style.clone()),
+                message,
+            ));
+            for (i, (method, solution)) in solutions.iter().enumerate() {
+                let text = match solution {
+                    Some(solution) => {
+                        let steps: Vec<String> = solution
+                            .steps
+                            .iter()
+                            .map(|(step, moves)| format!("{step} {}", moves.len()))
+                            .collect();
+                        Localized::new("compare-solution")
+                            .with("moves", solution.len())
+                            .with("steps", steps.join(", "))
+                    }
+                    None => Localized::new("compare-no-solution")
+                        .with("seconds", SOLVER_BUDGET.as_secs()),
+                };
+                let mut button = parent.spawn((
+                    TextBundle::from_section(String::new(), text_style.clone()).with_style(Style {
+                        margin: UiRect::vertical(Val::Px(5.0)),
+                        ..default()
+                    }),
+                    text.with("method", method.to_string()),
+                    Interaction::None,
+                ));
+                if solution.is_some() {
+                    button.insert(ComparisonButton::Play(i));
+                }
+            }
+            parent.spawn((
+                TextBundle::from_section(String::new(), text_style),
+                Localized::new("menu-close"),
+                ComparisonButton::Close,
+                Interaction::None,
+            ));
+        });
+    commands.insert_resource(Comparison {
+        moves: current_cube.moves().len(),
+        solutions,
+    });
+}
+
+// Plays the solution picked, unless the cube turned since
+fn comparison_buttons(
+    mut commands: Commands,
+    interaction_query: Query<(&Interaction, &ComparisonButton), Changed<Interaction>>,
+    comparison: Option<Res<Comparison>>,
+    current_cube: Played<CurrentCube>,
+    mut move_queue: PlayedMut<MoveQueue>,
+    mut session: PlayedMut<SolveSession>,
+    q_panel: Query<Entity, With<ComparisonPanel>>,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        if let ComparisonButton::Play(i) = *button {
+            let solution = comparison
+                .as_ref()
+                .filter(|comparison| {
+                    comparison.moves == current_cube.moves().len() && move_queue.is_empty()
+                })
+                .and_then(|comparison| comparison.solutions[i].1.as_ref());
+            match solution {
+                Some(solution) => {
+                    session.mark_assisted();
+                    info!(
+                        "{} solution {}",
+                        solution.method,
+                        solution
+                            .moves()
+                            .iter()
+                            .map(|m| m.to_string())
+                            .collect::<Vec<_>>()
+                            .join(" ")
+                    );
+                    move_queue.extend(solution.moves());
+                }
+                None => warn!("the cube turned since the solvers were compared"),
+            }
+        }
+        for entity in &q_panel {
+            commands.entity(entity).despawn_recursive();
+        }
+        commands.remove_resource::<Comparison>();
+    }
+}
+
+fn clean_up(mut commands: Commands, q_panel: Query<Entity, With<ComparisonPanel>>) {
+    for entity in &q_panel {
+        commands.entity(entity).despawn_recursive();
+    }
+    commands.remove_resource::<Comparison>();
+}
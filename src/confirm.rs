@@ -0,0 +1,202 @@
+use crate::{
+    i18n::Localized,
+    settings::Settings,
+    simulator::{CurrentCube, PlayButtonActions},
+    theme::{Theme, ThemeColor},
+    GameState,
+};
+use bevy::{ecs::system::SystemParam, prelude::*, ui::FocusPolicy};
+
+pub struct ConfirmPlugin;
+
+impl Plugin for ConfirmPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Asked>()
+            .add_event::<Confirmed>()
+            .add_systems(Update, (dialog_buttons, show_dialog).chain())
+            .add_systems(OnExit(GameState::Playing), close_dialog);
+    }
+}
+
+/// An action of the game confirmed in the dialog, to be done this time without asking.
+#[derive(Event, Clone, Copy, Debug)]
+pub(crate) struct Confirmed(pub PlayButtonActions);
+
+// The action waiting for an answer in the dialog, if it's open
+#[derive(Resource, Default)]
+struct Asked(Option<PlayButtonActions>);
+
+#[derive(Component)]
+struct ConfirmDialog;
+
+#[derive(Component, Clone, Copy)]
+enum DialogButton {
+    Yes,
+    No,
+    Never,
+}
+
+/// Asks for a confirmation before the actions giving up the solve in progress, unless the
+/// player chose not to be asked again.
+#[derive(SystemParam)]
+pub(crate) struct Confirmation<'w, 's> {
+    settings: Res<'w, Settings>,
+    asked: ResMut<'w, Asked>,
+    confirmed: EventReader<'w, 's, Confirmed>,
+}
+
+impl Confirmation<'_, '_> {
+    /// The actions confirmed since the last frame.
+    pub fn confirmed(&mut self) -> Vec<PlayButtonActions> {
+        self.confirmed
+            .read()
+            .map(|Confirmed(action)| *action)
+            .collect()
+    }
+
+    /// Opens the dialog instead of doing an action throwing away the solve in progress,
+    /// returning whether it was opened.
+    pub fn ask(&mut self, action: PlayButtonActions, current_cube: &CurrentCube) -> bool {
+        if !self.settings.confirm_discard || !current_cube.is_solving() {
+            return false;
+        }
+        self.asked.0 = Some(action);
+        true
+    }
+}
+
+fn dialog_buttons(
+    theme: Res<Theme>,
+    mut asked: ResMut<Asked>,
+    mut settings: ResMut<Settings>,
+    mut confirmed: EventWriter<Confirmed>,
+    mut q_buttons: Query<(&Interaction, &DialogButton, &mut BackgroundColor), Changed<Interaction>>,
+) {
+    for (interaction, button, mut background) in &mut q_buttons {
+        background.0 = theme.button(*interaction, false);
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let Some(action) = asked.0.take() else {
+            continue;
+        };
+
+        match button {
+            DialogButton::Yes => confirmed.send(Confirmed(action)),
+            DialogButton::No => {}
+            DialogButton::Never => {
+                settings.confirm_discard = false;
+                confirmed.send(Confirmed(action));
+            }
+        }
+    }
+}
+
+fn close_dialog(mut asked: ResMut<Asked>) {
+    asked.0 = None;
+}
+
+// Spawns the dialog over the whole window, keeping the clicks from the game's buttons below
+fn show_dialog(
+    mut commands: Commands,
+    asked: Res<Asked>,
+    asset_server: Res<AssetServer>,
+    theme: Res<Theme>,
+    q_dialog: Query<Entity, With<ConfirmDialog>>,
+) {
+    if !asked.is_changed() {
+        return;
+    }
+    for entity in &q_dialog {
+        commands.entity(entity).despawn_recursive();
+    }
+    if asked.0.is_none() {
+        return;
+    }
+
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    let button_style = Style {
+        padding: UiRect::axes(Val::Px(20.0), Val::Px(10.0)),
+        margin: UiRect::all(Val::Px(10.0)),
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        ..default()
+    };
+    let text_style = TextStyle {
+        font,
+        font_size: 30.0,
+        color: theme.text(),
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: Color::rgba(0.0, 0.0, 0.0, 0.5).into(),
+                focus_policy: FocusPolicy::Block,
+                ..default()
+            },
+            // over the game's panels and the diagnostics, under the console
+            ZIndex::Global(25),
+            ConfirmDialog,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    NodeBundle {
+                        style: Style {
+                            flex_direction: FlexDirection::Column,
+                            align_items: AlignItems::Center,
+                            padding: UiRect::all(Val::Px(20.0)),
+                            ..default()
+                        },
+                        ..default()
+                    },
+                    ThemeColor::Accent,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section(String::new(), text_style.clone()).with_style(
+                            Style {
+                                margin: UiRect::all(Val::Px(20.0)),
+                                ..default()
+                            },
+                        ),
+                        Localized::new("confirm-discard"),
+                    ));
+                    parent.spawn(NodeBundle::default()).with_children(|parent| {
+                        let buttons = [
+                            (DialogButton::Yes, "confirm-yes"),
+                            (DialogButton::No, "confirm-no"),
+                            (DialogButton::Never, "confirm-never"),
+                        ];
+                        for (button, text_id) in buttons {
+                            parent
+                                .spawn((
+                                    ButtonBundle {
+                                        style: button_style.clone(),
+                                        background_color: theme
+                                            .button(Interaction::None, false)
+                                            .into(),
+                                        ..default()
+                                    },
+                                    button,
+                                ))
+                                .with_children(|parent| {
+                                    parent.spawn((
+                                        TextBundle::from_section(String::new(), text_style.clone()),
+                                        Localized::new(text_id),
+                                    ));
+                                });
+                        }
+                    });
+                });
+        });
+}
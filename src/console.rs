@@ -0,0 +1,332 @@
+use crate::{
+    i18n::{Localized, Translations},
+    scramble::Scrambler,
+    session::SolveSession,
+    simulator::{CurrentCube, MoveQueue, PlayedMut},
+    CubeSizeSetting, GameState, Puzzle,
+};
+use bevy::{
+    ecs::system::SystemParam,
+    input::{keyboard::KeyboardInput, ButtonState, InputSystem},
+    prelude::*,
+};
+use cubesim::{parse_algorithm, prelude::*, ParseError};
+use std::str::FromStr;
+
+const TOGGLE_KEY: KeyCode = KeyCode::Grave;
+// the lines of output kept above the prompt
+const MAX_LINES: usize = 8;
+
+pub struct ConsolePlugin;
+
+impl Plugin for ConsolePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Console>()
+            .add_event::<ConsoleCommand>()
+            .add_systems(
+                PreUpdate,
+                type_command
+                    .in_set(ConsoleInput)
+                    .after(InputSystem)
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(resource_equals(Puzzle::Cube)),
+            )
+            .add_systems(
+                Update,
+                (run_commands, show_console)
+                    .chain()
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(resource_equals(Puzzle::Cube)),
+            )
+            .add_systems(OnEnter(GameState::Playing), console_setup)
+            .add_systems(OnExit(GameState::Playing), clean_up)
+            .add_systems(OnEnter(GameState::Restarting), restart)
+            .add_systems(OnEnter(GameState::Menu), close_console);
+    }
+}
+
+/// Reads the keys typed in the console, taking them from the other systems reading the input.
+#[derive(SystemSet, Clone, Debug, Eq, Hash, PartialEq)]
+pub(crate) struct ConsoleInput;
+
+/// A console toggled with `` ` ``, running commands typed in it on the cube. The keys typed
+/// while it's open don't turn the cube.
+#[derive(Resource, Default)]
+struct Console {
+    open: bool,
+    input: String,
+    lines: Vec<String>,
+}
+
+impl Console {
+    fn print(&mut self, line: impl Into<String>) {
+        self.lines.push(line.into());
+        if self.lines.len() > MAX_LINES {
+            self.lines.remove(0);
+        }
+    }
+}
+
+// The console, printing the answers to the commands in the chosen language
+#[derive(SystemParam)]
+struct ConsoleOutput<'w> {
+    console: ResMut<'w, Console>,
+    translations: Translations<'w>,
+}
+
+impl ConsoleOutput<'_> {
+    fn say(&mut self, line: Localized) {
+        let line = self.translations.text(line);
+        self.console.print(line);
+    }
+}
+
+// The game started over by the size and reset commands
+#[derive(SystemParam)]
+struct Restart<'w> {
+    cube_size: ResMut<'w, CubeSizeSetting>,
+    game_state: ResMut<'w, NextState<GameState>>,
+}
+
+/// A command typed in the console, or sent by a script.
+#[derive(Event, Clone, Debug, PartialEq)]
+pub(crate) enum ConsoleCommand {
+    /// Scrambles the cube, with a given number of moves or as many as the scramble button.
+    Scramble(Option<usize>),
+    /// Scrambles the cube with the moves of a scramble in WCA notation.
+    ScrambleWith(Vec<Move>),
+    /// Queues the moves of an algorithm in WCA notation.
+    Alg(Vec<Move>),
+    /// Starts over with a cube of another size.
+    Size(usize),
+    Solve,
+    /// Starts over with a solved cube.
+    Reset,
+    Help,
+}
+
+impl FromStr for ConsoleCommand {
+    type Err = Localized;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, args) = s.trim().split_once(' ').unwrap_or((s.trim(), ""));
+        let args = args.trim();
+
+        match name {
+            "scramble" if args.is_empty() => Ok(ConsoleCommand::Scramble(None)),
+            "scramble" => match args.parse() {
+                Ok(moves) if moves > 0 => Ok(ConsoleCommand::Scramble(Some(moves))),
+                Ok(_) => Err(Localized::new("console-invalid-length").with("length", args)),
+                Err(_) => Ok(ConsoleCommand::ScrambleWith(
+                    parse_algorithm(args).map_err(invalid_moves)?,
+                )),
+            },
+            "alg" if args.is_empty() => Err(Localized::new("console-missing-moves")),
+            "alg" => Ok(ConsoleCommand::Alg(
+                parse_algorithm(args).map_err(invalid_moves)?,
+            )),
+            "size" => match args.parse() {
+                Ok(size) if CubeSizeSetting::SIZES.contains(&size) => {
+                    Ok(ConsoleCommand::Size(size))
+                }
+                _ => Err(Localized::new("console-invalid-size").with("size", args)),
+            },
+            // the only solver there is
+            "solve" if args.is_empty() || args == "thistlethwaite" => Ok(ConsoleCommand::Solve),
+            "solve" => Err(Localized::new("console-unknown-solver").with("solver", args)),
+            "reset" if args.is_empty() => Ok(ConsoleCommand::Reset),
+            "help" if args.is_empty() => Ok(ConsoleCommand::Help),
+            "reset" | "help" => Err(Localized::new("console-no-arguments").with("command", name)),
+            _ => Err(Localized::new("console-unknown-command").with("command", name)),
+        }
+    }
+}
+
+fn invalid_moves(err: ParseError) -> Localized {
+    Localized::new("console-invalid-moves").with("error", err.to_string())
+}
+
+#[derive(Component)]
+struct ConsoleUi;
+
+#[derive(Component)]
+struct ConsoleText;
+
+fn console_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let text_style = TextStyle {
+        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+        font_size: 20.0,
+        color: Color::WHITE,
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(0.0),
+                    left: Val::Px(0.0),
+                    right: Val::Px(0.0),
+                    padding: UiRect::all(Val::Px(8.0)),
+                    ..default()
+                },
+                background_color: Color::rgba(0.0, 0.0, 0.0, 0.8).into(),
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+            // over the game's panels and overlays
+            ZIndex::Global(30),
+            ConsoleUi,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_sections([
+                    TextSection::new(String::new(), text_style.clone()),
+                    TextSection::new(String::new(), text_style),
+                ]),
+                ConsoleText,
+            ));
+        });
+}
+
+// Edits the command while the console is open, keeping the keys from the rest of the game
+fn type_command(
+    mut keyboard_input: ResMut<Input<KeyCode>>,
+    mut key_events: EventReader<KeyboardInput>,
+    mut characters: EventReader<ReceivedCharacter>,
+    mut console: ResMut<Console>,
+    mut commands: EventWriter<ConsoleCommand>,
+    translations: Translations,
+) {
+    if keyboard_input.just_pressed(TOGGLE_KEY) {
+        console.open = !console.open;
+    } else if console.open && keyboard_input.just_pressed(KeyCode::Escape) {
+        // only closing the console, not leaving the game
+        console.open = false;
+        keyboard_input.reset_all();
+    }
+    if !console.open {
+        key_events.clear();
+        characters.clear();
+        return;
+    }
+    keyboard_input.reset_all();
+
+    for character in characters.read() {
+        if !character.char.is_control() && character.char != '`' {
+            console.input.push(character.char);
+        }
+    }
+    // held keys repeat their events
+    for event in key_events.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+        match event.key_code {
+            Some(KeyCode::Back) => {
+                console.input.pop();
+            }
+            Some(KeyCode::Return | KeyCode::NumpadEnter) => {
+                let line = std::mem::take(&mut console.input);
+                if line.trim().is_empty() {
+                    continue;
+                }
+                console.print(format!("> {line}"));
+                match line.parse() {
+                    Ok(command) => commands.send(command),
+                    Err(err) => console.print(translations.text(err)),
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn run_commands(
+    mut commands: EventReader<ConsoleCommand>,
+    mut output: ConsoleOutput,
+    mut current_cube: PlayedMut<CurrentCube>,
+    mut move_queue: PlayedMut<MoveQueue>,
+    mut session: PlayedMut<SolveSession>,
+    mut restart: Restart,
+    mut scrambler: Scrambler,
+) {
+    for command in commands.read() {
+        match command {
+            ConsoleCommand::Scramble(moves) => {
+                let size = current_cube.cube_size() as CubeSize;
+                let scramble = match moves {
+                    Some(moves) => scrambler.scramble_of_length(size, Some(*moves)),
+                    None => scrambler.scramble(size),
+                };
+                output.say(Localized::new("console-scrambling").with("moves", scramble.len()));
+                session.queue_scramble(&mut current_cube, &mut move_queue, scramble);
+            }
+            ConsoleCommand::ScrambleWith(moves) => {
+                output.say(Localized::new("console-scrambling").with("moves", moves.len()));
+                session.queue_scramble(&mut current_cube, &mut move_queue, moves.clone());
+            }
+            ConsoleCommand::Alg(moves) => {
+                move_queue.extend(moves.iter().copied());
+            }
+            ConsoleCommand::Size(size) => {
+                restart.cube_size.0 = *size;
+                restart.game_state.set(GameState::Restarting);
+                output.say(Localized::new("console-size").with("size", *size));
+            }
+            ConsoleCommand::Solve => match session.solve(&current_cube, &move_queue) {
+                Ok(()) => output.say(Localized::new("console-solving")),
+                Err(err) => output.say(err),
+            },
+            ConsoleCommand::Reset => {
+                restart.game_state.set(GameState::Restarting);
+                output.say(Localized::new("console-reset"));
+            }
+            ConsoleCommand::Help => output.say(Localized::new("console-help")),
+        }
+    }
+}
+
+fn show_console(
+    console: Res<Console>,
+    mut q_ui: Query<&mut Visibility, With<ConsoleUi>>,
+    mut q_text: Query<&mut Text, With<ConsoleText>>,
+) {
+    for mut visibility in &mut q_ui {
+        visibility.set_if_neq(if console.open {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        });
+    }
+    let lines: String = console
+        .lines
+        .iter()
+        .map(|line| format!("{line}\n"))
+        .collect();
+    let prompt = format!("> {}_", console.input);
+    for mut text in &mut q_text {
+        if text.sections[0].value != lines {
+            text.sections[0].value = lines.clone();
+        }
+        if text.sections[1].value != prompt {
+            text.sections[1].value = prompt.clone();
+        }
+    }
+}
+
+// The game is left for a frame, so it starts over from the settings
+fn restart(mut game_state: ResMut<NextState<GameState>>) {
+    game_state.set(GameState::Playing);
+}
+
+fn close_console(mut console: ResMut<Console>) {
+    console.open = false;
+    console.input.clear();
+}
+
+fn clean_up(mut commands: Commands, q_ui: Query<Entity, With<ConsoleUi>>) {
+    for entity in &q_ui {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,128 @@
+use crate::{
+    i18n::{Localized, Translations},
+    simulator::{MoveQueue, Played},
+    solved::OptimalSearch,
+};
+use bevy::{
+    diagnostic::{DiagnosticsStore, EntityCountDiagnosticsPlugin, FrameTimeDiagnosticsPlugin},
+    prelude::*,
+};
+
+const TOGGLE_KEY: KeyCode = KeyCode::I;
+
+pub struct DiagnosticsPlugin;
+
+impl Plugin for DiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((FrameTimeDiagnosticsPlugin, EntityCountDiagnosticsPlugin))
+            .init_resource::<DiagnosticsOverlay>()
+            .register_type::<DiagnosticsOverlay>()
+            .add_systems(Startup, overlay_setup)
+            .add_systems(Update, (toggle_overlay, show_diagnostics).chain());
+    }
+}
+
+/// Whether the frame rate, entity count and solver status are shown, toggled with `I` or from
+/// the display settings.
+#[derive(Resource, Component, Reflect, Clone, Copy, Eq, PartialEq, Debug, Default)]
+#[reflect(Resource)]
+pub(crate) enum DiagnosticsOverlay {
+    #[default]
+    Hidden,
+    Shown,
+}
+
+impl DiagnosticsOverlay {
+    pub(crate) const ALL: [DiagnosticsOverlay; 2] =
+        [DiagnosticsOverlay::Hidden, DiagnosticsOverlay::Shown];
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            DiagnosticsOverlay::Hidden => "Off",
+            DiagnosticsOverlay::Shown => "On",
+        }
+    }
+}
+
+#[derive(Component)]
+struct DiagnosticsText;
+
+fn overlay_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((
+        TextBundle::from_section(
+            String::new(),
+            TextStyle {
+                font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                font_size: 18.0,
+                color: Color::LIME_GREEN,
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(5.0),
+            left: Val::Px(210.0),
+            ..default()
+        }),
+        // over the menus and the game's panels
+        ZIndex::Global(20),
+        DiagnosticsText,
+    ));
+}
+
+fn toggle_overlay(keyboard_input: Res<Input<KeyCode>>, mut overlay: ResMut<DiagnosticsOverlay>) {
+    if keyboard_input.just_pressed(TOGGLE_KEY) {
+        *overlay = match *overlay {
+            DiagnosticsOverlay::Hidden => DiagnosticsOverlay::Shown,
+            DiagnosticsOverlay::Shown => DiagnosticsOverlay::Hidden,
+        };
+    }
+}
+
+fn show_diagnostics(
+    overlay: Res<DiagnosticsOverlay>,
+    diagnostics: Res<DiagnosticsStore>,
+    move_queue: Played<MoveQueue>,
+    q_search: Query<(), With<OptimalSearch>>,
+    translations: Translations,
+    mut q_text: Query<(&mut Text, &mut Visibility), With<DiagnosticsText>>,
+) {
+    for (mut text, mut visibility) in &mut q_text {
+        if *overlay == DiagnosticsOverlay::Hidden {
+            visibility.set_if_neq(Visibility::Hidden);
+            continue;
+        }
+        visibility.set_if_neq(Visibility::Inherited);
+
+        let smoothed = |id| {
+            diagnostics
+                .get(id)
+                .and_then(|diagnostic| diagnostic.smoothed())
+                .unwrap_or_default()
+        };
+        let solver = if q_search.is_empty() {
+            "diagnostics-solver-idle"
+        } else {
+            "diagnostics-solver-searching"
+        };
+        text.sections[0].value = translations.text(
+            Localized::new("diagnostics")
+                .with(
+                    "fps",
+                    format!("{:.0}", smoothed(FrameTimeDiagnosticsPlugin::FPS)),
+                )
+                .with(
+                    "ms",
+                    format!("{:.1}", smoothed(FrameTimeDiagnosticsPlugin::FRAME_TIME)),
+                )
+                .with(
+                    "entities",
+                    format!(
+                        "{:.0}",
+                        smoothed(EntityCountDiagnosticsPlugin::ENTITY_COUNT)
+                    ),
+                )
+                .with("queued", move_queue.len())
+                .with("solver", translations.get(solver)),
+        );
+    }
+}
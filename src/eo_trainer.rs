@@ -0,0 +1,349 @@
+use crate::{
+    i18n::{Localized, Translations},
+    menu::{MenuButtonAction, MenuState},
+    simulator::FaceColors,
+    theme::{Accent, Theme, ThemeColor},
+};
+use bevy::prelude::*;
+use cubesim::{bad_edges, prelude::*, FaceletCube, EDGE_STICKERS};
+use std::time::Duration;
+
+// The width of a sticker of the net, in pixels
+const STICKER_WIDTH: f32 = 30.0;
+const MARK_WIDTH: f32 = 3.0;
+const MARKED_COLOR: Color = Color::WHITE;
+// Once checked, the bad edges found, the bad edges missed and the good edges marked
+const FOUND_COLOR: Color = Color::rgb(0.1, 0.8, 0.2);
+const MISSED_COLOR: Color = Color::rgb(0.9, 0.1, 0.1);
+const WRONG_COLOR: Color = Color::rgb(1.0, 0.6, 0.0);
+// Where each face sits in the unfolded net in whole faces, in the order of the state: U, R,
+// F, D, L and B
+const NET_POSITIONS: [(usize, usize); 6] = [(1, 0), (2, 1), (1, 1), (1, 2), (0, 1), (3, 1)];
+
+pub struct EoTrainerPlugin;
+
+impl Plugin for EoTrainerPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EoDrill>()
+            .add_systems(OnEnter(MenuState::EoTrainer), (next_drill, eo_screen_setup))
+            .add_systems(
+                Update,
+                (mark_edges, drill_buttons, show_drill)
+                    .chain()
+                    .run_if(in_state(MenuState::EoTrainer)),
+            )
+            .add_systems(OnExit(MenuState::EoTrainer), clean_up);
+    }
+}
+
+/// Drills the recognition of the edges misoriented on the F/B axis, as in the ZZ method and
+/// fewest moves solving: the bad edges of a random state are marked on its net, then checked
+/// against `cubesim`, the accuracy and the time taken kept over the drills.
+#[derive(Resource, Default)]
+struct EoDrill {
+    state: Vec<Face>,
+    bad: Vec<usize>,
+    marked: [bool; 12],
+    shown_at: Duration,
+    // the time taken to check the marks, the drill being reviewed once checked
+    answer: Option<Duration>,
+    drills: usize,
+    right: usize,
+    total_time: Duration,
+}
+
+impl EoDrill {
+    fn next(&mut self, now: Duration) {
+        let cube = FaceletCube::random_state(&mut rand::thread_rng());
+        self.state = cube.state();
+        self.bad = bad_edges(&self.state).unwrap_or_default();
+        self.marked = [false; 12];
+        self.shown_at = now;
+        self.answer = None;
+    }
+
+    fn is_right(&self) -> bool {
+        (0..12).all(|edge| self.marked[edge] == self.bad.contains(&edge))
+    }
+
+    fn check(&mut self, now: Duration) {
+        let time = now.saturating_sub(self.shown_at);
+        self.answer = Some(time);
+        self.drills += 1;
+        self.total_time += time;
+        if self.is_right() {
+            self.right += 1;
+        }
+    }
+
+    // the color of the mark around the stickers of an edge
+    fn mark_color(&self, edge: usize) -> Color {
+        let (marked, bad) = (self.marked[edge], self.bad.contains(&edge));
+        match (self.answer, marked, bad) {
+            (None, true, _) => MARKED_COLOR,
+            (Some(_), true, true) => FOUND_COLOR,
+            (Some(_), false, true) => MISSED_COLOR,
+            (Some(_), true, false) => WRONG_COLOR,
+            _ => Color::NONE,
+        }
+    }
+}
+
+#[derive(Component)]
+struct OnEoTrainerScreen;
+
+// A sticker of the net, by its index in the state, and the edge it belongs to if any
+#[derive(Component)]
+struct NetSticker {
+    index: usize,
+    edge: Option<usize>,
+}
+
+#[derive(Component, Clone, Copy)]
+enum DrillButton {
+    Check,
+    Next,
+}
+
+#[derive(Component)]
+struct DrillText;
+
+fn next_drill(time: Res<Time>, mut drill: ResMut<EoDrill>) {
+    drill.next(time.elapsed());
+}
+
+fn eo_screen_setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    theme: Res<Theme>,
+    accent: Res<Accent>,
+) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    let button_style = Style {
+        height: Val::Px(45.0),
+        margin: UiRect::all(Val::Px(5.0)),
+        padding: UiRect::horizontal(Val::Px(10.0)),
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        ..default()
+    };
+    let text_style = TextStyle {
+        font: font.clone(),
+        font_size: 30.0,
+        color: theme.text(),
+    };
+    let face_width = STICKER_WIDTH * 3.0;
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    margin: UiRect::all(Val::Auto),
+                    padding: UiRect::all(Val::Px(20.0)),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: accent.color().into(),
+                ..default()
+            },
+            OnEoTrainerScreen,
+            ThemeColor::Accent,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    String::new(),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 50.0,
+                        color: theme.text(),
+                    },
+                ),
+                Localized::new("training-eo"),
+            ));
+            parent.spawn((
+                TextBundle::from_section(String::new(), text_style.clone()),
+                Localized::new("eo-instructions"),
+            ));
+
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        width: Val::Px(face_width * 4.0),
+                        height: Val::Px(face_width * 3.0),
+                        margin: UiRect::all(Val::Px(10.0)),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for index in 0..54 {
+                        let (face, n) = (index / 9, index % 9);
+                        let (net_x, net_y) = NET_POSITIONS[face];
+                        let edge = EDGE_STICKERS
+                            .iter()
+                            .position(|stickers| stickers.contains(&index));
+                        let mut sticker = parent.spawn((
+                            NodeBundle {
+                                style: Style {
+                                    position_type: PositionType::Absolute,
+                                    left: Val::Px(
+                                        net_x as f32 * face_width + (n % 3) as f32 * STICKER_WIDTH,
+                                    ),
+                                    top: Val::Px(
+                                        net_y as f32 * face_width + (n / 3) as f32 * STICKER_WIDTH,
+                                    ),
+                                    width: Val::Px(STICKER_WIDTH - 2.0),
+                                    height: Val::Px(STICKER_WIDTH - 2.0),
+                                    border: UiRect::all(Val::Px(MARK_WIDTH)),
+                                    ..default()
+                                },
+                                ..default()
+                            },
+                            NetSticker { index, edge },
+                        ));
+                        if edge.is_some() {
+                            sticker.insert(Interaction::None);
+                        }
+                    }
+                });
+
+            parent
+                .spawn(TextBundle::from_section(String::new(), text_style.clone()))
+                .insert(DrillText);
+
+            parent.spawn(NodeBundle::default()).with_children(|parent| {
+                for (button, text_id) in [
+                    (DrillButton::Check, "eo-check"),
+                    (DrillButton::Next, "eo-next"),
+                ] {
+                    parent
+                        .spawn((
+                            ButtonBundle {
+                                style: button_style.clone(),
+                                background_color: theme.button(Interaction::None, false).into(),
+                                ..default()
+                            },
+                            button,
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                TextBundle::from_section(String::new(), text_style.clone()),
+                                Localized::new(text_id),
+                            ));
+                        });
+                }
+                parent
+                    .spawn((
+                        ButtonBundle {
+                            style: button_style.clone(),
+                            background_color: theme.button(Interaction::None, false).into(),
+                            ..default()
+                        },
+                        MenuButtonAction::BackToTraining,
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn((
+                            TextBundle::from_section(String::new(), text_style.clone()),
+                            Localized::new("menu-back"),
+                        ));
+                    });
+            });
+        });
+}
+
+// Clicking either sticker of an edge marks it, or unmarks it, until the marks are checked
+fn mark_edges(
+    q_stickers: Query<(&Interaction, &NetSticker), Changed<Interaction>>,
+    mut drill: ResMut<EoDrill>,
+) {
+    if drill.answer.is_some() {
+        return;
+    }
+    for (interaction, sticker) in &q_stickers {
+        if let (Interaction::Pressed, Some(edge)) = (interaction, sticker.edge) {
+            drill.marked[edge] = !drill.marked[edge];
+        }
+    }
+}
+
+fn drill_buttons(
+    time: Res<Time>,
+    q_buttons: Query<(&Interaction, &DrillButton), Changed<Interaction>>,
+    mut drill: ResMut<EoDrill>,
+) {
+    for (interaction, button) in &q_buttons {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match button {
+            DrillButton::Check if drill.answer.is_none() => drill.check(time.elapsed()),
+            DrillButton::Next => drill.next(time.elapsed()),
+            DrillButton::Check => {}
+        }
+    }
+}
+
+fn show_drill(
+    drill: Res<EoDrill>,
+    face_colors: Res<FaceColors>,
+    translations: Translations,
+    mut q_stickers: Query<(Ref<NetSticker>, &mut BackgroundColor, &mut BorderColor)>,
+    mut q_text: Query<&mut Text, With<DrillText>>,
+) {
+    let added = q_stickers.iter().any(|(sticker, ..)| sticker.is_added());
+    if !drill.is_changed() && !added {
+        return;
+    }
+
+    for (sticker, mut background, mut border) in &mut q_stickers {
+        if let Some(&face) = drill.state.get(sticker.index) {
+            background.0 = face_colors.color(face);
+        }
+        border.0 = sticker
+            .edge
+            .map_or(Color::NONE, |edge| drill.mark_color(edge));
+    }
+
+    let marked = drill.marked.iter().filter(|&&marked| marked).count();
+    let result = match drill.answer {
+        None => Localized::new("eo-marked"),
+        Some(time) => {
+            let result = match drill.is_right() {
+                true => Localized::new("eo-right"),
+                false => Localized::new("eo-wrong"),
+            };
+            result
+                .with("bad", drill.bad.len())
+                .with("seconds", format!("{:.1}", time.as_secs_f32()))
+        }
+    };
+    let result = translations.text(result.with("marked", marked));
+    let summary = match drill.drills {
+        0 => String::new(),
+        drills => {
+            let summary = Localized::new("eo-summary")
+                .with("right", drill.right)
+                .with("drills", drills)
+                .with(
+                    "percent",
+                    format!("{:.0}", drill.right as f32 / drills as f32 * 100.0),
+                )
+                .with(
+                    "seconds",
+                    format!("{:.1}", drill.total_time.as_secs_f32() / drills as f32),
+                );
+            format!("\n{}", translations.text(summary))
+        }
+    };
+    for mut text in &mut q_text {
+        text.sections[0].value = format!("{result}{summary}");
+    }
+}
+
+fn clean_up(mut commands: Commands, q_screen: Query<Entity, With<OnEoTrainerScreen>>) {
+    for entity in &q_screen {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,286 @@
+use crate::{
+    i18n::Localized,
+    screenshot::{timestamped, HideUi},
+    simulator::{CurrentCube, MoveQueue, Played, PlayedMut, StatusLine},
+    solved::CubeSolvedEvent,
+    GameState, Puzzle,
+};
+use bevy::{
+    prelude::*,
+    render::view::screenshot::ScreenshotManager,
+    tasks::{block_on, AsyncComputeTaskPool, Task},
+    time::TimeUpdateStrategy,
+    window::PrimaryWindow,
+};
+use cubesim::prelude::*;
+use image::{
+    codecs::gif::{GifEncoder, Repeat},
+    Delay, Frame, RgbaImage,
+};
+use std::{
+    fs,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+const EXPORT_KEY: KeyCode = KeyCode::V;
+// the fixed time step of the replay, whatever the frame rate of the app
+const FRAME_MILLIS: u32 = 40;
+// frames are scaled down to this width, keeping the export small
+const FRAME_WIDTH: u32 = 480;
+// the frames showing the scrambled cube before the solution, and the solved cube after it
+const HOLD_FRAMES: usize = 12;
+
+pub struct ExportPlugin;
+
+impl Plugin for ExportPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LastSolve>()
+            .add_systems(
+                Update,
+                (
+                    remember_solve,
+                    start_export.run_if(not(resource_exists::<Export>())),
+                    capture_frames.run_if(resource_exists::<Export>()),
+                    finish_export.run_if(resource_exists::<Export>()),
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(resource_equals(Puzzle::Cube)),
+            )
+            .add_systems(OnExit(GameState::Playing), clean_up);
+    }
+}
+
+// The scramble and solution of the last solve, replayed by the export
+#[derive(Resource, Default)]
+struct LastSolve(Option<(Vec<Move>, Vec<Move>)>);
+
+/// How the frames of a replay are saved.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum ExportFormat {
+    /// An animated GIF, looping forever.
+    Gif,
+    /// A folder of numbered PNG images, to be put together by a video editor.
+    PngSequence,
+}
+
+// The frames read back so far by their index, none if one couldn't be read. They are read back
+// from the render world, in no particular order.
+type ReadFrames = Arc<Mutex<Vec<(usize, Option<RgbaImage>)>>>;
+
+enum ExportPhase {
+    // the scramble is applied, off camera
+    Scrambling,
+    // the scrambled cube, then the solution, then the solved cube are captured
+    Capturing { solving: bool, held: usize },
+    // waiting for the last captured frames to be read back
+    Collecting,
+    Encoding(Task<Result<String, String>>),
+}
+
+/// A replay of the last solve being exported. The app runs at a fixed time step meanwhile, so
+/// the animation is the same however long each frame takes to capture.
+#[derive(Resource)]
+struct Export {
+    format: ExportFormat,
+    solution: Vec<Move>,
+    phase: ExportPhase,
+    frames: ReadFrames,
+    captured: usize,
+}
+
+fn remember_solve(
+    mut solved_events: EventReader<CubeSolvedEvent>,
+    mut last_solve: ResMut<LastSolve>,
+) {
+    if let Some(solved) = solved_events.read().last() {
+        last_solve.0 = Some((solved.scramble.clone(), solved.solution.clone()));
+    }
+}
+
+/// Replays the last solve with `V`, saving it as a GIF, or as PNG images with Shift + `V`.
+fn start_export(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    last_solve: Res<LastSolve>,
+    current_cube: Played<CurrentCube>,
+    mut move_queue: PlayedMut<MoveQueue>,
+    mut hide_ui: ResMut<HideUi>,
+    mut status_line: StatusLine,
+) {
+    if !keyboard_input.just_pressed(EXPORT_KEY) {
+        return;
+    }
+    let Some((scramble, solution)) = &last_solve.0 else {
+        status_line.show(Localized::new("export-no-solve"));
+        return;
+    };
+    // the replay starts from the solved cube, once every move is made
+    if !current_cube.is_solved() || !move_queue.is_empty() || move_queue.in_flight().is_some() {
+        status_line.show(Localized::new("export-unsolved"));
+        return;
+    }
+
+    let format = if keyboard_input.pressed(KeyCode::ShiftLeft) {
+        ExportFormat::PngSequence
+    } else {
+        ExportFormat::Gif
+    };
+    commands.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+        FRAME_MILLIS as u64,
+    )));
+    hide_ui.export = true;
+    move_queue.extend(scramble.iter().copied());
+    commands.insert_resource(Export {
+        format,
+        solution: solution.clone(),
+        phase: ExportPhase::Scrambling,
+        frames: Arc::default(),
+        captured: 0,
+    });
+    status_line.show(Localized::new("export-started"));
+}
+
+fn capture_frames(
+    mut export: ResMut<Export>,
+    mut move_queue: PlayedMut<MoveQueue>,
+    mut screenshot_manager: ResMut<ScreenshotManager>,
+    q_window: Query<Entity, With<PrimaryWindow>>,
+) {
+    let idle = move_queue.is_empty() && move_queue.in_flight().is_none();
+    let export = &mut *export;
+    let (solving, held) = match &mut export.phase {
+        ExportPhase::Scrambling if idle => {
+            export.phase = ExportPhase::Capturing {
+                solving: false,
+                held: 0,
+            };
+            return;
+        }
+        ExportPhase::Capturing { solving, held } => (solving, held),
+        _ => return,
+    };
+    let Ok(window) = q_window.get_single() else {
+        return;
+    };
+
+    let index = export.captured;
+    let frames = export.frames.clone();
+    let requested = screenshot_manager.take_screenshot(window, move |image| {
+        let frame = match image.try_into_dynamic() {
+            Ok(image) => Some(image.thumbnail(FRAME_WIDTH, u32::MAX).to_rgba8()),
+            Err(err) => {
+                warn!("frame {index} of the replay can't be read: {err}");
+                None
+            }
+        };
+        frames
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .push((index, frame));
+    });
+    if requested.is_err() {
+        return;
+    }
+    export.captured += 1;
+
+    if !idle {
+        return;
+    }
+    *held += 1;
+    if *held < HOLD_FRAMES {
+        return;
+    }
+    if *solving {
+        export.phase = ExportPhase::Collecting;
+    } else {
+        move_queue.extend(export.solution.iter().copied());
+        *solving = true;
+        *held = 0;
+    }
+}
+
+fn encode_gif(path: &str, frames: Vec<RgbaImage>) -> Result<(), String> {
+    let file = fs::File::create(path).map_err(|err| format!("{path}: {err}"))?;
+    let mut encoder = GifEncoder::new_with_speed(file, 10);
+    encoder
+        .set_repeat(Repeat::Infinite)
+        .map_err(|err| format!("{path}: {err}"))?;
+    for frame in frames {
+        let delay = Delay::from_numer_denom_ms(FRAME_MILLIS, 1);
+        encoder
+            .encode_frame(Frame::from_parts(frame, 0, 0, delay))
+            .map_err(|err| format!("{path}: {err}"))?;
+    }
+    Ok(())
+}
+
+fn save_png_sequence(path: &str, frames: Vec<RgbaImage>) -> Result<(), String> {
+    fs::create_dir_all(path).map_err(|err| format!("{path}: {err}"))?;
+    for (index, frame) in frames.into_iter().enumerate() {
+        let frame_path = format!("{path}/frame-{index:04}.png");
+        frame
+            .save(&frame_path)
+            .map_err(|err| format!("{frame_path}: {err}"))?;
+    }
+    Ok(())
+}
+
+fn finish_export(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut export: ResMut<Export>,
+    mut hide_ui: ResMut<HideUi>,
+    mut status_line: StatusLine,
+) {
+    let export = &mut *export;
+    match &mut export.phase {
+        ExportPhase::Collecting => {
+            let mut frames = {
+                let mut frames = export.frames.lock().unwrap_or_else(|err| err.into_inner());
+                if frames.len() < export.captured {
+                    return;
+                }
+                std::mem::take(&mut *frames)
+            };
+            frames.sort_by_key(|(index, _)| *index);
+            let frames: Vec<RgbaImage> =
+                frames.into_iter().filter_map(|(_, frame)| frame).collect();
+            let format = export.format;
+            let path = match format {
+                ExportFormat::Gif => timestamped("replay", ".gif", &time),
+                ExportFormat::PngSequence => timestamped("replay", "", &time),
+            };
+
+            // the app runs at its own pace again while the frames are encoded
+            commands.insert_resource(TimeUpdateStrategy::Automatic);
+            hide_ui.export = false;
+            export.phase = ExportPhase::Encoding(AsyncComputeTaskPool::get().spawn(async move {
+                match format {
+                    ExportFormat::Gif => encode_gif(&path, frames),
+                    ExportFormat::PngSequence => save_png_sequence(&path, frames),
+                }
+                .map(|()| path)
+            }));
+        }
+        ExportPhase::Encoding(task) if task.is_finished() => {
+            let status = match block_on(task) {
+                Ok(path) => Localized::new("export-done").with("path", path),
+                Err(err) => {
+                    warn!("failed to export the replay: {err}");
+                    Localized::new("export-failed").with("error", err)
+                }
+            };
+            status_line.show(status);
+            commands.remove_resource::<Export>();
+        }
+        _ => {}
+    }
+}
+
+fn clean_up(mut commands: Commands, mut hide_ui: ResMut<HideUi>) {
+    commands.remove_resource::<Export>();
+    commands.insert_resource(TimeUpdateStrategy::Automatic);
+    hide_ui.export = false;
+}
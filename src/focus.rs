@@ -0,0 +1,233 @@
+use crate::console::ConsoleInput;
+use bevy::{
+    ecs::system::SystemParam,
+    prelude::*,
+    ui::{FocusPolicy, UiSystem},
+};
+use std::cmp::Ordering;
+
+// The width of the ring's border, drawn around the focused button
+const RING_WIDTH: f32 = 3.0;
+const RING_COLOR: Color = Color::rgb(1.0, 0.8, 0.0);
+
+/// Lets the buttons of the menus and of the game be focused with the arrow keys, `Tab` or a
+/// gamepad's d-pad, and pressed with `Enter` or the gamepad's south button. The focused button
+/// is shown by a ring around it, until the mouse is used again.
+pub struct FocusPlugin;
+
+impl Plugin for FocusPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Focus>()
+            .add_systems(Startup, spawn_focus_ring)
+            .add_systems(
+                PreUpdate,
+                // the keys typed in the console don't press the focused button
+                navigate.after(UiSystem::Focus).after(ConsoleInput),
+            )
+            .add_systems(PostUpdate, place_focus_ring.before(UiSystem::Layout));
+    }
+}
+
+/// The button focused with the keyboard or a gamepad, if any.
+#[derive(Resource, Default)]
+struct Focus(Option<Entity>);
+
+#[derive(Component)]
+struct FocusRing;
+
+// Where the focus moves to
+enum Step {
+    Towards(Vec2),
+    Next,
+    Previous,
+}
+
+#[derive(SystemParam)]
+struct NavigationInput<'w> {
+    keyboard: Res<'w, Input<KeyCode>>,
+    mouse: Res<'w, Input<MouseButton>>,
+    gamepads: Res<'w, Gamepads>,
+    gamepad_buttons: Res<'w, Input<GamepadButton>>,
+}
+
+impl NavigationInput<'_> {
+    fn gamepad_pressed(&self, button_type: GamepadButtonType) -> bool {
+        self.gamepads.iter().any(|gamepad| {
+            self.gamepad_buttons
+                .just_pressed(GamepadButton::new(gamepad, button_type))
+        })
+    }
+
+    fn step(&self) -> Option<Step> {
+        // the UI's y axis points down
+        let directions = [
+            (KeyCode::Up, GamepadButtonType::DPadUp, Vec2::NEG_Y),
+            (KeyCode::Down, GamepadButtonType::DPadDown, Vec2::Y),
+            (KeyCode::Left, GamepadButtonType::DPadLeft, Vec2::NEG_X),
+            (KeyCode::Right, GamepadButtonType::DPadRight, Vec2::X),
+        ];
+        for (key, button_type, direction) in directions {
+            if self.keyboard.just_pressed(key) || self.gamepad_pressed(button_type) {
+                return Some(Step::Towards(direction));
+            }
+        }
+
+        if self.keyboard.just_pressed(KeyCode::Tab) {
+            let shift = self
+                .keyboard
+                .any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+            return Some(if shift { Step::Previous } else { Step::Next });
+        }
+        None
+    }
+
+    fn activate(&self) -> bool {
+        self.keyboard
+            .any_just_pressed([KeyCode::Return, KeyCode::NumpadEnter])
+            || self.gamepad_pressed(GamepadButtonType::South)
+    }
+}
+
+fn spawn_focus_ring(mut commands: Commands) {
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                border: UiRect::all(Val::Px(RING_WIDTH)),
+                ..default()
+            },
+            border_color: RING_COLOR.into(),
+            // the button below still gets hovered and clicked
+            focus_policy: FocusPolicy::Pass,
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        // over the console too
+        ZIndex::Global(40),
+        FocusRing,
+    ));
+}
+
+// The center of a node, in the UI's pixels like its size
+fn center(transform: &GlobalTransform) -> Vec2 {
+    transform.translation().truncate()
+}
+
+// Reading order, from the top left
+fn reading_order(a: Vec2, b: Vec2) -> Ordering {
+    a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x))
+}
+
+fn next_focus(
+    step: Step,
+    current: Option<Vec2>,
+    mut buttons: Vec<(Entity, Vec2)>,
+) -> Option<Entity> {
+    buttons.sort_by(|(_, a), (_, b)| reading_order(*a, *b));
+    let Some(current) = current else {
+        return buttons.first().map(|(entity, _)| *entity);
+    };
+
+    match step {
+        // the closest button that way, preferring those straight ahead
+        Step::Towards(direction) => buttons
+            .into_iter()
+            .filter_map(|(entity, position)| {
+                let offset = position - current;
+                let ahead = offset.dot(direction);
+                let aside = offset.perp_dot(direction).abs();
+                (ahead > 1.0).then_some((entity, ahead + 2.0 * aside))
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(entity, _)| entity),
+        Step::Next => buttons
+            .iter()
+            .find(|(_, position)| reading_order(*position, current).is_gt())
+            .or(buttons.first())
+            .map(|(entity, _)| *entity),
+        Step::Previous => buttons
+            .iter()
+            .rev()
+            .find(|(_, position)| reading_order(*position, current).is_lt())
+            .or(buttons.last())
+            .map(|(entity, _)| *entity),
+    }
+}
+
+// Runs after the mouse's interactions, so pressing a button here is seen like a click
+fn navigate(
+    input: NavigationInput,
+    mut focus: ResMut<Focus>,
+    mut q_buttons: Query<(
+        Entity,
+        &Node,
+        &GlobalTransform,
+        &InheritedVisibility,
+        &mut Interaction,
+    )>,
+    mut pressed: Local<Option<Entity>>,
+) {
+    // the press of the last frame is over
+    if let Some(entity) = pressed.take() {
+        if let Ok((.., mut interaction)) = q_buttons.get_mut(entity) {
+            interaction.set_if_neq(Interaction::None);
+        }
+    }
+    if input.mouse.get_just_pressed().next().is_some() {
+        focus.0 = None;
+        return;
+    }
+
+    // the buttons of the screen left are gone
+    let current = focus
+        .0
+        .and_then(|entity| q_buttons.get(entity).ok())
+        .map(|(_, _, transform, ..)| center(transform));
+    if current.is_none() {
+        focus.0 = None;
+    }
+
+    if let Some(step) = input.step() {
+        let buttons = q_buttons
+            .iter()
+            .filter(|(_, node, _, visibility, _)| visibility.get() && node.size() != Vec2::ZERO)
+            .map(|(entity, _, transform, ..)| (entity, center(transform)))
+            .collect();
+        if let Some(entity) = next_focus(step, current, buttons) {
+            focus.0 = Some(entity);
+        }
+    } else if input.activate() {
+        if let Some((entity, .., mut interaction)) = focus.0.and_then(|e| q_buttons.get_mut(e).ok())
+        {
+            *interaction = Interaction::Pressed;
+            *pressed = Some(entity);
+        }
+    }
+}
+
+fn place_focus_ring(
+    focus: Res<Focus>,
+    q_nodes: Query<(&Node, &GlobalTransform)>,
+    mut q_ring: Query<(&mut Style, &mut Visibility), With<FocusRing>>,
+) {
+    let focused = focus.0.and_then(|entity| q_nodes.get(entity).ok());
+    for (mut style, mut visibility) in &mut q_ring {
+        let Some((node, transform)) = focused else {
+            visibility.set_if_neq(Visibility::Hidden);
+            continue;
+        };
+
+        let size = node.size() + 2.0 * RING_WIDTH;
+        let top_left = center(transform) - size / 2.0;
+        // only laid out again once it moves
+        if style.left != Val::Px(top_left.x) || style.top != Val::Px(top_left.y) {
+            style.left = Val::Px(top_left.x);
+            style.top = Val::Px(top_left.y);
+        }
+        if style.width != Val::Px(size.x) || style.height != Val::Px(size.y) {
+            style.width = Val::Px(size.x);
+            style.height = Val::Px(size.y);
+        }
+        visibility.set_if_neq(Visibility::Inherited);
+    }
+}
@@ -0,0 +1,296 @@
+use crate::{
+    i18n::{Localized, Translations},
+    session::SolveSession,
+    simulator::{CurrentCube, MoveQueue, Played, PlayedMoves, PlayedMut, Tutorial},
+    GameState, Puzzle,
+};
+use bevy::prelude::*;
+use cubesim::{parse_scramble, prelude::*, simplify_moves, solve, FaceletCube};
+
+pub struct GuidePlugin;
+
+impl Plugin for GuidePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Guide>()
+            .add_systems(
+                OnEnter(GameState::Playing),
+                guide_ui.run_if(resource_equals(Puzzle::Cube)),
+            )
+            .add_systems(
+                Update,
+                (guide_input, guide_button_system, follow_guide, show_guide)
+                    .chain()
+                    .run_if(resource_equals(Puzzle::Cube)),
+            )
+            .add_systems(OnExit(GameState::Playing), clean_up);
+    }
+}
+
+const FLASH_SECONDS: f32 = 0.6;
+const WARNING_COLOR: Color = Color::rgb(1.0, 0.3, 0.3);
+
+/// A sequence of moves the user is following, such as a solution or a tutorial algorithm.
+#[derive(Resource, Default)]
+struct Guide {
+    moves: Vec<Move>,
+    done: usize,
+    // the moves made since the last expected move, as long as they don't add up to it
+    pending: Vec<Move>,
+    // counts down after a mistake, while the warning is shown
+    flash: Timer,
+}
+
+impl Guide {
+    fn new(moves: Vec<Move>) -> Self {
+        Self { moves, ..default() }
+    }
+
+    fn is_active(&self) -> bool {
+        !self.moves.is_empty()
+    }
+
+    fn expected(&self) -> Option<Move> {
+        self.moves.get(self.done).copied()
+    }
+
+    /// The moves taking the cube away from the guided moves, once simplified.
+    fn deviation(&self) -> Vec<Move> {
+        simplify_moves(&self.pending)
+    }
+
+    /// Whether the moves made can't be the start of the expected move, like a quarter turn
+    /// towards an expected half turn.
+    fn is_off_track(&self) -> bool {
+        match (self.deviation().as_slice(), self.expected()) {
+            ([], _) => false,
+            ([mv], Some(expected)) => {
+                expected.get_variant() != MoveVariant::Double
+                    || mv.with_variant(MoveVariant::Double) != expected
+            }
+            _ => true,
+        }
+    }
+
+    /// Follows a move made on the cube, returning whether it was a mistake.
+    fn follow(&mut self, mv: Move) -> bool {
+        let Some(expected) = self.expected() else {
+            return false;
+        };
+        let was_off_track = self.is_off_track();
+        let previous = self.deviation().len();
+
+        self.pending.push(mv);
+        let deviation = self.deviation();
+        if deviation == [expected] {
+            self.done += 1;
+            self.pending.clear();
+            return false;
+        }
+
+        // moves undoing part of a mistake aren't mistakes themselves
+        self.is_off_track() && (!was_off_track || deviation.len() > previous)
+    }
+
+    /// The moves bringing the cube back to where the guided moves left off.
+    fn undo_moves(&self) -> Vec<Move> {
+        self.deviation()
+            .iter()
+            .rev()
+            .map(|mv| mv.inverse())
+            .collect()
+    }
+
+    fn message(&self, translations: &Translations) -> String {
+        let moves_string = |moves: &[Move]| {
+            moves
+                .iter()
+                .map(|m| m.to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+
+        if !self.is_active() {
+            String::new()
+        } else if self.done == self.moves.len() {
+            translations.get("guide-done")
+        } else if self.is_off_track() {
+            translations.text(
+                Localized::new("guide-off-track").with("moves", moves_string(&self.undo_moves())),
+            )
+        } else {
+            translations.text(
+                Localized::new("guide-next")
+                    .with("moves", moves_string(&self.moves[self.done..]))
+                    .with("done", self.done)
+                    .with("total", self.moves.len()),
+            )
+        }
+    }
+}
+
+#[derive(Component)]
+struct GuideUiRoot;
+
+#[derive(Component)]
+struct GuideText;
+
+#[derive(Component)]
+enum GuideButtonActions {
+    GuideSolution,
+    BackOnTrack,
+}
+
+fn guide_ui(mut commands: Commands, asset_server: Res<AssetServer>, mut guide: ResMut<Guide>) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    *guide = Guide::default();
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(10.0),
+                left: Val::Px(220.0),
+                max_width: Val::Px(600.0),
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            ..default()
+        })
+        .insert(GuideUiRoot)
+        .with_children(|parent| {
+            parent.spawn(NodeBundle::default()).with_children(|parent| {
+                for (text_id, action) in [
+                    ("guide-solution", GuideButtonActions::GuideSolution),
+                    ("guide-back-on-track", GuideButtonActions::BackOnTrack),
+                ] {
+                    parent
+                        .spawn(
+                            TextBundle::from_section(
+                                String::new(),
+                                TextStyle {
+                                    font: font.clone(),
+                                    font_size: 25.0,
+                                    color: Color::WHITE,
+                                },
+                            )
+                            .with_style(Style {
+                                margin: UiRect::right(Val::Px(20.0)),
+                                ..default()
+                            }),
+                        )
+                        .insert(action)
+                        .insert(Localized::new(text_id))
+                        .insert(Interaction::None);
+                }
+            });
+
+            parent
+                .spawn(TextBundle::from_section(
+                    String::new(),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 25.0,
+                        color: Color::WHITE,
+                    },
+                ))
+                .insert(GuideText);
+        });
+}
+
+fn start_solution_guide(current_cube: &CurrentCube, session: &mut SolveSession, guide: &mut Guide) {
+    if current_cube.cube_size() != 3 {
+        warn!(
+            "no solver available to guide a {}x{0}x{0} cube",
+            current_cube.cube_size()
+        );
+        return;
+    }
+
+    match solve(&FaceletCube::new(3).apply_moves(current_cube.moves())) {
+        Some(solution) => {
+            session.mark_assisted();
+            *guide = Guide::new(simplify_moves(&solution));
+        }
+        None => warn!("no solution found to guide"),
+    }
+}
+
+fn back_on_track(guide: &Guide, move_queue: &mut MoveQueue) {
+    // the undo moves already queued would be undone twice
+    if guide.is_off_track() && move_queue.is_empty() {
+        move_queue.extend(guide.undo_moves());
+    }
+}
+
+fn guide_input(
+    keyboard_input: Res<Input<KeyCode>>,
+    tutorial: Res<Tutorial>,
+    mut guide: ResMut<Guide>,
+    mut move_queue: PlayedMut<MoveQueue>,
+) {
+    if keyboard_input.just_pressed(KeyCode::G) {
+        if let Some(alg) = tutorial.alg() {
+            *guide = Guide::new(parse_scramble(alg.to_string()));
+        }
+    }
+    if keyboard_input.just_pressed(KeyCode::Back) {
+        back_on_track(&guide, &mut move_queue);
+    }
+}
+
+fn guide_button_system(
+    interaction_query: Query<(&Interaction, &GuideButtonActions), Changed<Interaction>>,
+    current_cube: Played<CurrentCube>,
+    mut session: PlayedMut<SolveSession>,
+    mut guide: ResMut<Guide>,
+    mut move_queue: PlayedMut<MoveQueue>,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            match button {
+                GuideButtonActions::GuideSolution => {
+                    start_solution_guide(&current_cube, &mut session, &mut guide)
+                }
+                GuideButtonActions::BackOnTrack => back_on_track(&guide, &mut move_queue),
+            }
+        }
+    }
+}
+
+fn follow_guide(mut move_applied: PlayedMoves, mut guide: ResMut<Guide>) {
+    for mv in move_applied.read() {
+        if guide.is_active() && guide.follow(*mv) {
+            guide.flash = Timer::from_seconds(FLASH_SECONDS, TimerMode::Once);
+        }
+    }
+}
+
+fn show_guide(
+    time: Res<Time>,
+    mut guide: ResMut<Guide>,
+    translations: Translations,
+    mut q_text: Query<&mut Text, With<GuideText>>,
+) {
+    // only tick the timer through change detection while a warning is shown
+    if !guide.flash.finished() {
+        guide.flash.tick(time.delta());
+    } else if !guide.is_changed() {
+        return;
+    }
+
+    let color = if guide.flash.finished() {
+        Color::WHITE
+    } else {
+        WARNING_COLOR
+    };
+    for mut text in &mut q_text {
+        text.sections[0].value = guide.message(&translations);
+        text.sections[0].style.color = color;
+    }
+}
+
+fn clean_up(mut commands: Commands, q_ui: Query<Entity, With<GuideUiRoot>>) {
+    for entity in q_ui.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,470 @@
+use crate::{
+    i18n::{Localized, Translations},
+    menu::{MenuButtonAction, MenuState, SelectedOption},
+    solved::{CubeSolvedEvent, PenaltyChanged, SolveMode},
+    storage,
+    theme::{Accent, Theme, ThemeColor},
+};
+use bevy::prelude::*;
+use cubesim::{average_of, Penalty, SolveResult};
+use std::{fmt, str::FromStr, time::Duration};
+
+// One solve per line, appended to after every solve
+pub(crate) const HISTORY_PATH: &str = "history.txt";
+// the number of most recent solves plotted
+const CHART_SOLVES: usize = 30;
+const CHART_WIDTH: f32 = 600.0;
+const CHART_HEIGHT: f32 = 250.0;
+const BAR_COLOR: Color = Color::rgb(0.9, 0.75, 0.2);
+// the DNFs are shown as bars of the whole height
+const DNF_COLOR: Color = Color::rgb(0.9, 0.1, 0.1);
+
+pub struct HistoryPlugin;
+
+impl Plugin for HistoryPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(SolveHistory::load())
+            .init_resource::<HistoryFilter>()
+            .add_systems(Update, (record_solves, amend_penalty).chain())
+            .add_systems(Last, save_history)
+            .add_systems(OnEnter(MenuState::Statistics), statistics_screen_setup)
+            .add_systems(
+                Update,
+                (filter_buttons, draw_chart)
+                    .chain()
+                    .run_if(in_state(MenuState::Statistics)),
+            )
+            .add_systems(OnExit(MenuState::Statistics), clean_up);
+    }
+}
+
+/// A solve kept in the history.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct SolveRecord {
+    pub cube_size: usize,
+    pub mode: SolveMode,
+    pub time: Duration,
+    pub moves: usize,
+    pub penalty: Option<Penalty>,
+}
+
+impl SolveRecord {
+    pub(crate) fn result(&self) -> SolveResult {
+        SolveResult {
+            time: self.time,
+            penalty: self.penalty,
+        }
+    }
+}
+
+/// Written as ``<cube size> <mode> <milliseconds> <moves>``, like ``3 standard 42170 61``, then
+/// the penalty if any, like ``3 standard 42170 61 +2``.
+impl fmt::Display for SolveRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {}",
+            self.cube_size,
+            self.mode,
+            self.time.as_millis(),
+            self.moves
+        )?;
+        match self.penalty {
+            Some(penalty) => write!(f, " {penalty}"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl FromStr for SolveRecord {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let fields: Vec<&str> = s.split_whitespace().collect();
+        let ([cube_size, mode, millis, moves], penalty) = match fields[..] {
+            [cube_size, mode, millis, moves] => ([cube_size, mode, millis, moves], None),
+            [cube_size, mode, millis, moves, penalty] => {
+                ([cube_size, mode, millis, moves], Some(penalty))
+            }
+            _ => return Err(format!("expected 4 or 5 fields, found {}", fields.len())),
+        };
+        let number = |field: &str| {
+            field
+                .parse::<u64>()
+                .map_err(|err| format!("invalid number '{field}': {err}"))
+        };
+
+        Ok(SolveRecord {
+            cube_size: number(cube_size)? as usize,
+            mode: mode.parse()?,
+            time: Duration::from_millis(number(millis)?),
+            moves: number(moves)? as usize,
+            penalty: match penalty {
+                None => None,
+                Some("+2") => Some(Penalty::PlusTwo),
+                Some("DNF") => Some(Penalty::Dnf),
+                Some(penalty) => return Err(format!("unknown penalty '{penalty}'")),
+            },
+        })
+    }
+}
+
+/// Every solve made, oldest first, kept between runs of the app.
+#[derive(Resource, Default, Debug)]
+pub(crate) struct SolveHistory(pub Vec<SolveRecord>);
+
+impl SolveHistory {
+    fn load() -> Self {
+        let text = match storage::read_to_string(HISTORY_PATH) {
+            Ok(text) => text,
+            Err(err) => {
+                debug!("no history loaded from {HISTORY_PATH}: {err}");
+                return Self::default();
+            }
+        };
+
+        let records = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .filter_map(|line| match line.parse() {
+                Ok(record) => Some(record),
+                Err(err) => {
+                    warn!("skipping solve '{line}' in {HISTORY_PATH}: {err}");
+                    None
+                }
+            })
+            .collect();
+        Self(records)
+    }
+
+    fn to_text(&self) -> String {
+        self.0.iter().map(|record| format!("{record}\n")).collect()
+    }
+}
+
+pub(crate) fn record_solves(
+    mut solved_events: EventReader<CubeSolvedEvent>,
+    mut history: ResMut<SolveHistory>,
+) {
+    for solved in solved_events.read() {
+        history.0.push(SolveRecord {
+            cube_size: solved.cube_size,
+            mode: solved.mode,
+            time: solved.report.stats.duration,
+            moves: solved.solution_length,
+            penalty: solved.report.penalty,
+        });
+    }
+}
+
+// A penalty given on the solved screen goes to the solve just recorded
+fn amend_penalty(
+    mut penalty_events: EventReader<PenaltyChanged>,
+    mut history: ResMut<SolveHistory>,
+) {
+    for PenaltyChanged(penalty) in penalty_events.read() {
+        if let Some(record) = history.0.last_mut() {
+            record.penalty = *penalty;
+        }
+    }
+}
+
+fn save_history(history: Res<SolveHistory>) {
+    // the history was just loaded from the file
+    if !history.is_changed() || history.is_added() {
+        return;
+    }
+
+    if let Err(err) = storage::write(HISTORY_PATH, &history.to_text()) {
+        warn!("failed to save the history to {HISTORY_PATH}: {err}");
+    }
+}
+
+/// The solves shown on the statistics screen, ``None`` showing every size or mode.
+#[derive(Resource, Default, Clone, Copy, PartialEq)]
+struct HistoryFilter {
+    cube_size: Option<usize>,
+    mode: Option<SolveMode>,
+}
+
+impl HistoryFilter {
+    fn matches(&self, record: &SolveRecord) -> bool {
+        self.cube_size.is_none_or(|size| size == record.cube_size)
+            && self.mode.is_none_or(|mode| mode == record.mode)
+    }
+}
+
+#[derive(Component)]
+struct OnStatisticsScreen;
+
+#[derive(Component)]
+enum FilterButton {
+    CubeSize(Option<usize>),
+    Mode(Option<SolveMode>),
+}
+
+#[derive(Component)]
+struct Chart;
+
+#[derive(Component)]
+struct SummaryText;
+
+fn statistics_screen_setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    filter: Res<HistoryFilter>,
+    theme: Res<Theme>,
+    accent: Res<Accent>,
+) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    let button_style = Style {
+        height: Val::Px(45.0),
+        margin: UiRect::all(Val::Px(5.0)),
+        padding: UiRect::horizontal(Val::Px(10.0)),
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        ..default()
+    };
+    let text_style = TextStyle {
+        font: font.clone(),
+        font_size: 30.0,
+        color: theme.text(),
+    };
+
+    let size_buttons = [(
+        FilterButton::CubeSize(None),
+        Localized::new("history-all-sizes"),
+    )]
+    .into_iter()
+    .chain((2..=7).map(|size| {
+        let text = Localized::new("history-cube-size").with("size", size);
+        (FilterButton::CubeSize(Some(size)), text)
+    }));
+    let mode_buttons = [(
+        FilterButton::Mode(None),
+        Localized::new("history-all-modes"),
+    )]
+    .into_iter()
+    .chain(SolveMode::ALL.map(|mode| {
+        let text = Localized::new(format!("history-mode-{mode}"));
+        (FilterButton::Mode(Some(mode)), text)
+    }));
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    margin: UiRect::all(Val::Auto),
+                    padding: UiRect::all(Val::Px(20.0)),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: accent.color().into(),
+                ..default()
+            },
+            OnStatisticsScreen,
+            ThemeColor::Accent,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    String::new(),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 50.0,
+                        color: theme.text(),
+                    },
+                ),
+                Localized::new("history-title"),
+            ));
+
+            // Display a row of filter buttons for the sizes, then for the modes
+            for buttons in [size_buttons.collect::<Vec<_>>(), mode_buttons.collect()] {
+                parent.spawn(NodeBundle::default()).with_children(|parent| {
+                    for (button, text) in buttons {
+                        let selected = match button {
+                            FilterButton::CubeSize(size) => filter.cube_size == size,
+                            FilterButton::Mode(mode) => filter.mode == mode,
+                        };
+                        let mut entity = parent.spawn((
+                            ButtonBundle {
+                                style: button_style.clone(),
+                                background_color: theme.button(Interaction::None, false).into(),
+                                ..default()
+                            },
+                            button,
+                        ));
+                        entity.with_children(|parent| {
+                            parent.spawn((
+                                TextBundle::from_section(String::new(), text_style.clone()),
+                                text,
+                            ));
+                        });
+                        if selected {
+                            entity.insert(SelectedOption);
+                        }
+                    }
+                });
+            }
+
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        width: Val::Px(CHART_WIDTH),
+                        height: Val::Px(CHART_HEIGHT),
+                        margin: UiRect::all(Val::Px(10.0)),
+                        align_items: AlignItems::FlexEnd,
+                        ..default()
+                    },
+                    background_color: theme.panel().into(),
+                    ..default()
+                })
+                .insert((Chart, ThemeColor::Panel));
+
+            parent
+                .spawn(TextBundle::from_section(String::new(), text_style.clone()))
+                .insert(SummaryText);
+
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: button_style,
+                        background_color: theme.button(Interaction::None, false).into(),
+                        ..default()
+                    },
+                    MenuButtonAction::BackToMainMenu,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section(String::new(), text_style),
+                        Localized::new("menu-back"),
+                    ));
+                });
+        });
+}
+
+fn filter_buttons(
+    mut commands: Commands,
+    interaction_query: Query<(Entity, &Interaction, &FilterButton), Changed<Interaction>>,
+    mut selected_query: Query<(Entity, &FilterButton, &mut BackgroundColor), With<SelectedOption>>,
+    mut filter: ResMut<HistoryFilter>,
+    theme: Res<Theme>,
+) {
+    for (entity, interaction, button) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        // only one button of each row is selected
+        for (selected, selected_button, mut color) in &mut selected_query {
+            let same_row = matches!(
+                (selected_button, button),
+                (FilterButton::CubeSize(_), FilterButton::CubeSize(_))
+                    | (FilterButton::Mode(_), FilterButton::Mode(_))
+            );
+            if same_row && selected != entity {
+                *color = theme.button(Interaction::None, false).into();
+                commands.entity(selected).remove::<SelectedOption>();
+            }
+        }
+        commands.entity(entity).insert(SelectedOption);
+
+        match *button {
+            FilterButton::CubeSize(size) => filter.cube_size = size,
+            FilterButton::Mode(mode) => filter.mode = mode,
+        }
+    }
+}
+
+fn draw_chart(
+    mut commands: Commands,
+    history: Res<SolveHistory>,
+    filter: Res<HistoryFilter>,
+    q_chart: Query<Entity, With<Chart>>,
+    added: Query<(), Added<Chart>>,
+    translations: Translations,
+    mut q_summary: Query<&mut Text, With<SummaryText>>,
+) {
+    if !history.is_changed() && !filter.is_changed() && added.is_empty() {
+        return;
+    }
+
+    let solves: Vec<&SolveRecord> = history.0.iter().filter(|r| filter.matches(r)).collect();
+    let recent = &solves[solves.len().saturating_sub(CHART_SOLVES)..];
+    let slowest = recent
+        .iter()
+        .filter_map(|r| r.result().counted())
+        .max()
+        .unwrap_or_default();
+    let bar_width = CHART_WIDTH / CHART_SOLVES as f32;
+
+    for chart in &q_chart {
+        commands
+            .entity(chart)
+            .despawn_descendants()
+            .with_children(|parent| {
+                for record in recent {
+                    let (height, color) = match record.result().counted() {
+                        None => (100.0, DNF_COLOR),
+                        Some(_) if slowest.is_zero() => (0.0, BAR_COLOR),
+                        Some(time) => (
+                            time.as_secs_f32() / slowest.as_secs_f32() * 100.0,
+                            BAR_COLOR,
+                        ),
+                    };
+                    parent.spawn(NodeBundle {
+                        style: Style {
+                            width: Val::Px(bar_width - 4.0),
+                            height: Val::Percent(height),
+                            margin: UiRect::horizontal(Val::Px(2.0)),
+                            ..default()
+                        },
+                        background_color: color.into(),
+                        ..default()
+                    });
+                }
+            });
+    }
+
+    // the DNFs are left out of the best and mean times, but count in the averages
+    let counted: Vec<Duration> = solves.iter().filter_map(|r| r.result().counted()).collect();
+    let mut summary = match counted.iter().min() {
+        Some(best) => {
+            let mean = counted.iter().sum::<Duration>() / counted.len() as u32;
+            translations.text(
+                Localized::new("history-summary")
+                    .with("solves", solves.len())
+                    .with("dnfs", solves.len() - counted.len())
+                    .with("best", format!("{:.2}", best.as_secs_f32()))
+                    .with("mean", format!("{:.2}", mean.as_secs_f32())),
+            )
+        }
+        None if !solves.is_empty() => {
+            translations.text(Localized::new("history-all-dnf").with("solves", solves.len()))
+        }
+        None => translations.get("history-no-solves"),
+    };
+    for n in [5, 12] {
+        if solves.len() >= n {
+            let results: Vec<SolveResult> = solves[solves.len() - n..]
+                .iter()
+                .map(|r| r.result())
+                .collect();
+            let average = average_of(&results).map_or("DNF".to_string(), |average| {
+                format!("{:.2} s", average.as_secs_f32())
+            });
+            summary.push_str(&format!(", ao{n} {average}"));
+        }
+    }
+    for mut text in &mut q_summary {
+        text.sections[0].value = summary.clone();
+    }
+}
+
+fn clean_up(mut commands: Commands, q_screen: Query<Entity, With<OnStatisticsScreen>>) {
+    for entity in q_screen.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
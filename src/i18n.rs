@@ -0,0 +1,325 @@
+use crate::settings::Settings;
+use bevy::{asset::LoadState, ecs::system::SystemParam, prelude::*};
+use fluent_bundle::{concurrent::FluentBundle, FluentArgs, FluentResource, FluentValue};
+use std::{fmt, str::FromStr};
+use unic_langid::LanguageIdentifier;
+
+// The font of the languages written in the latin alphabet, and of the missing fonts
+const DEFAULT_FONT: &str = "fonts/FiraSans-Bold.ttf";
+
+pub struct I18nPlugin;
+
+impl Plugin for I18nPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Localization::load())
+            .init_resource::<Language>()
+            .init_resource::<UiFont>()
+            .add_systems(Startup, load_language)
+            .add_systems(
+                Update,
+                (save_language, load_font, fall_back_font, translate_texts).chain(),
+            );
+    }
+}
+
+/// The language of the UI, chosen in the settings.
+#[derive(Resource, Component, Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) enum Language {
+    #[default]
+    English,
+    Chinese,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::Chinese];
+
+    /// The id of the language's name, in the translations.
+    pub fn name_id(self) -> &'static str {
+        match self {
+            Language::English => "language-english",
+            Language::Chinese => "language-chinese",
+        }
+    }
+
+    // The Unicode language identifier, also naming the file of the translations
+    fn id(self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Chinese => "zh-CN",
+        }
+    }
+
+    fn messages(self) -> &'static str {
+        match self {
+            Language::English => include_str!("../assets/i18n/en.ftl"),
+            Language::Chinese => include_str!("../assets/i18n/zh-CN.ftl"),
+        }
+    }
+
+    // Fira Sans has no Chinese characters, the Noto Sans SC subset has those of the translations,
+    // built by `build/fonts/subset-noto-sans-sc.sh`
+    fn font(self) -> &'static str {
+        match self {
+            Language::English => DEFAULT_FONT,
+            Language::Chinese => "fonts/NotoSansSC-Bold.ttf",
+        }
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.id())
+    }
+}
+
+impl FromStr for Language {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Language::ALL
+            .into_iter()
+            .find(|language| language.id() == s)
+            .ok_or_else(|| format!("unknown language '{s}'"))
+    }
+}
+
+/// The translations of the UI's texts, in [Fluent](https://projectfluent.org) files of the
+/// `assets/i18n` folder, one per language.
+#[derive(Resource)]
+pub(crate) struct Localization {
+    // in the order of `Language::ALL`
+    bundles: Vec<FluentBundle<FluentResource>>,
+}
+
+impl Localization {
+    fn load() -> Self {
+        let bundles = Language::ALL
+            .into_iter()
+            .map(|language| {
+                let id: LanguageIdentifier = language.id().parse().expect("valid language id");
+                let mut bundle = FluentBundle::new_concurrent(vec![id]);
+                // the texts are shown by Bevy, not in a page mixing writing directions
+                bundle.set_use_isolating(false);
+
+                let resource = FluentResource::try_new(language.messages().to_string())
+                    .unwrap_or_else(|(resource, errors)| {
+                        warn!("skipping invalid {language} translations: {errors:?}");
+                        resource
+                    });
+                if let Err(errors) = bundle.add_resource(resource) {
+                    warn!("skipping duplicate {language} translations: {errors:?}");
+                }
+                bundle
+            })
+            .collect();
+        Self { bundles }
+    }
+
+    fn message(&self, language: Language, id: &str, args: Option<&FluentArgs>) -> Option<String> {
+        let index = Language::ALL.iter().position(|l| *l == language)?;
+        let bundle = &self.bundles[index];
+        let pattern = bundle.get_message(id)?.value()?;
+        let mut errors = Vec::new();
+        let text = bundle.format_pattern(pattern, args, &mut errors);
+        Some(text.into_owned())
+    }
+
+    /// The text with the given id in a language, falling back to English, and to the id itself
+    /// for a text missing in every language.
+    pub fn text(&self, language: Language, localized: &Localized) -> String {
+        let id = localized.id.as_str();
+        let args = (!localized.args.is_empty()).then(|| {
+            localized
+                .args
+                .iter()
+                .map(|(name, value)| {
+                    let value = match value {
+                        LocalizedArg::Number(n) => FluentValue::from(*n),
+                        LocalizedArg::Text(text) => FluentValue::from(text.as_str()),
+                    };
+                    (*name, value)
+                })
+                .collect::<FluentArgs>()
+        });
+        self.message(language, id, args.as_ref())
+            .or_else(|| self.message(Language::English, id, args.as_ref()))
+            .unwrap_or_else(|| {
+                warn!("no translation for '{id}'");
+                id.to_string()
+            })
+    }
+}
+
+/// A text of the UI, translated into the chosen language, and written in its font.
+#[derive(Component, Clone, Debug, PartialEq)]
+pub(crate) struct Localized {
+    id: String,
+    args: Vec<(&'static str, LocalizedArg)>,
+}
+
+impl Localized {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            args: Vec::new(),
+        }
+    }
+
+    /// Gives a value to a variable of the text.
+    pub fn with(mut self, name: &'static str, value: impl Into<LocalizedArg>) -> Self {
+        self.args.push((name, value.into()));
+        self
+    }
+}
+
+/// The value of a variable of a text, the numbers choosing the plural forms of the languages.
+/// Numbers shown with a given count of decimals are formatted before, as texts.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum LocalizedArg {
+    Number(f64),
+    Text(String),
+}
+
+macro_rules! number_args {
+    ($($number:ty),*) => {
+        $(impl From<$number> for LocalizedArg {
+            fn from(n: $number) -> Self {
+                LocalizedArg::Number(n as f64)
+            }
+        })*
+    };
+}
+
+number_args!(u8, u16, u32, u64, usize, i32, i64);
+
+impl From<String> for LocalizedArg {
+    fn from(text: String) -> Self {
+        LocalizedArg::Text(text)
+    }
+}
+
+impl From<&str> for LocalizedArg {
+    fn from(text: &str) -> Self {
+        LocalizedArg::Text(text.to_string())
+    }
+}
+
+/// The texts of the UI in the chosen language, for the texts put together by the systems
+/// rather than kept up to date by a [`Localized`] component.
+#[derive(SystemParam)]
+pub(crate) struct Translations<'w> {
+    language: Res<'w, Language>,
+    localization: Res<'w, Localization>,
+}
+
+impl Translations<'_> {
+    pub fn text(&self, localized: Localized) -> String {
+        self.localization.text(*self.language, &localized)
+    }
+
+    /// The text with the given id, without any variable.
+    pub fn get(&self, id: &str) -> String {
+        self.text(Localized::new(id))
+    }
+}
+
+/// The font of the chosen language.
+#[derive(Resource, Default)]
+struct UiFont(Handle<Font>);
+
+fn load_language(settings: Res<Settings>, mut language: ResMut<Language>) {
+    language.set_if_neq(settings.language);
+}
+
+fn save_language(language: Res<Language>, mut settings: ResMut<Settings>) {
+    if language.is_changed() && settings.language != *language {
+        settings.language = *language;
+    }
+}
+
+fn load_font(language: Res<Language>, asset_server: Res<AssetServer>, mut font: ResMut<UiFont>) {
+    if language.is_changed() {
+        font.0 = asset_server.load(language.font());
+    }
+}
+
+// Keeps the texts readable, or at least their latin characters, without the language's font
+fn fall_back_font(
+    language: Res<Language>,
+    asset_server: Res<AssetServer>,
+    mut font: ResMut<UiFont>,
+) {
+    if language.font() == DEFAULT_FONT
+        || asset_server.get_load_state(font.0.id()) != Some(LoadState::Failed)
+    {
+        return;
+    }
+
+    warn!(
+        "no font for the {} texts, add assets/{} to show them",
+        *language,
+        language.font()
+    );
+    font.0 = asset_server.load(DEFAULT_FONT);
+}
+
+fn translate_texts(
+    language: Res<Language>,
+    localization: Res<Localization>,
+    font: Res<UiFont>,
+    mut q_texts: Query<(Ref<Localized>, &mut Text)>,
+) {
+    let changed = language.is_changed() || font.is_changed();
+    for (localized, mut text) in &mut q_texts {
+        if !changed && !localized.is_changed() {
+            continue;
+        }
+        let section = &mut text.sections[0];
+        section.value = localization.text(*language, &localized);
+        section.style.font = font.0.clone();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The ids of the messages of a translation file
+    fn message_ids(messages: &str) -> Vec<&str> {
+        messages
+            .lines()
+            .filter_map(|line| line.split_once(" ="))
+            .map(|(id, _)| id)
+            .filter(|id| id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'))
+            .collect()
+    }
+
+    #[test]
+    fn every_language_has_every_english_text() {
+        let localization = Localization::load();
+        let ids = message_ids(Language::English.messages());
+        assert!(ids.contains(&"menu-back"));
+
+        for language in Language::ALL {
+            let index = Language::ALL.iter().position(|l| *l == language).unwrap();
+            let bundle = &localization.bundles[index];
+            for id in &ids {
+                assert!(bundle.has_message(id), "no {language} text for '{id}'");
+            }
+            assert_eq!(message_ids(language.messages()).len(), ids.len());
+        }
+    }
+
+    #[test]
+    fn texts_take_their_variables() {
+        let localization = Localization::load();
+        for language in Language::ALL {
+            let text = localization.text(language, &Localized::new("queue-more").with("moves", 12));
+            assert!(text.contains("12"), "{language}: {text}");
+        }
+        assert_eq!(
+            localization.text(Language::Chinese, &Localized::new("no-such-text")),
+            "no-such-text"
+        );
+    }
+}
@@ -0,0 +1,16 @@
+use bevy::prelude::*;
+use bevy_cubesim::{FaceColors, RotateSpeed};
+use bevy_inspector_egui::quick::{ResourceInspectorPlugin, WorldInspectorPlugin};
+
+/// Inspector panels for contributors, built with the `dev` feature. The world inspector lists
+/// every entity and resource, the queued moves of each cube being on its root, while the
+/// rotation speed and the sticker colors get panels of their own to tweak them while playing.
+pub struct InspectorPlugin;
+
+impl Plugin for InspectorPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(WorldInspectorPlugin::new())
+            .add_plugins(ResourceInspectorPlugin::<RotateSpeed>::default())
+            .add_plugins(ResourceInspectorPlugin::<FaceColors>::default());
+    }
+}
@@ -0,0 +1,125 @@
+use crate::{
+    simulator::{CurrentCube, Played},
+    GameState, Puzzle,
+};
+use bevy::prelude::*;
+use cubesim::prelude::*;
+
+const TOGGLE_KEY: KeyCode = KeyCode::N;
+// how far out of the cube's faces the labels float
+const LABEL_DISTANCE: f32 = 0.8;
+
+pub struct LabelPlugin;
+
+impl Plugin for LabelPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ShowLabels>()
+            .add_systems(
+                OnEnter(GameState::Playing),
+                label_ui.run_if(resource_equals(Puzzle::Cube)),
+            )
+            .add_systems(
+                Update,
+                (toggle_labels, place_labels)
+                    .chain()
+                    .run_if(resource_equals(Puzzle::Cube)),
+            )
+            .add_systems(OnExit(GameState::Playing), clean_up);
+    }
+}
+
+/// Whether the faces of the cube are labelled with their letter, toggled with `N`.
+#[derive(Resource, Clone, Copy, Eq, PartialEq, Debug)]
+struct ShowLabels(bool);
+
+impl Default for ShowLabels {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+// The letter of a face, floating over it. Face turns and rotations are made around fixed
+// axes, so a face keeps its letter whichever stickers a rotation brings to it.
+#[derive(Component)]
+struct FaceLabel(Face);
+
+fn face_normal(face: Face) -> Vec3 {
+    match face {
+        Face::U => Vec3::Y,
+        Face::D => Vec3::NEG_Y,
+        Face::R => Vec3::X,
+        Face::L => Vec3::NEG_X,
+        Face::F => Vec3::Z,
+        Face::B => Vec3::NEG_Z,
+        Face::X => unreachable!("masked stickers aren't on a face"),
+    }
+}
+
+fn label_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+
+    for face in [Face::U, Face::R, Face::F, Face::D, Face::L, Face::B] {
+        commands
+            .spawn(
+                TextBundle::from_section(
+                    face.to_string(),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 40.0,
+                        color: Color::WHITE,
+                    },
+                )
+                .with_style(Style {
+                    position_type: PositionType::Absolute,
+                    ..default()
+                }),
+            )
+            .insert(FaceLabel(face));
+    }
+}
+
+fn toggle_labels(keyboard_input: Res<Input<KeyCode>>, mut show_labels: ResMut<ShowLabels>) {
+    if keyboard_input.just_pressed(TOGGLE_KEY) {
+        show_labels.0 = !show_labels.0;
+    }
+}
+
+// Projects the labels onto the screen, hiding those of the faces turned away from the camera
+fn place_labels(
+    show_labels: Res<ShowLabels>,
+    current_cube: Played<CurrentCube>,
+    q_camera: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    mut q_labels: Query<(&FaceLabel, &Node, &mut Style, &mut Visibility)>,
+    ui_scale: Res<UiScale>,
+) {
+    let Ok((camera, camera_transform)) = q_camera.get_single() else {
+        return;
+    };
+
+    for (label, node, mut style, mut visibility) in &mut q_labels {
+        let normal = face_normal(label.0);
+        let position = normal * (current_cube.cube_size() as f32 / 2.0 + LABEL_DISTANCE);
+        let facing = normal.dot(camera_transform.translation() - position) > 0.0;
+        let viewport = camera.world_to_viewport(camera_transform, position);
+
+        match viewport {
+            Some(viewport) if show_labels.0 && facing => {
+                // centered on the projected point, the node's size being in the UI's scaled pixels
+                let size = node.size();
+                let scale = ui_scale.0 as f32;
+                style.left = Val::Px(viewport.x / scale - size.x / 2.0);
+                style.top = Val::Px(viewport.y / scale - size.y / 2.0);
+                visibility.set_if_neq(Visibility::Inherited);
+            }
+            _ => {
+                visibility.set_if_neq(Visibility::Hidden);
+            }
+        }
+    }
+}
+
+fn clean_up(mut commands: Commands, q_labels: Query<Entity, With<FaceLabel>>) {
+    for entity in q_labels.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
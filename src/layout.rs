@@ -0,0 +1,142 @@
+use crate::simulator::PlayButtonActions;
+use bevy::{
+    prelude::*,
+    window::{PrimaryWindow, WindowMode},
+};
+
+// The window size the UI was designed for
+const DESIGN_WIDTH: f32 = 1280.0;
+const DESIGN_HEIGHT: f32 = 720.0;
+// Below this, the text can no longer be read
+const MIN_UI_SCALE: f32 = 0.5;
+
+pub struct LayoutPlugin;
+
+impl Plugin for LayoutPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Layout>()
+            .add_systems(Update, (update_layout, apply_layout).chain())
+            .add_systems(Update, fullscreen_button);
+    }
+}
+
+/// How the game's panels are laid out, following the window's shape.
+#[derive(Resource, Default, Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Layout {
+    /// The buttons on the left of the cube and the moves on its right.
+    #[default]
+    Wide,
+    /// The buttons above the cube and the moves below it, for portrait phones.
+    Compact,
+}
+
+/// A node of the game's UI moved around by the [`Layout`].
+#[derive(Component, Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum LayoutNode {
+    /// The node holding the game's panels.
+    Root,
+    /// The panel of the buttons.
+    Buttons,
+    /// The list of the buttons, in the panel.
+    ButtonList,
+    /// The panel of the moves made.
+    Moves,
+    /// The instructions of the tutorial.
+    Tutorial,
+}
+
+impl LayoutNode {
+    fn apply(self, layout: Layout, style: &mut Style) {
+        match (self, layout) {
+            (LayoutNode::Root, Layout::Wide) => style.flex_direction = FlexDirection::Row,
+            (LayoutNode::Root, Layout::Compact) => style.flex_direction = FlexDirection::Column,
+            (LayoutNode::Buttons, Layout::Wide) => {
+                style.width = Val::Px(200.0);
+                style.height = Val::Auto;
+            }
+            (LayoutNode::Buttons | LayoutNode::Moves, Layout::Compact) => {
+                style.width = Val::Percent(100.0);
+                style.height = Val::Auto;
+            }
+            (LayoutNode::ButtonList, Layout::Wide) => {
+                style.flex_direction = FlexDirection::Column;
+                style.flex_wrap = FlexWrap::NoWrap;
+            }
+            (LayoutNode::ButtonList, Layout::Compact) => {
+                style.flex_direction = FlexDirection::Row;
+                style.flex_wrap = FlexWrap::Wrap;
+            }
+            (LayoutNode::Moves, Layout::Wide) => {
+                style.width = Val::Px(200.0);
+                style.height = Val::Percent(100.0);
+            }
+            (LayoutNode::Tutorial, Layout::Wide) => {
+                style.bottom = Val::Px(50.0);
+                style.left = Val::Px(220.0);
+            }
+            // above the moves
+            (LayoutNode::Tutorial, Layout::Compact) => {
+                style.bottom = Val::Percent(30.0);
+                style.left = Val::Px(10.0);
+            }
+        }
+    }
+}
+
+// Switches between the full screen and a window, from the button of any puzzle's game
+fn fullscreen_button(
+    q_buttons: Query<(&Interaction, &PlayButtonActions), Changed<Interaction>>,
+    mut q_window: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    let pressed = q_buttons.iter().any(|(interaction, button)| {
+        *interaction == Interaction::Pressed && *button == PlayButtonActions::Fullscreen
+    });
+    if !pressed {
+        return;
+    }
+    if let Ok(mut window) = q_window.get_single_mut() {
+        window.mode = match window.mode {
+            WindowMode::Windowed => WindowMode::BorderlessFullscreen,
+            _ => WindowMode::Windowed,
+        };
+    }
+}
+
+// Scales the UI down to fit small windows, such as the canvas of the web app on phones
+fn update_layout(
+    q_window: Query<&Window, (With<PrimaryWindow>, Changed<Window>)>,
+    mut layout: ResMut<Layout>,
+    mut ui_scale: ResMut<UiScale>,
+) {
+    let Ok(window) = q_window.get_single() else {
+        return;
+    };
+    let (width, height) = (window.width(), window.height());
+    if width <= 0.0 || height <= 0.0 {
+        // minimized
+        return;
+    }
+
+    let (new_layout, scale) = if width < height {
+        (Layout::Compact, width / DESIGN_HEIGHT)
+    } else {
+        (
+            Layout::Wide,
+            (width / DESIGN_WIDTH).min(height / DESIGN_HEIGHT),
+        )
+    };
+    layout.set_if_neq(new_layout);
+    let scale = scale.clamp(MIN_UI_SCALE, 1.0) as f64;
+    if ui_scale.0 != scale {
+        ui_scale.0 = scale;
+    }
+}
+
+fn apply_layout(layout: Res<Layout>, mut q_nodes: Query<(Ref<LayoutNode>, &mut Style)>) {
+    for (node, mut style) in &mut q_nodes {
+        // the nodes are spawned in the wide layout
+        if layout.is_changed() || node.is_added() {
+            node.apply(*layout, &mut style);
+        }
+    }
+}
@@ -1,9 +1,120 @@
-use crate::{menu::MenuPlugin, player::PlayerPlugin, simulator::SimulatorPlugin};
+use crate::{
+    achievements::AchievementsPlugin,
+    assistant::AssistantPlugin,
+    callout::CalloutPlugin,
+    compare::ComparePlugin,
+    confirm::ConfirmPlugin,
+    console::ConsolePlugin,
+    diagnostics::DiagnosticsPlugin,
+    eo_trainer::EoTrainerPlugin,
+    export::ExportPlugin,
+    focus::FocusPlugin,
+    guide::GuidePlugin,
+    history::HistoryPlugin,
+    i18n::I18nPlugin,
+    labels::LabelPlugin,
+    layout::LayoutPlugin,
+    lighting::{Background, Ground, LightingPlugin, Look},
+    macros::MacroPlugin,
+    marathon::MarathonPlugin,
+    megaminx::MegaminxPlugin,
+    menu::MenuPlugin,
+    mesh::MeshQuality,
+    notation_quiz::NotationQuizPlugin,
+    playback::PlaybackPlugin,
+    player::PlayerPlugin,
+    profile::ProfilePlugin,
+    queue::QueuePlugin,
+    race::RacePlugin,
+    reconstruction::ReconstructionPlugin,
+    relay::RelayPlugin,
+    robot::RobotPlugin,
+    scene::ScenePlugin,
+    scramble::ScramblePlugin,
+    screenshot::ScreenshotPlugin,
+    search::SearchOverlayPlugin,
+    session::SessionPlugin,
+    settings::SettingsPlugin,
+    share::SharePlugin,
+    showcase::ShowcasePlugin,
+    simulator::SimulatorPlugin,
+    solved::SolvedPlugin,
+    spectate::SpectatePlugin,
+    stream::StreamPlugin,
+    target::TargetPlugin,
+    theme::ThemePlugin,
+    timeline::TimelinePlugin,
+    timer::TimerPlugin,
+    views::ViewPlugin,
+    warmup::WarmupPlugin,
+    webcam::WebcamPlugin,
+};
 use bevy::prelude::*;
+use std::ops::RangeInclusive;
 
+pub use crate::{race::RaceOptions, spectate::SpectateMode, stream::StreamMode};
+
+mod achievements;
+mod assistant;
+mod callout;
+mod compare;
+mod confirm;
+mod console;
+mod diagnostics;
+mod eo_trainer;
+mod export;
+mod focus;
+mod guide;
+mod history;
+mod i18n;
+#[cfg(feature = "dev")]
+mod inspector;
+mod labels;
+mod layout;
+mod lighting;
+mod macros;
+mod marathon;
+mod megaminx;
 mod menu;
+mod mesh;
+mod notation_quiz;
+mod playback;
 mod player;
+mod profile;
+mod queue;
+mod race;
+#[cfg(all(feature = "online", not(target_arch = "wasm32")))]
+mod race_server;
+mod rating;
+mod reconstruction;
+mod relay;
+#[cfg(all(feature = "remote", not(target_arch = "wasm32")))]
+mod remote;
+mod robot;
+mod scene;
+mod scramble;
+mod screenshot;
+#[cfg(feature = "scripting")]
+mod scripting;
+mod search;
+mod session;
+mod settings;
+mod share;
+mod showcase;
 mod simulator;
+mod solved;
+mod spectate;
+mod stackmat;
+mod storage;
+mod stream;
+mod tables;
+mod target;
+mod theme;
+mod timeline;
+mod timer;
+mod views;
+mod warmup;
+mod webcam;
 
 #[derive(Clone, Eq, PartialEq, Debug, Hash, Default, States)]
 #[allow(dead_code)]
@@ -12,6 +123,33 @@ enum GameState {
     Menu,
     Playing,
     Solved,
+    // left right away for a new game, as when the console changes the size of the cube
+    Restarting,
+}
+
+// The puzzle played when starting a new game, chosen from the settings menu
+#[derive(Resource, Component, Reflect, Clone, Copy, Eq, PartialEq, Debug, Default)]
+#[reflect(Resource)]
+enum Puzzle {
+    #[default]
+    Cube,
+    Megaminx,
+}
+
+// The number of layers of the cube puzzle, chosen from the settings menu
+#[derive(Resource, Component, Reflect, Clone, Copy, Eq, PartialEq, Debug)]
+#[reflect(Resource)]
+struct CubeSizeSetting(usize);
+
+impl CubeSizeSetting {
+    // the sizes offered by the settings menu
+    const SIZES: RangeInclusive<usize> = 2..=7;
+}
+
+impl Default for CubeSizeSetting {
+    fn default() -> Self {
+        Self(3)
+    }
 }
 
 pub struct RubiksPlugin;
@@ -19,14 +157,72 @@ pub struct RubiksPlugin;
 impl Plugin for RubiksPlugin {
     fn build(&self, app: &mut App) {
         app.add_state::<GameState>()
+            .init_resource::<Puzzle>()
+            .init_resource::<CubeSizeSetting>()
+            .init_resource::<MeshQuality>()
+            .init_resource::<Look>()
+            .init_resource::<Background>()
+            .init_resource::<Ground>()
+            .register_type::<Puzzle>()
+            .register_type::<CubeSizeSetting>()
+            .register_type::<MeshQuality>()
+            .register_type::<Look>()
+            .register_type::<Background>()
+            .register_type::<Ground>()
+            .add_plugins(SettingsPlugin)
+            .add_plugins(ScramblePlugin)
+            .add_plugins(I18nPlugin)
+            .add_plugins(ThemePlugin)
             .add_plugins(PlayerPlugin)
+            .add_plugins(ViewPlugin)
+            .add_plugins(LabelPlugin)
+            .add_plugins(TargetPlugin)
+            .add_plugins(LayoutPlugin)
+            .add_plugins(LightingPlugin)
             .add_plugins(SimulatorPlugin)
-            .add_plugins(MenuPlugin);
+            .add_plugins(SessionPlugin)
+            .add_plugins(WarmupPlugin)
+            .add_plugins(ConfirmPlugin)
+            .add_plugins(QueuePlugin)
+            .add_plugins(PlaybackPlugin)
+            .add_plugins(TimelinePlugin)
+            .add_plugins(ShowcasePlugin)
+            .add_plugins(RelayPlugin)
+            .add_plugins(AssistantPlugin)
+            .add_plugins(CalloutPlugin)
+            .add_plugins(ComparePlugin)
+            .add_plugins(SearchOverlayPlugin)
+            .add_plugins(WebcamPlugin)
+            .add_plugins(RobotPlugin)
+            .add_plugins(ScenePlugin)
+            .add_plugins(SharePlugin)
+            .add_plugins(ScreenshotPlugin)
+            .add_plugins(StreamPlugin)
+            .add_plugins(SpectatePlugin)
+            .add_plugins(ExportPlugin)
+            .add_plugins(GuidePlugin)
+            .add_plugins(MacroPlugin)
+            .add_plugins(SolvedPlugin)
+            .add_plugins(HistoryPlugin)
+            .add_plugins(EoTrainerPlugin)
+            .add_plugins(NotationQuizPlugin)
+            .add_plugins(MarathonPlugin)
+            .add_plugins(TimerPlugin)
+            .add_plugins(ReconstructionPlugin)
+            .add_plugins(RacePlugin)
+            .add_plugins(ProfilePlugin)
+            .add_plugins(AchievementsPlugin)
+            .add_plugins(MegaminxPlugin)
+            .add_plugins(MenuPlugin)
+            .add_plugins(ConsolePlugin)
+            .add_plugins(FocusPlugin)
+            .add_plugins(DiagnosticsPlugin);
 
-        #[cfg(debug_assertions)]
-        {
-            // app.add_plugin(bevy::diagnostic::FrameTimeDiagnosticsPlugin::default());
-            //     .add_plugin(LogDiagnosticsPlugin::default());
-        }
+        #[cfg(feature = "dev")]
+        app.add_plugins(inspector::InspectorPlugin);
+        #[cfg(feature = "scripting")]
+        app.add_plugins(scripting::ScriptingPlugin);
+        #[cfg(all(feature = "remote", not(target_arch = "wasm32")))]
+        app.add_plugins(remote::RemotePlugin);
     }
 }
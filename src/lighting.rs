@@ -0,0 +1,274 @@
+use crate::{megaminx::MINX_SCALE, stream::StreamMode, CubeSizeSetting, GameState, Puzzle};
+use bevy::{
+    core_pipeline::Skybox,
+    ecs::system::SystemParam,
+    pbr::{CascadeShadowConfigBuilder, EnvironmentMapLight},
+    prelude::*,
+    render::render_resource::{
+        Extent3d, TextureDimension, TextureFormat, TextureViewDescriptor, TextureViewDimension,
+    },
+};
+
+// the size of each face of the generated environment map
+const SKY_SIZE: u32 = 16;
+const SKY_COLOR: Color = Color::rgb(0.85, 0.9, 1.0);
+const GROUND_COLOR: Color = Color::rgb(0.25, 0.22, 0.2);
+const DUSK_SKY_COLOR: Color = Color::rgb(0.95, 0.55, 0.35);
+const DUSK_GROUND_COLOR: Color = Color::rgb(0.12, 0.08, 0.2);
+const GROUND_PLANE_COLOR: Color = Color::rgb(0.4, 0.4, 0.42);
+// the gap between the puzzle and the ground plane
+const GROUND_GAP: f32 = 0.75;
+
+pub struct LightingPlugin;
+
+impl Plugin for LightingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Playing), (lighting_setup, ground_setup))
+            .add_systems(OnExit(GameState::Playing), clean_up);
+    }
+}
+
+/// How the puzzles are lit, chosen from the display settings.
+#[derive(Resource, Component, Reflect, Clone, Copy, Eq, PartialEq, Debug, Default)]
+#[reflect(Resource)]
+pub(crate) enum Look {
+    /// Plain colors, without lights or shadows.
+    #[default]
+    Flat,
+    /// Lit by the sun and the sky, the pieces casting soft shadows.
+    Realistic,
+    /// Like `Realistic`, with glossy stickers reflecting the sky.
+    Glossy,
+}
+
+impl Look {
+    pub(crate) const ALL: [Look; 3] = [Look::Flat, Look::Realistic, Look::Glossy];
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Look::Flat => "Flat",
+            Look::Realistic => "Realistic",
+            Look::Glossy => "Glossy",
+        }
+    }
+
+    /// The material of a piece's plastic body.
+    pub(crate) fn body_material(self, color: Color) -> StandardMaterial {
+        StandardMaterial {
+            base_color: color,
+            unlit: self == Look::Flat,
+            perceptual_roughness: 0.6,
+            ..default()
+        }
+    }
+
+    /// The material of a sticker.
+    pub(crate) fn sticker_material(self, color: Color) -> StandardMaterial {
+        StandardMaterial {
+            base_color: color,
+            unlit: self == Look::Flat,
+            perceptual_roughness: if self == Look::Glossy { 0.15 } else { 0.45 },
+            reflectance: if self == Look::Glossy { 0.6 } else { 0.4 },
+            ..default()
+        }
+    }
+}
+
+/// What is shown behind the puzzle, chosen from the display settings.
+#[derive(Resource, Component, Reflect, Clone, Copy, Eq, PartialEq, Debug, Default)]
+#[reflect(Resource)]
+pub(crate) enum Background {
+    /// The plain clear color.
+    #[default]
+    Plain,
+    /// A skybox fading from the ground to a blue sky.
+    Sky,
+    /// A skybox fading from a dark ground to an orange sky.
+    Dusk,
+}
+
+impl Background {
+    pub(crate) const ALL: [Background; 3] = [Background::Plain, Background::Sky, Background::Dusk];
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Background::Plain => "Plain",
+            Background::Sky => "Sky",
+            Background::Dusk => "Dusk",
+        }
+    }
+
+    // The colors of the sky's gradient, from the ground up, also reflected by glossy stickers
+    fn gradient(self) -> (Color, Color) {
+        match self {
+            Background::Plain | Background::Sky => (GROUND_COLOR, SKY_COLOR),
+            Background::Dusk => (DUSK_GROUND_COLOR, DUSK_SKY_COLOR),
+        }
+    }
+}
+
+/// Whether a ground plane is shown below the puzzle, catching its shadow when it's lit.
+#[derive(Resource, Component, Reflect, Clone, Copy, Eq, PartialEq, Debug, Default)]
+#[reflect(Resource)]
+pub(crate) enum Ground {
+    #[default]
+    Hidden,
+    Shown,
+}
+
+impl Ground {
+    pub(crate) const ALL: [Ground; 2] = [Ground::Hidden, Ground::Shown];
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Ground::Hidden => "Off",
+            Ground::Shown => "On",
+        }
+    }
+}
+
+#[derive(SystemParam)]
+struct DisplaySettings<'w> {
+    look: Res<'w, Look>,
+    background: Res<'w, Background>,
+    ground: Res<'w, Ground>,
+    stream: Res<'w, StreamMode>,
+}
+
+#[derive(Component)]
+struct Sun;
+
+#[derive(Component)]
+struct GroundPlane;
+
+// A gradient from the ground to the sky, as a cubemap for the skybox and environment map light
+fn sky_cubemap((ground, sky): (Color, Color)) -> Image {
+    let mut data = Vec::new();
+    // the faces of a cubemap are +X, -X, +Y, -Y, +Z and -Z
+    for face in 0..6 {
+        for y in 0..SKY_SIZE {
+            // how much a texel looks up, from -1 to 1
+            let up = match face {
+                2 => 1.0,
+                3 => -1.0,
+                _ => 1.0 - 2.0 * (y as f32 + 0.5) / SKY_SIZE as f32,
+            };
+            let t = (up + 1.0) / 2.0;
+            let color = ground * (1.0 - t) + sky * t;
+            for _ in 0..SKY_SIZE {
+                data.extend(color.as_rgba_u8());
+            }
+        }
+    }
+
+    let mut image = Image::new(
+        Extent3d {
+            width: SKY_SIZE,
+            height: SKY_SIZE,
+            depth_or_array_layers: 6,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+    );
+    image.texture_view_descriptor = Some(TextureViewDescriptor {
+        dimension: Some(TextureViewDimension::Cube),
+        ..default()
+    });
+    image
+}
+
+fn lighting_setup(
+    mut commands: Commands,
+    settings: DisplaySettings,
+    mut images: ResMut<Assets<Image>>,
+    q_camera: Query<Entity, With<Camera3d>>,
+) {
+    let look = *settings.look;
+    let background = *settings.background;
+    let sky = (background != Background::Plain || look == Look::Glossy)
+        .then(|| images.add(sky_cubemap(background.gradient())));
+
+    for camera in &q_camera {
+        let mut camera = commands.entity(camera);
+        camera.remove::<(EnvironmentMapLight, Skybox)>();
+        let Some(sky) = &sky else {
+            continue;
+        };
+        // the background is keyed out while streaming
+        if background != Background::Plain && *settings.stream == StreamMode::Off {
+            camera.insert(Skybox(sky.clone()));
+        }
+        if look == Look::Glossy {
+            camera.insert(EnvironmentMapLight {
+                diffuse_map: sky.clone(),
+                specular_map: sky.clone(),
+            });
+        }
+    }
+    if look == Look::Flat {
+        return;
+    }
+
+    commands.insert_resource(AmbientLight {
+        color: background.gradient().1,
+        brightness: 0.3,
+    });
+    // the shadows are filtered, so their edges are soft
+    commands.spawn((
+        DirectionalLightBundle {
+            directional_light: DirectionalLight {
+                illuminance: 12000.0,
+                shadows_enabled: true,
+                ..default()
+            },
+            transform: Transform::from_xyz(4.0, 10.0, 2.0).looking_at(Vec3::ZERO, Vec3::Y),
+            // the puzzle is small and close to the camera
+            cascade_shadow_config: CascadeShadowConfigBuilder {
+                first_cascade_far_bound: 10.0,
+                maximum_distance: 40.0,
+                ..default()
+            }
+            .build(),
+            ..default()
+        },
+        Sun,
+    ));
+}
+
+fn ground_setup(
+    mut commands: Commands,
+    settings: DisplaySettings,
+    puzzle: Res<Puzzle>,
+    cube_size: Res<CubeSizeSetting>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    if *settings.ground == Ground::Hidden || *settings.stream != StreamMode::Off {
+        return;
+    }
+
+    // how far the puzzle reaches below its center
+    let extent = match *puzzle {
+        Puzzle::Cube => cube_size.0 as f32 / 2.0,
+        // the corners of the dodecahedron are further out than its faces
+        Puzzle::Megaminx => MINX_SCALE * 1.3,
+    };
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Plane::from_size(60.0))),
+            material: materials.add(settings.look.body_material(GROUND_PLANE_COLOR)),
+            transform: Transform::from_xyz(0.0, -extent - GROUND_GAP, 0.0),
+            ..default()
+        },
+        GroundPlane,
+    ));
+}
+
+type Scenery = Or<(With<Sun>, With<GroundPlane>)>;
+
+fn clean_up(mut commands: Commands, q_scenery: Query<Entity, Scenery>) {
+    for entity in q_scenery.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,358 @@
+use crate::{
+    i18n::Localized,
+    settings::Settings,
+    simulator::{MoveQueue, PlayedMoves, PlayedMut},
+    GameState, Puzzle,
+};
+use bevy::prelude::*;
+use cubesim::{parse_algorithm, prelude::*};
+use std::{fmt, str::FromStr};
+
+pub struct MacroPlugin;
+
+impl Plugin for MacroPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Recording>()
+            .add_systems(
+                OnEnter(GameState::Playing),
+                macro_ui.run_if(resource_equals(Puzzle::Cube)),
+            )
+            .add_systems(
+                Update,
+                (
+                    record_input,
+                    macro_button_system,
+                    record_moves,
+                    play_macros,
+                    show_macros,
+                )
+                    .chain()
+                    .run_if(resource_equals(Puzzle::Cube)),
+            )
+            .add_systems(OnExit(GameState::Playing), clean_up);
+    }
+}
+
+const RECORD_KEY: KeyCode = KeyCode::M;
+
+const FUNCTION_KEYS: [(KeyCode, &str); 12] = [
+    (KeyCode::F1, "F1"),
+    (KeyCode::F2, "F2"),
+    (KeyCode::F3, "F3"),
+    (KeyCode::F4, "F4"),
+    (KeyCode::F5, "F5"),
+    (KeyCode::F6, "F6"),
+    (KeyCode::F7, "F7"),
+    (KeyCode::F8, "F8"),
+    (KeyCode::F9, "F9"),
+    (KeyCode::F10, "F10"),
+    (KeyCode::F11, "F11"),
+    (KeyCode::F12, "F12"),
+];
+
+/// A function key, held with some modifiers, playing a macro.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub(crate) struct KeyChord {
+    ctrl: bool,
+    shift: bool,
+    key: KeyCode,
+}
+
+impl KeyChord {
+    // Function keys alone first, then with shift and with ctrl
+    fn all() -> impl Iterator<Item = KeyChord> {
+        [(false, false), (false, true), (true, false)]
+            .into_iter()
+            .flat_map(|(ctrl, shift)| {
+                FUNCTION_KEYS
+                    .iter()
+                    .map(move |&(key, _)| KeyChord { ctrl, shift, key })
+            })
+    }
+
+    fn just_pressed(&self, keyboard_input: &Input<KeyCode>) -> bool {
+        let ctrl = keyboard_input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+        let shift = keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+
+        keyboard_input.just_pressed(self.key) && self.ctrl == ctrl && self.shift == shift
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ctrl {
+            write!(f, "Ctrl+")?;
+        }
+        if self.shift {
+            write!(f, "Shift+")?;
+        }
+        let (_, name) = FUNCTION_KEYS
+            .iter()
+            .find(|(key, _)| *key == self.key)
+            .expect("chords only use function keys");
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for KeyChord {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chord = s.trim();
+        let ctrl = chord
+            .strip_prefix("Ctrl+")
+            .map(|rest| chord = rest)
+            .is_some();
+        let shift = chord
+            .strip_prefix("Shift+")
+            .map(|rest| chord = rest)
+            .is_some();
+
+        FUNCTION_KEYS
+            .iter()
+            .find(|(_, name)| *name == chord)
+            .map(|&(key, _)| KeyChord { ctrl, shift, key })
+            .ok_or_else(|| format!("'{s}' is not a function key chord"))
+    }
+}
+
+/// A named sequence of moves, played by pressing its key chord.
+#[derive(Clone, Debug)]
+pub(crate) struct AlgMacro {
+    name: String,
+    chord: KeyChord,
+    moves: Vec<Move>,
+}
+
+impl AlgMacro {
+    fn moves_string(&self) -> String {
+        self.moves
+            .iter()
+            .map(|m| m.to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Written as ``<chord> = <name>: <moves>``, like ``Ctrl+F1 = Sune: R U R' U R U2 R'``.
+impl fmt::Display for AlgMacro {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} = {}: {}", self.chord, self.name, self.moves_string())
+    }
+}
+
+impl FromStr for AlgMacro {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (chord, rest) = s.split_once('=').ok_or("missing '=' after the key chord")?;
+        // the moves never contain a colon, unlike the name maybe
+        let (name, moves) = rest.rsplit_once(':').ok_or("missing ':' after the name")?;
+        let moves = parse_algorithm(moves).map_err(|err| err.to_string())?;
+
+        Ok(AlgMacro {
+            name: name.trim().to_string(),
+            chord: chord.parse()?,
+            moves,
+        })
+    }
+}
+
+// The moves made since recording started, if recording
+#[derive(Resource, Default)]
+struct Recording(Option<Vec<Move>>);
+
+fn toggle_recording(recording: &mut Recording, settings: &mut Settings) {
+    let Some(moves) = recording.0.take() else {
+        recording.0 = Some(Vec::new());
+        return;
+    };
+    if moves.is_empty() {
+        return;
+    }
+
+    let Some(chord) =
+        KeyChord::all().find(|chord| settings.macros.iter().all(|m| m.chord != *chord))
+    else {
+        warn!("no key chord left for a new macro");
+        return;
+    };
+    let name = (1..)
+        .map(|n| format!("Macro {n}"))
+        .find(|name| settings.macros.iter().all(|m| m.name != *name))
+        .unwrap();
+
+    info!("recorded macro '{name}' on {chord}");
+    settings.macros.push(AlgMacro { name, chord, moves });
+}
+
+fn record_input(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut recording: ResMut<Recording>,
+    mut settings: ResMut<Settings>,
+) {
+    if keyboard_input.just_pressed(RECORD_KEY) {
+        toggle_recording(&mut recording, &mut settings);
+    }
+}
+
+#[derive(Component)]
+struct MacroUiRoot;
+
+#[derive(Component)]
+struct MacroList;
+
+#[derive(Component)]
+enum MacroButtonActions {
+    Record,
+    Play(usize),
+}
+
+fn macro_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(50.0),
+                right: Val::Px(220.0),
+                max_width: Val::Px(400.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::FlexEnd,
+                ..default()
+            },
+            ..default()
+        })
+        .insert(MacroUiRoot)
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::FlexEnd,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .insert(MacroList);
+
+            parent
+                .spawn(TextBundle::from_section(
+                    String::new(),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 25.0,
+                        color: Color::WHITE,
+                    },
+                ))
+                .insert(MacroButtonActions::Record)
+                .insert(Localized::new("macro-record"))
+                .insert(Interaction::None);
+        });
+}
+
+fn macro_button_system(
+    interaction_query: Query<(&Interaction, &MacroButtonActions), Changed<Interaction>>,
+    mut recording: ResMut<Recording>,
+    mut settings: ResMut<Settings>,
+    mut move_queue: PlayedMut<MoveQueue>,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            match *button {
+                MacroButtonActions::Record => toggle_recording(&mut recording, &mut settings),
+                MacroButtonActions::Play(index) => {
+                    if let Some(alg_macro) = settings.macros.get(index) {
+                        move_queue.extend(alg_macro.moves.iter().copied());
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn record_moves(mut move_applied: PlayedMoves, mut recording: ResMut<Recording>) {
+    if recording.0.is_none() {
+        move_applied.clear();
+        return;
+    }
+    for mv in move_applied.read() {
+        if let Some(moves) = &mut recording.0 {
+            moves.push(*mv);
+        }
+    }
+}
+
+fn play_macros(
+    keyboard_input: Res<Input<KeyCode>>,
+    settings: Res<Settings>,
+    mut move_queue: PlayedMut<MoveQueue>,
+) {
+    for alg_macro in &settings.macros {
+        if alg_macro.chord.just_pressed(&keyboard_input) {
+            move_queue.extend(alg_macro.moves.iter().copied());
+        }
+    }
+}
+
+fn show_macros(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    settings: Res<Settings>,
+    recording: Res<Recording>,
+    q_list: Query<Entity, With<MacroList>>,
+    mut q_record: Query<(&mut Localized, &MacroButtonActions)>,
+    added: Query<(), Added<MacroList>>,
+) {
+    if !settings.is_changed() && !recording.is_changed() && added.is_empty() {
+        return;
+    }
+
+    for (mut localized, button) in &mut q_record {
+        if let MacroButtonActions::Record = button {
+            localized.set_if_neq(match &recording.0 {
+                Some(moves) => Localized::new("macro-stop").with("moves", moves.len()),
+                None => Localized::new("macro-record"),
+            });
+        }
+    }
+
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    for list in &q_list {
+        commands
+            .entity(list)
+            .despawn_descendants()
+            .with_children(|parent| {
+                for (index, alg_macro) in settings.macros.iter().enumerate() {
+                    parent
+                        .spawn(TextBundle::from_section(
+                            format!(
+                                "{}  {}: {}",
+                                alg_macro.chord,
+                                alg_macro.name,
+                                alg_macro.moves_string()
+                            ),
+                            TextStyle {
+                                font: font.clone(),
+                                font_size: 20.0,
+                                color: Color::WHITE,
+                            },
+                        ))
+                        .insert(MacroButtonActions::Play(index))
+                        .insert(Interaction::None);
+                }
+            });
+    }
+}
+
+fn clean_up(
+    mut commands: Commands,
+    q_ui: Query<Entity, With<MacroUiRoot>>,
+    mut recording: ResMut<Recording>,
+) {
+    recording.0 = None;
+    for entity in q_ui.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
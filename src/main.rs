@@ -8,14 +8,30 @@ use bevy::{
     winit::WinitWindows,
     DefaultPlugins,
 };
-use bevy_rubiks::RubiksPlugin;
+use bevy_rubiks::{RaceOptions, RubiksPlugin, SpectateMode, StreamMode};
 use std::io::Cursor;
 use winit::window::Icon;
 
 fn main() {
+    let stream_mode = StreamMode::from_args(std::env::args().skip(1)).unwrap_or_else(|err| {
+        eprintln!("{err}");
+        StreamMode::Off
+    });
+    let spectate_mode = SpectateMode::from_args(std::env::args().skip(1)).unwrap_or_else(|err| {
+        eprintln!("{err}");
+        SpectateMode::Off
+    });
+    let race_options = RaceOptions::from_args(std::env::args().skip(1)).unwrap_or_else(|err| {
+        eprintln!("{err}");
+        RaceOptions::default()
+    });
+
     App::new()
         // .insert_resource(Msaa { samples: 1 })
         .insert_resource(ClearColor(Color::rgb(0.4, 0.4, 0.4)))
+        .insert_resource(stream_mode)
+        .insert_resource(spectate_mode)
+        .insert_resource(race_options)
         .add_plugins(
             DefaultPlugins
                 .set(WindowPlugin {
@@ -24,6 +40,10 @@ fn main() {
                             .with_scale_factor_override(1.0),
                         title: "Bevy Rubik's cube".to_string(),
                         canvas: Some("#bevy".to_owned()),
+                        // the web app follows the size of the page, on phones too
+                        fit_canvas_to_parent: true,
+                        // only a transparent window lets the streaming software see behind it
+                        transparent: stream_mode == StreamMode::Transparent,
                         ..Default::default()
                     }),
                     ..default()
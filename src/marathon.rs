@@ -0,0 +1,502 @@
+use crate::{
+    i18n::{Localized, Translations},
+    menu::{MenuButtonAction, MenuState},
+    scramble::Scrambler,
+    session::SolveSession,
+    simulator::{CurrentCube, MoveQueue, PlayedMut},
+    solved::{CubeSolvedEvent, PenaltyChanged, StopTimer},
+    theme::{Accent, Theme, ThemeColor},
+    GameState, Puzzle,
+};
+use bevy::prelude::*;
+use cubesim::{average_of, prelude::*, SolveResult};
+use std::time::Duration;
+
+// The numbers of rounds offered, for an ao5, ao12, ao50 or ao100
+const MARATHON_ROUNDS: [usize; 4] = [5, 12, 50, 100];
+// The averages kept over the rounds
+const AVERAGES: [usize; 3] = [5, 12, 100];
+// The results listed on the panel while solving
+const RECENT_RESULTS: usize = 12;
+
+pub struct MarathonPlugin;
+
+impl Plugin for MarathonPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(MenuState::Marathon), marathon_screen_setup)
+            .add_systems(Update, start_buttons.run_if(in_state(MenuState::Marathon)))
+            .add_systems(OnExit(MenuState::Marathon), clean_up_screen)
+            .add_systems(
+                OnEnter(GameState::Playing),
+                marathon_ui.run_if(resource_exists::<Marathon>()),
+            )
+            .add_systems(
+                Update,
+                (
+                    scramble_round,
+                    end_round,
+                    marathon_buttons,
+                    show_marathon,
+                    show_summary,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(resource_exists::<Marathon>()),
+            )
+            .add_systems(OnExit(GameState::Playing), clean_up)
+            .add_systems(OnEnter(GameState::Menu), end_marathon);
+    }
+}
+
+/// A marathon started from the training menu: a number of rounds of a scramble and a solve,
+/// each result given a +2 or a DNF on the solved screen if need be before the next round, the
+/// averages kept as the WCA does and summed up once the last round is over.
+#[derive(Resource, Debug, Default)]
+struct Marathon {
+    rounds: usize,
+    results: Vec<SolveResult>,
+    // whether the cube of the current round was scrambled
+    scrambled: bool,
+    // whether the current round is over, its result waiting for a penalty or the next round
+    reviewing: bool,
+    // whether the last round is over and its result reviewed
+    finished: bool,
+}
+
+impl Marathon {
+    fn new(rounds: usize) -> Self {
+        Self {
+            rounds,
+            ..default()
+        }
+    }
+
+    fn is_over(&self) -> bool {
+        self.reviewing && self.results.len() == self.rounds
+    }
+
+    // The averages of the last results, as far as there are enough of them
+    fn current_averages(&self) -> String {
+        AVERAGES
+            .into_iter()
+            .filter(|&n| self.results.len() >= n)
+            .map(|n| {
+                let average = average_of(&self.results[self.results.len() - n..]);
+                format!("ao{n} {}", average_text(average))
+            })
+            .collect::<Vec<_>>()
+            .join("  ")
+    }
+
+    fn message(&self, translations: &Translations) -> String {
+        let round = (self.results.len() + usize::from(!self.reviewing)).min(self.rounds);
+        let recent: Vec<String> = self
+            .results
+            .iter()
+            .enumerate()
+            .skip(self.results.len().saturating_sub(RECENT_RESULTS))
+            .map(|(i, result)| format!("{}. {result}", i + 1))
+            .collect();
+        let hint = if self.reviewing {
+            "marathon-hint-next"
+        } else {
+            "marathon-hint-stop"
+        };
+        let title = Localized::new("marathon-round")
+            .with("round", round)
+            .with("rounds", self.rounds);
+        format!(
+            "{}\n{}\n{}\n{}",
+            translations.text(title),
+            recent.join("  "),
+            self.current_averages(),
+            translations.get(hint)
+        )
+    }
+
+    fn summary(&self, translations: &Translations) -> String {
+        let counted: Vec<Duration> = self.results.iter().filter_map(|r| r.counted()).collect();
+        let dnfs = self.results.len() - counted.len();
+        let done = Localized::new("marathon-done").with("rounds", self.rounds);
+        let mut summary = format!("{}\n", translations.text(done));
+        if let (Some(best), Some(worst)) = (counted.iter().min(), counted.iter().max()) {
+            let mean = counted.iter().sum::<Duration>() / counted.len() as u32;
+            let times = Localized::new("marathon-times")
+                .with("best", format!("{:.2}", best.as_secs_f32()))
+                .with("worst", format!("{:.2}", worst.as_secs_f32()))
+                .with("mean", format!("{:.2}", mean.as_secs_f32()))
+                .with("dnfs", dnfs);
+            summary.push_str(&format!("{}\n", translations.text(times)));
+        }
+        for n in AVERAGES.into_iter().filter(|&n| self.results.len() >= n) {
+            // a DNF average is worse than any other
+            let best = self
+                .results
+                .windows(n)
+                .map(average_of)
+                .min_by_key(|average| (average.is_none(), *average))
+                .flatten();
+            let average = Localized::new("marathon-average")
+                .with("n", n)
+                .with("best", average_text(best))
+                .with(
+                    "last",
+                    average_text(average_of(&self.results[self.results.len() - n..])),
+                );
+            summary.push_str(&format!("{}\n", translations.text(average)));
+        }
+        summary
+    }
+}
+
+fn average_text(average: Option<Duration>) -> String {
+    match average {
+        Some(average) => format!("{:.2}", average.as_secs_f32()),
+        None => "DNF".to_string(),
+    }
+}
+
+#[derive(Component)]
+struct OnMarathonScreen;
+
+// Starts a marathon of the number of rounds
+#[derive(Component)]
+struct MarathonLength(usize);
+
+#[derive(Component)]
+struct MarathonUiRoot;
+
+#[derive(Component)]
+struct MarathonText;
+
+#[derive(Component, Clone, Copy)]
+enum MarathonButton {
+    Stop,
+    Next,
+    MainMenu,
+}
+
+// Shown over the cube once the last round is over
+#[derive(Component)]
+struct SummaryScreen;
+
+fn marathon_screen_setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    theme: Res<Theme>,
+    accent: Res<Accent>,
+) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    let button_style = Style {
+        height: Val::Px(45.0),
+        margin: UiRect::all(Val::Px(5.0)),
+        padding: UiRect::horizontal(Val::Px(10.0)),
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        ..default()
+    };
+    let text_style = TextStyle {
+        font: font.clone(),
+        font_size: 30.0,
+        color: theme.text(),
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    margin: UiRect::all(Val::Auto),
+                    padding: UiRect::all(Val::Px(20.0)),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: accent.color().into(),
+                ..default()
+            },
+            OnMarathonScreen,
+            ThemeColor::Accent,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    String::new(),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 50.0,
+                        color: theme.text(),
+                    },
+                ),
+                Localized::new("training-marathon"),
+            ));
+            parent.spawn((
+                TextBundle::from_section(String::new(), text_style.clone()),
+                Localized::new("marathon-rounds"),
+            ));
+
+            parent.spawn(NodeBundle::default()).with_children(|parent| {
+                for rounds in MARATHON_ROUNDS {
+                    parent
+                        .spawn((
+                            ButtonBundle {
+                                style: button_style.clone(),
+                                background_color: theme.button(Interaction::None, false).into(),
+                                ..default()
+                            },
+                            MarathonLength(rounds),
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn(TextBundle::from_section(
+                                rounds.to_string(),
+                                text_style.clone(),
+                            ));
+                        });
+                }
+            });
+
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: button_style,
+                        background_color: theme.button(Interaction::None, false).into(),
+                        ..default()
+                    },
+                    MenuButtonAction::BackToTraining,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section(String::new(), text_style),
+                        Localized::new("menu-back"),
+                    ));
+                });
+        });
+}
+
+fn start_buttons(
+    mut commands: Commands,
+    interaction_query: Query<(&Interaction, &MarathonLength), Changed<Interaction>>,
+    mut puzzle: ResMut<Puzzle>,
+    mut menu_state: ResMut<NextState<MenuState>>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    for (interaction, MarathonLength(rounds)) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            commands.insert_resource(Marathon::new(*rounds));
+            *puzzle = Puzzle::Cube;
+            game_state.set(GameState::Playing);
+            menu_state.set(MenuState::Disabled);
+        }
+    }
+}
+
+fn marathon_ui(mut commands: Commands, asset_server: Res<AssetServer>, theme: Res<Theme>) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    let text_style = TextStyle {
+        font,
+        font_size: 25.0,
+        color: theme.text(),
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    bottom: Val::Px(10.0),
+                    left: Val::Px(220.0),
+                    max_width: Val::Px(600.0),
+                    padding: UiRect::all(Val::Px(10.0)),
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+                ..default()
+            },
+            ThemeColor::Panel,
+            MarathonUiRoot,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(String::new(), text_style.clone()),
+                MarathonText,
+            ));
+            parent.spawn(NodeBundle::default()).with_children(|parent| {
+                for (text_id, button) in [
+                    ("marathon-stop", MarathonButton::Stop),
+                    ("marathon-next", MarathonButton::Next),
+                ] {
+                    parent.spawn((
+                        TextBundle::from_section(String::new(), text_style.clone()).with_style(
+                            Style {
+                                margin: UiRect::right(Val::Px(20.0)),
+                                ..default()
+                            },
+                        ),
+                        Localized::new(text_id),
+                        button,
+                        Interaction::None,
+                    ));
+                }
+            });
+        });
+}
+
+fn show_summary(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    marathon: Res<Marathon>,
+    translations: Translations,
+    q_screen: Query<(), With<SummaryScreen>>,
+) {
+    if !marathon.finished || !q_screen.is_empty() {
+        return;
+    }
+
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    let text_style = TextStyle {
+        font,
+        font_size: 35.0,
+        color: Color::WHITE,
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                ..default()
+            },
+            SummaryScreen,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        padding: UiRect::all(Val::Px(20.0)),
+                        ..default()
+                    },
+                    background_color: Color::rgba(0.15, 0.15, 0.15, 0.9).into(),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        marathon.summary(&translations),
+                        text_style.clone(),
+                    ));
+                    parent.spawn((
+                        TextBundle::from_section(String::new(), text_style).with_style(Style {
+                            margin: UiRect::top(Val::Px(20.0)),
+                            ..default()
+                        }),
+                        Localized::new("marathon-main-menu"),
+                        MarathonButton::MainMenu,
+                        Interaction::None,
+                    ));
+                });
+        });
+}
+
+// Scrambles the cube of each round once it's set up
+fn scramble_round(
+    mut marathon: ResMut<Marathon>,
+    mut current_cube: PlayedMut<CurrentCube>,
+    mut move_queue: PlayedMut<MoveQueue>,
+    mut session: PlayedMut<SolveSession>,
+    mut scrambler: Scrambler,
+) {
+    if marathon.scrambled || marathon.reviewing {
+        return;
+    }
+
+    let scramble = scrambler.scramble(current_cube.cube_size() as CubeSize);
+    session.queue_scramble(&mut current_cube, &mut move_queue, scramble);
+    marathon.scrambled = true;
+}
+
+// Ends the round once its cube is solved, or its timer stopped, the penalty given on the
+// solved screen going to its result
+fn end_round(
+    mut marathon: ResMut<Marathon>,
+    mut solved_events: EventReader<CubeSolvedEvent>,
+    mut penalty_events: EventReader<PenaltyChanged>,
+) {
+    for solved in solved_events.read() {
+        if !marathon.scrambled || marathon.reviewing {
+            continue;
+        }
+        marathon.results.push(solved.report.result());
+        marathon.reviewing = true;
+    }
+    for PenaltyChanged(penalty) in penalty_events.read() {
+        let reviewing = marathon.reviewing;
+        if let Some(result) = marathon.results.last_mut().filter(|_| reviewing) {
+            result.penalty = *penalty;
+        }
+    }
+}
+
+fn marathon_buttons(
+    interaction_query: Query<(&Interaction, &MarathonButton), Changed<Interaction>>,
+    mut marathon: ResMut<Marathon>,
+    mut stop_events: EventWriter<StopTimer>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match button {
+            MarathonButton::Stop => stop_events.send(StopTimer),
+            MarathonButton::Next if marathon.is_over() => {
+                info!("marathon done: {}", marathon.current_averages());
+                marathon.finished = true;
+            }
+            MarathonButton::Next if marathon.reviewing => {
+                marathon.reviewing = false;
+                marathon.scrambled = false;
+                game_state.set(GameState::Restarting);
+            }
+            MarathonButton::Next => {}
+            MarathonButton::MainMenu => game_state.set(GameState::Menu),
+        }
+    }
+}
+
+fn show_marathon(
+    marathon: Res<Marathon>,
+    translations: Translations,
+    mut q_text: Query<&mut Text, With<MarathonText>>,
+) {
+    let message = marathon.message(&translations);
+    for mut text in &mut q_text {
+        if text.sections[0].value != message {
+            text.sections[0].value = message.clone();
+        }
+    }
+}
+
+// Leaving the game gives up the marathon, but starting over for its next round doesn't
+fn end_marathon(mut commands: Commands) {
+    commands.remove_resource::<Marathon>();
+}
+
+type MarathonUi = Or<(With<MarathonUiRoot>, With<SummaryScreen>)>;
+
+fn clean_up(mut commands: Commands, q_ui: Query<Entity, MarathonUi>) {
+    for entity in &q_ui {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn clean_up_screen(mut commands: Commands, q_screen: Query<Entity, With<OnMarathonScreen>>) {
+    for entity in &q_screen {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,254 @@
+use crate::{
+    lighting::Look,
+    simulator::{MovesText, PlayButtonActions},
+    GameState, Puzzle,
+};
+use bevy::{
+    prelude::*,
+    render::{mesh::Indices, render_resource::PrimitiveTopology},
+};
+use cubesim::{random_megaminx_scramble, Megaminx, MinxFace, MinxMove, TwistyPuzzle};
+
+pub struct MegaminxPlugin;
+
+impl Plugin for MegaminxPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CurrentMegaminx>()
+            .add_systems(
+                OnEnter(GameState::Playing),
+                megaminx_setup.run_if(resource_equals(Puzzle::Megaminx)),
+            )
+            .add_systems(
+                Update,
+                (keyboard_input_system, button_system, update_stickers)
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(resource_equals(Puzzle::Megaminx)),
+            )
+            .add_systems(OnExit(GameState::Playing), clean_up);
+    }
+}
+
+// Scales the unit-inradius dodecahedron to roughly the size of a 3x3x3 cube
+pub(crate) const MINX_SCALE: f32 = 1.8;
+const STICKER_RADIUS: f32 = 0.11;
+const CENTER_RADIUS: f32 = 0.2;
+
+#[derive(Resource, Default)]
+pub struct CurrentMegaminx {
+    megaminx: Megaminx,
+    moves: Vec<MinxMove>,
+}
+
+impl CurrentMegaminx {
+    fn apply_move(&mut self, mv: MinxMove) {
+        self.megaminx = self.megaminx.apply_move(mv);
+        self.moves.push(mv);
+    }
+}
+
+#[derive(Component)]
+struct MinxPiece;
+
+// Index of the sticker slot in `Megaminx::state`
+#[derive(Component)]
+struct MinxSticker(usize);
+
+#[derive(Resource)]
+struct MinxMaterials(Vec<Handle<StandardMaterial>>);
+
+fn face_color(face: MinxFace) -> Color {
+    match face {
+        MinxFace::U => Color::WHITE,
+        MinxFace::F => Color::GREEN,
+        MinxFace::R => Color::RED,
+        MinxFace::BR => Color::BLUE,
+        MinxFace::BL => Color::YELLOW,
+        MinxFace::L => Color::PURPLE,
+        MinxFace::D => Color::GRAY,
+        MinxFace::B => Color::LIME_GREEN,
+        MinxFace::DBL => Color::ORANGE,
+        MinxFace::DL => Color::rgb(0.4, 0.7, 1.0),
+        MinxFace::DR => Color::rgb(1.0, 0.95, 0.7),
+        MinxFace::DBR => Color::PINK,
+    }
+}
+
+// Builds the black dodecahedron the stickers are placed on
+fn dodecahedron_mesh() -> Mesh {
+    let mut positions = Vec::new();
+    let mut normals = Vec::new();
+    let mut indices = Vec::new();
+
+    for face in cubesim::ORDERED_MINX_FACES {
+        let normal = face.normal().as_vec3();
+        let start = positions.len() as u32;
+
+        positions.push((normal * MINX_SCALE).to_array());
+        for vertex in Megaminx::face_vertices(face) {
+            positions.push((vertex.as_vec3() * MINX_SCALE).to_array());
+        }
+        normals.extend([normal.to_array(); 6]);
+
+        // face vertices are clockwise from the outside, so reverse them for front faces
+        for i in 0..5 {
+            indices.extend([start, start + 1 + (i + 1) % 5, start + 1 + i]);
+        }
+    }
+
+    let uvs = vec![[0.0, 0.0]; positions.len()];
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    mesh.set_indices(Some(Indices::U32(indices)));
+    mesh
+}
+
+fn megaminx_setup(
+    mut commands: Commands,
+    mut current_minx: ResMut<CurrentMegaminx>,
+    look: Res<Look>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    *current_minx = CurrentMegaminx::default();
+
+    let face_materials = cubesim::ORDERED_MINX_FACES
+        .iter()
+        .map(|face| materials.add(look.sticker_material(face_color(*face))))
+        .collect::<Vec<_>>();
+
+    commands
+        .spawn(PbrBundle {
+            mesh: meshes.add(dodecahedron_mesh()),
+            material: materials.add(look.body_material(Color::BLACK)),
+            ..Default::default()
+        })
+        .insert(MinxPiece);
+
+    let center_mesh = meshes.add(Mesh::from(shape::RegularPolygon::new(
+        CENTER_RADIUS * MINX_SCALE,
+        5,
+    )));
+    let sticker_mesh = meshes.add(Mesh::from(shape::Circle::new(STICKER_RADIUS * MINX_SCALE)));
+    let state = current_minx.megaminx.state();
+
+    for (i, position) in Megaminx::sticker_positions().iter().enumerate() {
+        let face = cubesim::ORDERED_MINX_FACES[i / cubesim::MINX_STICKERS_PER_FACE];
+        let normal = face.normal().as_vec3();
+        let is_center = i % cubesim::MINX_STICKERS_PER_FACE == 0;
+
+        // lift the sticker slightly off the body and turn its +z towards the face normal
+        let transform =
+            Transform::from_translation(position.as_vec3() * MINX_SCALE + normal * 0.01)
+                .with_rotation(Quat::from_rotation_arc(Vec3::Z, normal));
+
+        commands
+            .spawn(PbrBundle {
+                mesh: if is_center {
+                    center_mesh.clone()
+                } else {
+                    sticker_mesh.clone()
+                },
+                material: face_materials[state[i].index()].clone(),
+                transform,
+                ..Default::default()
+            })
+            .insert((MinxPiece, MinxSticker(i)));
+    }
+
+    commands.insert_resource(MinxMaterials(face_materials));
+}
+
+fn update_stickers(
+    current_minx: Res<CurrentMegaminx>,
+    materials: Option<Res<MinxMaterials>>,
+    mut q_sticker: Query<(&MinxSticker, &mut Handle<StandardMaterial>)>,
+    mut q_text: Query<&mut Text, With<MovesText>>,
+) {
+    let Some(materials) = materials else {
+        return;
+    };
+    if !current_minx.is_changed() {
+        return;
+    }
+
+    let state = current_minx.megaminx.state();
+    for (sticker, mut material) in q_sticker.iter_mut() {
+        *material = materials.0[state[sticker.0].index()].clone();
+    }
+
+    if let Ok(mut text) = q_text.get_single_mut() {
+        text.sections[0].value = current_minx
+            .moves
+            .iter()
+            .map(|m| m.to_string())
+            .collect::<Vec<String>>()
+            .join(" ");
+    }
+}
+
+fn keyboard_input_system(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut current_minx: ResMut<CurrentMegaminx>,
+) {
+    let inverse = keyboard_input.pressed(KeyCode::ShiftLeft);
+    let wide = keyboard_input.pressed(KeyCode::ControlLeft);
+
+    for (key, face) in [
+        (KeyCode::U, MinxFace::U),
+        (KeyCode::F, MinxFace::F),
+        (KeyCode::R, MinxFace::R),
+        (KeyCode::L, MinxFace::L),
+        (KeyCode::D, MinxFace::D),
+    ] {
+        if keyboard_input.just_pressed(key) {
+            // Ctrl turns everything but the opposite layer, as in R++ / D--
+            let mv = match (wide, inverse) {
+                (true, false) => MinxMove::Wide(face, 2),
+                (true, true) => MinxMove::Wide(face, -2),
+                (false, false) => MinxMove::Turn(face, 1),
+                (false, true) => MinxMove::Turn(face, -1),
+            };
+            current_minx.apply_move(mv);
+        }
+    }
+}
+
+fn button_system(
+    interaction_query: Query<(&Interaction, &PlayButtonActions), Changed<Interaction>>,
+    mut game_state: ResMut<NextState<GameState>>,
+    mut current_minx: ResMut<CurrentMegaminx>,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            match *button {
+                PlayButtonActions::BackToMenu => {
+                    game_state.set(GameState::Menu);
+                }
+                PlayButtonActions::CubeScramble => {
+                    for mv in random_megaminx_scramble() {
+                        current_minx.apply_move(mv);
+                    }
+                }
+                PlayButtonActions::CubeSolver | PlayButtonActions::CompareSolvers => {
+                    warn!("no solver available for the megaminx");
+                }
+                // toggled by the layout, for every puzzle
+                PlayButtonActions::Fullscreen => {}
+                PlayButtonActions::ExplodedView
+                | PlayButtonActions::PieceTypes
+                | PlayButtonActions::Tutorial => {
+                    warn!("only the cube can be viewed this way");
+                }
+            }
+        }
+    }
+}
+
+fn clean_up(mut commands: Commands, q_piece: Query<Entity, With<MinxPiece>>) {
+    commands.remove_resource::<MinxMaterials>();
+    for entity in q_piece.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -1,5 +1,18 @@
-use crate::GameState;
-use bevy::{app::AppExit, prelude::*};
+use crate::{
+    assistant::start_assistant,
+    diagnostics::DiagnosticsOverlay,
+    i18n::{Language, Localized},
+    lighting::{Background, Ground, Look},
+    mesh::MeshQuality,
+    notation_quiz::start_quiz,
+    race::{RaceOptions, RaceRating},
+    relay::start_relay,
+    scramble::{Rotations, ScrambleLength, ScrambleSeed, WideMoves},
+    stream::StreamMode,
+    theme::{Accent, Theme, ThemeColor},
+    CubeSizeSetting, GameState, Puzzle,
+};
+use bevy::{app::AppExit, ecs::system::SystemParam, prelude::*};
 
 pub struct MenuPlugin;
 
@@ -12,12 +25,93 @@ impl Plugin for MenuPlugin {
             .add_systems(OnEnter(GameState::Menu), menu_setup)
             .add_systems(OnEnter(MenuState::Main), main_menu_setup)
             .add_systems(OnExit(MenuState::Main), despawn_screen::<OnMainMenuScreen>)
+            // Systems to handle the training menu screen
+            .add_systems(OnEnter(MenuState::Training), training_menu_setup)
+            .add_systems(
+                OnExit(MenuState::Training),
+                despawn_screen::<OnTrainingMenuScreen>,
+            )
             // Systems to handle the settings menu screen
             .add_systems(OnEnter(MenuState::Settings), settings_menu_setup)
             .add_systems(
                 OnExit(MenuState::Settings),
                 despawn_screen::<OnSettingsMenuScreen>,
             )
+            // Systems to handle the display settings screen
+            .add_systems(OnEnter(MenuState::SettingsDisplay), display_menu_setup)
+            .add_systems(
+                Update,
+                (
+                    setting_button::<MeshQuality>,
+                    setting_button::<Look>,
+                    setting_button::<Background>,
+                    setting_button::<Ground>,
+                    setting_button::<DiagnosticsOverlay>,
+                    setting_button::<StreamMode>,
+                )
+                    .run_if(in_state(MenuState::SettingsDisplay)),
+            )
+            .add_systems(
+                OnExit(MenuState::SettingsDisplay),
+                despawn_screen::<OnDisplayMenuScreen>,
+            )
+            // Systems to handle the puzzle selection screen
+            .add_systems(OnEnter(MenuState::SettingsPuzzle), puzzle_menu_setup)
+            .add_systems(
+                Update,
+                setting_button::<Puzzle>.run_if(in_state(MenuState::SettingsPuzzle)),
+            )
+            .add_systems(
+                OnExit(MenuState::SettingsPuzzle),
+                despawn_screen::<OnPuzzleMenuScreen>,
+            )
+            // Systems to handle the cube size selection screen
+            .add_systems(OnEnter(MenuState::SettingsCubeSize), cube_size_menu_setup)
+            .add_systems(
+                Update,
+                setting_button::<CubeSizeSetting>.run_if(in_state(MenuState::SettingsCubeSize)),
+            )
+            .add_systems(
+                OnExit(MenuState::SettingsCubeSize),
+                despawn_screen::<OnCubeSizeMenuScreen>,
+            )
+            // Systems to handle the language selection screen
+            .add_systems(OnEnter(MenuState::SettingsLanguage), language_menu_setup)
+            .add_systems(
+                Update,
+                setting_button::<Language>.run_if(in_state(MenuState::SettingsLanguage)),
+            )
+            .add_systems(
+                OnExit(MenuState::SettingsLanguage),
+                despawn_screen::<OnLanguageMenuScreen>,
+            )
+            // Systems to handle the theme screen
+            .add_systems(OnEnter(MenuState::SettingsTheme), theme_menu_setup)
+            .add_systems(
+                Update,
+                (setting_button::<Theme>, setting_button::<Accent>)
+                    .run_if(in_state(MenuState::SettingsTheme)),
+            )
+            .add_systems(
+                OnExit(MenuState::SettingsTheme),
+                despawn_screen::<OnThemeMenuScreen>,
+            )
+            // Systems to handle the scramble options screen
+            .add_systems(OnEnter(MenuState::SettingsScramble), scramble_menu_setup)
+            .add_systems(
+                Update,
+                (
+                    setting_button::<ScrambleLength>,
+                    setting_button::<WideMoves>,
+                    setting_button::<Rotations>,
+                    setting_button::<ScrambleSeed>,
+                )
+                    .run_if(in_state(MenuState::SettingsScramble)),
+            )
+            .add_systems(
+                OnExit(MenuState::SettingsScramble),
+                despawn_screen::<OnScrambleMenuScreen>,
+            )
             .add_systems(
                 Update,
                 (menu_action, button_system).run_if(in_state(GameState::Menu)),
@@ -27,22 +121,52 @@ impl Plugin for MenuPlugin {
 
 #[derive(Component)]
 #[allow(dead_code)]
-enum MenuButtonAction {
+pub(crate) enum MenuButtonAction {
     Play,
+    Relay,
+    ScrambleAssistant,
+    Training,
+    EoTrainer,
+    NotationQuiz,
+    Marathon,
+    Timer,
+    Import,
+    Race,
+    Statistics,
     Settings,
     SettingsDisplay,
+    SettingsPuzzle,
+    SettingsCubeSize,
+    SettingsLanguage,
+    SettingsTheme,
+    SettingsScramble,
+    Profile,
     BackToMainMenu,
     BackToSettings,
+    BackToTraining,
     Quit,
 }
 
 // State used for the current menu screen
 #[derive(Clone, Eq, PartialEq, Debug, Hash, Default, States)]
-enum MenuState {
+pub(crate) enum MenuState {
     #[default]
     Main,
+    Training,
+    EoTrainer,
+    Marathon,
+    Timer,
+    Import,
+    Race,
+    Statistics,
     Settings,
     SettingsDisplay,
+    SettingsPuzzle,
+    SettingsCubeSize,
+    SettingsLanguage,
+    SettingsTheme,
+    SettingsScramble,
+    Profile,
     Disabled,
 }
 
@@ -50,25 +174,46 @@ enum MenuState {
 #[derive(Component)]
 struct OnMainMenuScreen;
 
+#[derive(Component)]
+struct OnTrainingMenuScreen;
+
 #[derive(Component)]
 struct OnSettingsMenuScreen;
 
-// Tag component used to mark wich setting is currently selected
 #[derive(Component)]
-struct SelectedOption;
+struct OnDisplayMenuScreen;
+
+#[derive(Component)]
+struct OnPuzzleMenuScreen;
 
-const TEXT_COLOR: Color = Color::rgb(0.9, 0.9, 0.9);
+#[derive(Component)]
+struct OnCubeSizeMenuScreen;
+
+#[derive(Component)]
+struct OnLanguageMenuScreen;
+
+#[derive(Component)]
+struct OnThemeMenuScreen;
+
+#[derive(Component)]
+struct OnScrambleMenuScreen;
 
-const NORMAL_BUTTON: Color = Color::rgb(0.15, 0.15, 0.15);
-const HOVERED_BUTTON: Color = Color::rgb(0.25, 0.25, 0.25);
-const HOVERED_PRESSED_BUTTON: Color = Color::rgb(0.25, 0.65, 0.25);
-const PRESSED_BUTTON: Color = Color::rgb(0.35, 0.75, 0.35);
+// Tag component used to mark wich setting is currently selected
+#[derive(Component)]
+pub(crate) struct SelectedOption;
 
 fn menu_setup(mut menu_state: ResMut<NextState<MenuState>>) {
     let _ = menu_state.set(MenuState::Main);
 }
 
-fn main_menu_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+fn main_menu_setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    theme: Res<Theme>,
+    accent: Res<Accent>,
+    race_options: Res<RaceOptions>,
+    rating: Res<RaceRating>,
+) {
     let font = asset_server.load("fonts/FiraSans-Bold.ttf");
     // Common style for all buttons on the screen
     let button_style = Style {
@@ -94,7 +239,7 @@ fn main_menu_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
     let button_text_style = TextStyle {
         font: font.clone(),
         font_size: 40.0,
-        color: TEXT_COLOR,
+        color: theme.text(),
     };
 
     commands
@@ -106,37 +251,41 @@ fn main_menu_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                     align_items: AlignItems::Center,
                     ..default()
                 },
-                background_color: Color::CRIMSON.into(),
+                background_color: accent.color().into(),
                 ..default()
             },
             OnMainMenuScreen,
+            ThemeColor::Accent,
         ))
         .with_children(|parent| {
             // Display the game name
-            parent.spawn(
+            parent.spawn((
                 TextBundle::from_section(
-                    "Bevy Rubik's Cube",
+                    String::new(),
                     TextStyle {
                         font: font.clone(),
                         font_size: 80.0,
-                        color: TEXT_COLOR,
+                        color: theme.text(),
                     },
                 )
                 .with_style(Style {
                     margin: UiRect::all(Val::Px(50.0)),
                     ..default()
                 }),
-            );
+                Localized::new("menu-title"),
+            ));
 
-            // Display three buttons for each action available from the main menu:
+            // Display five buttons for each action available from the main menu:
             // - new game
+            // - relay
+            // - statistics
             // - settings
             // - quit
             parent
                 .spawn((
                     ButtonBundle {
                         style: button_style.clone(),
-                        background_color: NORMAL_BUTTON.into(),
+                        background_color: theme.button(Interaction::None, false).into(),
                         ..default()
                     },
                     MenuButtonAction::Play,
@@ -148,16 +297,76 @@ fn main_menu_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                         image: icon.into(),
                         ..default()
                     });
-                    parent.spawn(TextBundle::from_section(
-                        "New Game",
-                        button_text_style.clone(),
+                    parent.spawn((
+                        TextBundle::from_section(String::new(), button_text_style.clone()),
+                        Localized::new("menu-new-game"),
                     ));
                 });
             parent
                 .spawn((
                     ButtonBundle {
                         style: button_style.clone(),
-                        background_color: NORMAL_BUTTON.into(),
+                        background_color: theme.button(Interaction::None, false).into(),
+                        ..default()
+                    },
+                    MenuButtonAction::Relay,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section(String::new(), button_text_style.clone()),
+                        Localized::new("menu-relay"),
+                    ));
+                });
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: button_style.clone(),
+                        background_color: theme.button(Interaction::None, false).into(),
+                        ..default()
+                    },
+                    MenuButtonAction::ScrambleAssistant,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section(String::new(), button_text_style.clone()),
+                        Localized::new("menu-scramble-assistant"),
+                    ));
+                });
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: button_style.clone(),
+                        background_color: theme.button(Interaction::None, false).into(),
+                        ..default()
+                    },
+                    MenuButtonAction::Training,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section(String::new(), button_text_style.clone()),
+                        Localized::new("menu-training"),
+                    ));
+                });
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: button_style.clone(),
+                        background_color: theme.button(Interaction::None, false).into(),
+                        ..default()
+                    },
+                    MenuButtonAction::Statistics,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section(String::new(), button_text_style.clone()),
+                        Localized::new("menu-statistics"),
+                    ));
+                });
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: button_style.clone(),
+                        background_color: theme.button(Interaction::None, false).into(),
                         ..default()
                     },
                     MenuButtonAction::Settings,
@@ -169,16 +378,16 @@ fn main_menu_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                         image: icon.into(),
                         ..default()
                     });
-                    parent.spawn(TextBundle::from_section(
-                        "Settings",
-                        button_text_style.clone(),
+                    parent.spawn((
+                        TextBundle::from_section(String::new(), button_text_style.clone()),
+                        Localized::new("menu-settings"),
                     ));
                 });
             parent
                 .spawn((
                     ButtonBundle {
                         style: button_style,
-                        background_color: NORMAL_BUTTON.into(),
+                        background_color: theme.button(Interaction::None, false).into(),
                         ..default()
                     },
                     MenuButtonAction::Quit,
@@ -190,12 +399,129 @@ fn main_menu_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                         image: icon.into(),
                         ..default()
                     });
-                    parent.spawn(TextBundle::from_section("Quit", button_text_style));
+                    parent.spawn((
+                        TextBundle::from_section(String::new(), button_text_style),
+                        Localized::new("menu-quit"),
+                    ));
                 });
         });
+
+    // The profile card, with the racer's name and rating in the online races
+    let card_text_style = TextStyle {
+        font: font.clone(),
+        font_size: 25.0,
+        color: theme.text(),
+    };
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(10.0),
+                    left: Val::Px(10.0),
+                    padding: UiRect::all(Val::Px(10.0)),
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+                ..default()
+            },
+            OnMainMenuScreen,
+            ThemeColor::Panel,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                race_options.name(),
+                TextStyle {
+                    font_size: 35.0,
+                    ..card_text_style.clone()
+                },
+            ));
+            for (id, value) in [
+                ("menu-rating", format!(" {:.0}", rating.rating)),
+                ("menu-races", format!(" {}", rating.races)),
+            ] {
+                parent.spawn((
+                    TextBundle::from_sections([
+                        TextSection::new(String::new(), card_text_style.clone()),
+                        TextSection::new(value, card_text_style.clone()),
+                    ]),
+                    Localized::new(id),
+                ));
+            }
+        });
+}
+
+fn training_menu_setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    theme: Res<Theme>,
+    accent: Res<Accent>,
+) {
+    let button_style = Style {
+        width: Val::Px(300.),
+        height: Val::Px(65.0),
+        margin: UiRect::all(Val::Px(20.0)),
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        ..default()
+    };
+
+    let button_text_style = TextStyle {
+        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+        font_size: 40.0,
+        color: theme.text(),
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    margin: UiRect::all(Val::Auto),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: accent.color().into(),
+                ..default()
+            },
+            OnTrainingMenuScreen,
+            ThemeColor::Accent,
+        ))
+        .with_children(|parent| {
+            for (action, text) in [
+                (MenuButtonAction::EoTrainer, "training-eo"),
+                (MenuButtonAction::NotationQuiz, "training-notation"),
+                (MenuButtonAction::Marathon, "training-marathon"),
+                (MenuButtonAction::Timer, "training-timer"),
+                (MenuButtonAction::Import, "training-import"),
+                (MenuButtonAction::Race, "training-race"),
+                (MenuButtonAction::BackToMainMenu, "menu-back"),
+            ] {
+                parent
+                    .spawn((
+                        ButtonBundle {
+                            style: button_style.clone(),
+                            background_color: theme.button(Interaction::None, false).into(),
+                            ..default()
+                        },
+                        action,
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn((
+                            TextBundle::from_section(String::new(), button_text_style.clone()),
+                            Localized::new(text),
+                        ));
+                    });
+            }
+        });
 }
 
-fn settings_menu_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+fn settings_menu_setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    theme: Res<Theme>,
+    accent: Res<Accent>,
+) {
     let button_style = Style {
         width: Val::Px(200.),
         height: Val::Px(65.0),
@@ -209,7 +535,7 @@ fn settings_menu_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
     let button_text_style = TextStyle {
         font: asset_server.load("fonts/FiraSans-Bold.ttf"),
         font_size: 40.0,
-        color: TEXT_COLOR,
+        color: theme.text(),
     };
 
     commands
@@ -221,32 +547,716 @@ fn settings_menu_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                     align_items: AlignItems::Center,
                     ..default()
                 },
-                background_color: Color::CRIMSON.into(),
+                background_color: accent.color().into(),
                 ..default()
             },
             OnSettingsMenuScreen,
+            ThemeColor::Accent,
         ))
         .with_children(|parent| {
             for (action, text) in [
-                (MenuButtonAction::SettingsDisplay, "Display"),
-                (MenuButtonAction::BackToMainMenu, "Back"),
+                (MenuButtonAction::SettingsDisplay, "settings-display"),
+                (MenuButtonAction::SettingsPuzzle, "settings-puzzle"),
+                (MenuButtonAction::SettingsCubeSize, "settings-cube-size"),
+                (MenuButtonAction::SettingsLanguage, "settings-language"),
+                (MenuButtonAction::SettingsTheme, "settings-theme"),
+                (MenuButtonAction::SettingsScramble, "settings-scramble"),
+                (MenuButtonAction::Profile, "settings-profile"),
+                (MenuButtonAction::BackToMainMenu, "menu-back"),
             ] {
                 parent
                     .spawn((
                         ButtonBundle {
                             style: button_style.clone(),
-                            background_color: NORMAL_BUTTON.into(),
+                            background_color: theme.button(Interaction::None, false).into(),
                             ..default()
                         },
                         action,
                     ))
                     .with_children(|parent| {
-                        parent.spawn(TextBundle::from_section(text, button_text_style.clone()));
+                        parent.spawn((
+                            TextBundle::from_section(String::new(), button_text_style.clone()),
+                            Localized::new(text),
+                        ));
                     });
             }
         });
 }
 
+#[derive(SystemParam)]
+struct DisplaySettings<'w> {
+    mesh_quality: Res<'w, MeshQuality>,
+    look: Res<'w, Look>,
+    background: Res<'w, Background>,
+    ground: Res<'w, Ground>,
+    diagnostics: Res<'w, DiagnosticsOverlay>,
+    stream: Res<'w, StreamMode>,
+}
+
+fn display_menu_setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    settings: DisplaySettings,
+    theme: Res<Theme>,
+    accent: Res<Accent>,
+) {
+    let button_style = Style {
+        width: Val::Px(200.),
+        height: Val::Px(65.0),
+        margin: UiRect::all(Val::Px(10.0)),
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        ..default()
+    };
+
+    let button_text_style = TextStyle {
+        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+        font_size: 40.0,
+        color: theme.text(),
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    margin: UiRect::all(Val::Auto),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: accent.color().into(),
+                ..default()
+            },
+            OnDisplayMenuScreen,
+            ThemeColor::Accent,
+        ))
+        .with_children(|parent| {
+            // Display a row of buttons for each setting, highlighting the current values
+            let options = MeshQuality::ALL.map(|quality| (quality, quality.name()));
+            setting_row(
+                parent,
+                "display-cubies",
+                options,
+                *settings.mesh_quality,
+                *theme,
+                &button_style,
+                &button_text_style,
+            );
+            let options = Look::ALL.map(|look_option| (look_option, look_option.name()));
+            setting_row(
+                parent,
+                "display-look",
+                options,
+                *settings.look,
+                *theme,
+                &button_style,
+                &button_text_style,
+            );
+            let options = Background::ALL.map(|background| (background, background.name()));
+            setting_row(
+                parent,
+                "display-background",
+                options,
+                *settings.background,
+                *theme,
+                &button_style,
+                &button_text_style,
+            );
+            let options = Ground::ALL.map(|ground| (ground, ground.name()));
+            setting_row(
+                parent,
+                "display-ground",
+                options,
+                *settings.ground,
+                *theme,
+                &button_style,
+                &button_text_style,
+            );
+            let options = DiagnosticsOverlay::ALL.map(|overlay| (overlay, overlay.name()));
+            setting_row(
+                parent,
+                "display-diagnostics",
+                options,
+                *settings.diagnostics,
+                *theme,
+                &button_style,
+                &button_text_style,
+            );
+            let options = StreamMode::ALL.map(|mode| (mode, mode.name()));
+            setting_row(
+                parent,
+                "display-stream",
+                options,
+                *settings.stream,
+                *theme,
+                &button_style,
+                &button_text_style,
+            );
+
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: button_style,
+                        background_color: theme.button(Interaction::None, false).into(),
+                        ..default()
+                    },
+                    MenuButtonAction::BackToSettings,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section(String::new(), button_text_style),
+                        Localized::new("menu-back"),
+                    ));
+                });
+        });
+}
+
+// A row of buttons for the values of a setting, after its name, translating the name and the
+// values as `option-<value>`
+fn setting_row<T: Component + PartialEq + Copy>(
+    parent: &mut ChildBuilder,
+    name: &'static str,
+    options: impl IntoIterator<Item = (T, &'static str)>,
+    current: T,
+    theme: Theme,
+    button_style: &Style,
+    text_style: &TextStyle,
+) {
+    parent
+        .spawn(NodeBundle {
+            style: Style {
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(String::new(), text_style.clone()),
+                Localized::new(name),
+            ));
+            for (value, text) in options {
+                let mut entity = parent.spawn((
+                    ButtonBundle {
+                        style: button_style.clone(),
+                        background_color: theme.button(Interaction::None, false).into(),
+                        ..default()
+                    },
+                    value,
+                ));
+                entity.with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section(String::new(), text_style.clone()),
+                        Localized::new(format!("option-{}", text.to_lowercase())),
+                    ));
+                });
+                if current == value {
+                    entity.insert(SelectedOption);
+                }
+            }
+        });
+}
+
+fn puzzle_menu_setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    puzzle: Res<Puzzle>,
+    theme: Res<Theme>,
+    accent: Res<Accent>,
+) {
+    let button_style = Style {
+        width: Val::Px(250.),
+        height: Val::Px(65.0),
+        margin: UiRect::all(Val::Px(20.0)),
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        ..default()
+    };
+
+    let button_text_style = TextStyle {
+        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+        font_size: 40.0,
+        color: theme.text(),
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    margin: UiRect::all(Val::Auto),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: accent.color().into(),
+                ..default()
+            },
+            OnPuzzleMenuScreen,
+            ThemeColor::Accent,
+        ))
+        .with_children(|parent| {
+            // Display a button for each puzzle, highlighting the current one
+            for (puzzle_setting, text) in [
+                (Puzzle::Cube, "puzzle-cube"),
+                (Puzzle::Megaminx, "puzzle-megaminx"),
+            ] {
+                let mut entity = parent.spawn((
+                    ButtonBundle {
+                        style: button_style.clone(),
+                        background_color: theme.button(Interaction::None, false).into(),
+                        ..default()
+                    },
+                    puzzle_setting,
+                ));
+                entity.with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section(String::new(), button_text_style.clone()),
+                        Localized::new(text),
+                    ));
+                });
+                if *puzzle == puzzle_setting {
+                    entity.insert(SelectedOption);
+                }
+            }
+
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: button_style,
+                        background_color: theme.button(Interaction::None, false).into(),
+                        ..default()
+                    },
+                    MenuButtonAction::BackToSettings,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section(String::new(), button_text_style),
+                        Localized::new("menu-back"),
+                    ));
+                });
+        });
+}
+
+fn cube_size_menu_setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    cube_size: Res<CubeSizeSetting>,
+    theme: Res<Theme>,
+    accent: Res<Accent>,
+) {
+    let button_style = Style {
+        width: Val::Px(120.),
+        height: Val::Px(65.0),
+        margin: UiRect::all(Val::Px(10.0)),
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        ..default()
+    };
+
+    let button_text_style = TextStyle {
+        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+        font_size: 40.0,
+        color: theme.text(),
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    margin: UiRect::all(Val::Auto),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: accent.color().into(),
+                ..default()
+            },
+            OnCubeSizeMenuScreen,
+            ThemeColor::Accent,
+        ))
+        .with_children(|parent| {
+            // Display a row of buttons for the cube sizes, highlighting the current one
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for size in CubeSizeSetting::SIZES {
+                        let size_setting = CubeSizeSetting(size);
+                        let mut entity = parent.spawn((
+                            ButtonBundle {
+                                style: button_style.clone(),
+                                background_color: theme.button(Interaction::None, false).into(),
+                                ..default()
+                            },
+                            size_setting,
+                        ));
+                        entity.with_children(|parent| {
+                            parent.spawn(TextBundle::from_section(
+                                format!("{size}x{size}"),
+                                button_text_style.clone(),
+                            ));
+                        });
+                        if *cube_size == size_setting {
+                            entity.insert(SelectedOption);
+                        }
+                    }
+                });
+
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: button_style,
+                        background_color: theme.button(Interaction::None, false).into(),
+                        ..default()
+                    },
+                    MenuButtonAction::BackToSettings,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section(String::new(), button_text_style),
+                        Localized::new("menu-back"),
+                    ));
+                });
+        });
+}
+
+fn language_menu_setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    language: Res<Language>,
+    theme: Res<Theme>,
+    accent: Res<Accent>,
+) {
+    let button_style = Style {
+        width: Val::Px(250.),
+        height: Val::Px(65.0),
+        margin: UiRect::all(Val::Px(20.0)),
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        ..default()
+    };
+
+    let button_text_style = TextStyle {
+        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+        font_size: 40.0,
+        color: theme.text(),
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    margin: UiRect::all(Val::Auto),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: accent.color().into(),
+                ..default()
+            },
+            OnLanguageMenuScreen,
+            ThemeColor::Accent,
+        ))
+        .with_children(|parent| {
+            // Display a button for each language, highlighting the current one
+            for language_setting in Language::ALL {
+                let mut entity = parent.spawn((
+                    ButtonBundle {
+                        style: button_style.clone(),
+                        background_color: theme.button(Interaction::None, false).into(),
+                        ..default()
+                    },
+                    language_setting,
+                ));
+                entity.with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section(String::new(), button_text_style.clone()),
+                        Localized::new(language_setting.name_id()),
+                    ));
+                });
+                if *language == language_setting {
+                    entity.insert(SelectedOption);
+                }
+            }
+
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: button_style,
+                        background_color: theme.button(Interaction::None, false).into(),
+                        ..default()
+                    },
+                    MenuButtonAction::BackToSettings,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section(String::new(), button_text_style),
+                        Localized::new("menu-back"),
+                    ));
+                });
+        });
+}
+
+fn theme_menu_setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    theme: Res<Theme>,
+    accent: Res<Accent>,
+) {
+    let button_style = Style {
+        width: Val::Px(200.),
+        height: Val::Px(65.0),
+        margin: UiRect::all(Val::Px(10.0)),
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        ..default()
+    };
+
+    let button_text_style = TextStyle {
+        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+        font_size: 40.0,
+        color: theme.text(),
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    margin: UiRect::all(Val::Auto),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: accent.color().into(),
+                ..default()
+            },
+            OnThemeMenuScreen,
+            ThemeColor::Accent,
+        ))
+        .with_children(|parent| {
+            let options = Theme::ALL.map(|theme_option| (theme_option, theme_option.name()));
+            setting_row(
+                parent,
+                "theme-colors",
+                options,
+                *theme,
+                *theme,
+                &button_style,
+                &button_text_style,
+            );
+            // a custom accent of the settings file can't be picked again once left
+            let mut options: Vec<_> = Accent::ALL
+                .into_iter()
+                .map(|accent_option| (accent_option, accent_option.name()))
+                .collect();
+            if let Accent::Custom(_) = *accent {
+                options.push((*accent, accent.name()));
+            }
+            setting_row(
+                parent,
+                "theme-accent",
+                options,
+                *accent,
+                *theme,
+                &button_style,
+                &button_text_style,
+            );
+
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: button_style,
+                        background_color: theme.button(Interaction::None, false).into(),
+                        ..default()
+                    },
+                    MenuButtonAction::BackToSettings,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section(String::new(), button_text_style),
+                        Localized::new("menu-back"),
+                    ));
+                });
+        });
+}
+
+#[derive(SystemParam)]
+struct ScrambleSettings<'w> {
+    length: Res<'w, ScrambleLength>,
+    wide_moves: Res<'w, WideMoves>,
+    rotations: Res<'w, Rotations>,
+    seed: Res<'w, ScrambleSeed>,
+}
+
+fn scramble_menu_setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    settings: ScrambleSettings,
+    theme: Res<Theme>,
+    accent: Res<Accent>,
+) {
+    let button_style = Style {
+        width: Val::Px(120.),
+        height: Val::Px(65.0),
+        margin: UiRect::all(Val::Px(10.0)),
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        ..default()
+    };
+
+    let button_text_style = TextStyle {
+        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+        font_size: 40.0,
+        color: theme.text(),
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    margin: UiRect::all(Val::Auto),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: accent.color().into(),
+                ..default()
+            },
+            OnScrambleMenuScreen,
+            ThemeColor::Accent,
+        ))
+        .with_children(|parent| {
+            // The lengths are numbers, only the automatic one is translated
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section(String::new(), button_text_style.clone()),
+                        Localized::new("scramble-length"),
+                    ));
+                    // a length of the settings file can't be picked again once left
+                    let mut lengths = ScrambleLength::ALL.to_vec();
+                    if !lengths.contains(&settings.length) {
+                        lengths.push(*settings.length);
+                    }
+                    for length in lengths {
+                        let mut entity = parent.spawn((
+                            ButtonBundle {
+                                style: button_style.clone(),
+                                background_color: theme.button(Interaction::None, false).into(),
+                                ..default()
+                            },
+                            length,
+                        ));
+                        entity.with_children(|parent| match length.0 {
+                            Some(moves) => {
+                                parent.spawn(TextBundle::from_section(
+                                    moves.to_string(),
+                                    button_text_style.clone(),
+                                ));
+                            }
+                            None => {
+                                parent.spawn((
+                                    TextBundle::from_section(
+                                        String::new(),
+                                        button_text_style.clone(),
+                                    ),
+                                    Localized::new("option-auto"),
+                                ));
+                            }
+                        });
+                        if *settings.length == length {
+                            entity.insert(SelectedOption);
+                        }
+                    }
+                });
+            let options = WideMoves::ALL.map(|wide_moves| (wide_moves, wide_moves.name()));
+            setting_row(
+                parent,
+                "scramble-wide-moves",
+                options,
+                *settings.wide_moves,
+                *theme,
+                &button_style,
+                &button_text_style,
+            );
+            let options = Rotations::ALL.map(|rotations| (rotations, rotations.name()));
+            setting_row(
+                parent,
+                "scramble-rotations",
+                options,
+                *settings.rotations,
+                *theme,
+                &button_style,
+                &button_text_style,
+            );
+            // the seed of the settings file is kept while it's fixed
+            let fixed = match settings.seed.0 {
+                Some(_) => *settings.seed,
+                None => ScrambleSeed::FIXED,
+            };
+            let options = [ScrambleSeed(None), fixed].map(|seed| (seed, seed.name()));
+            setting_row(
+                parent,
+                "scramble-seed",
+                options,
+                *settings.seed,
+                *theme,
+                &button_style,
+                &button_text_style,
+            );
+
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: button_style,
+                        background_color: theme.button(Interaction::None, false).into(),
+                        ..default()
+                    },
+                    MenuButtonAction::BackToSettings,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section(String::new(), button_text_style),
+                        Localized::new("menu-back"),
+                    ));
+                });
+        });
+}
+
+type SelectedSetting<T> = (With<SelectedOption>, With<T>);
+
+// This system updates the settings when a new value for a setting is selected, and marks
+// the button as the one currently selected among the buttons of that setting
+fn setting_button<T: Resource + Component + PartialEq + Copy>(
+    interaction_query: Query<(&Interaction, &T, Entity), ChangedButton>,
+    mut selected_query: Query<(Entity, &mut BackgroundColor), SelectedSetting<T>>,
+    mut commands: Commands,
+    mut setting: ResMut<T>,
+    theme: Res<Theme>,
+) {
+    for (interaction, button_setting, entity) in &interaction_query {
+        if *interaction == Interaction::Pressed && *setting != *button_setting {
+            let (previous_button, mut previous_color) = selected_query.single_mut();
+            *previous_color = theme.button(Interaction::None, false).into();
+            commands.entity(previous_button).remove::<SelectedOption>();
+            commands.entity(entity).insert(SelectedOption);
+            *setting = *button_setting;
+        }
+    }
+}
+
 // Generic system that takes a component as a parameter, and will despawn all entities with that
 // component
 fn despawn_screen<T: Component>(to_despawn: Query<Entity, With<T>>, mut commands: Commands) {
@@ -256,10 +1266,13 @@ fn despawn_screen<T: Component>(to_despawn: Query<Entity, With<T>>, mut commands
 }
 
 fn menu_action(
+    mut commands: Commands,
     interaction_query: Query<(&Interaction, &MenuButtonAction), ChangedButton>,
     mut app_exit_events: EventWriter<AppExit>,
     mut menu_state: ResMut<NextState<MenuState>>,
     mut game_state: ResMut<NextState<GameState>>,
+    mut puzzle: ResMut<Puzzle>,
+    mut cube_size: ResMut<CubeSizeSetting>,
 ) {
     for (interaction, menu_button_action) in &interaction_query {
         if *interaction == Interaction::Pressed {
@@ -269,14 +1282,55 @@ fn menu_action(
                     game_state.set(GameState::Playing);
                     menu_state.set(MenuState::Disabled);
                 }
+                MenuButtonAction::Relay => {
+                    start_relay(&mut commands, &mut puzzle, &mut cube_size);
+                    game_state.set(GameState::Playing);
+                    menu_state.set(MenuState::Disabled);
+                }
+                MenuButtonAction::ScrambleAssistant => {
+                    start_assistant(&mut commands, &mut puzzle);
+                    game_state.set(GameState::Playing);
+                    menu_state.set(MenuState::Disabled);
+                }
+                MenuButtonAction::Training => menu_state.set(MenuState::Training),
+                MenuButtonAction::EoTrainer => menu_state.set(MenuState::EoTrainer),
+                MenuButtonAction::Marathon => menu_state.set(MenuState::Marathon),
+                MenuButtonAction::Timer => menu_state.set(MenuState::Timer),
+                MenuButtonAction::Import => menu_state.set(MenuState::Import),
+                MenuButtonAction::Race => menu_state.set(MenuState::Race),
+                MenuButtonAction::NotationQuiz => {
+                    start_quiz(&mut commands, &mut puzzle, &mut cube_size);
+                    game_state.set(GameState::Playing);
+                    menu_state.set(MenuState::Disabled);
+                }
+                MenuButtonAction::Statistics => menu_state.set(MenuState::Statistics),
                 MenuButtonAction::Settings => menu_state.set(MenuState::Settings),
                 MenuButtonAction::SettingsDisplay => {
                     menu_state.set(MenuState::SettingsDisplay);
                 }
+                MenuButtonAction::SettingsPuzzle => {
+                    menu_state.set(MenuState::SettingsPuzzle);
+                }
+                MenuButtonAction::SettingsCubeSize => {
+                    menu_state.set(MenuState::SettingsCubeSize);
+                }
+                MenuButtonAction::SettingsLanguage => {
+                    menu_state.set(MenuState::SettingsLanguage);
+                }
+                MenuButtonAction::SettingsTheme => {
+                    menu_state.set(MenuState::SettingsTheme);
+                }
+                MenuButtonAction::SettingsScramble => {
+                    menu_state.set(MenuState::SettingsScramble);
+                }
+                MenuButtonAction::Profile => menu_state.set(MenuState::Profile),
                 MenuButtonAction::BackToMainMenu => menu_state.set(MenuState::Main),
                 MenuButtonAction::BackToSettings => {
                     menu_state.set(MenuState::Settings);
                 }
+                MenuButtonAction::BackToTraining => {
+                    menu_state.set(MenuState::Training);
+                }
             }
         }
     }
@@ -284,19 +1338,17 @@ fn menu_action(
 
 type ChangedButton = (Changed<Interaction>, With<Button>);
 
-// This system handles changing all buttons color based on mouse interaction
-fn button_system(
-    mut interaction_query: Query<
-        (&Interaction, &mut BackgroundColor, Option<&SelectedOption>),
-        ChangedButton,
-    >,
-) {
+type ButtonColor<'a> = (
+    Ref<'a, Interaction>,
+    &'a mut BackgroundColor,
+    Option<&'a SelectedOption>,
+);
+
+// This system handles changing all buttons color based on mouse interaction, and on the theme
+fn button_system(mut interaction_query: Query<ButtonColor, With<Button>>, theme: Res<Theme>) {
     for (interaction, mut color, selected) in &mut interaction_query {
-        *color = match (*interaction, selected) {
-            (Interaction::Pressed, _) | (Interaction::None, Some(_)) => PRESSED_BUTTON.into(),
-            (Interaction::Hovered, Some(_)) => HOVERED_PRESSED_BUTTON.into(),
-            (Interaction::Hovered, None) => HOVERED_BUTTON.into(),
-            (Interaction::None, None) => NORMAL_BUTTON.into(),
+        if interaction.is_changed() || theme.is_changed() {
+            *color = theme.button(*interaction, selected.is_some()).into();
         }
     }
 }
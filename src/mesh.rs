@@ -0,0 +1,122 @@
+use bevy::{
+    prelude::*,
+    render::{mesh::Indices, render_resource::PrimitiveTopology},
+};
+
+// the radius of the rounded edges of a beveled cubie, for a cubie of size 1
+const BEVEL_RADIUS: f32 = 0.08;
+
+/// How the cubies are modelled, chosen from the display settings.
+#[derive(Resource, Component, Reflect, Clone, Copy, Eq, PartialEq, Debug, Default)]
+#[reflect(Resource)]
+pub(crate) enum MeshQuality {
+    /// Plain cubes with square stickers over their faces.
+    Flat,
+    /// Cubes with rounded edges and inset stickers, like a real speedcube.
+    #[default]
+    Low,
+    /// Like `Low`, with edges rounded more smoothly.
+    High,
+}
+
+impl MeshQuality {
+    pub(crate) const ALL: [MeshQuality; 3] =
+        [MeshQuality::Flat, MeshQuality::Low, MeshQuality::High];
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            MeshQuality::Flat => "Flat",
+            MeshQuality::Low => "Beveled",
+            MeshQuality::High => "Smooth",
+        }
+    }
+
+    // the number of segments rounding each edge
+    fn segments(self) -> usize {
+        match self {
+            MeshQuality::Flat => 0,
+            MeshQuality::Low => 2,
+            MeshQuality::High => 6,
+        }
+    }
+
+    /// The mesh of a cubie's body, centered on the origin.
+    pub(crate) fn cubie_mesh(self, size: f32) -> Mesh {
+        match self {
+            MeshQuality::Flat => Mesh::from(shape::Cube { size }),
+            _ => beveled_cube(size, BEVEL_RADIUS * size, self.segments()),
+        }
+    }
+
+    /// The size of a sticker, for a cubie of a given size. Beveled cubies have their stickers
+    /// inset from the rounded edges.
+    pub(crate) fn sticker_size(self, size: f32) -> f32 {
+        match self {
+            MeshQuality::Flat => size * 0.9,
+            _ => size * 0.82,
+        }
+    }
+}
+
+// A cube with its edges and corners rounded with a radius, each rounded edge made of a number
+// of segments
+fn beveled_cube(size: f32, radius: f32, segments: usize) -> Mesh {
+    let half = size / 2.0;
+    let inner = half - radius;
+    // the coordinates along each side of a face, closer together where the face is rounded
+    let steps: Vec<f32> = (0..=segments)
+        .map(|i| -half + radius * (i as f32 / segments as f32))
+        .chain((0..=segments).map(|i| inner + radius * (i as f32 / segments as f32)))
+        .collect();
+
+    let mut positions = Vec::new();
+    let mut normals = Vec::new();
+    let mut uvs = Vec::new();
+    let mut indices = Vec::new();
+    // each face's normal with two axes along it, turning counterclockwise seen from outside
+    let faces = [
+        (Vec3::X, Vec3::Y, Vec3::Z),
+        (Vec3::NEG_X, Vec3::Z, Vec3::Y),
+        (Vec3::Y, Vec3::Z, Vec3::X),
+        (Vec3::NEG_Y, Vec3::X, Vec3::Z),
+        (Vec3::Z, Vec3::X, Vec3::Y),
+        (Vec3::NEG_Z, Vec3::Y, Vec3::X),
+    ];
+
+    for (normal, u, v) in faces {
+        let start = positions.len() as u32;
+        for &b in &steps {
+            for &a in &steps {
+                let point = normal * half + u * a + v * b;
+                // pushes the points of the box's shell onto the rounded edges and corners
+                let core = point.clamp(Vec3::splat(-inner), Vec3::splat(inner));
+                let direction = (point - core).normalize_or_zero();
+                let direction = if direction == Vec3::ZERO {
+                    normal
+                } else {
+                    direction
+                };
+
+                positions.push((core + direction * radius).to_array());
+                normals.push(direction.to_array());
+                uvs.push([a / size + 0.5, b / size + 0.5]);
+            }
+        }
+
+        let row = steps.len() as u32;
+        for j in 0..row - 1 {
+            for i in 0..row - 1 {
+                let corner = start + j * row + i;
+                indices.extend([corner, corner + 1, corner + row + 1]);
+                indices.extend([corner, corner + row + 1, corner + row]);
+            }
+        }
+    }
+
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    mesh.set_indices(Some(Indices::U32(indices)));
+    mesh
+}
@@ -0,0 +1,267 @@
+use crate::{
+    i18n::{Localized, Translations},
+    simulator::PlayedMoves,
+    theme::{Theme, ThemeColor},
+    CubeSizeSetting, GameState, Puzzle,
+};
+use bevy::prelude::*;
+use cubesim::{prelude::*, FaceletCube};
+use rand::{seq::SliceRandom, Rng};
+use std::time::Duration;
+
+// The seconds given to make each move
+const TIME_LIMIT: f32 = 5.0;
+const TOKEN_COLOR: Color = Color::rgb(1.0, 0.8, 0.0);
+const RIGHT_COLOR: Color = Color::rgb(0.1, 0.8, 0.2);
+const WRONG_COLOR: Color = Color::rgb(0.9, 0.1, 0.1);
+
+type Turn = fn(MoveVariant) -> Move;
+
+// The moves asked, by their name in WCA notation, on a 3x3x3: the slice moves turn the middle
+// layer the way of the face they follow, M the way of L, E of D and S of F
+const TOKENS: [(&str, Turn); 15] = [
+    ("U", Move::U),
+    ("R", Move::R),
+    ("F", Move::F),
+    ("L", Move::L),
+    ("D", Move::D),
+    ("B", Move::B),
+    ("Uw", |variant| Move::Uw(2, variant)),
+    ("Rw", |variant| Move::Rw(2, variant)),
+    ("Fw", |variant| Move::Fw(2, variant)),
+    ("M", |variant| Move::Li(2, variant)),
+    ("E", |variant| Move::Di(2, variant)),
+    ("S", |variant| Move::Fi(2, variant)),
+    ("x", Move::X),
+    ("y", Move::Y),
+    ("z", Move::Z),
+];
+
+pub struct NotationQuizPlugin;
+
+impl Plugin for NotationQuizPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(GameState::Playing),
+            quiz_ui.run_if(resource_exists::<NotationQuiz>()),
+        )
+        .add_systems(
+            Update,
+            (answer_quiz, show_quiz)
+                .chain()
+                .run_if(in_state(GameState::Playing))
+                .run_if(resource_exists::<NotationQuiz>()),
+        )
+        .add_systems(OnExit(GameState::Playing), clean_up)
+        .add_systems(OnEnter(GameState::Menu), end_quiz);
+    }
+}
+
+/// Teaches the WCA notation, started from the training menu: a random move is named on a card
+/// and has to be made on the cube before the time runs out, the moves answered right kept
+/// over the quiz.
+#[derive(Resource, Default)]
+struct NotationQuiz {
+    token: String,
+    target: Vec<Face>,
+    // the moves made since the token was shown
+    made: Vec<Move>,
+    timer: Timer,
+    // whether the last token was made in time, and the token
+    last: Option<(bool, String)>,
+    asked: usize,
+    right: usize,
+    streak: usize,
+    // the time taken by the tokens made in time
+    total_time: Duration,
+}
+
+impl NotationQuiz {
+    fn next(&mut self) {
+        let mut rng = rand::thread_rng();
+        let (name, turn) = TOKENS.choose(&mut rng).copied().unwrap_or(TOKENS[0]);
+        let variant: MoveVariant = rng.gen();
+
+        self.token = format!("{name}{variant}");
+        self.target = FaceletCube::new(3).apply_move(turn(variant)).state();
+        self.made.clear();
+        self.timer = Timer::from_seconds(TIME_LIMIT, TimerMode::Once);
+    }
+
+    // The moves count as the token once they turn the cube the same way, as `L' R x'` does M
+    fn is_made(&self) -> bool {
+        FaceletCube::new(3).apply_moves(&self.made).state() == self.target
+    }
+
+    fn score(&mut self, right: bool) {
+        self.asked += 1;
+        if right {
+            self.right += 1;
+            self.streak += 1;
+            self.total_time += self.timer.elapsed();
+        } else {
+            self.streak = 0;
+        }
+        self.last = Some((right, self.token.clone()));
+        self.next();
+    }
+
+    fn summary(&self, translations: &Translations) -> String {
+        let result = match &self.last {
+            Some((true, token)) => Localized::new("quiz-right").with("move", token.as_str()),
+            Some((false, token)) => Localized::new("quiz-missed").with("move", token.as_str()),
+            None => Localized::new("quiz-make-move"),
+        };
+        let score = Localized::new("quiz-score")
+            .with("result", translations.text(result))
+            .with("left", format!("{:.1}", self.timer.remaining_secs()))
+            .with("right", self.right)
+            .with("asked", self.asked)
+            .with("streak", self.streak);
+        let score = match self.right {
+            0 => translations.text(score),
+            right => translations.text(
+                Localized::new("quiz-average")
+                    .with("score", translations.text(score))
+                    .with(
+                        "seconds",
+                        format!("{:.1}", self.total_time.as_secs_f32() / right as f32),
+                    ),
+            ),
+        };
+        format!("\n{score}")
+    }
+}
+
+#[derive(Component)]
+struct QuizUiRoot;
+
+// The token to make in large letters, then the result of the last one and the score
+#[derive(Component)]
+struct QuizCard;
+
+fn quiz_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    theme: Res<Theme>,
+    mut quiz: ResMut<NotationQuiz>,
+) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    quiz.next();
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(10.0),
+                    width: Val::Percent(100.0),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                ..default()
+            },
+            QuizUiRoot,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    NodeBundle {
+                        style: Style {
+                            flex_direction: FlexDirection::Column,
+                            align_items: AlignItems::Center,
+                            padding: UiRect::all(Val::Px(10.0)),
+                            ..default()
+                        },
+                        ..default()
+                    },
+                    ThemeColor::Panel,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_sections([
+                            TextSection::new(
+                                String::new(),
+                                TextStyle {
+                                    font: font.clone(),
+                                    font_size: 160.0,
+                                    color: TOKEN_COLOR,
+                                },
+                            ),
+                            TextSection::new(
+                                String::new(),
+                                TextStyle {
+                                    font,
+                                    font_size: 25.0,
+                                    color: theme.text(),
+                                },
+                            ),
+                        ])
+                        .with_text_alignment(TextAlignment::Center),
+                        QuizCard,
+                    ));
+                });
+        });
+}
+
+// Follows the moves made on the cube until they make the token or the time runs out
+fn answer_quiz(time: Res<Time>, mut move_applied: PlayedMoves, mut quiz: ResMut<NotationQuiz>) {
+    for mv in move_applied.read() {
+        quiz.made.push(*mv);
+        if quiz.is_made() {
+            quiz.score(true);
+        }
+    }
+
+    quiz.timer.tick(time.delta());
+    if quiz.timer.finished() {
+        quiz.score(false);
+    }
+}
+
+fn show_quiz(
+    quiz: Res<NotationQuiz>,
+    translations: Translations,
+    mut q_card: Query<&mut Text, With<QuizCard>>,
+) {
+    let color = match quiz.last {
+        Some((true, _)) => RIGHT_COLOR,
+        Some((false, _)) => WRONG_COLOR,
+        None => TOKEN_COLOR,
+    };
+    for mut text in &mut q_card {
+        text.sections[0].value = quiz.token.clone();
+        text.sections[1].value = quiz.summary(&translations);
+        text.sections[1].style.color = color;
+    }
+}
+
+// Playing again starts the score over
+fn clean_up(
+    mut commands: Commands,
+    quiz: Option<ResMut<NotationQuiz>>,
+    q_ui: Query<Entity, With<QuizUiRoot>>,
+) {
+    if let Some(mut quiz) = quiz {
+        *quiz = NotationQuiz::default();
+    }
+    for entity in &q_ui {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn end_quiz(mut commands: Commands) {
+    commands.remove_resource::<NotationQuiz>();
+}
+
+/// Starts the notation quiz from the training menu, on a 3x3x3 cube since the slice moves are
+/// named for its middle layers.
+pub(crate) fn start_quiz(
+    commands: &mut Commands,
+    puzzle: &mut Puzzle,
+    cube_size: &mut CubeSizeSetting,
+) {
+    commands.insert_resource(NotationQuiz::default());
+    *puzzle = Puzzle::Cube;
+    cube_size.0 = 3;
+}
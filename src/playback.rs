@@ -0,0 +1,316 @@
+use crate::{
+    i18n::{Localized, Translations},
+    session::SolveSession,
+    simulator::{CurrentCube, MoveQueue, Played, PlayedMut},
+    theme::{Theme, ThemeColor},
+    GameState, Puzzle,
+};
+use bevy::prelude::*;
+use cubesim::prelude::*;
+
+// The move being made stands out from the others of its phase
+const CURRENT_MOVE_COLOR: Color = Color::rgb(1.0, 0.8, 0.0);
+
+pub struct PlaybackPlugin;
+
+impl Plugin for PlaybackPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PhasePause>()
+            .add_systems(
+                OnEnter(GameState::Playing),
+                playback_ui.run_if(resource_equals(Puzzle::Cube)),
+            )
+            .add_systems(
+                Update,
+                (pause_buttons, pause_at_phases, show_playback, show_pause)
+                    .chain()
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(resource_equals(Puzzle::Cube)),
+            )
+            .add_systems(OnExit(GameState::Playing), clean_up);
+    }
+}
+
+/// Holds the solution's moves before each phase after the first, until the player continues,
+/// so the solve can be followed one phase at a time.
+#[derive(Resource, Debug, Default)]
+struct PhasePause {
+    // the number of the solution's moves made when it last paused, to pause only once there
+    paused_at: Option<usize>,
+    // the rest of the solution plays without pausing
+    play_all: bool,
+}
+
+#[derive(Component)]
+struct PlaybackUiRoot;
+
+// The move being made, in large letters
+#[derive(Component)]
+struct MoveToken;
+
+// The phase of the move and its moves, in three sections: the moves before, the move being
+// made and the moves after
+#[derive(Component)]
+struct PhaseMoves;
+
+// The prompt shown while paused before a phase, with what the phase accomplishes
+#[derive(Component)]
+struct PausePrompt;
+
+#[derive(Component)]
+struct NextPhaseText;
+
+#[derive(Component, Clone, Copy)]
+enum PauseButton {
+    Continue,
+    PlayAll,
+}
+
+fn playback_ui(mut commands: Commands, asset_server: Res<AssetServer>, theme: Res<Theme>) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    let style = TextStyle {
+        font: font.clone(),
+        font_size: 25.0,
+        color: Color::WHITE,
+    };
+    let prompt_style = TextStyle {
+        font: font.clone(),
+        font_size: 20.0,
+        color: theme.text(),
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Percent(35.0),
+                    right: Val::Px(230.0),
+                    max_width: Val::Px(300.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::FlexEnd,
+                    ..default()
+                },
+                ..default()
+            },
+            PlaybackUiRoot,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    String::new(),
+                    TextStyle {
+                        font,
+                        font_size: 90.0,
+                        color: CURRENT_MOVE_COLOR,
+                    },
+                ),
+                MoveToken,
+            ));
+            parent.spawn((
+                TextBundle::from_sections([
+                    TextSection::new(String::new(), style.clone()),
+                    TextSection::new(
+                        String::new(),
+                        TextStyle {
+                            color: CURRENT_MOVE_COLOR,
+                            ..style.clone()
+                        },
+                    ),
+                    TextSection::new(String::new(), style),
+                ]),
+                PhaseMoves,
+            ));
+            parent
+                .spawn((
+                    NodeBundle {
+                        style: Style {
+                            flex_direction: FlexDirection::Column,
+                            align_items: AlignItems::FlexEnd,
+                            margin: UiRect::top(Val::Px(10.0)),
+                            padding: UiRect::all(Val::Px(10.0)),
+                            ..default()
+                        },
+                        visibility: Visibility::Hidden,
+                        ..default()
+                    },
+                    ThemeColor::Panel,
+                    PausePrompt,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section(String::new(), prompt_style.clone()),
+                        NextPhaseText,
+                    ));
+                    parent.spawn(NodeBundle::default()).with_children(|parent| {
+                        let buttons = [
+                            (PauseButton::Continue, "playback-continue"),
+                            (PauseButton::PlayAll, "playback-play-all"),
+                        ];
+                        for (button, text_id) in buttons {
+                            parent
+                                .spawn((
+                                    ButtonBundle {
+                                        style: Style {
+                                            padding: UiRect::axes(Val::Px(10.0), Val::Px(5.0)),
+                                            margin: UiRect::left(Val::Px(10.0)),
+                                            ..default()
+                                        },
+                                        background_color: theme
+                                            .button(Interaction::None, false)
+                                            .into(),
+                                        ..default()
+                                    },
+                                    button,
+                                ))
+                                .with_children(|parent| {
+                                    parent.spawn((
+                                        TextBundle::from_section(
+                                            String::new(),
+                                            prompt_style.clone(),
+                                        ),
+                                        Localized::new(text_id),
+                                    ));
+                                });
+                        }
+                    });
+                });
+        });
+}
+
+fn pause_buttons(
+    theme: Res<Theme>,
+    mut pause: ResMut<PhasePause>,
+    mut move_queue: PlayedMut<MoveQueue>,
+    mut q_buttons: Query<(&Interaction, &PauseButton, &mut BackgroundColor), Changed<Interaction>>,
+) {
+    for (interaction, button, mut background) in &mut q_buttons {
+        background.0 = theme.button(*interaction, false);
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        if let PauseButton::PlayAll = button {
+            pause.play_all = true;
+        }
+        move_queue.release();
+    }
+}
+
+// Holds the queue while the last move of a phase is being made, if another phase follows
+fn pause_at_phases(
+    current_cube: Played<CurrentCube>,
+    session: Played<SolveSession>,
+    mut pause: ResMut<PhasePause>,
+    mut move_queue: PlayedMut<MoveQueue>,
+) {
+    let Some((solution, made)) = session.solution(&current_cube) else {
+        // the solution was cut short while paused, the moves left must still be made
+        if pause.paused_at.take().is_some() {
+            move_queue.release();
+        }
+        return;
+    };
+    if made == solution.len() {
+        *pause = PhasePause::default();
+        return;
+    }
+    if move_queue.is_held() || pause.play_all || made == 0 || pause.paused_at == Some(made) {
+        return;
+    }
+
+    if let Some((_, _, 0)) = solution.phase_of(made) {
+        move_queue.hold();
+        pause.paused_at = Some(made);
+    }
+}
+
+fn show_pause(
+    current_cube: Played<CurrentCube>,
+    session: Played<SolveSession>,
+    move_queue: Played<MoveQueue>,
+    translations: Translations,
+    mut q_prompt: Query<&mut Visibility, With<PausePrompt>>,
+    mut q_text: Query<&mut Text, With<NextPhaseText>>,
+) {
+    if !move_queue.is_changed() {
+        return;
+    }
+
+    let next_phase = session
+        .solution(&current_cube)
+        .filter(|_| move_queue.is_held())
+        .and_then(|(solution, made)| solution.phase_of(made));
+    for mut visibility in &mut q_prompt {
+        visibility.set_if_neq(match next_phase {
+            Some(_) => Visibility::Inherited,
+            None => Visibility::Hidden,
+        });
+    }
+    if let Some((phase, moves, _)) = next_phase {
+        for mut text in &mut q_text {
+            text.sections[0].value = translations.text(
+                Localized::new("playback-next-phase")
+                    .with("phase", phase.to_string())
+                    .with("moves", moves.len())
+                    .with("description", phase.description()),
+            );
+        }
+    }
+}
+
+// Each move after a space, to follow the phase's name or the moves before
+fn spaced(moves: &[Move]) -> String {
+    moves.iter().map(|mv| format!(" {mv}")).collect()
+}
+
+// Follows the solution's move being animated, as `rotate_control` takes it from the queue
+fn show_playback(
+    current_cube: Played<CurrentCube>,
+    session: Played<SolveSession>,
+    move_queue: Played<MoveQueue>,
+    mut q_token: Query<&mut Text, (With<MoveToken>, Without<PhaseMoves>)>,
+    mut q_phase: Query<&mut Text, (With<PhaseMoves>, Without<MoveToken>)>,
+) {
+    if !current_cube.is_changed() && !move_queue.is_changed() && !session.is_changed() {
+        return;
+    }
+
+    let playing = move_queue
+        .in_flight()
+        .and_then(|_| session.solution_move(&current_cube));
+    let (token, sections) = match playing {
+        Some((phase, moves, index)) => {
+            let before = format!("{phase}:{}", spaced(&moves[..index]));
+            let after = spaced(&moves[index + 1..]);
+            (
+                moves[index].to_string(),
+                [before, spaced(&moves[index..=index]), after],
+            )
+        }
+        None => (String::new(), Default::default()),
+    };
+
+    for mut text in &mut q_token {
+        if text.sections[0].value != token {
+            text.sections[0].value = token.clone();
+        }
+    }
+    for mut text in &mut q_phase {
+        for (section, value) in text.sections.iter_mut().zip(&sections) {
+            if section.value != *value {
+                section.value = value.clone();
+            }
+        }
+    }
+}
+
+fn clean_up(
+    mut commands: Commands,
+    mut pause: ResMut<PhasePause>,
+    q_ui: Query<Entity, With<PlaybackUiRoot>>,
+) {
+    *pause = PhasePause::default();
+    for entity in &q_ui {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -1,77 +1,266 @@
-use crate::simulator::MoveQueue;
-use bevy::prelude::*;
-use cubesim::{Move, MoveVariant};
+use crate::{
+    settings::Settings,
+    simulator::{CurrentCube, MoveQueue, Played, PlayedMut},
+    spectate::Spectator,
+    CubeSizeSetting, GameState, Puzzle,
+};
+use bevy::{input::mouse::MouseWheel, prelude::*};
+use cubesim::{prelude::CubeSize, Face, Move, MoveVariant};
 
 pub struct PlayerPlugin;
 
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, spawn_camera)
-            .add_systems(Update, keyboard_input_system);
+        app.init_resource::<WideDepth>()
+            .init_resource::<LayerPrefix>()
+            .init_resource::<CubeOrientation>()
+            .add_systems(Startup, spawn_camera)
+            .add_systems(OnEnter(GameState::Playing), frame_camera)
+            .add_systems(
+                Update,
+                (
+                    track_orientation.before(keyboard_input_system),
+                    keyboard_input_system,
+                    wide_depth_input,
+                )
+                    .run_if(resource_equals(Puzzle::Cube))
+                    // the spectators' cube only turns with the moves broadcast
+                    .run_if(not(resource_exists::<Spectator>())),
+            );
     }
 }
 
+pub(crate) const CAMERA_POSITION: Vec3 = Vec3::new(5.5, 3.5, 5.5);
+
 fn spawn_camera(mut commands: Commands) {
-    commands.spawn(Camera3dBundle {
-        transform: Transform::from_xyz(5.5, 3.5, 5.5).looking_at(Vec3::ZERO, Vec3::Y),
-        ..Default::default()
-    });
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_translation(CAMERA_POSITION).looking_at(Vec3::ZERO, Vec3::Y),
+            ..Default::default()
+        },
+        // hidden while taking a screenshot without the UI
+        UiCameraConfig::default(),
+    ));
 }
 
-fn keyboard_input_system(keyboard_input: Res<Input<KeyCode>>, mut move_queue: ResMut<MoveQueue>) {
-    let move_variant = if keyboard_input.pressed(KeyCode::ShiftLeft) {
-        MoveVariant::Inverse
-    } else if keyboard_input.pressed(KeyCode::Key2) {
-        MoveVariant::Double
-    } else {
-        MoveVariant::Standard
+// Moves the camera back for cubes larger than 3x3x3, so the whole cube stays in view
+fn frame_camera(
+    puzzle: Res<Puzzle>,
+    cube_size: Res<CubeSizeSetting>,
+    mut q_camera: Query<&mut Transform, With<Camera3d>>,
+) {
+    let scale = match *puzzle {
+        Puzzle::Cube => (cube_size.0 as f32 / 3.0).max(1.0),
+        Puzzle::Megaminx => 1.0,
     };
 
-    if keyboard_input.just_pressed(KeyCode::F) {
-        if keyboard_input.pressed(KeyCode::ControlLeft) {
-            move_queue.push_back(Move::Fw(2, move_variant));
-        } else {
-            move_queue.push_back(Move::F(move_variant));
-        }
+    for mut transform in &mut q_camera {
+        *transform =
+            Transform::from_translation(CAMERA_POSITION * scale).looking_at(Vec3::ZERO, Vec3::Y);
+    }
+}
+
+/// The number of layers turned by a wide move, selected with `[`/`]` or Ctrl + mouse wheel.
+#[derive(Resource, Clone, Copy, Eq, PartialEq, Debug)]
+pub(crate) struct WideDepth(pub CubeSize);
+
+impl Default for WideDepth {
+    fn default() -> Self {
+        Self(2)
     }
+}
 
-    if keyboard_input.just_pressed(KeyCode::B) {
+impl WideDepth {
+    /// The deepest wide move that still leaves a layer of the cube in place.
+    pub(crate) fn max_for(cube_size: usize) -> CubeSize {
+        (cube_size as CubeSize - 1).max(2)
+    }
+}
+
+fn wide_depth_input(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut mouse_wheel_events: EventReader<MouseWheel>,
+    current_cube: Played<CurrentCube>,
+    mut wide_depth: ResMut<WideDepth>,
+) {
+    let mut change = 0;
+    if keyboard_input.just_pressed(KeyCode::BracketRight) {
+        change += 1;
+    }
+    if keyboard_input.just_pressed(KeyCode::BracketLeft) {
+        change -= 1;
+    }
+    for event in mouse_wheel_events.read() {
         if keyboard_input.pressed(KeyCode::ControlLeft) {
-            move_queue.push_back(Move::Bw(2, move_variant));
-        } else {
-            move_queue.push_back(Move::B(move_variant));
+            change += event.y.signum() as CubeSize;
         }
     }
 
-    if keyboard_input.just_pressed(KeyCode::L) {
-        if keyboard_input.pressed(KeyCode::ControlLeft) {
-            move_queue.push_back(Move::Lw(2, move_variant));
-        } else {
-            move_queue.push_back(Move::L(move_variant));
+    if change != 0 {
+        let max = WideDepth::max_for(current_cube.cube_size());
+        wide_depth.set_if_neq(WideDepth((wide_depth.0 + change).clamp(2, max)));
+    }
+}
+
+/// A number typed before a face turn, selecting how many layers the turn affects.
+///
+/// Typing `3` and then `R` turns `3Rw`, while `1` turns only the outer face. Holding Alt
+/// turns the selected layer alone, so `3` and then Alt + `R` turns `3R`.
+#[derive(Resource, Clone, Copy, Eq, PartialEq, Debug, Default)]
+pub(crate) struct LayerPrefix(pub Option<CubeSize>);
+
+const DIGIT_KEYS: [KeyCode; 9] = [
+    KeyCode::Key1,
+    KeyCode::Key2,
+    KeyCode::Key3,
+    KeyCode::Key4,
+    KeyCode::Key5,
+    KeyCode::Key6,
+    KeyCode::Key7,
+    KeyCode::Key8,
+    KeyCode::Key9,
+];
+
+// The sides of the cube, in the order of ``CubeOrientation``
+const SIDES: [Face; 6] = [Face::U, Face::L, Face::F, Face::R, Face::B, Face::D];
+
+fn side_index(face: Face) -> usize {
+    SIDES.iter().position(|&side| side == face).unwrap()
+}
+
+/// The faces of the cube at each of its sides, in the order U, L, F, R, B, D, named as they
+/// were before the whole cube rotations made and queued since the cube was solved.
+///
+/// Without screen-relative controls, the face keys name these faces of the cube, so `R` still
+/// turns the face that was on the right before a `y`, wherever it is now.
+#[derive(Resource, Clone, Copy, Eq, PartialEq, Debug)]
+pub(crate) struct CubeOrientation([Face; 6]);
+
+impl Default for CubeOrientation {
+    fn default() -> Self {
+        Self(SIDES)
+    }
+}
+
+impl CubeOrientation {
+    /// The orientation after the given moves, starting from a solved cube.
+    pub(crate) fn after<'a>(moves: impl IntoIterator<Item = &'a Move>) -> Self {
+        let mut orientation = Self::default();
+        for &move_ in moves {
+            orientation.follow(move_);
         }
+        orientation
     }
 
-    if keyboard_input.just_pressed(KeyCode::R) {
-        if keyboard_input.pressed(KeyCode::ControlLeft) {
-            move_queue.push_back(Move::Rw(2, move_variant));
-        } else {
-            move_queue.push_back(Move::R(move_variant));
+    /// Follows a whole cube rotation, the other moves leaving the orientation as it is.
+    pub(crate) fn follow(&mut self, move_: Move) {
+        // the sides each face goes through, one quarter turn at a time
+        let cycle = match move_ {
+            Move::X(_) => [Face::F, Face::U, Face::B, Face::D],
+            Move::Y(_) => [Face::F, Face::L, Face::B, Face::R],
+            Move::Z(_) => [Face::U, Face::R, Face::D, Face::L],
+            _ => return,
+        };
+        let quarter_turns = match move_.get_variant() {
+            MoveVariant::Standard => 1,
+            MoveVariant::Double => 2,
+            MoveVariant::Inverse => 3,
+        };
+
+        for _ in 0..quarter_turns {
+            let faces = self.0;
+            for (from, to) in cycle.iter().zip(cycle.iter().cycle().skip(1)) {
+                self.0[side_index(*to)] = faces[side_index(*from)];
+            }
         }
     }
 
-    if keyboard_input.just_pressed(KeyCode::U) {
-        if keyboard_input.pressed(KeyCode::ControlLeft) {
-            move_queue.push_back(Move::Uw(2, move_variant));
-        } else {
-            move_queue.push_back(Move::U(move_variant));
+    /// The side of the cube the given face is at.
+    pub(crate) fn side_of(&self, face: Face) -> Face {
+        SIDES[self.0.iter().position(|&f| f == face).unwrap()]
+    }
+}
+
+// Follows the moves made and queued, so the keys pressed while a rotation is still queued
+// turn the faces as they'll be once it's made
+fn track_orientation(
+    current_cube: Played<CurrentCube>,
+    move_queue: Played<MoveQueue>,
+    mut orientation: ResMut<CubeOrientation>,
+) {
+    if current_cube.is_changed() || move_queue.is_changed() {
+        let moves = current_cube.moves().iter().chain(move_queue.iter());
+        orientation.set_if_neq(CubeOrientation::after(moves));
+    }
+}
+
+type FaceTurn = fn(MoveVariant) -> Move;
+type LayersTurn = fn(CubeSize, MoveVariant) -> Move;
+
+const FACE_KEYS: [(KeyCode, Face, FaceTurn, LayersTurn, LayersTurn); 6] = [
+    (KeyCode::F, Face::F, Move::F, Move::Fw, Move::Fi),
+    (KeyCode::B, Face::B, Move::B, Move::Bw, Move::Bi),
+    (KeyCode::L, Face::L, Move::L, Move::Lw, Move::Li),
+    (KeyCode::R, Face::R, Move::R, Move::Rw, Move::Ri),
+    (KeyCode::U, Face::U, Move::U, Move::Uw, Move::Ui),
+    (KeyCode::D, Face::D, Move::D, Move::Dw, Move::Di),
+];
+
+fn keyboard_input_system(
+    keyboard_input: Res<Input<KeyCode>>,
+    current_cube: Played<CurrentCube>,
+    wide_depth: Res<WideDepth>,
+    settings: Res<Settings>,
+    orientation: Res<CubeOrientation>,
+    mut layer_prefix: ResMut<LayerPrefix>,
+    mut move_queue: PlayedMut<MoveQueue>,
+) {
+    let max_depth = WideDepth::max_for(current_cube.cube_size());
+    for (depth, key) in (1..).zip(DIGIT_KEYS) {
+        if keyboard_input.just_pressed(key) && depth <= max_depth {
+            layer_prefix.0 = Some(depth);
         }
     }
+    if keyboard_input.just_pressed(KeyCode::Escape) {
+        layer_prefix.set_if_neq(LayerPrefix(None));
+    }
 
-    if keyboard_input.just_pressed(KeyCode::D) {
-        if keyboard_input.pressed(KeyCode::ControlLeft) {
-            move_queue.push_back(Move::Dw(2, move_variant));
+    let move_variant = if keyboard_input.pressed(KeyCode::ShiftLeft) {
+        MoveVariant::Inverse
+    } else if keyboard_input.pressed(KeyCode::Key2) {
+        MoveVariant::Double
+    } else {
+        MoveVariant::Standard
+    };
+
+    for (key, face, ..) in FACE_KEYS {
+        if !keyboard_input.just_pressed(key) {
+            continue;
+        }
+        let side = if settings.screen_relative {
+            face
+        } else {
+            orientation.side_of(face)
+        };
+        let (.., face_turn, wide_turn, inner_turn) = FACE_KEYS
+            .into_iter()
+            .find(|&(_, key_face, ..)| key_face == side)
+            .unwrap();
+
+        // a held `2` doubles the turn rather than being a prefix
+        let prefix = layer_prefix.0.take();
+        let depth = match prefix {
+            Some(depth) if move_variant != MoveVariant::Double => depth,
+            _ if keyboard_input.pressed(KeyCode::ControlLeft) => wide_depth.0,
+            _ => 1,
+        };
+
+        if depth == 1 {
+            move_queue.push_back(face_turn(move_variant));
+        } else if keyboard_input.pressed(KeyCode::AltLeft) {
+            move_queue.push_back(inner_turn(depth, move_variant));
         } else {
-            move_queue.push_back(Move::D(move_variant));
+            move_queue.push_back(wide_turn(depth, move_variant));
         }
     }
 
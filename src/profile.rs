@@ -0,0 +1,451 @@
+use crate::{
+    achievements::ACHIEVEMENTS_PATH,
+    history::HISTORY_PATH,
+    i18n::Localized,
+    menu::{MenuButtonAction, MenuState},
+    race::RATING_PATH,
+    reconstruction::IMPORT_PATH,
+    scene::SCENE_PATH,
+    settings::SETTINGS_PATH,
+    storage,
+    theme::{Accent, Theme, ThemeColor},
+};
+use bevy::prelude::*;
+
+// The profile exported and imported by native builds, next to the files it bundles
+#[cfg(not(target_arch = "wasm32"))]
+const PROFILE_PATH: &str = "profile.rubiks";
+// The name of the profile downloaded by the web app
+#[cfg(target_arch = "wasm32")]
+const PROFILE_NAME: &str = "profile.rubiks";
+const PROFILE_HEADER: &str = "bevy_rubiks profile";
+// The files kept between runs of the app making up a profile: the settings with their macros,
+// the solves, and the cube saved
+const PROFILE_FILES: [&str; 6] = [
+    SETTINGS_PATH,
+    HISTORY_PATH,
+    ACHIEVEMENTS_PATH,
+    RATING_PATH,
+    IMPORT_PATH,
+    SCENE_PATH,
+];
+
+pub struct ProfilePlugin;
+
+impl Plugin for ProfilePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ProfileStatus>()
+            .add_systems(OnEnter(MenuState::Profile), profile_screen_setup)
+            .add_systems(
+                Update,
+                (
+                    import_dropped_profile,
+                    receive_uploaded_profile,
+                    profile_buttons,
+                    show_status,
+                )
+                    .chain()
+                    .run_if(in_state(MenuState::Profile)),
+            )
+            .add_systems(OnExit(MenuState::Profile), clean_up_screen);
+    }
+}
+
+/// Bundles the files of the profile kept so far into a single text, each file after a line
+/// with its name and its length in bytes:
+///
+/// ```text
+/// bevy_rubiks profile
+/// == settings.txt 24
+/// [scramble]
+/// length = 25
+/// ```
+fn export_profile() -> String {
+    let mut profile = format!("{PROFILE_HEADER}\n");
+    for path in PROFILE_FILES {
+        match storage::read_to_string(path) {
+            Ok(text) => profile.push_str(&format!("== {path} {}\n{text}\n", text.len())),
+            Err(err) => debug!("{path} not exported: {err}"),
+        }
+    }
+    profile
+}
+
+/// The files bundled in a profile, only the ones of `PROFILE_FILES`.
+fn read_profile(profile: &str) -> Result<Vec<(&'static str, &str)>, String> {
+    let Some(mut rest) = profile.strip_prefix(PROFILE_HEADER) else {
+        return Err("not a profile exported by the app".to_string());
+    };
+
+    let mut files = Vec::new();
+    loop {
+        rest = rest.trim_start_matches(['\r', '\n']);
+        if rest.is_empty() {
+            return Ok(files);
+        }
+        let (line, after) = rest.split_once('\n').unwrap_or((rest, ""));
+        let line = line.trim_end();
+        let Some((name, len)) = line
+            .strip_prefix("== ")
+            .and_then(|line| line.rsplit_once(' '))
+        else {
+            return Err(format!("invalid line '{line}'"));
+        };
+        let Some(&path) = PROFILE_FILES.iter().find(|&&path| path == name) else {
+            return Err(format!("unknown file {name}"));
+        };
+        let text = len
+            .parse()
+            .ok()
+            .and_then(|len: usize| after.get(..len))
+            .ok_or_else(|| format!("{name} is cut short"))?;
+        files.push((path, text));
+        rest = &after[text.len()..];
+    }
+}
+
+/// What came of the last export or import.
+#[derive(Resource)]
+struct ProfileStatus(Localized);
+
+impl Default for ProfileStatus {
+    fn default() -> Self {
+        Self(default_status())
+    }
+}
+
+impl ProfileStatus {
+    // Keeps the files of the profile in place of the ones so far, the resources loaded from
+    // them being only read again on the next run
+    fn import(&mut self, source: &str, profile: &str) {
+        let files = match read_profile(profile) {
+            Ok(files) => files,
+            Err(err) => {
+                self.0 = Localized::new("profile-import-failed")
+                    .with("source", source)
+                    .with("error", err);
+                return;
+            }
+        };
+        for (path, text) in &files {
+            if let Err(err) = storage::write(path, text) {
+                self.0 = Localized::new("profile-write-failed")
+                    .with("path", *path)
+                    .with("error", err.to_string());
+                return;
+            }
+        }
+        info!("imported {} files from {source}", files.len());
+        self.0 = Localized::new("profile-imported")
+            .with("files", files.len())
+            .with("source", source);
+        restart_web_app();
+    }
+}
+
+#[derive(Component)]
+struct OnProfileScreen;
+
+#[derive(Component)]
+struct ProfileText;
+
+#[derive(Component, Clone, Copy)]
+enum ProfileButton {
+    Export,
+    Import,
+}
+
+fn profile_screen_setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    theme: Res<Theme>,
+    accent: Res<Accent>,
+    mut status: ResMut<ProfileStatus>,
+) {
+    status.0 = default_status();
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    let text_style = TextStyle {
+        font: font.clone(),
+        font_size: 30.0,
+        color: theme.text(),
+    };
+    let button_style = Style {
+        height: Val::Px(45.0),
+        margin: UiRect::all(Val::Px(5.0)),
+        padding: UiRect::horizontal(Val::Px(10.0)),
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        ..default()
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    margin: UiRect::all(Val::Auto),
+                    padding: UiRect::all(Val::Px(20.0)),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: accent.color().into(),
+                ..default()
+            },
+            OnProfileScreen,
+            ThemeColor::Accent,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    String::new(),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 50.0,
+                        color: theme.text(),
+                    },
+                ),
+                Localized::new("settings-profile"),
+            ));
+            parent.spawn((
+                TextBundle::from_section(
+                    String::new(),
+                    TextStyle {
+                        font_size: 25.0,
+                        ..text_style.clone()
+                    },
+                )
+                .with_style(Style {
+                    max_width: Val::Px(600.0),
+                    margin: UiRect::all(Val::Px(10.0)),
+                    ..default()
+                }),
+                ProfileText,
+                status.0.clone(),
+            ));
+            for (text_id, button) in [
+                ("profile-export", ProfileButton::Export),
+                ("profile-import", ProfileButton::Import),
+            ] {
+                parent
+                    .spawn((
+                        ButtonBundle {
+                            style: button_style.clone(),
+                            background_color: theme.button(Interaction::None, false).into(),
+                            ..default()
+                        },
+                        button,
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn((
+                            TextBundle::from_section(String::new(), text_style.clone()),
+                            Localized::new(text_id),
+                        ));
+                    });
+            }
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: button_style,
+                        background_color: theme.button(Interaction::None, false).into(),
+                        ..default()
+                    },
+                    MenuButtonAction::BackToSettings,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section(String::new(), text_style),
+                        Localized::new("menu-back"),
+                    ));
+                });
+        });
+}
+
+fn profile_buttons(
+    interaction_query: Query<(&Interaction, &ProfileButton), Changed<Interaction>>,
+    mut status: ResMut<ProfileStatus>,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match button {
+            ProfileButton::Export => status.0 = save_profile(&export_profile()),
+            ProfileButton::Import => match open_profile() {
+                Ok(Some((source, profile))) => status.import(&source, &profile),
+                Ok(None) => {}
+                Err(err) => status.0 = err,
+            },
+        }
+    }
+}
+
+fn import_dropped_profile(
+    mut drop_events: EventReader<FileDragAndDrop>,
+    mut status: ResMut<ProfileStatus>,
+) {
+    for event in drop_events.read() {
+        if let FileDragAndDrop::DroppedFile { path_buf, .. } = event {
+            let source = path_buf.display().to_string();
+            match std::fs::read_to_string(path_buf) {
+                Ok(profile) => status.import(&source, &profile),
+                Err(err) => {
+                    status.0 = Localized::new("profile-import-failed")
+                        .with("source", source)
+                        .with("error", err.to_string())
+                }
+            }
+        }
+    }
+}
+
+fn show_status(status: Res<ProfileStatus>, mut q_text: Query<&mut Localized, With<ProfileText>>) {
+    if !status.is_changed() {
+        return;
+    }
+    for mut localized in &mut q_text {
+        localized.set_if_neq(status.0.clone());
+    }
+}
+
+fn clean_up_screen(mut commands: Commands, q_screen: Query<Entity, With<OnProfileScreen>>) {
+    for entity in &q_screen {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn default_status() -> Localized {
+    Localized::new("profile-about").with("path", PROFILE_PATH)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn save_profile(profile: &str) -> Localized {
+    match storage::write(PROFILE_PATH, profile) {
+        Ok(()) => Localized::new("profile-exported").with("path", PROFILE_PATH),
+        Err(err) => Localized::new("profile-export-failed")
+            .with("path", PROFILE_PATH)
+            .with("error", err.to_string()),
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn open_profile() -> Result<Option<(String, String)>, Localized> {
+    storage::read_to_string(PROFILE_PATH)
+        .map(|profile| Some((PROFILE_PATH.to_string(), profile)))
+        .map_err(|err| {
+            Localized::new("profile-import-failed")
+                .with("source", PROFILE_PATH)
+                .with("error", err.to_string())
+        })
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn receive_uploaded_profile() {}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn restart_web_app() {}
+
+#[cfg(target_arch = "wasm32")]
+fn default_status() -> Localized {
+    Localized::new("profile-about-web")
+}
+
+// The profile chosen to be uploaded, once the browser read it
+#[cfg(target_arch = "wasm32")]
+static UPLOADED: std::sync::Mutex<Option<Result<String, Localized>>> = std::sync::Mutex::new(None);
+
+// Downloads the profile, through a link to its text
+#[cfg(target_arch = "wasm32")]
+fn save_profile(profile: &str) -> Localized {
+    use wasm_bindgen::JsCast;
+
+    let href = format!("data:text/plain;charset=utf-8,{}", percent_encode(profile));
+    let link = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.create_element("a").ok())
+        .and_then(|link| link.dyn_into::<web_sys::HtmlElement>().ok());
+    let Some(link) = link else {
+        return Localized::new("profile-download-unavailable");
+    };
+    if link.set_attribute("href", &href).is_err()
+        || link.set_attribute("download", PROFILE_NAME).is_err()
+    {
+        return Localized::new("profile-download-unavailable");
+    }
+    link.click();
+    Localized::new("profile-downloaded").with("name", PROFILE_NAME)
+}
+
+#[cfg(target_arch = "wasm32")]
+fn percent_encode(text: &str) -> String {
+    text.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            byte => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+// Asks the browser for the file of a profile, read once chosen
+#[cfg(target_arch = "wasm32")]
+fn open_profile() -> Result<Option<(String, String)>, Localized> {
+    use wasm_bindgen::{closure::Closure, JsCast};
+
+    let unavailable = || Localized::new("profile-upload-unavailable");
+    let input = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.create_element("input").ok())
+        .and_then(|input| input.dyn_into::<web_sys::HtmlInputElement>().ok())
+        .ok_or_else(unavailable)?;
+    input.set_type("file");
+
+    let chosen = input.clone();
+    let on_change = Closure::<dyn FnMut()>::new(move || {
+        let Some(file) = chosen.files().and_then(|files| files.get(0)) else {
+            return;
+        };
+        let Ok(reader) = web_sys::FileReader::new() else {
+            return;
+        };
+        let read = reader.clone();
+        let on_load = Closure::<dyn FnMut()>::new(move || {
+            let profile = read
+                .result()
+                .ok()
+                .and_then(|result| result.as_string())
+                .ok_or_else(|| Localized::new("profile-unreadable"));
+            *UPLOADED.lock().unwrap_or_else(|err| err.into_inner()) = Some(profile);
+        });
+        reader.set_onload(Some(on_load.as_ref().unchecked_ref()));
+        // the closures live as long as the page, called once the file is chosen and read
+        on_load.forget();
+        let _ = reader.read_as_text(&file);
+    });
+    input.set_onchange(Some(on_change.as_ref().unchecked_ref()));
+    on_change.forget();
+    input.click();
+    Ok(None)
+}
+
+#[cfg(target_arch = "wasm32")]
+fn receive_uploaded_profile(mut status: ResMut<ProfileStatus>) {
+    let uploaded = UPLOADED
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .take();
+    match uploaded {
+        Some(Ok(profile)) => status.import(PROFILE_NAME, &profile),
+        Some(Err(err)) => status.0 = err,
+        None => {}
+    }
+}
+
+// The web app reloads the page, to read the files imported right away
+#[cfg(target_arch = "wasm32")]
+fn restart_web_app() {
+    if let Some(window) = web_sys::window() {
+        let _ = window.location().reload();
+    }
+}
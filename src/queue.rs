@@ -0,0 +1,199 @@
+use crate::{
+    i18n::Localized,
+    session::SolveSession,
+    simulator::{CurrentCube, MoveQueue, Played, PlayedMut},
+    theme::Theme,
+    GameState, Puzzle,
+};
+use bevy::prelude::*;
+
+const STOP_KEY: KeyCode = KeyCode::Space;
+
+// the number of queued moves shown, the rest being counted
+const MAX_CHIPS: usize = 24;
+const IN_FLIGHT_CHIP: Color = Color::rgb(0.35, 0.75, 0.35);
+const STOP_BUTTON: Color = Color::rgb(0.75, 0.2, 0.2);
+
+pub struct QueuePlugin;
+
+impl Plugin for QueuePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(GameState::Playing),
+            queue_ui.run_if(resource_equals(Puzzle::Cube)),
+        )
+        .add_systems(
+            Update,
+            (stop_input, chip_button_system, show_queue)
+                .chain()
+                .run_if(resource_equals(Puzzle::Cube)),
+        )
+        .add_systems(OnExit(GameState::Playing), clean_up);
+    }
+}
+
+#[derive(Component)]
+struct QueueUiRoot;
+
+// A move waiting in the queue, by its index, or the move being animated. Clicking it drops
+// the moves queued from it on.
+#[derive(Component)]
+struct QueueChip(Option<usize>);
+
+fn queue_ui(mut commands: Commands) {
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(5.0),
+                left: Val::Px(220.0),
+                right: Val::Px(220.0),
+                flex_wrap: FlexWrap::WrapReverse,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            ..default()
+        },
+        QueueUiRoot,
+    ));
+}
+
+// Stops every move queued, the move being animated still finishing.
+#[derive(Component)]
+struct StopButton;
+
+// Keeps the first queued moves only. The move being animated is already applied to the
+// cube, so stopping it too would leave the pieces out of step with the cube.
+fn truncate_queue(
+    move_queue: &mut MoveQueue,
+    current_cube: &mut CurrentCube,
+    session: &mut SolveSession,
+    len: usize,
+) {
+    if len >= move_queue.len() {
+        return;
+    }
+
+    move_queue.truncate(len);
+    // a scramble stopped half way is scrambled enough
+    current_cube.cut_scramble(len);
+    session.cut_solution(current_cube, len);
+}
+
+fn stop_input(
+    keyboard_input: Res<Input<KeyCode>>,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<StopButton>)>,
+    mut move_queue: PlayedMut<MoveQueue>,
+    mut current_cube: PlayedMut<CurrentCube>,
+    mut session: PlayedMut<SolveSession>,
+) {
+    if keyboard_input.just_pressed(STOP_KEY)
+        || interaction_query.iter().any(|i| *i == Interaction::Pressed)
+    {
+        truncate_queue(&mut move_queue, &mut current_cube, &mut session, 0);
+    }
+}
+
+fn chip_button_system(
+    interaction_query: Query<(&Interaction, &QueueChip), Changed<Interaction>>,
+    mut move_queue: PlayedMut<MoveQueue>,
+    mut current_cube: PlayedMut<CurrentCube>,
+    mut session: PlayedMut<SolveSession>,
+) {
+    for (interaction, chip) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            let len = chip.0.unwrap_or(0);
+            truncate_queue(&mut move_queue, &mut current_cube, &mut session, len);
+        }
+    }
+}
+
+fn show_queue(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    move_queue: Played<MoveQueue>,
+    theme: Res<Theme>,
+    q_root: Query<Entity, With<QueueUiRoot>>,
+) {
+    if !move_queue.is_changed() {
+        return;
+    }
+
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    let text_style = TextStyle {
+        font,
+        font_size: 20.0,
+        color: theme.text(),
+    };
+    let in_flight = move_queue.in_flight().map(|mv| (None, mv, IN_FLIGHT_CHIP));
+    let queued = move_queue
+        .iter()
+        .take(MAX_CHIPS)
+        .enumerate()
+        .map(|(i, &mv)| (Some(i), mv, theme.button(Interaction::None, false)));
+
+    for root in &q_root {
+        commands
+            .entity(root)
+            .despawn_descendants()
+            .with_children(|parent| {
+                if !move_queue.is_empty() {
+                    parent
+                        .spawn((
+                            ButtonBundle {
+                                style: Style {
+                                    margin: UiRect::all(Val::Px(2.0)),
+                                    padding: UiRect::axes(Val::Px(6.0), Val::Px(2.0)),
+                                    ..default()
+                                },
+                                background_color: STOP_BUTTON.into(),
+                                ..default()
+                            },
+                            StopButton,
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                TextBundle::from_section(String::new(), text_style.clone()),
+                                Localized::new("queue-stop"),
+                            ));
+                        });
+                }
+
+                for (index, mv, color) in in_flight.into_iter().chain(queued.clone()) {
+                    parent
+                        .spawn((
+                            ButtonBundle {
+                                style: Style {
+                                    margin: UiRect::all(Val::Px(2.0)),
+                                    padding: UiRect::axes(Val::Px(6.0), Val::Px(2.0)),
+                                    ..default()
+                                },
+                                background_color: color.into(),
+                                ..default()
+                            },
+                            QueueChip(index),
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn(TextBundle::from_section(
+                                mv.to_string(),
+                                text_style.clone(),
+                            ));
+                        });
+                }
+
+                if move_queue.len() > MAX_CHIPS {
+                    parent.spawn((
+                        TextBundle::from_section(String::new(), text_style.clone()),
+                        Localized::new("queue-more").with("moves", move_queue.len() - MAX_CHIPS),
+                    ));
+                }
+            });
+    }
+}
+
+fn clean_up(mut commands: Commands, q_ui: Query<Entity, With<QueueUiRoot>>) {
+    for entity in q_ui.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,1009 @@
+use crate::{
+    i18n::{Localized, Translations},
+    menu::{MenuButtonAction, MenuState},
+    rating::INITIAL_RATING,
+    session::SolveSession,
+    simulator::{CurrentCube, MoveQueue, PlayedMut},
+    solved::{CubeSolvedEvent, SolveMode, StopTimer},
+    storage,
+    theme::{Accent, Theme, ThemeColor},
+    CubeSizeSetting, GameState, Puzzle,
+};
+use bevy::{ecs::system::SystemParam, prelude::*};
+use cubesim::{parse_algorithm_for, prelude::*, Penalty};
+use serde::{Deserialize, Serialize};
+#[cfg(all(feature = "online", not(target_arch = "wasm32")))]
+use std::{sync::mpsc, thread};
+use std::{
+    sync::{
+        mpsc::{Receiver, Sender},
+        Mutex,
+    },
+    time::Duration,
+};
+
+// The port of the race server, on the instance hosting the races
+const RACE_PORT: u16 = 9003;
+const PLUS_TWO: Duration = Duration::from_secs(2);
+// The rating of the racer and the number of races it counts, on one line
+pub(crate) const RATING_PATH: &str = "rating.txt";
+
+pub struct RacePlugin;
+
+impl Plugin for RacePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RaceOptions>()
+            .insert_resource(RaceRating::load())
+            .add_systems(Startup, host_races)
+            .add_systems(OnEnter(MenuState::Race), (connect, race_screen_setup))
+            .add_systems(
+                Update,
+                receive_race_events.run_if(resource_exists::<RaceClient>()),
+            )
+            .add_systems(
+                Update,
+                (list_lobbies, lobby_buttons)
+                    .chain()
+                    .run_if(in_state(MenuState::Race))
+                    .run_if(resource_exists::<RaceClient>()),
+            )
+            .add_systems(OnExit(MenuState::Race), (leave_lobby, clean_up_screen))
+            .add_systems(
+                OnEnter(GameState::Playing),
+                race_ui.run_if(resource_exists::<Race>()),
+            )
+            .add_systems(
+                Update,
+                (start_race, finish_race, race_buttons, show_race)
+                    .chain()
+                    .after(receive_race_events)
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(resource_exists::<Race>()),
+            )
+            .add_systems(OnExit(GameState::Playing), clean_up)
+            .add_systems(
+                Update,
+                back_to_lobby
+                    .run_if(in_state(MenuState::Main))
+                    .run_if(resource_exists::<Race>()),
+            )
+            .add_systems(Last, save_rating);
+    }
+}
+
+/// Where to race others, from the training menu: the instance started with the
+/// `--race-server` command line flag hosts the lobbies, and the others join them with
+/// `--race=HOST`, as the name given with `--name=NAME`. Only available with the `online`
+/// feature, on native builds.
+#[derive(Resource, Clone, Eq, PartialEq, Debug)]
+pub struct RaceOptions {
+    host: bool,
+    address: String,
+    name: String,
+}
+
+impl Default for RaceOptions {
+    fn default() -> Self {
+        RaceOptions {
+            host: false,
+            address: format!("127.0.0.1:{RACE_PORT}"),
+            name: std::env::var("USER")
+                .or_else(|_| std::env::var("USERNAME"))
+                .unwrap_or_else(|_| "Racer".to_string()),
+        }
+    }
+}
+
+impl RaceOptions {
+    /// The name to race as.
+    pub(crate) fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The options given on the command line, as `--race-server`, `--race=HOST`,
+    /// `--race=HOST:PORT` and `--name=NAME`. The host of the races joins its own lobbies.
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Result<RaceOptions, String> {
+        let mut options = RaceOptions::default();
+        for arg in args {
+            if arg == "--race-server" {
+                options.host = true;
+            } else if let Some(address) = arg.strip_prefix("--race=") {
+                if address.is_empty() {
+                    return Err("expected the address to race at, as --race=HOST".to_string());
+                }
+                options.address = match address.contains(':') {
+                    true => address.to_string(),
+                    false => format!("{address}:{RACE_PORT}"),
+                };
+            } else if let Some(name) = arg.strip_prefix("--name=") {
+                options.name = name.to_string();
+            }
+        }
+        Ok(options)
+    }
+}
+
+/// What a racer sends the race server, each as a JSON message with a `command` field.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub(crate) enum RaceCommand {
+    /// Lists the lobbies open.
+    Lobbies,
+    /// Joins the lobby, opening it for cubes of the size if it isn't open yet.
+    Join {
+        lobby: String,
+        name: String,
+        size: usize,
+    },
+    Leave,
+    /// Gets ready for the next race of the lobby, which starts once all its racers are.
+    Ready {
+        ready: bool,
+    },
+    /// Ends the race under way, in the time with its penalty, or none for a DNF.
+    Finish {
+        time_ms: Option<u64>,
+        moves: usize,
+    },
+}
+
+/// What the race server sends the racers, each as a JSON message with an `event` field.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub(crate) enum RaceEvent {
+    Lobbies {
+        lobbies: Vec<LobbySummary>,
+    },
+    /// The racers of the lobby joined, sent again whenever one of them joins, leaves, gets
+    /// ready or finishes.
+    Lobby {
+        name: String,
+        size: usize,
+        // the name of the racer, unique in the lobby
+        you: String,
+        racers: Vec<LobbyRacer>,
+    },
+    /// Starts the race with the scramble, once the countdown is over.
+    Start {
+        scramble: String,
+        size: usize,
+        countdown_ms: u64,
+    },
+    /// The results of the race, once every racer finished, the fastest first, with the
+    /// changes of the ratings of the racers.
+    Results {
+        results: Vec<RaceResult>,
+    },
+    Error {
+        message: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub(crate) struct LobbySummary {
+    pub name: String,
+    pub size: usize,
+    pub racers: usize,
+    pub racing: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub(crate) struct LobbyRacer {
+    pub name: String,
+    pub rating: f64,
+    pub ready: bool,
+    pub racing: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub(crate) struct RaceResult {
+    pub name: String,
+    // with the penalty, or none for a DNF
+    pub time_ms: Option<u64>,
+    pub moves: usize,
+    // the rating raced with, and how much the race changed it
+    pub rating: f64,
+    pub change: f64,
+}
+
+impl RaceResult {
+    /// Written like ``Ana (1512): 12.34 s, 45 moves, 3.65 TPS, +8`` in English.
+    fn text(&self, translations: &Translations) -> String {
+        let result = match self.time_ms {
+            Some(millis) => {
+                let seconds = millis as f32 / 1000.0;
+                let tps = if seconds > 0.0 {
+                    self.moves as f32 / seconds
+                } else {
+                    0.0
+                };
+                Localized::new("race-result")
+                    .with("seconds", format!("{seconds:.2}"))
+                    .with("tps", format!("{tps:.2}"))
+            }
+            None => Localized::new("race-result-dnf"),
+        };
+        translations.text(
+            result
+                .with("name", self.name.as_str())
+                .with("rating", format!("{:.0}", self.rating))
+                .with("moves", self.moves)
+                .with("change", format!("{:+.0}", self.change)),
+        )
+    }
+}
+
+// The commands sent to the race server, and what it sends back until the connection closes
+type RaceConnection = (Sender<RaceCommand>, Receiver<Result<RaceEvent, Localized>>);
+
+// The lobby joined, as the server last sent it
+struct JoinedLobby {
+    name: String,
+    size: usize,
+    you: String,
+    racers: Vec<LobbyRacer>,
+}
+
+impl JoinedLobby {
+    fn is_ready(&self) -> bool {
+        self.racers
+            .iter()
+            .any(|racer| racer.name == self.you && racer.ready)
+    }
+}
+
+// The connection to the race server, kept between the races, and what it last sent
+#[derive(Resource)]
+struct RaceClient {
+    commands: Option<Sender<RaceCommand>>,
+    events: Option<Mutex<Receiver<Result<RaceEvent, Localized>>>>,
+    lobbies: Vec<LobbySummary>,
+    lobby: Option<JoinedLobby>,
+    results: Vec<RaceResult>,
+    message: Option<Localized>,
+}
+
+impl RaceClient {
+    fn send(&mut self, command: RaceCommand) {
+        let sent = self
+            .commands
+            .as_ref()
+            .is_some_and(|commands| commands.send(command).is_ok());
+        if !sent {
+            self.commands = None;
+            self.message = Some(Localized::new("race-not-connected"));
+        }
+    }
+}
+
+/// The racer's rating, updated with the results of each race against the ratings of the
+/// others, and kept between runs of the app.
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
+pub(crate) struct RaceRating {
+    pub rating: f64,
+    pub races: usize,
+}
+
+impl Default for RaceRating {
+    fn default() -> Self {
+        RaceRating {
+            rating: INITIAL_RATING,
+            races: 0,
+        }
+    }
+}
+
+impl RaceRating {
+    fn load() -> Self {
+        let text = match storage::read_to_string(RATING_PATH) {
+            Ok(text) => text,
+            Err(err) => {
+                debug!("no rating loaded from {RATING_PATH}: {err}");
+                return Self::default();
+            }
+        };
+
+        let mut fields = text.split_whitespace();
+        match (
+            fields.next().and_then(|rating| rating.parse().ok()),
+            fields.next().and_then(|races| races.parse().ok()),
+        ) {
+            (Some(rating), Some(races)) => RaceRating { rating, races },
+            _ => {
+                warn!("skipping the invalid rating in {RATING_PATH}");
+                Self::default()
+            }
+        }
+    }
+
+    fn to_text(self) -> String {
+        format!("{:.1} {}\n", self.rating, self.races)
+    }
+}
+
+fn save_rating(rating: Res<RaceRating>) {
+    // the rating was just loaded from the file
+    if !rating.is_changed() || rating.is_added() {
+        return;
+    }
+
+    if let Err(err) = storage::write(RATING_PATH, &rating.to_text()) {
+        warn!("failed to save the rating to {RATING_PATH}: {err}");
+    }
+}
+
+/// A race started from a lobby, once all its racers got ready: the cube is scrambled at the
+/// end of the countdown, and the solve's time and moves sent once it's solved or the timer
+/// stopped. The results are shown once every racer finished, the solve kept in the history
+/// as a race.
+#[derive(Resource)]
+struct Race {
+    size: usize,
+    scramble: Vec<Move>,
+    // when the countdown ends, by the app's elapsed time
+    go: Duration,
+    scrambled: bool,
+    finished: bool,
+}
+
+#[derive(Component)]
+struct OnRaceScreen;
+
+// The lobbies open or the racers of the lobby joined, rebuilt once the server sends them
+#[derive(Component)]
+struct LobbyList;
+
+#[derive(Component, Clone)]
+enum LobbyButton {
+    Join(String),
+    Open,
+    Refresh,
+    Ready(bool),
+    Leave,
+}
+
+#[derive(Component)]
+struct RaceUiRoot;
+
+#[derive(Component)]
+struct RaceText;
+
+#[derive(Component, Clone, Copy)]
+enum RaceButton {
+    Stop,
+    Lobby,
+}
+
+fn host_races(options: Res<RaceOptions>) {
+    if options.host {
+        if let Err(err) = open_race_server() {
+            warn!("no races hosted: {err}");
+        }
+    }
+}
+
+#[cfg(all(feature = "online", not(target_arch = "wasm32")))]
+fn open_race_server() -> Result<(), String> {
+    crate::race_server::host_races(RACE_PORT)
+}
+
+#[cfg(not(all(feature = "online", not(target_arch = "wasm32"))))]
+fn open_race_server() -> Result<(), String> {
+    Err("Built without the online feature, only available on native builds".to_string())
+}
+
+#[cfg(all(feature = "online", not(target_arch = "wasm32")))]
+fn open_race_client(address: &str) -> Result<RaceConnection, Localized> {
+    use crate::race_server::{exchange, POLL_INTERVAL};
+    use tungstenite::stream::MaybeTlsStream;
+
+    let url = format!("ws://{address}");
+    let (command_sender, commands) = mpsc::channel();
+    let (event_sender, events) = mpsc::channel();
+    // an unreachable server is only given up on after the system's connect timeout, the
+    // commands sent meanwhile waiting for the connection
+    thread::spawn(move || {
+        let exchanged = tungstenite::connect(&url)
+            .map_err(|err| {
+                Localized::new("race-connection-failed")
+                    .with("url", url.as_str())
+                    .with("error", err.to_string())
+            })
+            .and_then(|(mut socket, _)| {
+                let lost = |err: String| Localized::new("race-connection-lost").with("error", err);
+                if let MaybeTlsStream::Plain(stream) = socket.get_mut() {
+                    stream
+                        .set_read_timeout(Some(POLL_INTERVAL))
+                        .map_err(|err| lost(err.to_string()))?;
+                }
+                exchange(&mut socket, &commands, |event| {
+                    let event = event
+                        .map_err(|err| Localized::new("race-invalid-message").with("error", err));
+                    event_sender.send(event).is_ok()
+                })
+                .map_err(lost)
+            });
+        let closed = match exchanged {
+            Ok(()) => Localized::new("race-closed"),
+            Err(err) => err,
+        };
+        let _ = event_sender.send(Err(closed));
+    });
+    Ok((command_sender, events))
+}
+
+#[cfg(not(all(feature = "online", not(target_arch = "wasm32"))))]
+fn open_race_client(_address: &str) -> Result<RaceConnection, Localized> {
+    Err(Localized::new("race-unavailable"))
+}
+
+// Connects to the race server on opening the screen, unless still connected from a race
+fn connect(mut commands: Commands, options: Res<RaceOptions>, client: Option<ResMut<RaceClient>>) {
+    if let Some(mut client) = client.filter(|client| client.commands.is_some()) {
+        client.send(RaceCommand::Lobbies);
+        return;
+    }
+
+    let mut client = RaceClient {
+        commands: None,
+        events: None,
+        lobbies: Vec::new(),
+        lobby: None,
+        results: Vec::new(),
+        message: None,
+    };
+    match open_race_client(&options.address) {
+        Ok((sender, events)) => {
+            client.commands = Some(sender);
+            client.events = Some(Mutex::new(events));
+            // a failed connection is received like the events
+            let racing = Localized::new("race-connected")
+                .with("address", options.address.as_str())
+                .with("name", options.name.as_str());
+            client.message = Some(racing);
+            client.send(RaceCommand::Lobbies);
+        }
+        Err(err) => client.message = Some(err),
+    }
+    commands.insert_resource(client);
+}
+
+// What starting a race sets up
+#[derive(SystemParam)]
+struct RaceSetup<'w> {
+    puzzle: ResMut<'w, Puzzle>,
+    cube_size: ResMut<'w, CubeSizeSetting>,
+    game_state: ResMut<'w, NextState<GameState>>,
+    menu_state: ResMut<'w, NextState<MenuState>>,
+}
+
+// Keeps what the server sends, starting the race it sends from the lobby, and rating the
+// racer with its results
+fn receive_race_events(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut client: ResMut<RaceClient>,
+    mut rating: ResMut<RaceRating>,
+    mut setup: RaceSetup,
+) {
+    let received: Vec<_> = match &client.events {
+        Some(events) => {
+            let events = events.lock().unwrap_or_else(|err| err.into_inner());
+            events.try_iter().collect()
+        }
+        None => return,
+    };
+
+    for event in received {
+        match event {
+            Ok(RaceEvent::Lobbies { lobbies }) => client.lobbies = lobbies,
+            Ok(RaceEvent::Lobby {
+                name,
+                size,
+                you,
+                racers,
+            }) => {
+                client.lobby = Some(JoinedLobby {
+                    name,
+                    size,
+                    you,
+                    racers,
+                });
+            }
+            Ok(RaceEvent::Start {
+                scramble,
+                size,
+                countdown_ms,
+            }) => {
+                let scramble = match parse_algorithm_for(&scramble, size as CubeSize) {
+                    Ok(scramble) => scramble,
+                    Err(err) => {
+                        let invalid =
+                            Localized::new("race-invalid-scramble").with("error", err.to_string());
+                        client.message = Some(invalid);
+                        client.send(RaceCommand::Finish {
+                            time_ms: None,
+                            moves: 0,
+                        });
+                        continue;
+                    }
+                };
+                client.results.clear();
+                commands.insert_resource(Race {
+                    size,
+                    scramble,
+                    go: time.elapsed() + Duration::from_millis(countdown_ms),
+                    scrambled: false,
+                    finished: false,
+                });
+                *setup.puzzle = Puzzle::Cube;
+                setup.cube_size.0 = size;
+                setup.game_state.set(GameState::Playing);
+                setup.menu_state.set(MenuState::Disabled);
+            }
+            Ok(RaceEvent::Results { results }) => {
+                let you = client.lobby.as_ref().map(|lobby| lobby.you.as_str());
+                // the server may have raced with the rating it keeps rather than ours
+                if let Some(result) = results.iter().find(|result| Some(&*result.name) == you) {
+                    rating.rating = result.rating + result.change;
+                    rating.races += 1;
+                }
+                client.results = results;
+            }
+            Ok(RaceEvent::Error { message }) => {
+                client.message = Some(Localized::new("race-server-error").with("message", message));
+            }
+            Err(err) => {
+                client.commands = None;
+                client.events = None;
+                client.lobby = None;
+                client.message = Some(err);
+            }
+        }
+    }
+}
+
+fn race_screen_setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    theme: Res<Theme>,
+    accent: Res<Accent>,
+) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    let text_style = TextStyle {
+        font: font.clone(),
+        font_size: 30.0,
+        color: theme.text(),
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    margin: UiRect::all(Val::Auto),
+                    padding: UiRect::all(Val::Px(20.0)),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: accent.color().into(),
+                ..default()
+            },
+            OnRaceScreen,
+            ThemeColor::Accent,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    String::new(),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 50.0,
+                        color: theme.text(),
+                    },
+                ),
+                Localized::new("training-race"),
+            ));
+            parent.spawn((
+                NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    ..default()
+                },
+                LobbyList,
+            ));
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: Style {
+                            height: Val::Px(45.0),
+                            margin: UiRect::all(Val::Px(5.0)),
+                            padding: UiRect::horizontal(Val::Px(10.0)),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        background_color: theme.button(Interaction::None, false).into(),
+                        ..default()
+                    },
+                    MenuButtonAction::BackToTraining,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section(String::new(), text_style),
+                        Localized::new("menu-back"),
+                    ));
+                });
+        });
+}
+
+// Lists the lobbies open, or the racers of the lobby joined and the results of its last race
+fn list_lobbies(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    theme: Res<Theme>,
+    client: Res<RaceClient>,
+    rating: Res<RaceRating>,
+    translations: Translations,
+    q_list: Query<(Entity, Ref<LobbyList>)>,
+) {
+    let added = q_list.iter().any(|(_, list)| list.is_added());
+    if !added && !client.is_changed() && !rating.is_changed() {
+        return;
+    }
+    let text_style = TextStyle {
+        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+        font_size: 25.0,
+        color: theme.text(),
+    };
+
+    let your_rating = Localized::new("race-your-rating")
+        .with("rating", format!("{:.0}", rating.rating))
+        .with("races", rating.races);
+    let mut lines = vec![
+        client
+            .message
+            .clone()
+            .map(|message| translations.text(message))
+            .unwrap_or_default(),
+        translations.text(your_rating),
+    ];
+    let mut buttons = Vec::new();
+    match &client.lobby {
+        _ if client.commands.is_none() => {}
+        Some(lobby) => {
+            lines.push(
+                translations.text(
+                    Localized::new("race-lobby")
+                        .with("lobby", lobby.name.as_str())
+                        .with("size", lobby.size),
+                ),
+            );
+            lines.extend(lobby.racers.iter().map(|racer| {
+                let state = match (racer.racing, racer.ready) {
+                    (true, _) => "race-racer-racing",
+                    (false, true) => "race-racer-ready",
+                    (false, false) => "race-racer-not-ready",
+                };
+                translations.text(
+                    Localized::new(state)
+                        .with("name", racer.name.as_str())
+                        .with("rating", format!("{:.0}", racer.rating)),
+                )
+            }));
+            if !client.results.is_empty() {
+                lines.push(translations.get("race-last-race"));
+                lines.extend(
+                    client
+                        .results
+                        .iter()
+                        .enumerate()
+                        .map(|(i, result)| format!("{}. {}", i + 1, result.text(&translations))),
+                );
+            }
+            buttons.push(match lobby.is_ready() {
+                true => (Localized::new("race-not-ready"), LobbyButton::Ready(false)),
+                false => (Localized::new("race-ready"), LobbyButton::Ready(true)),
+            });
+            buttons.push((Localized::new("race-leave"), LobbyButton::Leave));
+        }
+        None => {
+            for lobby in &client.lobbies {
+                let join = match lobby.racing {
+                    true => Localized::new("race-join-racing"),
+                    false => Localized::new("race-join"),
+                };
+                buttons.push((
+                    join.with("lobby", lobby.name.as_str())
+                        .with("size", lobby.size)
+                        .with("racers", lobby.racers),
+                    LobbyButton::Join(lobby.name.clone()),
+                ));
+            }
+            buttons.push((Localized::new("race-open"), LobbyButton::Open));
+            buttons.push((Localized::new("race-refresh"), LobbyButton::Refresh));
+        }
+    }
+
+    for (list, _) in &q_list {
+        commands
+            .entity(list)
+            .despawn_descendants()
+            .with_children(|parent| {
+                parent.spawn(TextBundle::from_section(
+                    lines.join("\n"),
+                    text_style.clone(),
+                ));
+                for (text, button) in &buttons {
+                    parent
+                        .spawn((
+                            ButtonBundle {
+                                style: Style {
+                                    margin: UiRect::all(Val::Px(3.0)),
+                                    padding: UiRect::axes(Val::Px(10.0), Val::Px(5.0)),
+                                    ..default()
+                                },
+                                background_color: theme.button(Interaction::None, false).into(),
+                                ..default()
+                            },
+                            button.clone(),
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                TextBundle::from_section(String::new(), text_style.clone()),
+                                text.clone(),
+                            ));
+                        });
+                }
+            });
+    }
+}
+
+fn lobby_buttons(
+    interaction_query: Query<(&Interaction, &LobbyButton), Changed<Interaction>>,
+    options: Res<RaceOptions>,
+    cube_size: Res<CubeSizeSetting>,
+    mut client: ResMut<RaceClient>,
+    translations: Translations,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let join = |lobby: String| RaceCommand::Join {
+            lobby,
+            name: options.name.clone(),
+            size: cube_size.0,
+        };
+        match button.clone() {
+            LobbyButton::Join(lobby) => client.send(join(lobby)),
+            // named after the racer opening it, for the cubes of the settings
+            LobbyButton::Open => {
+                let lobby = Localized::new("race-lobby-name").with("name", options.name.as_str());
+                client.send(join(translations.text(lobby)));
+            }
+            LobbyButton::Refresh => client.send(RaceCommand::Lobbies),
+            LobbyButton::Ready(ready) => client.send(RaceCommand::Ready { ready }),
+            LobbyButton::Leave => {
+                client.lobby = None;
+                client.results.clear();
+                client.send(RaceCommand::Leave);
+            }
+        }
+    }
+}
+
+// Leaving the screen leaves the lobby, but for its race
+fn leave_lobby(race: Option<Res<Race>>, client: Option<ResMut<RaceClient>>) {
+    let Some(mut client) = client.filter(|client| client.lobby.is_some()) else {
+        return;
+    };
+    if race.is_none() {
+        client.lobby = None;
+        client.results.clear();
+        client.send(RaceCommand::Leave);
+    }
+}
+
+fn race_ui(mut commands: Commands, asset_server: Res<AssetServer>, theme: Res<Theme>) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    let text_style = TextStyle {
+        font,
+        font_size: 25.0,
+        color: theme.text(),
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    bottom: Val::Px(10.0),
+                    left: Val::Px(220.0),
+                    max_width: Val::Px(600.0),
+                    padding: UiRect::all(Val::Px(10.0)),
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+                ..default()
+            },
+            ThemeColor::Panel,
+            RaceUiRoot,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(String::new(), text_style.clone()),
+                RaceText,
+            ));
+            parent.spawn(NodeBundle::default()).with_children(|parent| {
+                for (text_id, button) in [
+                    ("race-stop", RaceButton::Stop),
+                    ("race-back-to-lobby", RaceButton::Lobby),
+                ] {
+                    parent.spawn((
+                        TextBundle::from_section(String::new(), text_style.clone()).with_style(
+                            Style {
+                                margin: UiRect::right(Val::Px(20.0)),
+                                ..default()
+                            },
+                        ),
+                        Localized::new(text_id),
+                        button,
+                        Interaction::None,
+                    ));
+                }
+            });
+        });
+}
+
+// Keeps the cube still during the countdown, then scrambles it for every racer at once
+fn start_race(
+    time: Res<Time>,
+    mut race: ResMut<Race>,
+    mut current_cube: PlayedMut<CurrentCube>,
+    mut move_queue: PlayedMut<MoveQueue>,
+    mut session: PlayedMut<SolveSession>,
+) {
+    if race.scrambled || current_cube.cube_size() != race.size {
+        return;
+    }
+    // the moves made before the end of the countdown are dropped
+    move_queue.clear();
+    if time.elapsed() < race.go {
+        move_queue.hold();
+        return;
+    }
+
+    session.queue_scramble(&mut current_cube, &mut move_queue, race.scramble.clone());
+    session.mark_raced();
+    move_queue.release();
+    race.scrambled = true;
+}
+
+// Sends the time of the race's solve, the solver's help making it a DNF
+fn finish_race(
+    mut solved_events: EventReader<CubeSolvedEvent>,
+    mut race: ResMut<Race>,
+    mut client: ResMut<RaceClient>,
+) {
+    for solved in solved_events.read() {
+        if !race.scrambled || race.finished {
+            continue;
+        }
+        let duration = solved.report.stats.duration;
+        let time = match solved.report.penalty {
+            _ if solved.mode != SolveMode::Race => None,
+            Some(Penalty::Dnf) => None,
+            Some(Penalty::PlusTwo) => Some(duration + PLUS_TWO),
+            None => Some(duration),
+        };
+        client.send(RaceCommand::Finish {
+            time_ms: time.map(|time| time.as_millis() as u64),
+            moves: solved.solution_length,
+        });
+        race.finished = true;
+    }
+}
+
+fn race_buttons(
+    interaction_query: Query<(&Interaction, &RaceButton), Changed<Interaction>>,
+    mut stop_events: EventWriter<StopTimer>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match button {
+            RaceButton::Stop => stop_events.send(StopTimer),
+            RaceButton::Lobby => game_state.set(GameState::Menu),
+        }
+    }
+}
+
+fn show_race(
+    time: Res<Time>,
+    race: Res<Race>,
+    client: Res<RaceClient>,
+    translations: Translations,
+    mut q_text: Query<&mut Text, With<RaceText>>,
+) {
+    let now = time.elapsed();
+    let mut lines = Vec::new();
+    if !client.results.is_empty() {
+        lines.push(translations.get("race-results"));
+        lines.extend(
+            client
+                .results
+                .iter()
+                .enumerate()
+                .map(|(i, result)| format!("{}. {}", i + 1, result.text(&translations))),
+        );
+    } else if race.finished {
+        lines.push(translations.get("race-finished"));
+    } else if now < race.go {
+        let left = (race.go - now).as_secs_f32().ceil() as u64;
+        lines.push(translations.text(Localized::new("race-countdown").with("seconds", left)));
+    } else {
+        lines.push(translations.get("race-go"));
+    }
+    if client.results.is_empty() {
+        if let Some(lobby) = &client.lobby {
+            let racing: Vec<&str> = lobby
+                .racers
+                .iter()
+                .filter(|racer| racer.racing)
+                .map(|racer| racer.name.as_str())
+                .collect();
+            let racing = Localized::new("race-racing").with("racers", racing.join(", "));
+            lines.push(translations.text(racing));
+        }
+    }
+    if let Some(message) = &client.message {
+        lines.push(translations.text(message.clone()));
+    }
+
+    let message = lines.join("\n");
+    for mut text in &mut q_text {
+        if text.sections[0].value != message {
+            text.sections[0].value = message.clone();
+        }
+    }
+}
+
+fn clean_up(
+    mut commands: Commands,
+    mut move_queue: PlayedMut<MoveQueue>,
+    q_ui: Query<Entity, With<RaceUiRoot>>,
+) {
+    move_queue.release();
+    for entity in &q_ui {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+// Leaving the game ends the race, as a DNF if it isn't finished, and goes back to the lobby
+fn back_to_lobby(
+    mut commands: Commands,
+    race: Res<Race>,
+    mut client: ResMut<RaceClient>,
+    mut menu_state: ResMut<NextState<MenuState>>,
+) {
+    if !race.finished {
+        client.send(RaceCommand::Finish {
+            time_ms: None,
+            moves: 0,
+        });
+    }
+    commands.remove_resource::<Race>();
+    menu_state.set(MenuState::Race);
+}
+
+fn clean_up_screen(mut commands: Commands, q_screen: Query<Entity, With<OnRaceScreen>>) {
+    for entity in &q_screen {
+        commands.entity(entity).despawn_recursive();
+    }
+}
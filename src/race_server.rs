@@ -0,0 +1,663 @@
+use crate::{
+    race::{LobbyRacer, LobbySummary, RaceCommand, RaceEvent, RaceResult},
+    rating::{rating_changes, INITIAL_RATING},
+    storage,
+};
+use bevy::prelude::*;
+use cubesim::{prelude::*, random_scramble};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    collections::BTreeMap,
+    io::{ErrorKind, Read, Write},
+    net::{TcpListener, TcpStream},
+    sync::{
+        mpsc::{self, Receiver, Sender, TryRecvError},
+        Arc, Mutex,
+    },
+    thread,
+    time::Duration,
+};
+use tungstenite::{Error, Message, WebSocket};
+
+// A race starts once this many racers of its lobby are ready, and all of them are
+const MIN_RACERS: usize = 2;
+// The countdown of each race, from when the server sends its scramble
+const COUNTDOWN: Duration = Duration::from_secs(3);
+// How long the connections wait for a message before sending theirs
+pub(crate) const POLL_INTERVAL: Duration = Duration::from_millis(50);
+// How long a connection may take to open its WebSocket before it is dropped
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+// The ratings of the racers who raced on this server, one per line after their name's rating
+const RATINGS_PATH: &str = "race-ratings.txt";
+
+/// Hosts the lobbies of the races on the port, to every network interface, each racer
+/// connecting over a WebSocket. The ratings of the racers are kept by name, racers new to the
+/// server starting from the initial rating whatever their own.
+pub(crate) fn host_races(port: u16) -> Result<(), String> {
+    let listener = TcpListener::bind(("0.0.0.0", port))
+        .map_err(|err| format!("port {port} can't be opened: {err}"))?;
+    info!("hosting races on port {port}");
+
+    let lobbies = Arc::new(Mutex::new(Lobbies {
+        ratings: load_ratings(),
+        ..default()
+    }));
+    thread::spawn(move || {
+        for (id, stream) in listener.incoming().flatten().enumerate() {
+            let lobbies = lobbies.clone();
+            thread::spawn(move || {
+                if let Err(err) = handle_racer(stream, id, &lobbies) {
+                    debug!("racer {id} left: {err}");
+                }
+            });
+        }
+    });
+    Ok(())
+}
+
+/// Exchanges messages over the WebSocket until either side closes, sending the ones of
+/// `outgoing` and handing the ones received to `incoming`, which stops once it returns false.
+pub(crate) fn exchange<S, In, Out>(
+    socket: &mut WebSocket<S>,
+    outgoing: &Receiver<Out>,
+    mut incoming: impl FnMut(Result<In, String>) -> bool,
+) -> Result<(), String>
+where
+    S: Read + Write,
+    In: DeserializeOwned,
+    Out: Serialize,
+{
+    loop {
+        // the reads time out after the poll interval, to send the messages waiting
+        match socket.read() {
+            Ok(Message::Text(text)) => {
+                let message = serde_json::from_str(&text).map_err(|err| err.to_string());
+                if !incoming(message) {
+                    return Ok(());
+                }
+            }
+            Ok(Message::Close(_)) | Err(Error::ConnectionClosed) => return Ok(()),
+            Ok(_) => {}
+            Err(Error::Io(err))
+                if matches!(err.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+            Err(err) => return Err(err.to_string()),
+        }
+
+        loop {
+            let message = match outgoing.try_recv() {
+                Ok(message) => message,
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => return Ok(()),
+            };
+            let text = serde_json::to_string(&message).map_err(|err| err.to_string())?;
+            socket
+                .send(Message::Text(text))
+                .map_err(|err| err.to_string())?;
+        }
+    }
+}
+
+fn handle_racer(stream: TcpStream, id: usize, lobbies: &Mutex<Lobbies>) -> Result<(), String> {
+    // a connection that never opens its WebSocket would hold its thread forever
+    stream
+        .set_read_timeout(Some(HANDSHAKE_TIMEOUT))
+        .map_err(|err| err.to_string())?;
+    let mut socket = tungstenite::accept(stream).map_err(|err| err.to_string())?;
+    socket
+        .get_mut()
+        .set_read_timeout(Some(POLL_INTERVAL))
+        .map_err(|err| err.to_string())?;
+
+    let lock = || lobbies.lock().unwrap_or_else(|err| err.into_inner());
+    let (sender, events) = mpsc::channel();
+    lock().connect(id, sender);
+    let exchanged = exchange(&mut socket, &events, |command| {
+        match command {
+            Ok(command) => {
+                let mut lobbies = lock();
+                lobbies.handle(id, command);
+                lobbies.save_if_rated();
+            }
+            Err(err) => lock().send(
+                id,
+                RaceEvent::Error {
+                    message: format!("invalid command: {err}"),
+                },
+            ),
+        }
+        true
+    });
+    let mut lobbies = lock();
+    lobbies.disconnect(id);
+    lobbies.save_if_rated();
+    exchanged
+}
+
+fn load_ratings() -> BTreeMap<String, f64> {
+    let text = match storage::read_to_string(RATINGS_PATH) {
+        Ok(text) => text,
+        Err(err) => {
+            debug!("no ratings loaded from {RATINGS_PATH}: {err}");
+            return BTreeMap::new();
+        }
+    };
+
+    text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| {
+            let rating = line
+                .split_once(' ')
+                .and_then(|(rating, name)| Some((name.to_string(), rating.parse().ok()?)));
+            if rating.is_none() {
+                warn!("skipping invalid rating '{line}' in {RATINGS_PATH}");
+            }
+            rating
+        })
+        .collect()
+}
+
+fn save_ratings(ratings: &BTreeMap<String, f64>) {
+    let text: String = ratings
+        .iter()
+        .map(|(name, rating)| format!("{rating:.1} {name}\n"))
+        .collect();
+    if let Err(err) = storage::write(RATINGS_PATH, &text) {
+        warn!("failed to save the ratings to {RATINGS_PATH}: {err}");
+    }
+}
+
+struct Racer {
+    name: String,
+    rating: f64,
+    lobby: Option<String>,
+    ready: bool,
+    // racing in the race under way, until finished
+    racing: bool,
+    events: Sender<RaceEvent>,
+}
+
+struct Lobby {
+    size: usize,
+    // the number of racers of the race under way, and the results of those finished
+    racers: usize,
+    results: Vec<RaceResult>,
+}
+
+// The racers connected and the lobbies they joined, each lobby closed once its last racer
+// leaves
+#[derive(Default)]
+struct Lobbies {
+    racers: BTreeMap<usize, Racer>,
+    lobbies: BTreeMap<String, Lobby>,
+    ratings: BTreeMap<String, f64>,
+    // whether a race changed the ratings since they were saved
+    rated: bool,
+}
+
+impl Lobbies {
+    fn connect(&mut self, id: usize, events: Sender<RaceEvent>) {
+        self.racers.insert(
+            id,
+            Racer {
+                name: String::new(),
+                rating: INITIAL_RATING,
+                lobby: None,
+                ready: false,
+                racing: false,
+                events,
+            },
+        );
+    }
+
+    fn disconnect(&mut self, id: usize) {
+        self.leave(id);
+        self.racers.remove(&id);
+    }
+
+    fn send(&self, id: usize, event: RaceEvent) {
+        if let Some(racer) = self.racers.get(&id) {
+            // the racer's connection may be closing
+            let _ = racer.events.send(event);
+        }
+    }
+
+    fn members(&self, lobby: &str) -> Vec<usize> {
+        self.racers
+            .iter()
+            .filter(|(_, racer)| racer.lobby.as_deref() == Some(lobby))
+            .map(|(&id, _)| id)
+            .collect()
+    }
+
+    fn handle(&mut self, id: usize, command: RaceCommand) {
+        match command {
+            RaceCommand::Lobbies => self.send(id, self.summaries()),
+            RaceCommand::Join { lobby, name, size } => self.join(id, lobby, &name, size),
+            RaceCommand::Leave => {
+                self.leave(id);
+                self.send(id, self.summaries());
+            }
+            RaceCommand::Ready { ready } => {
+                let Some(racer) = self.racers.get_mut(&id) else {
+                    return;
+                };
+                racer.ready = ready;
+                if let Some(lobby) = racer.lobby.clone() {
+                    self.start_if_ready(&lobby);
+                    self.update(&lobby);
+                }
+            }
+            RaceCommand::Finish { time_ms, moves } => {
+                let Some(racer) = self.racers.get_mut(&id).filter(|racer| racer.racing) else {
+                    return;
+                };
+                racer.racing = false;
+                let result = RaceResult {
+                    name: racer.name.clone(),
+                    time_ms,
+                    moves,
+                    rating: racer.rating,
+                    change: 0.0,
+                };
+                if let Some(lobby) = racer.lobby.clone() {
+                    self.finish(&lobby, result);
+                }
+            }
+        }
+    }
+
+    fn summaries(&self) -> RaceEvent {
+        let lobbies = self
+            .lobbies
+            .iter()
+            .map(|(name, lobby)| LobbySummary {
+                name: name.clone(),
+                size: lobby.size,
+                racers: self.members(name).len(),
+                racing: lobby.racers > 0,
+            })
+            .collect();
+        RaceEvent::Lobbies { lobbies }
+    }
+
+    // Joins the lobby, opened with the cube size if it isn't yet, under a name no other racer
+    // of the lobby has, and with the rating kept for that name if any. Racers don't pick their
+    // own rating, any other starting from the initial one.
+    fn join(&mut self, id: usize, lobby: String, name: &str, size: usize) {
+        let lobby = lobby.trim().to_string();
+        if lobby.is_empty() || !(2..=7).contains(&size) {
+            self.send(
+                id,
+                RaceEvent::Error {
+                    message: "invalid lobby".to_string(),
+                },
+            );
+            return;
+        }
+        self.leave(id);
+
+        let taken: Vec<String> = self
+            .members(&lobby)
+            .iter()
+            .map(|id| self.racers[id].name.clone())
+            .collect();
+        let name = match name.trim() {
+            "" => "Racer",
+            name => name,
+        };
+        let name = (1..)
+            .map(|n| match n {
+                1 => name.to_string(),
+                n => format!("{name} ({n})"),
+            })
+            .find(|name| !taken.contains(name))
+            .unwrap_or_default();
+
+        self.lobbies.entry(lobby.clone()).or_insert(Lobby {
+            size,
+            racers: 0,
+            results: Vec::new(),
+        });
+        let rating = self.ratings.get(&name).copied().unwrap_or(INITIAL_RATING);
+        if let Some(racer) = self.racers.get_mut(&id) {
+            racer.name = name;
+            racer.rating = rating;
+            racer.lobby = Some(lobby.clone());
+            racer.ready = false;
+            racer.racing = false;
+        }
+        self.update(&lobby);
+    }
+
+    // Leaves the lobby, giving up the race under way if any
+    fn leave(&mut self, id: usize) {
+        let Some(racer) = self.racers.get_mut(&id) else {
+            return;
+        };
+        let Some(lobby) = racer.lobby.take() else {
+            return;
+        };
+        racer.ready = false;
+        if racer.racing {
+            racer.racing = false;
+            let result = RaceResult {
+                name: racer.name.clone(),
+                time_ms: None,
+                moves: 0,
+                rating: racer.rating,
+                change: 0.0,
+            };
+            self.finish(&lobby, result);
+        }
+
+        if self.members(&lobby).is_empty() {
+            self.lobbies.remove(&lobby);
+        } else {
+            self.start_if_ready(&lobby);
+            self.update(&lobby);
+        }
+    }
+
+    // Sends every member of the lobby who is in it, and whether they are ready
+    fn update(&self, lobby: &str) {
+        let Some(size) = self.lobbies.get(lobby).map(|lobby| lobby.size) else {
+            return;
+        };
+        let members = self.members(lobby);
+        let racers: Vec<LobbyRacer> = members
+            .iter()
+            .map(|id| LobbyRacer {
+                name: self.racers[id].name.clone(),
+                rating: self.racers[id].rating,
+                ready: self.racers[id].ready,
+                racing: self.racers[id].racing,
+            })
+            .collect();
+        for &id in &members {
+            self.send(
+                id,
+                RaceEvent::Lobby {
+                    name: lobby.to_string(),
+                    size,
+                    you: self.racers[&id].name.clone(),
+                    racers: racers.clone(),
+                },
+            );
+        }
+    }
+
+    // Starts the race once enough racers are ready, and all of them are, sending them the
+    // scramble at the same time
+    fn start_if_ready(&mut self, lobby: &str) {
+        let members = self.members(lobby);
+        let Some(state) = self.lobbies.get_mut(lobby) else {
+            return;
+        };
+        if state.racers > 0
+            || members.len() < MIN_RACERS
+            || !members.iter().all(|id| self.racers[id].ready)
+        {
+            return;
+        }
+
+        state.racers = members.len();
+        state.results.clear();
+        let size = state.size;
+        let scramble = random_scramble(size as CubeSize, size > 3)
+            .iter()
+            .map(|m| m.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        info!("race in {lobby} with {} racers", members.len());
+        for id in members {
+            if let Some(racer) = self.racers.get_mut(&id) {
+                racer.ready = false;
+                racer.racing = true;
+            }
+            self.send(
+                id,
+                RaceEvent::Start {
+                    scramble: scramble.clone(),
+                    size,
+                    countdown_ms: COUNTDOWN.as_millis() as u64,
+                },
+            );
+        }
+    }
+
+    // Keeps the result of a racer, rating the racers and sending them the results once every
+    // racer finished
+    fn finish(&mut self, lobby: &str, result: RaceResult) {
+        let Some(state) = self.lobbies.get_mut(lobby) else {
+            return;
+        };
+        state.results.push(result);
+        if state.results.len() >= state.racers {
+            state.racers = 0;
+            let mut results = std::mem::take(&mut state.results);
+            results.sort_by_key(|result| (result.time_ms.is_none(), result.time_ms));
+            self.rate(lobby, &mut results);
+            for id in self.members(lobby) {
+                self.send(
+                    id,
+                    RaceEvent::Results {
+                        results: results.clone(),
+                    },
+                );
+            }
+        }
+        self.update(lobby);
+    }
+
+    // Changes the ratings of the racers of the lobby with the results of its race, the ones
+    // who left it rated all the same
+    fn rate(&mut self, lobby: &str, results: &mut [RaceResult]) {
+        let racers: Vec<_> = results
+            .iter()
+            .map(|result| (result.rating, result.time_ms.map(Duration::from_millis)))
+            .collect();
+        for (result, change) in results.iter_mut().zip(rating_changes(&racers)) {
+            result.change = change;
+            let rating = result.rating + change;
+            self.ratings.insert(result.name.clone(), rating);
+            for id in self.members(lobby) {
+                if let Some(racer) = self.racers.get_mut(&id).filter(|r| r.name == result.name) {
+                    racer.rating = rating;
+                }
+            }
+        }
+        self.rated = true;
+    }
+
+    fn save_if_rated(&mut self) {
+        if std::mem::take(&mut self.rated) {
+            save_ratings(&self.ratings);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn connect(lobbies: &mut Lobbies, id: usize) -> Receiver<RaceEvent> {
+        let (sender, events) = mpsc::channel();
+        lobbies.connect(id, sender);
+        events
+    }
+
+    fn join(lobbies: &mut Lobbies, id: usize, name: &str) {
+        lobbies.handle(
+            id,
+            RaceCommand::Join {
+                lobby: "Lobby".to_string(),
+                name: name.to_string(),
+                size: 3,
+            },
+        );
+    }
+
+    fn finish(lobbies: &mut Lobbies, id: usize, time_ms: Option<u64>) {
+        lobbies.handle(id, RaceCommand::Finish { time_ms, moves: 40 });
+    }
+
+    // The racers of the lobby as its members were last sent them
+    fn last_lobby(events: &Receiver<RaceEvent>) -> Option<(String, Vec<LobbyRacer>)> {
+        events.try_iter().fold(None, |last, event| match event {
+            RaceEvent::Lobby { you, racers, .. } => Some((you, racers)),
+            _ => last,
+        })
+    }
+
+    fn start(events: &Receiver<RaceEvent>) -> Option<String> {
+        events.try_iter().find_map(|event| match event {
+            RaceEvent::Start { scramble, .. } => Some(scramble),
+            _ => None,
+        })
+    }
+
+    fn results(events: &Receiver<RaceEvent>) -> Option<Vec<RaceResult>> {
+        events.try_iter().find_map(|event| match event {
+            RaceEvent::Results { results } => Some(results),
+            _ => None,
+        })
+    }
+
+    // Two racers of the lobby, ready and racing
+    fn race(lobbies: &mut Lobbies) -> (Receiver<RaceEvent>, Receiver<RaceEvent>) {
+        let (ana, ben) = (connect(lobbies, 0), connect(lobbies, 1));
+        join(lobbies, 0, "Ana");
+        join(lobbies, 1, "Ben");
+        lobbies.handle(0, RaceCommand::Ready { ready: true });
+        lobbies.handle(1, RaceCommand::Ready { ready: true });
+        (ana, ben)
+    }
+
+    #[test]
+    fn racers_are_named_apart() {
+        let mut lobbies = Lobbies::default();
+        let events = connect(&mut lobbies, 0);
+        connect(&mut lobbies, 1);
+        join(&mut lobbies, 0, "Ana");
+        join(&mut lobbies, 1, " Ana ");
+
+        let (you, racers) = last_lobby(&events).unwrap();
+        assert_eq!(you, "Ana");
+        let names: Vec<_> = racers.iter().map(|racer| racer.name.as_str()).collect();
+        assert_eq!(names, ["Ana", "Ana (2)"]);
+    }
+
+    #[test]
+    fn new_racers_start_from_the_initial_rating() {
+        let mut lobbies = Lobbies {
+            ratings: BTreeMap::from([("Ana".to_string(), 1600.0)]),
+            ..default()
+        };
+        let events = connect(&mut lobbies, 0);
+        connect(&mut lobbies, 1);
+        join(&mut lobbies, 0, "Ana");
+        join(&mut lobbies, 1, "Ben");
+
+        let (_, racers) = last_lobby(&events).unwrap();
+        let ratings: Vec<_> = racers.iter().map(|racer| racer.rating).collect();
+        assert_eq!(ratings, [1600.0, INITIAL_RATING]);
+    }
+
+    #[test]
+    fn invalid_lobbies_are_refused() {
+        let mut lobbies = Lobbies::default();
+        let events = connect(&mut lobbies, 0);
+        lobbies.handle(
+            0,
+            RaceCommand::Join {
+                lobby: " ".to_string(),
+                name: "Ana".to_string(),
+                size: 3,
+            },
+        );
+        lobbies.handle(
+            0,
+            RaceCommand::Join {
+                lobby: "Lobby".to_string(),
+                name: "Ana".to_string(),
+                size: 8,
+            },
+        );
+
+        assert!(events
+            .try_iter()
+            .all(|event| matches!(event, RaceEvent::Error { .. })));
+        assert!(lobbies.lobbies.is_empty());
+    }
+
+    #[test]
+    fn race_starts_once_every_racer_is_ready() {
+        let mut lobbies = Lobbies::default();
+        let (ana, ben) = (connect(&mut lobbies, 0), connect(&mut lobbies, 1));
+        join(&mut lobbies, 0, "Ana");
+        lobbies.handle(0, RaceCommand::Ready { ready: true });
+        // a racer alone doesn't race
+        assert_eq!(start(&ana), None);
+
+        join(&mut lobbies, 1, "Ben");
+        assert_eq!(start(&ana), None);
+        lobbies.handle(1, RaceCommand::Ready { ready: true });
+
+        let scramble = start(&ana).unwrap();
+        assert_eq!(start(&ben), Some(scramble));
+        assert!(lobbies.racers.values().all(|racer| racer.racing));
+    }
+
+    #[test]
+    fn results_rate_the_racers_once_all_finished() {
+        let mut lobbies = Lobbies::default();
+        let (ana, ben) = race(&mut lobbies);
+        finish(&mut lobbies, 1, None);
+        assert_eq!(results(&ana), None);
+        assert!(!lobbies.rated);
+        finish(&mut lobbies, 0, Some(12_000));
+
+        let ranked = results(&ana).unwrap();
+        assert_eq!(results(&ben).as_ref(), Some(&ranked));
+        // the fastest first, and the DNFs last
+        assert_eq!(ranked[0].name, "Ana");
+        assert_eq!(ranked[1].time_ms, None);
+        assert!(ranked[0].change > 0.0 && ranked[1].change < 0.0);
+        assert_eq!(lobbies.ratings["Ana"], INITIAL_RATING + ranked[0].change);
+        assert!(lobbies.rated);
+    }
+
+    #[test]
+    fn racers_finish_only_once() {
+        let mut lobbies = Lobbies::default();
+        let (ana, _ben) = race(&mut lobbies);
+        finish(&mut lobbies, 0, Some(12_000));
+        finish(&mut lobbies, 0, Some(10_000));
+        assert_eq!(results(&ana), None);
+    }
+
+    #[test]
+    fn leaving_a_race_gives_it_up() {
+        let mut lobbies = Lobbies::default();
+        let (ana, _ben) = race(&mut lobbies);
+        lobbies.disconnect(1);
+        finish(&mut lobbies, 0, Some(12_000));
+
+        let ranked = results(&ana).unwrap();
+        assert_eq!(ranked[1].name, "Ben");
+        assert_eq!(ranked[1].time_ms, None);
+    }
+
+    #[test]
+    fn lobbies_close_with_their_last_racer() {
+        let mut lobbies = Lobbies::default();
+        let events = connect(&mut lobbies, 0);
+        join(&mut lobbies, 0, "Ana");
+        assert!(lobbies.lobbies.contains_key("Lobby"));
+
+        lobbies.handle(0, RaceCommand::Leave);
+        assert!(lobbies.lobbies.is_empty());
+        let summaries = events.try_iter().last();
+        assert_eq!(summaries, Some(RaceEvent::Lobbies { lobbies: vec![] }));
+    }
+}
@@ -0,0 +1,133 @@
+use std::{cmp::Ordering, time::Duration};
+
+/// The rating of a racer who never raced.
+pub(crate) const INITIAL_RATING: f64 = 1500.0;
+// The most a rating changes after a race, won or lost against every other racer
+const RATING_K: f64 = 32.0;
+
+// The expected score of a racer rated ``rating`` against one rated ``opponent``, from 0 for a
+// sure loss to 1 for a sure win
+fn expected_score(rating: f64, opponent: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf((opponent - rating) / 400.0))
+}
+
+/// The changes of the ratings of the racers of a race, in the order given, each racer given
+/// with their rating before the race and their time, or ``None`` for a DNF.
+///
+/// Every pair of racers is scored like a game of chess: the faster racer wins, and equal
+/// times or two DNFs draw. The changes of a racer against each of the others are averaged,
+/// so that a race against many racers moves ratings no more than a race against one.
+#[cfg_attr(
+    not(all(feature = "online", not(target_arch = "wasm32"))),
+    allow(dead_code)
+)]
+pub(crate) fn rating_changes(racers: &[(f64, Option<Duration>)]) -> Vec<f64> {
+    if racers.len() < 2 {
+        return racers.iter().map(|_| 0.0).collect();
+    }
+
+    let opponents = (racers.len() - 1) as f64;
+    racers
+        .iter()
+        .enumerate()
+        .map(|(i, &(rating, time))| {
+            let scores: f64 = racers
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .map(|(_, &(opponent, opponent_time))| {
+                    score(time, opponent_time) - expected_score(rating, opponent)
+                })
+                .sum();
+            RATING_K * scores / opponents
+        })
+        .collect()
+}
+
+// The score of a time against another, a DNF being slower than any time
+fn score(time: Option<Duration>, other: Option<Duration>) -> f64 {
+    let ordering = match (time, other) {
+        (Some(time), Some(other)) => time.cmp(&other),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    };
+    match ordering {
+        Ordering::Less => 1.0,
+        Ordering::Equal => 0.5,
+        Ordering::Greater => 0.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secs(secs: u64) -> Option<Duration> {
+        Some(Duration::from_secs(secs))
+    }
+
+    #[test]
+    fn expected_scores() {
+        assert_eq!(expected_score(INITIAL_RATING, INITIAL_RATING), 0.5);
+        // 400 points ahead, ten times as likely to win
+        assert!((expected_score(1900.0, 1500.0) - 10.0 / 11.0).abs() < 1e-9);
+        assert!(
+            (expected_score(1500.0, 1700.0) + expected_score(1700.0, 1500.0) - 1.0).abs() < 1e-9
+        );
+    }
+
+    #[test]
+    fn a_win_and_a_dnf() {
+        let changes = rating_changes(&[(INITIAL_RATING, secs(20)), (INITIAL_RATING, None)]);
+        assert_eq!(changes, vec![16.0, -16.0]);
+    }
+
+    #[test]
+    fn upsets_move_ratings_more() {
+        let expected = rating_changes(&[(1800.0, secs(20)), (1400.0, secs(30))]);
+        let upset = rating_changes(&[(1800.0, secs(30)), (1400.0, secs(20))]);
+        assert!(expected[0] > 0.0 && expected[0] < RATING_K / 2.0);
+        assert!(upset[1] > RATING_K / 2.0 && upset[1] < RATING_K);
+        assert!((upset[0] + upset[1]).abs() < 1e-9);
+    }
+
+    #[test]
+    fn draws() {
+        assert_eq!(
+            rating_changes(&[(INITIAL_RATING, secs(20)), (INITIAL_RATING, secs(20))]),
+            vec![0.0, 0.0]
+        );
+        assert_eq!(
+            rating_changes(&[(INITIAL_RATING, None), (INITIAL_RATING, None)]),
+            vec![0.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn many_racers() {
+        let changes = rating_changes(&[
+            (INITIAL_RATING, secs(25)),
+            (INITIAL_RATING, secs(15)),
+            (INITIAL_RATING, None),
+            (INITIAL_RATING, secs(20)),
+        ]);
+        // the winner beat every other racer, gaining as much as winning against one
+        assert_eq!(
+            changes,
+            vec![
+                -RATING_K / 6.0,
+                RATING_K / 2.0,
+                -RATING_K / 2.0,
+                RATING_K / 6.0
+            ]
+        );
+        assert!(changes.iter().sum::<f64>().abs() < 1e-9);
+    }
+
+    #[test]
+    fn racing_alone() {
+        assert_eq!(rating_changes(&[(INITIAL_RATING, secs(20))]), vec![0.0]);
+        assert!(rating_changes(&[]).is_empty());
+    }
+}
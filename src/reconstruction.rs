@@ -0,0 +1,483 @@
+use crate::{
+    i18n::{Localized, Translations},
+    menu::{MenuButtonAction, MenuState},
+    simulator::{CurrentCube, MoveQueue, Played, PlayedMut},
+    storage,
+    theme::{Accent, Theme, ThemeColor},
+    CubeSizeSetting, GameState, Puzzle,
+};
+use bevy::prelude::*;
+use cubesim::{read_solve_log, LoggedSolve, SolveReport};
+use std::time::Duration;
+
+// The log read when the screen opens, besides the files dropped on the window
+pub(crate) const IMPORT_PATH: &str = "smart-cube-solves.txt";
+// The number of most recent solves listed
+const LISTED_SOLVES: usize = 10;
+const PAUSE_SECONDS: f32 = 1.0;
+
+pub struct ReconstructionPlugin;
+
+impl Plugin for ReconstructionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ImportedSolves>()
+            .add_systems(
+                OnEnter(MenuState::Import),
+                (load_import_file, import_screen_setup),
+            )
+            .add_systems(
+                Update,
+                (import_dropped_files, list_solves, start_buttons)
+                    .chain()
+                    .run_if(in_state(MenuState::Import)),
+            )
+            .add_systems(OnExit(MenuState::Import), clean_up_screen)
+            .add_systems(
+                OnEnter(GameState::Playing),
+                replay_ui.run_if(resource_exists::<SolveReplay>()),
+            )
+            .add_systems(
+                Update,
+                (scramble_replay, play_replay, replay_buttons, show_replay)
+                    .chain()
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(resource_exists::<SolveReplay>()),
+            )
+            .add_systems(OnExit(GameState::Playing), clean_up)
+            .add_systems(OnEnter(GameState::Menu), end_replay);
+    }
+}
+
+/// The smart cube solves read from the log of another app, a csTimer or GAN app export put at
+/// ``smart-cube-solves.txt`` or dropped on the window of the import screen.
+#[derive(Resource, Default)]
+struct ImportedSolves {
+    solves: Vec<LoggedSolve>,
+    message: Option<Localized>,
+}
+
+impl ImportedSolves {
+    fn import(&mut self, source: &str, text: &str) {
+        match read_solve_log(text) {
+            Ok(solves) => {
+                self.message = Some(
+                    Localized::new("import-read")
+                        .with("solves", solves.len())
+                        .with("source", source),
+                );
+                self.solves = solves;
+            }
+            Err(err) => {
+                self.message = Some(
+                    Localized::new("import-failed")
+                        .with("source", source)
+                        .with("error", err.to_string()),
+                )
+            }
+        }
+    }
+}
+
+/// Replays an imported solve on the cube with the timing of its moves, started from the import
+/// screen: the scramble is made first, then each move once as much time has passed as it did
+/// on the smart cube, along its reconstruction annotated with the CFOP phases.
+#[derive(Resource)]
+struct SolveReplay {
+    solve: LoggedSolve,
+    report: SolveReport,
+    reconstruction: String,
+    scrambled: bool,
+    // when the first move was made, by the app's elapsed time
+    started: Option<Duration>,
+    played: usize,
+}
+
+impl SolveReplay {
+    fn new(solve: LoggedSolve) -> Self {
+        let report = solve.report(Duration::from_secs_f32(PAUSE_SECONDS));
+        SolveReplay {
+            reconstruction: report.reconstruction(),
+            report,
+            solve,
+            scrambled: false,
+            started: None,
+            played: 0,
+        }
+    }
+
+    // The phase of the last move played, counting the phases' moves from the first one
+    fn phase(&self) -> Option<String> {
+        let mut end = 0;
+        self.report
+            .stats
+            .phases
+            .iter()
+            .find(|phase| {
+                end += phase.moves;
+                phase.moves > 0 && self.played <= end
+            })
+            .map(|phase| phase.phase.to_string())
+    }
+
+    fn message(&self, now: Duration, translations: &Translations) -> String {
+        let elapsed = self
+            .started
+            .map_or(Duration::ZERO, |started| now.saturating_sub(started))
+            .min(self.report.stats.duration);
+        let phase = match (self.played, self.phase()) {
+            (0, _) => translations.get("replay-scrambling"),
+            (_, Some(phase)) => phase,
+            (_, None) => translations.get("replay-unfinished"),
+        };
+        translations.text(
+            Localized::new("replay-status")
+                .with("result", self.report.result().to_string())
+                .with("tps", format!("{:.2}", self.report.stats.tps))
+                .with("seconds", format!("{:.2}", elapsed.as_secs_f32()))
+                .with("move", self.played)
+                .with("moves", self.solve.solution.len())
+                .with("phase", phase)
+                .with("reconstruction", self.reconstruction.as_str()),
+        )
+    }
+}
+
+#[derive(Component)]
+struct OnImportScreen;
+
+// The buttons of the solves listed, rebuilt once others are imported
+#[derive(Component)]
+struct SolveList;
+
+// Replays the imported solve of the index
+#[derive(Component)]
+struct ReplayButton(usize);
+
+#[derive(Component)]
+struct ReplayUiRoot;
+
+#[derive(Component)]
+struct ReplayText;
+
+#[derive(Component)]
+struct RestartButton;
+
+fn load_import_file(mut imported: ResMut<ImportedSolves>) {
+    match storage::read_to_string(IMPORT_PATH) {
+        Ok(text) => imported.import(IMPORT_PATH, &text),
+        Err(err) => {
+            debug!("no solves imported from {IMPORT_PATH}: {err}");
+            if imported.solves.is_empty() {
+                imported.message =
+                    Some(Localized::new("import-drop-here").with("path", IMPORT_PATH));
+            }
+        }
+    }
+}
+
+fn import_dropped_files(
+    mut drop_events: EventReader<FileDragAndDrop>,
+    mut imported: ResMut<ImportedSolves>,
+) {
+    for event in drop_events.read() {
+        if let FileDragAndDrop::DroppedFile { path_buf, .. } = event {
+            let source = path_buf.display().to_string();
+            match std::fs::read_to_string(path_buf) {
+                Ok(text) => imported.import(&source, &text),
+                Err(err) => {
+                    imported.message = Some(
+                        Localized::new("import-failed")
+                            .with("source", source)
+                            .with("error", err.to_string()),
+                    )
+                }
+            }
+        }
+    }
+}
+
+fn import_screen_setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    theme: Res<Theme>,
+    accent: Res<Accent>,
+) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    let text_style = TextStyle {
+        font: font.clone(),
+        font_size: 30.0,
+        color: theme.text(),
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    margin: UiRect::all(Val::Auto),
+                    padding: UiRect::all(Val::Px(20.0)),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: accent.color().into(),
+                ..default()
+            },
+            OnImportScreen,
+            ThemeColor::Accent,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    String::new(),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 50.0,
+                        color: theme.text(),
+                    },
+                ),
+                Localized::new("training-import"),
+            ));
+            parent.spawn((
+                NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    ..default()
+                },
+                SolveList,
+            ));
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: Style {
+                            height: Val::Px(45.0),
+                            margin: UiRect::all(Val::Px(5.0)),
+                            padding: UiRect::horizontal(Val::Px(10.0)),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        background_color: theme.button(Interaction::None, false).into(),
+                        ..default()
+                    },
+                    MenuButtonAction::BackToTraining,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section(String::new(), text_style),
+                        Localized::new("menu-back"),
+                    ));
+                });
+        });
+}
+
+// Lists the most recent solves imported, the last one first, under the message of the import
+fn list_solves(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    theme: Res<Theme>,
+    imported: Res<ImportedSolves>,
+    q_list: Query<(Entity, Ref<SolveList>)>,
+) {
+    let added = q_list.iter().any(|(_, list)| list.is_added());
+    if !added && !imported.is_changed() {
+        return;
+    }
+    let text_style = TextStyle {
+        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+        font_size: 25.0,
+        color: theme.text(),
+    };
+
+    for (list, _) in &q_list {
+        commands
+            .entity(list)
+            .despawn_descendants()
+            .with_children(|parent| {
+                if let Some(message) = &imported.message {
+                    parent.spawn((
+                        TextBundle::from_section(String::new(), text_style.clone()),
+                        message.clone(),
+                    ));
+                }
+                for (i, solve) in imported.solves.iter().enumerate().rev().take(LISTED_SOLVES) {
+                    let report = solve.report(Duration::from_secs_f32(PAUSE_SECONDS));
+                    parent
+                        .spawn((
+                            ButtonBundle {
+                                style: Style {
+                                    margin: UiRect::all(Val::Px(3.0)),
+                                    padding: UiRect::axes(Val::Px(10.0), Val::Px(5.0)),
+                                    ..default()
+                                },
+                                background_color: theme.button(Interaction::None, false).into(),
+                                ..default()
+                            },
+                            ReplayButton(i),
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                TextBundle::from_section(String::new(), text_style.clone()),
+                                Localized::new("import-solve")
+                                    .with("number", i + 1)
+                                    .with("result", report.result().to_string())
+                                    .with("moves", report.stats.moves)
+                                    .with("tps", format!("{:.2}", report.stats.tps)),
+                            ));
+                        });
+                }
+            });
+    }
+}
+
+fn start_buttons(
+    mut commands: Commands,
+    interaction_query: Query<(&Interaction, &ReplayButton), Changed<Interaction>>,
+    imported: Res<ImportedSolves>,
+    mut puzzle: ResMut<Puzzle>,
+    mut cube_size: ResMut<CubeSizeSetting>,
+    mut menu_state: ResMut<NextState<MenuState>>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    for (interaction, ReplayButton(index)) in &interaction_query {
+        let Some(solve) = imported.solves.get(*index) else {
+            continue;
+        };
+        if *interaction == Interaction::Pressed {
+            commands.insert_resource(SolveReplay::new(solve.clone()));
+            *puzzle = Puzzle::Cube;
+            cube_size.0 = 3;
+            game_state.set(GameState::Playing);
+            menu_state.set(MenuState::Disabled);
+        }
+    }
+}
+
+fn replay_ui(mut commands: Commands, asset_server: Res<AssetServer>, theme: Res<Theme>) {
+    let text_style = TextStyle {
+        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+        font_size: 25.0,
+        color: theme.text(),
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    bottom: Val::Px(10.0),
+                    right: Val::Px(10.0),
+                    max_width: Val::Px(700.0),
+                    padding: UiRect::all(Val::Px(10.0)),
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+                ..default()
+            },
+            ThemeColor::Panel,
+            ReplayUiRoot,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(String::new(), text_style.clone()),
+                ReplayText,
+            ));
+            parent.spawn((
+                TextBundle::from_section(String::new(), text_style),
+                Localized::new("replay-again"),
+                RestartButton,
+                Interaction::None,
+            ));
+        });
+}
+
+// Makes the scramble without timing it as a solve, so the replay isn't recorded as one
+fn scramble_replay(
+    mut replay: ResMut<SolveReplay>,
+    current_cube: Played<CurrentCube>,
+    mut move_queue: PlayedMut<MoveQueue>,
+) {
+    if replay.scrambled || current_cube.cube_size() != 3 {
+        return;
+    }
+    move_queue.extend(replay.solve.scramble.iter().copied());
+    replay.scrambled = true;
+}
+
+// Queues each move once its time has come, from the end of the scramble
+fn play_replay(
+    time: Res<Time>,
+    mut replay: ResMut<SolveReplay>,
+    mut move_queue: PlayedMut<MoveQueue>,
+) {
+    if !replay.scrambled {
+        return;
+    }
+    let now = time.elapsed();
+    let started = match replay.started {
+        Some(started) => started,
+        None if move_queue.is_empty() && move_queue.in_flight().is_none() => {
+            *replay.started.insert(now)
+        }
+        None => return,
+    };
+
+    while let Some(timed) = replay.solve.solution.get(replay.played) {
+        if timed.at > now.saturating_sub(started) {
+            break;
+        }
+        move_queue.push_back(timed.mv);
+        replay.played += 1;
+    }
+}
+
+fn replay_buttons(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<RestartButton>)>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    if interaction_query.iter().any(|i| *i == Interaction::Pressed) {
+        game_state.set(GameState::Restarting);
+    }
+}
+
+fn show_replay(
+    time: Res<Time>,
+    replay: Res<SolveReplay>,
+    translations: Translations,
+    mut q_text: Query<&mut Text, With<ReplayText>>,
+) {
+    let message = replay.message(time.elapsed(), &translations);
+    for mut text in &mut q_text {
+        if text.sections[0].value != message {
+            text.sections[0].value = message.clone();
+        }
+    }
+}
+
+// Replaying again starts over from the scramble, leaving the game ends the replay
+fn clean_up(
+    mut commands: Commands,
+    replay: Option<ResMut<SolveReplay>>,
+    q_ui: Query<Entity, With<ReplayUiRoot>>,
+) {
+    if let Some(mut replay) = replay {
+        replay.scrambled = false;
+        replay.started = None;
+        replay.played = 0;
+    }
+    for entity in &q_ui {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn end_replay(mut commands: Commands) {
+    commands.remove_resource::<SolveReplay>();
+}
+
+fn clean_up_screen(mut commands: Commands, q_screen: Query<Entity, With<OnImportScreen>>) {
+    for entity in &q_screen {
+        commands.entity(entity).despawn_recursive();
+    }
+}
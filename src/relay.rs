@@ -0,0 +1,209 @@
+use crate::{
+    i18n::{Localized, Translations},
+    scramble::Scrambler,
+    session::SolveSession,
+    simulator::{CurrentCube, MoveQueue, PlayedMut},
+    solved::CubeSolvedEvent,
+    theme::{Theme, ThemeColor},
+    CubeSizeSetting, GameState, Puzzle,
+};
+use bevy::prelude::*;
+use cubesim::prelude::*;
+use std::time::Duration;
+
+// The cubes solved one after the other in a relay
+const RELAY_SIZES: [usize; 3] = [2, 3, 4];
+
+pub struct RelayPlugin;
+
+impl Plugin for RelayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(GameState::Playing),
+            relay_ui.run_if(resource_exists::<Relay>()),
+        )
+        .add_systems(
+            Update,
+            (scramble_leg, next_leg, show_relay)
+                .chain()
+                .run_if(in_state(GameState::Playing))
+                .run_if(resource_exists::<Relay>()),
+        )
+        .add_systems(OnExit(GameState::Playing), clean_up)
+        .add_systems(OnEnter(GameState::Menu), end_relay);
+    }
+}
+
+/// A relay started from the main menu, scrambling a 2x2x2, a 3x3x3 and a 4x4x4 cube in turn.
+/// Each cube is set up once the last one is solved, and the time is kept from the first move
+/// of the first solve to the end of the last one.
+#[derive(Resource, Debug, Default)]
+struct Relay {
+    // the solve time of each cube solved so far
+    splits: Vec<Duration>,
+    // when the first solve started and the last one ended, by the app's elapsed time
+    started: Option<Duration>,
+    finished: Option<Duration>,
+    // whether the cube of the current leg was scrambled
+    scrambled: bool,
+}
+
+impl Relay {
+    // The size of the cube to solve next, until the relay is over
+    fn size(&self) -> Option<usize> {
+        RELAY_SIZES.get(self.splits.len()).copied()
+    }
+
+    fn total(&self, now: Duration) -> Duration {
+        match self.started {
+            Some(started) => self.finished.unwrap_or(now).saturating_sub(started),
+            None => Duration::ZERO,
+        }
+    }
+
+    fn message(&self, now: Duration, translations: &Translations) -> String {
+        let mut message = translations.get("relay-title");
+        for (i, size) in RELAY_SIZES.into_iter().enumerate() {
+            let split = match self.splits.get(i) {
+                Some(split) => Localized::new("relay-split")
+                    .with("seconds", format!("{:.2}", split.as_secs_f32())),
+                None if i == self.splits.len() => Localized::new("relay-solving"),
+                None => Localized::new("relay-unsolved"),
+            };
+            let split = Localized::new("relay-cube")
+                .with("size", size)
+                .with("split", translations.text(split));
+            message.push_str(&format!("\n{}", translations.text(split)));
+        }
+        let total = match self.finished {
+            Some(_) => Localized::new("relay-done"),
+            None => Localized::new("relay-total"),
+        };
+        let total = total.with("seconds", format!("{:.2}", self.total(now).as_secs_f32()));
+        message.push_str(&format!("\n{}", translations.text(total)));
+        message
+    }
+}
+
+#[derive(Component)]
+struct RelayUiRoot;
+
+#[derive(Component)]
+struct RelayText;
+
+fn relay_ui(mut commands: Commands, asset_server: Res<AssetServer>, theme: Res<Theme>) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(10.0),
+                    left: Val::Px(220.0),
+                    padding: UiRect::all(Val::Px(10.0)),
+                    ..default()
+                },
+                ..default()
+            },
+            ThemeColor::Panel,
+            RelayUiRoot,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    String::new(),
+                    TextStyle {
+                        font,
+                        font_size: 25.0,
+                        color: theme.text(),
+                    },
+                ),
+                RelayText,
+            ));
+        });
+}
+
+// Scrambles the cube of each leg once it's set up
+fn scramble_leg(
+    mut relay: ResMut<Relay>,
+    mut current_cube: PlayedMut<CurrentCube>,
+    mut move_queue: PlayedMut<MoveQueue>,
+    mut session: PlayedMut<SolveSession>,
+    mut scrambler: Scrambler,
+) {
+    if relay.scrambled || relay.size() != Some(current_cube.cube_size()) {
+        return;
+    }
+
+    let scramble = scrambler.scramble(current_cube.cube_size() as CubeSize);
+    session.queue_scramble(&mut current_cube, &mut move_queue, scramble);
+    relay.scrambled = true;
+}
+
+// Moves on to the next cube once one is solved, the game starting over with its size
+fn next_leg(
+    time: Res<Time>,
+    mut relay: ResMut<Relay>,
+    mut solved_events: EventReader<CubeSolvedEvent>,
+    mut cube_size: ResMut<CubeSizeSetting>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    for solved in solved_events.read() {
+        if !relay.scrambled || relay.size() != Some(solved.cube_size) {
+            continue;
+        }
+
+        let now = time.elapsed();
+        relay
+            .started
+            .get_or_insert(now.saturating_sub(solved.report.stats.duration));
+        relay.splits.push(solved.report.stats.duration);
+        relay.scrambled = false;
+        match relay.size() {
+            Some(size) => {
+                cube_size.0 = size;
+                game_state.set(GameState::Restarting);
+            }
+            None => {
+                relay.finished = Some(now);
+                info!("relay done in {:.2} s", relay.total(now).as_secs_f32());
+            }
+        }
+    }
+}
+
+fn show_relay(
+    time: Res<Time>,
+    relay: Res<Relay>,
+    translations: Translations,
+    mut q_text: Query<&mut Text, With<RelayText>>,
+) {
+    let message = relay.message(time.elapsed(), &translations);
+    for mut text in &mut q_text {
+        if text.sections[0].value != message {
+            text.sections[0].value = message.clone();
+        }
+    }
+}
+
+// Leaving the game gives up the relay, but starting over for its next cube doesn't
+fn end_relay(mut commands: Commands) {
+    commands.remove_resource::<Relay>();
+}
+
+fn clean_up(mut commands: Commands, q_ui: Query<Entity, With<RelayUiRoot>>) {
+    for entity in &q_ui {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// Starts a relay from the main menu, with the cube of its first leg.
+pub(crate) fn start_relay(
+    commands: &mut Commands,
+    puzzle: &mut Puzzle,
+    cube_size: &mut CubeSizeSetting,
+) {
+    commands.insert_resource(Relay::default());
+    *puzzle = Puzzle::Cube;
+    cube_size.0 = RELAY_SIZES[0];
+}
@@ -0,0 +1,199 @@
+use crate::{
+    console::ConsoleCommand,
+    simulator::{CurrentCube, MoveQueue, Played},
+    GameState, Puzzle,
+};
+use bevy::prelude::*;
+use cubesim::{parse_algorithm, prelude::*};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::{
+    net::{TcpListener, TcpStream},
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Mutex,
+    },
+    thread,
+};
+use tungstenite::{Error, Message};
+
+// Only local tools can connect, as anyone connecting can turn the cube
+const REMOTE_ADDR: &str = "127.0.0.1:9001";
+
+/// Lets other programs, such as timers, chat bots or robot drivers, turn the cube over a
+/// WebSocket at `ws://127.0.0.1:9001`. Each message is a JSON command, answered with an
+/// object whose `ok` field tells whether it was accepted:
+///
+/// ```text
+/// {"command": "moves", "moves": "R U R' U'"}
+/// {"command": "scramble"}
+/// {"command": "scramble", "length": 25}
+/// {"command": "scramble", "moves": "F2 U' L"}
+/// {"command": "solve"}
+/// {"command": "reset"}
+/// {"command": "state"}
+/// ```
+pub struct RemotePlugin;
+
+impl Plugin for RemotePlugin {
+    fn build(&self, app: &mut App) {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            if let Err(err) = serve(sender) {
+                warn!("no remote control on {REMOTE_ADDR}: {err}");
+            }
+        });
+
+        app.insert_resource(RemoteRequests(Mutex::new(receiver)))
+            .add_systems(Update, answer_requests);
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(tag = "command", rename_all = "snake_case")]
+enum RemoteCommand {
+    Moves {
+        moves: String,
+    },
+    Scramble {
+        moves: Option<String>,
+        length: Option<usize>,
+    },
+    Solve,
+    Reset,
+    State,
+}
+
+// A command, with where to send its answer
+type RemoteRequest = (RemoteCommand, Sender<Value>);
+
+#[derive(Resource)]
+struct RemoteRequests(Mutex<Receiver<RemoteRequest>>);
+
+fn serve(requests: Sender<RemoteRequest>) -> std::io::Result<()> {
+    let listener = TcpListener::bind(REMOTE_ADDR)?;
+    info!("remote control listening on ws://{REMOTE_ADDR}");
+
+    for stream in listener.incoming().flatten() {
+        let requests = requests.clone();
+        thread::spawn(move || {
+            if let Err(err) = handle_connection(stream, requests) {
+                debug!("remote connection closed: {err}");
+            }
+        });
+    }
+    Ok(())
+}
+
+fn error(message: impl ToString) -> Value {
+    json!({ "ok": false, "error": message.to_string() })
+}
+
+fn handle_connection(stream: TcpStream, requests: Sender<RemoteRequest>) -> Result<(), String> {
+    let mut socket = tungstenite::accept(stream).map_err(|err| err.to_string())?;
+
+    loop {
+        let text = match socket.read() {
+            Ok(Message::Text(text)) => text,
+            Ok(Message::Close(_)) | Err(Error::ConnectionClosed) => return Ok(()),
+            Ok(_) => continue,
+            Err(err) => return Err(err.to_string()),
+        };
+
+        let answer = match serde_json::from_str(&text) {
+            Ok(command) => {
+                let (sender, answer) = mpsc::channel();
+                // the app has quit once it stops answering
+                if requests.send((command, sender)).is_err() {
+                    return Ok(());
+                }
+                answer.recv().unwrap_or_else(|_| error("the app has quit"))
+            }
+            Err(err) => error(format!("invalid command: {err}")),
+        };
+        socket
+            .send(Message::Text(answer.to_string()))
+            .map_err(|err| err.to_string())?;
+    }
+}
+
+fn moves_string(moves: &[Move]) -> String {
+    moves
+        .iter()
+        .map(|m| m.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn parse_moves(moves: &str) -> Result<Vec<Move>, String> {
+    parse_algorithm(moves).map_err(|err| err.to_string())
+}
+
+fn cube_state(current_cube: &CurrentCube, move_queue: &MoveQueue) -> Value {
+    let facelets: String = current_cube
+        .state()
+        .iter()
+        .map(|face| format!("{face:?}"))
+        .collect();
+    json!({
+        "ok": true,
+        "size": current_cube.cube_size(),
+        "moves": moves_string(current_cube.moves()),
+        "queued": move_queue.len(),
+        "solved": current_cube.is_solved(),
+        "scrambled": current_cube.scrambled_at().is_some(),
+        "facelets": facelets,
+    })
+}
+
+fn answer_requests(
+    requests: Res<RemoteRequests>,
+    game_state: Res<State<GameState>>,
+    puzzle: Res<Puzzle>,
+    current_cube: Played<CurrentCube>,
+    move_queue: Played<MoveQueue>,
+    mut commands: EventWriter<ConsoleCommand>,
+) {
+    let requests = requests.0.lock().unwrap_or_else(|err| err.into_inner());
+    for (command, answer) in requests.try_iter() {
+        if *game_state.get() != GameState::Playing || *puzzle != Puzzle::Cube {
+            // the connection may be gone already
+            let _ = answer.send(error("no cube is being played"));
+            continue;
+        }
+
+        // the other commands are run by the console
+        let command = match command {
+            RemoteCommand::State => {
+                let _ = answer.send(cube_state(&current_cube, &move_queue));
+                continue;
+            }
+            RemoteCommand::Moves { moves } => parse_moves(&moves).map(ConsoleCommand::Alg),
+            RemoteCommand::Scramble {
+                moves: Some(moves),
+                length: None,
+            } => parse_moves(&moves).map(ConsoleCommand::ScrambleWith),
+            RemoteCommand::Scramble {
+                moves: None,
+                length: Some(0),
+            } => Err("invalid scramble length 0".to_string()),
+            RemoteCommand::Scramble {
+                moves: None,
+                length,
+            } => Ok(ConsoleCommand::Scramble(length)),
+            RemoteCommand::Scramble { .. } => {
+                Err("a scramble has either moves or a length".to_string())
+            }
+            RemoteCommand::Solve => Ok(ConsoleCommand::Solve),
+            RemoteCommand::Reset => Ok(ConsoleCommand::Reset),
+        };
+        let value = match command {
+            Ok(command) => {
+                commands.send(command);
+                json!({ "ok": true })
+            }
+            Err(err) => error(err),
+        };
+        let _ = answer.send(value);
+    }
+}
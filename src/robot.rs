@@ -0,0 +1,133 @@
+use crate::{settings::Settings, simulator::PlayedMoves};
+use bevy::prelude::*;
+use std::{
+    fmt,
+    io::Write,
+    net::TcpStream,
+    str::FromStr,
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+};
+
+pub struct RobotPlugin;
+
+impl Plugin for RobotPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, connect_robot)
+            .add_systems(Update, mirror_moves.run_if(resource_exists::<RobotLink>()));
+    }
+}
+
+/// Where the moves made on the cube are sent, for a cube solving robot to make them too. Each
+/// move is written on its own line in WCA notation, like `R'` or `2Uw2`, including the cube
+/// rotations `X`, `Y` and `Z`.
+///
+/// Set in the `[robot]` section of the settings:
+///
+/// ```text
+/// [robot]
+/// output = tcp 192.168.1.20:9002
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) enum RobotOutput {
+    /// A TCP server listening at an address, such as ``tcp 192.168.1.20:9002``.
+    Tcp(String),
+    /// A serial port at a baud rate, such as ``serial /dev/ttyUSB0 115200``. Only available
+    /// with the `serial` feature.
+    Serial { port: String, baud_rate: u32 },
+}
+
+impl RobotOutput {
+    fn connect(&self) -> Result<Box<dyn Write + Send>, String> {
+        match self {
+            RobotOutput::Tcp(addr) => TcpStream::connect(addr)
+                .map(|stream| Box::new(stream) as Box<dyn Write + Send>)
+                .map_err(|err| format!("{addr}: {err}")),
+            #[cfg(feature = "serial")]
+            RobotOutput::Serial { port, baud_rate } => serialport::new(port, *baud_rate)
+                .timeout(std::time::Duration::from_secs(1))
+                .open()
+                .map(|serial| Box::new(serial) as Box<dyn Write + Send>)
+                .map_err(|err| format!("{port}: {err}")),
+            #[cfg(not(feature = "serial"))]
+            RobotOutput::Serial { port, .. } => {
+                Err(format!("{port}: built without the serial feature"))
+            }
+        }
+    }
+}
+
+impl fmt::Display for RobotOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RobotOutput::Tcp(addr) => write!(f, "tcp {addr}"),
+            RobotOutput::Serial { port, baud_rate } => write!(f, "serial {port} {baud_rate}"),
+        }
+    }
+}
+
+impl FromStr for RobotOutput {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let words: Vec<&str> = s.split_whitespace().collect();
+        match words[..] {
+            ["tcp", addr] => Ok(RobotOutput::Tcp(addr.to_string())),
+            ["serial", port, baud_rate] => Ok(RobotOutput::Serial {
+                port: port.to_string(),
+                baud_rate: baud_rate
+                    .parse()
+                    .map_err(|_| format!("invalid baud rate '{baud_rate}'"))?,
+            }),
+            _ => Err(format!(
+                "'{s}' is neither 'tcp <address>' nor 'serial <port> <baud rate>'"
+            )),
+        }
+    }
+}
+
+// The moves to send, written by a thread of its own as the robot may be slow to take them
+#[derive(Resource)]
+struct RobotLink(Sender<String>);
+
+fn connect_robot(mut commands: Commands, settings: Res<Settings>) {
+    let Some(output) = settings.robot.clone() else {
+        return;
+    };
+
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || send_moves(output, receiver));
+    commands.insert_resource(RobotLink(sender));
+}
+
+// Connects again when the robot's connection is lost, the moves made meanwhile being lost
+fn send_moves(output: RobotOutput, moves: Receiver<String>) {
+    let mut writer = None;
+    for mv in moves {
+        if writer.is_none() {
+            match output.connect() {
+                Ok(connected) => {
+                    info!("sending the moves to the robot at {output}");
+                    writer = Some(connected);
+                }
+                Err(err) => {
+                    warn!("move {mv} not sent to the robot: {err}");
+                    continue;
+                }
+            }
+        }
+        if let Some(connected) = &mut writer {
+            if let Err(err) = writeln!(connected, "{mv}").and_then(|()| connected.flush()) {
+                warn!("move {mv} not sent to the robot: {err}");
+                writer = None;
+            }
+        }
+    }
+}
+
+fn mirror_moves(mut move_applied: PlayedMoves, link: Res<RobotLink>) {
+    for mv in move_applied.read() {
+        // the thread only stops with the app
+        let _ = link.0.send(mv.to_string());
+    }
+}
@@ -0,0 +1,195 @@
+use crate::{
+    i18n::{Language, Localization, Localized},
+    simulator::{
+        played_cube, CubeRoot, CurrentCube, MoveQueue, Piece, PieceBody, PieceCoord, PlayedCube,
+        Rotating, StatusText, StickerFace,
+    },
+    storage, GameState, Puzzle,
+};
+use bevy::{
+    hierarchy::despawn_with_children_recursive,
+    input::common_conditions::{input_just_pressed, input_pressed},
+    prelude::*,
+    scene::serde::SceneDeserializer,
+    utils::HashMap,
+};
+use serde::de::DeserializeSeed;
+
+// The cube saved with Ctrl + S and loaded with Ctrl + O, which can be shared as it is
+pub(crate) const SCENE_PATH: &str = "cube.scn.ron";
+const SAVE_KEY: KeyCode = KeyCode::S;
+const LOAD_KEY: KeyCode = KeyCode::O;
+
+pub struct ScenePlugin;
+
+impl Plugin for ScenePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                save_cube.run_if(input_just_pressed(SAVE_KEY)),
+                load_cube.run_if(input_just_pressed(LOAD_KEY)),
+            )
+                .run_if(input_pressed(KeyCode::ControlLeft))
+                .run_if(in_state(GameState::Playing))
+                .run_if(resource_equals(Puzzle::Cube)),
+        );
+    }
+}
+
+// Shows the result of saving or loading below the layer status
+fn show_status(world: &mut World, status: Localized) {
+    let status = world
+        .resource::<Localization>()
+        .text(*world.resource::<Language>(), &status);
+    let mut q_status = world.query_filtered::<&mut Text, With<StatusText>>();
+    for mut text in q_status.iter_mut(world) {
+        text.sections[1].value = format!("\n{status}");
+    }
+}
+
+/// The cube and its pieces, with their stickers and places, and the moves made on it, kept on
+/// its root. The meshes and materials are left out, as they follow the display settings of
+/// whoever loads the scene.
+fn cube_scene(world: &World) -> DynamicScene {
+    let entities = world
+        .iter_entities()
+        .filter(|entity| {
+            entity.contains::<CubeRoot>()
+                || entity.contains::<Piece>()
+                || entity.contains::<PieceBody>()
+                || entity.contains::<StickerFace>()
+        })
+        .map(|entity| entity.id());
+
+    DynamicSceneBuilder::from_world(world)
+        .deny_all()
+        .allow::<CubeRoot>()
+        .allow::<CurrentCube>()
+        .allow::<Piece>()
+        .allow::<PieceCoord>()
+        .allow::<PieceBody>()
+        .allow::<StickerFace>()
+        .allow::<Transform>()
+        .allow::<Parent>()
+        .allow::<Children>()
+        .extract_entities(entities)
+        .build()
+}
+
+fn save_cube(world: &mut World) {
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let status = match cube_scene(world).serialize_ron(&type_registry) {
+        Ok(text) => match storage::write(SCENE_PATH, &text) {
+            Ok(()) => Localized::new("scene-saved").with("path", SCENE_PATH),
+            Err(err) => {
+                warn!("failed to save the cube to {SCENE_PATH}: {err}");
+                Localized::new("scene-save-failed").with("error", err.to_string())
+            }
+        },
+        Err(err) => {
+            warn!("failed to serialize the cube: {err}");
+            Localized::new("scene-save-failed").with("error", err.to_string())
+        }
+    };
+    show_status(world, status);
+}
+
+fn read_scene(world: &World) -> Result<DynamicScene, String> {
+    let text = storage::read_to_string(SCENE_PATH).map_err(|err| format!("{SCENE_PATH}: {err}"))?;
+    let type_registry = world.resource::<AppTypeRegistry>().read();
+    let mut deserializer =
+        ron::de::Deserializer::from_str(&text).map_err(|err| format!("{SCENE_PATH}: {err}"))?;
+    SceneDeserializer {
+        type_registry: &type_registry,
+    }
+    .deserialize(&mut deserializer)
+    .map_err(|err| format!("{SCENE_PATH}: {err}"))
+}
+
+fn load_cube(world: &mut World) {
+    // the pieces turning belong to moves already applied to the cube being replaced
+    if world
+        .query_filtered::<(), With<Rotating>>()
+        .iter(world)
+        .next()
+        .is_some()
+    {
+        show_status(world, Localized::new("scene-load-busy"));
+        return;
+    }
+
+    let scene = match read_scene(world) {
+        Ok(scene) => scene,
+        Err(err) => {
+            warn!("failed to load the cube: {err}");
+            show_status(
+                world,
+                Localized::new("scene-load-failed").with("error", err),
+            );
+            return;
+        }
+    };
+
+    // scenes saved while the moves were kept apart from the cube's root have no cube to play,
+    // and only one cube can be played
+    let loaded_cubes = scene
+        .entities
+        .iter()
+        .filter(|entity| {
+            entity
+                .components
+                .iter()
+                .any(|component| component.represents::<CurrentCube>())
+        })
+        .count();
+    if loaded_cubes != 1 {
+        let status = match loaded_cubes {
+            0 => Localized::new("scene-no-cube"),
+            _ => Localized::new("scene-several-cubes"),
+        };
+        show_status(world, status.with("path", SCENE_PATH));
+        return;
+    }
+
+    let cubes: Vec<Entity> = world
+        .query_filtered::<Entity, With<CubeRoot>>()
+        .iter(world)
+        .collect();
+    for cube in cubes {
+        despawn_with_children_recursive(world, cube);
+    }
+
+    let mut entity_map = HashMap::default();
+    let written = scene.write_to_world(world, &mut entity_map);
+    // the loaded cube is the one played, getting a queue of its own
+    for &entity in entity_map.values() {
+        let mut entity = world.entity_mut(entity);
+        let Some(mut current_cube) = entity.get_mut::<CurrentCube>() else {
+            continue;
+        };
+        // the state of the cube isn't part of the scene, only the moves leading to it
+        current_cube.replay();
+        entity.insert((PlayedCube, MoveQueue::default()));
+    }
+
+    if let Err(err) = written {
+        // the cube's systems always find a played cube, if only a solved one without pieces
+        if world
+            .query_filtered::<(), With<PlayedCube>>()
+            .iter(world)
+            .next()
+            .is_none()
+        {
+            world.spawn(played_cube());
+        }
+        warn!("failed to load the cube: {err}");
+        let status = Localized::new("scene-load-failed").with("error", err.to_string());
+        show_status(world, status);
+        return;
+    }
+    show_status(
+        world,
+        Localized::new("scene-loaded").with("path", SCENE_PATH),
+    );
+}
@@ -0,0 +1,236 @@
+use crate::settings::Settings;
+use bevy::{ecs::system::SystemParam, prelude::*};
+use cubesim::{prelude::*, random_scramble_with, ScrambleOptions};
+use std::{fmt, str::FromStr};
+
+pub struct ScramblePlugin;
+
+impl Plugin for ScramblePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ScrambleLength>()
+            .init_resource::<WideMoves>()
+            .init_resource::<Rotations>()
+            .init_resource::<ScrambleSeed>()
+            .init_resource::<ScrambleCount>()
+            .add_systems(Startup, load_scramble_options)
+            .add_systems(Update, save_scramble_options);
+    }
+}
+
+/// The number of moves of the random scrambles, chosen in the settings. By default the cube's
+/// size times ten.
+#[derive(Resource, Component, Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) struct ScrambleLength(pub Option<usize>);
+
+impl ScrambleLength {
+    pub const ALL: [ScrambleLength; 6] = [
+        ScrambleLength(None),
+        ScrambleLength(Some(20)),
+        ScrambleLength(Some(25)),
+        ScrambleLength(Some(40)),
+        ScrambleLength(Some(60)),
+        ScrambleLength(Some(100)),
+    ];
+}
+
+impl fmt::Display for ScrambleLength {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(length) => write!(f, "{length}"),
+            None => f.write_str("auto"),
+        }
+    }
+}
+
+impl FromStr for ScrambleLength {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(ScrambleLength(None)),
+            _ => match s.parse() {
+                Ok(length) if length > 0 => Ok(ScrambleLength(Some(length))),
+                _ => Err(format!("'{s}' is neither auto nor a number of moves")),
+            },
+        }
+    }
+}
+
+/// Whether the random scrambles turn several layers at once, by default only on cubes bigger
+/// than the 3x3x3 since their inner layers need scrambling too.
+#[derive(Resource, Component, Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) enum WideMoves {
+    #[default]
+    Auto,
+    On,
+    Off,
+}
+
+impl WideMoves {
+    pub const ALL: [WideMoves; 3] = [WideMoves::Auto, WideMoves::On, WideMoves::Off];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            WideMoves::Auto => "Auto",
+            WideMoves::On => "On",
+            WideMoves::Off => "Off",
+        }
+    }
+
+    fn allowed(self, cube_size: CubeSize) -> bool {
+        match self {
+            WideMoves::Auto => cube_size > 3,
+            WideMoves::On => true,
+            WideMoves::Off => false,
+        }
+    }
+}
+
+impl fmt::Display for WideMoves {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name().to_lowercase())
+    }
+}
+
+impl FromStr for WideMoves {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        WideMoves::ALL
+            .into_iter()
+            .find(|wide_moves| wide_moves.to_string() == s)
+            .ok_or_else(|| format!("unknown wide moves '{s}', try auto, on or off"))
+    }
+}
+
+/// Whether the random scrambles also turn the whole cube, chosen in the settings.
+#[derive(Resource, Component, Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) enum Rotations {
+    #[default]
+    Off,
+    On,
+}
+
+impl Rotations {
+    pub const ALL: [Rotations; 2] = [Rotations::Off, Rotations::On];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Rotations::Off => "Off",
+            Rotations::On => "On",
+        }
+    }
+}
+
+/// The seed of the random scrambles: random ones by default, or the same ones in the same
+/// order on each run, to practice the scrambles of another player given the seed.
+#[derive(Resource, Component, Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) struct ScrambleSeed(pub Option<u64>);
+
+impl ScrambleSeed {
+    // the seed picked from the menu, another one can be written in the settings file
+    pub const FIXED: ScrambleSeed = ScrambleSeed(Some(1));
+
+    pub fn name(self) -> &'static str {
+        match self.0 {
+            Some(_) => "Fixed",
+            None => "Random",
+        }
+    }
+}
+
+impl fmt::Display for ScrambleSeed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(seed) => write!(f, "{seed}"),
+            None => f.write_str("random"),
+        }
+    }
+}
+
+impl FromStr for ScrambleSeed {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "random" => Ok(ScrambleSeed(None)),
+            _ => s
+                .parse()
+                .map(|seed| ScrambleSeed(Some(seed)))
+                .map_err(|_| format!("'{s}' is neither random nor a number")),
+        }
+    }
+}
+
+// The number of scrambles made since the app started, so the scrambles of a fixed seed differ
+// from one another
+#[derive(Resource, Debug, Default)]
+struct ScrambleCount(u64);
+
+/// Makes the random scrambles with the options of the settings.
+#[derive(SystemParam)]
+pub(crate) struct Scrambler<'w> {
+    length: Res<'w, ScrambleLength>,
+    wide_moves: Res<'w, WideMoves>,
+    rotations: Res<'w, Rotations>,
+    seed: Res<'w, ScrambleSeed>,
+    count: ResMut<'w, ScrambleCount>,
+}
+
+impl Scrambler<'_> {
+    pub fn scramble(&mut self, cube_size: CubeSize) -> Vec<Move> {
+        self.scramble_of_length(cube_size, self.length.0)
+    }
+
+    /// A scramble of the given number of moves, or of the length of the settings.
+    pub fn scramble_of_length(&mut self, cube_size: CubeSize, length: Option<usize>) -> Vec<Move> {
+        let seed = self.seed.0.map(|seed| seed.wrapping_add(self.count.0));
+        self.count.0 += 1;
+        let options = ScrambleOptions {
+            length,
+            wide_moves: self.wide_moves.allowed(cube_size),
+            rotations: *self.rotations == Rotations::On,
+            seed,
+        };
+        random_scramble_with(cube_size, options)
+    }
+}
+
+fn load_scramble_options(
+    settings: Res<Settings>,
+    mut length: ResMut<ScrambleLength>,
+    mut wide_moves: ResMut<WideMoves>,
+    mut rotations: ResMut<Rotations>,
+    mut seed: ResMut<ScrambleSeed>,
+) {
+    length.set_if_neq(settings.scramble_length);
+    wide_moves.set_if_neq(settings.wide_moves);
+    rotations.set_if_neq(if settings.rotations {
+        Rotations::On
+    } else {
+        Rotations::Off
+    });
+    seed.set_if_neq(settings.scramble_seed);
+}
+
+fn save_scramble_options(
+    length: Res<ScrambleLength>,
+    wide_moves: Res<WideMoves>,
+    rotations: Res<Rotations>,
+    seed: Res<ScrambleSeed>,
+    mut settings: ResMut<Settings>,
+) {
+    if length.is_changed() && settings.scramble_length != *length {
+        settings.scramble_length = *length;
+    }
+    if wide_moves.is_changed() && settings.wide_moves != *wide_moves {
+        settings.wide_moves = *wide_moves;
+    }
+    let rotations_on = *rotations == Rotations::On;
+    if rotations.is_changed() && settings.rotations != rotations_on {
+        settings.rotations = rotations_on;
+    }
+    if seed.is_changed() && settings.scramble_seed != *seed {
+        settings.scramble_seed = *seed;
+    }
+}
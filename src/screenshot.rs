@@ -0,0 +1,79 @@
+use crate::{i18n::Localized, simulator::StatusLine, GameState};
+use bevy::{prelude::*, render::view::screenshot::ScreenshotManager, window::PrimaryWindow};
+
+const SCREENSHOT_KEY: KeyCode = KeyCode::P;
+
+pub struct ScreenshotPlugin;
+
+impl Plugin for ScreenshotPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HideUi>()
+            .add_systems(Update, take_screenshot.run_if(in_state(GameState::Playing)))
+            .add_systems(PostUpdate, hide_ui);
+    }
+}
+
+/// Hides the UI from the camera, for a screenshot or while a replay is exported.
+#[derive(Resource, Default)]
+pub(crate) struct HideUi {
+    // only for the frame of the screenshot
+    screenshot: bool,
+    pub export: bool,
+}
+
+/// A file name made unique by the time it's created at, like `screenshot-1700000000.png`.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn timestamped(name: &str, extension: &str, _time: &Time) -> String {
+    let secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|since| since.as_secs())
+        .unwrap_or_default();
+    format!("{name}-{secs}{extension}")
+}
+
+// the system clock can't be read from wasm, so the time since the app started is used
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn timestamped(name: &str, extension: &str, time: &Time) -> String {
+    format!("{name}-{}{extension}", time.elapsed().as_millis())
+}
+
+/// Saves the frame to a PNG with `P`, hiding the UI for that frame with Shift + `P`.
+fn take_screenshot(
+    keyboard_input: Res<Input<KeyCode>>,
+    time: Res<Time>,
+    mut screenshot_manager: ResMut<ScreenshotManager>,
+    mut hide_ui: ResMut<HideUi>,
+    q_window: Query<Entity, With<PrimaryWindow>>,
+    mut status_line: StatusLine,
+) {
+    if !keyboard_input.just_pressed(SCREENSHOT_KEY) {
+        return;
+    }
+    let Ok(window) = q_window.get_single() else {
+        return;
+    };
+
+    // the browser downloads the screenshot under that name instead of saving it
+    let path = timestamped("screenshot", ".png", &time);
+    let status = match screenshot_manager.save_screenshot_to_disk(window, &path) {
+        Ok(()) => {
+            hide_ui.screenshot = keyboard_input.pressed(KeyCode::ShiftLeft);
+            Localized::new("screenshot-taken").with("path", path)
+        }
+        Err(_) => Localized::new("screenshot-busy"),
+    };
+    status_line.show(status);
+}
+
+// Hides the UI while asked to, showing it again once the screenshot without it is rendered
+fn hide_ui(mut hide_ui: ResMut<HideUi>, mut q_camera: Query<&mut UiCameraConfig, With<Camera3d>>) {
+    let show_ui = !hide_ui.screenshot && !hide_ui.export;
+    for mut config in &mut q_camera {
+        if config.show_ui != show_ui {
+            config.show_ui = show_ui;
+        }
+    }
+    if hide_ui.screenshot {
+        hide_ui.screenshot = false;
+    }
+}
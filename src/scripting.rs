@@ -0,0 +1,302 @@
+use crate::{
+    console::ConsoleCommand,
+    i18n::Localized,
+    simulator::{CurrentCube, MoveQueue, Played, PlayedMoves, StatusLine},
+    solved::CubeSolvedEvent,
+    GameState, Puzzle,
+};
+use bevy::prelude::*;
+use cubesim::{parse_algorithm_for, prelude::*};
+use rhai::{Array, CallFnOptions, Dynamic, Engine, EvalAltResult, Map, Scope, AST};
+use std::{cell::RefCell, fs, path::Path, rc::Rc};
+
+// Every script in there is loaded when the app starts
+const SCRIPTS_DIR: &str = "scripts";
+
+/// Runs the [Rhai](https://rhai.rs) scripts of the `scripts` folder, calling the functions they
+/// define when something happens to the cube:
+///
+/// - `on_start()` once a game starts,
+/// - `on_move(move)` for each move made, in WCA notation,
+/// - `on_solved(solve)` once a scrambled cube is solved, or its timer stopped, with the `time`
+///   in seconds, the number of `moves`, the `scramble` and `solution` of the solve and its
+///   `penalty`, `+2`, `DNF` or empty.
+///
+/// The scripts can call `scramble()`, `scramble(moves)`, `alg(moves)`, `solve()` and `reset()`
+/// like the console, and read `cube_size()`, `moves()`, `queued()` and `is_solved()`. Each
+/// script keeps its own state in `this`, such as this drill:
+///
+/// ```text
+/// fn on_start() {
+///     this.times = [];
+///     scramble();
+/// }
+///
+/// fn on_solved(solve) {
+///     this.times.push(solve.time);
+///     print(`solve ${this.times.len()}: ${solve.time}`);
+///     if this.times.len() < 50 {
+///         scramble();
+///     }
+/// }
+/// ```
+pub struct ScriptingPlugin;
+
+impl Plugin for ScriptingPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_non_send_resource(Scripts::load(Path::new(SCRIPTS_DIR)))
+            .add_systems(
+                OnEnter(GameState::Playing),
+                start_scripts.run_if(resource_equals(Puzzle::Cube)),
+            )
+            .add_systems(
+                Update,
+                run_hooks
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(resource_equals(Puzzle::Cube)),
+            );
+    }
+}
+
+// What the scripts see of the cube, and what they ask for, between two calls to them
+#[derive(Default)]
+struct Shared {
+    cube_size: usize,
+    moves: Vec<String>,
+    queued: usize,
+    solved: bool,
+    commands: Vec<ConsoleCommand>,
+    printed: Vec<String>,
+}
+
+struct Script {
+    name: String,
+    ast: AST,
+    scope: Scope<'static>,
+    // the object map bound to `this`
+    state: Dynamic,
+}
+
+// The engine's functions hold on to the shared state, so it can't be sent to other threads
+struct Scripts {
+    engine: Engine,
+    scripts: Vec<Script>,
+    shared: Rc<RefCell<Shared>>,
+}
+
+impl Scripts {
+    fn load(dir: &Path) -> Self {
+        let shared = Rc::new(RefCell::new(Shared::default()));
+        let engine = script_engine(&shared);
+
+        let mut paths: Vec<_> = match fs::read_dir(dir) {
+            Ok(entries) => entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.extension().is_some_and(|ext| ext == "rhai"))
+                .collect(),
+            Err(err) => {
+                debug!("no scripts loaded from {}: {err}", dir.display());
+                Vec::new()
+            }
+        };
+        paths.sort();
+
+        let mut scripts = Vec::new();
+        for path in paths {
+            let name = path.display().to_string();
+            match engine.compile_file(path) {
+                Ok(ast) => {
+                    info!("loaded the script {name}");
+                    scripts.push(Script {
+                        name,
+                        ast,
+                        scope: Scope::new(),
+                        state: Map::new().into(),
+                    });
+                }
+                Err(err) => warn!("skipping the script {name}: {err}"),
+            }
+        }
+
+        Self {
+            engine,
+            scripts,
+            shared,
+        }
+    }
+
+    /// Calls a function on every script defining it, returning the errors they ran into.
+    fn call(&mut self, name: &str, args: impl Fn() -> Vec<Dynamic>) -> Vec<String> {
+        let mut errors = Vec::new();
+        for script in &mut self.scripts {
+            let defined = script
+                .ast
+                .iter_functions()
+                .any(|f| f.name == name && f.params.len() == args().len());
+            if !defined {
+                continue;
+            }
+
+            // the script's statements out of functions only run once, when it's loaded
+            let options = CallFnOptions::new()
+                .eval_ast(false)
+                .bind_this_ptr(&mut script.state);
+            if let Err(err) = self.engine.call_fn_with_options::<Dynamic>(
+                options,
+                &mut script.scope,
+                &script.ast,
+                name,
+                args(),
+            ) {
+                errors.push(format!("{}: {err}", script.name));
+            }
+        }
+        errors
+    }
+}
+
+fn script_engine(shared: &Rc<RefCell<Shared>>) -> Engine {
+    let mut engine = Engine::new();
+
+    let command = |command: ConsoleCommand| {
+        let shared = shared.clone();
+        move || shared.borrow_mut().commands.push(command.clone())
+    };
+    engine.register_fn("scramble", command(ConsoleCommand::Scramble(None)));
+    engine.register_fn("solve", command(ConsoleCommand::Solve));
+    engine.register_fn("reset", command(ConsoleCommand::Reset));
+
+    let s = shared.clone();
+    engine.register_fn(
+        "scramble",
+        move |moves: i64| -> Result<(), Box<EvalAltResult>> {
+            let moves = usize::try_from(moves)
+                .ok()
+                .filter(|&moves| moves > 0)
+                .ok_or_else(|| format!("invalid number of moves {moves}"))?;
+            s.borrow_mut()
+                .commands
+                .push(ConsoleCommand::Scramble(Some(moves)));
+            Ok(())
+        },
+    );
+    let s = shared.clone();
+    engine.register_fn(
+        "alg",
+        move |moves: &str| -> Result<(), Box<EvalAltResult>> {
+            let size = s.borrow().cube_size as CubeSize;
+            let moves = parse_algorithm_for(moves, size).map_err(|err| err.to_string())?;
+            s.borrow_mut().commands.push(ConsoleCommand::Alg(moves));
+            Ok(())
+        },
+    );
+
+    let s = shared.clone();
+    engine.register_fn("cube_size", move || s.borrow().cube_size as i64);
+    let s = shared.clone();
+    engine.register_fn("moves", move || -> Array {
+        s.borrow()
+            .moves
+            .iter()
+            .cloned()
+            .map(Dynamic::from)
+            .collect()
+    });
+    let s = shared.clone();
+    engine.register_fn("queued", move || s.borrow().queued as i64);
+    let s = shared.clone();
+    engine.register_fn("is_solved", move || s.borrow().solved);
+
+    let s = shared.clone();
+    engine.on_print(move |text| s.borrow_mut().printed.push(text.to_string()));
+    engine
+}
+
+fn moves_string(moves: &[Move]) -> String {
+    moves
+        .iter()
+        .map(|m| m.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// Shows what the scripts printed and the errors they ran into, sending the commands they made
+fn finish_calls(
+    scripts: &Scripts,
+    errors: Vec<String>,
+    commands: &mut EventWriter<ConsoleCommand>,
+    status_line: &mut StatusLine,
+) {
+    let mut shared = scripts.shared.borrow_mut();
+    commands.send_batch(shared.commands.drain(..));
+
+    for error in &errors {
+        warn!("script error in {error}");
+    }
+    let printed: Vec<String> = shared.printed.drain(..).collect();
+    for line in &printed {
+        info!("{line}");
+    }
+    if let Some(error) = errors.last() {
+        status_line.show(Localized::new("script-error").with("error", error.as_str()));
+    } else if let Some(line) = printed.last() {
+        status_line.show_text(line);
+    }
+}
+
+// Lets the scripts see the cube as it is before calling them
+fn update_shared(scripts: &Scripts, current_cube: &CurrentCube, move_queue: &MoveQueue) {
+    let mut shared = scripts.shared.borrow_mut();
+    shared.cube_size = current_cube.cube_size();
+    shared.moves = current_cube.moves().iter().map(|m| m.to_string()).collect();
+    shared.queued = move_queue.len();
+    shared.solved = current_cube.is_solved();
+}
+
+fn start_scripts(
+    mut scripts: NonSendMut<Scripts>,
+    current_cube: Played<CurrentCube>,
+    move_queue: Played<MoveQueue>,
+    mut commands: EventWriter<ConsoleCommand>,
+    mut status_line: StatusLine,
+) {
+    update_shared(&scripts, &current_cube, &move_queue);
+    let errors = scripts.call("on_start", Vec::new);
+    finish_calls(&scripts, errors, &mut commands, &mut status_line);
+}
+
+fn run_hooks(
+    mut scripts: NonSendMut<Scripts>,
+    mut move_applied: PlayedMoves,
+    mut solved_events: EventReader<CubeSolvedEvent>,
+    current_cube: Played<CurrentCube>,
+    move_queue: Played<MoveQueue>,
+    mut commands: EventWriter<ConsoleCommand>,
+    mut status_line: StatusLine,
+) {
+    if scripts.scripts.is_empty() {
+        move_applied.clear();
+        solved_events.clear();
+        return;
+    }
+    update_shared(&scripts, &current_cube, &move_queue);
+
+    let mut errors = Vec::new();
+    for mv in move_applied.read() {
+        errors.extend(scripts.call("on_move", || vec![mv.to_string().into()]));
+    }
+    for solved in solved_events.read() {
+        let mut solve = Map::new();
+        solve.insert(
+            "time".into(),
+            (solved.report.stats.duration.as_secs_f64()).into(),
+        );
+        solve.insert("moves".into(), (solved.solution_length as i64).into());
+        solve.insert("scramble".into(), moves_string(&solved.scramble).into());
+        solve.insert("solution".into(), moves_string(&solved.solution).into());
+        let penalty = solved.report.penalty.map(|penalty| penalty.to_string());
+        solve.insert("penalty".into(), penalty.unwrap_or_default().into());
+        errors.extend(scripts.call("on_solved", || vec![solve.clone().into()]));
+    }
+    finish_calls(&scripts, errors, &mut commands, &mut status_line);
+}
@@ -0,0 +1,394 @@
+use crate::{
+    i18n::{Localized, Translations},
+    simulator::{CurrentCube, FaceColors, Played},
+    spectate::Spectator,
+    target::net_position,
+    theme::{Theme, ThemeColor},
+    warmup::SolverTables,
+    GameState, Puzzle,
+};
+use bevy::{
+    prelude::*,
+    tasks::{block_on, AsyncComputeTaskPool, Task},
+    utils::Instant,
+};
+use cubesim::{
+    prelude::*, solve_phases_observed, sticker_index, FaceletCube, SearchProgress, Solution,
+    ThistlethwaitePhase,
+};
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+const TOGGLE_KEY: KeyCode = KeyCode::K;
+// How long each candidate stays on the ghost cube
+const FRAME_SECONDS: f32 = 0.25;
+// The most candidates kept for the ghost cube, the first ones of the search
+const MAX_FRAMES: usize = 400;
+const NET_WIDTH: f32 = 160.0;
+const GHOST_ALPHA: f32 = 0.6;
+
+pub struct SearchOverlayPlugin;
+
+impl Plugin for SearchOverlayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (toggle_overlay, finish_search, toggle_animation, show_search)
+                .chain()
+                .run_if(in_state(GameState::Playing))
+                .run_if(resource_equals(Puzzle::Cube))
+                .run_if(not(resource_exists::<Spectator>())),
+        )
+        .add_systems(OnExit(GameState::Playing), clean_up);
+    }
+}
+
+// What the Thistlethwaite search of the cube shown went through so far, written by the
+// search as it goes
+#[derive(Default)]
+struct SearchLog {
+    phase: Option<ThistlethwaitePhase>,
+    // the moves solving the phases before the one searched
+    before: Vec<Move>,
+    progress: Option<SearchProgress<Move>>,
+    // the states visited by the phases before the one searched
+    nodes_before: u64,
+    elapsed: Duration,
+    // the moves leading to the states visited, for the ghost cube
+    frames: Vec<Vec<Move>>,
+}
+
+impl SearchLog {
+    fn record(&mut self, phase: ThistlethwaitePhase, progress: &SearchProgress<Move>) {
+        if self.phase != Some(phase) {
+            if let Some(last) = self.progress.take() {
+                self.nodes_before += last.nodes;
+                self.before.extend(last.best);
+            }
+            self.phase = Some(phase);
+        }
+
+        let frame: Vec<Move> = self.before.iter().chain(&progress.moves).copied().collect();
+        if self.frames.len() < MAX_FRAMES && self.frames.last() != Some(&frame) {
+            self.frames.push(frame);
+        }
+        self.progress = Some(progress.clone());
+    }
+
+    fn nodes(&self) -> u64 {
+        self.nodes_before + self.progress.as_ref().map_or(0, |progress| progress.nodes)
+    }
+
+    // The best moves found so far, with the phases solved before
+    fn best(&self) -> Vec<Move> {
+        let best = self.progress.iter().flat_map(|progress| &progress.best);
+        self.before.iter().chain(best).copied().collect()
+    }
+}
+
+/// The search of the cube's solution shown by the overlay, toggled with `K`.
+#[derive(Resource)]
+struct SearchRun {
+    cube: FaceletCube,
+    log: Arc<Mutex<SearchLog>>,
+    task: Option<Task<Option<Solution>>>,
+    solution: Option<Option<Solution>>,
+    // whether the ghost cube goes through the states visited, or shows the best one
+    animate: bool,
+    frame: usize,
+    animation: Timer,
+}
+
+#[derive(Component)]
+struct SearchPanel;
+
+#[derive(Component)]
+struct SearchText;
+
+// A sticker of the ghost cube's net, by its index in the state of the cube
+#[derive(Component)]
+struct GhostSticker(usize);
+
+#[derive(Component)]
+struct GhostNet;
+
+fn toggle_overlay(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    current_cube: Played<CurrentCube>,
+    tables: Res<SolverTables>,
+    asset_server: Res<AssetServer>,
+    theme: Res<Theme>,
+    q_panel: Query<Entity, With<SearchPanel>>,
+) {
+    if !keyboard_input.just_pressed(TOGGLE_KEY) {
+        return;
+    }
+    if !q_panel.is_empty() {
+        for entity in &q_panel {
+            commands.entity(entity).despawn_recursive();
+        }
+        commands.remove_resource::<SearchRun>();
+        return;
+    }
+
+    let size = current_cube.cube_size();
+    let message = if size != 3 {
+        Some(Localized::new("search-cube-size").with("size", size))
+    } else if !tables.is_ready() {
+        Some(tables.status())
+    } else {
+        None
+    };
+    let searching = message.is_none();
+    spawn_panel(&mut commands, &asset_server, *theme, message);
+    if searching {
+        commands.insert_resource(start_search(current_cube.moves()));
+    }
+}
+
+fn start_search(moves: &[Move]) -> SearchRun {
+    let cube = FaceletCube::new(3).apply_moves(moves);
+    let log = Arc::new(Mutex::new(SearchLog::default()));
+
+    let searched = cube.clone();
+    let shared = log.clone();
+    let task = AsyncComputeTaskPool::get().spawn(async move {
+        let start = Instant::now();
+        solve_phases_observed(&searched, &mut |phase, progress| {
+            let mut log = shared.lock().unwrap();
+            log.record(phase, progress);
+            log.elapsed = start.elapsed();
+        })
+    });
+
+    SearchRun {
+        cube,
+        log,
+        task: Some(task),
+        solution: None,
+        animate: false,
+        frame: 0,
+        animation: Timer::from_seconds(FRAME_SECONDS, TimerMode::Repeating),
+    }
+}
+
+fn finish_search(run: Option<ResMut<SearchRun>>) {
+    let Some(mut run) = run else {
+        return;
+    };
+    if run.task.as_ref().is_some_and(|task| task.is_finished()) {
+        let task = run.task.take().unwrap();
+        run.solution = Some(block_on(task));
+    }
+}
+
+// Clicking the ghost cube goes through the states visited, or back to the best one
+fn toggle_animation(
+    run: Option<ResMut<SearchRun>>,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<GhostNet>)>,
+) {
+    let Some(mut run) = run else {
+        return;
+    };
+    if interaction_query.iter().any(|i| *i == Interaction::Pressed) {
+        run.animate = !run.animate;
+        run.frame = 0;
+        run.animation.reset();
+    }
+}
+
+fn show_search(
+    time: Res<Time>,
+    run: Option<ResMut<SearchRun>>,
+    face_colors: Res<FaceColors>,
+    translations: Translations,
+    mut q_text: Query<&mut Text, With<SearchText>>,
+    mut q_stickers: Query<(&GhostSticker, &mut BackgroundColor)>,
+) {
+    let Some(mut run) = run else {
+        return;
+    };
+    if run.animation.tick(time.delta()).just_finished() {
+        run.frame += run.animation.times_finished_this_tick() as usize;
+    }
+
+    let log = run.log.lock().unwrap();
+    let seconds = log.elapsed.as_secs_f64().max(1e-6);
+    let mut lines = vec![
+        translations.text(Localized::new("search-title").with("key", format!("{TOGGLE_KEY:?}"))),
+        translations.text(
+            Localized::new("search-states")
+                .with("states", count(log.nodes() as f64))
+                .with("rate", count(log.nodes() as f64 / seconds)),
+        ),
+    ];
+    if let (Some(phase), Some(progress)) = (log.phase, &log.progress) {
+        lines.push(
+            translations.text(
+                Localized::new("search-phase")
+                    .with("phase", phase.to_string())
+                    .with("depth", progress.depth),
+            ),
+        );
+        lines.push(
+            translations.text(
+                Localized::new("search-best")
+                    .with("moves", moves_text(&progress.best))
+                    .with("left", progress.best_bound),
+            ),
+        );
+    }
+    lines.push(
+        translations.text(match &run.solution {
+            None => Localized::new("search-searching"),
+            Some(Some(solution)) => Localized::new("search-solved")
+                .with("moves", solution.len())
+                .with("ms", format!("{:.1}", seconds * 1000.0)),
+            Some(None) => Localized::new("search-no-solution"),
+        }),
+    );
+
+    let (ghost, shown) = match run.animate && !log.frames.is_empty() {
+        true => {
+            let frame = run.frame % log.frames.len();
+            (
+                log.frames[frame].clone(),
+                Localized::new("search-ghost-visited")
+                    .with("frame", frame + 1)
+                    .with("frames", log.frames.len()),
+            )
+        }
+        false => (log.best(), Localized::new("search-ghost-best")),
+    };
+    lines.push(translations.text(shown));
+    drop(log);
+
+    for mut text in &mut q_text {
+        text.sections[0].value = lines.join("\n");
+    }
+    let state = run.cube.apply_moves(&ghost).state();
+    for (sticker, mut background) in &mut q_stickers {
+        if let Some(&face) = state.get(sticker.0) {
+            background.0 = face_colors.color(face).with_a(GHOST_ALPHA);
+        }
+    }
+}
+
+// A number of states, in thousands or millions once large
+fn count(n: f64) -> String {
+    match n {
+        n if n >= 1e6 => format!("{:.1}M", n / 1e6),
+        n if n >= 1e3 => format!("{:.1}k", n / 1e3),
+        n => format!("{n:.0}"),
+    }
+}
+
+fn moves_text(moves: &[Move]) -> String {
+    match moves.is_empty() {
+        true => "-".to_string(),
+        false => moves
+            .iter()
+            .map(|mv| mv.to_string())
+            .collect::<Vec<_>>()
+            .join(" "),
+    }
+}
+
+// The ghost cube's net and the search's text, or only why the search can't be shown
+fn spawn_panel(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    theme: Theme,
+    message: Option<Localized>,
+) {
+    let face_width = NET_WIDTH / 4.0;
+    let sticker_width = face_width / 3.0;
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    bottom: Val::Px(10.0),
+                    left: Val::Px(220.0),
+                    flex_direction: FlexDirection::Row,
+                    align_items: AlignItems::Center,
+                    column_gap: Val::Px(10.0),
+                    padding: UiRect::all(Val::Px(10.0)),
+                    ..default()
+                },
+                ..default()
+            },
+            ThemeColor::Panel,
+            SearchPanel,
+        ))
+        .with_children(|parent| {
+            if message.is_none() {
+                parent
+                    .spawn((
+                        NodeBundle {
+                            style: Style {
+                                width: Val::Px(NET_WIDTH),
+                                height: Val::Px(face_width * 3.0),
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        GhostNet,
+                        Interaction::None,
+                    ))
+                    .with_children(|parent| {
+                        for face in [Face::U, Face::R, Face::F, Face::D, Face::L, Face::B] {
+                            let (net_x, net_y) = net_position(face);
+                            for n in 0..9 {
+                                let (row, col) = (n / 3, n % 3);
+                                parent.spawn((
+                                    NodeBundle {
+                                        style: Style {
+                                            position_type: PositionType::Absolute,
+                                            left: Val::Px(
+                                                net_x as f32 * face_width
+                                                    + col as f32 * sticker_width,
+                                            ),
+                                            top: Val::Px(
+                                                net_y as f32 * face_width
+                                                    + row as f32 * sticker_width,
+                                            ),
+                                            width: Val::Px(sticker_width - 1.0),
+                                            height: Val::Px(sticker_width - 1.0),
+                                            ..default()
+                                        },
+                                        ..default()
+                                    },
+                                    GhostSticker(sticker_index(3, face, n + 1) as usize),
+                                ));
+                            }
+                        }
+                    });
+            }
+            let mut text = parent.spawn((
+                TextBundle::from_section(
+                    String::new(),
+                    TextStyle {
+                        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                        font_size: 18.0,
+                        color: theme.text(),
+                    },
+                ),
+                SearchText,
+            ));
+            if let Some(message) = message {
+                text.insert(message);
+            }
+        });
+}
+
+fn clean_up(mut commands: Commands, q_panel: Query<Entity, With<SearchPanel>>) {
+    for entity in &q_panel {
+        commands.entity(entity).despawn_recursive();
+    }
+    commands.remove_resource::<SearchRun>();
+}
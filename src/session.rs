@@ -0,0 +1,166 @@
+use crate::{
+    i18n::Localized,
+    simulator::{CurrentCube, MoveQueue, Played, PlayedMut, StatusLine},
+    GameState,
+};
+use bevy::{
+    prelude::*,
+    tasks::{block_on, AsyncComputeTaskPool, Task},
+};
+use cubesim::{
+    prelude::*, solve_phases_with, FaceletCube, Solution, SolveOptions, ThistlethwaitePhase,
+};
+
+pub struct SessionPlugin;
+
+impl Plugin for SessionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, queue_solution.run_if(in_state(GameState::Playing)));
+    }
+}
+
+/// How the solve of the played cube's last scramble is being made, and the solution the solver
+/// queued for it, next to the ``CurrentCube`` of the ``PlayedCube``.
+#[derive(Component, Default)]
+pub(crate) struct SolveSession {
+    // whether the solver or a guided solution was used since the last scramble
+    assisted: bool,
+    // whether the last scramble is the one of a race
+    raced: bool,
+    // the solution queued last, after the number of moves it starts from
+    solution: Option<(usize, Solution)>,
+    // the solver running in the background, after the number of moves it solves the cube from
+    solving: Option<(usize, Task<Option<Solution>>)>,
+}
+
+impl SolveSession {
+    /// Whether the solver or a guided solution was used since the last scramble.
+    pub(crate) fn is_assisted(&self) -> bool {
+        self.assisted
+    }
+
+    pub(crate) fn mark_assisted(&mut self) {
+        self.assisted = true;
+    }
+
+    /// Whether the last scramble was raced against others, until the next one.
+    pub(crate) fn is_raced(&self) -> bool {
+        self.raced
+    }
+
+    pub(crate) fn mark_raced(&mut self) {
+        self.raced = true;
+    }
+
+    /// Queues the moves of a scramble on the cube, starting a new session.
+    pub(crate) fn queue_scramble(
+        &mut self,
+        current_cube: &mut CurrentCube,
+        move_queue: &mut MoveQueue,
+        scramble: Vec<Move>,
+    ) {
+        current_cube.queue_scramble(move_queue, scramble);
+        *self = Self::default();
+    }
+
+    /// Stops following the solution once the moves made and the ``queued`` moves left no
+    /// longer hold all of it, as when the queue is cut short or the moves are seeked through.
+    pub(crate) fn cut_solution(&mut self, current_cube: &CurrentCube, queued: usize) {
+        let end = current_cube.moves().len() + queued;
+        if self
+            .solution
+            .as_ref()
+            .is_some_and(|(start, solution)| start + solution.len() > end)
+        {
+            self.solution = None;
+        }
+    }
+
+    /// The solution queued last, with the number of its moves made so far.
+    pub(crate) fn solution(&self, current_cube: &CurrentCube) -> Option<(&Solution, usize)> {
+        let (start, solution) = self.solution.as_ref()?;
+        let made = current_cube
+            .moves()
+            .len()
+            .saturating_sub(*start)
+            .min(solution.len());
+        Some((solution, made))
+    }
+
+    /// The phase of the last move made while it's part of the solution queued last, with the
+    /// moves of the phase and the move's index among them.
+    pub(crate) fn solution_move(
+        &self,
+        current_cube: &CurrentCube,
+    ) -> Option<(ThistlethwaitePhase, &[Move], usize)> {
+        let (start, solution) = self.solution.as_ref()?;
+        let index = current_cube.moves().len().checked_sub(start + 1)?;
+        solution.phase_of(index)
+    }
+
+    /// Starts solving the cube as it is once the moves queued are made, in the background so
+    /// that large scrambles don't freeze the game. Its moves are queued once found, unless the
+    /// cube turned in the meantime. Only 3x3x3 cubes can be solved.
+    pub(crate) fn solve(
+        &mut self,
+        current_cube: &CurrentCube,
+        move_queue: &MoveQueue,
+    ) -> Result<(), Localized> {
+        let size = current_cube.cube_size();
+        if size != 3 {
+            return Err(Localized::new("solver-cube-size").with("size", size));
+        }
+
+        let mut moves = current_cube.moves().to_vec();
+        moves.extend(move_queue.iter());
+        let start = moves.len();
+        let task = AsyncComputeTaskPool::get().spawn(async move {
+            let cube = FaceletCube::new(size as CubeSize).apply_moves(&moves);
+            let solution = solve_phases_with(&cube, SolveOptions::all_cores());
+            if solution.is_none() {
+                warn!("Facelet Cube {:?} no solver", cube.state());
+            }
+            solution
+        });
+        self.solving = Some((start, task));
+        Ok(())
+    }
+}
+
+fn queue_solution(
+    current_cube: Played<CurrentCube>,
+    mut move_queue: PlayedMut<MoveQueue>,
+    mut session: PlayedMut<SolveSession>,
+    mut status_line: StatusLine,
+) {
+    if !session
+        .solving
+        .as_ref()
+        .is_some_and(|(_, task)| task.is_finished())
+    {
+        return;
+    }
+    let (start, task) = session.solving.take().unwrap();
+    let Some(solution) = block_on(task) else {
+        status_line.show(Localized::new("search-no-solution"));
+        return;
+    };
+    // the solution only holds for the cube it was found for
+    if current_cube.moves().len() + move_queue.len() != start {
+        warn!("the cube turned while it was being solved");
+        return;
+    }
+
+    session.mark_assisted();
+    info!(
+        "Solution {}",
+        solution
+            .moves()
+            .iter()
+            .map(|m| m.to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    );
+    move_queue.extend(solution.moves());
+    session.solution = Some((start, solution));
+}
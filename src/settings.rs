@@ -0,0 +1,361 @@
+use crate::{
+    i18n::Language,
+    macros::AlgMacro,
+    robot::RobotOutput,
+    scramble::{ScrambleLength, ScrambleSeed, WideMoves},
+    storage,
+    theme::{Accent, Theme},
+};
+use bevy::prelude::*;
+
+// Kept next to the executable's working directory, in a plain text format meant to be
+// editable by hand
+pub(crate) const SETTINGS_PATH: &str = "settings.txt";
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Settings::load())
+            .add_systems(Last, save_settings);
+    }
+}
+
+/// The user's settings kept between runs of the app, in sections such as:
+///
+/// ```text
+/// [controls]
+/// screen_relative = false
+///
+/// [macros]
+/// F1 = Sexy move: R U R' U'
+///
+/// [robot]
+/// output = tcp 192.168.1.20:9002
+///
+/// [scramble]
+/// length = auto
+/// wide_moves = on
+/// rotations = false
+/// seed = 1234
+///
+/// [showcase]
+/// idle = 120
+///
+/// [speech]
+/// callout = true
+/// pace = 1.5
+///
+/// [ui]
+/// language = zh-CN
+/// theme = light
+/// accent = #3366cc
+/// confirm_discard = false
+/// ```
+#[derive(Resource, Debug)]
+pub(crate) struct Settings {
+    /// Whether the face keys turn the faces where they are on the screen, rather than the
+    /// faces of the cube named before its whole cube rotations.
+    pub screen_relative: bool,
+    pub macros: Vec<AlgMacro>,
+    pub robot: Option<RobotOutput>,
+    pub scramble_length: ScrambleLength,
+    pub wide_moves: WideMoves,
+    /// Whether the random scrambles also turn the whole cube.
+    pub rotations: bool,
+    pub scramble_seed: ScrambleSeed,
+    /// The seconds without input before the cube starts showing off on its own, as on a kiosk
+    /// at a cubing event, if ever.
+    pub showcase_idle: Option<f32>,
+    /// Whether the scrambles are read aloud, one move at a time.
+    pub callout: bool,
+    /// The seconds between two moves read aloud.
+    pub callout_pace: f32,
+    pub language: Language,
+    pub theme: Theme,
+    pub accent: Accent,
+    /// Whether to ask before giving up a solve in progress.
+    pub confirm_discard: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            screen_relative: true,
+            macros: Vec::new(),
+            robot: None,
+            scramble_length: ScrambleLength::default(),
+            wide_moves: WideMoves::default(),
+            rotations: false,
+            scramble_seed: ScrambleSeed::default(),
+            showcase_idle: None,
+            callout: false,
+            callout_pace: 2.0,
+            language: Language::default(),
+            theme: Theme::default(),
+            accent: Accent::default(),
+            confirm_discard: true,
+        }
+    }
+}
+
+impl Settings {
+    fn load() -> Self {
+        match storage::read_to_string(SETTINGS_PATH) {
+            Ok(text) => Self::parse(&text),
+            Err(err) => {
+                debug!("no settings loaded from {SETTINGS_PATH}: {err}");
+                Self::default()
+            }
+        }
+    }
+
+    fn parse(text: &str) -> Self {
+        let mut settings = Self::default();
+        let mut section = "";
+
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                section = name.trim();
+                continue;
+            }
+
+            match section {
+                "controls" => {
+                    let Some((key, value)) = line.split_once('=') else {
+                        warn!("skipping '{line}' in the controls section of {SETTINGS_PATH}");
+                        continue;
+                    };
+                    let parsed = match key.trim() {
+                        "screen_relative" => value
+                            .trim()
+                            .parse()
+                            .map(|s| settings.screen_relative = s)
+                            .map_err(|_| format!("'{}' is neither true nor false", value.trim())),
+                        key => Err(format!("unknown setting '{key}'")),
+                    };
+                    if let Err(err) = parsed {
+                        warn!(
+                            "skipping '{line}' in the controls section of {SETTINGS_PATH}: {err}"
+                        );
+                    }
+                }
+                "macros" => match line.parse() {
+                    Ok(alg_macro) => settings.macros.push(alg_macro),
+                    Err(err) => warn!("skipping macro '{line}' in {SETTINGS_PATH}: {err}"),
+                },
+                "robot" => match line.split_once('=') {
+                    Some((key, value)) if key.trim() == "output" => match value.parse() {
+                        Ok(output) => settings.robot = Some(output),
+                        Err(err) => warn!("skipping the robot output in {SETTINGS_PATH}: {err}"),
+                    },
+                    _ => warn!("skipping '{line}' in the robot section of {SETTINGS_PATH}"),
+                },
+                "scramble" => {
+                    let Some((key, value)) = line.split_once('=') else {
+                        warn!("skipping '{line}' in the scramble section of {SETTINGS_PATH}");
+                        continue;
+                    };
+                    let parsed = match key.trim() {
+                        "length" => value.trim().parse().map(|l| settings.scramble_length = l),
+                        "wide_moves" => value.trim().parse().map(|w| settings.wide_moves = w),
+                        "rotations" => value
+                            .trim()
+                            .parse()
+                            .map(|r| settings.rotations = r)
+                            .map_err(|_| format!("'{}' is neither true nor false", value.trim())),
+                        "seed" => value.trim().parse().map(|s| settings.scramble_seed = s),
+                        key => Err(format!("unknown setting '{key}'")),
+                    };
+                    if let Err(err) = parsed {
+                        warn!(
+                            "skipping '{line}' in the scramble section of {SETTINGS_PATH}: {err}"
+                        );
+                    }
+                }
+                "showcase" => match line.split_once('=') {
+                    Some((key, value)) if key.trim() == "idle" => match value.trim().parse() {
+                        Ok(idle) if idle > 0.0 => settings.showcase_idle = Some(idle),
+                        _ => warn!(
+                            "skipping the showcase idle time in {SETTINGS_PATH}: '{}' is no \
+                             number of seconds",
+                            value.trim()
+                        ),
+                    },
+                    _ => warn!("skipping '{line}' in the showcase section of {SETTINGS_PATH}"),
+                },
+                "speech" => {
+                    let Some((key, value)) = line.split_once('=') else {
+                        warn!("skipping '{line}' in the speech section of {SETTINGS_PATH}");
+                        continue;
+                    };
+                    let parsed = match key.trim() {
+                        "callout" => value
+                            .trim()
+                            .parse()
+                            .map(|c| settings.callout = c)
+                            .map_err(|_| format!("'{}' is neither true nor false", value.trim())),
+                        "pace" => match value.trim().parse::<f32>() {
+                            Ok(pace) if pace > 0.0 => {
+                                settings.callout_pace = pace;
+                                Ok(())
+                            }
+                            _ => Err(format!("'{}' isn't a number of seconds", value.trim())),
+                        },
+                        key => Err(format!("unknown setting '{key}'")),
+                    };
+                    if let Err(err) = parsed {
+                        warn!("skipping '{line}' in the speech section of {SETTINGS_PATH}: {err}");
+                    }
+                }
+                "ui" => {
+                    let Some((key, value)) = line.split_once('=') else {
+                        warn!("skipping '{line}' in the ui section of {SETTINGS_PATH}");
+                        continue;
+                    };
+                    let parsed = match key.trim() {
+                        "language" => value.trim().parse().map(|l| settings.language = l),
+                        "theme" => value.trim().parse().map(|t| settings.theme = t),
+                        "accent" => value.trim().parse().map(|a| settings.accent = a),
+                        "confirm_discard" => value
+                            .trim()
+                            .parse()
+                            .map(|c| settings.confirm_discard = c)
+                            .map_err(|_| format!("'{}' is neither true nor false", value.trim())),
+                        key => Err(format!("unknown setting '{key}'")),
+                    };
+                    if let Err(err) = parsed {
+                        warn!("skipping '{line}' in the ui section of {SETTINGS_PATH}: {err}");
+                    }
+                }
+                _ => warn!("skipping '{line}' in unknown section [{section}] of {SETTINGS_PATH}"),
+            }
+        }
+
+        settings
+    }
+
+    fn to_text(&self) -> String {
+        let mut text = format!(
+            "[controls]\nscreen_relative = {}\n\n[macros]\n",
+            self.screen_relative
+        );
+        for alg_macro in &self.macros {
+            text.push_str(&format!("{alg_macro}\n"));
+        }
+        if let Some(output) = &self.robot {
+            text.push_str(&format!("\n[robot]\noutput = {output}\n"));
+        }
+        text.push_str(&format!(
+            "\n[scramble]\nlength = {}\nwide_moves = {}\nrotations = {}\nseed = {}\n",
+            self.scramble_length, self.wide_moves, self.rotations, self.scramble_seed
+        ));
+        if let Some(idle) = self.showcase_idle {
+            text.push_str(&format!("\n[showcase]\nidle = {idle}\n"));
+        }
+        text.push_str(&format!(
+            "\n[speech]\ncallout = {}\npace = {}\n",
+            self.callout, self.callout_pace
+        ));
+        text.push_str(&format!(
+            "\n[ui]\nlanguage = {}\ntheme = {}\naccent = {}\nconfirm_discard = {}\n",
+            self.language, self.theme, self.accent, self.confirm_discard
+        ));
+        text
+    }
+}
+
+fn save_settings(settings: Res<Settings>) {
+    // the settings were just loaded from the file
+    if !settings.is_changed() || settings.is_added() {
+        return;
+    }
+
+    if let Err(err) = storage::write(SETTINGS_PATH, &settings.to_text()) {
+        warn!("failed to save settings to {SETTINGS_PATH}: {err}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SETTINGS_TEXT: &str = "\
+[controls]
+screen_relative = false
+
+[macros]
+F1 = Sexy move: R U R' U'
+
+[robot]
+output = tcp 192.168.1.20:9002
+
+[scramble]
+length = 40
+wide_moves = on
+rotations = true
+seed = 1234
+
+[showcase]
+idle = 120
+
+[speech]
+callout = true
+pace = 1.5
+
+[ui]
+language = zh-CN
+theme = light
+accent = #3366cc
+confirm_discard = false
+";
+
+    #[test]
+    fn parse_every_setting() {
+        let settings = Settings::parse(SETTINGS_TEXT);
+
+        assert!(!settings.screen_relative);
+        assert_eq!(settings.macros.len(), 1);
+        assert_eq!(
+            settings.robot,
+            Some(RobotOutput::Tcp("192.168.1.20:9002".to_string()))
+        );
+        assert_eq!(settings.scramble_length, ScrambleLength(Some(40)));
+        assert_eq!(settings.wide_moves, WideMoves::On);
+        assert!(settings.rotations);
+        assert_eq!(settings.scramble_seed, ScrambleSeed(Some(1234)));
+        assert_eq!(settings.showcase_idle, Some(120.0));
+        assert!(settings.callout);
+        assert_eq!(settings.callout_pace, 1.5);
+        assert_eq!(settings.language, Language::Chinese);
+        assert_eq!(settings.theme, Theme::Light);
+        assert_eq!(settings.accent, "#3366cc".parse().unwrap());
+        assert!(!settings.confirm_discard);
+    }
+
+    #[test]
+    fn to_text_round_trips() {
+        assert_eq!(Settings::parse(SETTINGS_TEXT).to_text(), SETTINGS_TEXT);
+
+        let defaults = Settings::default().to_text();
+        assert_eq!(Settings::parse(&defaults).to_text(), defaults);
+    }
+
+    #[test]
+    fn invalid_lines_keep_their_defaults() {
+        let settings = Settings::parse(
+            "[scramble]\nlength = many\nrotations = maybe\nseed\n\n[speech]\npace = -1\n\n\
+             [colors]\nbackground = red\n",
+        );
+        let defaults = Settings::default();
+
+        assert_eq!(settings.scramble_length, defaults.scramble_length);
+        assert_eq!(settings.rotations, defaults.rotations);
+        assert_eq!(settings.scramble_seed, defaults.scramble_seed);
+        assert_eq!(settings.callout_pace, defaults.callout_pace);
+        assert_eq!(settings.to_text(), defaults.to_text());
+    }
+}
@@ -0,0 +1,168 @@
+use crate::{
+    menu::MenuState,
+    session::SolveSession,
+    simulator::{played_changed, CurrentCube, MoveQueue, Played, PlayedMut},
+    CubeSizeSetting, GameState, Puzzle,
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use bevy::prelude::*;
+use cubesim::{decode_moves, encode_moves, parse_algorithm, prelude::*};
+
+pub struct SharePlugin;
+
+impl Plugin for SharePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, open_shared_link.run_if(run_once()))
+            .add_systems(
+                Update,
+                // once the cube of the new game is set up
+                scramble_shared
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(resource_exists::<SharedScramble>()),
+            )
+            .add_systems(
+                Update,
+                share_scramble
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(resource_equals(Puzzle::Cube))
+                    .run_if(played_changed::<MoveQueue>),
+            );
+    }
+}
+
+/// A scramble shared in the fragment of the web app's URL, like `#size=4&moves=CQIi`, the moves
+/// being encoded by `cubesim` then in URL-safe base64. The links of older versions, like
+/// `#size=4&scramble=R_U'_2Fw2` with the moves separated by underscores, still open.
+#[derive(Resource, Clone, Debug, PartialEq)]
+struct SharedScramble {
+    size: usize,
+    moves: Vec<Move>,
+}
+
+impl SharedScramble {
+    fn fragment(&self) -> String {
+        let moves = URL_SAFE_NO_PAD.encode(encode_moves(&self.moves));
+        format!("size={}&moves={moves}", self.size)
+    }
+
+    fn parse(fragment: &str) -> Result<Self, String> {
+        let mut size = 3;
+        let mut moves = None;
+        for pair in fragment.split('&') {
+            match pair.split_once('=') {
+                Some(("size", value)) => {
+                    size = value
+                        .parse()
+                        .ok()
+                        .filter(|size| CubeSizeSetting::SIZES.contains(size))
+                        .ok_or_else(|| format!("invalid cube size '{value}'"))?;
+                }
+                Some(("moves", value)) => {
+                    let bytes = URL_SAFE_NO_PAD
+                        .decode(value)
+                        .map_err(|err| format!("invalid moves '{value}': {err}"))?;
+                    moves = Some(decode_moves(&bytes)?);
+                }
+                Some(("scramble", value)) => {
+                    // browsers may escape the primes of the inverse moves
+                    let scramble = value.replace("%27", "'").replace('_', " ");
+                    moves = Some(parse_algorithm(&scramble).map_err(|err| err.to_string())?);
+                }
+                _ => return Err(format!("unknown '{pair}'")),
+            }
+        }
+
+        let moves = moves.ok_or("no scramble")?;
+        Ok(Self { size, moves })
+    }
+}
+
+// The fragment of the page's URL, without the '#'
+#[cfg(target_arch = "wasm32")]
+fn read_fragment() -> Option<String> {
+    let hash = web_sys::window()?.location().hash().ok()?;
+    let fragment = hash.strip_prefix('#').unwrap_or(&hash);
+    (!fragment.is_empty()).then(|| fragment.to_string())
+}
+
+// Native builds have no URL to read the scramble from
+#[cfg(not(target_arch = "wasm32"))]
+fn read_fragment() -> Option<String> {
+    None
+}
+
+// Replaces the fragment without adding to the browser's history, or reloading the page
+#[cfg(target_arch = "wasm32")]
+fn write_fragment(fragment: &str) {
+    let Some(history) = web_sys::window().and_then(|window| window.history().ok()) else {
+        return;
+    };
+    let url = format!("#{fragment}");
+    let state = wasm_bindgen::JsValue::NULL;
+    if let Err(err) = history.replace_state_with_url(&state, "", Some(&url)) {
+        warn!("failed to share the scramble in the URL: {err:?}");
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn write_fragment(_fragment: &str) {}
+
+// Starts the game right away with the scramble of the link the app was opened with
+fn open_shared_link(
+    mut commands: Commands,
+    mut puzzle: ResMut<Puzzle>,
+    mut cube_size: ResMut<CubeSizeSetting>,
+    mut game_state: ResMut<NextState<GameState>>,
+    mut menu_state: ResMut<NextState<MenuState>>,
+) {
+    let Some(fragment) = read_fragment() else {
+        return;
+    };
+    let shared = match SharedScramble::parse(&fragment) {
+        Ok(shared) => shared,
+        Err(err) => {
+            warn!("ignoring the scramble shared in the URL: {err}");
+            return;
+        }
+    };
+
+    *puzzle = Puzzle::Cube;
+    cube_size.0 = shared.size;
+    commands.insert_resource(shared);
+    game_state.set(GameState::Playing);
+    menu_state.set(MenuState::Disabled);
+}
+
+fn scramble_shared(
+    mut commands: Commands,
+    shared: Res<SharedScramble>,
+    mut current_cube: PlayedMut<CurrentCube>,
+    mut move_queue: PlayedMut<MoveQueue>,
+    mut session: PlayedMut<SolveSession>,
+) {
+    session.queue_scramble(&mut current_cube, &mut move_queue, shared.moves.clone());
+    commands.remove_resource::<SharedScramble>();
+}
+
+// Keeps the last scramble in the URL once it's made, for the link to be shared
+fn share_scramble(
+    current_cube: Played<CurrentCube>,
+    move_queue: Played<MoveQueue>,
+    mut shared: Local<Option<SharedScramble>>,
+) {
+    if !move_queue.is_empty() || move_queue.in_flight().is_some() {
+        return;
+    }
+    let Some(moves) = current_cube.scramble() else {
+        return;
+    };
+
+    let scramble = SharedScramble {
+        size: current_cube.cube_size(),
+        moves: moves.to_vec(),
+    };
+    if shared.as_ref() != Some(&scramble) {
+        write_fragment(&scramble.fragment());
+        *shared = Some(scramble);
+    }
+}
@@ -0,0 +1,306 @@
+use crate::{
+    i18n::Translations,
+    settings::Settings,
+    simulator::{CubieAssets, CurrentCube, MoveQueue, Played, PlayedCube},
+    spectate::Spectator,
+    theme::Theme,
+    GameState, Puzzle,
+};
+use bevy::{
+    ecs::system::SystemParam,
+    input::{
+        keyboard::KeyboardInput,
+        mouse::{MouseButtonInput, MouseWheel},
+        touch::TouchInput,
+    },
+    prelude::*,
+};
+use cubesim::{parse_scramble, prelude::*};
+
+/// The solutions played while showing off, by name. Each one is scrambled by its inverse
+/// first, so they all start and end on a solved cube.
+const SHOWCASE_SOLUTIONS: [(&str, &str); 8] = [
+    ("Sune", "R U R' U R U2 R'"),
+    ("T-perm", "R U R' U' R' F R2 U' R' U' R U R' F'"),
+    ("Y-perm", "F R U' R' U' R U R' F' R U R' U' R' F R F'"),
+    ("Ua-perm", "R U' R U R U R U' R' U' R2"),
+    ("Jb-perm", "R U R' F' R U R' U' R' F R2 U' R'"),
+    ("Checkerboard", "U2 D2 F2 B2 L2 R2"),
+    (
+        "Superflip",
+        "U R2 F B R B2 R U2 L B2 R U' D' R2 F R' L B2 U2 F2",
+    ),
+    ("Cube in a cube", "F L F U' R U F2 L2 U' L' B D' B' L2 U"),
+];
+// the seconds the cube rests between a scramble and its solution
+const SHOWCASE_PAUSE: f32 = 1.5;
+// the radians a second the camera orbits the cube by
+const ORBIT_SPEED: f32 = 0.3;
+
+pub struct ShowcasePlugin;
+
+impl Plugin for ShowcasePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Showcase>()
+            .add_systems(
+                OnEnter(GameState::Playing),
+                showcase_ui.run_if(resource_equals(Puzzle::Cube)),
+            )
+            .add_systems(
+                Update,
+                (watch_idle, toggle_showcase, play_showcase)
+                    .chain()
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(resource_equals(Puzzle::Cube))
+                    // the spectators' cube follows the one broadcast
+                    .run_if(not(resource_exists::<Spectator>())),
+            )
+            .add_systems(OnExit(GameState::Playing), clean_up);
+    }
+}
+
+/// Shows off once the player is idle long enough: a cube of its own replaces the played one,
+/// scrambled and solved over and over while the camera orbits it, until the player is back.
+#[derive(Resource, Default, Debug)]
+struct Showcase {
+    // the seconds since the player last did anything
+    idle: f32,
+    // whether the player is idle long enough to show off
+    shown: bool,
+    // the cube shown off in place of the played one
+    cube: Option<Entity>,
+    // the solution played next, by its index
+    next: usize,
+    // whether the solution played next is scrambled already
+    scrambled: bool,
+    // the seconds the cube rested since its last moves
+    pause: f32,
+}
+
+/// Keeps the camera's place before orbiting the cube, to put it back once the player is back.
+#[derive(Component)]
+struct ShowcaseOrbit {
+    home: Transform,
+}
+
+// The name of the solution shown, with how to get back to the game
+#[derive(Component)]
+struct ShowcaseText;
+
+fn showcase_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    theme: Res<Theme>,
+    translations: Translations,
+) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+
+    commands.spawn((
+        TextBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                top: Val::Percent(12.0),
+                width: Val::Percent(100.0),
+                ..default()
+            },
+            visibility: Visibility::Hidden,
+            ..TextBundle::from_sections([
+                TextSection::new(
+                    String::new(),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 40.0,
+                        color: theme.text(),
+                    },
+                ),
+                TextSection::new(
+                    format!("\n{}", translations.get("showcase-caption")),
+                    TextStyle {
+                        font,
+                        font_size: 20.0,
+                        color: theme.text(),
+                    },
+                ),
+            ])
+            .with_text_alignment(TextAlignment::Center)
+        },
+        ShowcaseText,
+    ));
+}
+
+// Anything the player does with the keyboard, the mouse or a touch screen
+#[derive(SystemParam)]
+struct PlayerInput<'w, 's> {
+    keys: EventReader<'w, 's, KeyboardInput>,
+    buttons: EventReader<'w, 's, MouseButtonInput>,
+    cursor: EventReader<'w, 's, CursorMoved>,
+    wheel: EventReader<'w, 's, MouseWheel>,
+    touches: EventReader<'w, 's, TouchInput>,
+}
+
+impl PlayerInput<'_, '_> {
+    // Whether the player did anything since last asked, every event being read so none is
+    // left over for the next time
+    fn any(&mut self) -> bool {
+        let events = self.keys.read().count()
+            + self.buttons.read().count()
+            + self.cursor.read().count()
+            + self.wheel.read().count()
+            + self.touches.read().count();
+        events > 0
+    }
+}
+
+// Counts the time the player is idle, moves queued on the played cube, like a solution being
+// played, keeping it busy
+fn watch_idle(
+    time: Res<Time>,
+    settings: Res<Settings>,
+    move_queue: Played<MoveQueue>,
+    mut input: PlayerInput,
+    mut showcase: ResMut<Showcase>,
+) {
+    let active = input.any() || !move_queue.is_empty() || move_queue.in_flight().is_some();
+    let Some(idle_after) = settings.showcase_idle.filter(|_| !active) else {
+        showcase.idle = 0.0;
+        showcase.shown = false;
+        return;
+    };
+
+    showcase.idle += time.delta_seconds();
+    if showcase.idle >= idle_after {
+        showcase.shown = true;
+    }
+}
+
+// The played cube and the cameras, swapped for the cube shown off and an orbit around it
+#[derive(SystemParam)]
+struct ShowcaseView<'w, 's> {
+    commands: Commands<'w, 's>,
+    q_played: Query<'w, 's, &'static mut Visibility, With<PlayedCube>>,
+    q_camera: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static mut Transform,
+            Option<&'static ShowcaseOrbit>,
+        ),
+        With<Camera3d>,
+    >,
+}
+
+impl ShowcaseView<'_, '_> {
+    fn enter(&mut self) {
+        for mut visibility in &mut self.q_played {
+            *visibility = Visibility::Hidden;
+        }
+        for (camera, transform, _) in &self.q_camera {
+            self.commands
+                .entity(camera)
+                .insert(ShowcaseOrbit { home: *transform });
+        }
+    }
+
+    fn leave(&mut self) {
+        for mut visibility in &mut self.q_played {
+            *visibility = Visibility::Inherited;
+        }
+        for (camera, mut transform, orbit) in &mut self.q_camera {
+            let Some(orbit) = orbit else {
+                continue;
+            };
+            *transform = orbit.home;
+            self.commands.entity(camera).remove::<ShowcaseOrbit>();
+        }
+    }
+}
+
+fn toggle_showcase(
+    mut showcase: ResMut<Showcase>,
+    current_cube: Played<CurrentCube>,
+    mut assets: CubieAssets,
+    mut view: ShowcaseView,
+) {
+    match (showcase.shown, showcase.cube) {
+        (true, None) => {
+            let cube = assets.spawn_cube(&mut view.commands, current_cube.cube_size());
+            view.enter();
+            showcase.cube = Some(cube);
+            showcase.scrambled = false;
+            showcase.pause = 0.0;
+        }
+        (false, Some(cube)) => {
+            view.commands.entity(cube).despawn_recursive();
+            view.leave();
+            showcase.cube = None;
+        }
+        _ => {}
+    }
+}
+
+// Orbits the camera around the cube shown off, scrambling it and solving it again in turn
+// once it rested from its last moves
+fn play_showcase(
+    time: Res<Time>,
+    mut showcase: ResMut<Showcase>,
+    mut q_queue: Query<&mut MoveQueue, Without<PlayedCube>>,
+    mut q_camera: Query<&mut Transform, (With<Camera3d>, With<ShowcaseOrbit>)>,
+    mut q_text: Query<(&mut Text, &mut Visibility), With<ShowcaseText>>,
+) {
+    for (_, mut visibility) in &mut q_text {
+        visibility.set_if_neq(match showcase.cube {
+            Some(_) => Visibility::Inherited,
+            None => Visibility::Hidden,
+        });
+    }
+    let Some(cube) = showcase.cube else {
+        return;
+    };
+
+    let orbit = Quat::from_rotation_y(ORBIT_SPEED * time.delta_seconds());
+    for mut transform in &mut q_camera {
+        transform.rotate_around(Vec3::ZERO, orbit);
+    }
+
+    let Ok(mut move_queue) = q_queue.get_mut(cube) else {
+        return;
+    };
+    if !move_queue.is_empty() || move_queue.in_flight().is_some() {
+        return;
+    }
+    showcase.pause += time.delta_seconds();
+    if showcase.pause < SHOWCASE_PAUSE {
+        return;
+    }
+
+    showcase.pause = 0.0;
+    let (name, solution) = SHOWCASE_SOLUTIONS[showcase.next];
+    let solution = parse_scramble(solution.to_string());
+    if showcase.scrambled {
+        move_queue.extend(solution);
+        showcase.next = (showcase.next + 1) % SHOWCASE_SOLUTIONS.len();
+    } else {
+        move_queue.extend(solution.iter().rev().map(Move::inverse));
+        for (mut text, _) in &mut q_text {
+            text.sections[0].value = name.to_string();
+        }
+    }
+    showcase.scrambled = !showcase.scrambled;
+}
+
+// The cube shown off is despawned with the other cubes once the game ends
+fn clean_up(
+    mut showcase: ResMut<Showcase>,
+    mut view: ShowcaseView,
+    q_text: Query<Entity, With<ShowcaseText>>,
+) {
+    view.leave();
+    *showcase = Showcase {
+        next: showcase.next,
+        ..default()
+    };
+    for entity in &q_text {
+        view.commands.entity(entity).despawn_recursive();
+    }
+}
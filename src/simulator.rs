@@ -1,535 +1,522 @@
-use crate::GameState;
+use crate::{
+    confirm::Confirmation,
+    i18n::{Localized, Translations},
+    layout::LayoutNode,
+    lighting::Look,
+    mesh::MeshQuality,
+    player::{LayerPrefix, WideDepth},
+    scramble::Scrambler,
+    session::SolveSession,
+    spectate::Spectator,
+    theme::{Theme, ThemeColor},
+    warmup::SolverTables,
+    CubeSizeSetting, GameState, Puzzle,
+};
 use bevy::{
+    ecs::system::SystemParam,
     input::mouse::{MouseScrollUnit, MouseWheel},
     prelude::*,
 };
-use cubesim::{prelude::*, random_scramble, solve, FaceletCube, GeoCube};
-use std::{
-    collections::VecDeque,
-    f32::consts::{FRAC_PI_2, PI},
-    ops::{Deref, DerefMut},
+use bevy_cubesim::{CubeEntityBuilder, CubesimPlugin, CubesimSet, StickerPaint, PIECE_SIZE};
+use cubesim::{
+    annotate_triggers, cycle_structure, prelude::*, BeginnerStep, FaceletCube, PieceType,
+};
+use std::ops::{Deref, DerefMut};
+
+pub(crate) use bevy_cubesim::{
+    CubeRoot, CurrentCube, FaceColors, MoveApplied, MoveQueue, Piece, PieceBody, PieceCoord,
+    Rotating, StickerFace, StickerPlace,
 };
 
 pub struct SimulatorPlugin;
 
 impl Plugin for SimulatorPlugin {
     fn build(&self, app: &mut App) {
-        app.insert_resource(CurrentCube::new(3))
-            .init_resource::<MoveQueue>()
+        app.add_plugins(CubesimPlugin)
+            .configure_sets(
+                Update,
+                CubesimSet::Animate.run_if(resource_equals(Puzzle::Cube)),
+            )
+            .init_resource::<ExplodedView>()
+            .init_resource::<PieceView>()
+            .init_resource::<Tutorial>()
+            .add_systems(Startup, spawn_played_cube)
             .add_systems(
                 Update,
-                (rotate_control, rotate_piece, button_system, mouse_scroll),
+                (
+                    show_moves.after(CubesimSet::Control),
+                    dress_pieces.run_if(resource_equals(Puzzle::Cube)),
+                    (toggle_exploded_view, explode_pieces)
+                        .chain()
+                        .after(CubesimSet::Animate)
+                        .run_if(resource_equals(Puzzle::Cube)),
+                    (cycle_piece_view, show_piece_types)
+                        .chain()
+                        .run_if(resource_equals(Puzzle::Cube)),
+                    (
+                        tutorial_progress.run_if(played_changed::<CurrentCube>),
+                        show_tutorial,
+                    )
+                        .chain()
+                        .after(CubesimSet::Control)
+                        .run_if(resource_equals(Puzzle::Cube)),
+                    button_system.run_if(resource_equals(Puzzle::Cube)),
+                    show_cycles
+                        .after(CubesimSet::Control)
+                        .run_if(resource_equals(Puzzle::Cube))
+                        .run_if(played_changed::<CurrentCube>),
+                    mouse_scroll,
+                    layers_status.run_if(
+                        resource_changed::<WideDepth>().or_else(resource_changed::<LayerPrefix>()),
+                    ),
+                ),
+            )
+            .add_systems(
+                OnEnter(GameState::Playing),
+                (cube_setup.run_if(resource_equals(Puzzle::Cube)), game_ui),
             )
-            .add_systems(OnEnter(GameState::Playing), (cube_setup, game_ui))
             .add_systems(OnExit(GameState::Playing), clean_up);
     }
 }
 
-const UP_COLOR: Color = Color::WHITE;
-const RIGHT_COLOR: Color = Color::RED;
-const FRONT_COLOR: Color = Color::GREEN;
-const DOWN_COLOR: Color = Color::YELLOW;
-const LEFT_COLOR: Color = Color::ORANGE;
-const BACK_COLOR: Color = Color::BLUE;
-const PIECE_SIZE: f32 = 1.0;
+const CENTER_TINT: Color = Color::rgb(0.45, 0.1, 0.55);
+const EDGE_TINT: Color = Color::rgb(0.1, 0.45, 0.5);
+const CORNER_TINT: Color = Color::rgb(0.6, 0.45, 0.1);
+
+// pieces move out by this fraction of their distance to the center in the exploded view
+const EXPLODE_DISTANCE: f32 = 0.6;
+const EXPLODE_SPEED: f32 = 2.0;
 
-const ROTATE_SPEED: f32 = 2.0;
+/// The cube the player turns, whose ``CubeRoot`` is kept from one game to the next, so its
+/// ``CurrentCube``, ``MoveQueue`` and ``SolveSession`` are always there to read, like
+/// resources. Any other cube is only shown.
+#[derive(Component)]
+pub(crate) struct PlayedCube;
 
-#[derive(Resource)]
-pub struct CurrentCube {
-    geo_cube: GeoCube,
-    cube_size: usize,
-    moves: Vec<Move>,
+// The root of the played cube, without any pieces until a game starts
+pub(crate) fn played_cube() -> impl Bundle {
+    (
+        SpatialBundle::default(),
+        CubeRoot,
+        PlayedCube,
+        CurrentCube::default(),
+        MoveQueue::default(),
+        SolveSession::default(),
+    )
 }
 
-impl CurrentCube {
-    pub fn new(cube_size: usize) -> Self {
-        let geo_cube = GeoCube::new(cube_size as CubeSize);
-        Self {
-            geo_cube,
-            cube_size,
-            moves: vec![],
-        }
+fn spawn_played_cube(mut commands: Commands) {
+    commands.spawn(played_cube());
+}
+
+/// A component of the played cube, read like ``Res``.
+#[derive(SystemParam)]
+pub(crate) struct Played<'w, 's, T: Component> {
+    query: Query<'w, 's, Ref<'static, T>, With<PlayedCube>>,
+}
+
+impl<T: Component> Played<'_, '_, T> {
+    pub(crate) fn is_changed(&self) -> bool {
+        self.query.single().is_changed()
+    }
+}
+
+impl<T: Component> Deref for Played<'_, '_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.query.single().into_inner()
     }
 }
 
-impl Deref for CurrentCube {
-    type Target = GeoCube;
+/// A component of the played cube, changed like ``ResMut``.
+#[derive(SystemParam)]
+pub(crate) struct PlayedMut<'w, 's, T: Component> {
+    query: Query<'w, 's, &'static mut T, With<PlayedCube>>,
+}
+
+impl<T: Component> Deref for PlayedMut<'_, '_, T> {
+    type Target = T;
 
     fn deref(&self) -> &Self::Target {
-        &self.geo_cube
+        self.query.single()
     }
 }
 
-impl DerefMut for CurrentCube {
+impl<T: Component> DerefMut for PlayedMut<'_, '_, T> {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.geo_cube
+        self.query.single_mut().into_inner()
     }
 }
 
-#[derive(Component)]
-struct Piece;
+/// Runs a system once a component of the played cube changed, like ``resource_changed``.
+pub(crate) fn played_changed<T: Component>(
+    q_changed: Query<(), (With<PlayedCube>, Changed<T>)>,
+) -> bool {
+    !q_changed.is_empty()
+}
 
-fn cube_setup(
-    mut commands: Commands,
-    mut current_cube: ResMut<CurrentCube>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
-) {
-    current_cube.geo_cube = GeoCube::new(current_cube.cube_size as CubeSize);
-    current_cube.moves = vec![];
-    let border = (current_cube.cube_size as f32 * PIECE_SIZE) / 2.0 - 0.5 * PIECE_SIZE;
-    info!("state {:?}", current_cube.state());
-    for (i, faces) in current_cube
-        .state()
-        .chunks(current_cube.cube_size * current_cube.cube_size)
-        .collect::<Vec<&[Face]>>()
-        .iter()
-        .enumerate()
-    {
-        let saw_face = match i {
-            0 => Face::U,
-            1 => Face::R,
-            2 => Face::F,
-            3 => Face::D,
-            4 => Face::L,
-            5 => Face::B,
-            _ => panic!("invalid index"),
-        };
+/// The moves applied to the played cube, leaving out those of any other cube.
+#[derive(SystemParam)]
+pub(crate) struct PlayedMoves<'w, 's> {
+    move_applied: EventReader<'w, 's, MoveApplied>,
+    q_played: Query<'w, 's, Entity, With<PlayedCube>>,
+}
 
-        for (j, faces) in faces.chunks(current_cube.cube_size).enumerate() {
-            for (k, face) in faces.iter().enumerate() {
-                let color = match face {
-                    Face::U => UP_COLOR,
-                    Face::L => LEFT_COLOR,
-                    Face::F => FRONT_COLOR,
-                    Face::R => RIGHT_COLOR,
-                    Face::B => BACK_COLOR,
-                    Face::D => DOWN_COLOR,
-                    Face::X => {
-                        unreachable!()
-                    }
-                };
-                let mut transform =
-                    Transform::from_xyz(k as f32 - border, border, j as f32 - border);
-                match saw_face {
-                    Face::U => {}
-                    Face::L => {
-                        transform.rotate_around(Vec3::ZERO, Quat::from_rotation_z(FRAC_PI_2));
-                        transform.rotate_around(Vec3::ZERO, Quat::from_rotation_x(FRAC_PI_2));
-                    }
-                    Face::F => {
-                        transform.rotate_around(Vec3::ZERO, Quat::from_rotation_x(FRAC_PI_2));
-                    }
-                    Face::R => {
-                        transform.rotate_around(Vec3::ZERO, Quat::from_rotation_z(-FRAC_PI_2));
-                        transform.rotate_around(Vec3::ZERO, Quat::from_rotation_x(FRAC_PI_2));
-                    }
-                    Face::B => {
-                        transform.rotate_around(Vec3::ZERO, Quat::from_rotation_x(-FRAC_PI_2));
-                        transform.rotate_around(Vec3::ZERO, Quat::from_rotation_z(PI));
-                    }
-                    Face::D => {
-                        transform.rotate_around(Vec3::ZERO, Quat::from_rotation_z(PI));
-                        transform.rotate_around(Vec3::ZERO, Quat::from_rotation_y(PI));
-                    }
-                    Face::X => {
-                        unreachable!()
-                    }
-                }
+impl PlayedMoves<'_, '_> {
+    pub(crate) fn read(&mut self) -> impl Iterator<Item = &Move> {
+        let played = self.q_played.get_single().ok();
+        self.move_applied
+            .read()
+            .filter(move |applied| Some(applied.cube) == played)
+            .map(|applied| &applied.move_)
+    }
 
-                commands
-                    .spawn(PbrBundle {
-                        mesh: meshes.add(Mesh::from(shape::Cube { size: PIECE_SIZE })),
-                        material: materials.add(StandardMaterial {
-                            base_color: Color::BLACK,
-                            unlit: true,
-                            ..Default::default()
-                        }),
-                        transform,
-                        ..Default::default()
-                    })
-                    .insert(Piece)
-                    .with_children(|parent| {
-                        parent.spawn(PbrBundle {
-                            mesh: meshes.add(Mesh::from(shape::Plane::from_size(PIECE_SIZE * 0.9))),
-                            material: materials.add(StandardMaterial {
-                                base_color: color,
-                                unlit: true,
-                                ..Default::default()
-                            }),
-                            transform: Transform::from_xyz(0.0, 0.501, 0.0),
-                            ..Default::default()
-                        });
-                    });
-            }
-        }
+    pub(crate) fn clear(&mut self) {
+        self.move_applied.clear();
     }
 }
 
-#[derive(Resource, Default)]
-pub struct MoveQueue {
-    moves: VecDeque<Move>,
+/// The beginner method tutorial, following the first step not yet done on the cube.
+#[derive(Resource, Clone, Copy, Eq, PartialEq, Debug, Default)]
+pub(crate) enum Tutorial {
+    #[default]
+    Off,
+    Step(BeginnerStep),
+    Done,
 }
 
-impl Deref for MoveQueue {
-    type Target = VecDeque<Move>;
-
-    fn deref(&self) -> &Self::Target {
-        &self.moves
+impl Tutorial {
+    // The id of the step's instructions, in the translations
+    fn instructions(self) -> &'static str {
+        match self {
+            Tutorial::Off => "tutorial-off",
+            Tutorial::Step(BeginnerStep::Cross) => "tutorial-cross",
+            Tutorial::Step(BeginnerStep::FirstLayer) => "tutorial-first-layer",
+            Tutorial::Step(BeginnerStep::SecondLayer) => "tutorial-second-layer",
+            Tutorial::Step(BeginnerStep::LastLayerCross) => "tutorial-last-layer-cross",
+            Tutorial::Step(BeginnerStep::LastLayerEdges) => "tutorial-last-layer-edges",
+            Tutorial::Step(BeginnerStep::LastLayerCorners) => "tutorial-last-layer-corners",
+            Tutorial::Done => "tutorial-done",
+        }
+    }
+    /// The algorithm suggested by the current step, which can be followed with the guide.
+    pub(crate) fn alg(self) -> Option<&'static str> {
+        match self {
+            Tutorial::Step(BeginnerStep::FirstLayer) => Some("R U R' U'"),
+            Tutorial::Step(BeginnerStep::SecondLayer) => Some("U R U' R' U' F' U F"),
+            Tutorial::Step(BeginnerStep::LastLayerCross) => Some("F R U R' U' F'"),
+            Tutorial::Step(BeginnerStep::LastLayerEdges) => Some("R U R' U R U2 R' U"),
+            Tutorial::Step(BeginnerStep::LastLayerCorners) => Some("U R U' L' U R' U' L"),
+            _ => None,
+        }
     }
 }
 
-impl DerefMut for MoveQueue {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.moves
+/// How the pieces are shown to tell centers, edges and corners apart.
+#[derive(Resource, Clone, Copy, Eq, PartialEq, Debug, Default)]
+pub(crate) enum PieceView {
+    #[default]
+    Normal,
+    /// Every piece is tinted by its type.
+    Tinted,
+    /// Only the pieces of one type are shown.
+    Only(PieceType),
+}
+
+impl PieceView {
+    fn next(self) -> Self {
+        match self {
+            PieceView::Normal => PieceView::Tinted,
+            PieceView::Tinted => PieceView::Only(PieceType::Center),
+            PieceView::Only(PieceType::Center) => PieceView::Only(PieceType::Edge),
+            PieceView::Only(PieceType::Edge) => PieceView::Only(PieceType::Corner),
+            PieceView::Only(PieceType::Corner) => PieceView::Normal,
+        }
     }
 }
 
-#[derive(Component)]
-struct Rotating {
-    axis: Vec3,
-    angle: f32,
+/// How far the pieces are moved apart, animated towards 1 while the exploded view is enabled.
+#[derive(Resource, Default)]
+pub(crate) struct ExplodedView {
+    pub enabled: bool,
+    amount: f32,
 }
 
-type NotRotatingPiece = (Without<Rotating>, With<Piece>);
+/// The look of the pieces, chosen from the display settings.
+#[derive(SystemParam)]
+pub(crate) struct CubieAssets<'w> {
+    mesh_quality: Res<'w, MeshQuality>,
+    look: Res<'w, Look>,
+    face_colors: Res<'w, FaceColors>,
+    meshes: ResMut<'w, Assets<Mesh>>,
+    materials: ResMut<'w, Assets<StandardMaterial>>,
+}
 
-fn rotate_control(
-    mut commands: Commands,
-    mut move_queue: ResMut<MoveQueue>,
-    mut current_cube: ResMut<CurrentCube>,
-    q_not_rotating: Query<(Entity, &GlobalTransform), NotRotatingPiece>,
-    q_rotating: Query<&Rotating>,
-    mut q_text: Query<&mut Text, With<MovesText>>,
-) {
-    if !q_rotating.is_empty() {
-        return;
+impl CubieAssets<'_> {
+    // The meshes of a piece's body and of its stickers, shared by every piece
+    fn meshes(&mut self) -> (Handle<Mesh>, Handle<Mesh>) {
+        let mesh_quality = *self.mesh_quality;
+        let body = self.meshes.add(mesh_quality.cubie_mesh(PIECE_SIZE));
+        let sticker = self.meshes.add(Mesh::from(shape::Plane::from_size(
+            mesh_quality.sticker_size(PIECE_SIZE),
+        )));
+        (body, sticker)
     }
-    if let Some(move_) = move_queue.pop_front() {
-        current_cube.geo_cube = current_cube.apply_move(move_);
-        current_cube.moves.push(move_);
-        let mut text = q_text.single_mut();
-
-        text.sections[0].value = current_cube
-            .moves
-            .iter()
-            .map(|m| m.to_string())
-            .collect::<Vec<String>>()
-            .join(" ");
-
-        debug!("move {}", move_);
-        let border = (current_cube.cube_size as f32 * PIECE_SIZE) / 2.0 - 0.5 * PIECE_SIZE;
-        match move_ {
-            Move::U(v) => {
-                let mut count = 0;
-                for (entity, transform) in q_not_rotating.iter() {
-                    if transform.translation().y >= border - 0.01 {
-                        commands.entity(entity).insert(Rotating {
-                            axis: Vec3::Y,
-                            angle: match v {
-                                MoveVariant::Standard => -FRAC_PI_2,
-                                MoveVariant::Inverse => FRAC_PI_2,
-                                MoveVariant::Double => PI,
-                            },
-                        });
 
-                        count += 1;
-                    }
-                }
-
-                trace!("U apply {count}");
-            }
-            Move::L(v) => {
-                let mut count = 0;
-                for (entity, transform) in q_not_rotating.iter() {
-                    if transform.translation().x <= -border + 0.01 {
-                        commands.entity(entity).insert(Rotating {
-                            axis: Vec3::X,
-                            angle: match v {
-                                MoveVariant::Standard => FRAC_PI_2,
-                                MoveVariant::Inverse => -FRAC_PI_2,
-                                MoveVariant::Double => PI,
-                            },
-                        });
-                        count += 1;
-                    }
-                }
+    // Each piece has materials of its own, as their colors change one by one
+    fn body_material(&mut self) -> Handle<StandardMaterial> {
+        self.materials.add(self.look.body_material(Color::BLACK))
+    }
 
-                trace!("L apply {count}");
-            }
-            Move::F(v) => {
-                let mut count = 0;
-                for (entity, transform) in q_not_rotating.iter() {
-                    if transform.translation().z >= border - 0.01 {
-                        commands.entity(entity).insert(Rotating {
-                            axis: Vec3::Z,
-                            angle: match v {
-                                MoveVariant::Standard => -FRAC_PI_2,
-                                MoveVariant::Inverse => FRAC_PI_2,
-                                MoveVariant::Double => PI,
-                            },
-                        });
-                        count += 1;
-                    }
-                }
+    fn sticker_material(&mut self, face: Face) -> Handle<StandardMaterial> {
+        let color = self.face_colors.color(face);
+        self.materials.add(self.look.sticker_material(color))
+    }
 
-                debug!("F apply {count}");
-            }
-            Move::R(v) => {
-                let mut count = 0;
-                for (entity, transform) in q_not_rotating.iter() {
-                    if transform.translation().x >= border - 0.01 {
-                        commands.entity(entity).insert(Rotating {
-                            axis: Vec3::X,
-                            angle: match v {
-                                MoveVariant::Standard => -FRAC_PI_2,
-                                MoveVariant::Inverse => FRAC_PI_2,
-                                MoveVariant::Double => PI,
-                            },
-                        });
-                        count += 1;
-                    }
-                }
+    // A solved cube with this look, its stickers given the colors of their faces
+    fn cube_builder(&mut self, size: usize) -> CubeEntityBuilder {
+        let (body_mesh, sticker_mesh) = self.meshes();
+        let look = *self.look;
+        CubeEntityBuilder::new(size)
+            .with_colors(self.face_colors.clone())
+            .with_meshes(body_mesh, sticker_mesh)
+            .with_materials(
+                look.body_material(Color::BLACK),
+                look.sticker_material(Color::WHITE),
+            )
+    }
 
-                trace!("R apply {count}");
-            }
-            Move::B(v) => {
-                let mut count = 0;
-                for (entity, transform) in q_not_rotating.iter() {
-                    if transform.translation().z <= -border + 0.01 {
-                        commands.entity(entity).insert(Rotating {
-                            axis: Vec3::Z,
-                            angle: match v {
-                                MoveVariant::Standard => FRAC_PI_2,
-                                MoveVariant::Inverse => -FRAC_PI_2,
-                                MoveVariant::Double => PI,
-                            },
-                        });
-                        count += 1;
-                    }
-                }
-                trace!("B apply {count}");
-            }
-            Move::D(v) => {
-                let mut count = 0;
-                for (entity, transform) in q_not_rotating.iter() {
-                    if transform.translation().y <= -border + 0.01 {
-                        commands.entity(entity).insert(Rotating {
-                            axis: Vec3::Y,
-                            angle: match v {
-                                MoveVariant::Standard => FRAC_PI_2,
-                                MoveVariant::Inverse => -FRAC_PI_2,
-                                MoveVariant::Double => PI,
-                            },
-                        });
-                        count += 1;
-                    }
-                }
+    /// Spawns a solved cube looking like the played one, which only the moves pushed onto its
+    /// own queue turn.
+    pub(crate) fn spawn_cube(&mut self, commands: &mut Commands, size: usize) -> Entity {
+        self.cube_builder(size)
+            .spawn(commands, &mut self.meshes, &mut self.materials)
+    }
+}
 
-                debug!("D apply {count}");
-            }
-            Move::Uw(slice, v) => {
-                let mut count = 0;
-                for (entity, transform) in q_not_rotating.iter() {
-                    if (((border - slice as f32 / 2.0) - 0.001)..=(border + 0.001))
-                        .contains(&transform.translation().y)
-                    {
-                        commands.entity(entity).insert(Rotating {
-                            axis: Vec3::Y,
-                            angle: match v {
-                                MoveVariant::Standard => -FRAC_PI_2,
-                                MoveVariant::Inverse => FRAC_PI_2,
-                                MoveVariant::Double => PI,
-                            },
-                        });
+fn cube_setup(
+    mut commands: Commands,
+    cube_size: Res<CubeSizeSetting>,
+    q_played: Query<Entity, With<PlayedCube>>,
+    mut wide_depth: ResMut<WideDepth>,
+    mut assets: CubieAssets,
+) {
+    let max_depth = WideDepth::max_for(cube_size.0);
+    if wide_depth.0 > max_depth {
+        wide_depth.0 = max_depth;
+    }
+    info!("new {0}x{0}x{0} cube", cube_size.0);
 
-                        count += 1;
-                    }
-                }
+    // the played cube keeps its root, getting a solved cube, new pieces and a new session
+    let mut played = commands.entity(q_played.single());
+    assets
+        .cube_builder(cube_size.0)
+        .insert(&mut played, &mut assets.meshes, &mut assets.materials);
+    played.insert(SolveSession::default());
+}
 
-                trace!("Uw apply {count}");
-            }
-            Move::Lw(slice, v) => {
-                let mut count = 0;
-                for (entity, transform) in q_not_rotating.iter() {
-                    if ((-border - 0.001)..=(border - (slice as f32 / 2.0) + 0.001))
-                        .contains(&transform.translation().x)
-                    {
-                        commands.entity(entity).insert(Rotating {
-                            axis: Vec3::X,
-                            angle: match v {
-                                MoveVariant::Standard => FRAC_PI_2,
-                                MoveVariant::Inverse => -FRAC_PI_2,
-                                MoveVariant::Double => PI,
-                            },
-                        });
-                        count += 1;
-                    }
-                }
+type UndressedBody = (With<PieceBody>, Without<Handle<Mesh>>);
 
-                trace!("Lw apply {count}");
-            }
-            Move::Fw(slice, v) => {
-                let mut count = 0;
-                for (entity, transform) in q_not_rotating.iter() {
-                    if (((border - slice as f32 / 2.0) - 0.001)..=(border + 0.001))
-                        .contains(&transform.translation().z)
-                    {
-                        commands.entity(entity).insert(Rotating {
-                            axis: Vec3::Z,
-                            angle: match v {
-                                MoveVariant::Standard => -FRAC_PI_2,
-                                MoveVariant::Inverse => FRAC_PI_2,
-                                MoveVariant::Double => PI,
-                            },
-                        });
-                        count += 1;
-                    }
-                }
+type UndressedPiece = (Or<(With<CubeRoot>, With<Piece>)>, Without<GlobalTransform>);
 
-                trace!("Fw apply {count}");
-            }
-            Move::Rw(slice, v) => {
-                let mut count = 0;
-                for (entity, transform) in q_not_rotating.iter() {
-                    if (((border - slice as f32 / 2.0) - 0.001)..=(border + 0.001))
-                        .contains(&transform.translation().x)
-                    {
-                        commands.entity(entity).insert(Rotating {
-                            axis: Vec3::X,
-                            angle: match v {
-                                MoveVariant::Standard => -FRAC_PI_2,
-                                MoveVariant::Inverse => FRAC_PI_2,
-                                MoveVariant::Double => PI,
-                            },
-                        });
+// Gives the meshes and materials to the pieces loaded from a scene, which only hold the
+// hierarchy and places of the cube and its pieces
+fn dress_pieces(
+    mut commands: Commands,
+    mut assets: CubieAssets,
+    q_piece: Query<Entity, UndressedPiece>,
+    q_body: Query<(Entity, &Transform), UndressedBody>,
+    q_sticker: Query<(Entity, &Transform, &StickerFace), Without<Handle<Mesh>>>,
+) {
+    for entity in &q_piece {
+        commands
+            .entity(entity)
+            .insert((GlobalTransform::default(), VisibilityBundle::default()));
+    }
+    if q_body.is_empty() && q_sticker.is_empty() {
+        return;
+    }
 
-                        count += 1;
-                    }
-                }
+    let (body_mesh, sticker_mesh) = assets.meshes();
+    for (entity, transform) in &q_body {
+        commands.entity(entity).insert(PbrBundle {
+            mesh: body_mesh.clone(),
+            material: assets.body_material(),
+            transform: *transform,
+            ..default()
+        });
+    }
+    for (entity, transform, sticker) in &q_sticker {
+        commands.entity(entity).insert((
+            PbrBundle {
+                mesh: sticker_mesh.clone(),
+                material: assets.sticker_material(sticker.face),
+                transform: *transform,
+                ..default()
+            },
+            // found by `reconcile_pieces` once added
+            StickerPlace(sticker.index as usize),
+        ));
+    }
+}
 
-                trace!("Rw apply {count}");
-            }
-            Move::Bw(slice, v) => {
-                let mut count = 0;
-                for (entity, transform) in q_not_rotating.iter() {
-                    if ((-border - 0.001)..=((border - slice as f32 / 2.0) + 0.001))
-                        .contains(&transform.translation().z)
-                    {
-                        commands.entity(entity).insert(Rotating {
-                            axis: Vec3::Z,
-                            angle: match v {
-                                MoveVariant::Standard => FRAC_PI_2,
-                                MoveVariant::Inverse => -FRAC_PI_2,
-                                MoveVariant::Double => PI,
-                            },
-                        });
-                        count += 1;
-                    }
-                }
+fn toggle_exploded_view(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut exploded_view: ResMut<ExplodedView>,
+) {
+    if keyboard_input.just_pressed(KeyCode::E) {
+        exploded_view.enabled = !exploded_view.enabled;
+    }
+}
 
-                trace!("Bw apply {count}");
-            }
-            Move::Dw(slice, v) => {
-                let mut count = 0;
-                for (entity, transform) in q_not_rotating.iter() {
-                    if ((-border - 0.001)..=((border - slice as f32 / 2.0) + 0.001))
-                        .contains(&transform.translation().y)
-                    {
-                        commands.entity(entity).insert(Rotating {
-                            axis: Vec3::Y,
-                            angle: match v {
-                                MoveVariant::Standard => FRAC_PI_2,
-                                MoveVariant::Inverse => -FRAC_PI_2,
-                                MoveVariant::Double => PI,
-                            },
-                        });
-                        count += 1;
-                    }
-                }
+fn explode_pieces(
+    time: Res<Time>,
+    mut exploded_view: ResMut<ExplodedView>,
+    q_piece: Query<(&Transform, &Children), With<Piece>>,
+    mut q_body: Query<&mut Transform, (With<PieceBody>, Without<Piece>)>,
+) {
+    let target = if exploded_view.enabled { 1.0 } else { 0.0 };
+    let step = EXPLODE_SPEED * time.delta_seconds();
+    let amount = exploded_view.amount + (target - exploded_view.amount).clamp(-step, step);
+    // pieces being turned keep following their layer, so the offset is updated every frame
+    exploded_view.bypass_change_detection().amount = amount;
 
-                trace!("Dw apply {count}");
-            }
-            Move::X(v) => {
-                for (entity, _transform) in q_not_rotating.iter() {
-                    commands.entity(entity).insert(Rotating {
-                        axis: Vec3::X,
-                        angle: match v {
-                            MoveVariant::Standard => -FRAC_PI_2,
-                            MoveVariant::Inverse => FRAC_PI_2,
-                            MoveVariant::Double => PI,
-                        },
-                    });
-                }
-            }
-            Move::Y(v) => {
-                for (entity, _transform) in q_not_rotating.iter() {
-                    commands.entity(entity).insert(Rotating {
-                        axis: Vec3::Y,
-                        angle: match v {
-                            MoveVariant::Standard => -FRAC_PI_2,
-                            MoveVariant::Inverse => FRAC_PI_2,
-                            MoveVariant::Double => PI,
-                        },
-                    });
-                }
-            }
-            Move::Z(v) => {
-                for (entity, _transform) in q_not_rotating.iter() {
-                    commands.entity(entity).insert(Rotating {
-                        axis: Vec3::Z,
-                        angle: match v {
-                            MoveVariant::Standard => -FRAC_PI_2,
-                            MoveVariant::Inverse => FRAC_PI_2,
-                            MoveVariant::Double => PI,
-                        },
-                    });
-                }
+    for (transform, children) in &q_piece {
+        // the offset points away from the center of the cube, in the piece's own frame
+        let offset =
+            transform.rotation.inverse() * transform.translation * amount * EXPLODE_DISTANCE;
+        for child in children {
+            if let Ok(mut body) = q_body.get_mut(*child) {
+                body.translation = offset;
             }
         }
     }
 }
 
-fn rotate_piece(
-    mut commands: Commands,
-    time: Res<Time>,
-    mut q_rotating: Query<(Entity, &mut Transform, &mut Rotating), With<Piece>>,
+fn cycle_piece_view(keyboard_input: Res<Input<KeyCode>>, mut piece_view: ResMut<PieceView>) {
+    if keyboard_input.just_pressed(KeyCode::T) {
+        *piece_view = piece_view.next();
+    }
+}
+
+fn show_piece_types(
+    piece_view: Res<PieceView>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut q_body: Query<(Ref<PieceBody>, &Handle<StandardMaterial>, &mut Visibility)>,
 ) {
-    for (entity, mut transform, mut rotating) in q_rotating.iter_mut() {
-        let mut rotate_angle = if rotating.angle > 0.0 {
-            ROTATE_SPEED * PI * time.delta_seconds()
+    for (body, material, mut visibility) in &mut q_body {
+        // newly spawned pieces follow the current view too
+        if !piece_view.is_changed() && !body.is_added() {
+            continue;
+        }
+
+        let tint = match body.0 {
+            PieceType::Center => CENTER_TINT,
+            PieceType::Edge => EDGE_TINT,
+            PieceType::Corner => CORNER_TINT,
+        };
+        let (color, visible) = match *piece_view {
+            PieceView::Normal => (Color::BLACK, true),
+            PieceView::Tinted => (tint, true),
+            PieceView::Only(only) => (tint, only == body.0),
+        };
+
+        if let Some(material) = materials.get_mut(material) {
+            material.base_color = color;
+        }
+        *visibility = if visible {
+            Visibility::Inherited
         } else {
-            -ROTATE_SPEED * PI * time.delta_seconds()
+            Visibility::Hidden
         };
+    }
+}
 
-        rotating.angle -= rotate_angle;
+// Follows the tutorial to the first step not done, checked after every move
+fn tutorial_progress(current_cube: Played<CurrentCube>, mut tutorial: ResMut<Tutorial>) {
+    if *tutorial == Tutorial::Off {
+        return;
+    }
+    if current_cube.cube_size() != 3 {
+        *tutorial = Tutorial::Off;
+        return;
+    }
+
+    let progress = match BeginnerStep::current(&**current_cube) {
+        Some(step) => Tutorial::Step(step),
+        None => Tutorial::Done,
+    };
+    tutorial.set_if_neq(progress);
+}
 
-        if (rotate_angle > 0.0 && rotating.angle < 0.0)
-            || (rotate_angle < 0.0 && rotating.angle > 0.0)
-        {
-            rotate_angle += rotating.angle;
-            commands.entity(entity).remove::<Rotating>();
+// Greys out the stickers not part of the current step and shows its instructions
+fn show_tutorial(
+    tutorial: Res<Tutorial>,
+    mut paint: StickerPaint,
+    q_sticker: Query<(Ref<StickerFace>, &Handle<StandardMaterial>)>,
+    mut q_text: Query<&mut Text, With<TutorialText>>,
+    translations: Translations,
+) {
+    // the colors may be changed from the inspector of the `dev` feature
+    let recolored = paint.face_colors.is_changed();
+    for (sticker, material) in &q_sticker {
+        if !tutorial.is_changed() && !recolored && !sticker.is_added() {
+            continue;
         }
 
-        let rotation = Quat::from_axis_angle(rotating.axis, rotate_angle);
-        transform.rotate_around(Vec3::ZERO, rotation);
+        let face = match *tutorial {
+            Tutorial::Step(step) => step.mask(sticker.index, sticker.face),
+            Tutorial::Off | Tutorial::Done => sticker.face,
+        };
+        paint.paint(material, face);
+    }
+
+    if tutorial.is_changed() {
+        for mut text in &mut q_text {
+            text.sections[0].value = translations.get(tutorial.instructions());
+        }
     }
 }
 
 #[derive(Component)]
 struct GameUiRoot;
 
-#[derive(Component)]
-enum PlayButtonActions {
+#[derive(Component, Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum PlayButtonActions {
     BackToMenu,
     CubeScramble,
     CubeSolver,
+    CompareSolvers,
+    ExplodedView,
+    PieceTypes,
+    Tutorial,
+    Fullscreen,
 }
 
 #[derive(Component)]
-struct MovesText;
+pub(crate) struct MovesText;
+
+#[derive(Component)]
+pub(crate) struct TutorialText;
+
+// How far the cube is from solved, by the cycles of its pieces
+#[derive(Component)]
+struct CyclesText;
 
-fn game_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
+// The first section shows the wide move depth, the second the result of the last action
+#[derive(Component)]
+pub(crate) struct StatusText;
+
+fn game_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    wide_depth: Res<WideDepth>,
+    layer_prefix: Res<LayerPrefix>,
+    tutorial: Res<Tutorial>,
+    theme: Res<Theme>,
+    translations: Translations,
+) {
     let font = asset_server.load("fonts/FiraSans-Bold.ttf");
 
     // root node
@@ -543,20 +530,21 @@ fn game_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
             },
             ..default()
         })
-        .insert(GameUiRoot)
+        .insert((GameUiRoot, LayoutNode::Root))
         .with_children(|parent| {
             // left vertical fill (border)
             parent
                 .spawn(NodeBundle {
                     style: Style {
                         width: Val::Px(200.),
-                        height: Val::Px(100.),
+                        height: Val::Auto,
                         border: UiRect::all(Val::Px(2.0)),
                         ..default()
                     },
-                    background_color: Color::rgb(0.65, 0.65, 0.65).into(),
+                    background_color: theme.border().into(),
                     ..default()
                 })
+                .insert((LayoutNode::Buttons, ThemeColor::Border))
                 .with_children(|parent| {
                     // left vertical fill (content)
                     parent
@@ -568,19 +556,20 @@ fn game_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
                                 align_self: AlignSelf::Center,
                                 ..default()
                             },
-                            background_color: Color::rgb(0.15, 0.15, 0.15).into(),
+                            background_color: theme.panel().into(),
                             ..default()
                         })
+                        .insert((LayoutNode::ButtonList, ThemeColor::Panel))
                         .with_children(|parent| {
                             // text
                             parent
                                 .spawn(
                                     TextBundle::from_section(
-                                        "Back to menu",
+                                        String::new(),
                                         TextStyle {
                                             font: font.clone(),
                                             font_size: 30.0,
-                                            color: Color::WHITE,
+                                            color: theme.text(),
                                         },
                                     )
                                     .with_style(Style {
@@ -589,17 +578,18 @@ fn game_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
                                     }),
                                 )
                                 .insert(PlayButtonActions::BackToMenu)
+                                .insert(Localized::new("game-back-to-menu"))
                                 .insert(Interaction::None);
 
                             // text
                             parent
                                 .spawn(
                                     TextBundle::from_section(
-                                        "Cube Scramble",
+                                        String::new(),
                                         TextStyle {
                                             font: font.clone(),
                                             font_size: 30.0,
-                                            color: Color::WHITE,
+                                            color: theme.text(),
                                         },
                                     )
                                     .with_style(Style {
@@ -608,16 +598,17 @@ fn game_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
                                     }),
                                 )
                                 .insert(PlayButtonActions::CubeScramble)
+                                .insert(Localized::new("game-scramble"))
                                 .insert(Interaction::None);
 
                             parent
                                 .spawn(
                                     TextBundle::from_section(
-                                        "Apply solver",
+                                        String::new(),
                                         TextStyle {
                                             font: font.clone(),
                                             font_size: 30.0,
-                                            color: Color::WHITE,
+                                            color: theme.text(),
                                         },
                                     )
                                     .with_style(Style {
@@ -626,7 +617,128 @@ fn game_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
                                     }),
                                 )
                                 .insert(PlayButtonActions::CubeSolver)
+                                .insert(Localized::new("game-solve"))
+                                .insert(Interaction::None);
+
+                            parent
+                                .spawn(
+                                    TextBundle::from_section(
+                                        String::new(),
+                                        TextStyle {
+                                            font: font.clone(),
+                                            font_size: 30.0,
+                                            color: theme.text(),
+                                        },
+                                    )
+                                    .with_style(Style {
+                                        margin: UiRect::all(Val::Px(15.0)),
+                                        ..default()
+                                    }),
+                                )
+                                .insert(PlayButtonActions::CompareSolvers)
+                                .insert(Localized::new("game-compare-solvers"))
+                                .insert(Interaction::None);
+
+                            parent
+                                .spawn(
+                                    TextBundle::from_section(
+                                        String::new(),
+                                        TextStyle {
+                                            font: font.clone(),
+                                            font_size: 30.0,
+                                            color: theme.text(),
+                                        },
+                                    )
+                                    .with_style(Style {
+                                        margin: UiRect::all(Val::Px(15.0)),
+                                        ..default()
+                                    }),
+                                )
+                                .insert(PlayButtonActions::ExplodedView)
+                                .insert(Localized::new("game-exploded-view"))
+                                .insert(Interaction::None);
+
+                            parent
+                                .spawn(
+                                    TextBundle::from_section(
+                                        String::new(),
+                                        TextStyle {
+                                            font: font.clone(),
+                                            font_size: 30.0,
+                                            color: theme.text(),
+                                        },
+                                    )
+                                    .with_style(Style {
+                                        margin: UiRect::all(Val::Px(15.0)),
+                                        ..default()
+                                    }),
+                                )
+                                .insert(PlayButtonActions::PieceTypes)
+                                .insert(Localized::new("game-piece-types"))
+                                .insert(Interaction::None);
+
+                            parent
+                                .spawn(
+                                    TextBundle::from_section(
+                                        String::new(),
+                                        TextStyle {
+                                            font: font.clone(),
+                                            font_size: 30.0,
+                                            color: theme.text(),
+                                        },
+                                    )
+                                    .with_style(Style {
+                                        margin: UiRect::all(Val::Px(15.0)),
+                                        ..default()
+                                    }),
+                                )
+                                .insert(PlayButtonActions::Tutorial)
+                                .insert(Localized::new("game-tutorial"))
+                                .insert(Interaction::None);
+
+                            parent
+                                .spawn(
+                                    TextBundle::from_section(
+                                        String::new(),
+                                        TextStyle {
+                                            font: font.clone(),
+                                            font_size: 30.0,
+                                            color: theme.text(),
+                                        },
+                                    )
+                                    .with_style(Style {
+                                        margin: UiRect::all(Val::Px(15.0)),
+                                        ..default()
+                                    }),
+                                )
+                                .insert(PlayButtonActions::Fullscreen)
+                                .insert(Localized::new("game-fullscreen"))
                                 .insert(Interaction::None);
+
+                            let status_style = TextStyle {
+                                font: font.clone(),
+                                font_size: 20.0,
+                                color: theme.text(),
+                            };
+                            parent
+                                .spawn(
+                                    TextBundle::from_sections([
+                                        TextSection::new(
+                                            layers_message(
+                                                *wide_depth,
+                                                *layer_prefix,
+                                                &translations,
+                                            ),
+                                            status_style.clone(),
+                                        ),
+                                        TextSection::new(String::new(), status_style),
+                                    ])
+                                    .with_style(Style {
+                                        margin: UiRect::all(Val::Px(15.0)),
+                                        ..default()
+                                    }),
+                                )
+                                .insert(StatusText);
                         });
                 }); // root node
 
@@ -640,18 +752,19 @@ fn game_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
                         height: Val::Percent(100.0),
                         ..default()
                     },
-                    background_color: Color::rgb(0.15, 0.15, 0.15).into(),
+                    background_color: theme.panel().into(),
                     ..default()
                 })
+                .insert((LayoutNode::Moves, ThemeColor::Panel))
                 .with_children(|parent| {
                     // Title
-                    parent.spawn(
+                    parent.spawn((
                         TextBundle::from_section(
-                            "Moves",
+                            String::new(),
                             TextStyle {
                                 font: font.clone(),
                                 font_size: 35.,
-                                color: Color::WHITE,
+                                color: theme.text(),
                             },
                         )
                         .with_text_alignment(TextAlignment::Center)
@@ -664,7 +777,8 @@ fn game_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
                             },
                             ..default()
                         }),
-                    );
+                        Localized::new("game-moves"),
+                    ));
 
                     parent
                         .spawn(
@@ -673,7 +787,7 @@ fn game_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
                                 TextStyle {
                                     font: font.clone(),
                                     font_size: 40.,
-                                    color: Color::WHITE,
+                                    color: theme.text(),
                                 },
                             )
                             .with_text_alignment(TextAlignment::Center)
@@ -686,60 +800,248 @@ fn game_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
                             }),
                         )
                         .insert(MovesText);
+
+                    parent
+                        .spawn(
+                            TextBundle::from_section(
+                                String::new(),
+                                TextStyle {
+                                    font: font.clone(),
+                                    font_size: 20.,
+                                    color: theme.text(),
+                                },
+                            )
+                            .with_style(Style {
+                                margin: UiRect::all(Val::Px(5.0)),
+                                max_width: Val::Px(180.0),
+                                ..default()
+                            }),
+                        )
+                        .insert(CyclesText);
                 });
+
+            // tutorial instructions
+            parent
+                .spawn(
+                    TextBundle::from_section(
+                        translations.get(tutorial.instructions()),
+                        TextStyle {
+                            font: font.clone(),
+                            font_size: 25.0,
+                            color: Color::WHITE,
+                        },
+                    )
+                    .with_style(Style {
+                        position_type: PositionType::Absolute,
+                        // above the queued moves
+                        bottom: Val::Px(50.0),
+                        left: Val::Px(220.0),
+                        max_width: Val::Px(600.0),
+                        ..default()
+                    }),
+                )
+                .insert((TutorialText, LayoutNode::Tutorial));
         });
 }
 
 fn clean_up(
     mut commands: Commands,
     q_ui: Query<Entity, With<GameUiRoot>>,
-    q_piece: Query<Entity, With<Piece>>,
-    mut move_queue: ResMut<MoveQueue>,
+    q_cube: Query<(Entity, Has<PlayedCube>), With<CubeRoot>>,
+    mut move_queue: PlayedMut<MoveQueue>,
 ) {
-    move_queue.moves.clear();
+    move_queue.reset();
     for entity in q_ui.iter() {
         commands.entity(entity).despawn_recursive();
     }
 
-    for entity in q_piece.iter() {
-        commands.entity(entity).despawn_recursive();
+    for (entity, played) in q_cube.iter() {
+        if played {
+            commands.entity(entity).despawn_descendants();
+        } else {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+fn cycles_message(current_cube: &CurrentCube, translations: &Translations) -> String {
+    // pieces can't be told apart on bigger cubes
+    if current_cube.cube_size() != 3 {
+        return String::new();
+    }
+
+    let cycles = cycle_structure(&FaceletCube::new(3).apply_moves(current_cube.moves()));
+    translations.text(
+        Localized::new("game-cycles")
+            .with("unsolved", cycles.unsolved_pieces())
+            .with("corners", cycles.corner_cycles.len())
+            .with("edges", cycles.edge_cycles.len()),
+    )
+}
+
+// Lists the moves made once another one is applied, triggers in parentheses like in solve
+// commentary
+fn show_moves(
+    mut move_applied: PlayedMoves,
+    current_cube: Played<CurrentCube>,
+    mut q_text: Query<&mut Text, With<MovesText>>,
+) {
+    if move_applied.read().count() == 0 {
+        return;
+    }
+    for mut text in &mut q_text {
+        text.sections[0].value = annotate_triggers(current_cube.moves());
     }
 }
 
+fn show_cycles(
+    current_cube: Played<CurrentCube>,
+    translations: Translations,
+    mut q_text: Query<&mut Text, With<CyclesText>>,
+) {
+    for mut text in &mut q_text {
+        text.sections[0].value = cycles_message(&current_cube, &translations);
+    }
+}
+
+fn layers_message(
+    wide_depth: WideDepth,
+    layer_prefix: LayerPrefix,
+    translations: &Translations,
+) -> String {
+    translations.text(match layer_prefix.0 {
+        Some(depth) => Localized::new("game-next-turn-layers").with("layers", depth),
+        None => Localized::new("game-wide-move-layers").with("layers", wide_depth.0),
+    })
+}
+
+fn layers_status(
+    wide_depth: Res<WideDepth>,
+    layer_prefix: Res<LayerPrefix>,
+    translations: Translations,
+    mut q_status: Query<&mut Text, With<StatusText>>,
+) {
+    for mut text in &mut q_status {
+        text.sections[0].value = layers_message(*wide_depth, *layer_prefix, &translations);
+    }
+}
+
+/// The status line of the game, telling the player how the last action went, in the chosen
+/// language.
+#[derive(SystemParam)]
+pub(crate) struct StatusLine<'w, 's> {
+    translations: Translations<'w>,
+    q_status: Query<'w, 's, &'static mut Text, With<StatusText>>,
+}
+
+impl StatusLine<'_, '_> {
+    pub fn show(&mut self, status: Localized) {
+        let status = self.translations.text(status);
+        self.show_text(&status);
+    }
+
+    /// Shows a text already written for the player, like the output of a script.
+    pub fn show_text(&mut self, status: &str) {
+        for mut text in &mut self.q_status {
+            text.sections[1].value = format!("\n{status}");
+        }
+    }
+
+    pub fn clear(&mut self) {
+        for mut text in &mut self.q_status {
+            text.sections[1].value.clear();
+        }
+    }
+}
+
+// The ways of showing the cube toggled from the game ui
+#[derive(SystemParam)]
+struct CubeViews<'w> {
+    exploded_view: ResMut<'w, ExplodedView>,
+    piece_view: ResMut<'w, PieceView>,
+    tutorial: ResMut<'w, Tutorial>,
+}
+
+// The moves queued from the game ui, but for spectators, the solver waiting for its tables
+#[derive(SystemParam)]
+struct QueuedMoves<'w, 's> {
+    move_queue: PlayedMut<'w, 's, MoveQueue>,
+    session: PlayedMut<'w, 's, SolveSession>,
+    scrambler: Scrambler<'w>,
+    spectator: Option<Res<'w, Spectator>>,
+    tables: Res<'w, SolverTables>,
+}
+
 fn button_system(
-    mut interaction_query: Query<(&Interaction, &PlayButtonActions), (Changed<Interaction>,)>,
+    interaction_query: Query<(&Interaction, &PlayButtonActions), Changed<Interaction>>,
     mut game_state: ResMut<NextState<GameState>>,
-    current_cube: Res<CurrentCube>,
-    mut move_queue: ResMut<MoveQueue>,
+    mut current_cube: PlayedMut<CurrentCube>,
+    mut queued: QueuedMoves,
+    mut views: CubeViews,
+    mut confirmation: Confirmation,
+    mut status_line: StatusLine,
 ) {
-    for (interaction, button) in &mut interaction_query {
-        if *interaction == Interaction::Pressed {
-            match *button {
-                PlayButtonActions::BackToMenu => {
-                    game_state.set(GameState::Menu);
-                }
-                PlayButtonActions::CubeScramble => {
-                    let mut cmds: VecDeque<Move> =
-                        random_scramble(current_cube.cube_size as CubeSize, false).into();
+    let pressed = interaction_query
+        .iter()
+        .filter(|(interaction, _)| **interaction == Interaction::Pressed)
+        .map(|(_, button)| (*button, false));
+    let confirmed = confirmation
+        .confirmed()
+        .into_iter()
+        .map(|button| (button, true));
+    let actions: Vec<_> = pressed.chain(confirmed).collect();
 
-                    move_queue.moves.append(&mut cmds);
-                }
-                PlayButtonActions::CubeSolver => {
-                    let cube = FaceletCube::new(current_cube.cube_size as CubeSize)
-                        .apply_moves(&current_cube.moves);
-                    let solution = solve(&cube);
-
-                    if let Some(s) = solution {
-                        let mut solution = String::new();
-                        for m in s.iter() {
-                            solution.push_str(&m.to_string());
-                            solution.push(' ');
-                            move_queue.moves.push_back(*m);
-                        }
-                        info!("Solution {}", solution);
-                    } else {
-                        warn!("Facelet Cube {:?} no solver", cube.state());
-                    }
+    for (button, confirmed) in actions {
+        match button {
+            PlayButtonActions::CubeScramble | PlayButtonActions::CubeSolver
+                if queued.spectator.is_some() =>
+            {
+                status_line.show(Localized::new("status-spectating"));
+            }
+            // giving up the solve in progress, once the player agrees
+            PlayButtonActions::BackToMenu | PlayButtonActions::CubeScramble
+                if !confirmed && confirmation.ask(button, &current_cube) => {}
+            PlayButtonActions::BackToMenu => {
+                game_state.set(GameState::Menu);
+            }
+            PlayButtonActions::CubeScramble => {
+                let cube_size = current_cube.cube_size() as CubeSize;
+                let scramble = queued.scrambler.scramble(cube_size);
+                queued
+                    .session
+                    .queue_scramble(&mut current_cube, &mut queued.move_queue, scramble);
+            }
+            PlayButtonActions::ExplodedView => {
+                views.exploded_view.enabled = !views.exploded_view.enabled;
+            }
+            PlayButtonActions::PieceTypes => {
+                *views.piece_view = views.piece_view.next();
+            }
+            PlayButtonActions::Tutorial if *views.tutorial != Tutorial::Off => {
+                *views.tutorial = Tutorial::Off;
+            }
+            PlayButtonActions::Tutorial if current_cube.cube_size() != 3 => {
+                status_line.show(Localized::new("status-tutorial-size"));
+            }
+            PlayButtonActions::Tutorial => {
+                *views.tutorial = match BeginnerStep::current(&**current_cube) {
+                    Some(step) => Tutorial::Step(step),
+                    None => Tutorial::Done,
+                };
+            }
+            PlayButtonActions::CubeSolver | PlayButtonActions::CompareSolvers
+                if !queued.tables.is_ready() =>
+            {
+                status_line.show(queued.tables.status());
+            }
+            // toggled by the layout, for every puzzle
+            PlayButtonActions::Fullscreen => {}
+            // handled by the comparison panel
+            PlayButtonActions::CompareSolvers => {}
+            PlayButtonActions::CubeSolver => {
+                match queued.session.solve(&current_cube, &queued.move_queue) {
+                    Ok(()) => status_line.clear(),
+                    Err(err) => status_line.show(err),
                 }
             }
         }
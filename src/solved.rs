@@ -0,0 +1,539 @@
+use crate::{
+    i18n::{Localized, Translations},
+    screenshot::timestamped,
+    session::SolveSession,
+    simulator::{CurrentCube, MoveQueue, Played, PlayedMoves, PlayedMut},
+    storage, GameState, Puzzle,
+};
+use bevy::{
+    ecs::system::SystemParam,
+    prelude::*,
+    tasks::{block_on, AsyncComputeTaskPool, Task},
+};
+use cubesim::{
+    penalty_at_stop, prelude::*, simplify_moves, solve, FaceletCube, Penalty, SolveReport,
+    SolveStats, TimedMove,
+};
+use std::{fmt, str::FromStr, time::Duration};
+
+pub struct SolvedPlugin;
+
+impl Plugin for SolvedPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SolveClock>()
+            .add_event::<CubeSolvedEvent>()
+            .add_event::<PenaltyChanged>()
+            .add_event::<StopTimer>()
+            .add_systems(
+                Update,
+                (
+                    time_moves,
+                    detect_solved,
+                    solved_screen,
+                    show_optimal,
+                    save_report,
+                    penalty_buttons,
+                    solved_button_system,
+                )
+                    .chain()
+                    .run_if(resource_equals(Puzzle::Cube)),
+            )
+            .add_systems(OnExit(GameState::Playing), clean_up);
+    }
+}
+
+/// Whether the solver or a guided solution helped solving the cube, the cube was raced against
+/// others, or a real cube was timed with an external timer.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub(crate) enum SolveMode {
+    Standard,
+    Assisted,
+    Race,
+    External,
+}
+
+impl SolveMode {
+    pub(crate) const ALL: [SolveMode; 4] = [
+        SolveMode::Standard,
+        SolveMode::Assisted,
+        SolveMode::Race,
+        SolveMode::External,
+    ];
+}
+
+impl fmt::Display for SolveMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            SolveMode::Standard => "standard",
+            SolveMode::Assisted => "assisted",
+            SolveMode::Race => "race",
+            SolveMode::External => "external",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for SolveMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        SolveMode::ALL
+            .into_iter()
+            .find(|mode| mode.to_string() == s)
+            .ok_or_else(|| format!("unknown solve mode '{s}'"))
+    }
+}
+
+/// Sent once a scrambled cube is solved.
+#[derive(Event, Clone, Debug)]
+pub(crate) struct CubeSolvedEvent {
+    pub cube_size: usize,
+    pub mode: SolveMode,
+    pub scramble: Vec<Move>,
+    pub solution: Vec<Move>,
+    // the moves of the solution once simplified, without the cube rotations
+    pub solution_length: usize,
+    // the timed moves of the solve and their statistics
+    pub report: SolveReport,
+}
+
+/// Sent to stop the timer of the solve under way, as the stop key does.
+#[derive(Event, Clone, Copy, Debug)]
+pub(crate) struct StopTimer;
+
+/// Sent when the penalty of the last solve is changed on the solved screen.
+#[derive(Event, Clone, Copy, Debug)]
+pub(crate) struct PenaltyChanged(pub Option<Penalty>);
+
+// Shown over the cube once it's solved after a scramble
+#[derive(Component)]
+struct SolvedScreen;
+
+#[derive(Component)]
+struct SolvedText;
+
+#[derive(Component)]
+struct CloseButton;
+
+// The time of the solve shown, with its penalty
+#[derive(Component)]
+struct ResultText;
+
+// Gives the solve shown the penalty, or takes it back
+#[derive(Component)]
+struct PenaltyButton(Penalty);
+
+// Saves the report of the solve shown as JSON, for other tools to read
+#[derive(Component)]
+struct SaveReportButton(SolveReport);
+
+// Looks for a shorter solution of the scramble than the one just made, in the background
+#[derive(Component)]
+pub(crate) struct OptimalSearch {
+    solution_length: usize,
+    task: Task<Option<Vec<Move>>>,
+}
+
+// pauses between moves longer than this are reported after a solve
+const PAUSE_SECONDS: f32 = 1.0;
+// ends the solve under way without solving the cube, with a penalty
+const STOP_TIMER_KEY: KeyCode = KeyCode::Period;
+
+// The moves made since the last scramble, timed from the first one
+#[derive(Resource, Default)]
+pub(crate) struct SolveClock {
+    started: Option<Duration>,
+    moves: Vec<TimedMove>,
+}
+
+impl SolveClock {
+    /// The time spent on the solve under way at `now`, or on the last one once it's over.
+    pub(crate) fn solve_time(&self, now: Duration, solving: bool) -> Duration {
+        match self.started {
+            Some(started) if solving => now.saturating_sub(started),
+            _ => self.moves.last().map_or(Duration::ZERO, |timed| timed.at),
+        }
+    }
+}
+
+// Counts the turns of a solution in the half turn metric, without the cube rotations
+fn move_count(moves: &[Move]) -> usize {
+    simplify_moves(moves)
+        .iter()
+        .filter(|mv| !matches!(mv, Move::X(_) | Move::Y(_) | Move::Z(_)))
+        .count()
+}
+
+fn time_moves(
+    time: Res<Time>,
+    mut move_applied: PlayedMoves,
+    current_cube: Played<CurrentCube>,
+    mut clock: ResMut<SolveClock>,
+) {
+    let applied: Vec<Move> = move_applied.read().copied().collect();
+    if applied.is_empty() {
+        return;
+    }
+
+    // the scramble's moves restart the clock, and only the moves made after it are timed
+    let solving = current_cube
+        .scrambled_at()
+        .map_or(0, |start| current_cube.moves().len().saturating_sub(start));
+    if applied.len() > solving {
+        *clock = SolveClock::default();
+    }
+    for &mv in &applied[applied.len() - solving.min(applied.len())..] {
+        let started = *clock.started.get_or_insert(time.elapsed());
+        clock.moves.push(TimedMove {
+            mv,
+            at: time.elapsed() - started,
+        });
+    }
+}
+
+fn stats_message(stats: &SolveStats, translations: &Translations) -> String {
+    let mut message = translations.text(
+        Localized::new("solved-stats")
+            .with("seconds", format!("{:.2}", stats.duration.as_secs_f32()))
+            .with("tps", format!("{:.2}", stats.tps)),
+    );
+    message.push('\n');
+    if let Some(longest) = stats.pauses.iter().map(|pause| pause.length).max() {
+        message.push_str(
+            &translations.text(
+                Localized::new("solved-pauses")
+                    .with("pauses", stats.pauses.len())
+                    .with("over", format!("{PAUSE_SECONDS}"))
+                    .with("longest", format!("{:.1}", longest.as_secs_f32())),
+            ),
+        );
+        message.push('\n');
+    }
+    let phases: Vec<String> = stats
+        .phases
+        .iter()
+        .map(|phase| {
+            translations.text(
+                Localized::new("solved-phase")
+                    .with("phase", phase.phase.to_string())
+                    .with("seconds", format!("{:.1}", phase.duration.as_secs_f32()))
+                    .with("moves", phase.moves),
+            )
+        })
+        .collect();
+    if !phases.is_empty() {
+        message.push_str(&phases.join(", "));
+        message.push('\n');
+    }
+
+    message
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn unix_time() -> Option<Duration> {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()
+}
+
+// the system clock can't be read from wasm
+#[cfg(target_arch = "wasm32")]
+fn unix_time() -> Option<Duration> {
+    None
+}
+
+// The ways of stopping the timer before the cube is solved
+#[derive(SystemParam)]
+struct StopInput<'w, 's> {
+    keyboard_input: Res<'w, Input<KeyCode>>,
+    stop_events: EventReader<'w, 's, StopTimer>,
+}
+
+impl StopInput<'_, '_> {
+    fn stopped(&mut self) -> bool {
+        self.stop_events.read().count() > 0 || self.keyboard_input.just_pressed(STOP_TIMER_KEY)
+    }
+}
+
+fn detect_solved(
+    mut stop_input: StopInput,
+    mut move_applied: PlayedMoves,
+    mut current_cube: PlayedMut<CurrentCube>,
+    move_queue: Played<MoveQueue>,
+    session: Played<SolveSession>,
+    clock: Res<SolveClock>,
+    mut solved_events: EventWriter<CubeSolvedEvent>,
+) {
+    let stopped = stop_input.stopped() && current_cube.is_solving();
+    // the solver's moves only count once they are all made
+    if (move_applied.read().count() == 0 && !stopped) || !move_queue.is_empty() {
+        return;
+    }
+    let cube_size = current_cube.cube_size();
+    let mode = if session.is_assisted() {
+        SolveMode::Assisted
+    } else if session.is_raced() {
+        SolveMode::Race
+    } else {
+        SolveMode::Standard
+    };
+    // the timer stopped one move from solved gives a +2, further away a DNF
+    let (penalty, solve) = if stopped {
+        (penalty_at_stop(&**current_cube), current_cube.stop_solve())
+    } else {
+        (None, current_cube.take_solve())
+    };
+    let Some((scramble, solution)) = solve else {
+        return;
+    };
+
+    let mut report = SolveReport::new(
+        cube_size as CubeSize,
+        scramble,
+        &clock.moves,
+        Duration::from_secs_f32(PAUSE_SECONDS),
+    );
+    report.finished_at = unix_time();
+    report.penalty = penalty;
+    solved_events.send(CubeSolvedEvent {
+        cube_size,
+        mode,
+        scramble: scramble.to_vec(),
+        solution: solution.to_vec(),
+        solution_length: move_count(solution),
+        report,
+    });
+}
+
+fn solved_screen(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut solved_events: EventReader<CubeSolvedEvent>,
+    translations: Translations,
+    q_screen: Query<Entity, With<SolvedScreen>>,
+) {
+    let Some(solved) = solved_events.read().last() else {
+        return;
+    };
+    let solution_length = solved.solution_length;
+    let search = (solved.cube_size == 3).then(|| {
+        let cube = FaceletCube::new(3).apply_moves(&solved.scramble);
+        OptimalSearch {
+            solution_length,
+            task: AsyncComputeTaskPool::get().spawn(async move { solve(&cube) }),
+        }
+    });
+
+    for entity in q_screen.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    let text_style = TextStyle {
+        font: font.clone(),
+        font_size: 35.0,
+        color: Color::WHITE,
+    };
+
+    let mut screen = commands.spawn(NodeBundle {
+        style: Style {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        ..default()
+    });
+    screen.insert(SolvedScreen).with_children(|parent| {
+        parent
+            .spawn(NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    padding: UiRect::all(Val::Px(20.0)),
+                    ..default()
+                },
+                background_color: Color::rgba(0.15, 0.15, 0.15, 0.9).into(),
+                ..default()
+            })
+            .with_children(|parent| {
+                let searching = match search {
+                    Some(_) => translations.get("solved-searching"),
+                    None => String::new(),
+                };
+                let title = match solved.report.penalty {
+                    None => "solved-title",
+                    Some(Penalty::PlusTwo) => "solved-plus-two",
+                    Some(Penalty::Dnf) => "solved-dnf",
+                };
+                let solution = Localized::new("solved-solution").with("moves", solution_length);
+                parent
+                    .spawn(TextBundle::from_sections([
+                        TextSection::new(
+                            format!(
+                                "{}\n{}\n{}",
+                                translations.get(title),
+                                translations.text(solution),
+                                stats_message(&solved.report.stats, &translations)
+                            ),
+                            text_style.clone(),
+                        ),
+                        TextSection::new(searching, text_style.clone()),
+                        // whether the report was saved
+                        TextSection::new(String::new(), text_style.clone()),
+                    ]))
+                    .insert(SolvedText);
+
+                parent
+                    .spawn(TextBundle::from_section(
+                        result_message(&solved.report, &translations),
+                        text_style.clone(),
+                    ))
+                    .insert(ResultText);
+                parent.spawn(NodeBundle::default()).with_children(|parent| {
+                    for penalty in [Penalty::PlusTwo, Penalty::Dnf] {
+                        parent
+                            .spawn(
+                                TextBundle::from_section(penalty.to_string(), text_style.clone())
+                                    .with_style(Style {
+                                        margin: UiRect::new(
+                                            Val::Px(10.0),
+                                            Val::Px(10.0),
+                                            Val::Px(20.0),
+                                            Val::Px(0.0),
+                                        ),
+                                        ..default()
+                                    }),
+                            )
+                            .insert(PenaltyButton(penalty))
+                            .insert(Interaction::None);
+                    }
+                });
+
+                parent
+                    .spawn(
+                        TextBundle::from_section(String::new(), text_style.clone()).with_style(
+                            Style {
+                                margin: UiRect::top(Val::Px(20.0)),
+                                ..default()
+                            },
+                        ),
+                    )
+                    .insert(Localized::new("solved-save-report"))
+                    .insert(SaveReportButton(solved.report.clone()))
+                    .insert(Interaction::None);
+                parent
+                    .spawn(
+                        TextBundle::from_section(String::new(), text_style.clone()).with_style(
+                            Style {
+                                margin: UiRect::top(Val::Px(20.0)),
+                                ..default()
+                            },
+                        ),
+                    )
+                    .insert(Localized::new("menu-close"))
+                    .insert(CloseButton)
+                    .insert(Interaction::None);
+            });
+    });
+    if let Some(search) = search {
+        screen.insert(search);
+    }
+}
+
+fn result_message(report: &SolveReport, translations: &Translations) -> String {
+    translations.text(Localized::new("solved-result").with("result", report.result().to_string()))
+}
+
+// Marks the solve shown with a +2 or a DNF, or takes the penalty back, for the report saved
+// and the solves recorded
+fn penalty_buttons(
+    interaction_query: Query<(&Interaction, &PenaltyButton), Changed<Interaction>>,
+    mut q_report: Query<&mut SaveReportButton>,
+    mut q_text: Query<&mut Text, With<ResultText>>,
+    mut penalty_events: EventWriter<PenaltyChanged>,
+    translations: Translations,
+) {
+    for (_, PenaltyButton(penalty)) in interaction_query
+        .iter()
+        .filter(|(interaction, _)| **interaction == Interaction::Pressed)
+    {
+        for mut button in &mut q_report {
+            let report = &mut button.0;
+            report.penalty = match report.penalty {
+                Some(given) if given == *penalty => None,
+                _ => Some(*penalty),
+            };
+            penalty_events.send(PenaltyChanged(report.penalty));
+            for mut text in &mut q_text {
+                text.sections[0].value = result_message(report, &translations);
+            }
+        }
+    }
+}
+
+fn show_optimal(
+    mut commands: Commands,
+    mut q_search: Query<(Entity, &mut OptimalSearch)>,
+    mut q_text: Query<&mut Text, With<SolvedText>>,
+    translations: Translations,
+) {
+    for (entity, mut search) in &mut q_search {
+        if !search.task.is_finished() {
+            continue;
+        }
+
+        // the solution made is also an upper bound of the optimal length
+        let message = match block_on(&mut search.task) {
+            Some(solution) => Localized::new("solved-optimal")
+                .with("moves", move_count(&solution).min(search.solution_length)),
+            None => Localized::new("solved-no-shorter"),
+        };
+        let message = translations.text(message);
+        for mut text in &mut q_text {
+            text.sections[1].value = message.clone();
+        }
+        commands.entity(entity).remove::<OptimalSearch>();
+    }
+}
+
+fn solved_button_system(
+    mut commands: Commands,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<CloseButton>)>,
+    q_screen: Query<Entity, With<SolvedScreen>>,
+) {
+    if interaction_query.iter().any(|i| *i == Interaction::Pressed) {
+        for entity in q_screen.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+fn save_report(
+    time: Res<Time>,
+    interaction_query: Query<(&Interaction, &SaveReportButton), Changed<Interaction>>,
+    mut q_text: Query<&mut Text, With<SolvedText>>,
+    translations: Translations,
+) {
+    for (_, SaveReportButton(report)) in interaction_query
+        .iter()
+        .filter(|(interaction, _)| **interaction == Interaction::Pressed)
+    {
+        let path = timestamped("solve", ".json", &time);
+        let message = match storage::write(&path, &report.to_json()) {
+            Ok(()) => Localized::new("solved-report-saved").with("path", path),
+            Err(err) => Localized::new("solved-report-failed").with("error", err.to_string()),
+        };
+        let message = format!("\n{}", translations.text(message));
+        for mut text in &mut q_text {
+            text.sections[2].value = message.clone();
+        }
+    }
+}
+
+fn clean_up(mut commands: Commands, q_screen: Query<Entity, With<SolvedScreen>>) {
+    for entity in q_screen.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
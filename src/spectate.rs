@@ -0,0 +1,518 @@
+use crate::{
+    i18n::{Localized, Translations},
+    menu::MenuState,
+    simulator::{CurrentCube, MoveQueue, Played, PlayedMut},
+    solved::SolveClock,
+    theme::{Theme, ThemeColor},
+    CubeSizeSetting, GameState, Puzzle,
+};
+use bevy::prelude::*;
+use cubesim::{parse_algorithm, prelude::*};
+use serde::{Deserialize, Serialize};
+#[cfg(all(feature = "online", not(target_arch = "wasm32")))]
+use std::{
+    net::{TcpListener, TcpStream},
+    sync::mpsc::{self, RecvTimeoutError},
+    thread,
+};
+use std::{
+    sync::{
+        mpsc::{Receiver, Sender},
+        Mutex,
+    },
+    time::Duration,
+};
+
+// The port broadcasting to the spectators, on every network interface
+const SPECTATE_PORT: u16 = 9002;
+// The spectators' clock is sent again once it has moved on by this much
+const CLOCK_INTERVAL: Duration = Duration::from_millis(100);
+#[cfg(all(feature = "online", not(target_arch = "wasm32")))]
+const ACCEPT_INTERVAL: Duration = Duration::from_millis(100);
+// A spectator too slow to take the moves in this long is left out
+#[cfg(all(feature = "online", not(target_arch = "wasm32")))]
+const WRITE_TIMEOUT: Duration = Duration::from_secs(1);
+// How long a spectator waits to connect again once the broadcast can't be reached
+#[cfg(all(feature = "online", not(target_arch = "wasm32")))]
+const RETRY_INTERVAL: Duration = Duration::from_secs(3);
+
+pub struct SpectatePlugin;
+
+impl Plugin for SpectatePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpectateMode>()
+            .add_systems(Startup, start_spectate)
+            .add_systems(
+                Update,
+                broadcast_cube
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(resource_equals(Puzzle::Cube))
+                    .run_if(resource_exists::<Broadcast>()),
+            )
+            .add_systems(
+                Update,
+                start_watching
+                    .run_if(in_state(MenuState::Main))
+                    .run_if(resource_exists::<Spectator>()),
+            )
+            .add_systems(
+                OnEnter(GameState::Playing),
+                spectator_ui.run_if(resource_exists::<Spectator>()),
+            )
+            .add_systems(
+                Update,
+                (receive_events, follow_cube, show_spectator)
+                    .chain()
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(resource_exists::<Spectator>()),
+            )
+            .add_systems(OnExit(GameState::Playing), clean_up);
+    }
+}
+
+/// Shares the cube with others over the network, for online classes or commentating a
+/// solve: one instance broadcasts its moves and timer with the `--broadcast` command line
+/// flag, and the others follow them with `--spectate=HOST`, on a cube they can't turn. Only
+/// available with the `online` feature, on native builds.
+#[derive(Resource, Clone, Eq, PartialEq, Debug, Default)]
+pub enum SpectateMode {
+    #[default]
+    Off,
+    /// Broadcasts the cube to the spectators connecting on port 9002.
+    Broadcast,
+    /// Follows the cube broadcast at the address, a host with an optional port.
+    Watch(String),
+}
+
+impl SpectateMode {
+    /// The mode asked for on the command line, as `--broadcast`, or `--spectate=HOST` and
+    /// `--spectate=HOST:PORT`.
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Result<SpectateMode, String> {
+        let mut mode = SpectateMode::Off;
+        for arg in args {
+            mode = match arg.as_str() {
+                "--broadcast" => SpectateMode::Broadcast,
+                "--spectate" | "--spectate=" => {
+                    return Err("expected the address to spectate, as --spectate=HOST".to_string());
+                }
+                _ => match arg.strip_prefix("--spectate=") {
+                    Some(address) if address.contains(':') => {
+                        SpectateMode::Watch(address.to_string())
+                    }
+                    Some(host) => SpectateMode::Watch(format!("{host}:{SPECTATE_PORT}")),
+                    None => continue,
+                },
+            };
+        }
+        Ok(mode)
+    }
+}
+
+/// What the broadcast sends to the spectators, each as a JSON message with an `event` field.
+/// A spectator connecting is sent the cube, then the clock if the solve is under way.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum SpectateEvent {
+    /// The cube started over, solved, then turned with the moves.
+    Cube { size: usize, moves: String },
+    /// The moves made since the last event.
+    Moves { moves: String },
+    /// The time of the solve under way, or of the last one once it's over.
+    Clock { time: f32, solving: bool },
+}
+
+// The moves and the clock of the cube played, sent to the broadcasting thread
+#[derive(Resource)]
+struct Broadcast {
+    events: Sender<SpectateEvent>,
+    size: usize,
+    sent: Vec<Move>,
+    // the last clock sent
+    time: Duration,
+    solving: bool,
+}
+
+/// The cube broadcast by another instance, followed on a cube that only turns with its
+/// moves.
+#[derive(Resource)]
+pub(crate) struct Spectator {
+    address: String,
+    events: Mutex<Receiver<Result<SpectateEvent, Localized>>>,
+    size: usize,
+    moves: Vec<Move>,
+    // the cube broadcast started over, the one shown starting over with it
+    restarted: bool,
+    time: Duration,
+    solving: bool,
+    message: Option<Localized>,
+}
+
+impl Spectator {
+    fn status(&self, translations: &Translations) -> String {
+        let state = if self.solving {
+            "spectate-solving"
+        } else {
+            "spectate-waiting"
+        };
+        let message = self
+            .message
+            .clone()
+            .map(|message| translations.text(message));
+        translations.text(
+            Localized::new("spectate-status")
+                .with("address", self.address.as_str())
+                .with("seconds", format!("{:.2}", self.time.as_secs_f32()))
+                .with("state", translations.get(state))
+                .with("moves", self.moves.len())
+                .with("message", message.unwrap_or_default()),
+        )
+    }
+}
+
+#[derive(Component)]
+struct SpectatorUiRoot;
+
+#[derive(Component)]
+struct SpectatorText;
+
+fn moves_string(moves: &[Move]) -> String {
+    moves
+        .iter()
+        .map(|m| m.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn parse_moves(moves: &str) -> Result<Vec<Move>, Localized> {
+    parse_algorithm(moves)
+        .map_err(|err| Localized::new("spectate-invalid-moves").with("error", err.to_string()))
+}
+
+fn start_spectate(mut commands: Commands, spectate_mode: Res<SpectateMode>) {
+    match &*spectate_mode {
+        SpectateMode::Off => {}
+        SpectateMode::Broadcast => match open_broadcast() {
+            Ok(events) => commands.insert_resource(Broadcast {
+                events,
+                size: 0,
+                sent: Vec::new(),
+                time: Duration::ZERO,
+                solving: false,
+            }),
+            Err(err) => warn!("no broadcast to spectators: {err}"),
+        },
+        SpectateMode::Watch(address) => match open_spectator(address) {
+            Ok(events) => commands.insert_resource(Spectator {
+                address: address.clone(),
+                events: Mutex::new(events),
+                size: 0,
+                moves: Vec::new(),
+                restarted: false,
+                time: Duration::ZERO,
+                solving: false,
+                message: Some(Localized::new("spectate-connecting")),
+            }),
+            Err(err) => warn!("can't spectate {address}: {err}"),
+        },
+    }
+}
+
+// Sends the moves made since the last frame, or the whole cube once it started over
+fn broadcast_cube(
+    time: Res<Time>,
+    clock: Res<SolveClock>,
+    current_cube: Played<CurrentCube>,
+    mut broadcast: ResMut<Broadcast>,
+) {
+    let moves = current_cube.moves();
+    let mut events = Vec::new();
+    if broadcast.size != current_cube.cube_size() || !moves.starts_with(&broadcast.sent) {
+        broadcast.size = current_cube.cube_size();
+        broadcast.sent = moves.to_vec();
+        events.push(SpectateEvent::Cube {
+            size: broadcast.size,
+            moves: moves_string(moves),
+        });
+    } else if moves.len() > broadcast.sent.len() {
+        events.push(SpectateEvent::Moves {
+            moves: moves_string(&moves[broadcast.sent.len()..]),
+        });
+        broadcast.sent = moves.to_vec();
+    }
+
+    let solving = current_cube.scrambled_at().is_some();
+    let solve_time = clock.solve_time(time.elapsed(), solving);
+    if solving != broadcast.solving || solve_time.abs_diff(broadcast.time) >= CLOCK_INTERVAL {
+        broadcast.solving = solving;
+        broadcast.time = solve_time;
+        events.push(SpectateEvent::Clock {
+            time: solve_time.as_secs_f32(),
+            solving,
+        });
+    }
+
+    for event in events {
+        // the broadcast may have stopped, as when its port was taken
+        let _ = broadcast.events.send(event);
+    }
+}
+
+#[cfg(all(feature = "online", not(target_arch = "wasm32")))]
+fn open_broadcast() -> Result<Sender<SpectateEvent>, String> {
+    let listener = TcpListener::bind(("0.0.0.0", SPECTATE_PORT))
+        .and_then(|listener| listener.set_nonblocking(true).map(|()| listener))
+        .map_err(|err| format!("port {SPECTATE_PORT} can't be opened: {err}"))?;
+    info!("broadcasting to spectators on port {SPECTATE_PORT}");
+
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || broadcast_events(&listener, &receiver));
+    Ok(sender)
+}
+
+// Sends each event to every spectator, and catches up the ones connecting with the cube
+// made up of the events so far
+#[cfg(all(feature = "online", not(target_arch = "wasm32")))]
+fn broadcast_events(listener: &TcpListener, events: &Receiver<SpectateEvent>) {
+    use tungstenite::{Message, WebSocket};
+
+    let send = |socket: &mut WebSocket<TcpStream>, event: &SpectateEvent| {
+        let text = serde_json::to_string(event).map_err(|err| err.to_string())?;
+        socket
+            .send(Message::Text(text))
+            .map_err(|err| err.to_string())
+    };
+
+    let mut spectators = Vec::new();
+    let mut cube = None;
+    let mut clock = None;
+    loop {
+        while let Ok((stream, address)) = listener.accept() {
+            let socket = stream
+                .set_nonblocking(false)
+                .and_then(|()| stream.set_write_timeout(Some(WRITE_TIMEOUT)))
+                .map_err(|err| err.to_string())
+                .and_then(|()| tungstenite::accept(stream).map_err(|err| err.to_string()));
+            let caught_up = socket.and_then(|mut socket| {
+                for event in cube.iter().chain(&clock) {
+                    send(&mut socket, event)?;
+                }
+                Ok(socket)
+            });
+            match caught_up {
+                Ok(socket) => {
+                    info!("spectator {address} connected");
+                    spectators.push(socket);
+                }
+                Err(err) => debug!("spectator {address} left: {err}"),
+            }
+        }
+
+        let event = match events.recv_timeout(ACCEPT_INTERVAL) {
+            Ok(event) => event,
+            Err(RecvTimeoutError::Timeout) => continue,
+            // the app has quit
+            Err(RecvTimeoutError::Disconnected) => return,
+        };
+        match (&event, &mut cube) {
+            (SpectateEvent::Cube { .. }, _) => cube = Some(event.clone()),
+            (SpectateEvent::Moves { moves }, Some(SpectateEvent::Cube { moves: all, .. })) => {
+                if !all.is_empty() {
+                    all.push(' ');
+                }
+                all.push_str(moves);
+            }
+            (SpectateEvent::Clock { .. }, _) => clock = Some(event.clone()),
+            _ => {}
+        }
+        spectators.retain_mut(|socket| match send(socket, &event) {
+            Ok(()) => true,
+            Err(err) => {
+                debug!("spectator left: {err}");
+                false
+            }
+        });
+    }
+}
+
+#[cfg(not(all(feature = "online", not(target_arch = "wasm32"))))]
+fn open_broadcast() -> Result<Sender<SpectateEvent>, String> {
+    Err("Built without the online feature, only available on native builds".to_string())
+}
+
+#[cfg(all(feature = "online", not(target_arch = "wasm32")))]
+fn open_spectator(address: &str) -> Result<Receiver<Result<SpectateEvent, Localized>>, String> {
+    let (sender, receiver) = mpsc::channel();
+    let url = format!("ws://{address}");
+    thread::spawn(move || loop {
+        let err = match receive_events_from(&url, &sender) {
+            Ok(()) => Localized::new("spectate-ended"),
+            Err(err) => err,
+        };
+        // the app has quit once nothing receives the events
+        if sender.send(Err(err)).is_err() {
+            return;
+        }
+        thread::sleep(RETRY_INTERVAL);
+    });
+    Ok(receiver)
+}
+
+#[cfg(all(feature = "online", not(target_arch = "wasm32")))]
+fn receive_events_from(
+    url: &str,
+    sender: &Sender<Result<SpectateEvent, Localized>>,
+) -> Result<(), Localized> {
+    use tungstenite::{Error, Message};
+
+    let (mut socket, _) = tungstenite::connect(url).map_err(|err| {
+        Localized::new("spectate-connection-failed")
+            .with("url", url)
+            .with("error", err.to_string())
+    })?;
+    loop {
+        let text = match socket.read() {
+            Ok(Message::Text(text)) => text,
+            Ok(Message::Close(_)) | Err(Error::ConnectionClosed) => return Ok(()),
+            Ok(_) => continue,
+            Err(err) => {
+                return Err(
+                    Localized::new("spectate-connection-lost").with("error", err.to_string())
+                )
+            }
+        };
+        let event = serde_json::from_str(&text)
+            .map_err(|err| Localized::new("spectate-invalid-event").with("error", err.to_string()));
+        if sender.send(event).is_err() {
+            return Ok(());
+        }
+    }
+}
+
+#[cfg(not(all(feature = "online", not(target_arch = "wasm32"))))]
+fn open_spectator(_address: &str) -> Result<Receiver<Result<SpectateEvent, Localized>>, String> {
+    Err("Built without the online feature, only available on native builds".to_string())
+}
+
+// Shows the cube broadcast right away, rather than the main menu, the first time only
+fn start_watching(
+    mut started: Local<bool>,
+    mut puzzle: ResMut<Puzzle>,
+    mut game_state: ResMut<NextState<GameState>>,
+    mut menu_state: ResMut<NextState<MenuState>>,
+) {
+    if *started {
+        return;
+    }
+    *started = true;
+    *puzzle = Puzzle::Cube;
+    game_state.set(GameState::Playing);
+    menu_state.set(MenuState::Disabled);
+}
+
+fn spectator_ui(mut commands: Commands, asset_server: Res<AssetServer>, theme: Res<Theme>) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(10.0),
+                    right: Val::Px(10.0),
+                    padding: UiRect::all(Val::Px(10.0)),
+                    ..default()
+                },
+                ..default()
+            },
+            ThemeColor::Panel,
+            SpectatorUiRoot,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    String::new(),
+                    TextStyle {
+                        font,
+                        font_size: 25.0,
+                        color: theme.text(),
+                    },
+                ),
+                SpectatorText,
+            ));
+        });
+}
+
+fn receive_events(mut spectator: ResMut<Spectator>) {
+    let received: Vec<_> = {
+        let events = spectator
+            .events
+            .lock()
+            .unwrap_or_else(|err| err.into_inner());
+        events.try_iter().collect()
+    };
+
+    for event in received {
+        let event = event.and_then(|event| match event {
+            SpectateEvent::Cube { size, moves } => {
+                spectator.moves = parse_moves(&moves)?;
+                spectator.size = size;
+                spectator.restarted = true;
+                Ok(())
+            }
+            SpectateEvent::Moves { moves } => {
+                let moves = parse_moves(&moves)?;
+                spectator.moves.extend(moves);
+                Ok(())
+            }
+            SpectateEvent::Clock { time, solving } => {
+                spectator.time = Duration::from_secs_f32(time.max(0.0));
+                spectator.solving = solving;
+                Ok(())
+            }
+        });
+        spectator.message = event.err();
+    }
+}
+
+// Queues the moves broadcast that the cube shown hasn't made yet, starting over with the
+// cube broadcast
+fn follow_cube(
+    mut spectator: ResMut<Spectator>,
+    current_cube: Played<CurrentCube>,
+    mut move_queue: PlayedMut<MoveQueue>,
+    mut cube_size: ResMut<CubeSizeSetting>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    if spectator.size == 0 {
+        return;
+    }
+    let made = current_cube.moves().len() + move_queue.len();
+    if spectator.restarted
+        || spectator.size != current_cube.cube_size()
+        || made > spectator.moves.len()
+    {
+        spectator.restarted = false;
+        cube_size.0 = spectator.size;
+        game_state.set(GameState::Restarting);
+        return;
+    }
+    move_queue.extend(spectator.moves[made..].iter().copied());
+}
+
+fn show_spectator(
+    spectator: Res<Spectator>,
+    translations: Translations,
+    mut q_text: Query<&mut Text, With<SpectatorText>>,
+) {
+    let status = spectator.status(&translations);
+    for mut text in &mut q_text {
+        if text.sections[0].value != status {
+            text.sections[0].value = status.clone();
+        }
+    }
+}
+
+fn clean_up(mut commands: Commands, q_ui: Query<Entity, With<SpectatorUiRoot>>) {
+    for entity in &q_ui {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,270 @@
+use std::time::Duration;
+
+// The rate of the serial signal sent by the timers, in bits per second
+const BAUD_RATE: f32 = 1200.0;
+// The bytes of a packet being all ASCII, no run of ten bits of the same level is found in one:
+// such a run is the line idling between packets
+const IDLE_BITS: usize = 10;
+// More bytes than a packet has are the noise of something else plugged in
+const MAX_PACKET: usize = 10;
+
+/// What a Stackmat timer shows, as sent with each of its packets.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub(crate) enum StackmatState {
+    /// Reset to zero.
+    Reset,
+    /// Both hands held on the pads long enough, the timer starting once they are lifted.
+    Ready,
+    /// Running.
+    Running,
+    /// Stopped, the time shown being the time of the solve.
+    Stopped,
+    /// Only the left hand on the pads.
+    LeftHand,
+    /// Only the right hand on the pads.
+    RightHand,
+    /// Both hands on the pads, not held long enough yet.
+    BothHands,
+}
+
+/// A packet sent by a Stackmat timer, many times a second, through its audio jack.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub(crate) struct StackmatPacket {
+    /// What the timer shows.
+    pub state: StackmatState,
+    /// The time on the display.
+    pub time: Duration,
+}
+
+impl StackmatPacket {
+    /// Parses the bytes of a packet: a letter for the state, the digits of the time from the
+    /// minutes down to the hundredths of a second, or the thousandths on gen 4 timers, the
+    /// checksum, 64 plus the sum of the digits, then a line feed and a carriage return.
+    pub fn parse(bytes: &[u8]) -> Result<Self, String> {
+        let [state, digits @ .., checksum, b'\n', b'\r'] = bytes else {
+            return Err(format!("invalid packet {bytes:?}"));
+        };
+        if !matches!(digits.len(), 5 | 6) || !digits.iter().all(u8::is_ascii_digit) {
+            return Err(format!("invalid time {digits:?}"));
+        }
+        let digits: Vec<u64> = digits.iter().map(|digit| u64::from(digit - b'0')).collect();
+        if u64::from(*checksum) != 64 + digits.iter().sum::<u64>() {
+            return Err(format!("invalid checksum {checksum}"));
+        }
+
+        let state = match state {
+            b'I' => StackmatState::Reset,
+            b'A' => StackmatState::Ready,
+            b' ' => StackmatState::Running,
+            b'S' => StackmatState::Stopped,
+            b'L' => StackmatState::LeftHand,
+            b'R' => StackmatState::RightHand,
+            b'C' => StackmatState::BothHands,
+            _ => return Err(format!("unknown state {state}")),
+        };
+        // minutes, seconds in two digits, then the fractions of a second
+        let millis = digits[0] * 60_000
+            + digits[1] * 10_000
+            + digits[2] * 1000
+            + digits[3] * 100
+            + digits[4] * 10
+            + digits.get(5).copied().unwrap_or(0);
+        Ok(StackmatPacket {
+            state,
+            time: Duration::from_millis(millis),
+        })
+    }
+}
+
+/// Decodes the packets of a Stackmat timer plugged into an audio input, from the samples of
+/// the signal: a serial line at 1200 bits per second, read whatever the polarity the sound
+/// card gives it.
+#[derive(Clone, Debug)]
+pub(crate) struct StackmatDecoder {
+    samples_per_bit: f32,
+    level: bool,
+    // the samples since the level last changed, and when to read the next bit
+    since_edge: f32,
+    next_bit: f32,
+    // the bits read since the level last changed
+    run: usize,
+    // the level of the line between packets, once found
+    idle: Option<bool>,
+    // the byte being read and the number of its bits read
+    byte: Option<(u8, u32)>,
+    bytes: Vec<u8>,
+}
+
+#[cfg_attr(
+    not(all(feature = "stackmat", not(target_arch = "wasm32"))),
+    allow(dead_code)
+)]
+impl StackmatDecoder {
+    /// A decoder of the samples of a single channel taken at the given rate, in hertz.
+    pub fn new(sample_rate: u32) -> Self {
+        let samples_per_bit = sample_rate as f32 / BAUD_RATE;
+        StackmatDecoder {
+            samples_per_bit,
+            level: false,
+            since_edge: 0.0,
+            next_bit: samples_per_bit / 2.0,
+            run: 0,
+            idle: None,
+            byte: None,
+            bytes: Vec::new(),
+        }
+    }
+
+    /// Feeds the next sample, between -1 and 1, returning the packet it ends, if any.
+    pub fn push(&mut self, sample: f32) -> Option<StackmatPacket> {
+        // the bits are read in their middle, counting from the last change of level
+        let level = sample > 0.0;
+        if level != self.level {
+            self.level = level;
+            self.since_edge = 0.0;
+            self.next_bit = self.samples_per_bit / 2.0;
+            self.run = 0;
+        }
+        self.since_edge += 1.0;
+        if self.since_edge < self.next_bit {
+            return None;
+        }
+        self.next_bit += self.samples_per_bit;
+
+        self.run += 1;
+        if self.run == IDLE_BITS {
+            self.idle = Some(level);
+            self.byte = None;
+            self.bytes.clear();
+        }
+        // the idle line is a 1, and a 0 starts a byte
+        let idle = self.idle?;
+        self.read_bit(level == idle)
+    }
+
+    // Reads the bits of each byte, least significant first, between a start and a stop bit
+    fn read_bit(&mut self, bit: bool) -> Option<StackmatPacket> {
+        match self.byte {
+            None => {
+                if !bit {
+                    self.byte = Some((0, 0));
+                }
+            }
+            Some((byte, 8)) => {
+                self.byte = None;
+                // a missing stop bit is the noise of something else plugged in
+                if !bit || self.bytes.len() == MAX_PACKET {
+                    self.bytes.clear();
+                    return None;
+                }
+                self.bytes.push(byte);
+                if self.bytes.ends_with(b"\n\r") {
+                    let packet = StackmatPacket::parse(&self.bytes).ok();
+                    self.bytes.clear();
+                    return packet;
+                }
+            }
+            Some((byte, read)) => self.byte = Some((byte | u8::from(bit) << read, read + 1)),
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_RATE: u32 = 44100;
+
+    // The samples of the packets sent as a serial line at 1200 bits per second, each byte as a
+    // start bit, 8 bits least significant first and a stop bit, with the line idling before and
+    // after them
+    fn signal(packets: &[&[u8]], inverted: bool) -> Vec<f32> {
+        let mut bits = vec![true; 30];
+        for packet in packets {
+            for &byte in packet.iter() {
+                bits.push(false);
+                bits.extend((0..8).map(|i| byte >> i & 1 == 1));
+                bits.push(true);
+            }
+            bits.extend([true; 30]);
+        }
+        let samples_per_bit = SAMPLE_RATE as f32 / 1200.0;
+        let samples = (bits.len() as f32 * samples_per_bit) as usize;
+        (0..samples)
+            .map(|i| {
+                let high = bits[(i as f32 / samples_per_bit) as usize] != inverted;
+                if high {
+                    0.6
+                } else {
+                    -0.6
+                }
+            })
+            .collect()
+    }
+
+    fn decode(samples: &[f32]) -> Vec<StackmatPacket> {
+        let mut decoder = StackmatDecoder::new(SAMPLE_RATE);
+        samples
+            .iter()
+            .filter_map(|&sample| decoder.push(sample))
+            .collect()
+    }
+
+    #[test]
+    fn parse_packets() {
+        let packet = StackmatPacket::parse(b"I00000@\n\r").unwrap();
+        assert_eq!(packet.state, StackmatState::Reset);
+        assert_eq!(packet.time, Duration::ZERO);
+
+        // the thousandths of gen 4 timers
+        let packet = StackmatPacket::parse(b" 012345O\n\r").unwrap();
+        assert_eq!(packet.state, StackmatState::Running);
+        assert_eq!(packet.time, Duration::from_millis(12345));
+
+        let packet = StackmatPacket::parse(b"C10203F\n\r").unwrap();
+        assert_eq!(packet.state, StackmatState::BothHands);
+        assert_eq!(packet.time, Duration::from_millis(62030));
+    }
+
+    #[test]
+    fn parse_invalid_packets() {
+        assert!(StackmatPacket::parse(b"S01234K\n\r").is_err());
+        assert!(StackmatPacket::parse(b"X01234J\n\r").is_err());
+        assert!(StackmatPacket::parse(b"S0123J\n\r").is_err());
+        assert!(StackmatPacket::parse(b"S01a34J\n\r").is_err());
+        assert!(StackmatPacket::parse(b"S01234J").is_err());
+        assert!(StackmatPacket::parse(b"").is_err());
+    }
+
+    #[test]
+    fn silence_is_no_timer() {
+        let mut decoder = StackmatDecoder::new(SAMPLE_RATE);
+        assert!((0..SAMPLE_RATE).all(|_| decoder.push(0.0).is_none()));
+    }
+
+    #[test]
+    fn decode_signal() {
+        let packets = decode(&signal(&[b" 01234J\n\r", b"S01234J\n\r"], false));
+        assert_eq!(packets.len(), 2);
+        assert_eq!(packets[0].state, StackmatState::Running);
+        assert_eq!(packets[1].state, StackmatState::Stopped);
+        assert_eq!(packets[1].time, Duration::from_millis(12340));
+    }
+
+    #[test]
+    fn decode_inverted_signal() {
+        let packets = decode(&signal(&[b"A00000@\n\r", b" 012345O\n\r"], true));
+        assert_eq!(packets.len(), 2);
+        assert_eq!(packets[0].state, StackmatState::Ready);
+        assert_eq!(packets[1].time, Duration::from_millis(12345));
+    }
+
+    #[test]
+    fn decode_corrupted_signal() {
+        // a packet with a bad checksum is dropped, the next one still read
+        let packets = decode(&signal(&[b"S01234K\n\r", b"S01234J\n\r"], false));
+        assert_eq!(packets.len(), 1);
+        assert_eq!(packets[0].time, Duration::from_millis(12340));
+    }
+}
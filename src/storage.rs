@@ -0,0 +1,48 @@
+// Where the files kept between runs of the app are stored: the working directory of native
+// builds, and the browser's local storage for the web app, as it has no files to write.
+
+use std::io;
+
+/// Reads a whole file, like [`std::fs::read_to_string`].
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn read_to_string(path: &str) -> io::Result<String> {
+    std::fs::read_to_string(path)
+}
+
+/// Writes a whole file, like [`std::fs::write`].
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn write(path: &str, text: &str) -> io::Result<()> {
+    std::fs::write(path, text)
+}
+
+// The files of other apps on the same site don't get mixed up with ours
+#[cfg(target_arch = "wasm32")]
+const KEY_PREFIX: &str = "bevy_rubiks/";
+
+#[cfg(target_arch = "wasm32")]
+fn local_storage() -> io::Result<web_sys::Storage> {
+    web_sys::window()
+        .and_then(|window| window.local_storage().ok().flatten())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::Unsupported, "no local storage"))
+}
+
+#[cfg(target_arch = "wasm32")]
+fn js_error(err: wasm_bindgen::JsValue) -> io::Error {
+    io::Error::other(format!("{err:?}"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn read_to_string(path: &str) -> io::Result<String> {
+    local_storage()?
+        .get_item(&format!("{KEY_PREFIX}{path}"))
+        .map_err(js_error)?
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "not in the local storage"))
+}
+
+// Fails once the site's quota is used up
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn write(path: &str, text: &str) -> io::Result<()> {
+    local_storage()?
+        .set_item(&format!("{KEY_PREFIX}{path}"), text)
+        .map_err(js_error)
+}
@@ -0,0 +1,215 @@
+use crate::{
+    simulator::{CurrentCube, Played},
+    solved::SolveClock,
+    GameState, Puzzle,
+};
+use bevy::prelude::*;
+use cubesim::{format_scramble, prelude::*, ScrambleFormat};
+
+// a pure green, keyed out by streaming software such as OBS
+const CHROMA_COLOR: Color = Color::rgb(0.0, 1.0, 0.0);
+// the number of moves shown below the scramble
+const LAST_MOVES: usize = 12;
+// the scramble in groups of five moves, the long scrambles of big cubes on a few lines
+const SCRAMBLE_FORMAT: ScrambleFormat = ScrambleFormat {
+    group: Some(5),
+    width: Some(60),
+    numbered: false,
+};
+
+pub struct StreamPlugin;
+
+impl Plugin for StreamPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<StreamMode>()
+            .register_type::<StreamMode>()
+            .add_systems(Startup, overlay_setup)
+            .add_systems(
+                Update,
+                (
+                    apply_background.run_if(resource_changed::<StreamMode>()),
+                    toggle_overlay,
+                    show_overlay
+                        .run_if(in_state(GameState::Playing))
+                        .run_if(resource_equals(Puzzle::Cube))
+                        .run_if(not(resource_equals(StreamMode::Off))),
+                ),
+            );
+    }
+}
+
+/// A mode for streaming practice sessions, showing a large timer, the scramble and the last
+/// moves over a background meant to be keyed out. Chosen from the display settings, or with
+/// the `--stream` command line flag.
+#[derive(Resource, Component, Reflect, Clone, Copy, Eq, PartialEq, Debug, Default)]
+#[reflect(Resource)]
+pub enum StreamMode {
+    #[default]
+    Off,
+    /// A green background, for a chroma key.
+    Chroma,
+    /// A transparent window, where the compositor supports it. Only available from the
+    /// command line, as the window has to be created transparent.
+    Transparent,
+}
+
+impl StreamMode {
+    // the transparent mode is left out, the window being created by then
+    pub(crate) const ALL: [StreamMode; 2] = [StreamMode::Off, StreamMode::Chroma];
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            StreamMode::Off => "Off",
+            StreamMode::Chroma => "Chroma",
+            StreamMode::Transparent => "Transparent",
+        }
+    }
+
+    /// The mode asked for on the command line, as `--stream` for a chroma key background, or
+    /// `--stream=chroma` and `--stream=transparent`.
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Result<StreamMode, String> {
+        let mut mode = StreamMode::Off;
+        for arg in args {
+            mode = match arg.as_str() {
+                "--stream" | "--stream=chroma" => StreamMode::Chroma,
+                "--stream=transparent" => StreamMode::Transparent,
+                _ if arg.starts_with("--stream=") => {
+                    return Err(format!("unknown stream mode in '{arg}'"));
+                }
+                _ => continue,
+            };
+        }
+        Ok(mode)
+    }
+}
+
+#[derive(Component)]
+struct StreamOverlay;
+
+#[derive(Component, Clone, Copy)]
+enum OverlayText {
+    Timer,
+    Scramble,
+    LastMoves,
+}
+
+fn overlay_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    let text_style = |font_size| TextStyle {
+        font: font.clone(),
+        font_size,
+        color: Color::WHITE,
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(10.0),
+                    left: Val::Px(0.0),
+                    right: Val::Px(0.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+            // over the game's panels, below the menus
+            ZIndex::Global(10),
+            StreamOverlay,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(String::new(), text_style(96.0)),
+                OverlayText::Timer,
+            ));
+            parent.spawn((
+                TextBundle::from_section(String::new(), text_style(24.0))
+                    .with_text_alignment(TextAlignment::Center)
+                    // long scrambles of big cubes wrap over a few lines
+                    .with_style(Style {
+                        max_width: Val::Percent(60.0),
+                        ..default()
+                    }),
+                OverlayText::Scramble,
+            ));
+            parent.spawn((
+                TextBundle::from_section(String::new(), text_style(36.0)),
+                OverlayText::LastMoves,
+            ));
+        });
+}
+
+// Switches to the background of the mode, going back to the app's own once it's turned off
+fn apply_background(
+    stream_mode: Res<StreamMode>,
+    mut clear_color: ResMut<ClearColor>,
+    mut plain_color: Local<Option<Color>>,
+) {
+    let plain_color = *plain_color.get_or_insert(clear_color.0);
+    clear_color.0 = match *stream_mode {
+        StreamMode::Off => plain_color,
+        StreamMode::Chroma => CHROMA_COLOR,
+        StreamMode::Transparent => Color::NONE,
+    };
+}
+
+fn moves_string(moves: &[Move]) -> String {
+    moves
+        .iter()
+        .map(|m| m.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// The overlay is only shown over the cube, while the game is played
+fn toggle_overlay(
+    stream_mode: Res<StreamMode>,
+    game_state: Res<State<GameState>>,
+    puzzle: Res<Puzzle>,
+    mut q_overlay: Query<&mut Visibility, With<StreamOverlay>>,
+) {
+    let shown = *stream_mode != StreamMode::Off
+        && *game_state.get() == GameState::Playing
+        && *puzzle == Puzzle::Cube;
+    for mut visibility in &mut q_overlay {
+        visibility.set_if_neq(if shown {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        });
+    }
+}
+
+fn show_overlay(
+    time: Res<Time>,
+    clock: Res<SolveClock>,
+    current_cube: Played<CurrentCube>,
+    // the scramble stays shown once the cube is solved, until the next one
+    mut last_scramble: Local<String>,
+    mut q_text: Query<(&mut Text, &OverlayText)>,
+) {
+    if let Some(scramble) = current_cube.scramble() {
+        *last_scramble = format_scramble(scramble, SCRAMBLE_FORMAT);
+    }
+
+    for (mut text, overlay_text) in &mut q_text {
+        let value = match overlay_text {
+            OverlayText::Timer => {
+                let solving = current_cube.scrambled_at().is_some();
+                let solve_time = clock.solve_time(time.elapsed(), solving);
+                format!("{:.2}", solve_time.as_secs_f32())
+            }
+            OverlayText::Scramble => last_scramble.clone(),
+            OverlayText::LastMoves => {
+                let moves = current_cube.moves();
+                moves_string(&moves[moves.len().saturating_sub(LAST_MOVES)..])
+            }
+        };
+        if text.sections[0].value != value {
+            text.sections[0].value = value;
+        }
+    }
+}
@@ -0,0 +1,117 @@
+// The ready-made solver tables of the `bundled-tables` and `download-tables` features, read
+// before the solvers would build them. The tables are written by cubesim's `tables` binary
+// and compressed:
+//
+//     cargo run -p cubesim --release --bin tables | gzip -9n > assets/tables/solver-tables.bin.gz
+//
+// The `download-tables` feature reads the same file from wherever it's put online instead:
+//
+//     SOLVER_TABLES_URL=https://example.com/tables.bin.gz cargo run --features download-tables
+
+#[cfg(any(feature = "bundled-tables", feature = "download-tables"))]
+use bevy::log::{info, warn};
+#[cfg(any(feature = "bundled-tables", feature = "download-tables"))]
+use cubesim::read_tables;
+#[cfg(any(feature = "bundled-tables", feature = "download-tables"))]
+use flate2::read::GzDecoder;
+#[cfg(any(feature = "bundled-tables", feature = "download-tables"))]
+use std::io::Read;
+
+#[cfg(feature = "bundled-tables")]
+const BUNDLED_TABLES: &[u8] = include_bytes!("../assets/tables/solver-tables.bin.gz");
+
+// Where the tables are published, given when building, as no release hosts them, and the SHA-256
+// of the tables written by this version of cubesim, to be updated with them
+#[cfg(all(feature = "download-tables", not(target_arch = "wasm32")))]
+const TABLES_URL: &str = env!(
+    "SOLVER_TABLES_URL",
+    "the `download-tables` feature needs SOLVER_TABLES_URL, the URL of the tables generated \
+     as explained in src/tables.rs"
+);
+#[cfg(all(feature = "download-tables", not(target_arch = "wasm32")))]
+const TABLES_SHA256: &str = "149bf06a7d2312f34f5d5db131af2191ffd42efc5b338372a452e8cc3ec30e9e";
+// Where the tables downloaded are kept for the next runs
+#[cfg(all(feature = "download-tables", not(target_arch = "wasm32")))]
+const DOWNLOADED_PATH: &str = "solver-tables.bin.gz";
+// Far more than the tables, in case the download is something else
+#[cfg(all(feature = "download-tables", not(target_arch = "wasm32")))]
+const MAX_DOWNLOAD: u64 = 16 * 1024 * 1024;
+
+/// Reads the ready-made solver tables bundled with the app, or else downloaded, if the
+/// features are enabled. The solvers build any table not read.
+pub(crate) fn read_ready_made_tables() {
+    #[cfg(feature = "bundled-tables")]
+    let read_bundled = read("bundled", BUNDLED_TABLES);
+    #[cfg(not(feature = "bundled-tables"))]
+    let read_bundled = false;
+
+    if !read_bundled {
+        #[cfg(all(feature = "download-tables", not(target_arch = "wasm32")))]
+        match downloaded() {
+            Ok(gz) => {
+                read("downloaded", &gz);
+            }
+            Err(err) => warn!("no solver tables downloaded: {err}"),
+        }
+    }
+}
+
+#[cfg(any(feature = "bundled-tables", feature = "download-tables"))]
+fn read(source: &str, gz: &[u8]) -> bool {
+    let mut bytes = Vec::new();
+    let read = GzDecoder::new(gz)
+        .read_to_end(&mut bytes)
+        .map_err(|err| err.to_string())
+        .and_then(|_| read_tables(&bytes));
+    match read {
+        Ok(count) => {
+            info!("read {count} {source} solver tables");
+            true
+        }
+        Err(err) => {
+            warn!("invalid {source} solver tables: {err}");
+            false
+        }
+    }
+}
+
+// The tables downloaded on an earlier run, or else downloaded now and kept for the next runs
+#[cfg(all(feature = "download-tables", not(target_arch = "wasm32")))]
+fn downloaded() -> Result<Vec<u8>, String> {
+    match std::fs::read(DOWNLOADED_PATH) {
+        Ok(gz) if verify(&gz).is_ok() => return Ok(gz),
+        Ok(_) => warn!("{DOWNLOADED_PATH} isn't the tables expected, downloading them again"),
+        Err(_) => {}
+    }
+
+    info!("downloading the solver tables from {TABLES_URL}");
+    let response = ureq::get(TABLES_URL)
+        .call()
+        .map_err(|err| err.to_string())?;
+    let mut gz = Vec::new();
+    response
+        .into_reader()
+        .take(MAX_DOWNLOAD)
+        .read_to_end(&mut gz)
+        .map_err(|err| err.to_string())?;
+    verify(&gz)?;
+
+    if let Err(err) = std::fs::write(DOWNLOADED_PATH, &gz) {
+        warn!("couldn't keep the solver tables in {DOWNLOADED_PATH}: {err}");
+    }
+    Ok(gz)
+}
+
+#[cfg(all(feature = "download-tables", not(target_arch = "wasm32")))]
+fn verify(gz: &[u8]) -> Result<(), String> {
+    use sha2::{Digest, Sha256};
+
+    let checksum: String = Sha256::digest(gz)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    if checksum != TABLES_SHA256 {
+        return Err(format!("SHA-256 {checksum} instead of {TABLES_SHA256}"));
+    }
+    Ok(())
+}
@@ -0,0 +1,294 @@
+use crate::{
+    i18n::{Localized, Translations},
+    simulator::{CurrentCube, FaceColors, Played, StickerPlace},
+    theme::{Theme, ThemeColor},
+    CubeSizeSetting, GameState, Puzzle,
+};
+use bevy::prelude::*;
+use cubesim::{diff, parse_scramble, prelude::*, sticker_index, FaceletCube};
+use std::collections::HashSet;
+
+const HIGHLIGHT_KEY: KeyCode = KeyCode::H;
+// The glow of the stickers not matching the target
+const MISTAKE_GLOW: Color = Color::rgb(0.6, 0.0, 0.0);
+// The width of the target's net, whatever the size of the cube
+const NET_WIDTH: f32 = 160.0;
+
+pub struct TargetPlugin;
+
+impl Plugin for TargetPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TargetPattern>()
+            .init_resource::<MistakeHighlight>()
+            .add_systems(
+                OnEnter(GameState::Playing),
+                target_ui.run_if(resource_equals(Puzzle::Cube)),
+            )
+            .add_systems(
+                Update,
+                (
+                    target_input,
+                    show_target,
+                    show_target_text,
+                    highlight_mistakes,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(resource_equals(Puzzle::Cube)),
+            )
+            .add_systems(OnExit(GameState::Playing), clean_up);
+    }
+}
+
+/// The state the cube is solved into, shown next to it. Clicking the target picks the next
+/// pattern.
+#[derive(Resource, Clone, Copy, Debug, Default, Eq, PartialEq)]
+enum TargetPattern {
+    #[default]
+    Solved,
+    Checkerboard,
+    SixSpots,
+    CubeInCube,
+}
+
+impl TargetPattern {
+    const ALL: [TargetPattern; 4] = [
+        TargetPattern::Solved,
+        TargetPattern::Checkerboard,
+        TargetPattern::SixSpots,
+        TargetPattern::CubeInCube,
+    ];
+
+    // Only turning the outer layers, to make a pattern of any size of cube
+    fn moves(self) -> &'static str {
+        match self {
+            TargetPattern::Solved => "",
+            TargetPattern::Checkerboard => "R2 L2 U2 D2 F2 B2",
+            TargetPattern::SixSpots => "U D' R L' F B' U D'",
+            TargetPattern::CubeInCube => "F L F U' R U F2 L2 U' L' B D' B' L2 U",
+        }
+    }
+
+    // The id of the pattern's name, in the translations
+    fn name_id(self) -> &'static str {
+        match self {
+            TargetPattern::Solved => "target-solved",
+            TargetPattern::Checkerboard => "target-checkerboard",
+            TargetPattern::SixSpots => "target-six-spots",
+            TargetPattern::CubeInCube => "target-cube-in-cube",
+        }
+    }
+
+    fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|p| *p == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    fn cube(self, size: usize) -> FaceletCube {
+        FaceletCube::new(size as CubeSize).apply_moves(&parse_scramble(self.moves().to_string()))
+    }
+}
+
+/// Whether the stickers of the cube not matching the target glow, toggled with `H`.
+#[derive(Resource, Clone, Copy, Debug, Default, Eq, PartialEq)]
+struct MistakeHighlight(bool);
+
+#[derive(Component)]
+struct TargetUiRoot;
+
+// A sticker of the target's net, by its index in the state of the cube
+#[derive(Component)]
+struct TargetSticker(usize);
+
+#[derive(Component)]
+struct TargetText;
+
+// Where each face sits in the unfolded net, in whole faces, like the net of `cubesim`
+pub(crate) fn net_position(face: Face) -> (usize, usize) {
+    match face {
+        Face::U => (1, 0),
+        Face::L => (0, 1),
+        Face::F => (1, 1),
+        Face::R => (2, 1),
+        Face::B => (3, 1),
+        Face::D => (1, 2),
+        Face::X => unreachable!(),
+    }
+}
+
+fn target_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    theme: Res<Theme>,
+    cube_size: Res<CubeSizeSetting>,
+) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    let size = cube_size.0;
+    let face_width = NET_WIDTH / 4.0;
+    let sticker_width = face_width / size as f32;
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(50.0),
+                    right: Val::Px(220.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    padding: UiRect::all(Val::Px(5.0)),
+                    ..default()
+                },
+                ..default()
+            },
+            ThemeColor::Panel,
+            TargetUiRoot,
+            Interaction::None,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        width: Val::Px(NET_WIDTH),
+                        height: Val::Px(face_width * 3.0),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for face in [Face::U, Face::R, Face::F, Face::D, Face::L, Face::B] {
+                        let (net_x, net_y) = net_position(face);
+                        for n in 0..size * size {
+                            let (row, col) = (n / size, n % size);
+                            let index = sticker_index(size as CubeSize, face, n as CubeSize + 1);
+                            parent.spawn((
+                                NodeBundle {
+                                    style: Style {
+                                        position_type: PositionType::Absolute,
+                                        left: Val::Px(
+                                            net_x as f32 * face_width + col as f32 * sticker_width,
+                                        ),
+                                        top: Val::Px(
+                                            net_y as f32 * face_width + row as f32 * sticker_width,
+                                        ),
+                                        width: Val::Px(sticker_width - 1.0),
+                                        height: Val::Px(sticker_width - 1.0),
+                                        ..default()
+                                    },
+                                    ..default()
+                                },
+                                TargetSticker(index as usize),
+                            ));
+                        }
+                    }
+                });
+            parent.spawn((
+                TextBundle::from_section(
+                    String::new(),
+                    TextStyle {
+                        font,
+                        font_size: 18.0,
+                        color: theme.text(),
+                    },
+                ),
+                TargetText,
+            ));
+        });
+}
+
+fn target_input(
+    keyboard_input: Res<Input<KeyCode>>,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<TargetUiRoot>)>,
+    mut pattern: ResMut<TargetPattern>,
+    mut highlight: ResMut<MistakeHighlight>,
+) {
+    if keyboard_input.just_pressed(HIGHLIGHT_KEY) {
+        highlight.0 = !highlight.0;
+    }
+    if interaction_query.iter().any(|i| *i == Interaction::Pressed) {
+        *pattern = pattern.next();
+    }
+}
+
+fn show_target(
+    pattern: Res<TargetPattern>,
+    face_colors: Res<FaceColors>,
+    cube_size: Res<CubeSizeSetting>,
+    mut q_stickers: Query<(Ref<TargetSticker>, &mut BackgroundColor)>,
+) {
+    let changed = pattern.is_changed() || face_colors.is_changed();
+    if changed || q_stickers.iter().any(|(sticker, _)| sticker.is_added()) {
+        let state = pattern.cube(cube_size.0).state();
+        for (sticker, mut background) in &mut q_stickers {
+            if let Some(&face) = state.get(sticker.0) {
+                background.0 = face_colors.color(face);
+            }
+        }
+    }
+}
+
+fn show_target_text(
+    pattern: Res<TargetPattern>,
+    highlight: Res<MistakeHighlight>,
+    translations: Translations,
+    mut q_text: Query<&mut Text, With<TargetText>>,
+) {
+    if !pattern.is_changed() && !highlight.is_changed() {
+        return;
+    }
+
+    let mistakes = if highlight.0 {
+        "target-mistakes-shown"
+    } else {
+        "target-mistakes-hidden"
+    };
+    let message = translations.text(
+        Localized::new("target-status")
+            .with("pattern", translations.get(pattern.name_id()))
+            .with("mistakes", translations.get(mistakes))
+            .with("key", format!("{HIGHLIGHT_KEY:?}")),
+    );
+    for mut text in &mut q_text {
+        text.sections[0].value = message.clone();
+    }
+}
+
+// Lights up the stickers of the cube which aren't where the target has them
+fn highlight_mistakes(
+    pattern: Res<TargetPattern>,
+    highlight: Res<MistakeHighlight>,
+    current_cube: Played<CurrentCube>,
+    q_stickers: Query<(Ref<StickerPlace>, &Handle<StandardMaterial>)>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let moved = q_stickers.iter().any(|(place, _)| place.is_changed());
+    if !moved && !pattern.is_changed() && !highlight.is_changed() {
+        return;
+    }
+
+    let target = pattern.cube(current_cube.cube_size());
+    let mistakes: HashSet<usize> = if highlight.0 {
+        diff(&target, &**current_cube)
+            .into_iter()
+            .map(|sticker| sticker.index)
+            .collect()
+    } else {
+        HashSet::new()
+    };
+    for (place, material) in &q_stickers {
+        let Some(material) = materials.get_mut(material) else {
+            continue;
+        };
+        material.emissive = if mistakes.contains(&place.0) {
+            MISTAKE_GLOW
+        } else {
+            Color::BLACK
+        };
+    }
+}
+
+fn clean_up(mut commands: Commands, q_ui: Query<Entity, With<TargetUiRoot>>) {
+    for entity in &q_ui {
+        commands.entity(entity).despawn_recursive();
+    }
+}
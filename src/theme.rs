@@ -0,0 +1,213 @@
+use crate::settings::Settings;
+use bevy::prelude::*;
+use std::{fmt, str::FromStr};
+
+pub struct ThemePlugin;
+
+impl Plugin for ThemePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Theme>()
+            .init_resource::<Accent>()
+            .add_systems(Startup, load_theme)
+            .add_systems(Update, (save_theme, apply_theme).chain());
+    }
+}
+
+/// The colors of the UI's panels, buttons and texts, chosen in the settings.
+#[derive(Resource, Component, Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) enum Theme {
+    /// Light texts on dark grey panels.
+    #[default]
+    Dark,
+    /// Dark texts on light grey panels.
+    Light,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 2] = [Theme::Dark, Theme::Light];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Theme::Dark => "Dark",
+            Theme::Light => "Light",
+        }
+    }
+
+    pub fn text(self) -> Color {
+        match self {
+            Theme::Dark => Color::rgb(0.9, 0.9, 0.9),
+            Theme::Light => Color::rgb(0.1, 0.1, 0.1),
+        }
+    }
+
+    pub fn panel(self) -> Color {
+        match self {
+            Theme::Dark => Color::rgb(0.15, 0.15, 0.15),
+            Theme::Light => Color::rgb(0.85, 0.85, 0.85),
+        }
+    }
+
+    pub fn border(self) -> Color {
+        match self {
+            Theme::Dark => Color::rgb(0.65, 0.65, 0.65),
+            Theme::Light => Color::rgb(0.45, 0.45, 0.45),
+        }
+    }
+
+    /// The color of a button, the selected option of a setting standing out in green.
+    pub fn button(self, interaction: Interaction, selected: bool) -> Color {
+        match (self, interaction, selected) {
+            (Theme::Dark, Interaction::Pressed, _) | (Theme::Dark, Interaction::None, true) => {
+                Color::rgb(0.35, 0.75, 0.35)
+            }
+            (Theme::Dark, Interaction::Hovered, true) => Color::rgb(0.25, 0.65, 0.25),
+            (Theme::Dark, Interaction::Hovered, false) => Color::rgb(0.25, 0.25, 0.25),
+            (Theme::Dark, Interaction::None, false) => Color::rgb(0.15, 0.15, 0.15),
+            (Theme::Light, Interaction::Pressed, _) | (Theme::Light, Interaction::None, true) => {
+                Color::rgb(0.45, 0.8, 0.45)
+            }
+            (Theme::Light, Interaction::Hovered, true) => Color::rgb(0.55, 0.85, 0.55),
+            (Theme::Light, Interaction::Hovered, false) => Color::rgb(0.7, 0.7, 0.7),
+            (Theme::Light, Interaction::None, false) => Color::rgb(0.8, 0.8, 0.8),
+        }
+    }
+}
+
+impl fmt::Display for Theme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name().to_lowercase())
+    }
+}
+
+impl FromStr for Theme {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Theme::ALL
+            .into_iter()
+            .find(|theme| theme.to_string() == s)
+            .ok_or_else(|| format!("unknown theme '{s}', try dark or light"))
+    }
+}
+
+/// The color of the menus' panels, one of the presets or any color of the settings, such as
+/// `accent = #3366cc`.
+#[derive(Resource, Component, Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) enum Accent {
+    #[default]
+    Crimson,
+    Blue,
+    Green,
+    Purple,
+    /// The red, green and blue of a color written in hex.
+    Custom([u8; 3]),
+}
+
+impl Accent {
+    pub const ALL: [Accent; 4] = [Accent::Crimson, Accent::Blue, Accent::Green, Accent::Purple];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Accent::Crimson => "Crimson",
+            Accent::Blue => "Blue",
+            Accent::Green => "Green",
+            Accent::Purple => "Purple",
+            Accent::Custom(_) => "Custom",
+        }
+    }
+
+    pub fn color(self) -> Color {
+        match self {
+            Accent::Crimson => Color::CRIMSON,
+            Accent::Blue => Color::rgb(0.2, 0.4, 0.8),
+            Accent::Green => Color::rgb(0.15, 0.55, 0.3),
+            Accent::Purple => Color::rgb(0.5, 0.25, 0.7),
+            Accent::Custom([r, g, b]) => Color::rgb_u8(r, g, b),
+        }
+    }
+}
+
+impl fmt::Display for Accent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Accent::Custom([r, g, b]) => write!(f, "#{r:02x}{g:02x}{b:02x}"),
+            preset => f.write_str(&preset.name().to_lowercase()),
+        }
+    }
+}
+
+impl FromStr for Accent {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(preset) = Accent::ALL.into_iter().find(|a| a.to_string() == s) {
+            return Ok(preset);
+        }
+
+        let invalid = || format!("'{s}' is neither a preset accent nor a color like #3366cc");
+        let hex = s
+            .strip_prefix('#')
+            .filter(|hex| hex.len() == 6)
+            .ok_or_else(invalid)?;
+        let channel = |i: usize| {
+            hex.get(i..i + 2)
+                .and_then(|channel| u8::from_str_radix(channel, 16).ok())
+                .ok_or_else(invalid)
+        };
+        Ok(Accent::Custom([channel(0)?, channel(2)?, channel(4)?]))
+    }
+}
+
+/// A node colored by the theme, the texts in it taking the theme's text color.
+#[derive(Component, Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum ThemeColor {
+    /// The panel of a menu screen.
+    Accent,
+    /// A panel of the game.
+    Panel,
+    /// The border around a panel of the game.
+    Border,
+}
+
+fn load_theme(settings: Res<Settings>, mut theme: ResMut<Theme>, mut accent: ResMut<Accent>) {
+    theme.set_if_neq(settings.theme);
+    accent.set_if_neq(settings.accent);
+}
+
+fn save_theme(theme: Res<Theme>, accent: Res<Accent>, mut settings: ResMut<Settings>) {
+    if theme.is_changed() && settings.theme != *theme {
+        settings.theme = *theme;
+    }
+    if accent.is_changed() && settings.accent != *accent {
+        settings.accent = *accent;
+    }
+}
+
+// Recolors the nodes as they're spawned, and all of them once another theme is chosen
+fn apply_theme(
+    theme: Res<Theme>,
+    accent: Res<Accent>,
+    mut q_nodes: Query<(Entity, Ref<ThemeColor>, &mut BackgroundColor), Without<Text>>,
+    q_children: Query<&Children>,
+    mut q_texts: Query<&mut Text>,
+) {
+    let changed = theme.is_changed() || accent.is_changed();
+    for (entity, theme_color, mut background) in &mut q_nodes {
+        if !changed && !theme_color.is_added() {
+            continue;
+        }
+
+        background.0 = match *theme_color {
+            ThemeColor::Accent => accent.color(),
+            ThemeColor::Panel => theme.panel(),
+            ThemeColor::Border => theme.border(),
+        };
+        for child in q_children.iter_descendants(entity) {
+            if let Ok(mut text) = q_texts.get_mut(child) {
+                for section in &mut text.sections {
+                    section.style.color = theme.text();
+                }
+            }
+        }
+    }
+}
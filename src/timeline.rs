@@ -0,0 +1,294 @@
+use crate::{
+    session::SolveSession,
+    simulator::{
+        CurrentCube, MoveQueue, Piece, PieceBody, PieceCoord, Played, PlayedCube, PlayedMut,
+        Rotating, StickerFace, StickerPlace,
+    },
+    spectate::Spectator,
+    theme::Theme,
+    GameState, Puzzle,
+};
+use bevy::{ecs::system::SystemParam, prelude::*, ui::RelativeCursorPosition};
+use bevy_cubesim::{sticker_transform, StickerPaint};
+use cubesim::{prelude::*, FaceletCube};
+
+const TRACK_HEIGHT: f32 = 10.0;
+
+pub struct TimelinePlugin;
+
+impl Plugin for TimelinePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Timeline>()
+            .add_systems(
+                OnEnter(GameState::Playing),
+                timeline_ui.run_if(resource_equals(Puzzle::Cube)),
+            )
+            .add_systems(
+                Update,
+                (follow_cube, scrub, show_timeline)
+                    .chain()
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(resource_equals(Puzzle::Cube)),
+            )
+            .add_systems(OnExit(GameState::Playing), clean_up);
+    }
+}
+
+/// The moves made on the cube this game, kept when seeking back through them so they can be
+/// seeked forward again, until another move is made from there.
+#[derive(Resource, Default, Debug)]
+struct Timeline {
+    moves: Vec<Move>,
+    // the number of the moves the cube is at
+    at: usize,
+}
+
+#[derive(Component)]
+struct TimelineUiRoot;
+
+// The bar of the moves, dragged along to seek the cube
+#[derive(Component)]
+struct TimelineTrack;
+
+// The move the cube is at, out of the moves of the timeline
+#[derive(Component)]
+struct TimelineText;
+
+fn timeline_ui(mut commands: Commands, asset_server: Res<AssetServer>, theme: Res<Theme>) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    // between the queued moves and the tutorial
+                    bottom: Val::Px(38.0),
+                    left: Val::Px(220.0),
+                    right: Val::Px(220.0),
+                    align_items: AlignItems::Center,
+                    column_gap: Val::Px(10.0),
+                    ..default()
+                },
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+            TimelineUiRoot,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                NodeBundle {
+                    style: Style {
+                        flex_grow: 1.0,
+                        height: Val::Px(TRACK_HEIGHT),
+                        ..default()
+                    },
+                    ..default()
+                },
+                Interaction::default(),
+                RelativeCursorPosition::default(),
+                TimelineTrack,
+            ));
+            parent.spawn((
+                TextBundle::from_section(
+                    String::new(),
+                    TextStyle {
+                        font,
+                        font_size: 16.0,
+                        color: theme.text(),
+                    },
+                ),
+                TimelineText,
+            ));
+        });
+}
+
+// Follows the moves made on the cube, dropping the moves seeked back from once another move
+// is made, or once the cube is replaced, as by loading a scene
+fn follow_cube(current_cube: Played<CurrentCube>, mut timeline: ResMut<Timeline>) {
+    if !current_cube.is_changed() {
+        return;
+    }
+
+    let moves = current_cube.moves();
+    if moves.len() == timeline.at && timeline.moves.starts_with(moves) {
+        return;
+    }
+    timeline.moves = moves.to_vec();
+    timeline.at = moves.len();
+}
+
+// The pieces of the played cube, put back in place for the state seeked to
+#[derive(SystemParam)]
+struct SeekedPieces<'w, 's> {
+    q_played: Query<'w, 's, &'static Children, With<PlayedCube>>,
+    q_piece: Query<
+        'w,
+        's,
+        (
+            &'static mut Transform,
+            &'static mut PieceCoord,
+            &'static Children,
+        ),
+        With<Piece>,
+    >,
+    q_body: Query<'w, 's, &'static Children, With<PieceBody>>,
+    q_sticker: Query<
+        'w,
+        's,
+        (
+            &'static mut StickerFace,
+            &'static mut StickerPlace,
+            &'static Handle<StandardMaterial>,
+        ),
+    >,
+    q_rotating: Query<'w, 's, (), With<Rotating>>,
+    paint: StickerPaint<'w>,
+}
+
+impl SeekedPieces<'_, '_> {
+    // Every piece goes back to its place on the solved cube, unturned, its stickers taking the
+    // colors of the state there, so the pieces needn't be turned back move by move
+    fn reset(&mut self, size: usize, state: &[Face]) {
+        let Ok(pieces) = self.q_played.get_single() else {
+            return;
+        };
+
+        let mut q_pieces = self.q_piece.iter_many_mut(pieces);
+        while let Some((mut transform, mut coord, children)) = q_pieces.fetch_next() {
+            for body in self.q_body.iter_many(children) {
+                let mut stickers = self.q_sticker.iter_many_mut(body);
+                while let Some((mut sticker, mut place, material)) = stickers.fetch_next() {
+                    let index = sticker.index as usize;
+                    let solved = sticker_transform(size, index);
+                    *coord = PieceCoord::from_translation(size, solved.translation);
+                    place.set_if_neq(StickerPlace(index));
+                    // masked stickers stay masked
+                    if sticker.face == state[index] || sticker.face == Face::X {
+                        continue;
+                    }
+                    sticker.face = state[index];
+                    self.paint.paint(material, state[index]);
+                }
+            }
+            *transform = Transform {
+                translation: coord.translation(size),
+                rotation: Quat::IDENTITY,
+                ..*transform
+            };
+        }
+    }
+}
+
+// Seeks the cube to the move under the cursor while the track is dragged, once the moves
+// queued are made
+fn scrub(
+    q_track: Query<(&Interaction, &RelativeCursorPosition), With<TimelineTrack>>,
+    mut timeline: ResMut<Timeline>,
+    mut current_cube: PlayedMut<CurrentCube>,
+    move_queue: Played<MoveQueue>,
+    mut session: PlayedMut<SolveSession>,
+    spectator: Option<Res<Spectator>>,
+    mut pieces: SeekedPieces,
+) {
+    // spectators follow the cube broadcast, and the pieces can only be reset between moves
+    let turning =
+        !move_queue.is_empty() || move_queue.in_flight().is_some() || !pieces.q_rotating.is_empty();
+    if spectator.is_some() || turning {
+        return;
+    }
+
+    for (interaction, cursor) in &q_track {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let Some(position) = cursor.normalized else {
+            continue;
+        };
+        let at = (position.x.clamp(0.0, 1.0) * timeline.moves.len() as f32).round() as usize;
+        if at == timeline.at {
+            continue;
+        }
+
+        // a solve seeked through is no longer made unaided
+        if current_cube.scrambled_at().is_some() {
+            session.mark_assisted();
+        }
+        timeline.at = at;
+        current_cube.set_moves(timeline.moves[..at].to_vec());
+        session.cut_solution(&current_cube, 0);
+
+        let size = current_cube.cube_size();
+        let state = FaceletCube::new(size as CubeSize)
+            .apply_moves(current_cube.moves())
+            .state();
+        pieces.reset(size, &state);
+    }
+}
+
+fn show_timeline(
+    mut commands: Commands,
+    timeline: Res<Timeline>,
+    theme: Res<Theme>,
+    mut q_root: Query<&mut Visibility, With<TimelineUiRoot>>,
+    q_track: Query<Entity, With<TimelineTrack>>,
+    mut q_text: Query<&mut Text, With<TimelineText>>,
+) {
+    if !timeline.is_changed() {
+        return;
+    }
+
+    for mut visibility in &mut q_root {
+        visibility.set_if_neq(if timeline.moves.is_empty() {
+            Visibility::Hidden
+        } else {
+            Visibility::Inherited
+        });
+    }
+    for mut text in &mut q_text {
+        text.sections[0].value = match timeline.at.checked_sub(1) {
+            Some(last) => format!(
+                "{}/{} {}",
+                timeline.at,
+                timeline.moves.len(),
+                timeline.moves[last]
+            ),
+            None => format!("0/{}", timeline.moves.len()),
+        };
+    }
+
+    // a segment for every move, those the cube is past standing out
+    let made = theme.button(Interaction::None, true);
+    let ahead = theme.border();
+    for track in &q_track {
+        commands
+            .entity(track)
+            .despawn_descendants()
+            .with_children(|parent| {
+                for index in 0..timeline.moves.len() {
+                    let color = if index < timeline.at { made } else { ahead };
+                    parent.spawn(NodeBundle {
+                        style: Style {
+                            flex_grow: 1.0,
+                            height: Val::Percent(100.0),
+                            margin: UiRect::horizontal(Val::Px(0.5)),
+                            ..default()
+                        },
+                        background_color: color.into(),
+                        ..default()
+                    });
+                }
+            });
+    }
+}
+
+fn clean_up(
+    mut commands: Commands,
+    mut timeline: ResMut<Timeline>,
+    q_ui: Query<Entity, With<TimelineUiRoot>>,
+) {
+    *timeline = Timeline::default();
+    for entity in &q_ui {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,511 @@
+use crate::{
+    history::{SolveHistory, SolveRecord},
+    i18n::{Localized, Translations},
+    menu::{MenuButtonAction, MenuState},
+    scramble::Scrambler,
+    solved::{PenaltyChanged, SolveMode},
+    stackmat::{StackmatPacket, StackmatState},
+    theme::{Accent, Theme, ThemeColor},
+    CubeSizeSetting,
+};
+use bevy::prelude::*;
+use cubesim::{average_of, prelude::*, Penalty, SolveResult};
+#[cfg(all(feature = "stackmat", not(target_arch = "wasm32")))]
+use std::{
+    sync::mpsc::{self, Sender},
+    thread,
+};
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::Receiver,
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+// Held and released to start the timer, then pressed to stop it, as USB timers do
+const TIMER_KEY: KeyCode = KeyCode::Space;
+const RECENT_RESULTS: usize = 5;
+const AVERAGES: [usize; 2] = [5, 12];
+
+pub struct TimerPlugin;
+
+impl Plugin for TimerPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(MenuState::Timer), timer_screen_setup)
+            .add_systems(
+                Update,
+                (
+                    next_scramble,
+                    timer_buttons,
+                    spacebar_timer,
+                    stackmat_timer,
+                    show_timer,
+                )
+                    .chain()
+                    .run_if(in_state(MenuState::Timer)),
+            )
+            .add_systems(OnExit(MenuState::Timer), clean_up);
+    }
+}
+
+/// Times solves of a real cube with the timers of competitions, started from the training menu:
+/// a Stackmat timer plugged into the audio input with the `stackmat` feature, on native builds,
+/// or a USB timer typing the spacebar, held and released to start and pressed to stop. The
+/// times are kept in the history as external solves, with their penalties.
+#[derive(Resource, Default)]
+struct ExternalTimer {
+    stackmat: Option<StackmatInput>,
+    // what the Stackmat timer last sent
+    stackmat_state: Option<StackmatState>,
+    // the spacebar held before starting, and when the timer started
+    holding: bool,
+    started_at: Option<Duration>,
+    time: Duration,
+    scramble: Option<String>,
+    results: Vec<SolveResult>,
+    message: Option<Localized>,
+}
+
+impl ExternalTimer {
+    fn record(&mut self, time: Duration, cube_size: usize, history: &mut SolveHistory) {
+        self.time = time;
+        self.results.push(SolveResult::new(time));
+        history.0.push(SolveRecord {
+            cube_size,
+            mode: SolveMode::External,
+            time,
+            moves: 0,
+            penalty: None,
+        });
+        self.scramble = None;
+    }
+
+    fn status(&self, translations: &Translations) -> String {
+        let source = match (&self.stackmat, self.stackmat_state) {
+            (Some(_), Some(state)) => {
+                Localized::new("stackmat-state").with("state", translations.get(state_id(state)))
+            }
+            (Some(_), None) => Localized::new("stackmat-waiting"),
+            (None, _) if self.started_at.is_some() => Localized::new("timer-press-to-stop"),
+            (None, _) if self.holding => Localized::new("timer-release-to-start"),
+            (None, _) => Localized::new("timer-hold-to-start"),
+        };
+        let source = translations.text(source);
+        let recent: Vec<String> = self
+            .results
+            .iter()
+            .enumerate()
+            .skip(self.results.len().saturating_sub(RECENT_RESULTS))
+            .map(|(i, result)| format!("{}. {result}", i + 1))
+            .collect();
+        let averages: Vec<String> = AVERAGES
+            .into_iter()
+            .filter(|&n| self.results.len() >= n)
+            .map(
+                |n| match average_of(&self.results[self.results.len() - n..]) {
+                    Some(average) => format!("ao{n} {:.2}", average.as_secs_f32()),
+                    None => format!("ao{n} DNF"),
+                },
+            )
+            .collect();
+        let message = self
+            .message
+            .clone()
+            .map(|message| translations.text(message));
+        format!(
+            "{source}\n{}\n{}\n{}",
+            recent.join("  "),
+            averages.join("  "),
+            message.unwrap_or_default()
+        )
+    }
+}
+
+// The id of the state's name, in the translations
+fn state_id(state: StackmatState) -> &'static str {
+    match state {
+        StackmatState::Reset => "stackmat-reset",
+        StackmatState::Ready => "stackmat-ready",
+        StackmatState::Running => "stackmat-running",
+        StackmatState::Stopped => "stackmat-stopped",
+        StackmatState::LeftHand => "stackmat-left-hand",
+        StackmatState::RightHand => "stackmat-right-hand",
+        StackmatState::BothHands => "stackmat-both-hands",
+    }
+}
+
+/// The packets decoded from the audio input by its own thread, which stops once this is
+/// dropped.
+#[cfg_attr(
+    not(all(feature = "stackmat", not(target_arch = "wasm32"))),
+    allow(dead_code)
+)]
+struct StackmatInput {
+    packets: Mutex<Receiver<Result<StackmatPacket, Localized>>>,
+    stop: Arc<AtomicBool>,
+}
+
+impl Drop for StackmatInput {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+#[cfg(all(feature = "stackmat", not(target_arch = "wasm32")))]
+fn open_stackmat() -> Result<StackmatInput, Localized> {
+    let (sender, receiver) = mpsc::channel();
+    let stop = Arc::new(AtomicBool::new(false));
+    let stopped = stop.clone();
+    thread::spawn(move || {
+        if let Err(err) = capture_packets(&sender, &stopped) {
+            let _ = sender.send(Err(err));
+        }
+    });
+    Ok(StackmatInput {
+        packets: Mutex::new(receiver),
+        stop,
+    })
+}
+
+#[cfg(all(feature = "stackmat", not(target_arch = "wasm32")))]
+fn capture_packets(
+    sender: &Sender<Result<StackmatPacket, Localized>>,
+    stop: &AtomicBool,
+) -> Result<(), Localized> {
+    use cpal::{
+        traits::{DeviceTrait, HostTrait, StreamTrait},
+        SampleFormat,
+    };
+
+    let unreadable = |err: String| Localized::new("stackmat-unreadable").with("error", err);
+    let device = cpal::default_host()
+        .default_input_device()
+        .ok_or_else(|| Localized::new("stackmat-no-input"))?;
+    let supported = device
+        .default_input_config()
+        .map_err(|err| unreadable(err.to_string()))?;
+    let config = supported.config();
+    let stream = match supported.sample_format() {
+        SampleFormat::F32 => input_stream::<f32>(&device, &config, sender.clone()),
+        SampleFormat::I16 => input_stream::<i16>(&device, &config, sender.clone()),
+        SampleFormat::U16 => input_stream::<u16>(&device, &config, sender.clone()),
+        format => {
+            return Err(Localized::new("stackmat-unsupported").with("format", format.to_string()))
+        }
+    }
+    .map_err(|err| unreadable(err.to_string()))?;
+    stream.play().map_err(|err| unreadable(err.to_string()))?;
+
+    // the stream is read by its own callbacks as long as it is kept
+    while !stop.load(Ordering::Relaxed) {
+        thread::sleep(Duration::from_millis(100));
+    }
+    Ok(())
+}
+
+#[cfg(all(feature = "stackmat", not(target_arch = "wasm32")))]
+fn input_stream<T>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    sender: Sender<Result<StackmatPacket, Localized>>,
+) -> Result<cpal::Stream, cpal::BuildStreamError>
+where
+    T: cpal::SizedSample,
+    f32: cpal::FromSample<T>,
+{
+    use cpal::traits::DeviceTrait;
+
+    let channels = usize::from(config.channels);
+    let mut decoder = crate::stackmat::StackmatDecoder::new(config.sample_rate.0);
+    device.build_input_stream(
+        config,
+        // the timers are mono, only the first channel is read
+        move |samples: &[T], _: &cpal::InputCallbackInfo| {
+            for &sample in samples.iter().step_by(channels) {
+                if let Some(packet) = decoder.push(sample.to_sample::<f32>()) {
+                    let _ = sender.send(Ok(packet));
+                }
+            }
+        },
+        |err| warn!("the audio input failed: {err}"),
+        None,
+    )
+}
+
+#[cfg(not(all(feature = "stackmat", not(target_arch = "wasm32"))))]
+fn open_stackmat() -> Result<StackmatInput, Localized> {
+    Err(Localized::new("stackmat-unavailable"))
+}
+
+#[derive(Component)]
+struct OnTimerScreen;
+
+#[derive(Component, Clone, Copy)]
+enum TimerButton {
+    Stackmat,
+    Penalty(Penalty),
+}
+
+// The scramble, the time in large digits, then the state of the timer and the results
+#[derive(Component)]
+struct TimerText;
+
+fn timer_screen_setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    theme: Res<Theme>,
+    accent: Res<Accent>,
+) {
+    commands.insert_resource(ExternalTimer::default());
+
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    let button_style = Style {
+        height: Val::Px(45.0),
+        margin: UiRect::all(Val::Px(5.0)),
+        padding: UiRect::horizontal(Val::Px(10.0)),
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        ..default()
+    };
+    let text_style = TextStyle {
+        font: font.clone(),
+        font_size: 30.0,
+        color: theme.text(),
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    margin: UiRect::all(Val::Auto),
+                    padding: UiRect::all(Val::Px(20.0)),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: accent.color().into(),
+                ..default()
+            },
+            OnTimerScreen,
+            ThemeColor::Accent,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    String::new(),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 50.0,
+                        color: theme.text(),
+                    },
+                ),
+                Localized::new("training-timer"),
+            ));
+
+            parent.spawn((
+                TextBundle::from_sections([
+                    TextSection::new(String::new(), text_style.clone()),
+                    TextSection::new(
+                        String::new(),
+                        TextStyle {
+                            font: font.clone(),
+                            font_size: 120.0,
+                            color: theme.text(),
+                        },
+                    ),
+                    TextSection::new(String::new(), text_style.clone()),
+                ])
+                .with_text_alignment(TextAlignment::Center),
+                TimerText,
+            ));
+
+            parent.spawn(NodeBundle::default()).with_children(|parent| {
+                for (button, text) in [
+                    (TimerButton::Stackmat, "Stackmat"),
+                    (TimerButton::Penalty(Penalty::PlusTwo), "+2"),
+                    (TimerButton::Penalty(Penalty::Dnf), "DNF"),
+                ] {
+                    parent
+                        .spawn((
+                            ButtonBundle {
+                                style: button_style.clone(),
+                                background_color: theme.button(Interaction::None, false).into(),
+                                ..default()
+                            },
+                            button,
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn(TextBundle::from_section(text, text_style.clone()));
+                        });
+                }
+                parent
+                    .spawn((
+                        ButtonBundle {
+                            style: button_style.clone(),
+                            background_color: theme.button(Interaction::None, false).into(),
+                            ..default()
+                        },
+                        MenuButtonAction::BackToTraining,
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn((
+                            TextBundle::from_section(String::new(), text_style.clone()),
+                            Localized::new("menu-back"),
+                        ));
+                    });
+            });
+        });
+}
+
+// A new scramble of the cube size of the settings once the last one is timed
+fn next_scramble(
+    cube_size: Res<CubeSizeSetting>,
+    mut scrambler: Scrambler,
+    mut timer: ResMut<ExternalTimer>,
+) {
+    if timer.scramble.is_none() {
+        let scramble = scrambler.scramble(cube_size.0 as CubeSize);
+        timer.scramble = Some(
+            scramble
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(" "),
+        );
+    }
+}
+
+// The Stackmat button plugs the timer in or out, and the penalties go to the last result
+fn timer_buttons(
+    q_buttons: Query<(&Interaction, &TimerButton), Changed<Interaction>>,
+    mut timer: ResMut<ExternalTimer>,
+    mut penalty_events: EventWriter<PenaltyChanged>,
+) {
+    for (interaction, button) in &q_buttons {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match button {
+            TimerButton::Stackmat if timer.stackmat.is_some() => {
+                timer.stackmat = None;
+                timer.stackmat_state = None;
+                timer.message = None;
+            }
+            TimerButton::Stackmat => match open_stackmat() {
+                Ok(input) => {
+                    timer.stackmat = Some(input);
+                    timer.message = None;
+                }
+                Err(err) => timer.message = Some(err),
+            },
+            TimerButton::Penalty(penalty) => {
+                if let Some(result) = timer.results.last_mut() {
+                    result.penalty = (result.penalty != Some(*penalty)).then_some(*penalty);
+                    penalty_events.send(PenaltyChanged(result.penalty));
+                }
+            }
+        }
+    }
+}
+
+fn spacebar_timer(
+    time: Res<Time>,
+    keyboard_input: Res<Input<KeyCode>>,
+    cube_size: Res<CubeSizeSetting>,
+    mut history: ResMut<SolveHistory>,
+    mut timer: ResMut<ExternalTimer>,
+) {
+    if timer.stackmat.is_some() {
+        return;
+    }
+
+    let now = time.elapsed();
+    match timer.started_at {
+        Some(started_at) => {
+            timer.time = now.saturating_sub(started_at);
+            if keyboard_input.just_pressed(TIMER_KEY) {
+                timer.started_at = None;
+                let time = timer.time;
+                timer.record(time, cube_size.0, &mut history);
+            }
+        }
+        None if keyboard_input.just_pressed(TIMER_KEY) => {
+            timer.holding = true;
+            timer.time = Duration::ZERO;
+        }
+        None if timer.holding && keyboard_input.just_released(TIMER_KEY) => {
+            timer.holding = false;
+            timer.started_at = Some(now);
+        }
+        None => {}
+    }
+}
+
+// The time shown follows the timer's display, a stopped time being recorded once
+fn stackmat_timer(
+    cube_size: Res<CubeSizeSetting>,
+    mut history: ResMut<SolveHistory>,
+    mut timer: ResMut<ExternalTimer>,
+) {
+    let Some(input) = &timer.stackmat else {
+        return;
+    };
+    let received: Vec<_> = match input.packets.lock() {
+        Ok(packets) => packets.try_iter().collect(),
+        Err(_) => Vec::new(),
+    };
+
+    for packet in received {
+        match packet {
+            Ok(packet) => {
+                let stopped = packet.state == StackmatState::Stopped
+                    && timer.stackmat_state != Some(StackmatState::Stopped)
+                    && timer.stackmat_state.is_some()
+                    && !packet.time.is_zero();
+                timer.stackmat_state = Some(packet.state);
+                timer.time = packet.time;
+                if stopped {
+                    timer.record(packet.time, cube_size.0, &mut history);
+                }
+            }
+            Err(err) => {
+                timer.stackmat = None;
+                timer.stackmat_state = None;
+                timer.message = Some(err);
+                return;
+            }
+        }
+    }
+}
+
+fn show_timer(
+    timer: Res<ExternalTimer>,
+    translations: Translations,
+    mut q_text: Query<&mut Text, With<TimerText>>,
+) {
+    if !timer.is_changed() {
+        return;
+    }
+    let time = match timer.results.last() {
+        // the penalty of the result just recorded
+        Some(result) if timer.started_at.is_none() && result.time == timer.time => {
+            result.to_string()
+        }
+        _ => format!("{:.2}", timer.time.as_secs_f32()),
+    };
+    for mut text in &mut q_text {
+        text.sections[0].value = timer.scramble.clone().unwrap_or_default();
+        text.sections[1].value = format!("\n{time}\n");
+        text.sections[2].value = timer.status(&translations);
+    }
+}
+
+// Leaving the screen stops reading the audio input
+fn clean_up(mut commands: Commands, q_screen: Query<Entity, With<OnTimerScreen>>) {
+    commands.remove_resource::<ExternalTimer>();
+    for entity in &q_screen {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,185 @@
+use crate::{i18n::Localized, player::CAMERA_POSITION, GameState};
+use bevy::prelude::*;
+
+// the time taken by the camera to move between two views
+const TRANSITION_SECONDS: f32 = 0.6;
+
+pub struct ViewPlugin;
+
+impl Plugin for ViewPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Playing), view_ui)
+            .add_systems(
+                Update,
+                (view_input, move_camera)
+                    .chain()
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(OnExit(GameState::Playing), clean_up);
+    }
+}
+
+/// A canonical position of the camera, looking at the puzzle from a fixed direction.
+#[derive(Component, Clone, Copy, Debug, Eq, PartialEq)]
+enum CameraView {
+    /// Showing the front, up and right faces.
+    Front,
+    /// Showing the back, up and left faces.
+    Back,
+    /// Looking down on the up face, with the front face at the bottom of the screen.
+    Top,
+    /// Showing the left, up and front faces.
+    Left,
+}
+
+impl CameraView {
+    const ALL: [CameraView; 4] = [
+        CameraView::Front,
+        CameraView::Back,
+        CameraView::Top,
+        CameraView::Left,
+    ];
+
+    // The id of the view's name, with its key, in the translations
+    fn name_id(self) -> &'static str {
+        match self {
+            CameraView::Front => "view-front",
+            CameraView::Back => "view-back",
+            CameraView::Top => "view-top",
+            CameraView::Left => "view-left",
+        }
+    }
+
+    fn key(self) -> KeyCode {
+        match self {
+            CameraView::Front => KeyCode::Numpad0,
+            CameraView::Back => KeyCode::Numpad1,
+            CameraView::Top => KeyCode::Numpad2,
+            CameraView::Left => KeyCode::Numpad3,
+        }
+    }
+
+    // The camera's orientation, looking at the center of the puzzle
+    fn rotation(self) -> Quat {
+        let (direction, up) = match self {
+            CameraView::Front => (CAMERA_POSITION, Vec3::Y),
+            CameraView::Back => (CAMERA_POSITION * Vec3::new(-1.0, 1.0, -1.0), Vec3::Y),
+            CameraView::Top => (Vec3::Y, Vec3::NEG_Z),
+            CameraView::Left => (CAMERA_POSITION * Vec3::new(-1.0, 1.0, 1.0), Vec3::Y),
+        };
+        Transform::from_translation(direction)
+            .looking_at(Vec3::ZERO, up)
+            .rotation
+    }
+}
+
+// Turns the camera around the center of the puzzle, keeping its distance
+#[derive(Component)]
+struct CameraTransition {
+    from: Quat,
+    to: Quat,
+    timer: Timer,
+}
+
+#[derive(Component)]
+struct ViewUiRoot;
+
+fn view_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(10.0),
+                right: Val::Px(220.0),
+                ..default()
+            },
+            ..default()
+        })
+        .insert(ViewUiRoot)
+        .with_children(|parent| {
+            for view in CameraView::ALL {
+                parent
+                    .spawn(
+                        TextBundle::from_section(
+                            String::new(),
+                            TextStyle {
+                                font: font.clone(),
+                                font_size: 20.0,
+                                color: Color::WHITE,
+                            },
+                        )
+                        .with_style(Style {
+                            margin: UiRect::horizontal(Val::Px(8.0)),
+                            ..default()
+                        }),
+                    )
+                    .insert(view)
+                    .insert(Localized::new(view.name_id()).with("key", format!("{:?}", view.key())))
+                    .insert(Interaction::None);
+            }
+        });
+}
+
+fn view_input(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    interaction_query: Query<(&Interaction, &CameraView), Changed<Interaction>>,
+    q_camera: Query<(Entity, &Transform), With<Camera3d>>,
+) {
+    let pressed = interaction_query
+        .iter()
+        .filter(|(interaction, _)| **interaction == Interaction::Pressed)
+        .map(|(_, view)| *view);
+    let Some(view) = CameraView::ALL
+        .into_iter()
+        .filter(|view| keyboard_input.just_pressed(view.key()))
+        .chain(pressed)
+        .last()
+    else {
+        return;
+    };
+
+    for (entity, transform) in &q_camera {
+        commands.entity(entity).insert(CameraTransition {
+            from: transform.rotation,
+            to: view.rotation(),
+            timer: Timer::from_seconds(TRANSITION_SECONDS, TimerMode::Once),
+        });
+    }
+}
+
+fn move_camera(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut q_camera: Query<(Entity, &mut Transform, &mut CameraTransition)>,
+) {
+    for (entity, mut transform, mut transition) in &mut q_camera {
+        transition.timer.tick(time.delta());
+
+        // eases in and out of the move
+        let t = transition.timer.percent();
+        let eased = t * t * (3.0 - 2.0 * t);
+        let distance = transform.translation.length();
+        transform.rotation = transition.from.slerp(transition.to, eased);
+        transform.translation = transform.rotation * Vec3::Z * distance;
+
+        if transition.timer.finished() {
+            commands.entity(entity).remove::<CameraTransition>();
+        }
+    }
+}
+
+fn clean_up(
+    mut commands: Commands,
+    q_ui: Query<Entity, With<ViewUiRoot>>,
+    q_camera: Query<Entity, With<CameraTransition>>,
+) {
+    for entity in q_ui.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    for entity in q_camera.iter() {
+        commands.entity(entity).remove::<CameraTransition>();
+    }
+}
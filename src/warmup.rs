@@ -0,0 +1,210 @@
+use crate::{
+    i18n::Localized,
+    simulator::PlayButtonActions,
+    tables::read_ready_made_tables,
+    theme::{Theme, ThemeColor},
+    GameState,
+};
+use bevy::{
+    prelude::*,
+    tasks::{AsyncComputeTaskPool, Task},
+};
+use cubesim::prepare_tables;
+use std::sync::{Arc, Mutex};
+
+pub struct WarmupPlugin;
+
+impl Plugin for WarmupPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, start_warmup)
+            .add_systems(
+                Update,
+                (
+                    finish_warmup,
+                    show_progress.run_if(in_state(GameState::Menu)),
+                    dim_solver_buttons.run_if(in_state(GameState::Playing)),
+                )
+                    .chain(),
+            )
+            .add_systems(OnExit(GameState::Menu), clean_up);
+    }
+}
+
+/// The pruning tables of the solvers, read or built in the background from startup so that the
+/// first solve doesn't freeze the app.
+#[derive(Resource)]
+pub(crate) struct SolverTables {
+    // the share of the tables built so far, from 0 to 1
+    progress: Arc<Mutex<f32>>,
+    task: Option<Task<()>>,
+}
+
+impl SolverTables {
+    /// The share of the tables built so far, from 0 to 1.
+    pub fn progress(&self) -> f32 {
+        *self.progress.lock().unwrap()
+    }
+
+    /// Whether every table is built, the solvers answering right away.
+    pub fn is_ready(&self) -> bool {
+        self.task.is_none()
+    }
+
+    /// What the player waits for, as long as the tables aren't built.
+    pub fn status(&self) -> Localized {
+        let percent = format!("{:.0}", self.progress() * 100.0);
+        Localized::new("solver-preparing").with("percent", percent)
+    }
+}
+
+// Shows how far the tables are built in the menu
+#[derive(Component)]
+struct ProgressPanel;
+
+#[derive(Component)]
+struct ProgressText;
+
+#[derive(Component)]
+struct ProgressBar;
+
+const BAR_WIDTH: f32 = 300.0;
+
+fn start_warmup(mut commands: Commands) {
+    let progress = Arc::new(Mutex::new(0.0));
+    let shared = progress.clone();
+    let task = AsyncComputeTaskPool::get().spawn(async move {
+        read_ready_made_tables();
+        prepare_tables(&mut |share| *shared.lock().unwrap() = share);
+    });
+    commands.insert_resource(SolverTables {
+        progress,
+        task: Some(task),
+    });
+}
+
+fn finish_warmup(mut tables: ResMut<SolverTables>) {
+    if tables.task.as_ref().is_some_and(|task| task.is_finished()) {
+        tables.task = None;
+        info!("solver tables ready");
+    }
+}
+
+fn show_progress(
+    mut commands: Commands,
+    tables: Res<SolverTables>,
+    asset_server: Res<AssetServer>,
+    theme: Res<Theme>,
+    q_panel: Query<Entity, With<ProgressPanel>>,
+    mut q_text: Query<&mut Localized, With<ProgressText>>,
+    mut q_bar: Query<&mut Style, With<ProgressBar>>,
+) {
+    if tables.is_ready() {
+        for entity in &q_panel {
+            commands.entity(entity).despawn_recursive();
+        }
+        return;
+    }
+    if q_panel.is_empty() {
+        spawn_panel(&mut commands, &tables, &asset_server, *theme);
+        return;
+    }
+
+    for mut text in &mut q_text {
+        text.set_if_neq(tables.status());
+    }
+    for mut style in &mut q_bar {
+        style.width = Val::Px(BAR_WIDTH * tables.progress());
+    }
+}
+
+// Greys the buttons out until the solvers can answer
+fn dim_solver_buttons(
+    tables: Res<SolverTables>,
+    theme: Res<Theme>,
+    mut q_buttons: Query<(&mut Text, &PlayButtonActions)>,
+) {
+    let color = match tables.is_ready() {
+        true => theme.text(),
+        false => theme.text().with_a(0.4),
+    };
+    for (mut text, button) in &mut q_buttons {
+        let solver = matches!(
+            button,
+            PlayButtonActions::CubeSolver | PlayButtonActions::CompareSolvers
+        );
+        if solver && text.sections[0].style.color != color {
+            text.sections[0].style.color = color;
+        }
+    }
+}
+
+fn spawn_panel(
+    commands: &mut Commands,
+    tables: &SolverTables,
+    asset_server: &AssetServer,
+    theme: Theme,
+) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    bottom: Val::Px(10.0),
+                    right: Val::Px(10.0),
+                    padding: UiRect::all(Val::Px(10.0)),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(5.0),
+                    ..default()
+                },
+                ..default()
+            },
+            ThemeColor::Panel,
+            ProgressPanel,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    String::new(),
+                    TextStyle {
+                        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                        font_size: 20.0,
+                        color: theme.text(),
+                    },
+                ),
+                tables.status(),
+                ProgressText,
+            ));
+            parent
+                .spawn((
+                    NodeBundle {
+                        style: Style {
+                            width: Val::Px(BAR_WIDTH),
+                            height: Val::Px(8.0),
+                            ..default()
+                        },
+                        ..default()
+                    },
+                    ThemeColor::Border,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        NodeBundle {
+                            style: Style {
+                                width: Val::Px(BAR_WIDTH * tables.progress()),
+                                height: Val::Percent(100.0),
+                                ..default()
+                            },
+                            background_color: theme.button(Interaction::None, true).into(),
+                            ..default()
+                        },
+                        ProgressBar,
+                    ));
+                });
+        });
+}
+
+fn clean_up(mut commands: Commands, q_panel: Query<Entity, With<ProgressPanel>>) {
+    for entity in &q_panel {
+        commands.entity(entity).despawn_recursive();
+    }
+}
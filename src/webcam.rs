@@ -0,0 +1,548 @@
+use crate::{
+    i18n::{Localized, Translations},
+    session::SolveSession,
+    simulator::{CurrentCube, MoveQueue, Played, PlayedMut},
+    theme::{Theme, ThemeColor},
+    GameState, Puzzle,
+};
+use bevy::{
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+use cubesim::{prelude::*, simplify_moves, solve, validate_state, FaceletCube};
+use std::sync::{mpsc::Receiver, Mutex};
+#[cfg(all(feature = "webcam", target_os = "linux"))]
+use std::{
+    sync::mpsc::{self, SyncSender},
+    thread,
+};
+
+const SCAN_KEY: KeyCode = KeyCode::W;
+const CANCEL_KEY: KeyCode = KeyCode::Q;
+// The faces in the order of the cube's state, each held up to the webcam with another face
+// on top, as in the net of the cube, by the id of its description in the translations
+const SCAN_ORDER: [(Face, &str); 6] = [
+    (Face::U, "scan-face-up"),
+    (Face::R, "scan-face-right"),
+    (Face::F, "scan-face-front"),
+    (Face::D, "scan-face-down"),
+    (Face::L, "scan-face-left"),
+    (Face::B, "scan-face-back"),
+];
+// The stickers are read in a square in the middle of the frame, this part of its height
+const GRID_SPAN: f32 = 0.6;
+// The part of each sticker averaged, around its middle
+const PATCH_SPAN: f32 = 0.4;
+const PREVIEW_WIDTH: f32 = 320.0;
+const GRID_COLOR: [u8; 4] = [255, 255, 255, 255];
+
+pub struct WebcamPlugin;
+
+impl Plugin for WebcamPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                scan_input,
+                receive_frames,
+                solve_when_set,
+                toggle_scan_ui,
+                apply_deferred,
+                show_scan,
+            )
+                .chain()
+                .run_if(in_state(GameState::Playing))
+                .run_if(resource_equals(Puzzle::Cube)),
+        )
+        .add_systems(OnExit(GameState::Playing), end_scan);
+    }
+}
+
+/// Reads the colors of a real 3x3x3 cube from the webcam one face at a time with `W`, then
+/// sets the cube on screen up the same and queues its solution. The frames are only read
+/// with the `webcam` feature, on Linux.
+#[derive(Resource)]
+struct CubeScan {
+    frames: Option<Mutex<Receiver<Result<Frame, Localized>>>>,
+    frame: Option<Frame>,
+    // the colors of the stickers of each face read so far
+    faces: Vec<[Vec3; 9]>,
+    // the scanned cube is being set up on screen, its solution queued once it is
+    setting_up: bool,
+    message: Option<Localized>,
+}
+
+impl CubeScan {
+    fn start(cube_size: usize) -> Self {
+        let mut scan = CubeScan {
+            frames: None,
+            frame: None,
+            faces: Vec::new(),
+            setting_up: false,
+            message: None,
+        };
+        if cube_size != 3 {
+            scan.message = Some(Localized::new("scan-cube-size"));
+            return scan;
+        }
+        match open_webcam() {
+            Ok(frames) => scan.frames = Some(Mutex::new(frames)),
+            Err(err) => scan.message = Some(err),
+        }
+        scan
+    }
+}
+
+// A frame of the webcam, in RGBA
+struct Frame {
+    width: usize,
+    height: usize,
+    rgba: Vec<u8>,
+}
+
+impl Frame {
+    // Each 4 bytes are the luma of 2 pixels and the chroma they share
+    #[cfg_attr(not(all(feature = "webcam", target_os = "linux")), allow(dead_code))]
+    fn from_yuyv(width: usize, height: usize, data: &[u8]) -> Self {
+        let mut rgba = Vec::with_capacity(width * height * 4);
+        for pixels in data.chunks_exact(4).take(width * height / 2) {
+            let (u, v) = (pixels[1] as f32 - 128.0, pixels[3] as f32 - 128.0);
+            for y in [pixels[0], pixels[2]] {
+                let y = y as f32;
+                let rgb = [y + 1.402 * v, y - 0.344 * u - 0.714 * v, y + 1.772 * u];
+                rgba.extend(rgb.map(|c| c.clamp(0.0, 255.0) as u8));
+                rgba.push(255);
+            }
+        }
+        Frame {
+            width,
+            height,
+            rgba,
+        }
+    }
+
+    // The left and top of the grid of stickers, and the width of a sticker, in pixels
+    fn grid(&self) -> (f32, f32, f32) {
+        let span = GRID_SPAN * self.width.min(self.height) as f32;
+        let left = (self.width as f32 - span) / 2.0;
+        let top = (self.height as f32 - span) / 2.0;
+        (left, top, span / 3.0)
+    }
+
+    // The average color in the middle of each sticker of the grid, in RGB from 0 to 1
+    fn sample_face(&self) -> [Vec3; 9] {
+        let (left, top, sticker) = self.grid();
+        let patch = ((sticker * PATCH_SPAN) as usize).max(1);
+        std::array::from_fn(|i| {
+            let (row, col) = (i / 3, i % 3);
+            let x = (left + (col as f32 + 0.5) * sticker) as usize - patch / 2;
+            let y = (top + (row as f32 + 0.5) * sticker) as usize - patch / 2;
+            let mut sum = Vec3::ZERO;
+            for y in y..y + patch {
+                for x in x..x + patch {
+                    let p = (y * self.width + x) * 4;
+                    let rgb = &self.rgba[p..p + 3];
+                    sum += Vec3::new(rgb[0] as f32, rgb[1] as f32, rgb[2] as f32) / 255.0;
+                }
+            }
+            sum / (patch * patch) as f32
+        })
+    }
+
+    // The frame with the grid drawn over it, to hold the face in
+    fn preview(&self) -> Image {
+        let mut rgba = self.rgba.clone();
+        let (left, top, sticker) = self.grid();
+        let (left, top, span) = (left as usize, top as usize, (sticker * 3.0) as usize);
+        for line in 0..4 {
+            let offset = (line as f32 * sticker) as usize;
+            for along in 0..=span {
+                for (x, y) in [(left + offset, top + along), (left + along, top + offset)] {
+                    let p = (y.min(self.height - 1) * self.width + x.min(self.width - 1)) * 4;
+                    rgba[p..p + 4].copy_from_slice(&GRID_COLOR);
+                }
+            }
+        }
+        Image::new(
+            Extent3d {
+                width: self.width as u32,
+                height: self.height as u32,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            rgba,
+            TextureFormat::Rgba8UnormSrgb,
+        )
+    }
+}
+
+#[cfg(all(feature = "webcam", target_os = "linux"))]
+fn open_webcam() -> Result<Receiver<Result<Frame, Localized>>, Localized> {
+    // only the latest frame is waiting, the webcam's thread waiting for it to be taken
+    let (sender, receiver) = mpsc::sync_channel(1);
+    thread::spawn(move || {
+        if let Err(err) = capture_frames(&sender) {
+            let _ = sender.send(Err(err));
+        }
+    });
+    Ok(receiver)
+}
+
+#[cfg(all(feature = "webcam", target_os = "linux"))]
+fn capture_frames(sender: &SyncSender<Result<Frame, Localized>>) -> Result<(), Localized> {
+    use v4l::{buffer::Type, io::traits::CaptureStream, prelude::*, video::Capture, FourCC};
+
+    let unreadable = |err: std::io::Error| {
+        Localized::new("scan-webcam-unreadable").with("error", err.to_string())
+    };
+    let device = Device::new(0)
+        .map_err(|err| Localized::new("scan-no-webcam").with("error", err.to_string()))?;
+    let mut format = device.format().map_err(unreadable)?;
+    format.fourcc = FourCC::new(b"YUYV");
+    let format = device.set_format(&format).map_err(unreadable)?;
+    if format.fourcc != FourCC::new(b"YUYV") {
+        return Err(Localized::new("scan-webcam-format"));
+    }
+
+    let mut stream =
+        MmapStream::with_buffers(&device, Type::VideoCapture, 4).map_err(unreadable)?;
+    loop {
+        let (data, _) = stream
+            .next()
+            .map_err(|err| Localized::new("scan-webcam-stopped").with("error", err.to_string()))?;
+        let frame = Frame::from_yuyv(format.width as usize, format.height as usize, data);
+        // the scan is over once its frames aren't received anymore
+        if sender.send(Ok(frame)).is_err() {
+            return Ok(());
+        }
+    }
+}
+
+#[cfg(not(all(feature = "webcam", target_os = "linux")))]
+fn open_webcam() -> Result<Receiver<Result<Frame, Localized>>, Localized> {
+    Err(Localized::new("scan-unavailable"))
+}
+
+// A color as a point of the HSV cone, where close colors are close and the whites and grays
+// lie around its axis, whatever their hue
+fn cone_point(rgb: Vec3) -> Vec3 {
+    let value = rgb.max_element();
+    let chroma = value - rgb.min_element();
+    let hue = if chroma == 0.0 {
+        0.0
+    } else if value == rgb.x {
+        ((rgb.y - rgb.z) / chroma).rem_euclid(6.0)
+    } else if value == rgb.y {
+        (rgb.z - rgb.x) / chroma + 2.0
+    } else {
+        (rgb.x - rgb.y) / chroma + 4.0
+    };
+    let (sin, cos) = (hue * 60.0).to_radians().sin_cos();
+    Vec3::new(chroma * cos, chroma * sin, value)
+}
+
+// Groups the stickers around the colors of the centers by k-means, each sticker then
+// belonging to the face of the center of its group
+fn classify(faces: &[[Vec3; 9]]) -> Result<Vec<Face>, Localized> {
+    let points: Vec<Vec3> = faces.iter().flatten().map(|&rgb| cone_point(rgb)).collect();
+    let mut means: Vec<Vec3> = faces.iter().map(|face| cone_point(face[4])).collect();
+    let mut groups = vec![0; points.len()];
+
+    for _ in 0..10 {
+        for (group, point) in groups.iter_mut().zip(&points) {
+            *group = (0..means.len())
+                .min_by(|&a, &b| {
+                    let (a, b) = (means[a].distance(*point), means[b].distance(*point));
+                    a.total_cmp(&b)
+                })
+                .unwrap_or(0);
+        }
+        for (i, mean) in means.iter_mut().enumerate() {
+            let members: Vec<Vec3> = points
+                .iter()
+                .zip(&groups)
+                .filter(|(_, &group)| group == i)
+                .map(|(&point, _)| point)
+                .collect();
+            if !members.is_empty() {
+                *mean = members.iter().sum::<Vec3>() / members.len() as f32;
+            }
+        }
+    }
+
+    for (i, (face, _)) in SCAN_ORDER.iter().enumerate() {
+        let group = groups[i * 9 + 4];
+        if group != i {
+            let other = SCAN_ORDER[group].0;
+            return Err(Localized::new("scan-centers-alike")
+                .with("face", format!("{face:?}"))
+                .with("other", format!("{other:?}")));
+        }
+    }
+    Ok(groups
+        .into_iter()
+        .map(|group| SCAN_ORDER[group].0)
+        .collect())
+}
+
+// The moves turning the cube on screen into the scanned one, through the solved cube
+fn setup_moves(current_cube: &CurrentCube, state: Vec<Face>) -> Result<Vec<Move>, Localized> {
+    validate_state(&state)
+        .map_err(|err| Localized::new("scan-invalid-cube").with("error", err.to_string()))?;
+    let current = FaceletCube::new(3).apply_moves(current_cube.moves());
+    let to_solved = solve(&current).ok_or_else(|| Localized::new("scan-no-solution-shown"))?;
+    let scanned = solve(&FaceletCube::from(state))
+        .ok_or_else(|| Localized::new("scan-no-solution-scanned"))?;
+
+    let from_solved = scanned.iter().rev().map(|mv| mv.inverse());
+    let moves: Vec<Move> = to_solved.into_iter().chain(from_solved).collect();
+    Ok(simplify_moves(&moves))
+}
+
+fn scan_input(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    scan: Option<ResMut<CubeScan>>,
+    mut current_cube: PlayedMut<CurrentCube>,
+    mut move_queue: PlayedMut<MoveQueue>,
+    mut session: PlayedMut<SolveSession>,
+) {
+    let Some(mut scan) = scan else {
+        if keyboard_input.just_pressed(SCAN_KEY) {
+            commands.insert_resource(CubeScan::start(current_cube.cube_size()));
+        }
+        return;
+    };
+    if keyboard_input.just_pressed(CANCEL_KEY) {
+        commands.remove_resource::<CubeScan>();
+        return;
+    }
+    if scan.setting_up || !keyboard_input.just_pressed(SCAN_KEY) {
+        return;
+    }
+    let Some(face) = scan.frame.as_ref().map(Frame::sample_face) else {
+        return;
+    };
+
+    scan.faces.push(face);
+    if scan.faces.len() < SCAN_ORDER.len() {
+        return;
+    }
+    // the cube is set up from its state once the moves queued are made
+    if !move_queue.is_empty() || move_queue.in_flight().is_some() {
+        scan.faces.pop();
+        scan.message = Some(Localized::new("scan-wait-for-moves"));
+        return;
+    }
+
+    let faces = std::mem::take(&mut scan.faces);
+    match classify(&faces).and_then(|state| setup_moves(&current_cube, state)) {
+        Ok(moves) => {
+            session.queue_scramble(&mut current_cube, &mut move_queue, moves);
+            scan.setting_up = true;
+            scan.frames = None;
+            scan.message = Some(Localized::new("scan-setting-up"));
+        }
+        Err(err) => scan.message = Some(err),
+    }
+}
+
+// Keeps the latest frame, dropping the others
+fn receive_frames(scan: Option<ResMut<CubeScan>>) {
+    let Some(mut scan) = scan else {
+        return;
+    };
+    let Some(latest) = scan.frames.as_ref().and_then(|frames| {
+        let frames = frames.lock().unwrap_or_else(|err| err.into_inner());
+        frames.try_iter().last()
+    }) else {
+        return;
+    };
+
+    match latest {
+        Ok(frame) => scan.frame = Some(frame),
+        Err(err) => {
+            scan.message = Some(err);
+            scan.frames = None;
+        }
+    }
+}
+
+fn solve_when_set(
+    mut commands: Commands,
+    scan: Option<Res<CubeScan>>,
+    current_cube: Played<CurrentCube>,
+    move_queue: Played<MoveQueue>,
+    mut session: PlayedMut<SolveSession>,
+) {
+    let set = current_cube.scrambled_at() == Some(current_cube.moves().len());
+    if !scan.is_some_and(|scan| scan.setting_up) || !set {
+        return;
+    }
+
+    if let Err(err) = session.solve(&current_cube, &move_queue) {
+        warn!("the scanned cube isn't solved: {err:?}");
+    }
+    commands.remove_resource::<CubeScan>();
+}
+
+#[derive(Component)]
+struct ScanUiRoot;
+
+#[derive(Component)]
+struct ScanPreview;
+
+// The colors read in the grid of the frame, as they would be scanned
+#[derive(Component)]
+struct ScanSwatch(usize);
+
+#[derive(Component)]
+struct ScanText;
+
+fn scan_ui(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    theme: &Theme,
+    images: &mut Assets<Image>,
+) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(60.0),
+                    left: Val::Px(220.0),
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(10.0)),
+                    row_gap: Val::Px(5.0),
+                    ..default()
+                },
+                ..default()
+            },
+            ThemeColor::Panel,
+            ScanUiRoot,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                ImageBundle {
+                    style: Style {
+                        width: Val::Px(PREVIEW_WIDTH),
+                        height: Val::Px(PREVIEW_WIDTH * 0.75),
+                        ..default()
+                    },
+                    image: images.add(Image::default()).into(),
+                    ..default()
+                },
+                ScanPreview,
+            ));
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        display: Display::Grid,
+                        grid_template_columns: RepeatedGridTrack::px(3, 20.0),
+                        column_gap: Val::Px(2.0),
+                        row_gap: Val::Px(2.0),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for i in 0..9 {
+                        parent.spawn((
+                            NodeBundle {
+                                style: Style {
+                                    height: Val::Px(20.0),
+                                    ..default()
+                                },
+                                ..default()
+                            },
+                            ScanSwatch(i),
+                        ));
+                    }
+                });
+            parent.spawn((
+                TextBundle::from_section(
+                    String::new(),
+                    TextStyle {
+                        font,
+                        font_size: 20.0,
+                        color: theme.text(),
+                    },
+                ),
+                ScanText,
+            ));
+        });
+}
+
+// Spawns the panel when a scan starts, despawning it once over
+fn toggle_scan_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    theme: Res<Theme>,
+    scan: Option<Res<CubeScan>>,
+    mut images: ResMut<Assets<Image>>,
+    q_root: Query<Entity, With<ScanUiRoot>>,
+) {
+    match scan {
+        Some(scan) if scan.is_added() => {
+            scan_ui(&mut commands, &asset_server, &theme, &mut images);
+        }
+        Some(_) => {}
+        None => {
+            for entity in &q_root {
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+    }
+}
+
+fn show_scan(
+    scan: Option<Res<CubeScan>>,
+    mut images: ResMut<Assets<Image>>,
+    mut q_preview: Query<(&UiImage, &mut Style), With<ScanPreview>>,
+    mut q_swatches: Query<(&ScanSwatch, &mut BackgroundColor)>,
+    translations: Translations,
+    mut q_text: Query<&mut Text, With<ScanText>>,
+) {
+    let Some(scan) = scan.filter(|scan| scan.is_changed()) else {
+        return;
+    };
+
+    if let Some(frame) = &scan.frame {
+        for (image, mut style) in &mut q_preview {
+            images.insert(image.texture.clone(), frame.preview());
+            style.height = Val::Px(PREVIEW_WIDTH * frame.height as f32 / frame.width as f32);
+        }
+        let colors = frame.sample_face();
+        for (swatch, mut background) in &mut q_swatches {
+            let rgb = colors[swatch.0];
+            background.0 = Color::rgb(rgb.x, rgb.y, rgb.z);
+        }
+    }
+
+    let step = match SCAN_ORDER.get(scan.faces.len()) {
+        Some((_, face)) if !scan.setting_up => Localized::new("scan-step")
+            .with("face", translations.get(face))
+            .with("key", format!("{SCAN_KEY:?}"))
+            .with("scanned", scan.faces.len() + 1)
+            .with("faces", SCAN_ORDER.len()),
+        _ => Localized::new("scan-cancel"),
+    };
+    let step = translations.text(step.with("cancel", format!("{CANCEL_KEY:?}")));
+    let message = scan
+        .message
+        .clone()
+        .map(|message| translations.text(message));
+    for mut text in &mut q_text {
+        text.sections[0].value = format!("{step}\n{}", message.clone().unwrap_or_default());
+    }
+}
+
+// The webcam is only read while playing
+fn end_scan(mut commands: Commands, q_root: Query<Entity, With<ScanUiRoot>>) {
+    commands.remove_resource::<CubeScan>();
+    for entity in &q_root {
+        commands.entity(entity).despawn_recursive();
+    }
+}